@@ -1,5 +1,5 @@
-use clap::{Parser, Subcommand, ValueEnum};
-use rusk_core::models::{TaskPriority, TaskStatus, EditScope};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use rusk_core::models::{DstGapPolicy, DstOverlapPolicy, TaskPriority, TaskStatus, EditScope};
 
 /// Rusk - A feature-rich, high-quality, robust CLI task management tool
 /// 
@@ -22,6 +22,14 @@ use rusk_core::models::{TaskPriority, TaskStatus, EditScope};
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Database to use: a SQLite file path or a postgres:// URL
+    #[arg(long, global = true, value_name = "PATH|URL")]
+    pub db: Option<String>,
+
+    /// Named profile with its own database and config overlay
+    #[arg(long, global = true, value_name = "NAME", value_parser = crate::paths::parse_profile_name)]
+    pub profile: Option<String>,
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -32,6 +40,12 @@ pub enum Commands {
     /// List and filter tasks with powerful query syntax
     #[command(visible_alias = "ls")]
     List(ListCommand),
+    /// Show every detail of a single task
+    Show(ShowCommand),
+    /// Show the most urgent tasks that are ready to work on
+    Next(NextCommand),
+    /// Show tasks as a kanban board with a column per status
+    Board(BoardCommand),
     /// Delete a task permanently (use --force to skip confirmation)
     #[command(visible_alias = "rm")]
     Delete(DeleteCommand),
@@ -40,6 +54,23 @@ pub enum Commands {
     Do(DoCommand),
     /// Cancel a task and mark it as cancelled
     Cancel(CancelCommand),
+    /// Move a task to another workflow status
+    #[command(visible_alias = "mv")]
+    Move(MoveCommand),
+    /// Manage workflow statuses and allowed transitions
+    Status(StatusCommand),
+    /// Add a timestamped note to a task, or show its notes
+    Note(NoteCommand),
+    /// Start the timer on a task
+    Start(StartCommand),
+    /// Stop the running timer
+    Stop,
+    /// Log time spent on a task
+    Track(TrackCommand),
+    /// Report tracked time grouped by project and tag
+    Timesheet(TimesheetCommand),
+    /// Compare estimated effort per day against your daily capacity
+    Forecast(ForecastCommand),
     /// Edit task properties with scope-aware recurring task support
     #[command(visible_alias = "e")]
     Edit(EditCommand),
@@ -49,6 +80,13 @@ pub enum Commands {
     /// Manage recurring task series (info, preview, skip, move, etc.)
     #[command(visible_alias = "r")]
     Recur(RecurrenceCommand),
+    /// Save task plans as blueprints and create tasks from them
+    #[command(visible_alias = "bp")]
+    Blueprint(BlueprintCommand),
+    /// Run the background daemon (materialization and reminders)
+    Daemon(DaemonCommand),
+    /// Check the task database for problems such as orphaned occurrences
+    Doctor(DoctorCommand),
 }
 
 /// Add a new task with comprehensive options
@@ -58,6 +96,9 @@ pub enum Commands {
 ///   rusk add "Daily standup" --every weekdays --at "9:00 AM" --project Work
 ///   rusk add "Team meeting" --every weekly --on mon --at "2:00 PM" --until "2025-12-31"
 ///   rusk add "Review code" --due "next friday" --depends-on abc123 --priority high
+///   rusk add "Take medication" --every daily --at "8:00 AM" --floating
+///   rusk add "Mom's birthday" --due 2025-06-14 --every yearly --all-day
+///   rusk add "Night backup" --every daily --at 2:30am --dst-gap skip
 #[derive(Parser, Debug, Clone)]
 pub struct AddCommand {
     /// Task name (required)
@@ -122,6 +163,57 @@ pub struct AddCommand {
     /// Timezone for recurrence calculations
     #[clap(long, help = "IANA timezone (e.g., 'America/New_York', 'Europe/London'). Run 'rusk recur timezones' to list options")]
     pub timezone: Option<String>,
+
+    /// Keep the local time of day wherever you are
+    ///
+    /// A floating series follows the timezone you are in when its occurrences
+    /// are materialized, e.g. "8am wherever I am".
+    #[clap(long, conflicts_with_all = ["timezone", "all_day"], help = "Recur at the same local time in whatever timezone you are in")]
+    pub floating: bool,
+
+    /// Due on a date, without a time
+    #[clap(long, conflicts_with_all = ["timezone", "at"], help = "Due on a date without a time (e.g., birthdays)")]
+    pub all_day: bool,
+
+    /// Where occurrences go whose local time the clocks skip
+    ///
+    /// E.g. 2:30am on spring-forward day: shift-forward (3:30am, the default),
+    /// shift-backward (1:30am) or skip.
+    #[clap(long, value_name = "POLICY", conflicts_with = "all_day", help = "For times skipped by DST: shift-forward, shift-backward or skip")]
+    pub dst_gap: Option<DstGapPolicy>,
+
+    /// Which of two instants occurrences take whose local time the clocks repeat
+    ///
+    /// E.g. 1:30am on fall-back day: earliest (the default) or latest.
+    #[clap(long, value_name = "POLICY", conflicts_with = "all_day", help = "For times repeated by DST: earliest or latest")]
+    pub dst_overlap: Option<DstOverlapPolicy>,
+
+    /// Reminder offset before the due date
+    /// 
+    /// Delivered by `rusk daemon`. Recurring tasks remind for every occurrence.
+    #[clap(long, help = "Remind this long before due (e.g., '30m', '1h30m', '2d')")]
+    pub remind: Option<String>,
+
+    /// Estimated effort, counted by `rusk forecast`
+    /// 
+    /// Recurring tasks pass the estimate on to every occurrence.
+    #[clap(long, help = "Estimated effort (e.g., '45m', '2h', '1h30m')")]
+    pub estimate: Option<String>,
+
+    /// Earliest time to work on the task; `rusk list` hides it until then
+    /// 
+    /// Accepts a date or an offset from the due date. Recurring tasks keep the
+    /// offset for every occurrence.
+    #[clap(long, help = "Scheduled date (e.g., 'next monday', '2d before due')")]
+    pub scheduled: Option<String>,
+
+    /// Hide the task until this date
+    #[clap(long, help = "Hide until (e.g., '3 days', '1w before due')")]
+    pub wait: Option<String>,
+
+    /// Planned start of work
+    #[clap(long, help = "Start date (e.g., 'tomorrow 9am', '4h before due')")]
+    pub start: Option<String>,
 }
 
 /// Edit an existing task with scope-aware recurring task support
@@ -135,10 +227,15 @@ pub struct AddCommand {
 ///   rusk edit abc123 --name "Updated task name"
 ///   rusk edit def456 --due "next week" --scope occurrence
 ///   rusk edit ghi789 --recurrence-clear  # Convert recurring to one-time
+///   rusk edit --where "project:Old" --project New
 #[derive(Parser, Debug, Clone)]
 pub struct EditCommand {
-    /// Task ID to edit (partial IDs accepted)
-    pub id: String,
+    #[command(flatten)]
+    pub selection: TaskSelection,
+
+    /// Apply edits to several tasks without asking for confirmation
+    #[arg(short, long)]
+    pub yes: bool,
     
     /// Skip interactive scope prompting (use default or specified scope)
     #[arg(long, help = "Force scope without interactive prompting (useful for scripts)")]
@@ -213,6 +310,79 @@ pub struct EditCommand {
     /// Remove existing tags
     #[arg(long, num_args = 1.., help = "Tags to remove (can specify multiple)")]
     pub remove_tag: Vec<String>,
+
+    /// Update reminder offset
+    #[arg(long, help = "Remind this long before due (e.g., '30m', '1h30m', '2d')")]
+    pub remind: Option<String>,
+    /// Clear reminder
+    #[arg(long, conflicts_with = "remind", help = "Remove the reminder")]
+    pub remind_clear: bool,
+
+    /// Update effort estimate
+    #[arg(long, help = "Estimated effort (e.g., '45m', '2h', '1h30m')")]
+    pub estimate: Option<String>,
+    /// Clear effort estimate
+    #[arg(long, conflicts_with = "estimate", help = "Remove the estimate")]
+    pub estimate_clear: bool,
+
+    /// Update scheduled date
+    #[arg(long, help = "Scheduled date (e.g., 'next monday', '2d before due')")]
+    pub scheduled: Option<String>,
+    /// Clear scheduled date
+    #[arg(long, conflicts_with = "scheduled", help = "Remove the scheduled date")]
+    pub scheduled_clear: bool,
+
+    /// Update wait date
+    #[arg(long, help = "Hide until (e.g., '3 days', '1w before due')")]
+    pub wait: Option<String>,
+    /// Clear wait date
+    #[arg(long, conflicts_with = "wait", help = "Stop waiting and show the task again")]
+    pub wait_clear: bool,
+
+    /// Update start date
+    #[arg(long, help = "Start date (e.g., 'tomorrow 9am', '4h before due')")]
+    pub start: Option<String>,
+    /// Clear start date
+    #[arg(long, conflicts_with = "start", help = "Remove the start date")]
+    pub start_clear: bool,
+}
+
+/// Run the background daemon
+/// 
+/// The daemon periodically materializes all active recurring series and
+/// delivers reminders for tasks created with --remind. Sinks and the polling
+/// interval are configured in the [daemon] section of config.toml.
+/// 
+/// Examples:
+///   rusk daemon                       # Run until Ctrl-C
+///   rusk daemon --interval 30         # Poll every 30 seconds
+///   rusk daemon --once                # Single pass (useful from cron)
+///   rusk daemon --sink notify --sink stdout
+#[derive(Parser, Debug, Clone)]
+pub struct DaemonCommand {
+    /// Seconds between polls (overrides config)
+    #[arg(long, help = "Polling interval in seconds")]
+    pub interval: Option<u64>,
+
+    /// Run a single pass and exit
+    #[arg(long, help = "Run one materialization/reminder pass and exit")]
+    pub once: bool,
+
+    /// Reminder sinks to use (overrides config)
+    #[arg(long, value_enum, help = "Reminder sink (stdout, command, notify); can be repeated")]
+    pub sink: Vec<ReminderSinkKind>,
+}
+
+/// Available reminder delivery mechanisms
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReminderSinkKind {
+    /// Print reminders to standard output
+    Stdout,
+    /// Run the configured command hook
+    Command,
+    /// Send a desktop notification
+    Notify,
 }
 
 /// Mark a task as completed
@@ -224,10 +394,15 @@ pub struct EditCommand {
 /// Examples:
 ///   rusk do abc123
 ///   rusk done def456  # Using alias
+///   rusk do abc123 def456
+///   rusk do --where "tag:batch and due:today" --dry-run
 #[derive(Parser, Debug, Clone)]
 pub struct DoCommand {
-    /// Task ID to mark as completed (partial IDs accepted)
-    pub id: String,
+    #[command(flatten)]
+    pub selection: TaskSelection,
+    /// Complete several tasks without asking for confirmation
+    #[arg(short, long)]
+    pub yes: bool,
 }
 
 /// Cancel a task and mark it as cancelled
@@ -237,44 +412,341 @@ pub struct DoCommand {
 /// 
 /// Examples:
 ///   rusk cancel abc123
+///   rusk cancel --where "project:Abandoned" --yes
 #[derive(Parser, Debug, Clone)]
 pub struct CancelCommand {
-    /// Task ID to cancel (partial IDs accepted)
+    #[command(flatten)]
+    pub selection: TaskSelection,
+    /// Cancel several tasks without asking for confirmation
+    #[arg(short, long)]
+    pub yes: bool,
+}
+
+/// The tasks a mutating command applies to: IDs or a filter query
+/// 
+/// Several IDs, --where and --dry-run list the affected tasks first and
+/// apply the change to all of them in one transaction, or to none.
+#[derive(Args, Debug, Clone)]
+pub struct TaskSelection {
+    /// Task IDs (partial IDs accepted)
+    #[arg(required_unless_present = "query", conflicts_with = "query")]
+    pub ids: Vec<String>,
+    /// Apply to every task matching a filter query, as in 'rusk list'
+    #[arg(long = "where", value_name = "QUERY")]
+    pub query: Option<String>,
+    /// Show the affected tasks without changing anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+impl TaskSelection {
+    /// Whether the command goes through the preview, rather than acting on one task directly
+    pub fn is_bulk(&self) -> bool {
+        self.ids.len() != 1 || self.query.is_some() || self.dry_run
+    }
+}
+
+/// Task IDs given before a command's last positional argument, or the
+/// query, and that argument; `None` when neither IDs nor a query are given
+fn split_selection(args: &[String], query: &Option<String>, dry_run: bool) -> Option<(TaskSelection, String)> {
+    let (value, ids) = args.split_last()?;
+    if ids.is_empty() && query.is_none() {
+        return None;
+    }
+    let selection = TaskSelection { ids: ids.to_vec(), query: query.clone(), dry_run };
+    Some((selection, value.clone()))
+}
+
+/// Move a task to another workflow status
+/// 
+/// Moving into a status of the completed category completes the task (and
+/// advances its recurring series); moving into a pending status reopens it.
+/// Transitions configured with 'rusk status allow' are enforced here, while
+/// 'rusk do' and 'rusk cancel' are always allowed.
+/// 
+/// Examples:
+///   rusk move abc123 in-progress
+///   rusk mv abc123 review
+///   rusk move --where "project:Website" in-progress
+#[derive(Parser, Debug, Clone)]
+pub struct MoveCommand {
+    /// Task IDs (partial IDs accepted) followed by the target status name
+    #[arg(value_name = "ID... STATUS", required = true)]
+    pub args: Vec<String>,
+    /// Move every task matching a filter query, as in 'rusk list'
+    #[arg(long = "where", value_name = "QUERY")]
+    pub query: Option<String>,
+    /// Show the tasks that would move without changing anything
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Move several tasks without asking for confirmation
+    #[arg(short, long)]
+    pub yes: bool,
+}
+
+impl MoveCommand {
+    /// The tasks to move and the target status
+    pub fn selection(&self) -> Option<(TaskSelection, String)> {
+        split_selection(&self.args, &self.query, self.dry_run)
+    }
+}
+
+/// Start the timer on a task
+/// 
+/// Only one timer runs at a time; stop it with 'rusk stop' before starting
+/// another one.
+/// 
+/// Examples:
+///   rusk start abc123
+#[derive(Parser, Debug, Clone)]
+pub struct StartCommand {
+    /// Task ID to track time on (partial IDs accepted)
     pub id: String,
 }
 
+/// Log time spent on a task without running a timer
+/// 
+/// The entry ends now and starts the given duration earlier.
+/// 
+/// Examples:
+///   rusk track abc123 1h30m
+///   rusk track abc123 45m
+///   rusk track abc123 def456 20m      # 20 minutes on each
+#[derive(Parser, Debug, Clone)]
+pub struct TrackCommand {
+    /// Task IDs (partial IDs accepted) followed by the time spent on each
+    /// (e.g., '30m', '1h30m')
+    #[arg(value_name = "ID... DURATION", required = true)]
+    pub args: Vec<String>,
+    /// Log time on every task matching a filter query, as in 'rusk list'
+    #[arg(long = "where", value_name = "QUERY")]
+    pub query: Option<String>,
+    /// Show the tasks time would be logged on without changing anything
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Log time on several tasks without asking for confirmation
+    #[arg(short, long)]
+    pub yes: bool,
+}
+
+impl TrackCommand {
+    /// The tasks to log time on and the time spent
+    pub fn selection(&self) -> Option<(TaskSelection, String)> {
+        split_selection(&self.args, &self.query, self.dry_run)
+    }
+}
+
+/// Add a timestamped note to a task, or show its notes
+/// 
+/// Notes are searchable with the 'note:' filter, e.g. rusk list note:contains:invoice
+/// 
+/// Examples:
+///   rusk note abc123 "Called the supplier, waiting for a quote"
+///   rusk note abc123                  # Show the notes timeline
+///   rusk note abc123 def456 "Waiting on the same delivery"
+///   rusk note --where "project:Move" "Movers booked for the 12th"
+#[derive(Parser, Debug, Clone)]
+pub struct NoteCommand {
+    /// Task IDs (partial IDs accepted) followed by the note text; a single
+    /// ID shows the task's notes
+    #[arg(value_name = "ID... TEXT", required_unless_present = "query")]
+    pub args: Vec<String>,
+    /// Add the note to every task matching a filter query, as in 'rusk list'
+    #[arg(long = "where", value_name = "QUERY")]
+    pub query: Option<String>,
+    /// Show the tasks that would get the note without changing anything
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Add the note to several tasks without asking for confirmation
+    #[arg(short, long)]
+    pub yes: bool,
+}
+
+impl NoteCommand {
+    /// The tasks to annotate, and the note text unless the notes of a single
+    /// task are to be shown
+    pub fn selection(&self) -> (TaskSelection, Option<String>) {
+        let mut ids = self.args.clone();
+        let text = match (&self.query, ids.len()) {
+            (None, 0 | 1) => None,
+            _ => ids.pop(),
+        };
+        let selection = TaskSelection { ids, query: self.query.clone(), dry_run: self.dry_run };
+        (selection, text)
+    }
+}
+
+/// Report tracked time grouped by project and tag
+/// 
+/// Examples:
+///   rusk timesheet            # This week
+///   rusk timesheet --today
+#[derive(Parser, Debug, Clone)]
+pub struct TimesheetCommand {
+    /// Report the current week, starting Monday (default)
+    #[arg(long)]
+    pub week: bool,
+
+    /// Report today only
+    #[arg(long, conflicts_with = "week")]
+    pub today: bool,
+}
+
+/// Forecast estimated effort per day
+/// 
+/// Sums the estimates of pending tasks and of upcoming recurring occurrences,
+/// including ones not materialized yet, and flags days whose total exceeds
+/// the daily capacity from the [forecast] section of config.toml.
+/// 
+/// Examples:
+///   rusk forecast
+///   rusk forecast --days 30 --capacity 4h
+#[derive(Parser, Debug, Clone)]
+pub struct ForecastCommand {
+    /// Number of days to forecast, starting today
+    #[arg(long, default_value_t = 14, value_parser = clap::value_parser!(u32).range(1..=366))]
+    pub days: u32,
+
+    /// Daily capacity (overrides config)
+    #[arg(long, help = "Available time per day (e.g., '6h', '7h30m')")]
+    pub capacity: Option<String>,
+}
+
+/// Manage workflow statuses
+/// 
+/// Every custom status belongs to one of the base categories pending,
+/// completed or cancelled, which decides how tasks in it behave. Once a
+/// status has allowed transitions, 'rusk move' only accepts those targets.
+/// 
+/// Examples:
+///   rusk status add in-progress --category pending
+///   rusk status allow in-progress review
+///   rusk status list
+#[derive(Parser, Debug, Clone)]
+pub struct StatusCommand {
+    #[command(subcommand)]
+    pub command: StatusSubcommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum StatusSubcommand {
+    /// List statuses and their allowed transitions
+    List,
+    /// Add a custom status
+    Add(AddStatusCommand),
+    /// Delete a custom status that no task is in
+    Delete(DeleteStatusCommand),
+    /// Allow moving tasks from one status to another
+    Allow(StatusTransitionCommand),
+    /// Remove an allowed transition
+    Disallow(StatusTransitionCommand),
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct AddStatusCommand {
+    /// The name of the status (letters, digits, '-' and '_')
+    pub name: String,
+
+    /// Base category the status belongs to
+    #[arg(long, value_enum, default_value = "pending")]
+    pub category: TaskStatus,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct DeleteStatusCommand {
+    /// The name of the status to delete
+    pub name: String,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct StatusTransitionCommand {
+    /// Status tasks move from
+    pub from: String,
+    /// Status tasks move to
+    pub to: String,
+}
+
 /// Delete a task permanently
 /// 
 /// WARNING: This permanently removes the task from the database.
-/// For recurring tasks, you'll be prompted about series deletion.
+/// For recurring tasks, you'll be prompted for what to delete: this
+/// occurrence only (it is skipped from then on), this and future occurrences
+/// (the series ends before it) or the entire series, with or without its
+/// completed occurrences. Deleting a template always deletes its series.
 /// Use --force to skip confirmation prompts (useful for scripts).
 /// 
 /// Examples:
 ///   rusk delete abc123        # With confirmation
 ///   rusk rm def456 --force    # Skip confirmation
+///   rusk delete abc123 --scope future
+///   rusk delete abc123 --scope series --keep-history
+///   rusk delete --where "status:cancelled and project:Old"
 #[derive(Parser, Debug, Clone)]
 pub struct DeleteCommand {
-    /// Task ID to delete permanently (partial IDs accepted)
-    pub id: String,
+    #[command(flatten)]
+    pub selection: TaskSelection,
     /// Skip confirmation prompt (useful for automation)
     #[clap(short, long, help = "Delete without confirmation prompt")]
     pub force: bool,
+
+    /// How much of a recurring task to delete
+    #[arg(long, value_enum, help = "Scope for recurring tasks: occurrence (this only), future (this and future), series (entire series)")]
+    pub scope: Option<EditScope>,
+
+    /// Keep completed occurrences as regular tasks when deleting an entire series
+    #[arg(long)]
+    pub keep_history: bool,
+}
+
+/// Check the task database for problems
+/// 
+/// Finds occurrences of recurring tasks whose series no longer exists, as
+/// left behind when rows are deleted without foreign keys enforced (e.g.
+/// from a database shell). --fix turns them into regular tasks.
+/// 
+/// Examples:
+///   rusk doctor
+///   rusk doctor --fix
+#[derive(Parser, Debug, Clone)]
+pub struct DoctorCommand {
+    /// Turn orphaned occurrences into regular tasks
+    #[arg(long)]
+    pub fix: bool,
+}
+
+/// Show every detail of a single task
+/// 
+/// Prints the full ID, description, project, tags, dates in local time, the
+/// parent chain, subtasks, dependencies in both directions, series details
+/// and a history of tracked time and notes.
+/// 
+/// Examples:
+///   rusk show abc123
+#[derive(Parser, Debug, Clone)]
+pub struct ShowCommand {
+    /// Task ID to show (partial IDs accepted)
+    pub id: String,
 }
 
 /// List and filter tasks with powerful query syntax
 /// 
 /// Supports advanced filtering with logical operators:
 ///   - Basic: status:pending project:Work tag:urgent
+///   - Workflow: status:in-progress (custom statuses, see 'rusk status')
 ///   - Logical: status:pending and (project:Work or tag:urgent)
 ///   - Dates: due:today due:before:friday overdue
 ///   - Negation: not status:completed
 ///   - Series: has:recurrence no:recurrence
+///   - Deferred: is:waiting is:scheduled (hidden unless asked for, or 'all')
+///   - Text: name:report description:contains:draft note:invoice
 /// 
 /// Examples:
 ///   rusk list                           # Default view (pending tasks)
+///   rusk list all                       # Every task, including waiting ones
 ///   rusk list due:today                 # Tasks due today
 ///   rusk list project:Work and tag:urgent
 ///   rusk list "due:before:friday and not status:completed"
+///   rusk list urgency:>5 --sort urgency
 ///   rusk ls overdue                     # Using alias
 #[derive(Parser, Debug, Clone)]
 pub struct ListCommand {
@@ -283,8 +755,71 @@ pub struct ListCommand {
     /// Available fields: status, project, tag, due, priority, has, no
     /// Operators: and, or, not, parentheses for grouping
     /// Date filters: today, tomorrow, overdue, before:DATE, after:DATE
+    /// Urgency filters: urgency:>5, urgency:<=2 (joined with 'and' only)
     #[clap(default_value = "", help = "Filter expression (empty shows default view)")]
     pub query: String,
+
+    /// Sort the flat task list instead of showing the hierarchy
+    #[arg(long, value_enum)]
+    pub sort: Option<ListSort>,
+}
+
+/// Orderings for 'rusk list --sort'
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListSort {
+    /// Most urgent first
+    Urgency,
+    /// Earliest due date first, undated tasks last
+    Due,
+    /// Highest priority first
+    Priority,
+}
+
+/// Show the most urgent tasks you can work on now
+/// 
+/// Lists pending tasks that are not blocked by a pending dependency, waiting
+/// or scheduled for later, most urgent first. Urgency coefficients are configured in the [urgency]
+/// section of config.toml.
+/// 
+/// Examples:
+///   rusk next
+///   rusk next project:Work --limit 3
+#[derive(Parser, Debug, Clone)]
+pub struct NextCommand {
+    /// Filter query, as for 'rusk list'
+    #[clap(default_value = "", help = "Filter expression (empty shows default view)")]
+    pub query: String,
+
+    /// Number of tasks to show
+    #[arg(short = 'n', long, default_value_t = 5)]
+    pub limit: usize,
+}
+
+/// Show tasks as a kanban board
+/// 
+/// Columns are the workflow statuses (see 'rusk status'), or the tags that
+/// start with a prefix when --by-tag is given. Accepts the same filter query
+/// as 'rusk list'. With --interactive, cards can be moved between columns.
+/// 
+/// Examples:
+///   rusk board                          # Default view (pending tasks)
+///   rusk board project:Work
+///   rusk board "not status:cancelled"   # Include done columns
+///   rusk board --by-tag stage-          # Columns from stage-todo, stage-doing, ...
+///   rusk board -i                       # Move cards between columns
+#[derive(Parser, Debug, Clone)]
+pub struct BoardCommand {
+    /// Filter query, as for 'rusk list'
+    #[clap(default_value = "", help = "Filter expression (empty shows default view)")]
+    pub query: String,
+
+    /// Group cards by tags starting with this prefix instead of by status
+    #[arg(long, value_name = "PREFIX")]
+    pub by_tag: Option<String>,
+
+    /// Prompt to move cards between columns
+    #[arg(short, long)]
+    pub interactive: bool,
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -318,6 +853,85 @@ pub struct DeleteProjectCommand {
     pub name: String,
 }
 
+/// Manage blueprints: reusable plans of tasks
+/// 
+/// A blueprint is saved from existing tasks with their subtasks, tags,
+/// dependencies and recurrence, with due dates kept relative to an anchor
+/// date. Applying it creates all of its tasks at once, due relative to the
+/// start date given.
+/// 
+/// Examples:
+///   rusk blueprint save onboarding --task abc123 --anchor 2025-01-06
+///   rusk blueprint save sprint --project "Sprint template"
+///   rusk blueprint apply onboarding --start 2026-11-02 --project Alice
+///   rusk blueprint show onboarding
+#[derive(Parser, Debug, Clone)]
+pub struct BlueprintCommand {
+    #[command(subcommand)]
+    pub command: BlueprintSubcommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum BlueprintSubcommand {
+    /// List blueprints
+    List,
+    /// Show the tasks of a blueprint
+    Show(ShowBlueprintCommand),
+    /// Save tasks and everything below them as a blueprint
+    Save(SaveBlueprintCommand),
+    /// Create the tasks of a blueprint relative to a start date
+    Apply(ApplyBlueprintCommand),
+    /// Delete a blueprint
+    Delete(DeleteBlueprintCommand),
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct ShowBlueprintCommand {
+    /// The name of the blueprint
+    pub name: String,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct SaveBlueprintCommand {
+    /// The name of the blueprint
+    pub name: String,
+
+    /// Task to save with its subtasks; can be repeated
+    #[arg(long = "task", short, required_unless_present = "project")]
+    pub tasks: Vec<String>,
+
+    /// Save every pending task of a project with its subtasks
+    #[arg(long, short, conflicts_with = "tasks")]
+    pub project: Option<String>,
+
+    /// Date due dates are kept relative to (default: the earliest due date)
+    #[arg(long)]
+    pub anchor: Option<String>,
+
+    #[arg(long)]
+    pub description: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct ApplyBlueprintCommand {
+    /// The name of the blueprint
+    pub name: String,
+
+    /// Date the blueprint's due dates are relative to
+    #[arg(long, short)]
+    pub start: String,
+
+    /// Project to put the created tasks in
+    #[arg(long, short)]
+    pub project: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct DeleteBlueprintCommand {
+    /// The name of the blueprint to delete
+    pub name: String,
+}
+
 /// Human-friendly recurrence patterns
 /// 
 /// These shortcuts make it easy to create common recurring patterns
@@ -374,6 +988,9 @@ pub struct RecurrenceCommand {
 
 #[derive(Subcommand, Debug, Clone)]
 pub enum RecurrenceSubcommand {
+    /// List recurring series with their rule, timezone and next occurrence
+    #[command(visible_alias = "ls")]
+    List(RecurrenceListCommand),
     /// Show comprehensive information about a recurring series
     #[command(visible_alias = "i")]
     Info(RecurrenceInfoCommand),
@@ -386,10 +1003,16 @@ pub enum RecurrenceSubcommand {
     /// Move an occurrence to a different date/time
     #[command(visible_alias = "m")]
     Move(RecurrenceMoveCommand),
+    /// Give one occurrence its own name, priority, tags, project or time
+    #[command(visible_alias = "o")]
+    Override(RecurrenceOverrideCommand),
     /// Pause series to stop generating new instances
     Pause(RecurrencePauseCommand),
     /// Resume a paused series
     Resume(RecurrenceResumeCommand),
+    /// Move series to another timezone, keeping their times of day
+    #[command(name = "set-timezone")]
+    SetTimezone(RecurrenceSetTimezoneCommand),
     /// List all exceptions (skips, moves, overrides) for a series
     #[command(visible_alias = "ex")]
     Exceptions(RecurrenceExceptionsCommand),
@@ -401,9 +1024,17 @@ pub enum RecurrenceSubcommand {
     Archive(RecurrenceArchiveCommand),
     /// Show detailed statistics and health metrics for a series
     Stats(RecurrenceStatsCommand),
+    /// Show a series' instances, exceptions and rule edits as one timeline
+    History(RecurrenceHistoryCommand),
     /// Skip multiple occurrences in a date range or list
     #[command(name = "bulk-skip")]
     BulkSkip(RecurrenceBulkSkipCommand),
+    /// Skip or move every occurrence matching a date pattern
+    Pattern(RecurrencePatternCommand),
+    /// List, add or remove the rule lines (RRULE, RDATE, EXRULE, EXDATE) of a series
+    Rules(RecurrenceRulesCommand),
+    /// Show or set how a series handles times skipped or repeated by DST
+    Dst(RecurrenceDstCommand),
     /// Remove specific exceptions to restore original schedule
     #[command(name = "remove-exceptions")]
     RemoveExceptions(RecurrenceRemoveExceptionsCommand),
@@ -412,6 +1043,40 @@ pub enum RecurrenceSubcommand {
     Timezones(RecurrenceTimezonesCommand),
 }
 
+/// List recurring series
+///
+/// Shows each series' template, rule, timezone, whether it is active, its
+/// next occurrence and how many exceptions it has. Paused series are listed
+/// too, without a next occurrence.
+///
+/// Examples:
+///   rusk recur list
+///   rusk recur list standup      # Template name or rule containing 'standup'
+///   rusk recur list BYDAY=MO
+#[derive(Parser, Debug, Clone)]
+pub struct RecurrenceListCommand {
+    /// Only series whose template name or rule contains this text
+    pub query: Option<String>,
+}
+
+/// The series a recur command applies to: one series or every series
+/// matching a pattern
+///
+/// --where lists the matching series first and changes all of them in one
+/// transaction, or none. An empty pattern matches every series.
+#[derive(Args, Debug, Clone)]
+pub struct SeriesSelection {
+    /// Series ID or template task ID
+    #[arg(required_unless_present = "pattern", conflicts_with = "pattern")]
+    pub id: Option<String>,
+    /// Apply to every series whose template name or rule contains this text
+    #[arg(long = "where", value_name = "PATTERN")]
+    pub pattern: Option<String>,
+    /// Show the affected series without changing anything
+    #[arg(long, requires = "pattern")]
+    pub dry_run: bool,
+}
+
 #[derive(Parser, Debug, Clone)]
 pub struct RecurrenceInfoCommand {
     /// Series ID or template task ID
@@ -449,15 +1114,82 @@ pub struct RecurrenceMoveCommand {
 }
 
 #[derive(Parser, Debug, Clone)]
-pub struct RecurrencePauseCommand {
+pub struct RecurrenceOverrideCommand {
     /// Series ID or template task ID
     pub id: String,
+    /// Date of the occurrence (e.g., '2025-08-20', 'next friday')
+    #[clap(long)]
+    pub on: String,
+    /// Task name for this occurrence
+    #[clap(long)]
+    pub name: Option<String>,
+    /// Description for this occurrence
+    #[clap(short = 'D', long)]
+    pub description: Option<String>,
+    /// Priority level (none, low, medium, high)
+    #[clap(long, value_enum)]
+    pub priority: Option<TaskPriority>,
+    /// Project name
+    #[clap(short, long)]
+    pub project: Option<String>,
+    /// Tags for this occurrence (can specify multiple)
+    #[clap(short, long, num_args = 1..)]
+    pub tag: Vec<String>,
+    /// Time of day for this occurrence (e.g., '9:00 AM', '14:30', '5pm')
+    #[clap(long)]
+    pub at: Option<String>,
+    /// Revert the occurrence to the series defaults, keeping its notes and tracked time
+    #[clap(long, conflicts_with_all = ["name", "description", "priority", "project", "tag", "at"])]
+    pub revert: bool,
+}
+
+/// Pause series so no new instances are created
+///
+/// Examples:
+///   rusk recur pause abc123
+///   rusk recur pause --where "Gym" --dry-run
+#[derive(Parser, Debug, Clone)]
+pub struct RecurrencePauseCommand {
+    #[command(flatten)]
+    pub selection: SeriesSelection,
+    /// Don't ask for confirmation
+    #[arg(short, long)]
+    pub yes: bool,
 }
 
+/// Resume paused series
+///
+/// Examples:
+///   rusk recur resume abc123
+///   rusk recur resume --where "Gym" --yes
 #[derive(Parser, Debug, Clone)]
 pub struct RecurrenceResumeCommand {
-    /// Series ID or template task ID
-    pub id: String,
+    #[command(flatten)]
+    pub selection: SeriesSelection,
+    /// Don't ask for confirmation
+    #[arg(short, long)]
+    pub yes: bool,
+}
+
+/// Move series to another timezone
+///
+/// Occurrences keep their times of day: a 9:00 series in Europe/Berlin runs
+/// at 9:00 in America/New_York afterwards, and so do its pending instances
+/// and exceptions. Floating and all-day series are not tied to a timezone
+/// and are left alone.
+///
+/// Examples:
+///   rusk recur set-timezone America/New_York abc123
+///   rusk recur set-timezone America/New_York --where "" --dry-run
+#[derive(Parser, Debug, Clone)]
+pub struct RecurrenceSetTimezoneCommand {
+    /// Timezone to move to (e.g., 'America/New_York', 'pst')
+    pub timezone: String,
+    #[command(flatten)]
+    pub selection: SeriesSelection,
+    /// Don't ask for confirmation
+    #[arg(short, long)]
+    pub yes: bool,
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -488,6 +1220,17 @@ pub struct RecurrenceArchiveCommand {
     pub force: bool,
 }
 
+/// Show statistics for a series
+///
+/// With --detailed, also shows habit statistics: current and longest streaks
+/// of on-time completions, the on-time rate and average lateness, completions
+/// per weekday, and on-time rates over the last 7, 30 and 90 days compared
+/// with the period before. Skipped and cancelled occurrences do not break a
+/// streak.
+///
+/// Examples:
+///   rusk recur stats abc123 --detailed
+///   rusk recur stats abc123 --json
 #[derive(Parser, Debug, Clone)]
 pub struct RecurrenceStatsCommand {
     /// Series ID or template task ID
@@ -495,6 +1238,32 @@ pub struct RecurrenceStatsCommand {
     /// Show detailed breakdown
     #[clap(long)]
     pub detailed: bool,
+    /// Print the statistics, including habit statistics, as JSON
+    #[clap(long)]
+    pub json: bool,
+}
+
+/// Show the timeline of a series
+///
+/// Lists the series' instances with their status and how late they were
+/// completed, its skipped, overridden and moved occurrences, and the edits of
+/// its rule, oldest first. Past occurrences that were never completed are
+/// marked as missed, also when they never got an instance.
+///
+/// Examples:
+///   rusk recur history abc123
+///   rusk recur history abc123 --since "last month"
+///   rusk recur history abc123 --missed
+#[derive(Parser, Debug, Clone)]
+pub struct RecurrenceHistoryCommand {
+    /// Series ID or template task ID
+    pub id: String,
+    /// Only entries from this date on (e.g., '2025-08-01', 'last month')
+    #[clap(long)]
+    pub since: Option<String>,
+    /// Only occurrences that were never completed
+    #[clap(long)]
+    pub missed: bool,
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -512,6 +1281,79 @@ pub struct RecurrenceBulkSkipCommand {
     pub to: Option<String>,
 }
 
+#[derive(Parser, Debug, Clone)]
+pub struct RecurrencePatternCommand {
+    /// Series ID or template task ID
+    pub id: String,
+    /// Skip the matching occurrences
+    #[clap(long, conflicts_with = "move_to", required_unless_present = "move_to")]
+    pub skip: bool,
+    /// Move the matching occurrences to this weekday of the same week, keeping the time (e.g., 'thu')
+    #[clap(long, value_name = "WEEKDAY")]
+    pub move_to: Option<String>,
+    /// Only occurrences in these months (e.g., 'aug', '10,11,12', 'q4')
+    #[clap(long)]
+    pub month: Option<String>,
+    /// Only occurrences on these weekdays (e.g., 'fri', 'sat,sun')
+    #[clap(long)]
+    pub weekday: Option<String>,
+    /// Only occurrences on the dates listed in this file, one YYYY-MM-DD per line
+    #[clap(long, value_name = "FILE")]
+    pub calendar: Option<std::path::PathBuf>,
+    /// Only every nth of the matching occurrences
+    #[clap(long, value_name = "N")]
+    pub every_nth: Option<usize>,
+    /// Start of the date range (default: now)
+    #[clap(long)]
+    pub from: Option<String>,
+    /// End of the date range (default: a year after the start)
+    #[clap(long)]
+    pub to: Option<String>,
+    /// Show the matching occurrences without changing anything
+    #[clap(long)]
+    pub dry_run: bool,
+    /// Don't ask for confirmation
+    #[clap(short, long)]
+    pub yes: bool,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct RecurrenceRulesCommand {
+    /// Series ID or template task ID
+    pub id: String,
+    /// Add a rule line, e.g. 'FREQ=MONTHLY;BYDAY=-1FR' or 'EXRULE:FREQ=YEARLY;BYMONTH=8' (a bare rule is an RRULE)
+    #[clap(long, value_name = "LINE")]
+    pub add: Vec<String>,
+    /// Add a one-off occurrence (RDATE) on this date, at the series' time of day
+    #[clap(long, value_name = "DATE")]
+    pub add_date: Vec<String>,
+    /// Remove the rule line with this number, as listed without options
+    #[clap(long, value_name = "N")]
+    pub remove: Vec<usize>,
+}
+
+/// Show or set the DST policy of a series
+///
+/// On spring-forward day a local time such as 2:30am does not exist; on
+/// fall-back day 1:30am happens twice. The policy decides where such
+/// occurrences go. Pending occurrences it moves are rescheduled.
+///
+/// Examples:
+///   rusk recur dst abc123                       # Show the policy
+///   rusk recur dst abc123 --gap skip
+///   rusk recur dst abc123 --overlap latest
+#[derive(Parser, Debug, Clone)]
+pub struct RecurrenceDstCommand {
+    /// Series ID or template task ID
+    pub id: String,
+    /// For times skipped by DST: shift-forward, shift-backward or skip
+    #[clap(long, value_name = "POLICY")]
+    pub gap: Option<DstGapPolicy>,
+    /// For times repeated by DST: earliest or latest
+    #[clap(long, value_name = "POLICY")]
+    pub overlap: Option<DstOverlapPolicy>,
+}
+
 #[derive(Parser, Debug, Clone)]
 pub struct RecurrenceRemoveExceptionsCommand {
     /// Series ID or template task ID
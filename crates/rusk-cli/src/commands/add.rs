@@ -1,9 +1,10 @@
 use anyhow::Result;
-use chrono::{DateTime, Utc, Timelike};
-use rusk_core::models::NewTaskData;
+use chrono::{DateTime, Duration, NaiveTime, Utc, Timelike};
+use rusk_core::models::{DstPolicy, NewTaskData, Task, TaskSeries, TimeMode};
+use rusk_core::recurrence::RecurrenceManager;
 use rusk_core::repository::Repository;
 use crate::cli::{AddCommand, RecurrenceShortcut};
-use crate::parser::parse_due_date;
+use crate::parser::{is_relative_to_due, parse_date_relative_to_due, parse_due_date, parse_duration};
 use crate::timezone::{detect_system_timezone, normalize_timezone_input};
 use uuid::Uuid;
 
@@ -14,9 +15,29 @@ pub async fn add_task(repo: &impl Repository, command: AddCommand) -> Result<()>
         .as_ref()
         .map(|d| d.parse::<Uuid>())
         .transpose()?;
+    let remind_before = command.remind.as_deref().map(parse_duration).transpose()?;
+    let estimate = command.estimate.as_deref().map(parse_duration).transpose()?;
+    let is_recurring = command.recurrence.is_some() || command.every.is_some();
+    if command.floating && !is_recurring {
+        return Err(anyhow::anyhow!("--floating only applies to recurring tasks; add --every or --recurrence"));
+    }
+    if (command.dst_gap.is_some() || command.dst_overlap.is_some()) && !is_recurring {
+        return Err(anyhow::anyhow!("--dst-gap and --dst-overlap only apply to recurring tasks; add --every or --recurrence"));
+    }
+    let dst_policy = DstPolicy {
+        dst_gap: command.dst_gap.unwrap_or_default(),
+        dst_overlap: command.dst_overlap.unwrap_or_default(),
+    };
+    let time_mode = if command.all_day {
+        TimeMode::AllDay
+    } else if command.floating {
+        TimeMode::Floating
+    } else {
+        TimeMode::Zoned
+    };
 
     // Handle recurrence options
-    let (rrule, timezone) = if command.recurrence.is_some() || command.every.is_some() {
+    let (rrule, timezone) = if is_recurring {
         let timezone = if let Some(ref tz) = command.timezone {
             normalize_timezone_input(&tz)?
         } else {
@@ -38,6 +59,24 @@ pub async fn add_task(repo: &impl Repository, command: AddCommand) -> Result<()>
         (None, command.timezone.map(|tz| normalize_timezone_input(&tz)).transpose()?)
     };
 
+    // A series without a due date measures dates relative to due from its
+    // first occurrence, which becomes the template's due date as with --due
+    let relative_dates = [&command.scheduled, &command.wait, &command.start];
+    let due_at = match (&rrule, &timezone) {
+        (Some(rrule), Some(timezone))
+            if due_at.is_none() && relative_dates.into_iter().flatten().any(|d| is_relative_to_due(d)) =>
+        {
+            first_occurrence(rrule, timezone, time_mode)?
+        }
+        _ => due_at,
+    };
+    let relative_to_due = |date: &Option<String>| {
+        date.as_deref().map(|d| parse_date_relative_to_due(d, due_at)).transpose()
+    };
+    let scheduled_at = relative_to_due(&command.scheduled)?;
+    let wait_until = relative_to_due(&command.wait)?;
+    let start_at = relative_to_due(&command.start)?;
+
     let new_task_data = NewTaskData {
         name: command.name,
         description: command.description,
@@ -51,9 +90,15 @@ pub async fn add_task(repo: &impl Repository, command: AddCommand) -> Result<()>
         depends_on,
         series_id: None,
         timezone,
+        remind_before,
+        estimate,
+        scheduled_at,
+        wait_until,
+        start_at,
+        time_mode,
+        dst_policy,
     };
 
-    let is_recurring = new_task_data.rrule.is_some();
     let added_task = repo.add_task(new_task_data).await?;
     
     // Enhanced success feedback with colors and helpful information
@@ -110,11 +155,12 @@ pub async fn add_task(repo: &impl Repository, command: AddCommand) -> Result<()>
         );
         
         // Show helpful next steps for regular tasks
-        if added_task.due_at.is_some() {
+        if let Some(due_at) = added_task.due_at {
+            let format = if added_task.all_day { "%Y-%m-%d" } else { "%Y-%m-%d %H:%M" };
             println!(
                 "  {} Due: {}", 
                 "→".style(info_style), 
-                added_task.due_at.unwrap().format("%Y-%m-%d %H:%M").to_string().cyan()
+                due_at.format(format).to_string().cyan()
             );
         }
         
@@ -137,6 +183,25 @@ pub async fn add_task(repo: &impl Repository, command: AddCommand) -> Result<()>
     Ok(())
 }
 
+/// The first occurrence from now of a series with `rrule`, before the
+/// series exists; all-day series start at today's date in UTC
+fn first_occurrence(rrule: &str, timezone: &str, time_mode: TimeMode) -> Result<Option<DateTime<Utc>>> {
+    let now = Utc::now();
+    let (dtstart, timezone) = match time_mode {
+        TimeMode::AllDay => (now.date_naive().and_time(NaiveTime::MIN).and_utc(), "UTC"),
+        TimeMode::Zoned | TimeMode::Floating => (now, timezone),
+    };
+    let series = TaskSeries {
+        rrule: rrule.to_string(),
+        dtstart,
+        timezone: timezone.to_string(),
+        time_mode,
+        ..Default::default()
+    };
+    let manager = RecurrenceManager::new(series, Task::default(), Vec::new())?;
+    Ok(manager.next_occurrence_after(dtstart - Duration::nanoseconds(1))?)
+}
+
 /// Generate RRULE from recurrence shortcut with additional options
 fn generate_rrule_from_shortcut(
     shortcut: RecurrenceShortcut,
@@ -173,7 +238,7 @@ fn generate_rrule_from_shortcut(
 }
 
 /// Parse time string like "9:00 AM", "14:30", "9pm", "noon", "midnight"
-fn parse_time_string(time_str: &str) -> Result<chrono::NaiveTime> {
+pub fn parse_time_string(time_str: &str) -> Result<chrono::NaiveTime> {
     use chrono::NaiveTime;
     
     let input = time_str.trim().to_lowercase();
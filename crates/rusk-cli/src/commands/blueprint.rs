@@ -0,0 +1,110 @@
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use rusk_core::blueprints::{capture_blueprint, project_roots};
+use rusk_core::models::NewBlueprint;
+use rusk_core::repository::Repository;
+
+use crate::cli::{
+    ApplyBlueprintCommand, BlueprintCommand, BlueprintSubcommand, DeleteBlueprintCommand, SaveBlueprintCommand,
+    ShowBlueprintCommand,
+};
+use crate::parser::parse_due_date;
+use crate::util::resolve_task_id;
+use crate::views::table::{display_blueprint_tasks, display_blueprints};
+
+pub async fn blueprint_command(repo: &(impl Repository + Sync), command: BlueprintCommand) -> Result<()> {
+    match command.command {
+        BlueprintSubcommand::List => list_blueprints(repo).await,
+        BlueprintSubcommand::Show(show_command) => show_blueprint(repo, show_command).await,
+        BlueprintSubcommand::Save(save_command) => save_blueprint(repo, save_command).await,
+        BlueprintSubcommand::Apply(apply_command) => apply_blueprint(repo, apply_command).await,
+        BlueprintSubcommand::Delete(delete_command) => delete_blueprint(repo, delete_command).await,
+    }
+}
+
+async fn list_blueprints(repo: &impl Repository) -> Result<()> {
+    display_blueprints(&repo.find_blueprints().await?);
+    Ok(())
+}
+
+async fn show_blueprint(repo: &impl Repository, command: ShowBlueprintCommand) -> Result<()> {
+    let blueprint = repo
+        .find_blueprint(&command.name)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Blueprint '{}' not found", command.name))?;
+    println!("{}", format!("Blueprint: {}", blueprint.name).blue().bold());
+    if let Some(description) = &blueprint.description {
+        println!("{}", description);
+    }
+    println!();
+    display_blueprint_tasks(&blueprint.tasks);
+    Ok(())
+}
+
+async fn save_blueprint(repo: &(impl Repository + Sync), command: SaveBlueprintCommand) -> Result<()> {
+    let roots = match &command.project {
+        Some(project) => {
+            if repo.find_project_by_name(project).await?.is_none() {
+                anyhow::bail!("Project '{}' not found", project);
+            }
+            project_roots(repo, project).await?
+        }
+        None => {
+            let mut roots = Vec::with_capacity(command.tasks.len());
+            for id in &command.tasks {
+                roots.push(resolve_task_id(repo, id).await?);
+            }
+            roots
+        }
+    };
+    if roots.is_empty() {
+        anyhow::bail!("No pending tasks to save");
+    }
+
+    let anchor = command.anchor.as_deref().map(|a| parse_due_date(a, None)).transpose()?;
+    let tasks = capture_blueprint(repo, &roots, anchor).await?;
+    let blueprint = repo
+        .save_blueprint(NewBlueprint {
+            name: command.name,
+            description: command.description,
+            tasks,
+        })
+        .await?;
+    println!(
+        "{} Saved blueprint '{}' with {} task(s)",
+        "✓".green().bold(),
+        blueprint.name,
+        blueprint.tasks.len()
+    );
+    Ok(())
+}
+
+async fn apply_blueprint(repo: &impl Repository, command: ApplyBlueprintCommand) -> Result<()> {
+    let start = parse_due_date(&command.start, None)?;
+    if let Some(project) = &command.project {
+        if repo.find_project_by_name(project).await?.is_none() {
+            anyhow::bail!("Project '{}' not found", project);
+        }
+    }
+    let created = repo.apply_blueprint(&command.name, start, command.project).await?;
+    println!(
+        "{} Created {} task(s) from blueprint '{}'",
+        "✓".green().bold(),
+        created.len(),
+        command.name
+    );
+    for task in &created {
+        let due = task
+            .due_at
+            .map(|due| format!(" (due {})", due.format("%Y-%m-%d %H:%M UTC")))
+            .unwrap_or_default();
+        println!("  {} {}{}", task.id.yellow(), task.name, due);
+    }
+    Ok(())
+}
+
+async fn delete_blueprint(repo: &impl Repository, command: DeleteBlueprintCommand) -> Result<()> {
+    repo.delete_blueprint(&command.name).await?;
+    println!("Blueprint deleted.");
+    Ok(())
+}
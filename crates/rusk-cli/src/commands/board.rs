@@ -0,0 +1,149 @@
+use anyhow::Result;
+use dialoguer::{Input, Select};
+use rusk_core::models::{TaskStatus, UpdateTaskData};
+use rusk_core::repository::Repository;
+use std::collections::BTreeMap;
+
+use crate::cli::BoardCommand;
+use crate::commands::list::{find_tasks, resolve_query};
+use crate::config::Config;
+use crate::query_parser::TaskFilter;
+use crate::views::board::{display_board, BoardColumn};
+use crate::views::table::ViewTask;
+
+/// Column for tasks without a tag matching `--by-tag`
+const UNTAGGED_COLUMN: &str = "(none)";
+
+pub async fn show_board(repo: &impl Repository, command: BoardCommand, config: &Config) -> Result<()> {
+    let filter = resolve_query(repo, command.query, config).await?;
+    let tag_prefix = command.by_tag.as_deref();
+
+    loop {
+        let columns = build_columns(repo, &filter, config, tag_prefix).await?;
+        display_board(&columns);
+
+        if !command.interactive || !move_card(repo, &columns, tag_prefix).await? {
+            return Ok(());
+        }
+    }
+}
+
+async fn build_columns(
+    repo: &impl Repository,
+    filter: &TaskFilter,
+    config: &Config,
+    tag_prefix: Option<&str>,
+) -> Result<Vec<BoardColumn>> {
+    let tasks = find_tasks(repo, filter, config).await?;
+
+    match tag_prefix {
+        None => {
+            // Pending columns always show so cards can be moved into them;
+            // done and cancelled columns only when the query matched tasks there
+            let statuses = repo.find_workflow_statuses().await?;
+            Ok(statuses
+                .into_iter()
+                .map(|status| BoardColumn {
+                    cards: tasks.iter().filter(|t| t.workflow_status == status.name).cloned().collect(),
+                    title: status.name,
+                    category: Some(status.category),
+                })
+                .filter(|column| column.category == Some(TaskStatus::Pending) || !column.cards.is_empty())
+                .collect())
+        }
+        Some(prefix) => {
+            let mut untagged = Vec::new();
+            let mut by_tag: BTreeMap<String, Vec<ViewTask>> = BTreeMap::new();
+            for task in tasks {
+                match column_tag(&task, prefix) {
+                    Some(title) => by_tag.entry(title.to_string()).or_default().push(task),
+                    None => untagged.push(task),
+                }
+            }
+
+            let mut columns = Vec::new();
+            if !untagged.is_empty() {
+                columns.push(BoardColumn { title: UNTAGGED_COLUMN.to_string(), category: None, cards: untagged });
+            }
+            columns.extend(by_tag.into_iter().map(|(title, cards)| BoardColumn { title, category: None, cards }));
+            Ok(columns)
+        }
+    }
+}
+
+/// Column a task belongs to in tag mode: its first tag with the prefix, minus the prefix
+fn column_tag<'a>(task: &'a ViewTask, prefix: &str) -> Option<&'a str> {
+    task.tags
+        .iter()
+        .find_map(|tag| tag.strip_prefix(prefix).filter(|rest| !rest.is_empty()))
+}
+
+/// Ask for a card and a target column and move it; returns false when the user is done
+async fn move_card(repo: &impl Repository, columns: &[BoardColumn], tag_prefix: Option<&str>) -> Result<bool> {
+    let cards: Vec<(&BoardColumn, &ViewTask)> = columns
+        .iter()
+        .flat_map(|column| column.cards.iter().map(move |card| (column, card)))
+        .collect();
+
+    let mut items: Vec<String> = cards
+        .iter()
+        .map(|(column, card)| format!("[{}] {} {}", column.title, &card.id.to_string()[..7], card.name))
+        .collect();
+    items.push("Done".to_string());
+
+    let selection = Select::new()
+        .with_prompt("Move which card?")
+        .items(&items)
+        .default(0)
+        .interact()?;
+    let Some((from, card)) = cards.get(selection) else {
+        return Ok(false);
+    };
+
+    let mut targets: Vec<String> = columns
+        .iter()
+        .map(|c| c.title.clone())
+        .filter(|title| *title != from.title)
+        .collect();
+    if tag_prefix.is_some() {
+        if from.title != UNTAGGED_COLUMN && !targets.iter().any(|t| t == UNTAGGED_COLUMN) {
+            targets.push(UNTAGGED_COLUMN.to_string());
+        }
+        targets.push("New column...".to_string());
+    }
+    if targets.is_empty() {
+        println!("There is no other column to move to.");
+        return Ok(true);
+    }
+
+    let target = Select::new()
+        .with_prompt(format!("Move '{}' to", card.name))
+        .items(&targets)
+        .default(0)
+        .interact()?;
+
+    match tag_prefix {
+        None => {
+            let task = repo.move_task(card.id, &targets[target]).await?;
+            println!("Moved task '{}' to {}", task.name, task.workflow_status);
+        }
+        Some(prefix) => {
+            let title = if target == targets.len() - 1 {
+                Input::<String>::new().with_prompt("Column name").interact_text()?
+            } else {
+                targets[target].clone()
+            };
+            let old_tag = column_tag(card, prefix).map(|t| format!("{}{}", prefix, t));
+            let new_tag = (title != UNTAGGED_COLUMN).then(|| format!("{}{}", prefix, title.trim()));
+            let update = UpdateTaskData {
+                add_tags: new_tag.map(|t| vec![t]),
+                remove_tags: old_tag.map(|t| vec![t]),
+                ..Default::default()
+            };
+            repo.update_task(card.id, update, None).await?;
+            println!("Moved task '{}' to {}", card.name, title.trim());
+        }
+    }
+
+    Ok(true)
+}
@@ -0,0 +1,111 @@
+use anyhow::{bail, Result};
+use dialoguer::Confirm;
+use rusk_core::error::CoreError;
+use rusk_core::models::BulkAction;
+use rusk_core::query::{Filter, Query};
+use rusk_core::repository::Repository;
+use std::collections::HashSet;
+
+use crate::cli::TaskSelection;
+use crate::commands::list::{find_tasks, resolve_query};
+use crate::config::Config;
+use crate::query_parser::TaskFilter;
+use crate::util::resolve_task_id;
+use crate::views::table::{display_tasks, ViewTask};
+
+/// Tasks named by the selection's IDs, in the order given, or matched by its
+/// query, in list order and without series templates
+pub async fn select_tasks(repo: &impl Repository, selection: &TaskSelection, config: &Config) -> Result<Vec<ViewTask>> {
+    let mut tasks = match &selection.query {
+        Some(query) => {
+            let filter = resolve_query(repo, query.clone(), config).await?;
+
+            // Templates stand in for their series; a query acts on the occurrences
+            let mut matched = Vec::new();
+            for task in find_tasks(repo, &filter, config).await? {
+                if repo.find_series_by_template(task.id).await?.is_none() {
+                    matched.push(task);
+                }
+            }
+            matched
+        }
+        None => {
+            let mut seen = HashSet::new();
+            let mut tasks = Vec::new();
+            for short_id in &selection.ids {
+                let id = resolve_task_id(repo, short_id).await?;
+                if seen.insert(id) {
+                    let filter = TaskFilter { query: Query::Filter(Filter::Id(id)), urgency: Vec::new() };
+                    tasks.extend(find_tasks(repo, &filter, config).await?);
+                }
+            }
+            tasks
+        }
+    };
+
+    // The preview is a flat list; parents may not be part of it
+    for task in &mut tasks {
+        task.depth = 0;
+    }
+    Ok(tasks)
+}
+
+/// Preview the selected tasks, ask for confirmation unless `confirmed`, and
+/// apply `action` to all of them in one transaction
+pub async fn apply_bulk_action(
+    repo: &impl Repository,
+    selection: &TaskSelection,
+    action: BulkAction,
+    confirmed: bool,
+    config: &Config,
+) -> Result<()> {
+    let tasks = select_tasks(repo, selection, config).await?;
+    if tasks.is_empty() {
+        println!("No tasks matched.");
+        return Ok(());
+    }
+
+    let (verb, past) = match action {
+        BulkAction::Complete => ("Complete", "Completed"),
+        BulkAction::Cancel => ("Cancel", "Cancelled"),
+        BulkAction::Delete => ("Delete", "Deleted"),
+        BulkAction::Update(_) => ("Update", "Updated"),
+        BulkAction::Move(_) => ("Move", "Moved"),
+        BulkAction::Annotate(_) => ("Add a note to", "Added a note to"),
+        BulkAction::LogTime(_) => ("Log time on", "Logged time on"),
+    };
+    let count = match tasks.len() {
+        1 => "1 task".to_string(),
+        n => format!("{} tasks", n),
+    };
+
+    display_tasks(&tasks);
+    if selection.dry_run {
+        println!("Dry run: would {} {}.", verb.to_lowercase(), count);
+        return Ok(());
+    }
+
+    if !confirmed {
+        let confirmation = Confirm::new()
+            .with_prompt(format!("{} {}?", verb, count))
+            .default(false)
+            .interact()
+            .unwrap_or(false);
+        if !confirmation {
+            println!("Nothing changed.");
+            return Ok(());
+        }
+    }
+
+    let ids: Vec<_> = tasks.iter().map(|t| t.id).collect();
+    match repo.bulk_update_tasks(&ids, action).await {
+        Ok(_) => {
+            println!("{} {}.", past, count);
+            Ok(())
+        }
+        Err(CoreError::TaskBlocked(deps)) => {
+            bail!("Nothing changed: tasks are blocked by the following tasks: {}", deps)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
@@ -1,11 +1,18 @@
 use anyhow::Result;
+use rusk_core::models::BulkAction;
 use rusk_core::repository::Repository;
 
 use crate::cli::CancelCommand;
+use crate::commands::bulk::apply_bulk_action;
+use crate::config::Config;
 use crate::util::resolve_task_id;
 
-pub async fn cancel_task(repo: &impl Repository, command: CancelCommand) -> Result<()> {
-    let task_id = resolve_task_id(repo, &command.id).await?;
+pub async fn cancel_task(repo: &impl Repository, command: CancelCommand, config: &Config) -> Result<()> {
+    if command.selection.is_bulk() {
+        return apply_bulk_action(repo, &command.selection, BulkAction::Cancel, command.yes, config).await;
+    }
+
+    let task_id = resolve_task_id(repo, &command.selection.ids[0]).await?;
     let task = repo.cancel_task(task_id).await?;
     println!("Cancelled task: {}", task.name);
     Ok(())
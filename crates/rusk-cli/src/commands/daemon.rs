@@ -0,0 +1,87 @@
+use anyhow::{anyhow, Result};
+use owo_colors::OwoColorize;
+use rusk_core::clock::SystemClock;
+use rusk_core::daemon::{
+    CommandSink, Daemon, DaemonConfig, DesktopNotifySink, ReminderSink, StdoutSink, TickReport,
+};
+use rusk_core::repository::Repository;
+use std::sync::Arc;
+
+use crate::cli::{DaemonCommand, ReminderSinkKind};
+use crate::config::{Config, DaemonSettings};
+
+pub async fn run_daemon(repo: &(impl Repository + Sync), command: DaemonCommand, config: &Config) -> Result<()> {
+    let settings = &config.daemon;
+    let daemon_config = DaemonConfig {
+        interval: std::time::Duration::from_secs(command.interval.unwrap_or(settings.interval_seconds).max(1)),
+        lookahead: chrono::Duration::days(config.recurrence.lookahead_days as i64),
+        grace: chrono::Duration::days(config.recurrence.materialization_grace_days as i64),
+        missed_reminder_window: chrono::Duration::minutes(settings.missed_reminder_minutes),
+    };
+
+    let sink_kinds = if command.sink.is_empty() {
+        settings.sinks.clone()
+    } else {
+        command.sink
+    };
+
+    let mut daemon = Daemon::new(repo, Arc::new(SystemClock), daemon_config);
+    for kind in &sink_kinds {
+        daemon = daemon.with_sink(build_sink(*kind, settings)?);
+    }
+    if sink_kinds.is_empty() {
+        eprintln!(
+            "{} no reminder sinks are set; reminders stay pending until one is (--sink, or 'sinks' in the [daemon] section of config.toml)",
+            "Warning:".yellow().bold()
+        );
+    }
+
+    if command.once {
+        let report = daemon.tick().await?;
+        print_failures(&report);
+        println!("Delivered {} reminder(s).", report.delivered.len());
+        return Ok(());
+    }
+
+    let sink_names: Vec<String> = sink_kinds.iter().map(|k| format!("{:?}", k).to_lowercase()).collect();
+    println!(
+        "{} rusk daemon running (every {}s, sinks: {}). Press Ctrl-C to stop.",
+        "→".blue(),
+        daemon.config().interval.as_secs(),
+        if sink_names.is_empty() { "none".to_string() } else { sink_names.join(", ") }
+    );
+
+    daemon
+        .run(
+            async {
+                let _ = tokio::signal::ctrl_c().await;
+            },
+            |result| match result {
+                Ok(report) => print_failures(&report),
+                Err(e) => eprintln!("{} {}", "Error:".red().bold(), e),
+            },
+        )
+        .await;
+
+    println!("rusk daemon stopped.");
+    Ok(())
+}
+
+fn build_sink(kind: ReminderSinkKind, settings: &DaemonSettings) -> Result<Box<dyn ReminderSink>> {
+    Ok(match kind {
+        ReminderSinkKind::Stdout => Box::new(StdoutSink),
+        ReminderSinkKind::Command => {
+            let command = settings.command.as_ref().ok_or_else(|| {
+                anyhow!("The 'command' sink requires 'command' in the [daemon] section of config.toml")
+            })?;
+            Box::new(CommandSink::new(command.clone()))
+        }
+        ReminderSinkKind::Notify => Box::new(DesktopNotifySink::new(settings.notify_command.clone())),
+    })
+}
+
+fn print_failures(report: &TickReport) {
+    for (sink, error) in &report.failures {
+        eprintln!("{} reminder sink '{}' failed: {}", "Warning:".yellow().bold(), sink, error);
+    }
+}
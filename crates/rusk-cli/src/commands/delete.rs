@@ -1,9 +1,98 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
+use dialoguer::{Confirm, Select};
+use owo_colors::{OwoColorize, Style};
+use rusk_core::models::{BulkAction, EditScope};
 use rusk_core::repository::Repository;
-use uuid::Uuid;
 
-pub async fn delete_task(repo: &impl Repository, task_id: Uuid) -> Result<()> {
-    repo.delete_task(task_id).await?;
-    println!("Task deleted successfully.");
+use crate::cli::DeleteCommand;
+use crate::commands::bulk::apply_bulk_action;
+use crate::config::Config;
+use crate::util::resolve_task_id;
+
+pub async fn delete_task(repo: &impl Repository, command: DeleteCommand, config: &Config) -> Result<()> {
+    if command.selection.is_bulk() {
+        // Each task is deleted on its own; series-wide deletion needs a single occurrence to start from
+        if command.scope.is_some_and(|scope| scope != EditScope::ThisOccurrence) || command.keep_history {
+            bail!("--scope future and series and --keep-history apply to a single task; delete one occurrence at a time");
+        }
+        return apply_bulk_action(repo, &command.selection, BulkAction::Delete, command.force, config).await;
+    }
+
+    let task_id = resolve_task_id(repo, &command.selection.ids[0]).await?;
+    let task = match repo.find_task_by_id(task_id).await? {
+        Some(t) => t,
+        None => {
+            let error_style = Style::new().red().bold();
+            eprintln!("{} Task with ID '{}' not found.", "Error:".style(error_style), task_id);
+            return Ok(());
+        }
+    };
+
+    let series = match task.series_id {
+        Some(series_id) => repo.find_series_by_id(series_id).await?,
+        None => repo.find_series_by_template(task_id).await?,
+    };
+    let is_template = series.as_ref().is_some_and(|series| series.template_task_id == task_id);
+
+    let (scope, keep_history) = if is_template {
+        // A template without its series is not a task of its own
+        (EditScope::EntireSeries, command.keep_history)
+    } else if series.is_none() || command.scope.is_some() || command.force {
+        (command.scope.unwrap_or(EditScope::ThisOccurrence), command.keep_history)
+    } else {
+        let scope_options = vec![
+            format!("This occurrence only ({})",
+                task.due_at.map(|d| d.format("%Y-%m-%d").to_string())
+                    .unwrap_or_else(|| "No due date".to_string())),
+            "This and future occurrences".to_string(),
+            "Entire series, keeping completed occurrences".to_string(),
+            "Entire series, including completed occurrences".to_string(),
+        ];
+
+        println!("{}", "This task is part of a recurring series.".yellow());
+        let selection = Select::new()
+            .with_prompt("What would you like to delete?")
+            .items(&scope_options)
+            .default(0)
+            .interact()?;
+
+        match selection {
+            0 => (EditScope::ThisOccurrence, false),
+            1 => (EditScope::ThisAndFuture, false),
+            2 => (EditScope::EntireSeries, true),
+            3 => (EditScope::EntireSeries, false),
+            _ => unreachable!(),
+        }
+    };
+
+    if !command.force {
+        let prompt = match (series.is_some(), scope) {
+            (false, _) | (true, EditScope::ThisOccurrence) => format!("Are you sure you want to delete task '{}'?", task.name),
+            (true, EditScope::ThisAndFuture) => format!("Are you sure you want to delete '{}' and all future occurrences?", task.name),
+            (true, EditScope::EntireSeries) => format!("Are you sure you want to delete the entire series '{}'?", task.name),
+        };
+        let confirmation = Confirm::new()
+            .with_prompt(prompt)
+            .default(false)
+            .interact()
+            .unwrap_or(false);
+
+        if !confirmation {
+            println!("Deletion cancelled.");
+            return Ok(());
+        }
+    }
+
+    // Scoped even for regular tasks, so deleting a moved occurrence still skips it
+    repo.delete_task_with_scope(task_id, scope, keep_history).await?;
+    match (series.is_some(), scope) {
+        (false, _) => println!("Task deleted successfully."),
+        (true, EditScope::ThisOccurrence) => println!("Occurrence deleted; the series will skip it."),
+        (true, EditScope::ThisAndFuture) => println!("Deleted this and future occurrences; the series now ends before it."),
+        (true, EditScope::EntireSeries) if keep_history => {
+            println!("Series deleted; completed occurrences were kept as regular tasks.")
+        }
+        (true, EditScope::EntireSeries) => println!("Series deleted with all its occurrences."),
+    }
     Ok(())
-}
\ No newline at end of file
+}
@@ -1,13 +1,19 @@
 use anyhow::{anyhow, Result};
 use rusk_core::error::CoreError;
-use rusk_core::models::CompletionResult;
+use rusk_core::models::{BulkAction, CompletionResult};
 use rusk_core::repository::Repository;
 
 use crate::cli::DoCommand;
+use crate::commands::bulk::apply_bulk_action;
+use crate::config::Config;
 use crate::util::resolve_task_id;
 
-pub async fn do_task(repo: &impl Repository, command: DoCommand) -> Result<()> {
-    let task_id = resolve_task_id(repo, &command.id).await?;
+pub async fn do_task(repo: &impl Repository, command: DoCommand, config: &Config) -> Result<()> {
+    if command.selection.is_bulk() {
+        return apply_bulk_action(repo, &command.selection, BulkAction::Complete, command.yes, config).await;
+    }
+
+    let task_id = resolve_task_id(repo, &command.selection.ids[0]).await?;
     let result = repo.complete_task(task_id).await;
 
     match result {
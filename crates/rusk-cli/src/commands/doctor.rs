@@ -0,0 +1,35 @@
+use anyhow::Result;
+use chrono::Local;
+use owo_colors::OwoColorize;
+use rusk_core::repository::Repository;
+
+use crate::cli::DoctorCommand;
+
+pub async fn doctor_command(repo: &impl Repository, command: DoctorCommand) -> Result<()> {
+    let orphans = repo.find_orphaned_instances().await?;
+    if orphans.is_empty() {
+        println!("{}", "No problems found.".green());
+        return Ok(());
+    }
+
+    println!(
+        "{} {} occurrence(s) belong to a series that no longer exists:",
+        "Warning:".yellow().bold(),
+        orphans.len()
+    );
+    for task in &orphans {
+        let due = task
+            .due_at
+            .map(|due| due.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "no due date".to_string());
+        println!("  {}  {} ({})", task.id, task.name, due.dimmed());
+    }
+
+    if command.fix {
+        let detached = repo.detach_orphaned_instances().await?;
+        println!("Turned {} orphaned occurrence(s) into regular tasks.", detached.len());
+    } else {
+        println!("{}", "Tip: Use rusk doctor --fix to turn them into regular tasks".blue());
+    }
+    Ok(())
+}
@@ -1,16 +1,29 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
 use dialoguer::Select;
 use owo_colors::OwoColorize;
-use rusk_core::models::{UpdateTaskData, EditScope};
+use rusk_core::models::{BulkAction, UpdateTaskData, EditScope};
 use rusk_core::repository::{Repository, TaskRepository};
 
 use crate::cli::EditCommand;
-use crate::parser::parse_due_date;
+use crate::commands::bulk::apply_bulk_action;
+use crate::config::Config;
+use crate::parser::{parse_date_relative_to_due, parse_due_date, parse_duration};
 use crate::timezone::normalize_timezone_input;
 use crate::util::resolve_task_id;
 
-pub async fn edit_task(repo: &(impl Repository + Sync), command: EditCommand) -> Result<()> {
-    let task_id = resolve_task_id(repo, &command.id).await?;
+pub async fn edit_task(repo: &(impl Repository + Sync), command: EditCommand, config: &Config) -> Result<()> {
+    if command.selection.is_bulk() {
+        // Each task is edited on its own; series-wide edits need a single occurrence to start from
+        if command.scope.is_some_and(|scope| scope != EditScope::ThisOccurrence) {
+            bail!("--scope future and series apply to a single task; edit one occurrence at a time");
+        }
+        let (selection, confirmed) = (command.selection.clone(), command.yes);
+        let data = update_data(repo, command, None).await?;
+        return apply_bulk_action(repo, &selection, BulkAction::Update(Box::new(data)), confirmed, config).await;
+    }
+
+    let task_id = resolve_task_id(repo, &command.selection.ids[0]).await?;
 
     // Check if this task is part of a series and determine scope
     let task = repo.find_task_by_id(task_id).await?
@@ -50,6 +63,25 @@ pub async fn edit_task(repo: &(impl Repository + Sync), command: EditCommand) ->
         EditScope::ThisOccurrence // Not a recurring task
     };
 
+    let update_data = update_data(repo, command, task.due_at).await?;
+    let updated_task = repo.update_task(task_id, update_data, Some(scope)).await?;
+
+    match scope {
+        EditScope::ThisOccurrence => println!("Updated task with ID: {}", updated_task.id),
+        EditScope::ThisAndFuture => println!("Updated series and future occurrences (template task ID: {})", updated_task.id),
+        EditScope::EntireSeries => println!("Updated entire series (template task ID: {})", updated_task.id),
+    }
+
+    Ok(())
+}
+
+/// The changes requested by the command; `current_due_at` anchors offsets
+/// such as "2d before due" when the due date itself is not changed
+async fn update_data(
+    repo: &impl Repository,
+    command: EditCommand,
+    current_due_at: Option<DateTime<Utc>>,
+) -> Result<UpdateTaskData> {
     let description = if command.description_clear {
         Some(None)
     } else {
@@ -94,7 +126,31 @@ pub async fn edit_task(repo: &(impl Repository + Sync), command: EditCommand) ->
 
     let timezone = command.timezone.map(|tz| normalize_timezone_input(&tz).map(Some)).transpose()?;
 
-    let update_data = UpdateTaskData {
+    let remind_before = if command.remind_clear {
+        Some(None)
+    } else {
+        command.remind.as_deref().map(parse_duration).transpose()?.map(Some)
+    };
+
+    let estimate = if command.estimate_clear {
+        Some(None)
+    } else {
+        command.estimate.as_deref().map(parse_duration).transpose()?.map(Some)
+    };
+
+    // Offsets such as "2d before due" follow the new due date when both change
+    let new_due_at = due_at.unwrap_or(current_due_at);
+    let relative_to_due = |date: Option<String>, clear: bool| -> Result<Option<Option<_>>> {
+        if clear {
+            return Ok(Some(None));
+        }
+        Ok(date.as_deref().map(|d| parse_date_relative_to_due(d, new_due_at)).transpose()?.map(Some))
+    };
+    let scheduled_at = relative_to_due(command.scheduled, command.scheduled_clear)?;
+    let wait_until = relative_to_due(command.wait, command.wait_clear)?;
+    let start_at = relative_to_due(command.start, command.start_clear)?;
+
+    Ok(UpdateTaskData {
         name: command.name,
         description,
         due_at,
@@ -116,15 +172,10 @@ pub async fn edit_task(repo: &(impl Repository + Sync), command: EditCommand) ->
         depends_on,
         timezone,
         series_id: None, // Not user-editable for now
-    };
-
-    let updated_task = repo.update_task(task_id, update_data, Some(scope)).await?;
-
-    match scope {
-        EditScope::ThisOccurrence => println!("Updated task with ID: {}", updated_task.id),
-        EditScope::ThisAndFuture => println!("Updated series and future occurrences (template task ID: {})", updated_task.id),
-        EditScope::EntireSeries => println!("Updated entire series (template task ID: {})", updated_task.id),
-    }
-
-    Ok(())
-}
\ No newline at end of file
+        remind_before,
+        estimate,
+        scheduled_at,
+        wait_until,
+        start_at,
+    })
+}
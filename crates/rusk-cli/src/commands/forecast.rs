@@ -0,0 +1,68 @@
+use anyhow::Result;
+use chrono::{Duration, Local};
+use rusk_core::forecast::{collect_forecast_items, group_by_day};
+use rusk_core::repository::Repository;
+
+use crate::cli::ForecastCommand;
+use crate::config::Config;
+use crate::parser::parse_duration;
+use crate::util::local_midnight;
+use crate::views::table::{display_forecast, format_duration, ViewDailyLoad};
+
+pub async fn show_forecast(repo: &(impl Repository + Sync), command: ForecastCommand, config: &Config) -> Result<()> {
+    let capacity = parse_duration(command.capacity.as_deref().unwrap_or(&config.forecast.daily_capacity))?;
+    if capacity <= Duration::zero() {
+        return Err(anyhow::anyhow!("Daily capacity must be positive"));
+    }
+    let capacity_minutes = capacity.num_minutes();
+
+    let first_day = Local::now().date_naive();
+    let start = local_midnight(first_day);
+    let end = local_midnight(first_day + Duration::days(command.days as i64));
+
+    let items = collect_forecast_items(repo, start, end).await?;
+    let loads = group_by_day(items, &Local, first_day, command.days);
+
+    let days: Vec<ViewDailyLoad> = loads
+        .iter()
+        .map(|load| ViewDailyLoad {
+            date: load.date,
+            planned_minutes: load.planned_minutes(),
+            items: load
+                .items
+                .iter()
+                .map(|item| format!("{} ({})", item.name, format_duration(item.estimate_minutes * 60)))
+                .collect(),
+        })
+        .collect();
+
+    println!(
+        "Forecast for the next {} days (capacity {} per day)",
+        command.days,
+        format_duration(capacity_minutes * 60)
+    );
+    display_forecast(&days, capacity_minutes);
+
+    let planned: i64 = days.iter().map(|d| d.planned_minutes).sum();
+    println!(
+        "Planned: {} of {} available",
+        format_duration(planned * 60),
+        format_duration(capacity_minutes * command.days as i64 * 60)
+    );
+
+    let overloaded: Vec<&ViewDailyLoad> = days.iter().filter(|d| d.planned_minutes > capacity_minutes).collect();
+    if overloaded.is_empty() {
+        println!("No overloaded days.");
+    } else {
+        println!("Overloaded days:");
+        for day in overloaded {
+            println!(
+                "  {} over by {}",
+                day.date.format("%a %Y-%m-%d"),
+                format_duration((day.planned_minutes - capacity_minutes) * 60)
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -1,43 +1,152 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use rusk_core::models::{TaskPriority, TaskStatus};
+use rusk_core::query::{Filter, Operator, Query};
 use rusk_core::repository::Repository;
-use crate::cli::ListCommand;
+use rusk_core::urgency::urgency;
+use crate::cli::{ListCommand, ListSort, NextCommand};
 use crate::views::table::{display_tasks, ViewTask};
 use crate::config::Config;
-use crate::query_parser;
+use crate::query_parser::{self, TaskFilter};
 
 pub async fn list_tasks(repo: &impl Repository, command: ListCommand, config: &Config) -> Result<()> {
-    let query_str = if command.query.is_empty() && !config.default_filters.is_empty() {
+    let filter = resolve_query(repo, command.query, config).await?;
+
+    let mut view_tasks = find_tasks(repo, &filter, config).await?;
+    if let Some(sort) = command.sort {
+        sort_tasks(&mut view_tasks, sort);
+    }
+
+    display_tasks(&view_tasks);
+
+    Ok(())
+}
+
+/// Show the most urgent pending tasks that are actionable now: not blocked by
+/// a pending dependency, waiting or scheduled for later
+pub async fn next_tasks(repo: &impl Repository, command: NextCommand, config: &Config) -> Result<()> {
+    let filter = resolve_query(repo, command.query, config).await?;
+
+    let now = Utc::now();
+    let later = |date: Option<DateTime<Utc>>| date.is_some_and(|date| date > now);
+    let mut candidates = find_tasks(repo, &filter, config).await?;
+    candidates.retain(|t| {
+        t.status == TaskStatus::Pending && !t.is_blocked && !later(t.wait_until) && !later(t.scheduled_at)
+    });
+    sort_tasks(&mut candidates, ListSort::Urgency);
+
+    // Templates stand in for their series; the occurrences are the actual work
+    let mut next = Vec::with_capacity(command.limit);
+    for task in candidates {
+        if next.len() == command.limit {
+            break;
+        }
+        if repo.find_series_by_template(task.id).await?.is_none() {
+            next.push(task);
+        }
+    }
+
+    if next.is_empty() {
+        println!("Nothing to do right now.");
+    } else {
+        display_tasks(&next);
+    }
+    Ok(())
+}
+
+/// Parse a filter expression, falling back to the configured default filters
+/// when it is empty, and check that referenced custom statuses exist
+pub async fn resolve_query(repo: &impl Repository, query: String, config: &Config) -> Result<TaskFilter> {
+    let query_str = if query.is_empty() && !config.default_filters.is_empty() {
         config.default_filters.join(" and ")
     } else {
-        command.query
+        query
     };
 
-    let query = query_parser::parse_query(&query_str)?;
+    let mut filter = query_parser::parse_task_filter(&query_str)?;
+
+    let mut workflow_statuses = Vec::new();
+    collect_workflow_statuses(&filter.query, &mut workflow_statuses);
+    for name in workflow_statuses {
+        if repo.find_workflow_status(name).await?.is_none() {
+            bail!("Unknown status '{}'. See 'rusk status list'", name);
+        }
+    }
+
+    filter.query = hide_deferred(filter.query);
+    Ok(filter)
+}
 
-    let tasks = repo.find_tasks_with_details(&query).await?;
+/// Hide waiting and future-scheduled tasks unless the query asks for them
+/// with `is:waiting`, `is:scheduled` or `all`
+fn hide_deferred(mut query: Query) -> Query {
+    if references(&query, &Filter::All) {
+        return query;
+    }
+    for deferred in [Filter::Waiting, Filter::Scheduled] {
+        if !references(&query, &deferred) {
+            query = Query::Binary {
+                op: Operator::And,
+                left: Box::new(query),
+                right: Box::new(Query::Not(Box::new(Query::Filter(deferred)))),
+            };
+        }
+    }
+    query
+}
 
-    let view_tasks: Vec<ViewTask> = tasks
+/// Whether `filter` appears anywhere in the query
+fn references(query: &Query, filter: &Filter) -> bool {
+    match query {
+        Query::Filter(f) => f == filter,
+        Query::Not(inner) => references(inner, filter),
+        Query::Binary { left, right, .. } => references(left, filter) || references(right, filter),
+    }
+}
+
+/// Run a filter, scoring each task's urgency and applying the urgency conditions
+pub async fn find_tasks(repo: &impl Repository, filter: &TaskFilter, config: &Config) -> Result<Vec<ViewTask>> {
+    let coefficients = config.urgency.coefficients();
+    let now = Utc::now();
+
+    Ok(repo
+        .find_tasks_with_details(&filter.query)
+        .await?
         .into_iter()
-        .map(|t| {
-            let tags = t.tags.map_or_else(Vec::new, |s| s.split(',').map(String::from).collect());
-            ViewTask {
-                id: t.id,
-                name: t.name,
-                status: t.status,
-                priority: t.priority,
-                due_at: t.due_at,
-                project_name: t.project_name,
-                tags,
-                depth: t.depth as usize,
-                series_id: t.series_id,
-                is_template: false, // TODO: Determine from series relationship
-                has_exceptions: false, // TODO: Implement exception checking
-                timezone: None, // TODO: Get from series if applicable
-            }
+        .map(|task| {
+            let score = urgency(&task, &coefficients, now);
+            ViewTask { urgency: score, ..ViewTask::from(task) }
         })
-        .collect();
+        .filter(|task| filter.urgency.iter().all(|condition| condition.matches(task.urgency)))
+        .collect())
+}
 
-    display_tasks(&view_tasks);
+/// Sort tasks into a flat list; the hierarchy indentation no longer applies
+fn sort_tasks(tasks: &mut [ViewTask], sort: ListSort) {
+    match sort {
+        ListSort::Urgency => tasks.sort_by(|a, b| b.urgency.total_cmp(&a.urgency)),
+        ListSort::Due => tasks.sort_by_key(|t| (t.due_at.is_none(), t.due_at)),
+        ListSort::Priority => tasks.sort_by_key(|t| match t.priority {
+            TaskPriority::High => 0,
+            TaskPriority::Medium => 1,
+            TaskPriority::Low => 2,
+            TaskPriority::None => 3,
+        }),
+    }
+    for task in tasks {
+        task.depth = 0;
+    }
+}
 
-    Ok(())
-}
\ No newline at end of file
+/// Custom status names referenced by `status:` filters in the query
+fn collect_workflow_statuses<'a>(query: &'a Query, names: &mut Vec<&'a str>) {
+    match query {
+        Query::Filter(Filter::WorkflowStatus(name)) => names.push(name),
+        Query::Filter(_) => {}
+        Query::Not(inner) => collect_workflow_statuses(inner, names),
+        Query::Binary { left, right, .. } => {
+            collect_workflow_statuses(left, names);
+            collect_workflow_statuses(right, names);
+        }
+    }
+}
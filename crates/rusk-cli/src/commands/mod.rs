@@ -2,11 +2,22 @@
 // Each command (e.g., add, list) will be its own submodule.
 
 pub mod add;
+pub mod blueprint;
+pub mod board;
+pub mod bulk;
 pub mod cancel;
+pub mod daemon;
 pub mod delete;
 pub mod r#do;
+pub mod doctor;
 pub mod edit;
+pub mod forecast;
 
 pub mod list;
+pub mod r#move;
+pub mod note;
 pub mod project;
-pub mod recurrence;
\ No newline at end of file
+pub mod recurrence;
+pub mod show;
+pub mod status;
+pub mod time;
\ No newline at end of file
@@ -0,0 +1,22 @@
+use anyhow::{bail, Result};
+use rusk_core::models::BulkAction;
+use rusk_core::repository::Repository;
+
+use crate::cli::MoveCommand;
+use crate::commands::bulk::apply_bulk_action;
+use crate::config::Config;
+use crate::util::resolve_task_id;
+
+pub async fn move_task(repo: &impl Repository, command: MoveCommand, config: &Config) -> Result<()> {
+    let Some((selection, status)) = command.selection() else {
+        bail!("Give the tasks to move, by ID or with --where, and the status to move them to");
+    };
+    if selection.is_bulk() {
+        return apply_bulk_action(repo, &selection, BulkAction::Move(status), command.yes, config).await;
+    }
+
+    let task_id = resolve_task_id(repo, &selection.ids[0]).await?;
+    let task = repo.move_task(task_id, &status).await?;
+    println!("Moved task '{}' to {}", task.name, task.workflow_status);
+    Ok(())
+}
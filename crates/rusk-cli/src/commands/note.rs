@@ -0,0 +1,50 @@
+use anyhow::{bail, Result};
+use chrono::Local;
+use owo_colors::OwoColorize;
+use rusk_core::models::BulkAction;
+use rusk_core::repository::Repository;
+
+use crate::cli::NoteCommand;
+use crate::commands::bulk::apply_bulk_action;
+use crate::config::Config;
+use crate::util::resolve_task_id;
+
+/// Annotate tasks, or show the notes of one task when no text is given
+pub async fn note_command(repo: &impl Repository, command: NoteCommand, config: &Config) -> Result<()> {
+    let (selection, text) = command.selection();
+    if let Some(text) = &text {
+        if selection.is_bulk() {
+            return apply_bulk_action(repo, &selection, BulkAction::Annotate(text.clone()), command.yes, config).await;
+        }
+    }
+    let Some(short_id) = selection.ids.first() else {
+        bail!("Give the text of the note to add to the matching tasks");
+    };
+
+    let task_id = resolve_task_id(repo, short_id).await?;
+    let name = repo
+        .find_task_by_id(task_id)
+        .await?
+        .map_or_else(|| task_id.to_string(), |t| t.name);
+
+    if let Some(text) = text {
+        repo.add_annotation(task_id, &text).await?;
+        println!("Added note to '{}'", name);
+        return Ok(());
+    }
+
+    let annotations = repo.find_annotations(task_id).await?;
+    if annotations.is_empty() {
+        println!("No notes on '{}'.", name);
+        return Ok(());
+    }
+    println!("{}", format!("Notes on '{}'", name).blue().bold());
+    for annotation in annotations {
+        println!(
+            "  {}  {}",
+            annotation.created_at.with_timezone(&Local).format("%Y-%m-%d %H:%M").dimmed(),
+            annotation.text
+        );
+    }
+    Ok(())
+}
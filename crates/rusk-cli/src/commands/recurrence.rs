@@ -1,45 +1,94 @@
-use anyhow::Result;
-use chrono::Utc;
+use anyhow::{bail, Result};
+use chrono::{DateTime, Duration, Month, NaiveDate, NaiveTime, Utc, Weekday};
+use chrono_tz::Tz;
 use comfy_table::Table;
 use dialoguer::Confirm;
 use owo_colors::OwoColorize;
-use rusk_core::models::{NewSeriesException, ExceptionType, UpdateSeriesData};
-use rusk_core::recurrence::RecurrenceManager;
+use serde::Serialize;
+use rusk_core::models::{DstPolicy, NewSeriesException, EditScope, ExceptionType, SeriesStatistics, TaskSeries, TaskStatus, TimeMode, UpdateSeriesData, UpdateTaskData};
+use rusk_core::exception_patterns::{apply_exception_plan, plan_exceptions, OccurrencePattern, PatternAction};
+use rusk_core::query::{Filter, Query};
+use rusk_core::recurrence::{describe_rrule, recurrence_lines, RecurrenceManager};
+use rusk_core::series_habits::{series_habits, HabitStatistics};
+use rusk_core::series_history::{series_timeline, TimelineEvent, TimelineSummary};
+use rusk_core::timezone::resolve_local_time;
 use rusk_core::repository::{Repository, TaskRepository, SeriesRepository, ExceptionRepository};
 
 use crate::cli::{
-    RecurrenceCommand, RecurrenceSubcommand, RecurrenceInfoCommand, RecurrencePreviewCommand,
-    RecurrenceSkipCommand, RecurrenceMoveCommand, RecurrencePauseCommand, 
-    RecurrenceResumeCommand, RecurrenceExceptionsCommand, RecurrenceDuplicateCommand,
-    RecurrenceArchiveCommand, RecurrenceStatsCommand, RecurrenceBulkSkipCommand,
-    RecurrenceRemoveExceptionsCommand, RecurrenceTimezonesCommand,
+    RecurrenceCommand, RecurrenceSubcommand, RecurrenceListCommand, RecurrenceInfoCommand, RecurrencePreviewCommand,
+    RecurrenceSkipCommand, RecurrenceMoveCommand, RecurrenceOverrideCommand, RecurrencePauseCommand, 
+    RecurrenceResumeCommand, RecurrenceSetTimezoneCommand, SeriesSelection, RecurrenceExceptionsCommand, RecurrenceDuplicateCommand,
+    RecurrenceArchiveCommand, RecurrenceStatsCommand, RecurrenceHistoryCommand, RecurrenceBulkSkipCommand,
+    RecurrencePatternCommand, RecurrenceRulesCommand, RecurrenceDstCommand, RecurrenceRemoveExceptionsCommand,
+    RecurrenceTimezonesCommand,
 };
+use crate::commands::add::parse_time_string;
 use crate::parser::parse_due_date;
-use crate::timezone::format_timezone_display;
+use crate::timezone::{format_timezone_display, normalize_timezone_input};
 use crate::util::resolve_task_id;
+use crate::views::table::{display_series, ViewSeries};
 
-pub async fn recurrence_command<R: Repository>(
+pub async fn recurrence_command<R: Repository + Sync>(
     repository: &R,
     command: RecurrenceCommand,
 ) -> Result<()> {
     match command.command {
+        RecurrenceSubcommand::List(cmd) => list_command(repository, cmd).await,
         RecurrenceSubcommand::Info(cmd) => info_command(repository, cmd).await,
         RecurrenceSubcommand::Preview(cmd) => preview_command(repository, cmd).await,
         RecurrenceSubcommand::Skip(cmd) => skip_command(repository, cmd).await,
         RecurrenceSubcommand::Move(cmd) => move_command(repository, cmd).await,
+        RecurrenceSubcommand::Override(cmd) => override_command(repository, cmd).await,
         RecurrenceSubcommand::Pause(cmd) => pause_command(repository, cmd).await,
         RecurrenceSubcommand::Resume(cmd) => resume_command(repository, cmd).await,
+        RecurrenceSubcommand::SetTimezone(cmd) => set_timezone_command(repository, cmd).await,
         RecurrenceSubcommand::Exceptions(cmd) => exceptions_command(repository, cmd).await,
         // Phase 5: Advanced Series Management
         RecurrenceSubcommand::Duplicate(cmd) => duplicate_command(repository, cmd).await,
         RecurrenceSubcommand::Archive(cmd) => archive_command(repository, cmd).await,
         RecurrenceSubcommand::Stats(cmd) => stats_command(repository, cmd).await,
+        RecurrenceSubcommand::History(cmd) => history_command(repository, cmd).await,
         RecurrenceSubcommand::BulkSkip(cmd) => bulk_skip_command(repository, cmd).await,
+        RecurrenceSubcommand::Pattern(cmd) => pattern_command(repository, cmd).await,
+        RecurrenceSubcommand::Rules(cmd) => rules_command(repository, cmd).await,
+        RecurrenceSubcommand::Dst(cmd) => dst_command(repository, cmd).await,
         RecurrenceSubcommand::RemoveExceptions(cmd) => remove_exceptions_command(repository, cmd).await,
         RecurrenceSubcommand::Timezones(cmd) => timezones_command(repository, cmd).await,
     }
 }
 
+async fn list_command<R: Repository>(
+    repository: &R,
+    command: RecurrenceListCommand,
+) -> Result<()> {
+    let series = series_matching(repository, command.query.as_deref().unwrap_or_default()).await?;
+    display_series(&series);
+    Ok(())
+}
+
+/// Series whose template name or rule contains `pattern`, by template name
+async fn series_matching<R: Repository>(repository: &R, pattern: &str) -> Result<Vec<ViewSeries>> {
+    let now = Utc::now();
+    let mut matching = Vec::new();
+    for series in repository.find_series_by_pattern(pattern).await? {
+        let Some(template_task) = repository.find_task_by_id(series.template_task_id).await? else {
+            continue;
+        };
+        let exceptions = repository.find_series_exceptions(series.id).await?;
+        let exception_count = exceptions.len();
+        let next_occurrence = if series.active {
+            RecurrenceManager::new(series.clone(), template_task.clone(), exceptions)?.next_occurrence_after(now)?
+        } else {
+            None
+        };
+        matching.push(ViewSeries { series, name: template_task.name, next_occurrence, exception_count });
+    }
+    matching.sort_by(|a, b| {
+        a.name.to_lowercase().cmp(&b.name.to_lowercase()).then(a.series.created_at.cmp(&b.series.created_at))
+    });
+    Ok(matching)
+}
+
 async fn info_command<R: Repository>(
     repository: &R,
     command: RecurrenceInfoCommand,
@@ -73,6 +122,12 @@ async fn info_command<R: Repository>(
     println!("Template Task: {} ({})", template_task.name.cyan(), template_task.id.yellow());
     println!("RRULE: {}", series.rrule.green());
     println!("Timezone: {}", series.timezone.magenta());
+    if series.time_mode != TimeMode::Zoned {
+        println!("Time mode: {}", series.time_mode.to_string().magenta());
+    }
+    if series.dst_policy != DstPolicy::default() {
+        println!("DST policy: {}", series.dst_policy.to_string().magenta());
+    }
     println!("Active: {}", if series.active { "Yes".green().to_string() } else { "No".red().to_string() });
     println!("Created: {}", series.created_at.format("%Y-%m-%d %H:%M:%S UTC"));
     
@@ -291,6 +346,13 @@ async fn move_command<R: Repository>(
         series_id: None, // This is a standalone moved task
         timezone: None,
         project_name: None,
+        remind_before: None,
+        estimate: template_task.estimate(),
+        scheduled_at: template_task.date_relative_to_due(template_task.scheduled_at, to_date),
+        wait_until: template_task.date_relative_to_due(template_task.wait_until, to_date),
+        start_at: template_task.date_relative_to_due(template_task.start_at, to_date),
+        time_mode: if template_task.all_day { TimeMode::AllDay } else { TimeMode::Zoned },
+        dst_policy: DstPolicy::default(),
     };
     
     let moved_task = repository.add_task(moved_task_data).await?;
@@ -319,11 +381,135 @@ async fn move_command<R: Repository>(
     Ok(())
 }
 
+async fn override_command<R: Repository>(
+    repository: &R,
+    command: RecurrenceOverrideCommand,
+) -> Result<()> {
+    let task_id = resolve_task_id(repository, &command.id).await?;
+    
+    // Find series
+    let task = repository.find_task_by_id(task_id).await?
+        .ok_or_else(|| anyhow::anyhow!("Task not found"))?;
+    
+    let series = if let Some(series_id) = task.series_id {
+        repository.find_series_by_id(series_id).await?
+    } else {
+        repository.find_series_by_template(task_id).await?
+    };
+    
+    let series = series.ok_or_else(|| anyhow::anyhow!("No recurring series found for this task"))?;
+    let tz: Tz = series.timezone.parse()
+        .map_err(|_| anyhow::anyhow!("Invalid series timezone '{}'", series.timezone))?;
+    
+    let template_task = repository.find_task_by_id(series.template_task_id).await?
+        .ok_or_else(|| anyhow::anyhow!("Template task not found"))?;
+    let exceptions = repository.find_series_exceptions(series.id).await?;
+    
+    let date = parse_due_date(&command.on, None)?.date_naive();
+    let on_date = |dt: &DateTime<Utc>| dt.with_timezone(&tz).date_naive() == date;
+    
+    if command.revert {
+        let exception = exceptions.iter()
+            .find(|e| e.exception_type == ExceptionType::Override && on_date(&e.occurrence_dt))
+            .ok_or_else(|| anyhow::anyhow!("No overridden occurrence on {}", date))?;
+        repository.remove_series_exception(series.id, exception.occurrence_dt).await?;
+        
+        println!("{} Occurrence on {} reverted to the series defaults", 
+            "Success:".green().bold(),
+            date
+        );
+        return Ok(());
+    }
+    
+    // Find the occurrence on that day, in the series timezone
+    let manager = RecurrenceManager::new(series.clone(), template_task.clone(), exceptions.clone())?;
+    let window_start = date.and_time(NaiveTime::MIN).and_utc() - Duration::days(1);
+    let occurrence_dt = manager
+        .generate_occurrences_between(window_start, window_start + Duration::days(3))?
+        .into_iter()
+        .map(|o| o.occurrence_dt)
+        .find(on_date)
+        .ok_or_else(|| anyhow::anyhow!("Series has no occurrence on {}", date))?;
+    
+    if let Some(existing) = exceptions.iter().find(|e| e.occurrence_dt == occurrence_dt) {
+        bail!(
+            "Occurrence on {} already has an exception ({}); remove it first with 'rusk recur remove-exceptions'",
+            date, existing.exception_type
+        );
+    }
+    
+    // Start from what the series would create, then apply the overrides
+    let due_at = match &command.at {
+        Some(at) => occurrence_at(&tz, date, parse_time_string(at)?, series.dst_policy)?,
+        None => occurrence_dt,
+    };
+    let mut data = template_task.instance_data(series.id, due_at);
+    data.series_id = None;
+    if let Some(name) = command.name {
+        data.name = name;
+    }
+    if let Some(description) = command.description {
+        data.description = Some(description);
+    }
+    if let Some(priority) = command.priority {
+        data.priority = Some(priority);
+    }
+    if let Some(project) = command.project {
+        data.project_id = None;
+        data.project_name = Some(project);
+    }
+    // Without --tag, the occurrence keeps the tags it already has
+    data.tags = if command.tag.is_empty() {
+        occurrence_tags(repository, &series, occurrence_dt).await?
+    } else {
+        command.tag
+    };
+    
+    let override_task = repository.override_occurrence_with_task(series.id, occurrence_dt, data).await?;
+    
+    println!("{} Occurrence on {} overridden (Task ID: {})", 
+        "Success:".green().bold(),
+        date,
+        override_task.id.yellow()
+    );
+    
+    Ok(())
+}
+
+/// Tags of the instance materialized for the occurrence at `occurrence_dt`,
+/// if any; occurrences not yet materialized have none
+async fn occurrence_tags<R: Repository>(repository: &R, series: &TaskSeries, occurrence_dt: DateTime<Utc>) -> Result<Vec<String>> {
+    let instance = repository
+        .find_materialized_tasks_for_series(series.id, occurrence_dt, occurrence_dt)
+        .await?
+        .into_iter()
+        .find(|t| t.due_at == Some(occurrence_dt));
+    let Some(instance) = instance else {
+        return Ok(Vec::new());
+    };
+    let row = repository
+        .find_tasks_with_details(&Query::Filter(Filter::Id(instance.id)))
+        .await?
+        .into_iter()
+        .next();
+    Ok(row.and_then(|r| r.tags).map_or_else(Vec::new, |t| t.split(',').map(String::from).collect()))
+}
+
+/// `time` on `date` in the series timezone, as UTC, placed by the series'
+/// DST policy if the clocks skip or repeat it
+fn occurrence_at(tz: &Tz, date: NaiveDate, time: NaiveTime, dst_policy: DstPolicy) -> Result<DateTime<Utc>> {
+    resolve_local_time(*tz, date.and_time(time), dst_policy)
+        .ok_or_else(|| anyhow::anyhow!("{} {} does not exist in {}", date, time, tz))
+}
+
 async fn pause_command<R: Repository>(
     repository: &R,
     command: RecurrencePauseCommand,
 ) -> Result<()> {
-    let task_id = resolve_task_id(repository, &command.id).await?;
+    if command.selection.pattern.is_some() {
+        return bulk_series_change(repository, &command.selection, SeriesChange::Pause, command.yes).await;
+    }
+    let task_id = resolve_task_id(repository, command.selection.id.as_deref().unwrap_or_default()).await?;
     
     // Find series
     let task = repository.find_task_by_id(task_id).await?
@@ -343,7 +529,7 @@ async fn pause_command<R: Repository>(
     }
     
     // Confirm action
-    let confirmation = Confirm::new()
+    let confirmation = command.yes || Confirm::new()
         .with_prompt("Pause this series? No new instances will be created until resumed")
         .default(false)
         .interact()?;
@@ -370,7 +556,10 @@ async fn resume_command<R: Repository>(
     repository: &R,
     command: RecurrenceResumeCommand,
 ) -> Result<()> {
-    let task_id = resolve_task_id(repository, &command.id).await?;
+    if command.selection.pattern.is_some() {
+        return bulk_series_change(repository, &command.selection, SeriesChange::Resume, command.yes).await;
+    }
+    let task_id = resolve_task_id(repository, command.selection.id.as_deref().unwrap_or_default()).await?;
     
     // Find series
     let task = repository.find_task_by_id(task_id).await?
@@ -390,7 +579,7 @@ async fn resume_command<R: Repository>(
     }
     
     // Confirm action
-    let confirmation = Confirm::new()
+    let confirmation = command.yes || Confirm::new()
         .with_prompt("Resume this series? New instances will be created automatically")
         .default(true)
         .interact()?;
@@ -413,6 +602,142 @@ async fn resume_command<R: Repository>(
     Ok(())
 }
 
+async fn set_timezone_command<R: Repository>(
+    repository: &R,
+    command: RecurrenceSetTimezoneCommand,
+) -> Result<()> {
+    let change = SeriesChange::Timezone(normalize_timezone_input(&command.timezone)?);
+    if command.selection.pattern.is_some() {
+        return bulk_series_change(repository, &command.selection, change, command.yes).await;
+    }
+    let task_id = resolve_task_id(repository, command.selection.id.as_deref().unwrap_or_default()).await?;
+    
+    // Find series
+    let task = repository.find_task_by_id(task_id).await?
+        .ok_or_else(|| anyhow::anyhow!("Task not found"))?;
+    
+    let series = if let Some(series_id) = task.series_id {
+        repository.find_series_by_id(series_id).await?
+    } else {
+        repository.find_series_by_template(task_id).await?
+    };
+    
+    let series = series.ok_or_else(|| anyhow::anyhow!("No recurring series found for this task"))?;
+    
+    match change.update_for(&series) {
+        Ok(update_data) => {
+            repository.update_series(series.id, update_data).await?;
+            println!("{} Series moved{}", "Success:".green().bold(), change.target());
+        }
+        Err(reason) => println!("{} Series is {}", "Info:".yellow().bold(), reason),
+    }
+    
+    Ok(())
+}
+
+/// What `--where` does to each matching series
+enum SeriesChange {
+    Pause,
+    Resume,
+    Timezone(String),
+}
+
+impl SeriesChange {
+    /// The update for `series`, or why the change leaves it alone
+    fn update_for(&self, series: &TaskSeries) -> Result<UpdateSeriesData, &'static str> {
+        match self {
+            SeriesChange::Pause if !series.active => Err("already paused"),
+            SeriesChange::Pause => Ok(UpdateSeriesData { active: Some(false), ..Default::default() }),
+            SeriesChange::Resume if series.active => Err("already active"),
+            SeriesChange::Resume => Ok(UpdateSeriesData { active: Some(true), ..Default::default() }),
+            SeriesChange::Timezone(_) if series.time_mode != TimeMode::Zoned => {
+                Err("not tied to a timezone (floating or all-day)")
+            }
+            SeriesChange::Timezone(timezone) if *timezone == series.timezone => Err("already in that timezone"),
+            SeriesChange::Timezone(timezone) => {
+                Ok(UpdateSeriesData { timezone: Some(timezone.clone()), ..Default::default() })
+            }
+        }
+    }
+
+    fn verbs(&self) -> (&'static str, &'static str) {
+        match self {
+            SeriesChange::Pause => ("Pause", "Paused"),
+            SeriesChange::Resume => ("Resume", "Resumed"),
+            SeriesChange::Timezone(_) => ("Move", "Moved"),
+        }
+    }
+
+    fn target(&self) -> String {
+        match self {
+            SeriesChange::Timezone(timezone) => format!(" to {}", timezone),
+            _ => String::new(),
+        }
+    }
+}
+
+/// Preview the series matching the selection's pattern, ask for confirmation
+/// unless `confirmed`, and apply `change` to all of them in one transaction
+async fn bulk_series_change<R: Repository>(
+    repository: &R,
+    selection: &SeriesSelection,
+    change: SeriesChange,
+    confirmed: bool,
+) -> Result<()> {
+    let matching = series_matching(repository, selection.pattern.as_deref().unwrap_or_default()).await?;
+    if matching.is_empty() {
+        println!("No series matched.");
+        return Ok(());
+    }
+
+    let mut affected = Vec::new();
+    let mut updates = Vec::new();
+    let mut left_alone: Vec<(&str, usize)> = Vec::new();
+    for item in matching {
+        match change.update_for(&item.series) {
+            Ok(update_data) => {
+                updates.push((item.series.id, update_data));
+                affected.push(item);
+            }
+            Err(reason) => match left_alone.iter_mut().find(|(r, _)| *r == reason) {
+                Some((_, count)) => *count += 1,
+                None => left_alone.push((reason, 1)),
+            },
+        }
+    }
+    for (reason, count) in &left_alone {
+        println!("{} Leaving {} series alone: {}", "Info:".yellow().bold(), count, reason);
+    }
+    if affected.is_empty() {
+        println!("Nothing changed.");
+        return Ok(());
+    }
+
+    let (verb, past) = change.verbs();
+    let count = format!("{} series{}", affected.len(), change.target());
+    display_series(&affected);
+    if selection.dry_run {
+        println!("Dry run: would {} {}.", verb.to_lowercase(), count);
+        return Ok(());
+    }
+
+    if !confirmed {
+        let confirmation = Confirm::new()
+            .with_prompt(format!("{} {}?", verb, count))
+            .default(false)
+            .interact()
+            .unwrap_or(false);
+        if !confirmation {
+            println!("Nothing changed.");
+            return Ok(());
+        }
+    }
+
+    repository.bulk_update_series(updates).await?;
+    println!("{} {}.", past, count);
+    Ok(())
+}
+
 async fn exceptions_command<R: Repository>(
     repository: &R,
     command: RecurrenceExceptionsCommand,
@@ -557,7 +882,7 @@ async fn archive_command<R: Repository>(
     Ok(())
 }
 
-async fn stats_command<R: Repository>(
+async fn stats_command<R: Repository + Sync>(
     repository: &R,
     command: RecurrenceStatsCommand,
 ) -> Result<()> {
@@ -577,6 +902,22 @@ async fn stats_command<R: Repository>(
     
     // Get statistics
     let stats = repository.get_series_statistics(series.id).await?;
+    let habits = if command.detailed || command.json {
+        Some(series_habits(repository, &series, Utc::now()).await?)
+    } else {
+        None
+    };
+    
+    if command.json {
+        #[derive(Serialize)]
+        struct StatsReport<'a> {
+            #[serde(flatten)]
+            statistics: &'a SeriesStatistics,
+            habits: Option<HabitStatistics>,
+        }
+        println!("{}", serde_json::to_string_pretty(&StatsReport { statistics: &stats, habits })?);
+        return Ok(());
+    }
     
     println!("{}", "Series Statistics".blue().bold());
     println!("Series ID: {}", stats.series_id.yellow());
@@ -614,6 +955,9 @@ async fn stats_command<R: Repository>(
     } else {
         println!("  Next Occurrence: None (series ended or paused)");
     }
+    if let Some(hours) = stats.average_completion_time_hours {
+        println!("  Average Time Tracked: {}", crate::views::table::format_duration((hours * 3600.0).round() as i64));
+    }
     println!();
     
     println!("{}", "Health Score:".blue());
@@ -632,6 +976,198 @@ async fn stats_command<R: Repository>(
         }
     );
     
+    if let Some(habits) = habits {
+        println!();
+        print_habits(&habits);
+    }
+    
+    Ok(())
+}
+
+fn format_rate(rate: Option<f64>) -> String {
+    rate.map_or_else(|| "-".to_string(), |rate| format!("{:.1}%", rate * 100.0))
+}
+
+fn print_habits(habits: &HabitStatistics) {
+    println!("{}", "Habit:".blue());
+    println!("  Current Streak: {} on time (longest {})", habits.current_streak, habits.longest_streak);
+    println!("  On-Time Rate: {} ({} of {})", format_rate(habits.on_time_rate), habits.on_time, habits.due);
+    if let Some(hours) = habits.average_lateness_hours {
+        let seconds = (hours * 3600.0).round() as i64;
+        let lateness = match seconds {
+            s if s >= 60 => format!("{} late", crate::views::table::format_duration(s)),
+            s if s <= -60 => format!("{} early", crate::views::table::format_duration(-s)),
+            _ => "on time".to_string(),
+        };
+        println!("  Average Lateness: {}", lateness);
+    }
+    println!();
+    
+    println!("{}", "Completions by Weekday:".blue());
+    for day in &habits.weekdays {
+        let rate = day.completion_rate();
+        let filled = rate.map_or(0, |rate| (rate * 10.0).round() as usize);
+        println!("  {}  {}{}  {:>6} ({}/{})",
+            day.weekday,
+            "█".repeat(filled).green(),
+            "░".repeat(10 - filled).dimmed(),
+            format_rate(rate),
+            day.completed,
+            day.due,
+        );
+    }
+    println!();
+    
+    println!("{}", "Trends:".blue());
+    for window in &habits.trends {
+        let change = match (window.on_time_rate, window.previous_on_time_rate) {
+            (Some(rate), Some(previous)) if rate > previous => format!(", up from {}", format_rate(Some(previous))).green().to_string(),
+            (Some(rate), Some(previous)) if rate < previous => format!(", down from {}", format_rate(Some(previous))).red().to_string(),
+            (Some(_), Some(_)) => ", unchanged".to_string(),
+            _ => String::new(),
+        };
+        println!("  Last {} days: {} on time ({}/{}){}",
+            window.days, format_rate(window.on_time_rate), window.on_time, window.due, change);
+    }
+}
+
+async fn history_command<R: Repository + Sync>(
+    repository: &R,
+    command: RecurrenceHistoryCommand,
+) -> Result<()> {
+    let task_id = resolve_task_id(repository, &command.id).await?;
+    
+    // Find series
+    let task = repository.find_task_by_id(task_id).await?
+        .ok_or_else(|| anyhow::anyhow!("Task not found"))?;
+    
+    let series = if let Some(series_id) = task.series_id {
+        repository.find_series_by_id(series_id).await?
+    } else {
+        repository.find_series_by_template(task_id).await?
+    };
+    
+    let series = series.ok_or_else(|| anyhow::anyhow!("No recurring series found for this task"))?;
+    let template_task = repository.find_task_by_id(series.template_task_id).await?
+        .ok_or_else(|| anyhow::anyhow!("Template task not found"))?;
+    
+    let now = Utc::now();
+    let since = command.since.as_deref().map(|s| parse_due_date(s, None)).transpose()?;
+    let mut timeline = series_timeline(repository, &series, now).await?;
+    timeline.retain(|entry| since.is_none_or(|since| entry.at >= since));
+    let summary = TimelineSummary::new(&timeline);
+    // Materialized instances ahead of now are not history yet
+    timeline.retain(|entry| entry.at <= now || !matches!(entry.event, TimelineEvent::Instance(_)));
+    if command.missed {
+        timeline.retain(|entry| entry.missed);
+    }
+    
+    println!("{}", "Series History".blue().bold());
+    println!("Template Task: {}", template_task.name.cyan());
+    println!();
+    
+    if timeline.is_empty() {
+        println!("No history found for this series");
+        return Ok(());
+    }
+    
+    let format_dt = |dt: DateTime<Utc>| format_timezone_display(dt, &series.timezone)
+        .unwrap_or_else(|_| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string());
+    
+    let mut table = Table::new();
+    table
+        .set_header(vec!["Date/Time", "Event", "Status", "Details"])
+        .load_preset(comfy_table::presets::UTF8_FULL);
+    
+    for entry in &timeline {
+        let (event, details) = match &entry.event {
+            TimelineEvent::Instance(_) => ("Occurrence".to_string(), String::new()),
+            TimelineEvent::Exception { exception, task } => {
+                let event = match exception.exception_type {
+                    ExceptionType::Skip => "Skip",
+                    ExceptionType::Override => "Override",
+                    ExceptionType::Move => "Move",
+                };
+                let mut details = Vec::new();
+                if exception.exception_type == ExceptionType::Move {
+                    if let Some(due_at) = task.as_ref().and_then(|t| t.due_at) {
+                        details.push(format!("moved to {}", format_dt(due_at)));
+                    }
+                }
+                details.extend(exception.notes.clone());
+                (event.to_string(), details.join("; "))
+            }
+            TimelineEvent::Unmaterialized => ("Occurrence".to_string(), "no instance".to_string()),
+            TimelineEvent::RuleChange(change) => {
+                let mut details = Vec::new();
+                if change.old_rrule != change.new_rrule {
+                    details.push(format!("{} → {}", describe_rrule(&change.old_rrule), describe_rrule(&change.new_rrule)));
+                }
+                if change.old_dtstart != change.new_dtstart {
+                    details.push(format!("start {} → {}", format_dt(change.old_dtstart), format_dt(change.new_dtstart)));
+                }
+                if change.old_timezone != change.new_timezone {
+                    details.push(format!("timezone {} → {}", change.old_timezone, change.new_timezone));
+                }
+                ("Rule edit".to_string(), details.join("; "))
+            }
+        };
+        
+        let status = if entry.missed {
+            "missed".to_string()
+        } else if let Some(task) = entry.task() {
+            if task.status == TaskStatus::Pending && task.due_at.is_some_and(|due| due >= now) {
+                "upcoming".to_string()
+            } else {
+                task.workflow_status.clone()
+            }
+        } else {
+            String::new()
+        };
+        
+        let lateness = entry.lateness().map(|lateness| {
+            if lateness > Duration::zero() {
+                format!("{} late", crate::views::table::format_duration(lateness.num_seconds()))
+            } else {
+                "on time".to_string()
+            }
+        });
+        let details = match (lateness, details.is_empty()) {
+            (Some(lateness), true) => lateness,
+            (Some(lateness), false) => format!("{}; {}", lateness, details),
+            (None, _) => details,
+        };
+        
+        table.add_row(vec![format_dt(entry.at), event, status, details]);
+    }
+    
+    println!("{}", table);
+    println!();
+    
+    let mut totals = vec![
+        format!("{} completed", summary.completed),
+        format!("{} late", summary.completed_late),
+        format!("{} missed", summary.missed),
+        format!("{} skipped", summary.skipped),
+    ];
+    if summary.cancelled > 0 {
+        totals.push(format!("{} cancelled", summary.cancelled));
+    }
+    if summary.rule_changes > 0 {
+        totals.push(format!("{} rule edit(s)", summary.rule_changes));
+    }
+    println!("Summary: {}", totals.join(", "));
+    if let Some(average) = summary.average_lateness.filter(|average| *average > Duration::zero()) {
+        println!("Average lateness: {}", crate::views::table::format_duration(average.num_seconds()));
+    }
+    if series.active {
+        let exceptions = repository.find_series_exceptions(series.id).await?;
+        let recurrence_manager = RecurrenceManager::new(series.clone(), template_task, exceptions)?;
+        if let Some(next) = recurrence_manager.next_occurrence_after(now)? {
+            println!("Next occurrence: {}", format_dt(next));
+        }
+    }
+    
     Ok(())
 }
 
@@ -726,6 +1262,307 @@ async fn bulk_skip_command<R: Repository>(
     Ok(())
 }
 
+async fn pattern_command<R: Repository + Sync>(
+    repository: &R,
+    command: RecurrencePatternCommand,
+) -> Result<()> {
+    let task_id = resolve_task_id(repository, &command.id).await?;
+    
+    // Find series
+    let task = repository.find_task_by_id(task_id).await?
+        .ok_or_else(|| anyhow::anyhow!("Task not found"))?;
+    
+    let series = if let Some(series_id) = task.series_id {
+        repository.find_series_by_id(series_id).await?
+    } else {
+        repository.find_series_by_template(task_id).await?
+    };
+    
+    let series = series.ok_or_else(|| anyhow::anyhow!("No recurring series found for this task"))?;
+    
+    let action = match &command.move_to {
+        Some(weekday) => PatternAction::MoveToWeekday(parse_weekday(weekday)?),
+        None => PatternAction::Skip,
+    };
+    let pattern = OccurrencePattern {
+        from: command.from.as_deref().map(|s| parse_due_date(s, None)).transpose()?,
+        until: command.to.as_deref().map(|s| parse_due_date(s, None)).transpose()?,
+        months: command.month.as_deref().map(parse_months).transpose()?.unwrap_or_default(),
+        weekdays: command.weekday.as_deref().map(parse_weekdays).transpose()?.unwrap_or_default(),
+        dates: command.calendar.as_deref().map(read_calendar).transpose()?.unwrap_or_default(),
+        every_nth: command.every_nth,
+    };
+    
+    // Get template task and exceptions for RecurrenceManager
+    let template_task = repository.find_task_by_id(series.template_task_id).await?
+        .ok_or_else(|| anyhow::anyhow!("Template task not found"))?;
+    let exceptions = repository.find_series_exceptions(series.id).await?;
+    
+    let recurrence_manager = RecurrenceManager::new(series.clone(), template_task.clone(), exceptions)?;
+    let plan = plan_exceptions(&recurrence_manager, &pattern, action, Utc::now())?;
+    
+    if plan.is_empty() {
+        println!("No occurrences match the pattern.");
+        return Ok(());
+    }
+    
+    let format_dt = |dt: DateTime<Utc>| format_timezone_display(dt, &series.timezone)
+        .unwrap_or_else(|_| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string());
+    
+    println!("{} ({} matching occurrences)", 
+        "Pattern Preview".blue().bold(), 
+        plan.len()
+    );
+    println!("Task: {}", template_task.name.cyan());
+    println!();
+    
+    for (i, planned) in plan.iter().enumerate() {
+        let change = match planned.move_to {
+            Some(to_dt) => format!("move to {}", format_dt(to_dt)),
+            None => "skip".to_string(),
+        };
+        let status = if planned.occurrence.has_exception {
+            " (EXCEPTION)".yellow().to_string()
+        } else {
+            "".to_string()
+        };
+        
+        println!("  {}. {} → {}{}", i + 1, format_dt(planned.occurrence.occurrence_dt), change, status);
+    }
+    
+    if command.dry_run {
+        println!();
+        println!("Dry run: no exceptions were added.");
+        return Ok(());
+    }
+    
+    // Confirm action
+    if !command.yes {
+        let verb = if command.skip { "Skip" } else { "Move" };
+        let confirmation = Confirm::new()
+            .with_prompt(format!("{} {} occurrence(s)?", verb, plan.len()))
+            .default(false)
+            .interact()?;
+        
+        if !confirmation {
+            println!("Pattern exceptions cancelled.");
+            return Ok(());
+        }
+    }
+    
+    let result = apply_exception_plan(repository, &series, &plan, Utc::now()).await?;
+    
+    println!();
+    println!("{} {} occurrence(s) {}", 
+        "Success:".green().bold(),
+        result.successful_operations,
+        if command.skip { "skipped" } else { "moved" }
+    );
+    if result.failed_operations > 0 {
+        println!("{} {} occurrence(s) left unchanged:", 
+            "Failed:".red().bold(),
+            result.failed_operations
+        );
+        for error in &result.errors {
+            println!("  • {}", error);
+        }
+    }
+    
+    Ok(())
+}
+
+fn parse_weekday(s: &str) -> Result<Weekday> {
+    s.trim().parse::<Weekday>()
+        .map_err(|_| anyhow::anyhow!("Invalid weekday '{}'", s.trim()))
+}
+
+/// Comma-separated weekday names, e.g. "sat,sun"
+fn parse_weekdays(s: &str) -> Result<Vec<Weekday>> {
+    s.split(',').filter(|d| !d.trim().is_empty()).map(parse_weekday).collect()
+}
+
+/// Comma-separated month names, numbers or quarters, e.g. "aug", "10,11,12" or "q4"
+fn parse_months(s: &str) -> Result<Vec<u32>> {
+    let mut months = Vec::new();
+    for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let lower = part.to_lowercase();
+        if let Some(quarter) = lower.strip_prefix('q').and_then(|q| q.parse::<u32>().ok()) {
+            if !(1..=4).contains(&quarter) {
+                bail!("Invalid quarter '{}'", part);
+            }
+            months.extend((quarter - 1) * 3 + 1..=quarter * 3);
+        } else if let Ok(number) = lower.parse::<u32>() {
+            if !(1..=12).contains(&number) {
+                bail!("Invalid month '{}'", part);
+            }
+            months.push(number);
+        } else {
+            let month = lower.parse::<Month>()
+                .map_err(|_| anyhow::anyhow!("Invalid month '{}'", part))?;
+            months.push(month.number_from_month());
+        }
+    }
+    Ok(months)
+}
+
+/// Dates from a calendar file: one YYYY-MM-DD per line, optionally followed
+/// by a label; blank lines and lines starting with '#' are ignored
+fn read_calendar(path: &std::path::Path) -> Result<Vec<NaiveDate>> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Could not read calendar '{}': {}", path.display(), e))?;
+    contents.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let date = line.split_whitespace().next().unwrap_or_default();
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|_| anyhow::anyhow!("Invalid date '{}' in calendar '{}'", date, path.display()))
+        })
+        .collect()
+}
+
+async fn rules_command<R: Repository>(
+    repository: &R,
+    command: RecurrenceRulesCommand,
+) -> Result<()> {
+    let task_id = resolve_task_id(repository, &command.id).await?;
+    
+    // Find series
+    let task = repository.find_task_by_id(task_id).await?
+        .ok_or_else(|| anyhow::anyhow!("Task not found"))?;
+    
+    let series = if let Some(series_id) = task.series_id {
+        repository.find_series_by_id(series_id).await?
+    } else {
+        repository.find_series_by_template(task_id).await?
+    };
+    
+    let series = series.ok_or_else(|| anyhow::anyhow!("No recurring series found for this task"))?;
+    let mut lines = recurrence_lines(&series.rrule);
+    
+    if command.add.is_empty() && command.add_date.is_empty() && command.remove.is_empty() {
+        println!("{}", "Series Rules".blue().bold());
+        println!("{}", describe_rrule(&series.rrule).cyan());
+        println!();
+        for (i, line) in lines.iter().enumerate() {
+            println!("  {}. {}", i + 1, line);
+        }
+        return Ok(());
+    }
+    
+    // Remove from the highest number down so the others keep their numbers
+    let mut remove = command.remove.clone();
+    remove.sort_unstable();
+    remove.dedup();
+    for number in remove.into_iter().rev() {
+        if number == 0 || number > lines.len() {
+            bail!("No rule line {} (the series has {})", number, lines.len());
+        }
+        let removed = lines.remove(number - 1);
+        println!("Removed: {}", removed.red());
+    }
+    
+    for input in &command.add {
+        let added = recurrence_lines(input);
+        if added.is_empty() {
+            bail!("Nothing to add from '{}'; give an RRULE, RDATE, EXRULE or EXDATE line", input);
+        }
+        for line in added {
+            println!("Added: {}", line.green());
+            lines.push(line);
+        }
+    }
+    
+    // One-off dates fall at the series' local time of day
+    let tz: Tz = series.timezone.parse()
+        .map_err(|_| anyhow::anyhow!("Invalid series timezone '{}'", series.timezone))?;
+    let time_of_day = series.dtstart.with_timezone(&tz).time();
+    for date in &command.add_date {
+        let date = parse_due_date(date, None)?.date_naive();
+        let local = date.and_time(time_of_day).format("%Y%m%dT%H%M%S");
+        let line = match series.time_mode {
+            TimeMode::Zoned => format!("RDATE;TZID={}:{}", series.timezone, local),
+            TimeMode::Floating => format!("RDATE:{}", local),
+            TimeMode::AllDay => format!("RDATE;VALUE=DATE:{}", date.format("%Y%m%d")),
+        };
+        println!("Added: {}", line.green());
+        lines.push(line);
+    }
+    
+    if !lines.iter().any(|line| line.starts_with("RRULE") || line.starts_with("RDATE")) {
+        bail!("A series needs at least one RRULE or RDATE line");
+    }
+    let rrule = RecurrenceManager::normalize_series_rrule(&lines.join("\n"), series.dtstart, &series.timezone, series.time_mode)?;
+    
+    // Re-materialize from the next pending instance on, as when editing
+    // this and future occurrences; past instances are kept
+    let now = Utc::now();
+    let next_instance = repository
+        .find_materialized_tasks_for_series(series.id, now, now + Duration::days(3650))
+        .await?
+        .into_iter()
+        .find(|t| t.id != series.template_task_id && t.status == TaskStatus::Pending);
+    match next_instance {
+        Some(instance) => {
+            let data = UpdateTaskData { rrule: Some(Some(rrule.clone())), ..Default::default() };
+            repository.update_task(instance.id, data, Some(EditScope::ThisAndFuture)).await?;
+        }
+        None => {
+            let data = UpdateSeriesData { rrule: Some(rrule.clone()), ..Default::default() };
+            repository.update_series(series.id, data).await?;
+        }
+    }
+    
+    println!("{} Series now repeats {}", 
+        "Success:".green().bold(),
+        describe_rrule(&rrule)
+    );
+    
+    Ok(())
+}
+
+async fn dst_command<R: Repository>(
+    repository: &R,
+    command: RecurrenceDstCommand,
+) -> Result<()> {
+    let task_id = resolve_task_id(repository, &command.id).await?;
+    
+    // Find series
+    let task = repository.find_task_by_id(task_id).await?
+        .ok_or_else(|| anyhow::anyhow!("Task not found"))?;
+    
+    let series = if let Some(series_id) = task.series_id {
+        repository.find_series_by_id(series_id).await?
+    } else {
+        repository.find_series_by_template(task_id).await?
+    };
+    
+    let series = series.ok_or_else(|| anyhow::anyhow!("No recurring series found for this task"))?;
+    
+    if command.gap.is_none() && command.overlap.is_none() {
+        println!("Skipped times (clocks spring forward): {}", series.dst_policy.dst_gap.to_string().cyan());
+        println!("Repeated times (clocks fall back): {}", series.dst_policy.dst_overlap.to_string().cyan());
+        if series.time_mode == TimeMode::AllDay {
+            println!("{} All-day series run in UTC, which has no DST", "Info:".yellow().bold());
+        }
+        return Ok(());
+    }
+    
+    let dst_policy = DstPolicy {
+        dst_gap: command.gap.unwrap_or(series.dst_policy.dst_gap),
+        dst_overlap: command.overlap.unwrap_or(series.dst_policy.dst_overlap),
+    };
+    let data = UpdateSeriesData { dst_policy: Some(dst_policy), ..Default::default() };
+    repository.update_series(series.id, data).await?;
+    
+    println!("{} DST policy set to {}", 
+        "Success:".green().bold(),
+        dst_policy
+    );
+    
+    Ok(())
+}
+
 async fn remove_exceptions_command<R: Repository>(
     repository: &R,
     command: RecurrenceRemoveExceptionsCommand,
@@ -0,0 +1,133 @@
+use anyhow::Result;
+use rusk_core::models::{Task, TaskStatus, TimeMode};
+use rusk_core::query::{Filter, Query};
+use rusk_core::recurrence::{describe_rrule, RecurrenceManager};
+use rusk_core::repository::Repository;
+use rusk_core::urgency::urgency;
+
+use crate::cli::ShowCommand;
+use crate::config::Config;
+use crate::util::resolve_task_id;
+use crate::views::detail::{display_task_detail, ViewHistoryEvent, ViewSeriesDetail, ViewTaskDetail};
+use crate::views::table::format_duration;
+
+/// Number of upcoming occurrences listed for a recurring task
+const NEXT_OCCURRENCES: usize = 3;
+
+/// Show every detail of a single task
+pub async fn show_task(repo: &impl Repository, command: ShowCommand, config: &Config) -> Result<()> {
+    let task_id = resolve_task_id(repo, &command.id).await?;
+    let task = repo
+        .find_task_by_id(task_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Task not found"))?;
+    let row = repo
+        .find_tasks_with_details(&Query::Filter(Filter::Id(task_id)))
+        .await?
+        .into_iter()
+        .next();
+
+    let relations = repo.find_task_relations(task_id).await?;
+    let series = series_detail(repo, &task).await?;
+    let history = history(repo, &task).await?;
+
+    let (project_name, tags, tracked_seconds, is_blocked, score, progress) = match row {
+        Some(row) => {
+            let score = urgency(&row, &config.urgency.coefficients(), repo.clock().now());
+            let tags = row.tags.map_or_else(Vec::new, |t| t.split(',').map(String::from).collect());
+            let progress = (row.subtask_count, row.completed_subtask_count);
+            (row.project_name, tags, row.tracked_seconds, row.is_blocked, score, progress)
+        }
+        None => (None, Vec::new(), 0, false, 0.0, (0, 0)),
+    };
+
+    display_task_detail(&ViewTaskDetail {
+        task,
+        project_name,
+        tags,
+        tracked_seconds,
+        urgency: score,
+        is_blocked,
+        subtask_count: progress.0,
+        completed_subtask_count: progress.1,
+        relations,
+        series,
+        history,
+    });
+    Ok(())
+}
+
+/// Series details for a template or an occurrence, `None` for other tasks
+async fn series_detail(repo: &impl Repository, task: &Task) -> Result<Option<ViewSeriesDetail>> {
+    let series = match task.series_id {
+        Some(series_id) => repo.find_series_by_id(series_id).await?,
+        None => repo.find_series_by_template(task.id).await?,
+    };
+    let Some(series) = series else {
+        return Ok(None);
+    };
+    let Some(template) = repo.find_task_by_id(series.template_task_id).await? else {
+        return Ok(None);
+    };
+
+    let exceptions = repo.find_series_exceptions(series.id).await?;
+    let is_template = template.id == task.id;
+    let affecting: Vec<_> = exceptions
+        .iter()
+        .filter(|e| !is_template && (e.exception_task_id == Some(task.id) || Some(e.occurrence_dt) == task.due_at))
+        .cloned()
+        .collect();
+
+    let next_occurrences = if series.active {
+        let manager = RecurrenceManager::new(series.clone(), template, exceptions)?;
+        manager
+            .upcoming_occurrences(repo.clock().now())
+            .filter(|o| o.is_visible())
+            .map(|o| o.effective_dt)
+            .take(NEXT_OCCURRENCES)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let timezone = match series.time_mode {
+        TimeMode::Floating => repo.local_timezone().name().to_string(),
+        TimeMode::Zoned | TimeMode::AllDay => series.timezone,
+    };
+
+    Ok(Some(ViewSeriesDetail {
+        is_template,
+        rule: describe_rrule(&series.rrule),
+        timezone,
+        time_mode: series.time_mode,
+        active: series.active,
+        next_occurrences,
+        exceptions: affecting,
+    }))
+}
+
+/// Creation, tracked time, notes and completion of a task, oldest first
+async fn history(repo: &impl Repository, task: &Task) -> Result<Vec<ViewHistoryEvent>> {
+    let mut events = vec![ViewHistoryEvent { at: task.created_at, text: "Created".to_string() }];
+
+    for entry in repo.find_time_entries(task.id).await? {
+        let text = match entry.duration_seconds {
+            Some(seconds) => format!("Tracked {}", format_duration(seconds)),
+            None => "Timer started (running)".to_string(),
+        };
+        events.push(ViewHistoryEvent { at: entry.started_at, text });
+    }
+    for annotation in repo.find_annotations(task.id).await? {
+        events.push(ViewHistoryEvent { at: annotation.created_at, text: format!("Note: {}", annotation.text) });
+    }
+    if let Some(completed_at) = task.completed_at {
+        let text = match task.status {
+            TaskStatus::Cancelled => "Cancelled",
+            _ => "Completed",
+        };
+        events.push(ViewHistoryEvent { at: completed_at, text: text.to_string() });
+    }
+
+    events.sort_by_key(|e| e.at);
+    Ok(events)
+}
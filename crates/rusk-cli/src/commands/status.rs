@@ -0,0 +1,61 @@
+use anyhow::Result;
+use rusk_core::repository::Repository;
+use crate::cli::{AddStatusCommand, DeleteStatusCommand, StatusCommand, StatusSubcommand, StatusTransitionCommand};
+
+use crate::views::table::{display_workflow_statuses, ViewWorkflowStatus};
+
+pub async fn status_command(repo: &impl Repository, command: StatusCommand) -> Result<()> {
+    match command.command {
+        StatusSubcommand::List => list_statuses(repo).await,
+        StatusSubcommand::Add(add_command) => add_status(repo, add_command).await,
+        StatusSubcommand::Delete(delete_command) => delete_status(repo, delete_command).await,
+        StatusSubcommand::Allow(transition) => allow_transition(repo, transition).await,
+        StatusSubcommand::Disallow(transition) => disallow_transition(repo, transition).await,
+    }
+}
+
+async fn list_statuses(repo: &impl Repository) -> Result<()> {
+    let statuses = repo.find_workflow_statuses().await?;
+    let transitions = repo.find_workflow_transitions().await?;
+    let view_statuses: Vec<ViewWorkflowStatus> = statuses
+        .into_iter()
+        .map(|s| {
+            let allowed = transitions
+                .iter()
+                .filter(|t| t.from_status == s.name)
+                .map(|t| t.to_status.clone())
+                .collect();
+            ViewWorkflowStatus {
+                name: s.name,
+                category: s.category,
+                allowed,
+            }
+        })
+        .collect();
+    display_workflow_statuses(&view_statuses);
+    Ok(())
+}
+
+async fn add_status(repo: &impl Repository, command: AddStatusCommand) -> Result<()> {
+    let status = repo.add_workflow_status(&command.name, command.category).await?;
+    println!("Added status: {} ({})", status.name, status.category.as_str());
+    Ok(())
+}
+
+async fn delete_status(repo: &impl Repository, command: DeleteStatusCommand) -> Result<()> {
+    repo.delete_workflow_status(&command.name).await?;
+    println!("Status deleted.");
+    Ok(())
+}
+
+async fn allow_transition(repo: &impl Repository, command: StatusTransitionCommand) -> Result<()> {
+    let transition = repo.add_workflow_transition(&command.from, &command.to).await?;
+    println!("Allowed transition: {} -> {}", transition.from_status, transition.to_status);
+    Ok(())
+}
+
+async fn disallow_transition(repo: &impl Repository, command: StatusTransitionCommand) -> Result<()> {
+    repo.remove_workflow_transition(&command.from, &command.to).await?;
+    println!("Removed transition: {} -> {}", command.from, command.to);
+    Ok(())
+}
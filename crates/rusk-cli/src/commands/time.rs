@@ -0,0 +1,134 @@
+use anyhow::{bail, Result};
+use chrono::{Datelike, Duration, Local};
+use rusk_core::models::BulkAction;
+use rusk_core::repository::Repository;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::cli::{StartCommand, TimesheetCommand, TrackCommand};
+use crate::commands::bulk::apply_bulk_action;
+use crate::config::Config;
+use crate::parser::parse_duration;
+use crate::util::{local_midnight, resolve_task_id};
+use crate::views::table::{display_time_totals, format_duration, ViewTimeTotal};
+
+pub async fn start_timer(repo: &impl Repository, command: StartCommand) -> Result<()> {
+    let task_id = resolve_task_id(repo, &command.id).await?;
+    let entry = repo.start_timer(task_id).await?;
+    let name = task_name(repo, entry.task_id).await?;
+    println!("Started timer for '{}'", name);
+    Ok(())
+}
+
+pub async fn stop_timer(repo: &impl Repository) -> Result<()> {
+    let Some(entry) = repo.stop_timer().await? else {
+        println!("No timer is running.");
+        return Ok(());
+    };
+    let name = task_name(repo, entry.task_id).await?;
+    println!(
+        "Stopped timer for '{}' after {} (total {})",
+        name,
+        format_duration(entry.duration_seconds.unwrap_or(0)),
+        format_duration(total_tracked(repo, entry.task_id).await?)
+    );
+    Ok(())
+}
+
+pub async fn track_time(repo: &impl Repository, command: TrackCommand, config: &Config) -> Result<()> {
+    let Some((selection, duration)) = command.selection() else {
+        bail!("Give the tasks to log time on, by ID or with --where, and the time spent");
+    };
+    let duration = parse_duration(&duration)?;
+    if selection.is_bulk() {
+        return apply_bulk_action(repo, &selection, BulkAction::LogTime(duration), command.yes, config).await;
+    }
+
+    let task_id = resolve_task_id(repo, &selection.ids[0]).await?;
+    let entry = repo.log_time(task_id, duration).await?;
+    let name = task_name(repo, task_id).await?;
+    println!(
+        "Logged {} on '{}' (total {})",
+        format_duration(entry.duration_seconds.unwrap_or(0)),
+        name,
+        format_duration(total_tracked(repo, task_id).await?)
+    );
+    Ok(())
+}
+
+pub async fn show_timesheet(repo: &impl Repository, command: TimesheetCommand) -> Result<()> {
+    let today = Local::now().date_naive();
+    let (first_day, days, period) = if command.today {
+        (today, 1, "today")
+    } else {
+        (today - Duration::days(today.weekday().num_days_from_monday() as i64), 7, "this week")
+    };
+    let start = local_midnight(first_day);
+    let end = local_midnight(first_day + Duration::days(days));
+
+    let entries = repo.find_timesheet_entries(start, end).await?;
+    if entries.is_empty() {
+        println!("No time tracked {}.", period);
+    } else {
+        let mut by_project: BTreeMap<String, i64> = BTreeMap::new();
+        let mut by_tag: BTreeMap<String, i64> = BTreeMap::new();
+        for entry in &entries {
+            let project = entry.project_name.clone().unwrap_or_else(|| "(no project)".to_string());
+            *by_project.entry(project).or_default() += entry.duration_seconds;
+
+            // Entries count towards every tag of their task
+            match entry.tags.as_deref() {
+                Some(tags) => {
+                    for tag in tags.split(',') {
+                        *by_tag.entry(tag.to_string()).or_default() += entry.duration_seconds;
+                    }
+                }
+                None => *by_tag.entry("(untagged)".to_string()).or_default() += entry.duration_seconds,
+            }
+        }
+
+        let total: i64 = entries.iter().map(|e| e.duration_seconds).sum();
+        let to_rows = |totals: BTreeMap<String, i64>| {
+            totals
+                .into_iter()
+                .map(|(label, seconds)| ViewTimeTotal { label, seconds })
+                .collect::<Vec<_>>()
+        };
+
+        println!(
+            "Timesheet {} ({} to {})",
+            period,
+            first_day.format("%Y-%m-%d"),
+            (first_day + Duration::days(days - 1)).format("%Y-%m-%d")
+        );
+        display_time_totals("Project", &to_rows(by_project), total);
+        display_time_totals("Tag", &to_rows(by_tag), total);
+        println!("Total: {}", format_duration(total));
+    }
+
+    if let Some(active) = repo.find_active_timer().await? {
+        println!(
+            "Timer running for '{}' since {}",
+            task_name(repo, active.task_id).await?,
+            active.started_at.with_timezone(&Local).format("%H:%M")
+        );
+    }
+
+    Ok(())
+}
+
+async fn task_name(repo: &impl Repository, task_id: Uuid) -> Result<String> {
+    Ok(repo
+        .find_task_by_id(task_id)
+        .await?
+        .map_or_else(|| task_id.to_string(), |t| t.name))
+}
+
+async fn total_tracked(repo: &impl Repository, task_id: Uuid) -> Result<i64> {
+    Ok(repo
+        .find_time_entries(task_id)
+        .await?
+        .iter()
+        .filter_map(|e| e.duration_seconds)
+        .sum())
+}
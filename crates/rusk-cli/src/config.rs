@@ -1,13 +1,30 @@
 use serde::Deserialize;
 use figment::{Figment, providers::{Format, Toml, Env}};
 use chrono_tz::Tz;
+use rusk_core::models::{CompletionPolicy, SubtaskPolicy};
+use rusk_core::urgency::UrgencyCoefficients;
+use std::collections::HashMap;
 use std::str::FromStr;
+use crate::cli::ReminderSinkKind;
+use crate::paths;
 
 #[derive(Deserialize, Debug)]
 pub struct Config {
+    #[serde(default)]
     pub default_filters: Vec<String>,
+    /// Database path or postgres:// URL, overriding the profile's default
+    #[serde(default)]
+    pub database: Option<String>,
     #[serde(default)]
     pub recurrence: MaterializationConfig,
+    #[serde(default)]
+    pub daemon: DaemonSettings,
+    #[serde(default)]
+    pub forecast: ForecastSettings,
+    #[serde(default)]
+    pub urgency: UrgencySettings,
+    #[serde(default)]
+    pub completion: CompletionSettings,
 }
 
 /// Configuration for series materialization and recurrence handling
@@ -40,12 +57,158 @@ impl Default for MaterializationConfig {
     }
 }
 
+/// Configuration for `rusk daemon`
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct DaemonSettings {
+    /// Seconds between daemon polls
+    pub interval_seconds: u64,
+    /// Reminder sinks used when none are given on the command line
+    pub sinks: Vec<ReminderSinkKind>,
+    /// Shell command run by the `command` sink (receives RUSK_* variables)
+    pub command: Option<String>,
+    /// Program used by the `notify` sink
+    pub notify_command: String,
+    /// Drop reminders for tasks overdue by more than this many minutes
+    pub missed_reminder_minutes: i64,
+}
+
+impl Default for DaemonSettings {
+    fn default() -> Self {
+        Self {
+            interval_seconds: 60,
+            sinks: vec![ReminderSinkKind::Stdout],
+            command: None,
+            notify_command: "notify-send".to_string(),
+            missed_reminder_minutes: 60,
+        }
+    }
+}
+
+/// Configuration for `rusk forecast`
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct ForecastSettings {
+    /// Time available for estimated work per day (e.g. "6h")
+    pub daily_capacity: String,
+}
+
+impl Default for ForecastSettings {
+    fn default() -> Self {
+        Self {
+            daily_capacity: "8h".to_string(),
+        }
+    }
+}
+
+/// Urgency coefficients for `rusk next` and the urgency column
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct UrgencySettings {
+    pub priority_high: f64,
+    pub priority_medium: f64,
+    pub priority_low: f64,
+    /// Weight of due date proximity, at its maximum a week past due
+    pub due: f64,
+    /// Bonus for tasks that other pending tasks depend on
+    pub blocking: f64,
+    /// Penalty for tasks waiting on a pending dependency
+    pub blocked: f64,
+    /// Penalty for tasks that are waiting or scheduled for later
+    pub waiting: f64,
+    /// Weight of task age, at its maximum after `age_max_days`
+    pub age: f64,
+    pub age_max_days: f64,
+    /// Bonus for tasks in any project
+    pub has_project: f64,
+    /// Bonus for tagged tasks
+    pub has_tags: f64,
+    /// Extra weight per project name
+    pub projects: HashMap<String, f64>,
+    /// Extra weight per tag
+    pub tags: HashMap<String, f64>,
+}
+
+impl Default for UrgencySettings {
+    fn default() -> Self {
+        let defaults = UrgencyCoefficients::default();
+        Self {
+            priority_high: defaults.priority_high,
+            priority_medium: defaults.priority_medium,
+            priority_low: defaults.priority_low,
+            due: defaults.due,
+            blocking: defaults.blocking,
+            blocked: defaults.blocked,
+            waiting: defaults.waiting,
+            age: defaults.age,
+            age_max_days: defaults.age_max_days,
+            has_project: defaults.has_project,
+            has_tags: defaults.has_tags,
+            projects: defaults.projects,
+            tags: defaults.tags,
+        }
+    }
+}
+
+impl UrgencySettings {
+    pub fn coefficients(&self) -> UrgencyCoefficients {
+        UrgencyCoefficients {
+            priority_high: self.priority_high,
+            priority_medium: self.priority_medium,
+            priority_low: self.priority_low,
+            due: self.due,
+            blocking: self.blocking,
+            blocked: self.blocked,
+            waiting: self.waiting,
+            age: self.age,
+            age_max_days: self.age_max_days,
+            has_project: self.has_project,
+            has_tags: self.has_tags,
+            projects: self.projects.clone(),
+            tags: self.tags.clone(),
+        }
+    }
+}
+
+/// How completing a task affects its subtasks and parent
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct CompletionSettings {
+    /// "allow", "block" or "cascade" when the task has pending subtasks
+    pub subtasks: SubtaskPolicy,
+    /// Complete a parent once its last pending subtask is done
+    pub complete_parent: bool,
+}
+
+impl CompletionSettings {
+    pub fn policy(&self) -> CompletionPolicy {
+        CompletionPolicy {
+            subtasks: self.subtasks,
+            complete_parent: self.complete_parent,
+        }
+    }
+}
+
 impl Config {
-    pub fn new() -> Result<Self, figment::Error> {
-        Figment::new()
-            .merge(Toml::file("config.toml"))
-            .merge(Env::prefixed("RUSK_"))
-            .extract()
+    /// Load the base config file, then the profile's overlay, then `RUSK_*` variables
+    pub fn new(profile: Option<&str>) -> Result<Self, figment::Error> {
+        let mut figment = Figment::new().merge(Toml::file(paths::config_file()));
+        if let Some(profile) = profile {
+            figment = figment.merge(Toml::file(paths::profile_config_file(profile)));
+        }
+        figment.merge(Env::prefixed("RUSK_")).extract()
+    }
+
+    /// Resolve the database to open
+    ///
+    /// Precedence: `--db`, then `RUSK_DATABASE_PATH`, then the `database`
+    /// setting, then the profile's default location.
+    pub fn database_url(&self, db_flag: Option<&str>, profile: Option<&str>) -> String {
+        db_flag
+            .map(str::to_string)
+            .or_else(|| std::env::var("RUSK_DATABASE_PATH").ok())
+            .or_else(|| self.database.clone())
+            .unwrap_or_else(|| paths::database_path(profile).to_string_lossy().into_owned())
     }
 }
 
@@ -1,31 +1,36 @@
 use clap::Parser;
-use dialoguer::Confirm;
 use owo_colors::{OwoColorize, Style};
-use rusk_core::db;
+use rusk_core::db::{self, DbPool};
 use rusk_core::error::CoreError;
-use rusk_core::repository::{Repository, SqliteRepository, TaskRepository};
-use util::resolve_task_id;
+use rusk_core::repository::{PostgresRepository, Repository, SqliteRepository};
 
 mod cli;
 mod commands;
 mod config;
 mod parser;
+mod paths;
 mod query_parser;
 mod timezone;
 mod util;
 mod views;
 
-const DATABASE_URL: &str = "rusk.db";
-
 #[tokio::main]
 async fn main() {
-    let config = config::Config::new().unwrap_or_else(|_| config::Config { 
-        default_filters: vec![], 
-        recurrence: config::MaterializationConfig::default(),
-    });
+    let cli = cli::Cli::parse();
+    let profile = cli.profile.as_deref();
+
+    // Missing config files are skipped; one that does not parse is an error,
+    // since falling back to defaults would open a different database
+    let config = match config::Config::new(profile) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{} Invalid configuration: {}", "Error:".red().bold(), e);
+            std::process::exit(1);
+        }
+    };
     
-    // Allow override of database path for testing
-    let db_path = std::env::var("RUSK_DATABASE_PATH").unwrap_or_else(|_| DATABASE_URL.to_string());
+    // A postgres:// URL selects PostgreSQL, anything else is a SQLite file
+    let db_path = config.database_url(cli.db.as_deref(), profile);
     
     let db_pool = match db::establish_connection(&db_path).await {
         Ok(pool) => pool,
@@ -37,68 +42,80 @@ async fn main() {
     use rusk_core::recurrence::{MaterializationConfig, MaterializationManager};
     
     let materialization_manager = MaterializationManager::new(MaterializationConfig::default());
-    let repository = SqliteRepository::new(db_pool, materialization_manager);
+    // Floating series follow the timezone the user is in right now
+    let local_timezone = timezone::detect_system_timezone().parse().unwrap_or(chrono_tz::Tz::UTC);
 
-    let cli = cli::Cli::parse();
+    // Run the command, then shut the pool down cleanly before exiting
+    let result = match db_pool {
+        DbPool::Sqlite(pool) => {
+            let repository = SqliteRepository::new(pool, materialization_manager)
+                .with_completion_policy(config.completion.policy())
+                .with_local_timezone(local_timezone);
+            let result = run(&repository, cli, &config).await;
+            repository.close().await;
+            result
+        }
+        DbPool::Postgres(pool) => {
+            let repository = PostgresRepository::new(pool, materialization_manager)
+                .with_completion_policy(config.completion.policy())
+                .with_local_timezone(local_timezone);
+            let result = run(&repository, cli, &config).await;
+            repository.close().await;
+            result
+        }
+    };
+
+    if let Err(e) = result {
+        handle_error(e);
+    }
+}
 
-    let result = match cli.command {
-        cli::Commands::Add(command) => commands::add::add_task(&repository, command).await,
+async fn run(repository: &(impl Repository + Sync), cli: cli::Cli, config: &config::Config) -> anyhow::Result<()> {
+    match cli.command {
+        cli::Commands::Add(command) => commands::add::add_task(repository, command).await,
         cli::Commands::List(command) => {
-            commands::list::list_tasks(&repository, command, &config).await
+            commands::list::list_tasks(repository, command, config).await
+        }
+        cli::Commands::Show(command) => commands::show::show_task(repository, command, config).await,
+        cli::Commands::Next(command) => commands::list::next_tasks(repository, command, config).await,
+        cli::Commands::Board(command) => {
+            commands::board::show_board(repository, command, config).await
         }
         cli::Commands::Delete(command) => {
-            let task_id = match resolve_task_id(&repository, &command.id).await {
-                Ok(id) => id,
-                Err(e) => {
-                    handle_error(e.into());
-                    return;
-                }
-            };
-            let task = match repository.find_task_by_id(task_id).await {
-                Ok(Some(t)) => t,
-                Ok(None) => {
-                    let error_style = Style::new().red().bold();
-                    eprintln!("{} Task with ID '{}' not found.", "Error:".style(error_style), task_id);
-                    return;
-                }
-                Err(e) => {
-                    handle_error(e.into());
-                    return;
-                }
-            };
-
-            if !command.force {
-                let confirmation = Confirm::new()
-                    .with_prompt(format!(
-                        "Are you sure you want to delete task '{}'?",
-                        task.name
-                    ))
-                    .default(false)
-                    .interact()
-                    .unwrap_or(false);
-
-                if !confirmation {
-                    println!("Deletion cancelled.");
-                    return;
-                }
-            }
-            commands::delete::delete_task(&repository, task_id).await
+            commands::delete::delete_task(repository, command, config).await
         }
-        cli::Commands::Do(command) => commands::r#do::do_task(&repository, command).await,
+        cli::Commands::Do(command) => commands::r#do::do_task(repository, command, config).await,
         cli::Commands::Cancel(command) => {
-            commands::cancel::cancel_task(&repository, command).await
+            commands::cancel::cancel_task(repository, command, config).await
+        }
+        cli::Commands::Move(command) => commands::r#move::move_task(repository, command, config).await,
+        cli::Commands::Status(command) => {
+            commands::status::status_command(repository, command).await
         }
-        cli::Commands::Edit(command) => commands::edit::edit_task(&repository, command).await,
+        cli::Commands::Note(command) => commands::note::note_command(repository, command, config).await,
+        cli::Commands::Start(command) => commands::time::start_timer(repository, command).await,
+        cli::Commands::Stop => commands::time::stop_timer(repository).await,
+        cli::Commands::Track(command) => commands::time::track_time(repository, command, config).await,
+        cli::Commands::Timesheet(command) => commands::time::show_timesheet(repository, command).await,
+        cli::Commands::Forecast(command) => {
+            commands::forecast::show_forecast(repository, command, config).await
+        }
+        cli::Commands::Edit(command) => commands::edit::edit_task(repository, command, config).await,
         cli::Commands::Project(command) => {
-            commands::project::project_command(&repository, command).await
+            commands::project::project_command(repository, command).await
         }
         cli::Commands::Recur(command) => {
-            commands::recurrence::recurrence_command(&repository, command).await
+            commands::recurrence::recurrence_command(repository, command).await
+        }
+        cli::Commands::Blueprint(command) => {
+            commands::blueprint::blueprint_command(repository, command).await
+        }
+        cli::Commands::Daemon(command) => {
+            commands::daemon::run_daemon(repository, command, config).await
+        }
+        cli::Commands::Doctor(command) => {
+            commands::doctor::doctor_command(repository, command).await
         }
-    };
-
-    if let Err(e) = result {
-        handle_error(e);
     }
 }
 
@@ -125,6 +142,15 @@ fn handle_error(err: anyhow::Error) {
                 eprintln!("{} Complete the blocking task first, or remove the dependency", "Tip:".style(tip_style));
                 eprintln!("{} Remove dependency: rusk edit <task-id> --depends-on-clear", "Example:".style(example_style));
             }
+            CoreError::PendingSubtasks(s) => {
+                eprintln!(
+                    "{} Task has pending subtasks: {}",
+                    "Error:".style(error_style),
+                    s.yellow()
+                );
+                eprintln!("{} Complete or cancel the subtasks first", "Tip:".style(tip_style));
+                eprintln!("{} Change the policy with 'subtasks' under [completion] in config.toml", "Tip:".style(tip_style));
+            }
             CoreError::AmbiguousId(tasks) => {
                 eprintln!("{}", "Error: Multiple tasks match that ID prefix.".style(error_style));
                 eprintln!("\n{} Which task did you mean?", "Please specify:".style(suggestion_style));
@@ -139,6 +165,15 @@ fn handle_error(err: anyhow::Error) {
                 eprintln!("{} Use --help with any command for detailed usage information", "Tip:".style(tip_style));
                 eprintln!("{} rusk add --help", "Example:".style(example_style));
             }
+            CoreError::InvalidTransition(s) => {
+                eprintln!("{} Status transition not allowed: {}", "Error:".style(error_style), s);
+                eprintln!("{} See the allowed transitions with: rusk status list", "Tip:".style(tip_style));
+                eprintln!("{} rusk status allow <from> <to>", "Example:".style(example_style));
+            }
+            CoreError::TimerRunning(s) => {
+                eprintln!("{} A timer is already running for '{}'", "Error:".style(error_style), s.yellow());
+                eprintln!("{} Stop it first with: rusk stop", "Tip:".style(tip_style));
+            }
             CoreError::InvalidTimezone(s) => {
                 eprintln!("{} Invalid timezone: {}", "Error:".style(error_style), s);
                 eprintln!("{} Use standard IANA timezone names (not abbreviations)", "Tip:".style(tip_style));
@@ -181,7 +216,7 @@ fn handle_error(err: anyhow::Error) {
             CoreError::Database(e) => {
                 eprintln!("{} Database error: {}", "Error:".style(error_style), e);
                 eprintln!("{} This may indicate database corruption or permission issues", "Tip:".style(tip_style));
-                eprintln!("{} Check file permissions on rusk.db", "Tip:".style(tip_style));
+                eprintln!("{} Check permissions on the database file (see --db and --profile)", "Tip:".style(tip_style));
                 eprintln!("{} Consider backing up and reinitializing if problems persist", "Tip:".style(tip_style));
             }
             _ => {
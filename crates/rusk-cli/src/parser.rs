@@ -1,5 +1,5 @@
 use anyhow::Result;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use chrono_english::{parse_date_string, Dialect};
 
 pub fn parse_due_date(date_str: &str, _timezone: Option<&str>) -> Result<DateTime<Utc>> {
@@ -7,4 +7,81 @@ pub fn parse_due_date(date_str: &str, _timezone: Option<&str>) -> Result<DateTim
     // Future enhancement: use timezone parameter for localized parsing
     parse_date_string(date_str, Utc::now(), Dialect::Us)
         .map_err(|e| anyhow::anyhow!("Failed to parse due date '{}': {}", date_str, e))
-}
\ No newline at end of file
+}
+
+/// Parse a scheduling date (scheduled, wait or start): natural language, or
+/// relative to the task's due date such as "2d before due" or "1h after due"
+pub fn parse_date_relative_to_due(input: &str, due_at: Option<DateTime<Utc>>) -> Result<DateTime<Utc>> {
+    match split_relative_to_due(input) {
+        Some((offset, sign)) => {
+            let due_at = due_at.ok_or_else(|| {
+                anyhow::anyhow!("'{}' is relative to the due date, but the task has no due date", input)
+            })?;
+            Ok(due_at + parse_duration(&offset)? * sign)
+        }
+        None => parse_date_string(input, Utc::now(), Dialect::Us)
+            .map_err(|e| anyhow::anyhow!("Failed to parse date '{}': {}", input, e)),
+    }
+}
+
+/// Whether `input` is a date relative to the due date, such as "2d before due"
+pub fn is_relative_to_due(input: &str) -> bool {
+    split_relative_to_due(input).is_some()
+}
+
+/// The offset and its sign (-1 before due, 1 after) of a date relative to due
+fn split_relative_to_due(input: &str) -> Option<(String, i32)> {
+    let trimmed = input.trim().to_lowercase();
+    [(" before due", -1), (" after due", 1)]
+        .into_iter()
+        .find_map(|(suffix, sign)| trimmed.strip_suffix(suffix).map(|offset| (offset.to_string(), sign)))
+}
+
+/// Parse a compact duration such as "30m", "1h30m", "2d" or "1w".
+/// A bare number is interpreted as minutes.
+pub fn parse_duration(input: &str) -> Result<Duration> {
+    let trimmed = input.trim().to_lowercase();
+    if trimmed.is_empty() {
+        return Err(anyhow::anyhow!("Duration cannot be empty"));
+    }
+    if let Ok(minutes) = trimmed.parse::<i64>() {
+        return Ok(Duration::minutes(minutes));
+    }
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for c in trimmed.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        if c.is_whitespace() {
+            continue;
+        }
+        let value: i64 = number.parse().map_err(|_| {
+            anyhow::anyhow!("Invalid duration '{}'. Examples: '30m', '1h30m', '2d'", input)
+        })?;
+        total += match c {
+            'w' => Duration::weeks(value),
+            'd' => Duration::days(value),
+            'h' => Duration::hours(value),
+            'm' => Duration::minutes(value),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Invalid duration unit '{}' in '{}'. Use w, d, h or m",
+                    c,
+                    input
+                ))
+            }
+        };
+        number.clear();
+    }
+    if !number.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Missing unit after '{}' in '{}'. Examples: '30m', '1h30m'",
+            number,
+            input
+        ));
+    }
+    Ok(total)
+}
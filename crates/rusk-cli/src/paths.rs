@@ -0,0 +1,84 @@
+//! Locations of rusk's database and configuration files
+//!
+//! Paths follow the XDG base directory spec: the database lives under
+//! `$XDG_DATA_HOME/rusk` (default `~/.local/share/rusk`) and configuration
+//! under `$XDG_CONFIG_HOME/rusk` (default `~/.config/rusk`). Named profiles get
+//! their own database and an optional config overlay:
+//!
+//! ```text
+//! ~/.local/share/rusk/rusk.db                 default database
+//! ~/.local/share/rusk/profiles/<name>/rusk.db profile database
+//! ~/.config/rusk/config.toml                  base configuration
+//! ~/.config/rusk/profiles/<name>.toml         profile overlay
+//! ```
+
+use std::path::PathBuf;
+
+const APP_DIR: &str = "rusk";
+const DATABASE_FILE: &str = "rusk.db";
+const CONFIG_FILE: &str = "config.toml";
+const PROFILES_DIR: &str = "profiles";
+
+/// Resolves an XDG base directory, falling back to `$HOME/<fallback>`
+///
+/// Per the spec, unset, empty and relative values are ignored.
+fn xdg_base_dir(var: &str, fallback: &str) -> PathBuf {
+    if let Some(dir) = std::env::var_os(var).map(PathBuf::from) {
+        if dir.is_absolute() {
+            return dir;
+        }
+    }
+    home_dir().join(fallback)
+}
+
+fn home_dir() -> PathBuf {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// Directory holding rusk's databases
+pub fn data_dir() -> PathBuf {
+    xdg_base_dir("XDG_DATA_HOME", ".local/share").join(APP_DIR)
+}
+
+/// Directory holding rusk's configuration files
+pub fn config_dir() -> PathBuf {
+    xdg_base_dir("XDG_CONFIG_HOME", ".config").join(APP_DIR)
+}
+
+/// Default SQLite database for a profile, or the main database without one
+pub fn database_path(profile: Option<&str>) -> PathBuf {
+    match profile {
+        Some(name) => data_dir().join(PROFILES_DIR).join(name).join(DATABASE_FILE),
+        None => data_dir().join(DATABASE_FILE),
+    }
+}
+
+/// Base configuration file shared by every profile
+pub fn config_file() -> PathBuf {
+    config_dir().join(CONFIG_FILE)
+}
+
+/// Configuration overlay merged on top of the base file for a profile
+pub fn profile_config_file(profile: &str) -> PathBuf {
+    config_dir().join(PROFILES_DIR).join(format!("{}.toml", profile))
+}
+
+/// Validates a profile name given on the command line
+///
+/// Names become path components, so only ASCII letters, digits, `-` and `_`
+/// are accepted.
+pub fn parse_profile_name(name: &str) -> Result<String, String> {
+    if name.is_empty() {
+        return Err("Profile name cannot be empty".to_string());
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(format!(
+            "Invalid profile name '{}'. Use letters, digits, '-' and '_' only",
+            name
+        ));
+    }
+    Ok(name.to_string())
+}
@@ -10,6 +10,8 @@ use chrono_english::{parse_date_string, Dialect};
 
 use rusk_core::models::{TaskPriority, TaskStatus};
 use rusk_core::query::{DueDate, Filter, Operator, Query, TagFilter, TextFilter};
+use rusk_core::urgency::{Comparison, UrgencyCondition};
+use rusk_core::workflow::normalize_status_name;
 
 #[derive(Parser)]
 #[grammar = "filter.pest"]
@@ -31,7 +33,7 @@ lazy_static::lazy_static! {
 #[derive(Error, Debug)]
 pub enum QueryParseError {
     #[error("Pest parsing error: {0}")]
-    Pest(#[from] pest::error::Error<Rule>),
+    Pest(#[source] Box<pest::error::Error<Rule>>),
     #[error("Invalid filter expression: {0}")]
     InvalidFilter(String),
     #[error("Unknown rule: {0:?}")]
@@ -46,6 +48,23 @@ pub enum QueryParseError {
     DateParseError(String),
 }
 
+// Boxed, as pest errors are large enough to bloat every parse result
+impl From<pest::error::Error<Rule>> for QueryParseError {
+    fn from(e: pest::error::Error<Rule>) -> Self {
+        QueryParseError::Pest(Box::new(e))
+    }
+}
+
+/// Map a `status:` value to a base category filter, or to a workflow status
+/// filter for custom status names
+fn parse_status_filter(value: &str) -> Result<Filter, QueryParseError> {
+    if let Ok(status) = TaskStatus::from_str(value) {
+        return Ok(Filter::Status(status));
+    }
+    let name = normalize_status_name(value).map_err(|_| QueryParseError::InvalidStatus(value.to_string()))?;
+    Ok(Filter::WorkflowStatus(name))
+}
+
 /// Parse a date value from a pest pair, supporting various date formats
 fn parse_date_value(pair: Pair<Rule>) -> Result<DueDate, QueryParseError> {
     let input = pair.as_str();
@@ -225,6 +244,16 @@ fn parse_tag_filter(pair: Pair<Rule>) -> Result<TagFilter, QueryParseError> {
     }
 }
 
+/// Parse an `is:` filter such as `is:waiting`
+fn parse_is_filter(pair: Pair<Rule>) -> Result<Query, QueryParseError> {
+    let value = pair.into_inner().next().unwrap().as_str();
+    match value {
+        "waiting" => Ok(Query::Filter(Filter::Waiting)),
+        "scheduled" => Ok(Query::Filter(Filter::Scheduled)),
+        _ => Err(QueryParseError::InvalidFilter(format!("Unknown state: is:{}", value))),
+    }
+}
+
 fn build_ast(pairs: Pairs<Rule>) -> Result<Query, QueryParseError> {
     PRATT_PARSER
         .map_primary(|primary| match primary.as_rule() {
@@ -267,6 +296,8 @@ fn build_ast(pairs: Pairs<Rule>) -> Result<Query, QueryParseError> {
                             "name"
                         } else if full_text.starts_with("description:") {
                             "description"
+                        } else if full_text.starts_with("note:") {
+                            "note"
                         } else {
                             return Err(QueryParseError::InvalidFilter(format!("Unknown text field in: {}", full_text)));
                         };
@@ -286,6 +317,7 @@ fn build_ast(pairs: Pairs<Rule>) -> Result<Query, QueryParseError> {
                         let filter = match field_key {
                             "name" => Filter::Name(text_filter),
                             "description" => Filter::Description(text_filter),
+                            "note" => Filter::Note(text_filter),
                             _ => return Err(QueryParseError::InvalidFilter(format!(
                                 "Unknown text field: {}", field_key
                             ))),
@@ -293,6 +325,8 @@ fn build_ast(pairs: Pairs<Rule>) -> Result<Query, QueryParseError> {
                         
                         Ok(Query::Filter(filter))
                     }
+                    Rule::is_filter => parse_is_filter(inner_rule),
+                    Rule::all_filter => Ok(Query::Filter(Filter::All)),
                     Rule::basic_filter => {
                         let mut basic_inner = inner_rule.into_inner();
                         let key = basic_inner.next().unwrap().as_str();
@@ -301,9 +335,7 @@ fn build_ast(pairs: Pairs<Rule>) -> Result<Query, QueryParseError> {
                         let filter = match key {
                             "project" => Filter::Project(value.to_string()),
                             "status" => {
-                                let status = TaskStatus::from_str(value)
-                                    .map_err(|_| QueryParseError::InvalidStatus(value.to_string()))?;
-                                Filter::Status(status)
+                                parse_status_filter(value)?
                             }
                             "priority" => {
                                 let priority = TaskPriority::from_str(value)
@@ -365,6 +397,8 @@ fn build_ast(pairs: Pairs<Rule>) -> Result<Query, QueryParseError> {
                                     "name"
                                 } else if full_text.starts_with("description:") {
                                     "description"
+                                } else if full_text.starts_with("note:") {
+                                    "note"
                                 } else {
                                     return Err(QueryParseError::InvalidFilter(format!("Unknown text field in: {}", full_text)));
                                 };
@@ -384,6 +418,7 @@ fn build_ast(pairs: Pairs<Rule>) -> Result<Query, QueryParseError> {
                                 let filter = match field_key {
                                     "name" => Filter::Name(text_filter),
                                     "description" => Filter::Description(text_filter),
+                                    "note" => Filter::Note(text_filter),
                                     _ => return Err(QueryParseError::InvalidFilter(format!(
                                         "Unknown text field: {}", field_key
                                     ))),
@@ -391,6 +426,8 @@ fn build_ast(pairs: Pairs<Rule>) -> Result<Query, QueryParseError> {
                                 
                                 Ok(Query::Filter(filter))
                             }
+                            Rule::is_filter => parse_is_filter(filter_inner),
+                            Rule::all_filter => Ok(Query::Filter(Filter::All)),
                             Rule::basic_filter => {
                                 let mut basic_inner = filter_inner.into_inner();
                                 let key = basic_inner.next().unwrap().as_str();
@@ -399,9 +436,7 @@ fn build_ast(pairs: Pairs<Rule>) -> Result<Query, QueryParseError> {
                                 let filter = match key {
                                     "project" => Filter::Project(value.to_string()),
                                     "status" => {
-                                        let status = TaskStatus::from_str(value)
-                                            .map_err(|_| QueryParseError::InvalidStatus(value.to_string()))?;
-                                        Filter::Status(status)
+                                        parse_status_filter(value)?
                                     }
                                     "priority" => {
                                         let priority = TaskPriority::from_str(value)
@@ -492,10 +527,98 @@ pub fn parse_query(input: &str) -> Result<Query, QueryParseError> {
     build_ast(expression_pair.into_inner())
 }
 
+/// A filter expression split into the query the repository evaluates and the
+/// `urgency:` conditions applied to its results afterwards
+///
+/// Urgency depends on the configured coefficients, so the repository cannot
+/// evaluate it. Urgency conditions may therefore only be joined to the rest
+/// of the expression with a top-level `and`.
+#[derive(Debug, Clone)]
+pub struct TaskFilter {
+    pub query: Query,
+    pub urgency: Vec<UrgencyCondition>,
+}
+
+pub fn parse_task_filter(input: &str) -> Result<TaskFilter, QueryParseError> {
+    let trimmed_input = input.trim();
+    let parsed = if trimmed_input.is_empty() {
+        None
+    } else {
+        Some(FilterParser::parse(Rule::filter_query, trimmed_input)?)
+    };
+    let Some(parsed) = parsed.filter(|p| p.clone().flatten().any(|p| p.as_rule() == Rule::urgency_filter)) else {
+        return Ok(TaskFilter { query: parse_query(trimmed_input)?, urgency: Vec::new() });
+    };
+
+    let expression_pair = parsed
+        .into_iter()
+        .next()
+        .and_then(|main_pair| main_pair.into_inner().next())
+        .ok_or_else(|| QueryParseError::InvalidFilter("No expression found".to_string()))?;
+
+    let misplaced = || {
+        QueryParseError::InvalidFilter(
+            "urgency filters can only be combined with other filters using 'and'".to_string(),
+        )
+    };
+    let mut urgency = Vec::new();
+    let mut rest = Vec::new();
+    for pair in expression_pair.into_inner() {
+        match pair.as_rule() {
+            Rule::and => {}
+            Rule::or => return Err(misplaced()),
+            Rule::term => {
+                let filter = pair
+                    .clone()
+                    .into_inner()
+                    .next()
+                    .filter(|inner| inner.as_rule() == Rule::filter_expression)
+                    .and_then(|inner| inner.into_inner().next())
+                    .filter(|inner| inner.as_rule() == Rule::urgency_filter);
+                match filter {
+                    Some(filter) => urgency.push(parse_urgency_condition(filter)?),
+                    None if pair.clone().into_inner().flatten().any(|p| p.as_rule() == Rule::urgency_filter) => {
+                        return Err(misplaced());
+                    }
+                    None => rest.push(pair.as_str()),
+                }
+            }
+            rule => return Err(QueryParseError::UnknownRule(rule)),
+        }
+    }
+
+    Ok(TaskFilter {
+        query: parse_query(&rest.join(" and "))?,
+        urgency,
+    })
+}
+
+/// Parse an `urgency:<op><number>` filter
+fn parse_urgency_condition(pair: Pair<Rule>) -> Result<UrgencyCondition, QueryParseError> {
+    let mut inner = pair.into_inner();
+    let op = inner.next().unwrap().as_str();
+    let value = inner.next().unwrap().as_str();
+
+    let comparison = match op {
+        ">" => Comparison::Greater,
+        ">=" => Comparison::GreaterOrEqual,
+        "<" => Comparison::Less,
+        "<=" => Comparison::LessOrEqual,
+        "=" => Comparison::Equal,
+        _ => return Err(QueryParseError::InvalidFilter(format!("Unknown urgency operator: {}", op))),
+    };
+    let value = value
+        .parse()
+        .map_err(|_| QueryParseError::InvalidFilter(format!("Invalid urgency value: {}", value)))?;
+    Ok(UrgencyCondition { comparison, value })
+}
+
 fn parse_simple_filter(filter_pair: Pair<Rule>) -> Result<Query, QueryParseError> {
     let inner_rule = filter_pair.into_inner().next().unwrap();
     
     match inner_rule.as_rule() {
+        Rule::is_filter => parse_is_filter(inner_rule),
+        Rule::all_filter => Ok(Query::Filter(Filter::All)),
         Rule::basic_filter => {
             let mut basic_inner = inner_rule.into_inner();
             let key = basic_inner.next().unwrap().as_str();
@@ -504,9 +627,7 @@ fn parse_simple_filter(filter_pair: Pair<Rule>) -> Result<Query, QueryParseError
             let filter = match key {
                 "project" => Filter::Project(value.to_string()),
                 "status" => {
-                    let status = TaskStatus::from_str(value)
-                        .map_err(|_| QueryParseError::InvalidStatus(value.to_string()))?;
-                    Filter::Status(status)
+                    parse_status_filter(value)?
                 }
                 "priority" => {
                     let priority = TaskPriority::from_str(value)
@@ -559,6 +680,8 @@ fn parse_simple_filter(filter_pair: Pair<Rule>) -> Result<Query, QueryParseError
                 "name"
             } else if full_text.starts_with("description:") {
                 "description"
+            } else if full_text.starts_with("note:") {
+                "note"
             } else {
                 return Err(QueryParseError::InvalidFilter(format!("Unknown text field in: {}", full_text)));
             };
@@ -578,6 +701,7 @@ fn parse_simple_filter(filter_pair: Pair<Rule>) -> Result<Query, QueryParseError
             let filter = match field_key {
                 "name" => Filter::Name(text_filter),
                 "description" => Filter::Description(text_filter),
+                "note" => Filter::Note(text_filter),
                 _ => return Err(QueryParseError::InvalidFilter(format!(
                     "Unknown text field: {}", field_key
                 ))),
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeZone, Utc};
 use rusk_core::error::CoreError;
 use rusk_core::repository::Repository;
 use uuid::Uuid;
@@ -23,4 +24,14 @@ pub async fn resolve_task_id(repo: &impl Repository, short_id: &str) -> Result<U
             .collect();
         Err(CoreError::AmbiguousId(task_info))
     }
-}
\ No newline at end of file
+}
+
+/// Start of `date` in the local timezone, as UTC
+pub fn local_midnight(date: NaiveDate) -> DateTime<Utc> {
+    let midnight = date.and_time(NaiveTime::MIN);
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .unwrap_or_else(|| Utc.from_utc_datetime(&midnight).with_timezone(&Local))
+        .with_timezone(&Utc)
+}
@@ -0,0 +1,64 @@
+use chrono::Utc;
+use chrono_humanize::Humanize;
+use comfy_table::{Attribute, Cell, Color, Row, Table};
+use rusk_core::models::TaskStatus;
+
+use crate::views::table::{priority_style, status_style, ViewTask};
+
+/// One board column and the cards in it
+#[derive(Debug, Clone)]
+pub struct BoardColumn {
+    pub title: String,
+    /// Status category when columns are workflow statuses
+    pub category: Option<TaskStatus>,
+    pub cards: Vec<ViewTask>,
+}
+
+pub fn display_board(columns: &[BoardColumn]) {
+    if columns.iter().all(|c| c.cards.is_empty()) {
+        println!("No tasks found.");
+        return;
+    }
+
+    let mut table = Table::new();
+    table.set_header(columns.iter().map(|column| {
+        let header = Cell::new(format!("{} ({})", column.title, column.cards.len()))
+            .add_attribute(Attribute::Bold);
+        match &column.category {
+            Some(category) => status_style(header, category),
+            None => header,
+        }
+    }));
+
+    // Cards are laid out row by row, so row `i` holds the `i`th card of each column
+    let depth = columns.iter().map(|c| c.cards.len()).max().unwrap_or(0);
+    for i in 0..depth {
+        let mut row = Row::new();
+        for column in columns {
+            row.add_cell(column.cards.get(i).map_or_else(|| Cell::new(""), card_cell));
+        }
+        table.add_row(row);
+    }
+
+    println!("{table}");
+}
+
+fn card_cell(task: &ViewTask) -> Cell {
+    let mut lines = vec![format!("{} {}", &task.id.to_string()[..7], task.name)];
+    if let Some(due_at) = task.due_at {
+        if task.status == TaskStatus::Pending && due_at < Utc::now() {
+            lines.push(format!("overdue {}", due_at.humanize()));
+        } else {
+            lines.push(format!("due {}", due_at.humanize()));
+        }
+    }
+    if !task.tags.is_empty() {
+        lines.push(task.tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" "));
+    }
+
+    let cell = Cell::new(lines.join("\n"));
+    match task.status {
+        TaskStatus::Completed | TaskStatus::Cancelled => cell.add_attribute(Attribute::CrossedOut).fg(Color::DarkGrey),
+        TaskStatus::Pending => priority_style(cell, &task.priority),
+    }
+}
@@ -0,0 +1,178 @@
+use chrono::{DateTime, Local, Utc};
+use chrono_humanize::Humanize;
+use owo_colors::OwoColorize;
+use rusk_core::models::{ExceptionType, SeriesException, Task, TaskPriority, TaskRelations, TaskStatus, TimeMode};
+
+use crate::views::table::{format_duration, format_progress};
+
+/// Everything `rusk show` prints about a single task
+#[derive(Debug, Clone)]
+pub struct ViewTaskDetail {
+    pub task: Task,
+    pub project_name: Option<String>,
+    pub tags: Vec<String>,
+    /// Seconds of finished time entries
+    pub tracked_seconds: i64,
+    pub urgency: f64,
+    pub is_blocked: bool,
+    /// Subtasks anywhere below the task, not counting cancelled ones
+    pub subtask_count: i64,
+    pub completed_subtask_count: i64,
+    pub relations: TaskRelations,
+    pub series: Option<ViewSeriesDetail>,
+    /// Events in chronological order
+    pub history: Vec<ViewHistoryEvent>,
+}
+
+/// The recurring series a task is the template or an occurrence of
+#[derive(Debug, Clone)]
+pub struct ViewSeriesDetail {
+    pub is_template: bool,
+    /// Human-readable recurrence rule
+    pub rule: String,
+    /// The series' own timezone when zoned, the user's when floating
+    pub timezone: String,
+    pub time_mode: TimeMode,
+    pub active: bool,
+    pub next_occurrences: Vec<DateTime<Utc>>,
+    /// Exceptions that apply to this occurrence
+    pub exceptions: Vec<SeriesException>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ViewHistoryEvent {
+    pub at: DateTime<Utc>,
+    pub text: String,
+}
+
+fn local(dt: DateTime<Utc>) -> String {
+    dt.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string()
+}
+
+fn field(label: &str, value: impl std::fmt::Display) {
+    println!("{} {}", format!("{:<12}", label).bold(), value);
+}
+
+fn task_line(task: &Task) -> String {
+    format!("{} {} ({})", &task.id.to_string()[..7], task.name, task.workflow_status)
+}
+
+fn section(title: &str, tasks: &[Task]) {
+    if tasks.is_empty() {
+        return;
+    }
+    println!();
+    println!("{}", title.blue().bold());
+    for task in tasks {
+        println!("  {}", task_line(task));
+    }
+}
+
+pub fn display_task_detail(detail: &ViewTaskDetail) {
+    let task = &detail.task;
+    println!("{}", task.name.bold());
+    field("ID", task.id);
+    let status = if detail.is_blocked && task.status == TaskStatus::Pending {
+        format!("{} (blocked)", task.workflow_status)
+    } else {
+        task.workflow_status.clone()
+    };
+    field("Status", status);
+    if task.priority != TaskPriority::None {
+        field("Priority", format!("{:?}", task.priority));
+    }
+    if let Some(description) = &task.description {
+        field("Description", description);
+    }
+    if let Some(project) = &detail.project_name {
+        field("Project", project);
+    }
+    if !detail.tags.is_empty() {
+        field("Tags", detail.tags.join(", "));
+    }
+    if let Some(due_at) = task.due_at {
+        if task.all_day {
+            field("Due", format!("{} (all day)", due_at.format("%Y-%m-%d")));
+        } else {
+            field("Due", format!("{} ({})", local(due_at), due_at.humanize()));
+        }
+    }
+    for (label, date) in [("Scheduled", task.scheduled_at), ("Wait", task.wait_until), ("Start", task.start_at)] {
+        if let Some(date) = date {
+            field(label, local(date));
+        }
+    }
+    if let Some(minutes) = task.estimate_minutes {
+        field("Estimate", format_duration(minutes * 60));
+    }
+    if detail.tracked_seconds > 0 {
+        field("Tracked", format_duration(detail.tracked_seconds));
+    }
+    if detail.subtask_count > 0 {
+        field("Progress", format_progress(detail.completed_subtask_count, detail.subtask_count));
+    }
+    if task.status == TaskStatus::Pending {
+        field("Urgency", format!("{:.1}", detail.urgency));
+    }
+    field("Created", local(task.created_at));
+    field("Updated", local(task.updated_at));
+    if let Some(completed_at) = task.completed_at {
+        field("Completed", local(completed_at));
+    }
+
+    if !detail.relations.ancestors.is_empty() {
+        // Outermost parent first, down to this task
+        let chain: Vec<String> = detail.relations.ancestors.iter().rev().map(task_line).collect();
+        println!();
+        println!("{}", "Parents".blue().bold());
+        for (depth, line) in chain.iter().enumerate() {
+            println!("  {}{}", "  ".repeat(depth), line);
+        }
+    }
+    section("Subtasks", &detail.relations.subtasks);
+    section("Depends on", &detail.relations.depends_on);
+    section("Blocking", &detail.relations.dependents);
+
+    if let Some(series) = &detail.series {
+        println!();
+        let title = if series.is_template { "Series (template)" } else { "Series" };
+        println!("{}", title.blue().bold());
+        field("Repeats", &series.rule);
+        match series.time_mode {
+            TimeMode::Zoned => field("Timezone", &series.timezone),
+            TimeMode::Floating => field("Timezone", format!("floating (currently {})", series.timezone)),
+            TimeMode::AllDay => field("Time", "all day"),
+        }
+        if !series.active {
+            field("Active", "no (paused)");
+        }
+        if !series.next_occurrences.is_empty() {
+            let next: Vec<String> = series
+                .next_occurrences
+                .iter()
+                .map(|dt| match series.time_mode {
+                    TimeMode::AllDay => dt.format("%Y-%m-%d").to_string(),
+                    _ => local(*dt),
+                })
+                .collect();
+            field("Next", next.join(", "));
+        }
+        for exception in &series.exceptions {
+            let kind = match exception.exception_type {
+                ExceptionType::Skip => "Skipped",
+                ExceptionType::Override => "Overridden",
+                ExceptionType::Move => "Moved",
+            };
+            let notes = exception.notes.as_deref().map(|n| format!(" - {}", n)).unwrap_or_default();
+            field("Exception", format!("{} from {}{}", kind, local(exception.occurrence_dt), notes));
+        }
+    }
+
+    if !detail.history.is_empty() {
+        println!();
+        println!("{}", "History".blue().bold());
+        for event in &detail.history {
+            println!("  {}  {}", local(event.at).dimmed(), event.text);
+        }
+    }
+}
@@ -1,3 +1,5 @@
 // Logic for rendering different views (e.g., tables, dashboard) will go here.
 
-pub mod table;
\ No newline at end of file
+pub mod board;
+pub mod detail;
+pub mod table;
@@ -1,7 +1,9 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use chrono_humanize::Humanize;
 use comfy_table::{Attribute, Cell, Color, Row, Table};
-use rusk_core::models::{TaskPriority, TaskStatus};
+use rusk_core::models::{Blueprint, BlueprintTask, TaskPriority, TaskSeries, TaskStatus, TimeMode};
+use rusk_core::recurrence::describe_rrule;
+use rusk_core::repository::TaskQueryResult;
 use uuid::Uuid;
 
 #[derive(Debug, Clone)]
@@ -9,8 +11,12 @@ pub struct ViewTask {
     pub id: Uuid,
     pub name: String,
     pub status: TaskStatus,
+    /// Workflow status name; shown instead of `status` when it is a custom one
+    pub workflow_status: String,
     pub priority: TaskPriority,
     pub due_at: Option<DateTime<Utc>>,
+    /// Due on the date of `due_at` rather than at a time
+    pub all_day: bool,
     pub project_name: Option<String>,
     pub tags: Vec<String>,
     pub depth: usize,
@@ -18,6 +24,69 @@ pub struct ViewTask {
     pub is_template: bool,
     pub has_exceptions: bool,
     pub timezone: Option<String>,
+    /// Seconds of finished time entries
+    pub tracked_seconds: i64,
+    pub estimate_minutes: Option<i64>,
+    pub scheduled_at: Option<DateTime<Utc>>,
+    pub wait_until: Option<DateTime<Utc>>,
+    pub start_at: Option<DateTime<Utc>>,
+    /// Urgency score; filled in by the caller since it depends on configuration
+    pub urgency: f64,
+    /// Whether a pending dependency blocks the task
+    pub is_blocked: bool,
+    /// Subtasks anywhere below the task, not counting cancelled ones
+    pub subtask_count: i64,
+    pub completed_subtask_count: i64,
+}
+
+impl From<TaskQueryResult> for ViewTask {
+    fn from(t: TaskQueryResult) -> Self {
+        let tags = t.tags.map_or_else(Vec::new, |s| s.split(',').map(String::from).collect());
+        ViewTask {
+            id: t.id,
+            name: t.name,
+            status: t.status,
+            workflow_status: t.workflow_status,
+            priority: t.priority,
+            due_at: t.due_at,
+            all_day: t.all_day,
+            project_name: t.project_name,
+            tags,
+            depth: t.depth as usize,
+            series_id: t.series_id,
+            is_template: false, // TODO: Determine from series relationship
+            has_exceptions: false, // TODO: Implement exception checking
+            timezone: None, // TODO: Get from series if applicable
+            tracked_seconds: t.tracked_seconds,
+            estimate_minutes: t.estimate_minutes,
+            scheduled_at: t.scheduled_at,
+            wait_until: t.wait_until,
+            start_at: t.start_at,
+            urgency: 0.0,
+            is_blocked: t.is_blocked,
+            subtask_count: t.subtask_count,
+            completed_subtask_count: t.completed_subtask_count,
+        }
+    }
+}
+
+/// Colour a cell by task priority, as used for pending task names
+pub fn priority_style(cell: Cell, priority: &TaskPriority) -> Cell {
+    match priority {
+        TaskPriority::High => cell.fg(Color::Red).add_attribute(Attribute::Bold),
+        TaskPriority::Medium => cell.fg(Color::Yellow),
+        TaskPriority::Low => cell.fg(Color::Green),
+        TaskPriority::None => cell,
+    }
+}
+
+/// Colour a cell by status category
+pub fn status_style(cell: Cell, status: &TaskStatus) -> Cell {
+    match status {
+        TaskStatus::Completed => cell.fg(Color::Green),
+        TaskStatus::Cancelled => cell.fg(Color::DarkGrey),
+        TaskStatus::Pending => cell,
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -28,14 +97,40 @@ pub struct ViewProject {
     pub created_at: DateTime<Utc>,
 }
 
+/// An optional date column: its title and how to read the date from a task
+type DateColumn = (&'static str, fn(&ViewTask) -> Option<DateTime<Utc>>);
+
 pub fn display_tasks(tasks: &[ViewTask]) {
     if tasks.is_empty() {
         println!("No tasks found.");
         return;
     }
 
+    // Optional columns only show once some task uses them
+    let mut date_columns: Vec<DateColumn> = vec![
+        ("Scheduled", |t| t.scheduled_at),
+        ("Wait", |t| t.wait_until),
+        ("Start", |t| t.start_at),
+    ];
+    date_columns.retain(|(_, date)| tasks.iter().any(|t| date(t).is_some()));
+    let show_estimate = tasks.iter().any(|t| t.estimate_minutes.is_some());
+    let show_tracked = tasks.iter().any(|t| t.tracked_seconds > 0);
+    let show_progress = tasks.iter().any(|t| t.subtask_count > 0);
+
     let mut table = Table::new();
-    table.set_header(vec!["ID", "Name", "Status", "Due Date", "Project", "Tags"]);
+    let mut header = vec!["ID", "Name", "Status", "Due Date", "Project", "Tags"];
+    header.extend(date_columns.iter().map(|(title, _)| *title));
+    if show_estimate {
+        header.push("Estimate");
+    }
+    if show_tracked {
+        header.push("Tracked");
+    }
+    if show_progress {
+        header.push("Progress");
+    }
+    header.push("Urgency");
+    table.set_header(header);
 
     for task in tasks {
         let mut row = Row::new();
@@ -75,30 +170,28 @@ pub fn display_tasks(tasks: &[ViewTask]) {
                     .fg(Color::DarkGrey);
             }
             TaskStatus::Pending => {
-                name_cell = match task.priority {
-                    TaskPriority::High => name_cell.fg(Color::Red).add_attribute(Attribute::Bold),
-                    TaskPriority::Medium => name_cell.fg(Color::Yellow),
-                    TaskPriority::Low => name_cell.fg(Color::Green),
-                    TaskPriority::None => name_cell,
-                };
+                name_cell = priority_style(name_cell, &task.priority);
             }
         };
         row.add_cell(name_cell);
 
-        let mut status_cell = Cell::new(format!("{:?}", task.status));
-        status_cell = match task.status {
-            TaskStatus::Completed => status_cell.fg(Color::Green),
-            TaskStatus::Cancelled => status_cell.fg(Color::DarkGrey),
-            TaskStatus::Pending => status_cell,
+        let status_label = if task.workflow_status == task.status.as_str() {
+            format!("{:?}", task.status)
+        } else {
+            task.workflow_status.clone()
         };
-        row.add_cell(status_cell);
+        row.add_cell(status_style(Cell::new(status_label), &task.status));
 
         let due_date_cell = if let Some(due_at) = task.due_at {
             let now = Utc::now();
             let today = now.date_naive();
             let due_date = due_at.date_naive();
 
-            let mut due_text = due_at.humanize();
+            let mut due_text = if task.all_day {
+                due_date.format("%Y-%m-%d").to_string()
+            } else {
+                due_at.humanize()
+            };
             
             // Add timezone abbreviation for recurring tasks
             if task.series_id.is_some() && task.timezone.is_some() {
@@ -108,7 +201,8 @@ pub fn display_tasks(tasks: &[ViewTask]) {
             }
             
             if task.status == TaskStatus::Pending {
-                if due_at < now {
+                let overdue = if task.all_day { due_date < today } else { due_at < now };
+                if overdue {
                     Cell::new(due_text).fg(Color::Red) // Overdue
                 } else if due_date == today {
                     Cell::new(due_text).fg(Color::Yellow) // Due today
@@ -129,12 +223,137 @@ pub fn display_tasks(tasks: &[ViewTask]) {
         } else {
             task.tags.join(", ")
         }));
+        for (_, date) in &date_columns {
+            row.add_cell(Cell::new(date(task).map(|d| d.humanize()).unwrap_or_default()));
+        }
+        if show_estimate {
+            row.add_cell(Cell::new(
+                task.estimate_minutes.map(|m| format_duration(m * 60)).unwrap_or_default(),
+            ));
+        }
+        if show_tracked {
+            row.add_cell(Cell::new(if task.tracked_seconds > 0 {
+                format_duration(task.tracked_seconds)
+            } else {
+                String::new()
+            }));
+        }
+        if show_progress {
+            row.add_cell(Cell::new(if task.subtask_count > 0 {
+                format_progress(task.completed_subtask_count, task.subtask_count)
+            } else {
+                String::new()
+            }));
+        }
+        row.add_cell(Cell::new(if task.status == TaskStatus::Pending {
+            format!("{:.1}", task.urgency)
+        } else {
+            String::new()
+        }));
         table.add_row(row);
     }
 
     println!("{table}");
 }
 
+/// Format tracked seconds as hours and minutes, e.g. "1h 30m"
+pub fn format_duration(seconds: i64) -> String {
+    let minutes = seconds / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h {}m", h, m),
+    }
+}
+
+/// Completed out of all subtasks with the percentage, e.g. "2/3 (67%)"
+pub fn format_progress(completed: i64, total: i64) -> String {
+    let percent = if total > 0 { completed * 100 / total } else { 0 };
+    format!("{}/{} ({}%)", completed, total, percent)
+}
+
+/// One row of a timesheet summary
+#[derive(Debug, Clone)]
+pub struct ViewTimeTotal {
+    pub label: String,
+    pub seconds: i64,
+}
+
+pub fn display_time_totals(title: &str, totals: &[ViewTimeTotal], grand_total: i64) {
+    let mut table = Table::new();
+    table.set_header(vec![title, "Time", "Share"]);
+
+    for total in totals {
+        let mut row = Row::new();
+        row.add_cell(Cell::new(&total.label));
+        row.add_cell(Cell::new(format_duration(total.seconds)));
+        row.add_cell(Cell::new(format!("{:.0}%", total.seconds as f64 * 100.0 / grand_total.max(1) as f64)));
+        table.add_row(row);
+    }
+
+    println!("{table}");
+}
+
+/// One day of a workload forecast
+#[derive(Debug, Clone)]
+pub struct ViewDailyLoad {
+    pub date: NaiveDate,
+    pub planned_minutes: i64,
+    /// Names of the work due that day, with their estimates
+    pub items: Vec<String>,
+}
+
+pub fn display_forecast(days: &[ViewDailyLoad], capacity_minutes: i64) {
+    let mut table = Table::new();
+    table.set_header(vec!["Date", "Planned", "Load", "Work"]);
+
+    for day in days {
+        let overloaded = day.planned_minutes > capacity_minutes;
+        let mut row = Row::new();
+        row.add_cell(Cell::new(day.date.format("%a %Y-%m-%d")));
+
+        let planned = Cell::new(format_duration(day.planned_minutes * 60));
+        row.add_cell(if overloaded { planned.fg(Color::Red).add_attribute(Attribute::Bold) } else { planned });
+
+        let load = format!("{:.0}%", day.planned_minutes as f64 * 100.0 / capacity_minutes.max(1) as f64);
+        row.add_cell(if overloaded {
+            Cell::new(format!("{} ⚠", load)).fg(Color::Red)
+        } else {
+            Cell::new(load)
+        });
+        row.add_cell(Cell::new(day.items.join(", ")));
+        table.add_row(row);
+    }
+
+    println!("{table}");
+}
+
+#[derive(Debug, Clone)]
+pub struct ViewWorkflowStatus {
+    pub name: String,
+    pub category: TaskStatus,
+    /// Statuses tasks may move to; empty means any
+    pub allowed: Vec<String>,
+}
+
+pub fn display_workflow_statuses(statuses: &[ViewWorkflowStatus]) {
+    let mut table = Table::new();
+    table.set_header(vec!["Status", "Category", "Moves To"]);
+
+    for status in statuses {
+        let mut row = Row::new();
+        row.add_cell(status_style(Cell::new(&status.name), &status.category));
+        row.add_cell(Cell::new(format!("{:?}", status.category)));
+        row.add_cell(Cell::new(if status.allowed.is_empty() {
+            "any".to_string()
+        } else {
+            status.allowed.join(", ")
+        }));
+        table.add_row(row);
+    }
+
+    println!("{table}");
+}
 
 pub fn display_projects(projects: &[ViewProject]) {
     if projects.is_empty() {
@@ -155,4 +374,125 @@ pub fn display_projects(projects: &[ViewProject]) {
     }
 
     println!("{table}");
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone)]
+pub struct ViewSeries {
+    pub series: TaskSeries,
+    /// Name of the template task
+    pub name: String,
+    /// None for paused series and series that have ended
+    pub next_occurrence: Option<DateTime<Utc>>,
+    pub exception_count: usize,
+}
+
+pub fn display_series(series: &[ViewSeries]) {
+    if series.is_empty() {
+        println!("No recurring series found.");
+        return;
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["Template ID", "Name", "Rule", "Timezone", "Active", "Next", "Exceptions"]);
+
+    for item in series {
+        let timezone = match item.series.time_mode {
+            TimeMode::Zoned => item.series.timezone.clone(),
+            mode => mode.to_string(),
+        };
+        let next = item.next_occurrence.map_or_else(
+            || "None".to_string(),
+            |next| match (item.series.time_mode, item.series.timezone.parse::<chrono_tz::Tz>()) {
+                (TimeMode::AllDay, _) => next.format("%Y-%m-%d").to_string(),
+                (_, Ok(tz)) => next.with_timezone(&tz).format("%Y-%m-%d %H:%M %Z").to_string(),
+                (_, Err(_)) => next.format("%Y-%m-%d %H:%M UTC").to_string(),
+            },
+        );
+
+        let mut row = Row::new();
+        row.add_cell(Cell::new(item.series.template_task_id));
+        row.add_cell(Cell::new(&item.name));
+        row.add_cell(Cell::new(describe_rrule(&item.series.rrule)));
+        row.add_cell(Cell::new(timezone));
+        row.add_cell(if item.series.active {
+            Cell::new("Yes").fg(Color::Green)
+        } else {
+            Cell::new("Paused").fg(Color::Yellow)
+        });
+        row.add_cell(Cell::new(next));
+        row.add_cell(Cell::new(item.exception_count));
+        table.add_row(row);
+    }
+
+    println!("{table}");
+}
+
+pub fn display_blueprints(blueprints: &[Blueprint]) {
+    if blueprints.is_empty() {
+        println!("No blueprints found.");
+        return;
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["Name", "Tasks", "Recurring", "Description", "Created At"]);
+
+    for blueprint in blueprints {
+        let recurring = blueprint.tasks.iter().filter(|t| t.rrule.is_some()).count();
+        let mut row = Row::new();
+        row.add_cell(Cell::new(&blueprint.name));
+        row.add_cell(Cell::new(blueprint.tasks.len()));
+        row.add_cell(Cell::new(recurring));
+        row.add_cell(Cell::new(blueprint.description.as_deref().unwrap_or("None")));
+        row.add_cell(Cell::new(blueprint.created_at.humanize()));
+        table.add_row(row);
+    }
+
+    println!("{table}");
+}
+
+/// Due offset from a blueprint's anchor, e.g. "day 3 09:30" or "day 0"
+/// for an all-day task
+pub fn format_due_offset(minutes: i64, time_mode: TimeMode) -> String {
+    let day = minutes.div_euclid(24 * 60);
+    let minute_of_day = minutes.rem_euclid(24 * 60);
+    if time_mode == TimeMode::AllDay {
+        format!("day {}", day)
+    } else {
+        format!("day {} {:02}:{:02}", day, minute_of_day / 60, minute_of_day % 60)
+    }
+}
+
+pub fn display_blueprint_tasks(tasks: &[BlueprintTask]) {
+    if tasks.is_empty() {
+        println!("No tasks in this blueprint.");
+        return;
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["#", "Name", "Due", "Priority", "Tags", "Depends On", "Recurrence"]);
+
+    for task in tasks {
+        let mut depth = 0;
+        let mut parent = task.parent_position;
+        while let Some(position) = parent {
+            depth += 1;
+            parent = tasks.get(position as usize).and_then(|t| t.parent_position);
+        }
+        let depends_on: Vec<String> = task.depends_on.iter().map(|p| format!("#{}", p + 1)).collect();
+
+        let mut row = Row::new();
+        row.add_cell(Cell::new(task.position + 1));
+        row.add_cell(Cell::new(format!("{}{}", "  ".repeat(depth), task.name)));
+        row.add_cell(Cell::new(
+            task.due_offset_minutes
+                .map_or_else(String::new, |minutes| format_due_offset(minutes, task.time_mode)),
+        ));
+        row.add_cell(priority_style(Cell::new(format!("{:?}", task.priority)), &task.priority));
+        row.add_cell(Cell::new(task.tags.join(", ")));
+        row.add_cell(Cell::new(depends_on.join(", ")));
+        row.add_cell(Cell::new(task.rrule.as_deref().map(describe_rrule).unwrap_or_default()));
+        table.add_row(row);
+    }
+
+    println!("{table}");
+}
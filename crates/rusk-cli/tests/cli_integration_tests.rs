@@ -425,4 +425,844 @@ fn test_output_formatting() {
     
     // Test empty project list
     harness.run_success(&["project", "list"]);
-}
\ No newline at end of file
+}
+/// Test reminders delivered by a single daemon pass
+#[test]
+fn test_daemon_once_delivers_reminders() {
+    let harness = CliTestHarness::new();
+
+    harness.run_success(&["add", "Renew passport", "--due", "tomorrow", "--remind", "2d"])
+        .stdout(assertions::task_created_successfully());
+    harness.run_success(&["add", "Plan holiday", "--due", "tomorrow", "--remind", "30m"]);
+
+    // Only the task whose reminder window has opened is delivered
+    harness.run_success(&["daemon", "--once", "--sink", "stdout"])
+        .stdout(predicate::str::contains("Reminder: 'Renew passport'"))
+        .stdout(predicate::str::contains("Plan holiday").not())
+        .stdout(predicate::str::contains("Delivered 1 reminder(s)."));
+
+    // Already delivered reminders are not repeated
+    harness.run_success(&["daemon", "--once", "--sink", "stdout"])
+        .stdout(predicate::str::contains("Delivered 0 reminder(s)."));
+
+    // Invalid offsets are rejected
+    harness.run_failure(&["add", "Bad reminder", "--remind", "soon"]);
+}
+
+/// Test database selection via XDG defaults, --db and --profile
+#[test]
+fn test_database_location_and_profiles() {
+    let harness = CliTestHarness::new();
+
+    // The default database lives under XDG_DATA_HOME, not the working directory
+    harness.xdg_command()
+        .args(["add", "Default task"])
+        .assert()
+        .success();
+    assert!(harness.data_home().join("rusk/rusk.db").exists());
+
+    // Profiles get their own database
+    harness.xdg_command()
+        .args(["--profile", "work", "add", "Work task"])
+        .assert()
+        .success();
+    assert!(harness.data_home().join("rusk/profiles/work/rusk.db").exists());
+    harness.xdg_command()
+        .args(["list", "--profile", "work"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Work task"))
+        .stdout(predicate::str::contains("Default task").not());
+
+    // A profile overlay can point the profile at another database
+    let shared_db = harness.data_home().join("shared.db");
+    let overlay_dir = harness.config_home().join("rusk/profiles");
+    std::fs::create_dir_all(&overlay_dir).unwrap();
+    std::fs::write(
+        overlay_dir.join("team.toml"),
+        format!("database = {:?}\n", shared_db.to_str().unwrap()),
+    )
+    .unwrap();
+    harness.xdg_command()
+        .args(["--profile", "team", "add", "Team task"])
+        .assert()
+        .success();
+    assert!(shared_db.exists());
+
+    // --db wins over everything else
+    let explicit_db = harness.data_home().join("explicit.db");
+    harness.xdg_command()
+        .args(["--profile", "team", "--db", explicit_db.to_str().unwrap(), "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Team task").not());
+    assert!(explicit_db.exists());
+
+    // Profile names cannot escape the data directory
+    harness.xdg_command()
+        .args(["--profile", "../evil", "list"])
+        .assert()
+        .failure();
+}
+
+/// Test that a config file that does not parse stops rusk instead of falling back to defaults
+#[test]
+fn test_malformed_profile_config() {
+    let harness = CliTestHarness::new();
+    let overlay_dir = harness.config_home().join("rusk/profiles");
+    std::fs::create_dir_all(&overlay_dir).unwrap();
+    std::fs::write(overlay_dir.join("broken.toml"), "database = \"/tmp/unclosed\n").unwrap();
+
+    harness.xdg_command()
+        .args(["--profile", "broken", "add", "Lost task"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid configuration"))
+        .stderr(predicate::str::contains("broken.toml"));
+    assert!(!harness.data_home().join("rusk/profiles/broken/rusk.db").exists());
+
+    // Profiles without an overlay still start from the defaults
+    harness.xdg_command()
+        .args(["--profile", "fresh", "list"])
+        .assert()
+        .success();
+}
+
+/// Test custom workflow statuses, transitions and `rusk move`
+#[test]
+fn test_workflow_statuses_and_move() {
+    let harness = CliTestHarness::new();
+
+    harness.run_success(&["status", "add", "in-progress"])
+        .stdout(predicate::str::contains("Added status: in-progress (pending)"));
+    harness.run_success(&["status", "add", "shipped", "--category", "completed"]);
+    harness.run_success(&["status", "allow", "pending", "in-progress"]);
+    harness.run_success(&["status", "list"])
+        .stdout(predicate::str::contains("in-progress"))
+        .stdout(predicate::str::contains("shipped"));
+
+    harness.run_success(&["add", "Write release notes"]);
+    let output = harness.run_success(&["list"]).get_output().stdout.clone();
+    let output = String::from_utf8(output).unwrap();
+    let id = output
+        .lines()
+        .find(|line| line.contains("Write release notes"))
+        .and_then(|line| line.split('|').nth(1))
+        .map(|cell| cell.trim().to_string())
+        .expect("task row in list output");
+
+    // Only the allowed transition out of pending is accepted
+    harness.run_failure(&["move", &id, "shipped"])
+        .stderr(predicate::str::contains("not allowed"));
+    harness.run_failure(&["do", &id])
+        .stderr(predicate::str::contains("not allowed"));
+    harness.run_failure(&["cancel", &id])
+        .stderr(predicate::str::contains("not allowed"));
+    harness.run_success(&["move", &id, "in-progress"])
+        .stdout(predicate::str::contains("Moved task 'Write release notes' to in-progress"));
+    harness.run_success(&["list", "status:in-progress"])
+        .stdout(predicate::str::contains("Write release notes"));
+    harness.run_failure(&["list", "status:unknown-status"]);
+
+    // Moving into a completed status completes the task
+    harness.run_success(&["mv", &id, "shipped"]);
+    harness.run_success(&["list", "status:completed"])
+        .stdout(predicate::str::contains("shipped"));
+
+    harness.run_failure(&["status", "delete", "shipped"]);
+    harness.run_success(&["status", "delete", "in-progress"])
+        .stdout(predicate::str::contains("Status deleted."));
+}
+
+/// Test the kanban board grouped by status and by tag prefix
+#[test]
+fn test_board_columns() {
+    let harness = CliTestHarness::new();
+
+    harness.run_success(&["board"])
+        .stdout(predicate::str::contains("No tasks found."));
+
+    harness.run_success(&["status", "add", "doing"]);
+    harness.run_success(&["add", "Draft proposal", "--tag", "stage-todo"]);
+    harness.run_success(&["add", "Review budget", "--tag", "stage-review"]);
+
+    harness.run_success(&["board"])
+        .stdout(predicate::str::contains("pending (2)"))
+        .stdout(predicate::str::contains("doing (0)"))
+        .stdout(predicate::str::contains("Draft proposal"));
+
+    harness.run_success(&["board", "--by-tag", "stage-"])
+        .stdout(predicate::str::contains("todo (1)"))
+        .stdout(predicate::str::contains("review (1)"))
+        .stdout(predicate::str::contains("pending").not());
+
+    harness.run_success(&["board", "name:Review"])
+        .stdout(predicate::str::contains("Review budget"))
+        .stdout(predicate::str::contains("Draft proposal").not());
+}
+
+/// Test timers, logged time and the weekly timesheet
+#[test]
+fn test_time_tracking() {
+    let harness = CliTestHarness::new();
+
+    harness.run_success(&["project", "add", "Work"]);
+    let report = harness.add_task(&["Write report", "--project", "Work", "--tag", "writing"]);
+    let review = harness.add_task(&["Review PR"]);
+
+    harness.run_success(&["track", &report, "1h30m"])
+        .stdout(predicate::str::contains("Logged 1h 30m on 'Write report' (total 1h 30m)"));
+    harness.run_failure(&["track", &report, "soon"]);
+
+    // Only one timer runs at a time
+    harness.run_success(&["start", &report])
+        .stdout(predicate::str::contains("Started timer for 'Write report'"));
+    harness.run_failure(&["start", &review])
+        .stderr(predicate::str::contains("already running"));
+    harness.run_success(&["stop"])
+        .stdout(predicate::str::contains("Stopped timer for 'Write report'"));
+    harness.run_success(&["stop"])
+        .stdout(predicate::str::contains("No timer is running."));
+
+    harness.run_success(&["list"])
+        .stdout(predicate::str::contains("Tracked"))
+        .stdout(predicate::str::contains("1h 30m"));
+
+    harness.run_success(&["timesheet", "--week"])
+        .stdout(predicate::str::contains("Work"))
+        .stdout(predicate::str::contains("writing"))
+        .stdout(predicate::str::contains("Total: 1h 30m"));
+    harness.run_failure(&["timesheet", "--week", "--today"]);
+}
+
+#[test]
+fn test_estimates_and_forecast() {
+    let harness = CliTestHarness::new();
+
+    let report = harness.add_task(&["Write report", "--due", "tomorrow", "--estimate", "5h"]);
+    harness.add_task(&["Review PR", "--due", "tomorrow", "--estimate", "2h"]);
+    harness.run_failure(&["add", "Tiny", "--estimate", "0m"]);
+
+    harness.run_success(&["list"])
+        .stdout(predicate::str::contains("Estimate"))
+        .stdout(predicate::str::contains("5h"));
+
+    harness.run_success(&["forecast", "--days", "3", "--capacity", "6h"])
+        .stdout(predicate::str::contains("Write report (5h)"))
+        .stdout(predicate::str::contains("Planned: 7h of 18h available"))
+        .stdout(predicate::str::contains("over by 1h"));
+
+    harness.run_success(&["edit", &report, "--estimate", "3h"]);
+    harness.run_success(&["forecast", "--days", "3", "--capacity", "6h"])
+        .stdout(predicate::str::contains("No overloaded days."));
+
+    harness.run_success(&["edit", &report, "--estimate-clear"]);
+    harness.run_success(&["forecast", "--days", "3"])
+        .stdout(predicate::str::contains("Planned: 2h of 24h available"));
+    harness.run_failure(&["forecast", "--days", "0"]);
+}
+
+#[test]
+fn test_urgency_and_next() {
+    let harness = CliTestHarness::new();
+
+    let design = harness.add_task(&["Design schema", "--priority", "medium"]);
+    harness.add_task(&["Build API", "--priority", "high", "--depends-on", &design]);
+    harness.add_task(&["Pay invoice", "--due", "yesterday"]);
+    harness.add_task(&["Water plants"]);
+
+    let output = harness.run_success(&["list", "--sort", "urgency"]).get_output().stdout.clone();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("Urgency"));
+    let position = |name: &str| output.find(name).unwrap();
+    assert!(position("Design schema") < position("Pay invoice"));
+    assert!(position("Pay invoice") < position("Water plants"));
+
+    harness.run_success(&["list", "urgency:>5"])
+        .stdout(predicate::str::contains("Design schema"))
+        .stdout(predicate::str::contains("Water plants").not());
+    harness.run_failure(&["list", "urgency:>5 or tag:home"])
+        .stderr(predicate::str::contains("using 'and'"));
+
+    harness.run_success(&["next"])
+        .stdout(predicate::str::contains("Design schema"))
+        .stdout(predicate::str::contains("Build API").not());
+    harness.run_success(&["next", "-n", "1"])
+        .stdout(predicate::str::contains("Design schema"))
+        .stdout(predicate::str::contains("Pay invoice").not());
+
+    harness.run_success(&["do", &design]);
+    harness.run_success(&["next"])
+        .stdout(predicate::str::contains("Build API"));
+}
+
+#[test]
+fn test_scheduled_wait_and_start_dates() {
+    let harness = CliTestHarness::new();
+
+    harness.add_task(&["Today's work"]);
+    let passport = harness.add_task(&["Renew passport", "--wait", "3 days"]);
+    harness.add_task(&["Plan trip", "--due", "2 weeks", "--scheduled", "1w before due", "--start", "2d before due"]);
+    harness.run_failure(&["add", "No due", "--scheduled", "2d before due"])
+        .stderr(predicate::str::contains("has no due date"));
+
+    harness.run_success(&["list"])
+        .stdout(predicate::str::contains("Today's work"))
+        .stdout(predicate::str::contains("Renew passport").not())
+        .stdout(predicate::str::contains("Plan trip").not());
+    harness.run_success(&["list", "is:waiting"])
+        .stdout(predicate::str::contains("Renew passport"))
+        .stdout(predicate::str::contains("Wait"))
+        .stdout(predicate::str::contains("Today's work").not());
+    harness.run_success(&["list", "is:scheduled"])
+        .stdout(predicate::str::contains("Plan trip"))
+        .stdout(predicate::str::contains("Start"));
+    harness.run_success(&["list", "all"])
+        .stdout(predicate::str::contains("Renew passport"))
+        .stdout(predicate::str::contains("Plan trip"));
+    harness.run_success(&["next"])
+        .stdout(predicate::str::contains("Renew passport").not());
+
+    harness.run_success(&["edit", &passport, "--wait-clear"]);
+    harness.run_success(&["list"])
+        .stdout(predicate::str::contains("Renew passport"));
+
+    // Recurring tasks keep the offset for every occurrence
+    harness.run_success(&["add", "Water plants", "--every", "weekly", "--due", "tomorrow", "--scheduled", "3d before due"]);
+    harness.run_success(&["list", "name:plants"])
+        .stdout(predicate::str::contains("Water plants"));
+    harness.run_success(&["list", "name:plants and is:scheduled"])
+        .stdout(predicate::str::contains("Water plants"));
+
+    // Without --due, the offset is measured from the first occurrence
+    harness.run_success(&["add", "Bins", "--every", "weekly", "--on", "monday", "--at", "9:00 AM", "--wait", "2d before due"]);
+    harness.run_success(&["list", "name:bins and is:waiting"])
+        .stdout(predicate::str::contains("Bins"));
+}
+
+#[test]
+fn test_task_notes() {
+    let harness = CliTestHarness::new();
+
+    let order = harness.add_task(&["Order parts"]);
+    harness.add_task(&["Sweep floor"]);
+
+    harness.run_success(&["note", &order])
+        .stdout(predicate::str::contains("No notes on 'Order parts'."));
+    harness.run_success(&["note", &order, "Asked for a quote"])
+        .stdout(predicate::str::contains("Added note to 'Order parts'"));
+    harness.run_success(&["note", &order, "Quote arrived"]);
+    harness.run_failure(&["note", &order, "  "]);
+
+    harness.run_success(&["note", &order])
+        .stdout(predicate::str::contains("Asked for a quote"))
+        .stdout(predicate::str::contains("Quote arrived"));
+
+    harness.run_success(&["list", "note:quote"])
+        .stdout(predicate::str::contains("Order parts"))
+        .stdout(predicate::str::contains("Sweep floor").not());
+}
+
+#[test]
+fn test_show_task() {
+    let harness = CliTestHarness::new();
+
+    harness.run_success(&["project", "add", "Home"]);
+    let parent = harness.add_task(&["Renovate kitchen", "--project", "Home"]);
+    let paint = harness.add_task(&["Buy paint", "--parent", &parent, "--tag", "shopping", "errand"]);
+    let walls = harness.add_task(&["Paint walls", "--parent", &parent, "--depends-on", &paint, "-D", "Two coats"]);
+    harness.run_success(&["note", &walls, "Ask for the matte finish"]);
+    harness.run_success(&["track", &walls, "45m"]);
+
+    harness.run_success(&["show", &walls])
+        .stdout(predicate::str::contains(&walls))
+        .stdout(predicate::str::contains("Two coats"))
+        .stdout(predicate::str::contains("pending (blocked)"))
+        .stdout(predicate::str::contains("Parents"))
+        .stdout(predicate::str::contains("Renovate kitchen"))
+        .stdout(predicate::str::contains("Depends on"))
+        .stdout(predicate::str::contains("Buy paint"))
+        .stdout(predicate::str::contains("Note: Ask for the matte finish"))
+        .stdout(predicate::str::contains("Tracked 45m"));
+
+    harness.run_success(&["show", &paint])
+        .stdout(predicate::str::contains("errand, shopping"))
+        .stdout(predicate::str::contains("Blocking"))
+        .stdout(predicate::str::contains("Paint walls"));
+    harness.run_success(&["show", &parent])
+        .stdout(predicate::str::contains("Home"))
+        .stdout(predicate::str::contains("Subtasks"));
+
+    let standup = harness.add_task(&["Standup", "--every", "weekly", "--on", "mon,wed", "--at", "9:00 AM"]);
+    harness.run_success(&["show", &standup])
+        .stdout(predicate::str::contains("Series (template)"))
+        .stdout(predicate::str::contains("every week on Monday and Wednesday"))
+        .stdout(predicate::str::contains("Next"));
+
+    harness.run_failure(&["show", "ffffffff"]);
+}
+
+#[test]
+fn test_bulk_operations() {
+    let harness = CliTestHarness::new();
+
+    harness.run_success(&["project", "add", "Old"]);
+    harness.run_success(&["project", "add", "New"]);
+    let invoice = harness.add_task(&["Send invoice", "--project", "Old", "--tag", "batch"]);
+    let receipt = harness.add_task(&["File receipt", "--project", "Old", "--tag", "batch"]);
+    let call = harness.add_task(&["Call bank", "--project", "Old"]);
+
+    harness.run_success(&["edit", "--where", "project:Old", "--project", "New", "--dry-run"])
+        .stdout(predicate::str::contains("Call bank"))
+        .stdout(predicate::str::contains("Dry run: would update 3 tasks."));
+    harness.run_success(&["list", "project:New"])
+        .stdout(predicate::str::contains("Send invoice").not());
+
+    // Without a terminal to confirm on, nothing changes
+    harness.run_success(&["do", "--where", "tag:batch"])
+        .stdout(predicate::str::contains("Nothing changed."));
+
+    harness.run_success(&["edit", "--where", "project:Old", "--project", "New", "--yes"])
+        .stdout(predicate::str::contains("Updated 3 tasks."));
+    harness.run_success(&["list", "project:New"])
+        .stdout(predicate::str::contains("Send invoice"))
+        .stdout(predicate::str::contains("Call bank"));
+
+    harness.run_success(&["do", "--where", "tag:batch", "--yes"])
+        .stdout(predicate::str::contains("Completed 2 tasks."));
+    harness.run_success(&["list"])
+        .stdout(predicate::str::contains("Call bank"))
+        .stdout(predicate::str::contains("Send invoice").not());
+
+    harness.run_success(&["cancel", &call, &invoice, "-y"])
+        .stdout(predicate::str::contains("Cancelled 2 tasks."));
+    harness.run_success(&["delete", &receipt, &call, "--force"])
+        .stdout(predicate::str::contains("Deleted 2 tasks."));
+    harness.run_success(&["do", "--where", "project:Nowhere", "--yes"])
+        .stdout(predicate::str::contains("No tasks matched."));
+    harness.run_failure(&["do", &invoice, "--where", "tag:batch"]);
+}
+
+/// Moving, annotating and logging time take several IDs or a query too
+#[test]
+fn test_bulk_move_note_and_track() {
+    let harness = CliTestHarness::new();
+
+    harness.run_success(&["status", "add", "in-progress", "--category", "pending"]);
+    let paint = harness.add_task(&["Paint fence", "--tag", "garden"]);
+    let mow = harness.add_task(&["Mow lawn", "--tag", "garden"]);
+    harness.add_task(&["Fix sink"]);
+
+    harness.run_success(&["move", "--where", "tag:garden", "in-progress", "--dry-run"])
+        .stdout(predicate::str::contains("Dry run: would move 2 tasks."));
+    harness.run_success(&["move", "--where", "tag:garden", "in-progress", "--yes"])
+        .stdout(predicate::str::contains("Moved 2 tasks."));
+    harness.run_success(&["list", "status:in-progress"])
+        .stdout(predicate::str::contains("Paint fence"))
+        .stdout(predicate::str::contains("Mow lawn"))
+        .stdout(predicate::str::contains("Fix sink").not());
+    harness.run_failure(&["move", "in-progress"]);
+
+    harness.run_success(&["note", &paint, &mow, "Wait for a dry day", "--yes"])
+        .stdout(predicate::str::contains("Added a note to 2 tasks."));
+    harness.run_success(&["list", "note:dry"])
+        .stdout(predicate::str::contains("Paint fence"))
+        .stdout(predicate::str::contains("Mow lawn"));
+    harness.run_failure(&["note", "--where", "tag:garden"]);
+
+    harness.run_success(&["track", "--where", "tag:garden", "30m", "--yes"])
+        .stdout(predicate::str::contains("Logged time on 2 tasks."));
+    harness.run_success(&["track", &paint, "15m"])
+        .stdout(predicate::str::contains("(total 45m)"));
+}
+
+/// Bulk queries act on occurrences and leave the series templates they match alone
+#[test]
+fn test_bulk_where_skips_series_templates() {
+    let harness = CliTestHarness::new();
+    let rent = harness.add_task(&["Pay rent", "--every", "monthly", "--at", "10:00", "--timezone", "UTC"]);
+    harness.add_task(&["Pay invoice"]);
+
+    harness.run_success(&["do", "--where", "name:Pay", "--dry-run"])
+        .stdout(predicate::str::contains("Pay invoice"))
+        .stdout(predicate::str::contains("Pay rent").not())
+        .stdout(predicate::str::contains("Dry run: would complete 1 task."));
+    harness.run_success(&["cancel", "--where", "name:rent", "--yes"])
+        .stdout(predicate::str::contains("No tasks matched."));
+    harness.run_success(&["delete", "--where", "name:Pay", "--force"])
+        .stdout(predicate::str::contains("Deleted 1 task."));
+
+    harness.run_success(&["recur", "list"])
+        .stdout(predicate::str::contains(rent.as_str()));
+    harness.run_success(&["show", &rent])
+        .stdout(predicate::str::contains("pending"));
+}
+
+#[test]
+fn test_subtask_completion_policy() {
+    let harness = CliTestHarness::new();
+    let config_dir = harness.config_home().join("rusk");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(
+        config_dir.join("config.toml"),
+        "[completion]\nsubtasks = \"block\"\ncomplete_parent = true\n",
+    )
+    .unwrap();
+
+    let parent = harness.add_task(&["Plan trip"]);
+    let flights = harness.add_task(&["Book flights", "--parent", &parent]);
+    let hotel = harness.add_task(&["Book hotel", "--parent", &parent]);
+
+    harness.run_failure(&["do", &parent])
+        .stderr(predicate::str::contains("pending subtasks"))
+        .stderr(predicate::str::contains("Book flights, Book hotel"));
+
+    harness.run_success(&["do", &flights]);
+    harness.run_success(&["list", "all"])
+        .stdout(predicate::str::contains("Progress"))
+        .stdout(predicate::str::contains("1/2 (50%)"));
+    harness.run_success(&["show", &parent])
+        .stdout(predicate::str::contains("1/2 (50%)"));
+
+    // The last subtask completes the parent
+    harness.run_success(&["do", &hotel]);
+    harness.run_success(&["list"])
+        .stdout(predicate::str::contains("Plan trip").not());
+}
+
+/// Test overriding a single occurrence and reverting it
+#[test]
+fn test_recur_override() {
+    use chrono::{Duration, Utc};
+
+    let harness = CliTestHarness::new();
+    harness.run_success(&["project", "add", "Garden"]);
+    let template = harness.add_task(&["Water plants", "--due", "tomorrow", "--every", "daily", "--timezone", "UTC"]);
+    let day = (Utc::now() + Duration::days(3)).format("%Y-%m-%d").to_string();
+
+    harness.run_success(&[
+        "recur", "override", &template, "--on", &day,
+        "--name", "Water plants (porch)", "--priority", "high", "--project", "Garden", "--tag", "outside", "--at", "18:30",
+    ])
+        .stdout(predicate::str::contains(format!("Occurrence on {} overridden", day)));
+    harness.run_success(&["list", "tag:outside"])
+        .stdout(predicate::str::contains("Water plants (porch)"))
+        .stdout(predicate::str::contains("Garden"));
+    harness.run_success(&["recur", "exceptions", &template])
+        .stdout(predicate::str::contains("Override"));
+
+    harness.run_failure(&["recur", "override", &template, "--on", &day, "--name", "Again"])
+        .stderr(predicate::str::contains("already has an exception (override)"));
+    let before_start = (Utc::now() - Duration::days(3)).format("%Y-%m-%d").to_string();
+    harness.run_failure(&["recur", "override", &template, "--on", &before_start, "--name", "Early"])
+        .stderr(predicate::str::contains("Series has no occurrence"));
+
+    harness.run_success(&["recur", "override", &template, "--on", &day, "--revert"])
+        .stdout(predicate::str::contains("reverted to the series defaults"));
+    harness.run_success(&["recur", "exceptions", &template])
+        .stdout(predicate::str::contains("No exceptions found"));
+    harness.run_success(&["list"])
+        .stdout(predicate::str::contains("Water plants (porch)").not());
+    harness.run_failure(&["recur", "override", &template, "--on", &day, "--revert"])
+        .stderr(predicate::str::contains("No overridden occurrence"));
+
+    // Without --tag the occurrence keeps its tags
+    let tomorrow = (Utc::now() + Duration::days(1)).format("%Y-%m-%d").to_string();
+    harness.run_success(&["edit", "--where", "name:plants and due:tomorrow", "--add-tag", "indoors", "--yes"]);
+    harness.run_success(&["recur", "override", &template, "--on", &tomorrow, "--name", "Water plants early"]);
+    harness.run_success(&["list", "tag:indoors"])
+        .stdout(predicate::str::contains("Water plants early"));
+}
+
+/// Test skipping and moving occurrences by pattern
+#[test]
+fn test_recur_pattern() {
+    use chrono::{Datelike, Duration, Utc};
+
+    let harness = CliTestHarness::new();
+    let template = harness.add_task(&["Stand-up", "--due", "tomorrow", "--every", "daily", "--timezone", "UTC"]);
+    let from = (Utc::now() + Duration::days(1)).format("%Y-%m-%d").to_string();
+    let to = (Utc::now() + Duration::days(14)).format("%Y-%m-%d").to_string();
+
+    harness.run_success(&["recur", "pattern", &template, "--skip", "--from", &from, "--to", &to, "--every-nth", "2", "--dry-run"])
+        .stdout(predicate::str::contains("(6 matching occurrences)"))
+        .stdout(predicate::str::contains("Dry run"));
+    harness.run_success(&["recur", "exceptions", &template])
+        .stdout(predicate::str::contains("No exceptions found"));
+
+    let calendar_dir = tempfile::tempdir().unwrap();
+    let calendar = calendar_dir.path().join("holidays.txt");
+    let holiday = Utc::now() + Duration::days(5);
+    std::fs::write(
+        &calendar,
+        format!("# Holidays\n{} Company day off\n\n2001-01-01\n", holiday.format("%Y-%m-%d")),
+    ).unwrap();
+    harness.run_success(&["recur", "pattern", &template, "--skip", "--calendar", calendar.to_str().unwrap(), "--yes"])
+        .stdout(predicate::str::contains("1 occurrence(s) skipped"));
+
+    let overridden = (holiday + Duration::days(7)).format("%Y-%m-%d").to_string();
+    harness.run_success(&["recur", "override", &template, "--on", &overridden, "--name", "Long stand-up"]);
+    let weekday = holiday.weekday().to_string();
+    let to = (Utc::now() + Duration::days(21)).format("%Y-%m-%d").to_string();
+    harness.run_success(&["recur", "pattern", &template, "--move-to", "sun", "--weekday", &weekday, "--from", &from, "--to", &to, "--yes"])
+        .stdout(predicate::str::contains("1 occurrence(s) moved"))
+        .stdout(predicate::str::contains("1 occurrence(s) left unchanged"))
+        .stdout(predicate::str::contains("already has an exception"));
+    harness.run_success(&["recur", "exceptions", &template])
+        .stdout(predicate::str::contains("Skip"))
+        .stdout(predicate::str::contains("Override"))
+        .stdout(predicate::str::contains("Move"));
+
+    harness.run_failure(&["recur", "pattern", &template, "--skip", "--month", "13"])
+        .stderr(predicate::str::contains("Invalid month '13'"));
+    harness.run_failure(&["recur", "pattern", &template, "--month", "aug"]);
+}
+
+/// Test adding and removing rule lines of a series
+#[test]
+fn test_recur_rules() {
+    let harness = CliTestHarness::new();
+    let template = harness.add_task(&["Pay bills", "--due", "tomorrow", "--every", "monthly", "--timezone", "UTC"]);
+
+    harness.run_success(&["recur", "rules", &template])
+        .stdout(predicate::str::contains("1. RRULE:FREQ=MONTHLY"));
+    harness.run_success(&[
+        "recur", "rules", &template,
+        "--add", "FREQ=MONTHLY;BYDAY=-1FR", "--add", "EXRULE:FREQ=YEARLY;BYMONTH=8", "--add-date", "2030-12-24",
+    ])
+        .stdout(predicate::str::contains("Added: "))
+        .stdout(predicate::str::contains("every month and every month on the last Friday, plus 1 more date, except every year in August"));
+    harness.run_success(&["recur", "rules", &template])
+        .stdout(predicate::str::contains("2. RRULE:FREQ=MONTHLY;BYDAY=-1FR"))
+        .stdout(predicate::str::contains("3. EXRULE:FREQ=YEARLY;BYMONTH=8"))
+        .stdout(predicate::str::contains("4. RDATE;TZID=UTC:20301224T"));
+
+    harness.run_success(&["recur", "rules", &template, "--remove", "2", "--remove", "3"])
+        .stdout(predicate::str::contains("Series now repeats every month, plus 1 more date"));
+    harness.run_failure(&["recur", "rules", &template, "--remove", "9"])
+        .stderr(predicate::str::contains("No rule line 9"));
+    harness.run_failure(&["recur", "rules", &template, "--remove", "1", "--remove", "2"])
+        .stderr(predicate::str::contains("at least one RRULE or RDATE"));
+    harness.run_failure(&["recur", "rules", &template, "--add", "EXRULE:FREQ=BOGUS"])
+        .stderr(predicate::str::contains("Invalid RRULE"));
+}
+
+#[test]
+fn test_all_day_and_floating_series() {
+    let harness = CliTestHarness::new();
+    let birthday = harness.add_task(&["Birthday", "--due", "2030-06-14", "--every", "yearly", "--all-day"]);
+    harness.run_success(&["show", &birthday])
+        .stdout(predicate::str::contains("2030-06-14 (all day)"));
+    harness.run_success(&["recur", "info", &birthday])
+        .stdout(predicate::str::contains("DTSTART;VALUE=DATE:20300614"))
+        .stdout(predicate::str::contains("all-day"));
+    harness.run_success(&["recur", "rules", &birthday, "--add-date", "2030-12-24"]);
+    harness.run_success(&["recur", "rules", &birthday])
+        .stdout(predicate::str::contains("RDATE;VALUE=DATE:20301224"));
+
+    let walk = harness.add_task(&["Morning walk", "--due", "tomorrow", "--every", "daily", "--at", "8:00", "--floating"]);
+    harness.run_success(&["recur", "info", &walk])
+        .stdout(predicate::str::contains("floating"));
+    // Paused series are not materialized, so this one keeps its stored timezone
+    harness.run_success(&["recur", "pause", &walk, "--yes"]);
+    harness.command()
+        .env("TZ", "Pacific/Auckland")
+        .args(["show", &walk])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("floating (currently Pacific/Auckland)"));
+
+    harness.run_failure(&["add", "Walk", "--floating"])
+        .stderr(predicate::str::contains("only applies to recurring tasks"));
+    harness.run_failure(&["add", "Walk", "--all-day", "--timezone", "UTC"]);
+}
+
+#[test]
+fn test_series_dst_policy() {
+    let harness = CliTestHarness::new();
+    let backup = harness.add_task(&[
+        "Night backup", "--due", "2030-03-09", "--every", "daily", "--at", "2:30",
+        "--timezone", "America/New_York", "--dst-gap", "skip",
+    ]);
+    harness.run_success(&["recur", "dst", &backup])
+        .stdout(predicate::str::contains("skip"))
+        .stdout(predicate::str::contains("earliest"));
+    harness.run_success(&["recur", "dst", &backup, "--overlap", "latest"])
+        .stdout(predicate::str::contains("gap: skip, overlap: latest"));
+    harness.run_success(&["recur", "info", &backup])
+        .stdout(predicate::str::contains("DST policy"))
+        .stdout(predicate::str::contains("gap: skip, overlap: latest"));
+
+    harness.run_failure(&["recur", "dst", &backup, "--gap", "sideways"]);
+    harness.run_failure(&["add", "Backup", "--dst-gap", "skip"])
+        .stderr(predicate::str::contains("only apply to recurring tasks"));
+}
+
+/// Test the timeline of a series
+#[test]
+fn test_recur_history() {
+    use chrono::{Duration, Utc};
+
+    let harness = CliTestHarness::new();
+    let start = (Utc::now() - Duration::days(3)).format("%Y-%m-%d").to_string();
+    let template = harness.add_task(&["Water plants", "--due", &start, "--every", "daily", "--at", "9:00", "--timezone", "UTC"]);
+    harness.run_success(&["recur", "history", &template])
+        .stdout(predicate::str::contains("Series History"))
+        .stdout(predicate::str::contains("missed"))
+        .stdout(predicate::str::contains("Next occurrence"));
+
+    let from = (Utc::now() + Duration::days(2)).format("%Y-%m-%d").to_string();
+    let to = (Utc::now() + Duration::days(3)).format("%Y-%m-%d").to_string();
+    harness.run_success(&["recur", "pattern", &template, "--skip", "--from", &from, "--to", &to, "--yes"]);
+    harness.run_success(&["recur", "rules", &template, "--add", "EXRULE:FREQ=YEARLY;BYMONTH=8"]);
+    harness.run_success(&["recur", "history", &template])
+        .stdout(predicate::str::contains("Skip"))
+        .stdout(predicate::str::contains("Skipped by pattern"))
+        .stdout(predicate::str::contains("Rule edit"))
+        .stdout(predicate::str::contains("except every year in August"))
+        .stdout(predicate::str::contains("1 rule edit(s)"));
+    harness.run_success(&["recur", "history", &template, "--missed"])
+        .stdout(predicate::str::contains("missed"))
+        .stdout(predicate::str::contains("Rule edit").not());
+
+    harness.run_failure(&["recur", "history", &template, "--since", "not a date"]);
+}
+
+/// Test habit statistics in recur stats, as text and as JSON
+#[test]
+fn test_recur_stats_habits() {
+    use chrono::{Duration, Utc};
+
+    let harness = CliTestHarness::new();
+    let start = (Utc::now() - Duration::days(3)).format("%Y-%m-%d").to_string();
+    let template = harness.add_task(&["Stretch", "--due", &start, "--every", "daily", "--at", "9:00", "--timezone", "UTC"]);
+
+    harness.run_success(&["recur", "stats", &template])
+        .stdout(predicate::str::contains("Series Statistics"))
+        .stdout(predicate::str::contains("Current Streak").not());
+    harness.run_success(&["recur", "stats", &template, "--detailed"])
+        .stdout(predicate::str::contains("Current Streak: 0 on time (longest 0)"))
+        .stdout(predicate::str::contains("Completions by Weekday"))
+        .stdout(predicate::str::contains("Last 7 days"));
+
+    let output = harness.run_success(&["recur", "stats", &template, "--json"]).get_output().stdout.clone();
+    let stats: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert!(stats["total_occurrences_created"].as_u64().unwrap() > 0);
+    let habits = &stats["habits"];
+    assert_eq!(habits["current_streak"], 0);
+    assert_eq!(habits["on_time"], 0);
+    assert!(habits["due"].as_u64().unwrap() >= 2);
+    assert_eq!(habits["weekdays"].as_array().unwrap().len(), 7);
+    assert_eq!(habits["trends"][0]["days"], 7);
+}
+
+/// Test listing series and pausing, resuming and moving them by pattern
+#[test]
+fn test_recur_list_and_bulk_changes() {
+    let harness = CliTestHarness::new();
+    let standup = harness.add_task(&["Standup", "--every", "weekdays", "--at", "9:00 AM", "--timezone", "Europe/Berlin"]);
+    harness.add_task(&["Rent", "--every", "monthly", "--at", "10:00", "--timezone", "UTC"]);
+    harness.add_task(&["Pills", "--every", "daily", "--at", "8:00 AM", "--floating"]);
+
+    harness.run_success(&["recur", "list"])
+        .stdout(predicate::str::contains(standup.as_str()))
+        .stdout(predicate::str::contains("every weekday at 09:00"))
+        .stdout(predicate::str::contains("Europe/Berlin"))
+        .stdout(predicate::str::contains("floating"))
+        .stdout(predicate::str::contains("Rent"));
+    harness.run_success(&["recur", "list", "rent"])
+        .stdout(predicate::str::contains("Rent"))
+        .stdout(predicate::str::contains("Standup").not());
+
+    harness.run_success(&["recur", "pause", "--where", "rent", "--yes"])
+        .stdout(predicate::str::contains("Paused 1 series."));
+    harness.run_success(&["recur", "list", "rent"])
+        .stdout(predicate::str::contains("Paused"));
+    harness.run_success(&["recur", "resume", "--where", "", "--yes"])
+        .stdout(predicate::str::contains("Leaving 2 series alone: already active"))
+        .stdout(predicate::str::contains("Resumed 1 series."));
+
+    harness.run_success(&["recur", "set-timezone", "America/New_York", "--where", "", "--dry-run"])
+        .stdout(predicate::str::contains("Leaving 1 series alone: not tied to a timezone"))
+        .stdout(predicate::str::contains("Dry run: would move 2 series to America/New_York."));
+    harness.run_success(&["recur", "list", "standup"])
+        .stdout(predicate::str::contains("Europe/Berlin"));
+    harness.run_success(&["recur", "set-timezone", "America/New_York", "--where", "", "--yes"])
+        .stdout(predicate::str::contains("Moved 2 series to America/New_York."));
+    harness.run_success(&["recur", "list", "standup"])
+        .stdout(predicate::str::contains("America/New_York"))
+        .stdout(predicate::str::contains("09:00 E"));
+    harness.run_success(&["recur", "set-timezone", "Asia/Tokyo", &standup])
+        .stdout(predicate::str::contains("Series moved to Asia/Tokyo"));
+    harness.run_success(&["recur", "list", "standup"])
+        .stdout(predicate::str::contains("09:00 JST"));
+
+    harness.run_failure(&["recur", "set-timezone", "Nowhere/City", "--where", ""]);
+    harness.run_failure(&["recur", "pause"]);
+    harness.run_success(&["recur", "pause", "--where", "nothing-like-this"])
+        .stdout(predicate::str::contains("No series matched."));
+}
+
+/// Test saving a blueprint from a task tree and applying it at a new date
+#[test]
+fn test_blueprint_save_and_apply() {
+    let harness = CliTestHarness::new();
+    harness.run_success(&["project", "add", "Alice"]);
+    let root = harness.add_task(&["Onboard", "--due", "2026-01-05", "--tag", "hr"]);
+    let laptop = harness.add_task(&["Set up laptop", "--due", "2026-01-06", "--parent", &root]);
+    harness.add_task(&["Create accounts", "--due", "2026-01-07", "--parent", &root, "--depends-on", &laptop]);
+
+    harness.run_success(&["blueprint", "save", "onboarding", "--task", &root, "--description", "First weeks"])
+        .stdout(predicate::str::contains("Saved blueprint 'onboarding' with 3 task(s)"));
+    harness.run_failure(&["blueprint", "save", "onboarding", "--task", &root]);
+    harness.run_success(&["blueprint", "list"])
+        .stdout(predicate::str::contains("onboarding"))
+        .stdout(predicate::str::contains("First weeks"));
+    harness.run_success(&["blueprint", "show", "onboarding"])
+        .stdout(predicate::str::contains("Set up laptop"))
+        .stdout(predicate::str::contains("day 2 00:00"))
+        .stdout(predicate::str::contains("#2"));
+
+    harness.run_failure(&["blueprint", "apply", "onboarding", "--start", "2026-11-02", "--project", "Nobody"])
+        .stderr(predicate::str::contains("Project 'Nobody' not found"));
+    harness.run_success(&["blueprint", "apply", "onboarding", "--start", "2026-11-02", "--project", "Alice"])
+        .stdout(predicate::str::contains("Created 3 task(s)"))
+        .stdout(predicate::str::contains("Create accounts (due 2026-11-04 00:00 UTC)"));
+    harness.run_success(&["list", "project:Alice"])
+        .stdout(predicate::str::contains("Set up laptop"));
+
+    harness.run_success(&["blueprint", "delete", "onboarding"]);
+    harness.run_failure(&["blueprint", "show", "onboarding"]);
+}
+
+/// Test deleting occurrences, the rest of a series or all of it, and the doctor check
+#[test]
+fn test_delete_scopes_and_doctor() {
+    use chrono::{Duration, Utc};
+
+    let harness = CliTestHarness::new();
+    let template = harness.add_task(&["Water plants", "--due", "tomorrow", "--every", "daily", "--timezone", "UTC"]);
+    let overridden = |days: i64| {
+        let day = (Utc::now() + Duration::days(days)).format("%Y-%m-%d").to_string();
+        harness.run_for_task_id(&["recur", "override", &template, "--on", &day, "--name", "Water porch"])
+    };
+
+    let occurrence = overridden(3);
+    harness.run_success(&["delete", &occurrence, "--scope", "occurrence", "--force"]);
+    harness.run_success(&["recur", "exceptions", &template])
+        .stdout(predicate::str::contains("Skip"));
+
+    let occurrence = overridden(6);
+    harness.run_success(&["delete", &occurrence, "--scope", "future", "--force"]);
+    harness.run_success(&["recur", "info", &template])
+        .stdout(predicate::str::contains(";UNTIL="));
+
+    harness.run_failure(&["delete", "--where", "name:\"Water plants\"", "--scope", "series", "--force"])
+        .stderr(predicate::str::contains("apply to a single task"));
+    harness.run_success(&["delete", &template, "--keep-history", "--force"])
+        .stdout(predicate::str::contains("Series deleted; completed occurrences were kept as regular tasks."));
+    harness.run_success(&["list"])
+        .stdout(predicate::str::contains("Water plants").not());
+    harness.run_success(&["doctor"])
+        .stdout(predicate::str::contains("No problems found."));
+}
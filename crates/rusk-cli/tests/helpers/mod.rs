@@ -28,10 +28,35 @@ impl CliTestHarness {
         
         // Set the database path via environment variable
         cmd.env("RUSK_DATABASE_PATH", &self.db_path);
+        self.isolate_dirs(&mut cmd);
         
         cmd
     }
     
+    /// Get a Command that resolves its database from XDG directories and profiles
+    pub fn xdg_command(&self) -> Command {
+        let mut cmd = Command::cargo_bin("rusk").expect("Failed to find rusk binary");
+        cmd.env_remove("RUSK_DATABASE_PATH");
+        self.isolate_dirs(&mut cmd);
+        cmd
+    }
+    
+    /// Point XDG data and config directories inside the temp directory
+    fn isolate_dirs(&self, cmd: &mut Command) {
+        cmd.env("XDG_DATA_HOME", self.data_home());
+        cmd.env("XDG_CONFIG_HOME", self.config_home());
+    }
+    
+    /// XDG data directory used by this test instance
+    pub fn data_home(&self) -> PathBuf {
+        self.temp_dir.path().join("data")
+    }
+    
+    /// XDG config directory used by this test instance
+    pub fn config_home(&self) -> PathBuf {
+        self.temp_dir.path().join("config")
+    }
+    
     /// Get the database path for this test instance
     pub fn db_path(&self) -> &std::path::Path {
         &self.db_path
@@ -59,6 +84,22 @@ impl CliTestHarness {
             .args(args)
             .assert()
     }
+
+    /// Add a task and return its full ID, taken from the creation message
+    pub fn add_task(&self, args: &[&str]) -> String {
+        self.run_for_task_id(&[&["add"], args].concat())
+    }
+
+    /// Runs a command that succeeds and returns the first full task ID it prints
+    pub fn run_for_task_id(&self, args: &[&str]) -> String {
+        let output = self.run_success(args).get_output().stdout.clone();
+        String::from_utf8(output)
+            .unwrap()
+            .split(|c: char| !(c.is_ascii_hexdigit() || c == '-'))
+            .find(|token| token.len() == 36 && token.matches('-').count() == 4)
+            .expect("task ID in command output")
+            .to_string()
+    }
 }
 
 /// Common test fixtures
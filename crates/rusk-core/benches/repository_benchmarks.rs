@@ -3,14 +3,14 @@ use rusk_core::repository::{SqliteRepository, Repository};
 use rusk_core::recurrence::MaterializationManager;
 use rusk_core::models::{NewTaskData, TaskStatus, TaskPriority};
 use rusk_core::query::{Query, Filter, DueDate};
-use rusk_core::db::establish_connection;
+use rusk_core::db::connect_sqlite;
 use chrono::{DateTime, Utc, Duration};
 use uuid::Uuid;
 use tokio::runtime::Runtime;
 use std::sync::Arc;
 
 async fn setup_test_repository() -> SqliteRepository {
-    let pool = establish_connection(":memory:").await.unwrap();
+    let pool = connect_sqlite(":memory:").await.unwrap();
     let materialization_manager = MaterializationManager::with_defaults();
     SqliteRepository::new(pool, materialization_manager)
 }
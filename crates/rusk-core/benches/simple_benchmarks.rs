@@ -1,6 +1,6 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use rusk_core::recurrence::{RecurrenceManager, MaterializationManager};
-use rusk_core::models::{TaskSeries, Task, TaskStatus, TaskPriority};
+use rusk_core::models::{DstPolicy, TaskSeries, Task, TaskStatus, TaskPriority, TimeMode};
 use chrono::{DateTime, Utc, Duration};
 use uuid::Uuid;
 
@@ -11,6 +11,8 @@ fn create_test_series(rrule: &str) -> TaskSeries {
         rrule: rrule.to_string(),
         dtstart: Utc::now(),
         timezone: "UTC".to_string(),
+        time_mode: TimeMode::Zoned,
+        dst_policy: DstPolicy::default(),
         active: true,
         last_materialized_until: None,
         created_at: Utc::now(),
@@ -32,6 +34,12 @@ fn create_test_task() -> Task {
         project_id: None,
         parent_id: None,
         series_id: None,
+        workflow_status: TaskStatus::Pending.as_str().to_string(),
+        estimate_minutes: None,
+        scheduled_at: None,
+        wait_until: None,
+        start_at: None,
+        all_day: false,
     }
 }
 
@@ -0,0 +1,5 @@
+-- Rollback reminders schema
+-- Migration: 20250901000000_reminders_schema.down.sql
+
+DROP TABLE IF EXISTS reminder_deliveries;
+DROP TABLE IF EXISTS task_reminders;
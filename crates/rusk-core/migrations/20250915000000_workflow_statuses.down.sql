@@ -0,0 +1,7 @@
+-- Rollback workflow statuses
+-- Migration: 20250915000000_workflow_statuses.down.sql
+
+DROP INDEX IF EXISTS idx_tasks_workflow_status;
+ALTER TABLE tasks DROP COLUMN workflow_status;
+DROP TABLE IF EXISTS workflow_transitions;
+DROP TABLE IF EXISTS workflow_statuses;
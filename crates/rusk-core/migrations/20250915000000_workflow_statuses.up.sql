@@ -0,0 +1,33 @@
+-- Custom workflow statuses
+-- Migration: 20250915000000_workflow_statuses.up.sql
+-- Purpose: User-definable statuses mapped onto the base pending/completed/cancelled
+-- categories, optional transition rules, and the workflow status of each task
+
+CREATE TABLE workflow_statuses (
+    name TEXT PRIMARY KEY NOT NULL,                  -- Lowercase status name (e.g. "in-progress")
+    category TEXT NOT NULL CHECK (category IN ('pending', 'completed', 'cancelled')),
+    position INTEGER NOT NULL DEFAULT 0,             -- Display order within the category
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Built-in statuses, one per base category
+INSERT INTO workflow_statuses (name, category, position) VALUES
+    ('pending', 'pending', 0),
+    ('completed', 'completed', 0),
+    ('cancelled', 'cancelled', 0);
+
+-- Allowed moves. A status without outgoing rows may move anywhere.
+CREATE TABLE workflow_transitions (
+    from_status TEXT NOT NULL,
+    to_status TEXT NOT NULL,
+    PRIMARY KEY (from_status, to_status),
+    FOREIGN KEY (from_status) REFERENCES workflow_statuses(name) ON DELETE CASCADE,
+    FOREIGN KEY (to_status) REFERENCES workflow_statuses(name) ON DELETE CASCADE
+);
+
+-- SQLite cannot add a foreign key column with a non-NULL default, so the
+-- reference to workflow_statuses is enforced by the repository instead
+ALTER TABLE tasks ADD COLUMN workflow_status TEXT NOT NULL DEFAULT 'pending';
+UPDATE tasks SET workflow_status = status;
+
+CREATE INDEX idx_tasks_workflow_status ON tasks(workflow_status);
@@ -0,0 +1,4 @@
+-- Rollback time tracking
+-- Migration: 20250920000000_time_entries.down.sql
+
+DROP TABLE IF EXISTS time_entries;
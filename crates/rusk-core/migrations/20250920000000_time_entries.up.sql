@@ -0,0 +1,20 @@
+-- Time tracking
+-- Migration: 20250920000000_time_entries.up.sql
+-- Purpose: Timers and manually logged work per task, used by `rusk start`,
+-- `rusk stop`, `rusk track` and `rusk timesheet`
+
+CREATE TABLE time_entries (
+    id TEXT PRIMARY KEY NOT NULL,                  -- UUID v7
+    task_id TEXT NOT NULL,                         -- Task the work was done on
+    started_at TIMESTAMP NOT NULL,
+    ended_at TIMESTAMP,                            -- NULL while the timer is running
+    duration_seconds INTEGER CHECK (duration_seconds >= 0),  -- Set once the entry is finished
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    CHECK ((ended_at IS NULL) = (duration_seconds IS NULL))
+);
+
+CREATE INDEX idx_time_entries_task_id ON time_entries(task_id);
+CREATE INDEX idx_time_entries_started_at ON time_entries(started_at);
+
+-- Only one timer may run at a time
+CREATE UNIQUE INDEX idx_time_entries_single_active ON time_entries((ended_at IS NULL)) WHERE ended_at IS NULL;
@@ -0,0 +1,21 @@
+-- Task annotations
+-- Migration: 20251005000000_annotations.up.sql
+-- Purpose: Timestamped notes on tasks, added with `rusk note`. A note on a
+-- series instance is detached to its occurrence while a series edit
+-- regenerates the instance, and re-attached once the occurrence is
+-- materialized again.
+
+CREATE TABLE annotations (
+    id TEXT PRIMARY KEY NOT NULL,                  -- UUID v7
+    task_id TEXT,                                  -- NULL while detached from a regenerated instance
+    series_id TEXT,                                -- Series of a detached note
+    occurrence_dt TIMESTAMP,                       -- Occurrence of a detached note (UTC)
+    text TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (series_id) REFERENCES task_series(id) ON DELETE CASCADE,
+    CHECK (task_id IS NOT NULL OR (series_id IS NOT NULL AND occurrence_dt IS NOT NULL))
+);
+
+CREATE INDEX idx_annotations_task_id ON annotations(task_id);
+CREATE INDEX idx_annotations_occurrence ON annotations(series_id, occurrence_dt);
@@ -0,0 +1,9 @@
+-- Daylight saving policy per series
+-- Migration: 20251015000000_series_dst_policy.up.sql
+-- Purpose: Decide where occurrences go whose local time does not exist
+-- (spring forward: shift_forward, shift_backward or skip) or exists twice
+-- (fall back: earliest or latest). The defaults match how occurrences were
+-- generated before.
+
+ALTER TABLE task_series ADD COLUMN dst_gap TEXT NOT NULL DEFAULT 'shift_forward';
+ALTER TABLE task_series ADD COLUMN dst_overlap TEXT NOT NULL DEFAULT 'earliest';
@@ -0,0 +1,4 @@
+-- Rollback series rule edits
+-- Migration: 20251020000000_series_rule_changes.down.sql
+
+DROP TABLE IF EXISTS series_rule_changes;
@@ -0,0 +1,19 @@
+-- Series rule edits
+-- Migration: 20251020000000_series_rule_changes.up.sql
+-- Purpose: Keep the schedule a series had before each edit of its RRULE,
+-- start or timezone, so `rusk recur history` can show when the rule changed.
+
+CREATE TABLE series_rule_changes (
+    id TEXT PRIMARY KEY NOT NULL,                  -- UUID v7
+    series_id TEXT NOT NULL,
+    changed_at TIMESTAMP NOT NULL,
+    old_rrule TEXT NOT NULL,
+    new_rrule TEXT NOT NULL,
+    old_dtstart TIMESTAMP NOT NULL,
+    new_dtstart TIMESTAMP NOT NULL,
+    old_timezone TEXT NOT NULL,
+    new_timezone TEXT NOT NULL,
+    FOREIGN KEY (series_id) REFERENCES task_series(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_series_rule_changes_series_id ON series_rule_changes(series_id, changed_at);
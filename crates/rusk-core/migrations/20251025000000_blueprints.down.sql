@@ -0,0 +1,7 @@
+-- Rollback task blueprints
+-- Migration: 20251025000000_blueprints.down.sql
+
+DROP TABLE IF EXISTS blueprint_task_dependencies;
+DROP TABLE IF EXISTS blueprint_task_tags;
+DROP TABLE IF EXISTS blueprint_tasks;
+DROP TABLE IF EXISTS blueprints;
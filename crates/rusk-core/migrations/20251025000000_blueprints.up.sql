@@ -0,0 +1,45 @@
+-- Task blueprints
+-- Migration: 20251025000000_blueprints.up.sql
+-- Purpose: Reusable plans of tasks, saved with `rusk blueprint save` and
+-- instantiated relative to an anchor date with `rusk blueprint apply`. Tasks
+-- of a blueprint are keyed by their position in it, which parents,
+-- dependencies and tags refer to.
+
+CREATE TABLE blueprints (
+    id TEXT PRIMARY KEY NOT NULL,                  -- UUID v7
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    created_at TIMESTAMP NOT NULL
+);
+
+CREATE TABLE blueprint_tasks (
+    blueprint_id TEXT NOT NULL,
+    position INTEGER NOT NULL,                     -- 0-based; parents come before their subtasks
+    parent_position INTEGER,
+    name TEXT NOT NULL,
+    description TEXT,
+    priority TEXT NOT NULL DEFAULT 'none',
+    due_offset_minutes INTEGER,                    -- Due time relative to the anchor date
+    time_mode TEXT NOT NULL DEFAULT 'zoned',
+    estimate_minutes INTEGER,
+    rrule TEXT,                                    -- Series rule lines without DTSTART, for recurring tasks
+    timezone TEXT,
+    PRIMARY KEY (blueprint_id, position),
+    FOREIGN KEY (blueprint_id) REFERENCES blueprints(id) ON DELETE CASCADE
+);
+
+CREATE TABLE blueprint_task_tags (
+    blueprint_id TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    tag_name TEXT NOT NULL,
+    PRIMARY KEY (blueprint_id, position, tag_name),
+    FOREIGN KEY (blueprint_id, position) REFERENCES blueprint_tasks(blueprint_id, position) ON DELETE CASCADE
+);
+
+CREATE TABLE blueprint_task_dependencies (
+    blueprint_id TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    depends_on_position INTEGER NOT NULL,
+    PRIMARY KEY (blueprint_id, position, depends_on_position),
+    FOREIGN KEY (blueprint_id, position) REFERENCES blueprint_tasks(blueprint_id, position) ON DELETE CASCADE
+);
@@ -0,0 +1,5 @@
+-- Drop all tables in reverse order of creation
+DROP TABLE task_dependencies;
+DROP TABLE task_tags;
+DROP TABLE tasks;
+DROP TABLE projects;
@@ -0,0 +1,54 @@
+-- PostgreSQL counterpart of ../20250807121500_initial_schema.up.sql
+-- Ids are native UUIDs and timestamps are stored as TIMESTAMPTZ (UTC)
+
+-- Create the projects table
+CREATE TABLE projects (
+    id UUID PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Create the tasks table with all columns, including rrule and recurrence_template_id
+CREATE TABLE tasks (
+    id UUID PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    status TEXT NOT NULL DEFAULT 'pending',
+    priority TEXT NOT NULL DEFAULT 'none',
+    due_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    project_id UUID,
+    parent_id UUID,
+    rrule TEXT,
+    recurrence_template_id UUID,
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE SET NULL,
+    FOREIGN KEY (parent_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (recurrence_template_id) REFERENCES tasks(id) ON DELETE SET NULL
+);
+
+-- Create the task_tags table
+CREATE TABLE task_tags (
+    task_id UUID NOT NULL,
+    tag_name TEXT NOT NULL,
+    PRIMARY KEY (task_id, tag_name),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+-- Create the task_dependencies table
+CREATE TABLE task_dependencies (
+    task_id UUID NOT NULL,
+    depends_on_id UUID NOT NULL,
+    PRIMARY KEY (task_id, depends_on_id),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (depends_on_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+-- Create all necessary indexes for performance
+CREATE INDEX idx_tasks_status ON tasks(status);
+CREATE INDEX idx_tasks_priority ON tasks(priority);
+CREATE INDEX idx_tasks_due_at ON tasks(due_at);
+CREATE INDEX idx_tasks_project_id ON tasks(project_id);
+CREATE INDEX idx_tasks_parent_id ON tasks(parent_id);
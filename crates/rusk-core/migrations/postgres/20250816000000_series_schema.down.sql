@@ -0,0 +1,14 @@
+-- Rollback series-based recurrence to template-instance model
+-- Note: This is primarily for development; production should not use rollbacks
+
+DROP INDEX IF EXISTS idx_tasks_series_status;
+DROP INDEX IF EXISTS idx_tasks_status_due;
+DROP INDEX IF EXISTS idx_tasks_due_at_series;
+DROP INDEX IF EXISTS idx_tasks_series_id;
+
+ALTER TABLE tasks DROP COLUMN series_id;
+ALTER TABLE tasks ADD COLUMN rrule TEXT;
+ALTER TABLE tasks ADD COLUMN recurrence_template_id UUID REFERENCES tasks(id) ON DELETE SET NULL;
+
+DROP TABLE IF EXISTS series_exceptions;
+DROP TABLE IF EXISTS task_series;
@@ -0,0 +1,55 @@
+-- PostgreSQL counterpart of ../20250816000000_series_schema.up.sql
+-- Purpose: Transform from template-instance to series-based recurrence model
+
+-- Create the task_series table for managing recurring series
+CREATE TABLE task_series (
+    id UUID PRIMARY KEY NOT NULL,                    -- UUIDv7, time-ordered for performance
+    template_task_id UUID NOT NULL UNIQUE,          -- Foreign key to template task (unique constraint)
+    rrule TEXT NOT NULL,                             -- Canonical RFC 5545 recurrence rule with DTSTART
+    dtstart TIMESTAMPTZ NOT NULL,                    -- Series start time in UTC
+    timezone TEXT NOT NULL DEFAULT 'UTC',           -- IANA timezone name (e.g., "America/New_York")
+    active BOOLEAN NOT NULL DEFAULT TRUE,           -- Whether series is currently generating instances
+    last_materialized_until TIMESTAMPTZ,            -- Boundary for idempotent materialization
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,  -- Series creation timestamp
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,  -- Last modification timestamp
+    FOREIGN KEY (template_task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+-- Create the series_exceptions table for handling deviations from series pattern
+CREATE TABLE series_exceptions (
+    series_id UUID NOT NULL,                        -- Foreign key to task_series
+    occurrence_dt TIMESTAMPTZ NOT NULL,             -- Original scheduled occurrence time (UTC)
+    exception_type TEXT NOT NULL,                   -- Type of exception (skip|override|move)
+    exception_task_id UUID,                         -- Reference to custom task (for override/move)
+    notes TEXT,                                     -- Optional explanation for the exception
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,  -- Exception creation timestamp
+    PRIMARY KEY (series_id, occurrence_dt),
+    FOREIGN KEY (series_id) REFERENCES task_series(id) ON DELETE CASCADE,
+    FOREIGN KEY (exception_task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    -- Constraint: exception_task_id required for override/move, forbidden for skip
+    CHECK (
+        (exception_type = 'skip' AND exception_task_id IS NULL) OR
+        (exception_type IN ('override', 'move') AND exception_task_id IS NOT NULL)
+    )
+);
+
+-- Remove legacy recurrence fields from tasks table and add series_id.
+-- Unlike SQLite, PostgreSQL can alter the table in place.
+ALTER TABLE tasks DROP COLUMN recurrence_template_id;
+ALTER TABLE tasks DROP COLUMN rrule;
+ALTER TABLE tasks ADD COLUMN series_id UUID REFERENCES task_series(id) ON DELETE SET NULL;
+
+-- Primary performance indexes for task_series
+CREATE INDEX idx_task_series_template_task_id ON task_series(template_task_id);
+CREATE INDEX idx_task_series_active ON task_series(active);
+CREATE INDEX idx_task_series_materialized ON task_series(last_materialized_until);
+
+-- Performance indexes for series_exceptions
+CREATE INDEX idx_series_exceptions_series_id ON series_exceptions(series_id);
+CREATE INDEX idx_series_exceptions_lookup ON series_exceptions(series_id, occurrence_dt);
+
+-- Enhanced indexes for tasks table with series support
+CREATE INDEX idx_tasks_series_id ON tasks(series_id);
+CREATE INDEX idx_tasks_due_at_series ON tasks(due_at, series_id);
+CREATE INDEX idx_tasks_status_due ON tasks(status, due_at);
+CREATE INDEX idx_tasks_series_status ON tasks(series_id, status);
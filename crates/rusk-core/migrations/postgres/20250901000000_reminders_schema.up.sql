@@ -0,0 +1,21 @@
+-- PostgreSQL counterpart of ../20250901000000_reminders_schema.up.sql
+-- Purpose: Per-task reminder offsets and a delivery log used by `rusk daemon`
+
+-- Reminder offset per task. Series instances inherit the offset of their
+-- template task, so recurring tasks only need a single row.
+CREATE TABLE task_reminders (
+    task_id UUID PRIMARY KEY NOT NULL,              -- Task (or series template) to remind about
+    offset_minutes BIGINT NOT NULL CHECK (offset_minutes >= 0),  -- How long before due_at to fire
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+-- Delivery log so each (task, due time) pair is announced exactly once.
+-- Keyed on due_at so that rescheduling a task re-arms its reminder.
+CREATE TABLE reminder_deliveries (
+    task_id UUID NOT NULL,
+    due_at TIMESTAMPTZ NOT NULL,
+    delivered_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (task_id, due_at),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
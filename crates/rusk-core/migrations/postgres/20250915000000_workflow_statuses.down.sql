@@ -0,0 +1,6 @@
+-- Rollback workflow statuses
+-- Migration: 20250915000000_workflow_statuses.down.sql
+
+ALTER TABLE tasks DROP COLUMN IF EXISTS workflow_status;
+DROP TABLE IF EXISTS workflow_transitions;
+DROP TABLE IF EXISTS workflow_statuses;
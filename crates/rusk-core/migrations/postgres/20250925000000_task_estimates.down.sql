@@ -0,0 +1,4 @@
+-- Rollback task effort estimates
+-- Migration: 20250925000000_task_estimates.down.sql
+
+ALTER TABLE tasks DROP COLUMN estimate_minutes;
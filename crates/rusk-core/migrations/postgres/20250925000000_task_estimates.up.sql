@@ -0,0 +1,5 @@
+-- PostgreSQL counterpart of ../20250925000000_task_estimates.up.sql
+-- Purpose: Estimated effort per task, inherited by series instances from
+-- their template and summed per day by `rusk forecast`
+
+ALTER TABLE tasks ADD COLUMN estimate_minutes BIGINT CHECK (estimate_minutes IS NULL OR estimate_minutes > 0);
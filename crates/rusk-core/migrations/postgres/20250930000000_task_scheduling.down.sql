@@ -0,0 +1,7 @@
+-- Rollback task scheduling dates
+-- Migration: 20250930000000_task_scheduling.down.sql
+
+DROP INDEX IF EXISTS idx_tasks_wait_until;
+ALTER TABLE tasks DROP COLUMN start_at;
+ALTER TABLE tasks DROP COLUMN wait_until;
+ALTER TABLE tasks DROP COLUMN scheduled_at;
@@ -0,0 +1,9 @@
+-- PostgreSQL counterpart of ../20250930000000_task_scheduling.up.sql
+-- Purpose: Dates that control when a task becomes actionable, separate from
+-- its due date. Series instances keep their template's distance from due.
+
+ALTER TABLE tasks ADD COLUMN scheduled_at TIMESTAMPTZ;
+ALTER TABLE tasks ADD COLUMN wait_until TIMESTAMPTZ;
+ALTER TABLE tasks ADD COLUMN start_at TIMESTAMPTZ;
+
+CREATE INDEX idx_tasks_wait_until ON tasks(wait_until);
@@ -0,0 +1,4 @@
+-- Rollback task annotations
+-- Migration: 20251005000000_annotations.down.sql
+
+DROP TABLE IF EXISTS annotations;
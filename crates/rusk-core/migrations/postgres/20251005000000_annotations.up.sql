@@ -0,0 +1,20 @@
+-- PostgreSQL counterpart of ../20251005000000_annotations.up.sql
+-- Purpose: Timestamped notes on tasks, added with `rusk note`. A note on a
+-- series instance is detached to its occurrence while a series edit
+-- regenerates the instance, and re-attached once the occurrence is
+-- materialized again.
+
+CREATE TABLE annotations (
+    id UUID PRIMARY KEY NOT NULL,
+    task_id UUID,                                  -- NULL while detached from a regenerated instance
+    series_id UUID,                                -- Series of a detached note
+    occurrence_dt TIMESTAMPTZ,                     -- Occurrence of a detached note
+    text TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (series_id) REFERENCES task_series(id) ON DELETE CASCADE,
+    CHECK (task_id IS NOT NULL OR (series_id IS NOT NULL AND occurrence_dt IS NOT NULL))
+);
+
+CREATE INDEX idx_annotations_task_id ON annotations(task_id);
+CREATE INDEX idx_annotations_occurrence ON annotations(series_id, occurrence_dt);
@@ -0,0 +1,5 @@
+-- Rollback floating-time and all-day series
+-- Migration: 20251010000000_series_time_modes.down.sql
+
+ALTER TABLE tasks DROP COLUMN all_day;
+ALTER TABLE task_series DROP COLUMN time_mode;
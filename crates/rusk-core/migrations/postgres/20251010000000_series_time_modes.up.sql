@@ -0,0 +1,8 @@
+-- PostgreSQL counterpart of ../20251010000000_series_time_modes.up.sql
+-- Purpose: A series is either zoned (anchored to its IANA timezone), floating
+-- (the same wall-clock time in whatever timezone the user is in when it is
+-- materialized) or all-day (dates without a time). All-day tasks keep their
+-- date as midnight UTC in due_at.
+
+ALTER TABLE task_series ADD COLUMN time_mode TEXT NOT NULL DEFAULT 'zoned';
+ALTER TABLE tasks ADD COLUMN all_day BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,5 @@
+-- Rollback daylight saving policy per series
+-- Migration: 20251015000000_series_dst_policy.down.sql
+
+ALTER TABLE task_series DROP COLUMN dst_overlap;
+ALTER TABLE task_series DROP COLUMN dst_gap;
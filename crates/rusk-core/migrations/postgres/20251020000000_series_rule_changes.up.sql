@@ -0,0 +1,18 @@
+-- PostgreSQL counterpart of ../20251020000000_series_rule_changes.up.sql
+-- Purpose: Keep the schedule a series had before each edit of its RRULE,
+-- start or timezone, so `rusk recur history` can show when the rule changed.
+
+CREATE TABLE series_rule_changes (
+    id UUID PRIMARY KEY NOT NULL,
+    series_id UUID NOT NULL,
+    changed_at TIMESTAMPTZ NOT NULL,
+    old_rrule TEXT NOT NULL,
+    new_rrule TEXT NOT NULL,
+    old_dtstart TIMESTAMPTZ NOT NULL,
+    new_dtstart TIMESTAMPTZ NOT NULL,
+    old_timezone TEXT NOT NULL,
+    new_timezone TEXT NOT NULL,
+    FOREIGN KEY (series_id) REFERENCES task_series(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_series_rule_changes_series_id ON series_rule_changes(series_id, changed_at);
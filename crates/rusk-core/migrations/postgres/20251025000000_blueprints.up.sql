@@ -0,0 +1,44 @@
+-- PostgreSQL counterpart of ../20251025000000_blueprints.up.sql
+-- Purpose: Reusable plans of tasks, saved with `rusk blueprint save` and
+-- instantiated relative to an anchor date with `rusk blueprint apply`. Tasks
+-- of a blueprint are keyed by their position in it, which parents,
+-- dependencies and tags refer to.
+
+CREATE TABLE blueprints (
+    id UUID PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE blueprint_tasks (
+    blueprint_id UUID NOT NULL,
+    position BIGINT NOT NULL,                      -- 0-based; parents come before their subtasks
+    parent_position BIGINT,
+    name TEXT NOT NULL,
+    description TEXT,
+    priority TEXT NOT NULL DEFAULT 'none',
+    due_offset_minutes BIGINT,                     -- Due time relative to the anchor date
+    time_mode TEXT NOT NULL DEFAULT 'zoned',
+    estimate_minutes BIGINT,
+    rrule TEXT,                                    -- Series rule lines without DTSTART, for recurring tasks
+    timezone TEXT,
+    PRIMARY KEY (blueprint_id, position),
+    FOREIGN KEY (blueprint_id) REFERENCES blueprints(id) ON DELETE CASCADE
+);
+
+CREATE TABLE blueprint_task_tags (
+    blueprint_id UUID NOT NULL,
+    position BIGINT NOT NULL,
+    tag_name TEXT NOT NULL,
+    PRIMARY KEY (blueprint_id, position, tag_name),
+    FOREIGN KEY (blueprint_id, position) REFERENCES blueprint_tasks(blueprint_id, position) ON DELETE CASCADE
+);
+
+CREATE TABLE blueprint_task_dependencies (
+    blueprint_id UUID NOT NULL,
+    position BIGINT NOT NULL,
+    depends_on_position BIGINT NOT NULL,
+    PRIMARY KEY (blueprint_id, position, depends_on_position),
+    FOREIGN KEY (blueprint_id, position) REFERENCES blueprint_tasks(blueprint_id, position) ON DELETE CASCADE
+);
@@ -0,0 +1,117 @@
+//! Blueprints captured from existing tasks
+//!
+//! A blueprint is saved from task trees: each task comes with its subtasks,
+//! tags, the dependencies among the captured tasks and, for the template of a
+//! recurring task, its series rule. Due dates are kept as offsets from an
+//! anchor date, so applying the blueprint at another date shifts them all.
+
+use crate::error::CoreError;
+use crate::models::{BlueprintTask, Task, TaskStatus, TimeMode};
+use crate::query::{Filter, Query};
+use crate::recurrence::recurrence_lines;
+use crate::repository::Repository;
+use chrono::{DateTime, NaiveTime, Utc};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// The tasks of a blueprint capturing `roots` and every task below them
+///
+/// Due dates become offsets from `anchor`, or from the start (UTC) of the
+/// earliest captured due date when no anchor is given, so times of day stay.
+/// Series instances are left out, and a series' dated extra and excluded
+/// occurrences (`RDATE` and `EXDATE` lines) are not kept since they would not
+/// move with the anchor. Dependencies on tasks outside the captured trees are
+/// dropped.
+pub async fn capture_blueprint<R: Repository + Sync + ?Sized>(
+    repo: &R,
+    roots: &[Uuid],
+    anchor: Option<DateTime<Utc>>,
+) -> Result<Vec<BlueprintTask>, CoreError> {
+    // Parents before their subtasks, in the order the roots were given
+    let mut tasks: Vec<Task> = Vec::new();
+    let mut positions: HashMap<Uuid, i64> = HashMap::new();
+    let mut stack: Vec<Uuid> = roots.iter().rev().copied().collect();
+    while let Some(id) = stack.pop() {
+        if positions.contains_key(&id) {
+            continue;
+        }
+        let task = repo
+            .find_task_by_id(id)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(id.to_string()))?;
+        if task.series_id.is_some() {
+            return Err(CoreError::InvalidInput(format!(
+                "'{}' is an occurrence of a recurring task; capture its template instead",
+                task.name
+            )));
+        }
+        let subtasks = repo.find_task_relations(id).await?.subtasks;
+        stack.extend(subtasks.iter().rev().filter(|t| t.series_id.is_none()).map(|t| t.id));
+        positions.insert(id, tasks.len() as i64);
+        tasks.push(task);
+    }
+
+    let anchor = anchor.or_else(|| {
+        let earliest = tasks.iter().filter_map(|t| t.due_at).min()?;
+        Some(earliest.date_naive().and_time(NaiveTime::MIN).and_utc())
+    });
+    let mut blueprint_tasks = Vec::with_capacity(tasks.len());
+    for (position, task) in tasks.iter().enumerate() {
+        let row = repo.find_tasks_with_details(&Query::Filter(Filter::Id(task.id))).await?.into_iter().next();
+        let tags = row
+            .and_then(|row| row.tags)
+            .map_or_else(Vec::new, |tags| tags.split(',').map(String::from).collect());
+        let depends_on = repo
+            .find_task_relations(task.id)
+            .await?
+            .depends_on
+            .iter()
+            .filter_map(|t| positions.get(&t.id).copied())
+            .collect();
+
+        let series = repo.find_series_by_template(task.id).await?;
+        let (rrule, timezone, time_mode) = match series {
+            Some(series) => {
+                let lines: Vec<String> = recurrence_lines(&series.rrule)
+                    .into_iter()
+                    .filter(|line| line.starts_with("RRULE:") || line.starts_with("EXRULE:"))
+                    .collect();
+                (Some(lines.join("\n")), Some(series.timezone), series.time_mode)
+            }
+            None => (None, None, if task.all_day { TimeMode::AllDay } else { TimeMode::Zoned }),
+        };
+
+        blueprint_tasks.push(BlueprintTask {
+            position: position as i64,
+            parent_position: task.parent_id.and_then(|parent| positions.get(&parent).copied()),
+            name: task.name.clone(),
+            description: task.description.clone(),
+            priority: task.priority.clone(),
+            due_offset_minutes: task.due_at.zip(anchor).map(|(due, anchor)| (due - anchor).num_minutes()),
+            time_mode,
+            estimate_minutes: task.estimate_minutes,
+            rrule,
+            timezone,
+            tags,
+            depends_on,
+        });
+    }
+    Ok(blueprint_tasks)
+}
+
+/// The pending tasks of a project that are not below another of them,
+/// leaving out series instances
+pub async fn project_roots<R: Repository + Sync + ?Sized>(repo: &R, project_name: &str) -> Result<Vec<Uuid>, CoreError> {
+    let rows = repo
+        .find_tasks_with_details(&Query::and(vec![
+            Filter::Project(project_name.to_string()),
+            Filter::Status(TaskStatus::Pending),
+        ]))
+        .await?;
+    let ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+    Ok(rows
+        .iter()
+        .filter(|row| row.series_id.is_none() && row.parent_id.is_none_or(|parent| !ids.contains(&parent)))
+        .map(|row| row.id)
+        .collect())
+}
@@ -0,0 +1,71 @@
+//! # Clock Abstraction
+//!
+//! Long-running components such as the reminder daemon need to ask "what time
+//! is it?" in a way that tests can control. The [`Clock`] trait provides that
+//! seam: production code uses [`SystemClock`], while tests drive a
+//! [`FakeClock`] forwards and backwards in time.
+
+use chrono::{DateTime, Duration, Utc};
+use std::sync::{Arc, Mutex};
+
+/// Source of the current time (UTC)
+pub trait Clock: Send + Sync {
+    /// Returns the current instant
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Clock backed by the operating system time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Manually controlled clock for deterministic tests
+///
+/// Cloned handles share the same underlying instant, so a test can keep one
+/// handle and pass another into the component under test.
+///
+/// # Examples
+///
+/// ```rust
+/// use rusk_core::clock::{Clock, FakeClock};
+/// use chrono::{Duration, TimeZone, Utc};
+///
+/// let clock = FakeClock::new(Utc.with_ymd_and_hms(2025, 1, 1, 9, 0, 0).unwrap());
+/// clock.advance(Duration::hours(2));
+/// assert_eq!(clock.now(), Utc.with_ymd_and_hms(2025, 1, 1, 11, 0, 0).unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct FakeClock {
+    now: Arc<Mutex<DateTime<Utc>>>,
+}
+
+impl FakeClock {
+    /// Create a fake clock frozen at the given instant
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Arc::new(Mutex::new(now)),
+        }
+    }
+
+    /// Jump to an absolute instant
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap() = now;
+    }
+
+    /// Move the clock by a (possibly negative) duration
+    pub fn advance(&self, by: Duration) {
+        let mut now = self.now.lock().unwrap();
+        *now += by;
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+}
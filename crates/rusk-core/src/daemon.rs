@@ -0,0 +1,293 @@
+//! # Background Daemon
+//!
+//! `rusk daemon` keeps recurring series materialized ahead of time and emits
+//! reminders for tasks approaching their due time. Everything time-dependent
+//! goes through a [`Clock`], so a whole day of daemon activity can be
+//! simulated in a test by advancing a [`FakeClock`](crate::clock::FakeClock)
+//! and calling [`Daemon::tick`].
+//!
+//! Reminders are delivered through [`ReminderSink`]s. Three sinks ship with
+//! the library: [`StdoutSink`] for logging, [`CommandSink`] for user hooks and
+//! [`DesktopNotifySink`] for desktop notifications.
+
+use crate::clock::Clock;
+use crate::error::CoreError;
+use crate::models::DueReminder;
+use crate::repository::Repository;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use std::future::Future;
+use std::sync::Arc;
+
+/// Destination for reminder notifications
+#[async_trait]
+pub trait ReminderSink: Send + Sync {
+    /// Short name used in error reports
+    fn name(&self) -> &str;
+
+    /// Deliver a single reminder
+    async fn deliver(&self, reminder: &DueReminder, now: DateTime<Utc>) -> Result<(), CoreError>;
+}
+
+/// Formats the one-line message shared by the built-in sinks
+pub fn format_reminder(reminder: &DueReminder, now: DateTime<Utc>) -> String {
+    match reminder.task.due_at {
+        Some(due) if due > now => {
+            let minutes = (due - now).num_minutes();
+            let until = if minutes >= 60 {
+                format!("{}h{:02}m", minutes / 60, minutes % 60)
+            } else {
+                format!("{}m", minutes)
+            };
+            format!(
+                "'{}' is due at {} (in {})",
+                reminder.task.name,
+                due.format("%Y-%m-%d %H:%M UTC"),
+                until
+            )
+        }
+        Some(due) => format!(
+            "'{}' was due at {}",
+            reminder.task.name,
+            due.format("%Y-%m-%d %H:%M UTC")
+        ),
+        None => format!("'{}' needs attention", reminder.task.name),
+    }
+}
+
+/// Writes reminders to standard output, one line each
+#[derive(Debug, Default)]
+pub struct StdoutSink;
+
+#[async_trait]
+impl ReminderSink for StdoutSink {
+    fn name(&self) -> &str {
+        "stdout"
+    }
+
+    async fn deliver(&self, reminder: &DueReminder, now: DateTime<Utc>) -> Result<(), CoreError> {
+        println!(
+            "[{}] Reminder: {} ({})",
+            now.format("%Y-%m-%d %H:%M:%S"),
+            format_reminder(reminder, now),
+            reminder.task.id
+        );
+        Ok(())
+    }
+}
+
+/// Runs a user-supplied shell command for every reminder
+///
+/// The command receives the reminder through environment variables:
+/// `RUSK_TASK_ID`, `RUSK_TASK_NAME`, `RUSK_DUE_AT` (RFC 3339), `RUSK_REMIND_AT`
+/// and `RUSK_MESSAGE`.
+#[derive(Debug, Clone)]
+pub struct CommandSink {
+    command: String,
+}
+
+impl CommandSink {
+    pub fn new(command: impl Into<String>) -> Self {
+        Self { command: command.into() }
+    }
+}
+
+#[async_trait]
+impl ReminderSink for CommandSink {
+    fn name(&self) -> &str {
+        "command"
+    }
+
+    async fn deliver(&self, reminder: &DueReminder, now: DateTime<Utc>) -> Result<(), CoreError> {
+        let format_opt = |dt: Option<DateTime<Utc>>| dt.map(|d| d.to_rfc3339()).unwrap_or_default();
+
+        let status = tokio::process::Command::new("sh")
+            .arg("-c")
+            .arg(&self.command)
+            .env("RUSK_TASK_ID", reminder.task.id.to_string())
+            .env("RUSK_TASK_NAME", &reminder.task.name)
+            .env("RUSK_DUE_AT", format_opt(reminder.task.due_at))
+            .env("RUSK_REMIND_AT", format_opt(reminder.remind_at()))
+            .env("RUSK_MESSAGE", format_reminder(reminder, now))
+            .status()
+            .await?;
+
+        if !status.success() {
+            return Err(CoreError::ReminderDelivery(format!(
+                "command '{}' exited with {}",
+                self.command, status
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Sends desktop notifications through a `notify-send` compatible program
+#[derive(Debug, Clone)]
+pub struct DesktopNotifySink {
+    program: String,
+}
+
+impl DesktopNotifySink {
+    pub fn new(program: impl Into<String>) -> Self {
+        Self { program: program.into() }
+    }
+}
+
+impl Default for DesktopNotifySink {
+    fn default() -> Self {
+        Self::new("notify-send")
+    }
+}
+
+#[async_trait]
+impl ReminderSink for DesktopNotifySink {
+    fn name(&self) -> &str {
+        "notify"
+    }
+
+    async fn deliver(&self, reminder: &DueReminder, now: DateTime<Utc>) -> Result<(), CoreError> {
+        let status = tokio::process::Command::new(&self.program)
+            .arg(format!("Rusk: {}", reminder.task.name))
+            .arg(format_reminder(reminder, now))
+            .status()
+            .await?;
+
+        if !status.success() {
+            return Err(CoreError::ReminderDelivery(format!(
+                "'{}' exited with {}",
+                self.program, status
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Daemon scheduling settings
+#[derive(Debug, Clone)]
+pub struct DaemonConfig {
+    /// Pause between two ticks
+    pub interval: std::time::Duration,
+    /// How far ahead series are materialized on each tick
+    pub lookahead: Duration,
+    /// How far back series are materialized on each tick
+    pub grace: Duration,
+    /// Reminders for tasks that became overdue longer ago than this are dropped
+    /// (e.g. after the daemon was offline) instead of being delivered late
+    pub missed_reminder_window: Duration,
+}
+
+impl Default for DaemonConfig {
+    fn default() -> Self {
+        Self {
+            interval: std::time::Duration::from_secs(60),
+            lookahead: Duration::days(30),
+            grace: Duration::days(3),
+            missed_reminder_window: Duration::hours(1),
+        }
+    }
+}
+
+/// Outcome of a single daemon tick
+#[derive(Debug, Default)]
+pub struct TickReport {
+    /// Time at which the tick ran, according to the daemon's clock
+    pub ran_at: Option<DateTime<Utc>>,
+    /// Reminders delivered by at least one sink
+    pub delivered: Vec<DueReminder>,
+    /// Sink failures as `(sink name, error message)`
+    pub failures: Vec<(String, String)>,
+}
+
+/// Periodic materialization and reminder loop
+pub struct Daemon<'a, R: Repository + Sync + ?Sized> {
+    repo: &'a R,
+    clock: Arc<dyn Clock>,
+    sinks: Vec<Box<dyn ReminderSink>>,
+    config: DaemonConfig,
+}
+
+impl<'a, R: Repository + Sync + ?Sized> Daemon<'a, R> {
+    pub fn new(repo: &'a R, clock: Arc<dyn Clock>, config: DaemonConfig) -> Self {
+        Self {
+            repo,
+            clock,
+            sinks: Vec::new(),
+            config,
+        }
+    }
+
+    /// Register an additional reminder sink
+    pub fn with_sink(mut self, sink: Box<dyn ReminderSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    pub fn config(&self) -> &DaemonConfig {
+        &self.config
+    }
+
+    /// Run one materialization pass and deliver every reminder that is due
+    pub async fn tick(&self) -> Result<TickReport, CoreError> {
+        let now = self.clock.now();
+        let mut report = TickReport {
+            ran_at: Some(now),
+            ..Default::default()
+        };
+
+        self.repo
+            .refresh_series_materialization(now - self.config.grace, now + self.config.lookahead)
+            .await?;
+
+        let pending = self
+            .repo
+            .find_pending_reminders(now - self.config.missed_reminder_window)
+            .await?;
+
+        for reminder in pending {
+            let (due_at, remind_at) = match (reminder.task.due_at, reminder.remind_at()) {
+                (Some(due_at), Some(remind_at)) => (due_at, remind_at),
+                _ => continue,
+            };
+            if remind_at > now {
+                continue;
+            }
+
+            let mut failed = 0;
+            for sink in &self.sinks {
+                if let Err(e) = sink.deliver(&reminder, now).await {
+                    failed += 1;
+                    report.failures.push((sink.name().to_string(), e.to_string()));
+                }
+            }
+
+            // Retry on the next tick when every sink failed, or there is none
+            if failed < self.sinks.len() {
+                self.repo
+                    .mark_reminder_delivered(reminder.task.id, due_at, now)
+                    .await?;
+                report.delivered.push(reminder);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Tick every `interval` until `shutdown` resolves.
+    ///
+    /// Each tick result is passed to `on_tick`; failed ticks do not stop the loop.
+    pub async fn run<F, T>(&self, shutdown: F, mut on_tick: T)
+    where
+        F: Future<Output = ()>,
+        T: FnMut(Result<TickReport, CoreError>),
+    {
+        tokio::pin!(shutdown);
+        loop {
+            on_tick(self.tick().await);
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = tokio::time::sleep(self.config.interval) => {}
+            }
+        }
+    }
+}
@@ -1,9 +1,50 @@
+use sqlx::postgres::{PgPool, PgPoolOptions};
 use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
 use std::path::Path;
 use crate::error::CoreError;
 
-// Re-export the pool for use in other parts of the core crate
-pub use sqlx::SqlitePool as DbPool;
+/// Connection pool for whichever backend the connection URL selected
+#[derive(Debug, Clone)]
+pub enum DbPool {
+    Sqlite(SqlitePool),
+    Postgres(PgPool),
+}
+
+impl DbPool {
+    /// Close the underlying pool, waiting for open connections to shut down
+    pub async fn close(&self) {
+        match self {
+            DbPool::Sqlite(pool) => pool.close().await,
+            DbPool::Postgres(pool) => pool.close().await,
+        }
+    }
+}
+
+/// Returns `true` if `url` points at a PostgreSQL server rather than a SQLite file.
+pub fn is_postgres_url(url: &str) -> bool {
+    url.starts_with("postgres://") || url.starts_with("postgresql://")
+}
+
+/// Establishes a connection pool and runs migrations, picking the backend from `url`.
+///
+/// `postgres://` and `postgresql://` URLs connect to a PostgreSQL server;
+/// anything else is treated as a SQLite database path.
+///
+/// # Arguments
+///
+/// * `url` - A PostgreSQL connection URL or the path to the SQLite database file.
+///
+/// # Returns
+///
+/// A `Result` containing the `DbPool` or a `CoreError` if the connection fails
+/// or migrations cannot be run.
+pub async fn establish_connection(url: &str) -> Result<DbPool, CoreError> {
+    if is_postgres_url(url) {
+        Ok(DbPool::Postgres(connect_postgres(url).await?))
+    } else {
+        Ok(DbPool::Sqlite(connect_sqlite(url).await?))
+    }
+}
 
 /// Establishes a connection pool to the SQLite database and runs migrations.
 ///
@@ -15,7 +56,7 @@ pub use sqlx::SqlitePool as DbPool;
 ///
 /// A `Result` containing the `SqlitePool` or a `CoreError` if the connection fails
 /// or migrations cannot be run.
-pub async fn establish_connection(db_path: &str) -> Result<SqlitePool, CoreError> {
+pub async fn connect_sqlite(db_path: &str) -> Result<SqlitePool, CoreError> {
     // Create the database file and directory if they don't exist
     if let Some(parent) = Path::new(db_path).parent() {
         if !parent.exists() {
@@ -39,4 +80,29 @@ pub async fn establish_connection(db_path: &str) -> Result<SqlitePool, CoreError
         .await?;
 
     Ok(pool)
-}
\ No newline at end of file
+}
+
+/// Establishes a connection pool to a PostgreSQL database and runs migrations.
+///
+/// The database itself must already exist; the schema is created on first use.
+///
+/// # Arguments
+///
+/// * `url` - A `postgres://` connection URL.
+///
+/// # Returns
+///
+/// A `Result` containing the `PgPool` or a `CoreError` if the connection fails
+/// or migrations cannot be run.
+pub async fn connect_postgres(url: &str) -> Result<PgPool, CoreError> {
+    let pool = PgPoolOptions::new()
+        .max_connections(5)
+        .connect(url)
+        .await?;
+
+    sqlx::migrate!("./migrations/postgres")
+        .run(&pool)
+        .await?;
+
+    Ok(pool)
+}
@@ -20,6 +20,9 @@ pub enum CoreError {
     #[error("Task is blocked by: {0}")]
     TaskBlocked(String),
 
+    #[error("Task has pending subtasks: {0}")]
+    PendingSubtasks(String),
+
     #[error("Ambiguous short ID. Did you mean one of these?")]
     AmbiguousId(Vec<(String, String)>), // Vec of (ID, Name)
 
@@ -44,6 +47,15 @@ pub enum CoreError {
     #[error("Series not completed: {0}")]
     SeriesNotCompleted(String),
 
+    #[error("Status transition not allowed: {0}")]
+    InvalidTransition(String),
+
+    #[error("A timer is already running for task '{0}'")]
+    TimerRunning(String),
+
+    #[error("Reminder delivery failed: {0}")]
+    ReminderDelivery(String),
+
     #[error("An unknown error has occurred.")]
     Unknown,
 }
\ No newline at end of file
@@ -0,0 +1,199 @@
+//! Series exceptions driven by date patterns
+//!
+//! Instead of naming occurrences one by one, a pattern picks them by month,
+//! weekday or calendar date (such as a list of holidays), optionally keeping
+//! only every nth match. The matched occurrences are planned first, which
+//! doubles as the dry-run preview, and the plan is then applied one
+//! occurrence at a time so that a single conflict does not stop the rest.
+
+use crate::error::CoreError;
+use crate::models::{BulkExceptionResult, ExceptionType, NewSeriesException, TaskSeries, TaskStatus};
+use crate::recurrence::{RecurrenceManager, SeriesOccurrence};
+use crate::repository::Repository;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+
+/// Upper bound on the occurrences a single pattern looks at
+const MAX_PATTERN_OCCURRENCES: usize = 500;
+
+/// Which occurrences of a series a pattern applies to
+///
+/// Every non-empty criterion must match, in the series timezone.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OccurrencePattern {
+    /// First occurrence to consider; defaults to now
+    pub from: Option<DateTime<Utc>>,
+    /// Last occurrence to consider (inclusive); defaults to a year after `from`
+    pub until: Option<DateTime<Utc>>,
+    /// Months, 1 to 12
+    pub months: Vec<u32>,
+    pub weekdays: Vec<Weekday>,
+    /// Calendar dates, e.g. a holiday calendar
+    pub dates: Vec<NaiveDate>,
+    /// Keep only every nth of the otherwise matching occurrences (the nth,
+    /// 2nth, ...)
+    pub every_nth: Option<usize>,
+}
+
+impl OccurrencePattern {
+    /// Whether an occurrence on the local date `date` matches the calendar
+    /// criteria
+    pub fn matches_date(&self, date: NaiveDate) -> bool {
+        (self.months.is_empty() || self.months.contains(&date.month()))
+            && (self.weekdays.is_empty() || self.weekdays.contains(&date.weekday()))
+            && (self.dates.is_empty() || self.dates.contains(&date))
+    }
+}
+
+/// The exception a pattern gives each matched occurrence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternAction {
+    Skip,
+    /// Move to this weekday of the same Monday-to-Sunday week, at the same
+    /// local time
+    MoveToWeekday(Weekday),
+}
+
+/// A matched occurrence and the exception it is about to get
+#[derive(Debug, Clone)]
+pub struct PlannedException {
+    pub occurrence: SeriesOccurrence,
+    /// Where a move lands, `None` for skips
+    pub move_to: Option<DateTime<Utc>>,
+}
+
+/// The occurrences `pattern` matches, with what `action` would do to each
+///
+/// Built on [`RecurrenceManager::preview_occurrences`], so already skipped
+/// occurrences are left out and the search stops a year after the start.
+/// Occurrences that already carry an override or move are included; applying
+/// the plan reports them as errors.
+pub fn plan_exceptions(
+    manager: &RecurrenceManager,
+    pattern: &OccurrencePattern,
+    action: PatternAction,
+    now: DateTime<Utc>,
+) -> Result<Vec<PlannedException>, CoreError> {
+    if pattern.every_nth == Some(0) {
+        return Err(CoreError::InvalidInput("Every nth occurrence needs n of at least 1".to_string()));
+    }
+    let from = pattern.from.unwrap_or(now);
+    let until = pattern.until.unwrap_or(from + Duration::days(365));
+    let tz = manager.timezone();
+
+    let matched = manager
+        .preview_occurrences(from, MAX_PATTERN_OCCURRENCES)?
+        .into_iter()
+        .filter(|o| o.occurrence_dt <= until)
+        .filter(|o| pattern.matches_date(o.occurrence_dt.with_timezone(tz).date_naive()));
+    let every_nth = pattern.every_nth.unwrap_or(1);
+
+    matched
+        .enumerate()
+        .filter(|(index, _)| (index + 1) % every_nth == 0)
+        .map(|(_, occurrence)| {
+            let move_to = match action {
+                PatternAction::Skip => None,
+                PatternAction::MoveToWeekday(weekday) => Some(move_to_weekday(tz, occurrence.occurrence_dt, weekday)?),
+            };
+            Ok(PlannedException { occurrence, move_to })
+        })
+        .collect()
+}
+
+/// `dt` moved to `weekday` of its Monday-to-Sunday week, keeping the local time
+fn move_to_weekday(tz: &Tz, dt: DateTime<Utc>, weekday: Weekday) -> Result<DateTime<Utc>, CoreError> {
+    let local = dt.with_timezone(tz).naive_local();
+    let days = weekday.num_days_from_monday() as i64 - local.weekday().num_days_from_monday() as i64;
+    let target = local + Duration::days(days);
+    tz.from_local_datetime(&target)
+        .earliest()
+        .map(|moved| moved.with_timezone(&Utc))
+        .ok_or_else(|| CoreError::InvalidInput(format!("{} does not exist in {}", target, tz)))
+}
+
+/// Add the planned exceptions to `series`, one occurrence at a time
+///
+/// An occurrence that cannot take its exception (it already has one, or its
+/// instance is already finished) is counted as failed and described in
+/// [`BulkExceptionResult::errors`]; the others still go through. A skipped
+/// occurrence's pending instance is removed, and a moved one's instance is
+/// moved along with it.
+pub async fn apply_exception_plan<R: Repository + Sync + ?Sized>(
+    repo: &R,
+    series: &TaskSeries,
+    plan: &[PlannedException],
+    now: DateTime<Utc>,
+) -> Result<BulkExceptionResult, CoreError> {
+    let mut result = BulkExceptionResult {
+        successful_operations: 0,
+        failed_operations: 0,
+        errors: Vec::new(),
+        created_exceptions: Vec::new(),
+    };
+    let mut applied = Vec::new();
+
+    for planned in plan {
+        let occurrence_dt = planned.occurrence.occurrence_dt;
+        match apply_planned(repo, series, planned, now).await {
+            Ok(()) => {
+                result.successful_operations += 1;
+                applied.push(occurrence_dt);
+            }
+            Err(e) => {
+                result.failed_operations += 1;
+                result.errors.push(format!("{}: {}", occurrence_dt.format("%Y-%m-%d %H:%M"), e));
+            }
+        }
+    }
+
+    result.created_exceptions = repo
+        .find_series_exceptions(series.id)
+        .await?
+        .into_iter()
+        .filter(|e| applied.contains(&e.occurrence_dt))
+        .collect();
+    Ok(result)
+}
+
+async fn apply_planned<R: Repository + Sync + ?Sized>(
+    repo: &R,
+    series: &TaskSeries,
+    planned: &PlannedException,
+    now: DateTime<Utc>,
+) -> Result<(), CoreError> {
+    let occurrence_dt = planned.occurrence.occurrence_dt;
+    if planned.occurrence.has_exception {
+        return Err(CoreError::InvalidException("occurrence already has an exception".to_string()));
+    }
+
+    let instance = repo
+        .find_materialized_tasks_for_series(series.id, occurrence_dt, occurrence_dt)
+        .await?
+        .into_iter()
+        .find(|t| t.due_at == Some(occurrence_dt));
+    if let Some(instance) = &instance {
+        if instance.status != TaskStatus::Pending {
+            return Err(CoreError::InvalidException(format!("occurrence is already {}", instance.workflow_status)));
+        }
+    }
+
+    match planned.move_to {
+        Some(to_dt) => {
+            repo.move_occurrence_with_validation(series.id, occurrence_dt, to_dt, &series.timezone).await?;
+        }
+        None => {
+            repo.add_series_exception(NewSeriesException {
+                series_id: series.id,
+                occurrence_dt,
+                exception_type: ExceptionType::Skip,
+                exception_task_id: None,
+                notes: Some(format!("Skipped by pattern on {}", now.format("%Y-%m-%d"))),
+            }).await?;
+            if let Some(instance) = instance {
+                repo.delete_task(instance.id).await?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,151 @@
+//! Effort estimates and workload forecasting
+//!
+//! A forecast sums the estimated effort of pending work per day: tasks that
+//! already exist (including materialized series instances) and occurrences of
+//! active series that have not been materialized yet, which are generated from
+//! the series rule and carry their template's estimate.
+
+use crate::error::CoreError;
+use crate::models::TaskStatus;
+use crate::query::{Filter, Query};
+use crate::recurrence::RecurrenceManager;
+use crate::repository::Repository;
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Validate an effort estimate and convert it to the stored whole minutes
+pub fn estimate_minutes(estimate: Duration) -> Result<i64, CoreError> {
+    let minutes = estimate.num_minutes();
+    if minutes <= 0 {
+        return Err(CoreError::InvalidInput("Estimate must be at least one minute".to_string()));
+    }
+    Ok(minutes)
+}
+
+/// One unit of estimated work falling due inside the forecast window
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForecastItem {
+    /// Existing task, or `None` for a series occurrence not materialized yet
+    pub task_id: Option<Uuid>,
+    pub series_id: Option<Uuid>,
+    pub name: String,
+    pub due_at: DateTime<Utc>,
+    pub estimate_minutes: i64,
+}
+
+/// Estimated effort for a single day of the forecast
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyLoad {
+    pub date: NaiveDate,
+    pub items: Vec<ForecastItem>,
+}
+
+impl DailyLoad {
+    /// Total estimated minutes due on this day
+    pub fn planned_minutes(&self) -> i64 {
+        self.items.iter().map(|item| item.estimate_minutes).sum()
+    }
+
+    /// Whether the planned effort exceeds `capacity_minutes`
+    pub fn is_overloaded(&self, capacity_minutes: i64) -> bool {
+        self.planned_minutes() > capacity_minutes
+    }
+}
+
+/// Collect estimated work due in `[start, end)`, ordered by due time
+///
+/// Templates are left out since their series' occurrences stand in for them,
+/// and tasks without an estimate contribute nothing.
+pub async fn collect_forecast_items<R: Repository + Sync + ?Sized>(
+    repo: &R,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Vec<ForecastItem>, CoreError> {
+    let mut items = Vec::new();
+    let in_window = |due_at: DateTime<Utc>| due_at >= start && due_at < end;
+
+    let pending = repo
+        .find_tasks_with_details(&Query::Filter(Filter::Status(TaskStatus::Pending)))
+        .await?;
+    for task in pending {
+        let (Some(due_at), Some(estimate_minutes)) = (task.due_at, task.estimate_minutes) else {
+            continue;
+        };
+        if !in_window(due_at) || repo.find_series_by_template(task.id).await?.is_some() {
+            continue;
+        }
+        items.push(ForecastItem {
+            task_id: Some(task.id),
+            series_id: task.series_id,
+            name: task.name,
+            due_at,
+            estimate_minutes,
+        });
+    }
+
+    for series in repo.find_active_series().await? {
+        let Some(template) = repo.find_task_by_id(series.template_task_id).await? else {
+            continue;
+        };
+        let Some(estimate_minutes) = template.estimate_minutes else {
+            continue;
+        };
+
+        let materialized: HashSet<DateTime<Utc>> = repo
+            .find_materialized_tasks_for_series(series.id, start, end)
+            .await?
+            .into_iter()
+            .filter_map(|task| task.due_at)
+            .collect();
+        let exceptions = repo.find_series_exceptions(series.id).await?;
+        let series_id = series.id;
+        let manager = RecurrenceManager::new(series, template.clone(), exceptions)?;
+
+        // Overridden and moved occurrences exist as tasks of their own
+        for occurrence in manager.generate_occurrences_between(start, end)? {
+            if occurrence.task_id.is_some()
+                || !in_window(occurrence.effective_dt)
+                || materialized.contains(&occurrence.effective_dt)
+            {
+                continue;
+            }
+            items.push(ForecastItem {
+                task_id: None,
+                series_id: Some(series_id),
+                name: template.name.clone(),
+                due_at: occurrence.effective_dt,
+                estimate_minutes,
+            });
+        }
+    }
+
+    items.sort_by(|a, b| a.due_at.cmp(&b.due_at).then_with(|| a.name.cmp(&b.name)));
+    Ok(items)
+}
+
+/// Group forecast items into `days` consecutive days starting at `first_day`
+///
+/// Days are calendar days in `tz`; every day of the range is present even
+/// when nothing is due on it, and items outside the range are dropped.
+pub fn group_by_day<Tz: TimeZone>(
+    items: Vec<ForecastItem>,
+    tz: &Tz,
+    first_day: NaiveDate,
+    days: u32,
+) -> Vec<DailyLoad> {
+    let mut loads: Vec<DailyLoad> = first_day
+        .iter_days()
+        .take(days as usize)
+        .map(|date| DailyLoad { date, items: Vec::new() })
+        .collect();
+
+    for item in items {
+        let date = item.due_at.with_timezone(tz).date_naive();
+        let offset = (date - first_day).num_days();
+        if let Some(load) = usize::try_from(offset).ok().and_then(|i| loads.get_mut(i)) {
+            load.items.push(item);
+        }
+    }
+    loads
+}
@@ -18,11 +18,20 @@
 //!
 //! - [`db`]: Database connection and migration management
 //! - [`models`]: Core data structures and transfer objects
-//! - [`repository`]: Data access layer with Repository pattern
+//! - [`repository`]: Data access layer with Repository pattern (SQLite, PostgreSQL and in-memory backends)
 //! - [`recurrence`]: Recurrence calculation and materialization engines
 //! - [`timezone`]: Timezone utilities and validation
+//! - [`workflow`]: Custom workflow status rules
+//! - [`urgency`]: Urgency scoring for picking the next task
 //! - [`error`]: Comprehensive error types with context
 //! - [`query`]: Advanced filtering and query parsing
+//! - [`clock`]: Injectable time source for deterministic tests
+//! - [`daemon`]: Background materialization and reminder delivery
+//! - [`forecast`]: Effort estimates and per-day workload forecasting
+//! - [`exception_patterns`]: Series exceptions for occurrences picked by date patterns
+//! - [`series_history`]: Timeline of a series' instances, exceptions and rule edits
+//! - [`series_habits`]: Streaks, on-time rates and trends of a recurring series
+//! - [`blueprints`]: Reusable task plans captured from existing tasks
 //!
 //! ## Example Usage
 //!
@@ -35,7 +44,7 @@
 //! #[tokio::main]
 //! async fn main() -> anyhow::Result<()> {
 //!     // Initialize database
-//!     let pool = db::establish_connection("tasks.db").await?;
+//!     let pool = db::connect_sqlite("tasks.db").await?;
 //!     
 //!     // Create repository with materialization
 //!     let config = MaterializationConfig::default();
@@ -57,10 +66,19 @@
 //! }
 //! ```
 
+pub mod blueprints;
+pub mod clock;
+pub mod daemon;
 pub mod db;
 pub mod error;
+pub mod exception_patterns;
+pub mod forecast;
 pub mod models;
 pub mod query;
 pub mod repository;
 pub mod recurrence;
-pub mod timezone;
\ No newline at end of file
+pub mod series_history;
+pub mod series_habits;
+pub mod timezone;
+pub mod urgency;
+pub mod workflow;
\ No newline at end of file
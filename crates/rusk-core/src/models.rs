@@ -3,7 +3,8 @@
 //! This module contains all core data structures used throughout the Rusk
 //! task management system, including tasks, projects, series, and supporting types.
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use std::str::FromStr;
@@ -29,7 +30,7 @@ use uuid::Uuid;
 ///     created_at: Utc::now(),
 /// };
 /// ```
-#[derive(Debug, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Project {
     /// Unique identifier using UUIDv7 for time-ordered performance
     #[serde(with = "uuid::serde::compact")]
@@ -51,6 +52,9 @@ pub struct Project {
 /// 
 /// For recurring tasks, completing a task generates the next occurrence.
 /// Cancelling a recurring task stops the series generation.
+///
+/// Custom workflow statuses (see [`WorkflowStatus`]) each map onto one of
+/// these base categories, which remain the source of completion semantics.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
 #[sqlx(type_name = "TEXT", rename_all = "lowercase")]
 pub enum TaskStatus {
@@ -67,6 +71,17 @@ pub enum TaskStatus {
 #[error("Invalid task status: {0}")]
 pub struct ParseTaskStatusError(String);
 
+impl TaskStatus {
+    /// Lowercase name as stored in the database
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskStatus::Pending => "pending",
+            TaskStatus::Completed => "completed",
+            TaskStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
 impl FromStr for TaskStatus {
     type Err = ParseTaskStatusError;
 
@@ -158,6 +173,12 @@ impl FromStr for TaskPriority {
 ///     due_at: Some(Utc::now()),
 ///     // ... other fields
 ///     series_id: None, // Regular task
+///     # workflow_status: "pending".to_string(),
+///     # estimate_minutes: None,
+///     # scheduled_at: None,
+///     # wait_until: None,
+///     # start_at: None,
+///     # all_day: false,
 ///     # completed_at: None,
 ///     # created_at: Utc::now(),
 ///     # updated_at: Utc::now(),
@@ -194,6 +215,19 @@ pub struct Task {
     /// - `None`: Regular or template task
     /// - `Some(uuid)`: Instance task belonging to a series
     pub series_id: Option<Uuid>,
+    /// Workflow status name; `status` always holds its base category
+    pub workflow_status: String,
+    /// Estimated effort in minutes (inherited by series instances)
+    pub estimate_minutes: Option<i64>,
+    /// Earliest time to work on the task; `rusk list` hides it until then
+    pub scheduled_at: Option<DateTime<Utc>>,
+    /// The task is hidden as waiting until this time
+    pub wait_until: Option<DateTime<Utc>>,
+    /// When work on the task is planned to begin
+    pub start_at: Option<DateTime<Utc>>,
+    /// Due on a date rather than at a time; `due_at` holds midnight UTC of
+    /// that date
+    pub all_day: bool,
 }
 
 impl Default for Task {
@@ -211,10 +245,94 @@ impl Default for Task {
             project_id: None,
             parent_id: None,
             series_id: None,
+            workflow_status: TaskStatus::Pending.as_str().to_string(),
+            estimate_minutes: None,
+            scheduled_at: None,
+            wait_until: None,
+            start_at: None,
+            all_day: false,
         }
     }
 }
 
+impl Task {
+    /// Estimated effort, if one was set
+    pub fn estimate(&self) -> Option<Duration> {
+        self.estimate_minutes.map(Duration::minutes)
+    }
+
+    /// Carry one of this task's dates over to a copy due at `due_at`
+    ///
+    /// The date keeps its distance from this task's due date, which is how
+    /// series instances inherit "two days before due" from their template.
+    /// Without a due date to measure from, the date is kept as is.
+    pub fn date_relative_to_due(&self, date: Option<DateTime<Utc>>, due_at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match (date, self.due_at) {
+            (Some(date), Some(own_due_at)) => Some(due_at + (date - own_due_at)),
+            (date, _) => date,
+        }
+    }
+
+    /// The fields of the series instance due at `due_at`, when this task is
+    /// the series' template
+    pub fn instance_data(&self, series_id: Uuid, due_at: DateTime<Utc>) -> NewTaskData {
+        NewTaskData {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            due_at: Some(due_at),
+            priority: Some(self.priority.clone()),
+            project_id: self.project_id,
+            parent_id: self.parent_id,
+            series_id: Some(series_id),
+            estimate: self.estimate(),
+            scheduled_at: self.date_relative_to_due(self.scheduled_at, due_at),
+            wait_until: self.date_relative_to_due(self.wait_until, due_at),
+            start_at: self.date_relative_to_due(self.start_at, due_at),
+            time_mode: if self.all_day { TimeMode::AllDay } else { TimeMode::Zoned },
+            ..Default::default()
+        }
+    }
+
+    /// Whether the task is hidden as waiting at `now`
+    pub fn is_waiting(&self, now: DateTime<Utc>) -> bool {
+        self.wait_until.is_some_and(|wait_until| wait_until > now)
+    }
+
+    /// Whether the task is scheduled for later than `now`
+    pub fn is_scheduled_later(&self, now: DateTime<Utc>) -> bool {
+        self.scheduled_at.is_some_and(|scheduled_at| scheduled_at > now)
+    }
+}
+
+/// A user-definable workflow status such as `in-progress` or `review`
+///
+/// Every workflow status belongs to one of the base [`TaskStatus`]
+/// categories. Moving a task into a `Completed` status completes it (and
+/// advances its series), a `Cancelled` status cancels it, and a `Pending`
+/// status reopens it. The base categories themselves exist as built-in
+/// statuses named `pending`, `completed` and `cancelled`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct WorkflowStatus {
+    /// Unique lowercase name (letters, digits, `-` and `_`)
+    pub name: String,
+    /// Base category that drives completion semantics
+    pub category: TaskStatus,
+    /// Display order within the category
+    pub position: i64,
+    /// When the status was defined (UTC timezone)
+    pub created_at: DateTime<Utc>,
+}
+
+/// An allowed move between two workflow statuses
+///
+/// A status without outgoing transitions may move to any status; once one
+/// transition is defined from it, only the listed targets are allowed.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq, Eq)]
+pub struct WorkflowTransition {
+    pub from_status: String,
+    pub to_status: String,
+}
+
 /// Represents a filter for listing tasks.
 #[derive(Debug, Clone)]
 pub enum Filter {
@@ -253,6 +371,40 @@ pub struct NewTaskData {
     pub series_id: Option<Uuid>,
     /// Timezone for recurrence calculations (used with rrule)
     pub timezone: Option<String>,
+    /// How long before `due_at` to send a reminder (inherited by series instances)
+    pub remind_before: Option<Duration>,
+    /// Estimated effort (inherited by series instances)
+    pub estimate: Option<Duration>,
+    /// Earliest time to work on the task (kept relative to due by series instances)
+    pub scheduled_at: Option<DateTime<Utc>>,
+    /// Hide the task until this time (kept relative to due by series instances)
+    pub wait_until: Option<DateTime<Utc>>,
+    /// Planned start of work (kept relative to due by series instances)
+    pub start_at: Option<DateTime<Utc>>,
+    /// How the due time is anchored: all-day tasks are due on the date of
+    /// `due_at`, and floating only matters for recurring tasks
+    pub time_mode: TimeMode,
+    /// DST policy of the series created with `rrule`
+    pub dst_policy: DstPolicy,
+}
+
+impl NewTaskData {
+    /// The data as its time mode stores it
+    ///
+    /// All-day tasks are due at midnight UTC of their due date and their
+    /// series run in UTC; floating series start out in `local_timezone`, the
+    /// timezone the user is in.
+    pub fn anchored(mut self, local_timezone: Tz) -> Self {
+        match self.time_mode {
+            TimeMode::Zoned => {}
+            TimeMode::Floating => self.timezone = Some(local_timezone.name().to_string()),
+            TimeMode::AllDay => {
+                self.due_at = self.due_at.map(|due| due.date_naive().and_time(NaiveTime::MIN).and_utc());
+                self.timezone = Some("UTC".to_string());
+            }
+        }
+        self
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -274,6 +426,66 @@ pub struct UpdateTaskData {
     pub timezone: Option<Option<String>>,
     /// For linking/unlinking tasks to/from series (advanced operations)
     pub series_id: Option<Option<Uuid>>,
+    /// Set (`Some(Some(_))`) or clear (`Some(None)`) the reminder offset
+    pub remind_before: Option<Option<Duration>>,
+    /// Set (`Some(Some(_))`) or clear (`Some(None)`) the effort estimate
+    pub estimate: Option<Option<Duration>>,
+    /// Set (`Some(Some(_))`) or clear (`Some(None)`) the scheduled date
+    pub scheduled_at: Option<Option<DateTime<Utc>>>,
+    /// Set (`Some(Some(_))`) or clear (`Some(None)`) the wait date
+    pub wait_until: Option<Option<DateTime<Utc>>>,
+    /// Set (`Some(Some(_))`) or clear (`Some(None)`) the start date
+    pub start_at: Option<Option<DateTime<Utc>>>,
+}
+
+impl UpdateTaskData {
+    /// Re-anchor the scheduling dates, given for `task`, onto a copy of it
+    /// due at `due_at`, such as the template of the series `task` belongs to
+    pub fn dates_relative_to(mut self, task: &Task, due_at: DateTime<Utc>) -> Self {
+        for date in [&mut self.scheduled_at, &mut self.wait_until, &mut self.start_at] {
+            if let Some(Some(value)) = date {
+                *date = Some(task.date_relative_to_due(Some(*value), due_at));
+            }
+        }
+        self
+    }
+}
+
+/// What completing a task does to its pending subtasks
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SubtaskPolicy {
+    /// Complete the task and leave its subtasks pending
+    #[default]
+    Allow,
+    /// Refuse to complete a task while any subtask is pending
+    Block,
+    /// Complete every pending subtask along with the task
+    Cascade,
+}
+
+/// How completing a task affects the rest of its hierarchy
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompletionPolicy {
+    pub subtasks: SubtaskPolicy,
+    /// Complete a parent once none of its subtasks are pending any more
+    pub complete_parent: bool,
+}
+
+/// A change applied to many tasks at once with `bulk_update_tasks`
+#[derive(Debug, Clone)]
+pub enum BulkAction {
+    Complete,
+    Cancel,
+    Delete,
+    /// Edit each task on its own, as `EditScope::ThisOccurrence` does for series instances
+    Update(Box<UpdateTaskData>),
+    /// Move each task into the named workflow status, as `move_task` does
+    Move(String),
+    /// Add the same note to each task
+    Annotate(String),
+    /// Log time spent on each task, ending now
+    LogTime(Duration),
 }
 
 #[derive(Debug)]
@@ -289,6 +501,30 @@ pub enum CompletionResult {
     },
 }
 
+impl CompletionResult {
+    /// The task that was completed, discarding any follow-up occurrence
+    pub fn into_completed(self) -> Task {
+        match self {
+            CompletionResult::Single(completed)
+            | CompletionResult::Recurring { completed, .. }
+            | CompletionResult::SeriesInstance { completed, .. } => completed,
+        }
+    }
+}
+
+/// Tasks linked to a task through subtasks and dependencies
+#[derive(Debug, Clone, Default)]
+pub struct TaskRelations {
+    /// Parent chain, nearest parent first
+    pub ancestors: Vec<Task>,
+    /// Direct subtasks, oldest first
+    pub subtasks: Vec<Task>,
+    /// Tasks this task depends on, oldest first
+    pub depends_on: Vec<Task>,
+    /// Tasks that depend on this task, oldest first
+    pub dependents: Vec<Task>,
+}
+
 // ============================================================================
 // Series-Based Recurrence Models (Phase 1)
 // ============================================================================
@@ -307,8 +543,15 @@ pub struct TaskSeries {
     pub rrule: String,
     /// Series start time in UTC
     pub dtstart: DateTime<Utc>,
-    /// IANA timezone name (e.g., "America/New_York")
+    /// IANA timezone name (e.g., "America/New_York"); for floating series,
+    /// the timezone the user was in at the last materialization
     pub timezone: String,
+    /// How occurrences are anchored in time
+    pub time_mode: TimeMode,
+    /// Where occurrences go whose local time is skipped or repeated by a
+    /// daylight saving change
+    #[sqlx(flatten)]
+    pub dst_policy: DstPolicy,
     /// Whether series is currently generating instances
     pub active: bool,
     /// Boundary for idempotent materialization
@@ -327,6 +570,8 @@ impl Default for TaskSeries {
             rrule: String::new(),
             dtstart: Utc::now(),
             timezone: "UTC".to_string(),
+            time_mode: TimeMode::Zoned,
+            dst_policy: DstPolicy::default(),
             active: true,
             last_materialized_until: None,
             created_at: Utc::now(),
@@ -335,6 +580,125 @@ impl Default for TaskSeries {
     }
 }
 
+/// How the occurrences of a series are anchored in time
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TimeMode {
+    /// At a local time in the series' IANA timezone
+    #[default]
+    Zoned,
+    /// At a wall-clock time in whatever timezone the user is in when the
+    /// occurrence is materialized, e.g. "8am wherever I am"
+    Floating,
+    /// On a date, without a time, e.g. birthdays
+    AllDay,
+}
+
+impl std::fmt::Display for TimeMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimeMode::Zoned => write!(f, "zoned"),
+            TimeMode::Floating => write!(f, "floating"),
+            TimeMode::AllDay => write!(f, "all-day"),
+        }
+    }
+}
+
+/// Where an occurrence goes when its local time falls into a daylight saving
+/// gap, e.g. 2:30am on spring-forward day in America/New_York
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum DstGapPolicy {
+    /// Later by the length of the gap (2:30am becomes 3:30am)
+    #[default]
+    ShiftForward,
+    /// Earlier by the length of the gap (2:30am becomes 1:30am)
+    ShiftBackward,
+    /// Leave the occurrence out
+    Skip,
+}
+
+impl std::fmt::Display for DstGapPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DstGapPolicy::ShiftForward => write!(f, "shift-forward"),
+            DstGapPolicy::ShiftBackward => write!(f, "shift-backward"),
+            DstGapPolicy::Skip => write!(f, "skip"),
+        }
+    }
+}
+
+impl FromStr for DstGapPolicy {
+    type Err = ParseDstPolicyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('_', "-").as_str() {
+            "shift-forward" | "forward" => Ok(DstGapPolicy::ShiftForward),
+            "shift-backward" | "backward" => Ok(DstGapPolicy::ShiftBackward),
+            "skip" => Ok(DstGapPolicy::Skip),
+            _ => Err(ParseDstPolicyError(s.to_string())),
+        }
+    }
+}
+
+/// Which instant an occurrence takes when its local time happens twice
+/// because of a daylight saving change, e.g. 1:30am on fall-back day in
+/// America/New_York
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum DstOverlapPolicy {
+    /// The first time the clock shows it, before the change
+    #[default]
+    Earliest,
+    /// The second time the clock shows it, after the change
+    Latest,
+}
+
+impl std::fmt::Display for DstOverlapPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DstOverlapPolicy::Earliest => write!(f, "earliest"),
+            DstOverlapPolicy::Latest => write!(f, "latest"),
+        }
+    }
+}
+
+impl FromStr for DstOverlapPolicy {
+    type Err = ParseDstPolicyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "earliest" => Ok(DstOverlapPolicy::Earliest),
+            "latest" => Ok(DstOverlapPolicy::Latest),
+            _ => Err(ParseDstPolicyError(s.to_string())),
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+#[error("Invalid DST policy: {0}")]
+pub struct ParseDstPolicyError(String);
+
+/// How a series resolves local times that daylight saving changes skip or
+/// repeat; the default moves skipped times forward and takes the first of
+/// repeated ones
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, FromRow)]
+pub struct DstPolicy {
+    /// For local times that do not exist
+    pub dst_gap: DstGapPolicy,
+    /// For local times that exist twice
+    pub dst_overlap: DstOverlapPolicy,
+}
+
+impl std::fmt::Display for DstPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "gap: {}, overlap: {}", self.dst_gap, self.dst_overlap)
+    }
+}
+
 /// Types of exceptions that can be applied to series occurrences
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
 #[sqlx(type_name = "TEXT", rename_all = "lowercase")]
@@ -394,6 +758,41 @@ pub struct SeriesException {
     pub created_at: DateTime<Utc>,
 }
 
+/// An edit of a series' schedule: its RRULE, start or timezone
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct SeriesRuleChange {
+    pub id: Uuid,
+    pub series_id: Uuid,
+    pub changed_at: DateTime<Utc>,
+    pub old_rrule: String,
+    pub new_rrule: String,
+    pub old_dtstart: DateTime<Utc>,
+    pub new_dtstart: DateTime<Utc>,
+    pub old_timezone: String,
+    pub new_timezone: String,
+}
+
+impl SeriesRuleChange {
+    /// The change from `before` to `after`, or `None` when the schedule is
+    /// the same (an edit of only its active flag or DST policy)
+    pub fn between(before: &TaskSeries, after: &TaskSeries, changed_at: DateTime<Utc>) -> Option<Self> {
+        if before.rrule == after.rrule && before.dtstart == after.dtstart && before.timezone == after.timezone {
+            return None;
+        }
+        Some(Self {
+            id: Uuid::now_v7(),
+            series_id: after.id,
+            changed_at,
+            old_rrule: before.rrule.clone(),
+            new_rrule: after.rrule.clone(),
+            old_dtstart: before.dtstart,
+            new_dtstart: after.dtstart,
+            old_timezone: before.timezone.clone(),
+            new_timezone: after.timezone.clone(),
+        })
+    }
+}
+
 // ============================================================================
 // Data Transfer Objects (DTOs) for Series Operations
 // ============================================================================
@@ -409,6 +808,10 @@ pub struct NewSeriesData {
     pub dtstart: DateTime<Utc>,
     /// IANA timezone name
     pub timezone: String,
+    /// How occurrences are anchored in time
+    pub time_mode: TimeMode,
+    /// Where occurrences go whose local time a DST change skips or repeats
+    pub dst_policy: DstPolicy,
 }
 
 /// Data for modifying existing series
@@ -422,6 +825,8 @@ pub struct UpdateSeriesData {
     pub timezone: Option<String>,
     /// Pause/resume series
     pub active: Option<bool>,
+    /// Change how DST gaps and overlaps are resolved
+    pub dst_policy: Option<DstPolicy>,
 }
 
 /// Data for creating series exceptions
@@ -546,6 +951,173 @@ impl SeriesOccurrence {
 }
 
 
+// ============================================================================
+// Reminder Models
+// ============================================================================
+
+/// Reminder offset attached to a task.
+/// For recurring tasks the row lives on the template and applies to every instance.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TaskReminder {
+    pub task_id: Uuid,
+    /// Minutes before `due_at` at which the reminder fires
+    pub offset_minutes: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TaskReminder {
+    /// Reminder offset as a duration
+    pub fn offset(&self) -> Duration {
+        Duration::minutes(self.offset_minutes)
+    }
+}
+
+/// A pending task whose reminder has not been delivered yet for its current due time
+#[derive(Debug, Clone, FromRow)]
+pub struct DueReminder {
+    #[sqlx(flatten)]
+    pub task: Task,
+    /// Effective offset (the task's own, or its series template's)
+    pub offset_minutes: i64,
+}
+
+impl DueReminder {
+    /// Instance at which the reminder should fire, if the task has a due date
+    pub fn remind_at(&self) -> Option<DateTime<Utc>> {
+        self.task
+            .due_at
+            .map(|due| due - Duration::minutes(self.offset_minutes))
+    }
+}
+
+// ============================================================================
+// Time Tracking Models
+// ============================================================================
+
+/// Work logged against a task, either by a timer or entered manually.
+/// A running timer has no `ended_at`; at most one timer runs at a time.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct TimeEntry {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    /// Length of the entry in seconds, `None` while the timer is running
+    pub duration_seconds: Option<i64>,
+}
+
+impl TimeEntry {
+    /// Whether this entry is the active timer
+    pub fn is_running(&self) -> bool {
+        self.ended_at.is_none()
+    }
+
+    /// Length of a finished entry
+    pub fn duration(&self) -> Option<Duration> {
+        self.duration_seconds.map(Duration::seconds)
+    }
+}
+
+/// A finished time entry with the task details used to build timesheets
+#[derive(Debug, Clone, FromRow)]
+pub struct TimesheetEntry {
+    pub task_id: Uuid,
+    pub task_name: String,
+    pub project_name: Option<String>,
+    /// Comma-separated tag names, like [`crate::repository::TaskQueryResult::tags`]
+    pub tags: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub duration_seconds: i64,
+}
+
+// ============================================================================
+// Annotation Models
+// ============================================================================
+
+/// A timestamped note on a task
+///
+/// Notes on a series instance belong to its occurrence: when a series edit
+/// regenerates the instance, they move over to the regenerated task.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct Annotation {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub text: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// Blueprint Models
+// ============================================================================
+
+/// A saved plan of tasks, applied relative to an anchor date
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct Blueprint {
+    pub id: Uuid,
+    /// Unique name the blueprint is applied by
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// The blueprint's tasks by position
+    #[sqlx(skip)]
+    pub tasks: Vec<BlueprintTask>,
+}
+
+/// One task of a blueprint
+///
+/// Tasks are referred to by their position in the blueprint: a task's parent
+/// comes before it, and dependencies may point either way.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct BlueprintTask {
+    /// Index of the task in [`Blueprint::tasks`]
+    pub position: i64,
+    pub parent_position: Option<i64>,
+    pub name: String,
+    pub description: Option<String>,
+    pub priority: TaskPriority,
+    /// Minutes from the anchor date to the due time
+    pub due_offset_minutes: Option<i64>,
+    /// How the due time is anchored, also for the series of a recurring task
+    pub time_mode: TimeMode,
+    pub estimate_minutes: Option<i64>,
+    /// Rule lines of the series of a recurring task, without DTSTART
+    pub rrule: Option<String>,
+    /// Timezone of the series of a recurring task
+    pub timezone: Option<String>,
+    #[sqlx(skip)]
+    pub tags: Vec<String>,
+    /// Positions of the tasks this task depends on
+    #[sqlx(skip)]
+    pub depends_on: Vec<i64>,
+}
+
+impl BlueprintTask {
+    /// The fields of the task created for this one when its blueprint is
+    /// applied at `anchor`; parent and dependencies are left to the caller
+    pub fn task_data(&self, anchor: DateTime<Utc>) -> NewTaskData {
+        NewTaskData {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            due_at: self.due_offset_minutes.map(|minutes| anchor + Duration::minutes(minutes)),
+            priority: Some(self.priority.clone()),
+            tags: self.tags.clone(),
+            rrule: self.rrule.clone(),
+            timezone: self.timezone.clone(),
+            estimate: self.estimate_minutes.map(Duration::minutes),
+            time_mode: self.time_mode,
+            ..Default::default()
+        }
+    }
+}
+
+/// Data for saving a blueprint
+#[derive(Debug, Clone, Default)]
+pub struct NewBlueprint {
+    pub name: String,
+    pub description: Option<String>,
+    pub tasks: Vec<BlueprintTask>,
+}
+
 /// Statistics for a recurring series (Phase 5)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SeriesStatistics {
@@ -561,6 +1133,7 @@ pub struct SeriesStatistics {
     pub first_occurrence: Option<DateTime<Utc>>,
     pub last_occurrence: Option<DateTime<Utc>>,
     pub next_occurrence: Option<DateTime<Utc>>,
+    /// Mean time tracked on completed occurrences that have time entries
     pub average_completion_time_hours: Option<f64>,
     pub series_health_score: f64, // 0.0-1.0 based on completion rate and consistency
 }
@@ -1,5 +1,6 @@
 use crate::models::{TaskPriority, TaskStatus};
 use chrono::{DateTime, Utc, Duration};
+use uuid::Uuid;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Operator {
@@ -116,11 +117,24 @@ pub enum TextFilter {
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Filter {
+    /// A single task by its full id
+    Id(Uuid),
     Project(String),
     Tags(TagFilter),
     Status(TaskStatus),
+    /// Exact workflow status name, e.g. `review`
+    WorkflowStatus(String),
     Priority(TaskPriority),
     Due(DueDate),
     Name(TextFilter),
     Description(TextFilter),
+    /// Tasks with an annotation matching the text filter; `NotContains`
+    /// matches tasks none of whose annotations contain the text
+    Note(TextFilter),
+    /// Tasks whose wait date is still in the future
+    Waiting,
+    /// Tasks whose scheduled date is still in the future
+    Scheduled,
+    /// Every task, whatever its status
+    All,
 }
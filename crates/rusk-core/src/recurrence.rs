@@ -1,12 +1,13 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, NaiveDateTime, Offset, TimeZone, Utc};
 use chrono_tz::Tz;
 use rrule::{RRuleSet, Tz as RRuleTz};
 use uuid::Uuid;
 use std::sync::OnceLock;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::error::CoreError;
-use crate::models::{SeriesException, Task, TaskSeries};
+use crate::models::{DstGapPolicy, DstPolicy, SeriesException, Task, TaskSeries, TimeMode, UpdateSeriesData};
+use crate::timezone::resolve_local_time;
 
 /// Simple static caches for performance optimization
 static TIMEZONE_CACHE: OnceLock<std::sync::Mutex<HashMap<String, bool>>> = OnceLock::new();
@@ -39,40 +40,351 @@ fn is_timezone_valid_cached(timezone: &str) -> Result<bool, CoreError> {
 }
 
 /// Check if RRULE is valid (cached)
+///
+/// The cache holds whether a rule parses, keyed on the rule and timezone
+/// anchored at a fixed start, so it grows with the distinct rules in use and
+/// not with every series start. Whether an UNTIL lies before `dtstart` (or the
+/// rule's own DTSTART) is checked on every call.
 #[inline]
-fn is_rrule_valid_cached(rrule_key: &str) -> Result<bool, CoreError> {
+fn is_rrule_valid_cached(rrule_key: &str, dtstart: DateTime<Utc>, timezone: &str) -> Result<bool, CoreError> {
     ensure_caches_initialized();
-    
-    if let Ok(mut cache) = RRULE_CACHE.get().unwrap().lock() {
-        if let Some(&is_valid) = cache.get(rrule_key) {
-            return Ok(is_valid);
+
+    let rrule_string = with_dtstart(&with_fixed_start(rrule_key), DateTime::<Utc>::UNIX_EPOCH, timezone);
+
+    let parses = if let Ok(mut cache) = RRULE_CACHE.get().unwrap().lock() {
+        match cache.get(&rrule_string) {
+            Some(&is_valid) => is_valid,
+            None => {
+                // Not in cache, validate and cache result
+                let is_valid = parse_recurrence_set(&rrule_string).is_ok();
+                cache.insert(rrule_string, is_valid);
+                is_valid
+            }
         }
-        
-        // Not in cache, validate and cache result  
-        let test_dtstart = Utc::now();
-        let rrule_string = if !rrule_key.contains("DTSTART") {
-            format!("DTSTART:{}\nRRULE:{}", 
-                test_dtstart.format("%Y%m%dT%H%M%SZ"), 
-                rrule_key)
-        } else {
-            rrule_key.to_string()
-        };
-        
-        let is_valid = rrule_string.parse::<RRuleSet>().is_ok();
-        cache.insert(rrule_key.to_string(), is_valid);
-        Ok(is_valid)
     } else {
         // Fallback if cache lock fails
-        let test_dtstart = Utc::now();
-        let rrule_string = if !rrule_key.contains("DTSTART") {
-            format!("DTSTART:{}\nRRULE:{}", 
-                test_dtstart.format("%Y%m%dT%H%M%SZ"), 
-                rrule_key)
-        } else {
-            rrule_key.to_string()
-        };
-        Ok(rrule_string.parse::<RRuleSet>().is_ok())
+        parse_recurrence_set(&rrule_string).is_ok()
+    };
+
+    Ok(parses && !ends_before_start(&with_dtstart(rrule_key, dtstart, timezone)))
+}
+
+/// `rrule` with the value of its own DTSTART, if any, replaced by the Unix
+/// epoch in the same form
+fn with_fixed_start(rrule: &str) -> String {
+    rrule
+        .lines()
+        .map(|line| match line.trim().split_once(':') {
+            Some((params, value)) if line_property(params) == "DTSTART" => {
+                let epoch = match (value.contains('T'), value.ends_with('Z')) {
+                    (false, _) => "19700101",
+                    (true, false) => "19700101T000000",
+                    (true, true) => "19700101T000000Z",
+                };
+                format!("{}:{}", params, epoch)
+            }
+            _ => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Whether a rule of `set`, an anchored recurrence set, has an UNTIL before
+/// the set's DTSTART, which the rrule crate rejects
+fn ends_before_start(set: &str) -> bool {
+    let Ok((set, _)) = wall_clock_set(set) else {
+        return true;
+    };
+    // Wall-clock values are written as UTC; dates are read as their midnight
+    let value = |value: &str| {
+        let value = value.trim_end_matches('Z');
+        NaiveDateTime::parse_from_str(value, ICAL_DATE_TIME)
+            .or_else(|_| NaiveDateTime::parse_from_str(&format!("{}T000000", value), ICAL_DATE_TIME))
+            .ok()
+    };
+    let start = set.lines().next().and_then(|header| header.split_once(':')).and_then(|(_, start)| value(start));
+    set.lines()
+        .filter(|line| matches!(line_property(line), "RRULE" | "EXRULE"))
+        .filter_map(|line| line.split_once(':').map(|(_, rule)| rule))
+        .flat_map(|rule| rule.split(';'))
+        .filter_map(|part| part.strip_prefix("UNTIL="))
+        .any(|until| value(until).zip(start).is_none_or(|(until, start)| until < start))
+}
+
+/// Properties a recurrence set may hold besides its DTSTART
+const SET_PROPERTIES: [&str; 4] = ["RRULE", "RDATE", "EXRULE", "EXDATE"];
+
+/// The property a recurrence set line starts with, e.g. `RDATE` for
+/// `RDATE;TZID=Europe/Berlin:20250101T090000`
+fn line_property(line: &str) -> &str {
+    line.split([':', ';']).next().unwrap_or_default()
+}
+
+/// The lines of a recurrence set, without its DTSTART header
+///
+/// A series rule is either a single bare rule such as `FREQ=DAILY` or a set
+/// of `RRULE`, `RDATE`, `EXRULE` and `EXDATE` lines. Bare rules come back as
+/// an `RRULE:` line, so both forms can be handled the same way.
+pub fn recurrence_lines(rrule: &str) -> Vec<String> {
+    rrule
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && line_property(line) != "DTSTART")
+        .map(|line| {
+            if SET_PROPERTIES.contains(&line_property(line)) {
+                line.to_string()
+            } else {
+                format!("RRULE:{}", line)
+            }
+        })
+        .collect()
+}
+
+/// `dt` at the wall-clock time it had in `from`, now read in `to`
+///
+/// Used to carry a series into another timezone, such as a floating one into
+/// the timezone the user is in. A time that falls into a daylight saving gap
+/// in `to` moves later by the gap.
+pub(crate) fn same_wall_clock(dt: DateTime<Utc>, from: Tz, to: Tz) -> DateTime<Utc> {
+    let local = dt.with_timezone(&from).naive_local();
+    resolve_local_time(to, local, DstPolicy::default()).unwrap_or(dt)
+}
+
+/// The timezones `series` moves between when `data` changes its timezone
+/// and nothing else of its schedule
+///
+/// `data` gets the start moved to the same wall-clock time in the new
+/// timezone and the rule rewritten with that DTSTART, so occurrences stay at
+/// the times of day they had. The caller moves pending tasks and exceptions
+/// with [`same_wall_clock`].
+pub(crate) fn timezone_move(series: &TaskSeries, data: &mut UpdateSeriesData) -> Result<Option<(Tz, Tz)>, CoreError> {
+    let Some(timezone) = data.timezone.clone() else {
+        return Ok(None);
+    };
+    if data.rrule.is_some() || data.dtstart.is_some() || timezone == series.timezone {
+        return Ok(None);
+    }
+    let parse = |name: &str| name.parse::<Tz>().map_err(|_| CoreError::InvalidTimezone(name.to_string()));
+    let (from, to) = (parse(&series.timezone)?, parse(&timezone)?);
+    let dtstart = same_wall_clock(series.dtstart, from, to);
+    data.rrule = Some(RecurrenceManager::normalize_series_rrule(&series.rrule, dtstart, &timezone, series.time_mode)?);
+    data.dtstart = Some(dtstart);
+    Ok(Some((from, to)))
+}
+
+/// The rule of `series` ending just before `cut`, for deleting an
+/// occurrence together with the ones after it
+///
+/// RRULE lines that still reach `cut` get an UNTIL one second before it in
+/// place of their COUNT or UNTIL, and RDATE values from `cut` on are dropped.
+/// EXRULE and EXDATE lines stay, since they only take occurrences away.
+pub(crate) fn truncated_rrule(series: &TaskSeries, cut: DateTime<Utc>) -> Result<String, CoreError> {
+    let invalid = |e: String| CoreError::InvalidRRule(format!("Failed to parse RRULE '{}': {}", series.rrule, e));
+    let header = series
+        .rrule
+        .lines()
+        .map(str::trim)
+        .find(|line| line_property(line) == "DTSTART")
+        .map(str::to_string);
+    let until = format!("UNTIL={}Z", (cut - Duration::seconds(1)).format(ICAL_DATE_TIME));
+
+    let mut lines: Vec<String> = header.iter().cloned().collect();
+    for line in recurrence_lines(&series.rrule) {
+        match line_property(&line) {
+            "RRULE" => {
+                let set = with_dtstart(&format!("{}\n{}", header.as_deref().unwrap_or_default(), line), series.dtstart, &series.timezone);
+                let (set, timezone) = wall_clock_set(&set).map_err(invalid)?;
+                let rrule_set = set.parse::<RRuleSet>().map_err(|e| invalid(e.to_string()))?;
+                let wall_cut = (cut - Duration::seconds(1)).with_timezone(&timezone).naive_local().and_utc();
+                let (later, _) = rrule_set.after(wall_cut.with_timezone(&RRuleTz::UTC)).all(1);
+                if later.is_empty() {
+                    lines.push(line);
+                    continue;
+                }
+                let parts: Vec<&str> = line
+                    .trim_start_matches("RRULE:")
+                    .split(';')
+                    .filter(|part| !part.starts_with("COUNT=") && !part.starts_with("UNTIL="))
+                    .collect();
+                lines.push(format!("RRULE:{};{}", parts.join(";"), until));
+            }
+            "RDATE" => {
+                // Anchoring pins floating times and dates without changing the number of values
+                let anchored = anchor_line(&line, &series.timezone);
+                let zone = line_timezone(&anchored).map_err(invalid)?.unwrap_or(Tz::UTC);
+                let (Some((params, values)), Some((_, anchored_values))) = (line.split_once(':'), anchored.split_once(':')) else {
+                    lines.push(line);
+                    continue;
+                };
+                let mut kept = Vec::new();
+                for (value, anchored_value) in values.split(',').zip(anchored_values.split(',')) {
+                    let anchored_value = anchored_value.trim();
+                    let local = NaiveDateTime::parse_from_str(anchored_value.trim_end_matches('Z'), ICAL_DATE_TIME)
+                        .map_err(|e| invalid(format!("Invalid date-time '{}': {}", value, e)))?;
+                    let instant = if anchored_value.ends_with('Z') {
+                        local.and_utc()
+                    } else {
+                        resolve_local_time(zone, local, series.dst_policy).unwrap_or_else(|| local.and_utc())
+                    };
+                    if instant < cut {
+                        kept.push(value);
+                    }
+                }
+                if !kept.is_empty() {
+                    lines.push(format!("{}:{}", params, kept.join(",")));
+                }
+            }
+            _ => lines.push(line),
+        }
     }
+    Ok(lines.join("\n"))
+}
+
+/// `rrule` as a full recurrence set, anchored at `dtstart` unless it has a
+/// DTSTART of its own, with floating and date values read in `timezone`
+fn with_dtstart(rrule: &str, dtstart: DateTime<Utc>, timezone: &str) -> String {
+    let header = rrule
+        .lines()
+        .map(str::trim)
+        .find(|line| line_property(line) == "DTSTART")
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("DTSTART:{}", dtstart.format("%Y%m%dT%H%M%SZ")));
+    std::iter::once(header)
+        .chain(recurrence_lines(rrule))
+        .map(|line| anchor_line(&line, timezone))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `line` with its floating times and dates pinned to `timezone`
+///
+/// RFC 5545 lets DTSTART, RDATE and EXDATE hold floating times (neither `Z`
+/// nor `TZID`) and, with `VALUE=DATE`, plain dates. The rrule crate needs
+/// every value anchored, so these are read as wall-clock times in `timezone`,
+/// dates at midnight.
+fn anchor_line(line: &str, timezone: &str) -> String {
+    let property = line_property(line);
+    let Some((params, values)) = line.split_once(':') else {
+        return line.to_string();
+    };
+    if !["DTSTART", "RDATE", "EXDATE"].contains(&property) {
+        return line.to_string();
+    }
+    if params.split(';').any(|param| param == "VALUE=DATE") {
+        let values: Vec<String> = values.split(',').map(|date| format!("{}T000000", date.trim())).collect();
+        format!("{};TZID={}:{}", property, timezone, values.join(","))
+    } else if params == property && !values.contains('Z') {
+        format!("{};TZID={}:{}", property, timezone, values)
+    } else {
+        line.to_string()
+    }
+}
+
+/// Format of the date-times in recurrence set lines
+const ICAL_DATE_TIME: &str = "%Y%m%dT%H%M%S";
+
+/// `set`, an anchored recurrence set, with its times as the wall-clock times
+/// they have in the timezone of its DTSTART, written as UTC, and that timezone
+///
+/// The rrule crate resolves local times that a daylight saving change skips
+/// or repeats on its own terms. Generating wall-clock times leaves that to
+/// the series' [`DstPolicy`] instead.
+fn wall_clock_set(set: &str) -> Result<(String, Tz), String> {
+    let header = set.lines().next().unwrap_or_default();
+    let timezone = line_timezone(header)?.unwrap_or(Tz::UTC);
+
+    let lines = set
+        .lines()
+        .map(|line| {
+            let property = line_property(line);
+            let Some((params, values)) = line.split_once(':') else {
+                return Ok(line.to_string());
+            };
+            match property {
+                "DTSTART" | "RDATE" | "EXDATE" => {
+                    let zone = line_timezone(line)?;
+                    let values = values
+                        .split(',')
+                        .map(|value| wall_clock_value(value.trim(), zone, timezone))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(format!("{}:{}", property, values.join(",")))
+                }
+                "RRULE" | "EXRULE" => {
+                    let parts = values
+                        .split(';')
+                        .map(|part| match part.split_once('=') {
+                            Some(("UNTIL", until)) if until.contains('T') => {
+                                wall_clock_value(until, None, timezone).map(|until| format!("UNTIL={}", until))
+                            }
+                            _ => Ok(part.to_string()),
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(format!("{}:{}", params, parts.join(";")))
+                }
+                _ => Ok(line.to_string()),
+            }
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok((lines.join("\n"), timezone))
+}
+
+/// The timezone of a line's `TZID` parameter, if it has one
+fn line_timezone(line: &str) -> Result<Option<Tz>, String> {
+    let params = line.split_once(':').map_or(line, |(params, _)| params);
+    params
+        .split(';')
+        .find_map(|param| param.strip_prefix("TZID="))
+        .map(|name| name.parse::<Tz>().map_err(|_| format!("Invalid timezone: {}", name)))
+        .transpose()
+}
+
+/// A date-time `value` of a line as a wall-clock time in `timezone`, written
+/// as UTC
+///
+/// `Z` values are instants, and `zone` is the timezone of the line; values
+/// without either are already wall-clock times in `timezone`.
+fn wall_clock_value(value: &str, zone: Option<Tz>, timezone: Tz) -> Result<String, String> {
+    let local = NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), ICAL_DATE_TIME)
+        .map_err(|e| format!("Invalid date-time '{}': {}", value, e))?;
+    let instant = if value.ends_with('Z') {
+        Some(local.and_utc())
+    } else {
+        zone.filter(|zone| *zone != timezone)
+            .map(|zone| resolve_local_time(zone, local, DstPolicy::default()).unwrap_or_else(|| local.and_utc()))
+    };
+    let wall = instant.map_or(local, |instant| instant.with_timezone(&timezone).naive_local());
+    Ok(format!("{}Z", wall.format(ICAL_DATE_TIME)))
+}
+
+/// Parse a full recurrence set into the set that generates occurrences and
+/// one set per EXRULE
+///
+/// The rrule crate does not apply EXRULE lines, so each one is parsed as a
+/// set of its own (sharing the DTSTART) whose occurrences are removed from
+/// the generated ones.
+fn parse_recurrence_set(set: &str) -> Result<(RRuleSet, Vec<RRuleSet>), String> {
+    let header = set.lines().next().unwrap_or_default();
+    let (exrules, lines): (Vec<String>, Vec<String>) = recurrence_lines(set)
+        .into_iter()
+        .partition(|line| line_property(line) == "EXRULE");
+
+    let rrule_set = format!("{}\n{}", header, lines.join("\n"))
+        .parse::<RRuleSet>()
+        .map_err(|e| e.to_string())?;
+    let exrule_sets = exrules
+        .iter()
+        .map(|line| {
+            let rule = line
+                .strip_prefix("EXRULE:")
+                .ok_or_else(|| format!("EXRULE does not take parameters: {}", line))?;
+            format!("{}\nRRULE:{}", header, rule)
+                .parse::<RRuleSet>()
+                .map_err(|e| e.to_string())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((rrule_set, exrule_sets))
 }
 
 /// Occurrence of a series, with exception handling applied
@@ -96,6 +408,13 @@ impl SeriesOccurrence {
         !self.has_exception || self.task_id.is_some()
     }
 
+    /// Returns whether materialization should create an instance for this
+    /// occurrence; overridden and moved occurrences already have their own task
+    #[inline]
+    pub fn needs_instance(&self) -> bool {
+        !self.has_exception
+    }
+
     /// Returns the effective time for this occurrence
     #[inline]
     pub fn effective_at(&self) -> DateTime<Utc> {
@@ -123,10 +442,15 @@ pub struct RecurrenceManager {
     series: TaskSeries,
     /// The template task for this series
     template_task: Task,
-    /// RRule set for occurrence calculation
+    /// RRule set for occurrence calculation, in wall-clock times
     rrule_set: RRuleSet,
+    /// One set per EXRULE line; their occurrences are left out
+    exrule_sets: Vec<RRuleSet>,
     /// Timezone for this series
     timezone: Tz,
+    /// Timezone the wall-clock times of the sets are read in, that of the
+    /// rule's DTSTART
+    rule_timezone: Tz,
     /// Exceptions mapped by occurrence datetime for O(1) lookup
     exceptions: std::collections::HashMap<DateTime<Utc>, SeriesException>,
 }
@@ -144,7 +468,8 @@ impl RecurrenceManager {
     /// 
     /// # Behavior
     /// - Validate series timezone as parseable IANA name
-    /// - Parse RRULE string and create RRuleSet
+    /// - Parse the recurrence set (RRULE, RDATE, EXRULE and EXDATE lines) as
+    ///   wall-clock times, resolved by the series' DST policy when generating
     /// - Build exception lookup map for O(1) access during generation
     /// - Store timezone for later calculations
     pub fn new(
@@ -159,17 +484,14 @@ impl RecurrenceManager {
         let timezone: Tz = series.timezone.parse()
             .map_err(|_| CoreError::InvalidTimezone(series.timezone.clone()))?;
 
-        // For RRULE parsing, we need a complete RRULE string with DTSTART
-        let rrule_string = if !series.rrule.contains("DTSTART") {
-            format!("DTSTART:{}\nRRULE:{}", 
-                series.dtstart.format("%Y%m%dT%H%M%SZ"), 
-                series.rrule)
-        } else {
-            series.rrule.clone()
-        };
+        // For RRULE parsing, we need a complete recurrence set with DTSTART
+        let rrule_string = with_dtstart(&series.rrule, series.dtstart, &series.timezone);
 
-        // Parse the RRULE
-        let rrule_set = rrule_string.parse::<RRuleSet>()
+        // Parse the recurrence set
+        let (rrule_set, exrule_sets, rule_timezone) = wall_clock_set(&rrule_string)
+            .and_then(|(set, rule_timezone)| {
+                parse_recurrence_set(&set).map(|(rrule_set, exrule_sets)| (rrule_set, exrule_sets, rule_timezone))
+            })
             .map_err(|e| CoreError::InvalidRRule(format!("Failed to parse RRULE '{}': {}", rrule_string, e)))?;
 
         // Build exception lookup map - use with_capacity for better performance
@@ -182,7 +504,9 @@ impl RecurrenceManager {
             series,
             template_task,
             rrule_set,
+            exrule_sets,
             timezone,
+            rule_timezone,
             exceptions: exceptions_map,
         })
     }
@@ -211,24 +535,14 @@ impl RecurrenceManager {
     ) -> Result<Vec<SeriesOccurrence>, CoreError> {
         let mut occurrences = Vec::new();
 
-        // Generate occurrences using RRuleSet with proper bounds  
-        // Safe approach: use iterator directly to avoid memory limits
-        let start_rrule = start.with_timezone(&RRuleTz::UTC);
-        let end_rrule = end.with_timezone(&RRuleTz::UTC);
-        
-        let bounded_rrule = self.rrule_set.clone()
-            .after(start_rrule)
-            .before(end_rrule);
-        
         // Use safe iteration with reasonable upper bound for materialization windows
         let max_occurrences_for_window = self.calculate_max_safe_occurrences(start, end)?;
-        let (occurrences_vec, _) = bounded_rrule.all(max_occurrences_for_window);
+        let occurrences_vec = self.occurrences_within(start, Some(end), max_occurrences_for_window);
         
         // Pre-allocate with estimated capacity for better performance
         occurrences.reserve(occurrences_vec.len());
         
-        for dt in occurrences_vec {
-            let occurrence_dt = dt.with_timezone(&Utc);
+        for occurrence_dt in occurrences_vec {
             
             // Check for exceptions with more efficient pattern matching
             match self.exceptions.get(&occurrence_dt) {
@@ -282,13 +596,10 @@ impl RecurrenceManager {
         after: DateTime<Utc>
     ) -> Result<Option<DateTime<Utc>>, CoreError> {
         // Safe approach: limit search to reasonable number of candidates
-        let after_rrule = after.with_timezone(&RRuleTz::UTC);
-        let after_rrule_set = self.rrule_set.clone().after(after_rrule);
-        let (next_occurrences_vec, _) = after_rrule_set.all(50); // Get next 50 to handle many skipped ones
+        let next_occurrences_vec = self.occurrences_within(after, None, 50); // Get next 50 to handle many skipped ones
         
         // Use iterator and find for more efficient processing
-        for dt in next_occurrences_vec {
-            let next_utc = dt.with_timezone(&Utc);
+        for next_utc in next_occurrences_vec {
             
             // Check if this occurrence is skipped by an exception
             match self.exceptions.get(&next_utc) {
@@ -305,6 +616,27 @@ impl RecurrenceManager {
         Ok(None)
     }
 
+    /// Occurrences between `start` and `end` that the series places
+    /// differently than it would under `policy`
+    ///
+    /// These are the instances to drop before the series' DST policy changes
+    /// to `policy`; materialization then creates them at their new times.
+    pub fn occurrences_moved_by(
+        &self,
+        policy: DstPolicy,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<DateTime<Utc>>, CoreError> {
+        let series = TaskSeries { dst_policy: policy, ..self.series.clone() };
+        let other = Self::new(series, self.template_task.clone(), Vec::new())?;
+        let kept: HashSet<DateTime<Utc>> = other.occurrences_within(start, Some(end), u16::MAX).into_iter().collect();
+        Ok(self
+            .occurrences_within(start, Some(end), u16::MAX)
+            .into_iter()
+            .filter(|dt| !kept.contains(dt))
+            .collect())
+    }
+
     /// Validates an RRULE string in the context of a timezone.
     /// 
     /// # Arguments
@@ -315,16 +647,22 @@ impl RecurrenceManager {
     /// * `Result<(), CoreError>` - Ok if valid, error if invalid
     /// 
     /// # Behavior
-    /// - Validate RRULE parses correctly in given timezone
-    /// - Ensure consistent storage format across all series
+    /// - Validate RRULE syntax and timezone only; the result does not depend on
+    ///   the current time. Use [`Self::validate_rrule_at`] to also check the
+    ///   rule against a concrete series start (e.g. UNTIL before DTSTART).
     pub fn validate_rrule(rrule: &str, timezone: &str) -> Result<(), CoreError> {
+        Self::validate_rrule_at(rrule, timezone, DateTime::<Utc>::UNIX_EPOCH)
+    }
+
+    /// Validates an RRULE string for a series starting at `dtstart`.
+    pub fn validate_rrule_at(rrule: &str, timezone: &str, dtstart: DateTime<Utc>) -> Result<(), CoreError> {
         // Validate timezone (with caching)
         if !is_timezone_valid_cached(timezone)? {
             return Err(CoreError::InvalidTimezone(timezone.to_string()));
         }
 
         // Validate RRULE (with caching)
-        if !is_rrule_valid_cached(rrule)? {
+        if !is_rrule_valid_cached(rrule, dtstart, timezone)? {
             return Err(CoreError::InvalidRRule(format!("Invalid RRULE: {}", rrule)));
         }
 
@@ -334,7 +672,7 @@ impl RecurrenceManager {
     /// Normalizes an RRULE string to canonical format.
     /// 
     /// # Arguments
-    /// * `rrule` - Raw RRULE string
+    /// * `rrule` - Raw RRULE string, or a set of RRULE/RDATE/EXRULE/EXDATE lines
     /// * `dtstart` - Series start time
     /// * `timezone` - IANA timezone name
     /// 
@@ -344,13 +682,37 @@ impl RecurrenceManager {
     /// # Behavior
     /// - Normalize to canonical format with explicit DTSTART and TZID
     /// - Ensure consistent storage format across all series
+    /// - Already-normalized rules have their DTSTART header replaced, so
+    ///   normalizing twice (e.g. when duplicating a series) is safe
+    /// - Every set line is kept, in order; a bare rule becomes an `RRULE:` line
     pub fn normalize_rrule(
         rrule: &str, 
         dtstart: DateTime<Utc>, 
         timezone: &str
     ) -> Result<String, CoreError> {
-        // First validate the inputs (uses caching)
-        Self::validate_rrule(rrule, timezone)?;
+        Self::normalize_series_rrule(rrule, dtstart, timezone, TimeMode::Zoned)
+    }
+
+    /// Normalizes an RRULE string for a series anchored by `time_mode`.
+    ///
+    /// Like [`Self::normalize_rrule`], but the DTSTART header takes the
+    /// RFC 5545 form of the mode: `DTSTART;TZID=...` for zoned series, a
+    /// floating `DTSTART:20250101T080000` for floating ones and
+    /// `DTSTART;VALUE=DATE:20250101` for all-day ones, which are kept in UTC.
+    pub fn normalize_series_rrule(
+        rrule: &str,
+        dtstart: DateTime<Utc>,
+        timezone: &str,
+        time_mode: TimeMode,
+    ) -> Result<String, CoreError> {
+        let rrule = recurrence_lines(rrule).join("\n");
+
+        if time_mode == TimeMode::AllDay && timezone != "UTC" {
+            return Err(CoreError::InvalidInput("All-day series must use the UTC timezone".to_string()));
+        }
+
+        // First validate the inputs against the actual start (uses caching)
+        Self::validate_rrule_at(&rrule, timezone, dtstart)?;
 
         // Parse timezone (we know it's valid from cache check above)
         let tz: Tz = timezone.parse()
@@ -359,15 +721,63 @@ impl RecurrenceManager {
         // Convert dtstart to the series timezone
         let dtstart_local = dtstart.with_timezone(&tz);
         
-        // Create a normalized RRULE with explicit DTSTART (skip re-validation)
-        let normalized = format!(
-            "DTSTART;TZID={}:{}\nRRULE:{}",
-            timezone,
-            dtstart_local.format("%Y%m%dT%H%M%S"),
-            rrule
-        );
+        // Create a normalized set with explicit DTSTART (skip re-validation)
+        let header = match time_mode {
+            TimeMode::Zoned => format!("DTSTART;TZID={}:{}", timezone, dtstart_local.format("%Y%m%dT%H%M%S")),
+            TimeMode::Floating => format!("DTSTART:{}", dtstart_local.format("%Y%m%dT%H%M%S")),
+            TimeMode::AllDay => format!("DTSTART;VALUE=DATE:{}", dtstart.format("%Y%m%d")),
+        };
+
+        Ok(format!("{}\n{}", header, rrule))
+    }
+
+    /// Raw occurrences from `after` (inclusive) up to `before`, at most
+    /// `limit` of them, with the EXRULE matches removed and the DST policy
+    /// applied
+    fn occurrences_within(
+        &self,
+        after: DateTime<Utc>,
+        before: Option<DateTime<Utc>>,
+        limit: u16,
+    ) -> Vec<DateTime<Utc>> {
+        let mut bounded_rrule = self.rrule_set.clone().after(self.wall_clock_bound(after, true));
+        if let Some(before) = before {
+            bounded_rrule = bounded_rrule.before(self.wall_clock_bound(before, false));
+        }
+        let (dates, _) = bounded_rrule.all(limit);
 
-        Ok(normalized)
+        let (Some(first), Some(last)) = (dates.first(), dates.last()) else {
+            return Vec::new();
+        };
+        let excluded: HashSet<NaiveDateTime> = self
+            .exrule_sets
+            .iter()
+            .flat_map(|exrule| exrule.clone().after(*first).before(*last).all(u16::MAX).0)
+            .map(|dt| dt.naive_utc())
+            .collect();
+
+        dates
+            .into_iter()
+            .map(|dt| dt.naive_utc())
+            .filter(|wall| !excluded.contains(wall))
+            .filter_map(|wall| resolve_local_time(self.rule_timezone, wall, self.series.dst_policy))
+            .filter(|dt| *dt >= after && before.is_none_or(|before| *dt <= before))
+            .collect()
+    }
+
+    /// The wall-clock time, written as UTC, that occurrences at or after
+    /// (`earliest`) or at or before `dt` can have
+    ///
+    /// Around a daylight saving change the offset of `dt` itself is not
+    /// enough, so the bound allows for the offsets of the days around it.
+    fn wall_clock_bound(&self, dt: DateTime<Utc>, earliest: bool) -> DateTime<RRuleTz> {
+        let offsets = [-1, 0, 1].map(|days| {
+            let utc = (dt + Duration::days(days)).naive_utc();
+            self.rule_timezone.offset_from_utc_datetime(&utc).fix().local_minus_utc()
+        });
+        let offset = if earliest { offsets.iter().min() } else { offsets.iter().max() };
+        let wall = dt.naive_utc() + Duration::seconds(offset.copied().unwrap_or_default().into());
+        wall.and_utc().with_timezone(&RRuleTz::UTC)
     }
 
     /// Calculates a safe maximum number of occurrences for a given time window.
@@ -436,64 +846,252 @@ impl RecurrenceManager {
     ) -> Result<Vec<SeriesOccurrence>, CoreError> {
         let end_time = from + chrono::Duration::days(365); // Look ahead one year
         
-        // More efficient approach: avoid unnecessary timezone conversions
-        let from_rrule = from.with_timezone(&RRuleTz::UTC);
-        let end_rrule = end_time.with_timezone(&RRuleTz::UTC);
-        
-        let bounded_rrule = self.rrule_set.clone()
-            .after(from_rrule)
-            .before(end_rrule);
-        
-        // Safe limit: respect requested count but cap at reasonable maximum
-        let safe_limit = (count.min(500) as u16).max(1);
-        let (occurrences_vec, _) = bounded_rrule.all(safe_limit);
+        // Safe limit: respect requested count but cap at reasonable maximum;
+        // EXRULEs and skipped DST gaps may remove some of them, so look
+        // further ahead then
+        let safe_limit = if self.exrule_sets.is_empty() && self.series.dst_policy.dst_gap != DstGapPolicy::Skip {
+            (count.min(500) as u16).max(1)
+        } else {
+            500
+        };
+        let occurrences_vec = self.occurrences_within(from, Some(end_time), safe_limit);
         
         // Pre-allocate with better capacity estimation
         let mut result = Vec::with_capacity(count.min(occurrences_vec.len()));
         
-        for dt in occurrences_vec.into_iter().take(count) {
-            let occurrence_dt = dt.with_timezone(&Utc);
-            
-            // Check for exceptions with more efficient pattern matching
-            match self.exceptions.get(&occurrence_dt) {
-                Some(exception) => {
-                    match exception.exception_type {
-                        crate::models::ExceptionType::Skip => {
-                            // Skip this occurrence completely
-                            continue;
-                        },
-                        crate::models::ExceptionType::Override | crate::models::ExceptionType::Move => {
-                            // Include with exception marker
-                            result.push(SeriesOccurrence {
-                                occurrence_dt,
-                                effective_dt: occurrence_dt, // May be updated for move exceptions
-                                task_id: exception.exception_task_id,
-                                has_exception: true,
-                            });
-                        }
-                    }
-                },
-                None => {
-                    // Normal occurrence without exception
-                    result.push(SeriesOccurrence {
-                        occurrence_dt,
-                        effective_dt: occurrence_dt,
-                        task_id: None,
-                        has_exception: false,
-                    });
-                }
-            }
-        }
+        result.extend(
+            occurrences_vec
+                .into_iter()
+                .take(count)
+                .filter_map(|occurrence_dt| self.occurrence_at(occurrence_dt)),
+        );
         
         Ok(result)
     }
+
+    /// Upcoming occurrences from `from` on, up to a year ahead, computed as
+    /// they are consumed
+    ///
+    /// Unlike [`Self::preview_occurrences`], skipped occurrences do not count
+    /// towards anything taken from the iterator, so filtering first and then
+    /// taking `n` yields `n` results whenever the year holds that many.
+    pub fn upcoming_occurrences(&self, from: DateTime<Utc>) -> impl Iterator<Item = SeriesOccurrence> + '_ {
+        // Step through time rather than counts: EXRULEs and skipped DST gaps
+        // drop dates after the rrule limit is applied
+        let window = Duration::days(31);
+        let end_time = from + Duration::days(365);
+        let mut cursor = Some(from);
+        let mut batch = std::collections::VecDeque::new();
+
+        std::iter::from_fn(move || loop {
+            if let Some(occurrence_dt) = batch.pop_front() {
+                return Some(occurrence_dt);
+            }
+            let after = cursor.take()?;
+            let before = (after + window).min(end_time);
+            if before < end_time {
+                cursor = Some(before + Duration::nanoseconds(1));
+            }
+            batch.extend(self.occurrences_within(after, Some(before), u16::MAX));
+        })
+        .filter_map(|occurrence_dt| self.occurrence_at(occurrence_dt))
+    }
+
+    /// The occurrence at `occurrence_dt` with its exception applied, `None`
+    /// when it is skipped
+    fn occurrence_at(&self, occurrence_dt: DateTime<Utc>) -> Option<SeriesOccurrence> {
+        match self.exceptions.get(&occurrence_dt) {
+            Some(exception) => match exception.exception_type {
+                crate::models::ExceptionType::Skip => None,
+                crate::models::ExceptionType::Override | crate::models::ExceptionType::Move => Some(SeriesOccurrence {
+                    occurrence_dt,
+                    effective_dt: occurrence_dt,
+                    task_id: exception.exception_task_id,
+                    has_exception: true,
+                }),
+            },
+            None => Some(SeriesOccurrence {
+                occurrence_dt,
+                effective_dt: occurrence_dt,
+                task_id: None,
+                has_exception: false,
+            }),
+        }
+    }
+}
+
+/// Describe a recurrence rule in plain English, e.g. "every 2 weeks on Monday
+/// and Friday, 10 times"
+///
+/// Accepts a bare or normalized rule; a DTSTART line is ignored. Sets with
+/// several lines describe each rule, then count the extra (RDATE) and
+/// excluded (EXDATE) dates, e.g. "every month on day 1 and every month on the
+/// last Friday, plus 2 more dates, except every week in August". Parts the
+/// description does not cover are left out, and rules with an unknown
+/// frequency are returned unchanged.
+pub fn describe_rrule(rrule: &str) -> String {
+    let mut rules = Vec::new();
+    let mut exclusions = Vec::new();
+    let mut extra_dates = 0;
+    let mut excluded_dates = 0;
+    for line in recurrence_lines(rrule) {
+        let value = line.split_once(':').map_or("", |(_, value)| value);
+        let dates = value.split(',').filter(|date| !date.is_empty()).count();
+        match line_property(&line) {
+            "RRULE" => rules.push(describe_rule(value)),
+            "EXRULE" => exclusions.push(describe_rule(value)),
+            "RDATE" => extra_dates += dates,
+            _ => excluded_dates += dates,
+        }
+    }
+    let (Some(rules), Some(mut exclusions)) = (
+        rules.into_iter().collect::<Option<Vec<_>>>(),
+        exclusions.into_iter().collect::<Option<Vec<_>>>(),
+    ) else {
+        return rrule.to_string();
+    };
+
+    let plural = |n: usize| if n == 1 { "date" } else { "dates" };
+    let mut description = join_words(&rules);
+    if extra_dates > 0 {
+        description = if description.is_empty() {
+            format!("on {} {}", extra_dates, plural(extra_dates))
+        } else {
+            format!("{}, plus {} more {}", description, extra_dates, plural(extra_dates))
+        };
+    }
+    if excluded_dates > 0 {
+        exclusions.push(format!("{} {}", excluded_dates, plural(excluded_dates)));
+    }
+    if !exclusions.is_empty() {
+        description.push_str(&format!(", except {}", join_words(&exclusions)));
+    }
+    description
+}
+
+/// Describe a single rule such as `FREQ=WEEKLY;BYDAY=MO`, or `None` when its
+/// frequency is unknown
+fn describe_rule(rule: &str) -> Option<String> {
+    let parts: HashMap<&str, &str> = rule
+        .split(';')
+        .filter_map(|part| part.split_once('='))
+        .collect();
+
+    let interval = parts.get("INTERVAL").and_then(|i| i.parse::<u32>().ok()).unwrap_or(1);
+    let unit = parts.get("FREQ").and_then(|freq| match *freq {
+        "SECONDLY" => Some("second"),
+        "MINUTELY" => Some("minute"),
+        "HOURLY" => Some("hour"),
+        "DAILY" => Some("day"),
+        "WEEKLY" => Some("week"),
+        "MONTHLY" => Some("month"),
+        "YEARLY" => Some("year"),
+        _ => None,
+    })?;
+
+    let weekdays: Vec<&str> = parts.get("BYDAY").map_or_else(Vec::new, |days| days.split(',').collect());
+    let mut description = if weekdays == ["MO", "TU", "WE", "TH", "FR"] && interval == 1 {
+        "every weekday".to_string()
+    } else if interval == 1 {
+        format!("every {}", unit)
+    } else {
+        format!("every {} {}s", interval, unit)
+    };
+
+    if let Some(months) = parts.get("BYMONTH") {
+        let months: Vec<&str> = months.split(',').filter_map(|m| month_name(m.parse().ok()?)).collect();
+        if !months.is_empty() {
+            description.push_str(&format!(" in {}", join_words(&months)));
+        }
+    }
+    if !weekdays.is_empty() && description != "every weekday" {
+        let days: Vec<String> = weekdays.iter().filter_map(|day| describe_weekday(day)).collect();
+        if !days.is_empty() {
+            description.push_str(&format!(" on {}", join_words(&days)));
+        }
+    }
+    if let Some(month_days) = parts.get("BYMONTHDAY") {
+        let days: Vec<&str> = month_days.split(',').collect();
+        let noun = if days.len() == 1 { "day" } else { "days" };
+        description.push_str(&format!(" on {} {}", noun, join_words(&days)));
+    }
+    if let (Some(hour), minute) = (parts.get("BYHOUR"), parts.get("BYMINUTE")) {
+        if let (Ok(hour), Ok(minute)) = (hour.parse::<u32>(), minute.unwrap_or(&"0").parse::<u32>()) {
+            description.push_str(&format!(" at {:02}:{:02}", hour, minute));
+        }
+    }
+
+    if let Some(count) = parts.get("COUNT") {
+        description.push_str(&match *count {
+            "1" => ", once".to_string(),
+            count => format!(", {} times", count),
+        });
+    }
+    if let Some(until) = parts.get("UNTIL") {
+        // UNTIL is YYYYMMDD, optionally followed by a time
+        if until.len() >= 8 && until.is_char_boundary(8) {
+            let date = &until[..8];
+            description.push_str(&format!(", until {}-{}-{}", &date[..4], &date[4..6], &date[6..]));
+        }
+    }
+
+    Some(description)
+}
+
+/// "Monday", or "the last Friday" for positional days such as `-1FR`
+fn describe_weekday(day: &str) -> Option<String> {
+    let split = day.len().checked_sub(2)?;
+    let name = match day.get(split..)? {
+        "MO" => "Monday",
+        "TU" => "Tuesday",
+        "WE" => "Wednesday",
+        "TH" => "Thursday",
+        "FR" => "Friday",
+        "SA" => "Saturday",
+        "SU" => "Sunday",
+        _ => return None,
+    };
+    let ordinal = match &day[..split] {
+        "" => return Some(name.to_string()),
+        "1" | "+1" => "first",
+        "2" | "+2" => "second",
+        "3" | "+3" => "third",
+        "4" | "+4" => "fourth",
+        "-1" => "last",
+        "-2" => "second to last",
+        other => return Some(format!("{} {}", other, name)),
+    };
+    Some(format!("the {} {}", ordinal, name))
+}
+
+fn month_name(month: u32) -> Option<&'static str> {
+    const MONTHS: [&str; 12] = [
+        "January", "February", "March", "April", "May", "June",
+        "July", "August", "September", "October", "November", "December",
+    ];
+    MONTHS.get(month.checked_sub(1)? as usize).copied()
+}
+
+/// "a", "a and b", "a, b and c"
+fn join_words<S: AsRef<str>>(words: &[S]) -> String {
+    match words {
+        [] => String::new(),
+        [only] => only.as_ref().to_string(),
+        [rest @ .., last] => format!(
+            "{} and {}",
+            rest.iter().map(|w| w.as_ref()).collect::<Vec<_>>().join(", "),
+            last.as_ref()
+        ),
+    }
 }
 
 // ============================================================================
 // MaterializationManager (Phase 2)
 // ============================================================================
 
+use crate::clock::{Clock, SystemClock};
 use crate::models::Filter;
+use std::sync::Arc;
 
 /// Statistics collected during materialization operations
 #[derive(Debug, Clone)]
@@ -533,6 +1131,8 @@ impl Default for MaterializationSummary {
 pub struct MaterializationManager {
     /// Configuration for materialization policies
     config: MaterializationConfig,
+    /// Time source for window calculations
+    clock: Arc<dyn Clock>,
 }
 
 /// Configuration for materialization behavior
@@ -565,7 +1165,12 @@ impl Default for MaterializationConfig {
 impl MaterializationManager {
     /// Creates a new MaterializationManager with the given configuration.
     pub fn new(config: MaterializationConfig) -> Self {
-        Self { config }
+        Self::with_clock(config, Arc::new(SystemClock))
+    }
+
+    /// Creates a MaterializationManager that reads the current time from `clock`.
+    pub fn with_clock(config: MaterializationConfig, clock: Arc<dyn Clock>) -> Self {
+        Self { config, clock }
     }
 
     /// Creates a MaterializationManager with default configuration.
@@ -591,7 +1196,7 @@ impl MaterializationManager {
         &self,
         filters: &[Filter],
     ) -> (DateTime<Utc>, DateTime<Utc>) {
-        let now = Utc::now();
+        let now = self.clock.now();
         let mut start_time = now - chrono::Duration::days(self.config.materialization_grace_days as i64);
         let mut end_time = now + chrono::Duration::days(self.config.lookahead_days as i64);
 
@@ -650,13 +1255,23 @@ impl MaterializationManager {
     pub fn update_config(&mut self, config: MaterializationConfig) {
         self.config = config;
     }
+
+    /// Gets the clock used for window calculations.
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    /// Replaces the clock used for window calculations.
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{Task, TaskStatus, TaskPriority};
-    use chrono::Utc;
+    use crate::models::{ExceptionType, Task, TaskStatus, TaskPriority};
+    use chrono::{Datelike, TimeZone, Utc};
     use uuid::Uuid;
 
     fn create_test_series() -> TaskSeries {
@@ -666,6 +1281,8 @@ mod tests {
             rrule: "FREQ=DAILY;INTERVAL=1".to_string(),
             dtstart: Utc::now(),
             timezone: "UTC".to_string(),
+            time_mode: TimeMode::Zoned,
+            dst_policy: DstPolicy::default(),
             active: true,
             last_materialized_until: None,
             created_at: Utc::now(),
@@ -687,6 +1304,12 @@ mod tests {
             project_id: None,
             parent_id: None,
             series_id: None,
+            workflow_status: TaskStatus::Pending.as_str().to_string(),
+            estimate_minutes: None,
+            scheduled_at: None,
+            wait_until: None,
+            start_at: None,
+            all_day: false,
         }
     }
 
@@ -748,6 +1371,33 @@ mod tests {
             assert!(matches!(result.unwrap_err(), CoreError::InvalidTimezone(_)));
         }
 
+        #[test]
+        fn test_validate_rrule_until_against_start() {
+            let start = Utc.with_ymd_and_hms(2025, 3, 10, 9, 0, 0).unwrap();
+            let validate = |rrule: &str, timezone: &str| RecurrenceManager::validate_rrule_at(rrule, timezone, start);
+
+            assert!(validate("FREQ=DAILY;UNTIL=20250310T090000Z", "UTC").is_ok());
+            assert!(validate("FREQ=DAILY;UNTIL=20250310T085959Z", "UTC").is_err());
+            // 9am in New York is 2pm UTC
+            let new_york = "DTSTART;TZID=America/New_York:20250301T090000\nRRULE:FREQ=DAILY;UNTIL=";
+            assert!(validate(&format!("{}20250301T140000Z", new_york), "America/New_York").is_ok());
+            assert!(validate(&format!("{}20250301T135959Z", new_york), "America/New_York").is_err());
+            // A rule's own DTSTART wins over the series start
+            assert!(validate("DTSTART;TZID=UTC:20250301T090000\nRRULE:FREQ=DAILY;UNTIL=20250305T090000Z", "UTC").is_ok());
+            assert!(validate("FREQ=DAILY;UNTIL=20250305T090000Z", "UTC").is_err());
+        }
+
+        #[test]
+        fn test_rrule_cache_key_ignores_start() {
+            let key = |rrule: &str| with_dtstart(&with_fixed_start(rrule), DateTime::<Utc>::UNIX_EPOCH, "UTC");
+            assert_eq!(
+                key("DTSTART;TZID=UTC:20250301T090000\nRRULE:FREQ=DAILY"),
+                key("DTSTART;TZID=UTC:20260101T170000\nRRULE:FREQ=DAILY"),
+            );
+            assert_eq!(key("DTSTART;VALUE=DATE:20250301\nRRULE:FREQ=DAILY"), key("DTSTART;VALUE=DATE:20270704\nRRULE:FREQ=DAILY"));
+            assert_ne!(key("FREQ=DAILY"), key("FREQ=WEEKLY"));
+        }
+
         #[test]
         fn test_normalize_rrule_success() {
             let dtstart = Utc::now();
@@ -760,6 +1410,194 @@ mod tests {
             assert!(normalized.contains("UTC"));
         }
 
+        #[test]
+        fn test_normalize_recurrence_set() {
+            let dtstart = Utc.with_ymd_and_hms(2025, 1, 1, 9, 0, 0).unwrap();
+            let set = "FREQ=MONTHLY;BYMONTHDAY=1\nRRULE:FREQ=MONTHLY;BYDAY=-1FR\nRDATE:20250704T090000Z\nEXRULE:FREQ=YEARLY;BYMONTH=8";
+            let normalized = RecurrenceManager::normalize_rrule(set, dtstart, "UTC").unwrap();
+
+            assert_eq!(
+                normalized,
+                "DTSTART;TZID=UTC:20250101T090000\nRRULE:FREQ=MONTHLY;BYMONTHDAY=1\nRRULE:FREQ=MONTHLY;BYDAY=-1FR\n\
+                 RDATE:20250704T090000Z\nEXRULE:FREQ=YEARLY;BYMONTH=8"
+            );
+            assert_eq!(RecurrenceManager::normalize_rrule(&normalized, dtstart, "UTC").unwrap(), normalized);
+            assert!(RecurrenceManager::validate_rrule("EXRULE:FREQ=DAILY", "UTC").is_err());
+            assert!(RecurrenceManager::validate_rrule("RRULE:FREQ=DAILY\nEXRULE:FREQ=NEVER", "UTC").is_err());
+        }
+
+        #[test]
+        fn test_time_mode_recurrence_sets() {
+            let dtstart = Utc.with_ymd_and_hms(2025, 1, 1, 13, 0, 0).unwrap();
+            let normalize = |mode, timezone| {
+                RecurrenceManager::normalize_series_rrule("FREQ=WEEKLY", dtstart, timezone, mode)
+            };
+            assert_eq!(normalize(TimeMode::Floating, "America/New_York").unwrap(), "DTSTART:20250101T080000\nRRULE:FREQ=WEEKLY");
+            assert_eq!(normalize(TimeMode::AllDay, "UTC").unwrap(), "DTSTART;VALUE=DATE:20250101\nRRULE:FREQ=WEEKLY");
+            assert!(normalize(TimeMode::AllDay, "Europe/Berlin").is_err());
+
+            // Floating and date values are read in the series timezone
+            let mut series = create_test_series();
+            series.timezone = "Europe/Berlin".to_string();
+            series.rrule = "DTSTART:20250101T080000\nRRULE:FREQ=DAILY;COUNT=2\nRDATE;VALUE=DATE:20250110".to_string();
+            let manager = RecurrenceManager::new(series, create_test_task(), vec![]).unwrap();
+            let occurrences: Vec<_> = manager
+                .generate_occurrences_between(dtstart - Duration::days(1), dtstart + Duration::days(30))
+                .unwrap()
+                .into_iter()
+                .map(|o| o.occurrence_dt)
+                .collect();
+            assert_eq!(occurrences, vec![
+                Utc.with_ymd_and_hms(2025, 1, 1, 7, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 1, 2, 7, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 1, 9, 23, 0, 0).unwrap(),
+            ]);
+        }
+
+        #[test]
+        fn test_dst_policies() {
+            let shift_backward = DstPolicy { dst_gap: DstGapPolicy::ShiftBackward, ..Default::default() };
+            let skip = DstPolicy { dst_gap: DstGapPolicy::Skip, ..Default::default() };
+            let latest = DstPolicy { dst_overlap: crate::models::DstOverlapPolicy::Latest, ..Default::default() };
+
+            // Local times of three daily occurrences across a DST change
+            let local_times = |rrule: &str, timezone: &str, policy: DstPolicy| -> Vec<String> {
+                let tz: Tz = timezone.parse().unwrap();
+                let mut series = create_test_series();
+                series.timezone = timezone.to_string();
+                series.rrule = rrule.to_string();
+                series.dst_policy = policy;
+                let manager = RecurrenceManager::new(series, create_test_task(), vec![]).unwrap();
+                let from = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+                manager
+                    .generate_occurrences_between(from, from + Duration::days(366))
+                    .unwrap()
+                    .iter()
+                    .map(|o| o.occurrence_dt.with_timezone(&tz).format("%m-%d %H:%M%:z").to_string())
+                    .collect()
+            };
+            let daily = |timezone: &str, start: &str, policy: DstPolicy| {
+                local_times(&format!("DTSTART;TZID={}:{}\nRRULE:FREQ=DAILY;COUNT=3", timezone, start), timezone, policy)
+            };
+
+            // New York springs forward from 2am to 3am
+            let new_york = "America/New_York";
+            assert_eq!(daily(new_york, "20240309T023000", DstPolicy::default()), ["03-09 02:30-05:00", "03-10 03:30-04:00", "03-11 02:30-04:00"]);
+            assert_eq!(daily(new_york, "20240309T023000", shift_backward), ["03-09 02:30-05:00", "03-10 01:30-05:00", "03-11 02:30-04:00"]);
+            assert_eq!(daily(new_york, "20240309T023000", skip), ["03-09 02:30-05:00", "03-11 02:30-04:00"]);
+            // ... and falls back from 2am to 1am
+            assert_eq!(daily(new_york, "20241102T013000", DstPolicy::default()), ["11-02 01:30-04:00", "11-03 01:30-04:00", "11-04 01:30-05:00"]);
+            assert_eq!(daily(new_york, "20241102T013000", latest), ["11-02 01:30-04:00", "11-03 01:30-05:00", "11-04 01:30-05:00"]);
+
+            // Europe changes at 2am and 3am local time
+            let berlin = "Europe/Berlin";
+            assert_eq!(daily(berlin, "20240330T023000", skip), ["03-30 02:30+01:00", "04-01 02:30+02:00"]);
+            assert_eq!(daily(berlin, "20241026T023000", latest), ["10-26 02:30+02:00", "10-27 02:30+01:00", "10-28 02:30+01:00"]);
+
+            // Sydney springs forward in October
+            let sydney = "Australia/Sydney";
+            assert_eq!(daily(sydney, "20241005T023000", DstPolicy::default()), ["10-05 02:30+10:00", "10-06 03:30+11:00", "10-07 02:30+11:00"]);
+            assert_eq!(daily(sydney, "20241005T023000", shift_backward), ["10-05 02:30+10:00", "10-06 01:30+10:00", "10-07 02:30+11:00"]);
+
+            // Lord Howe Island moves its clocks by half an hour
+            let lord_howe = "Australia/Lord_Howe";
+            assert_eq!(daily(lord_howe, "20241005T021500", DstPolicy::default()), ["10-05 02:15+10:30", "10-06 02:45+11:00", "10-07 02:15+11:00"]);
+            assert_eq!(daily(lord_howe, "20241005T021500", shift_backward), ["10-05 02:15+10:30", "10-06 01:45+10:30", "10-07 02:15+11:00"]);
+
+            // Chile springs forward at midnight, so the whole first hour of the
+            // day is missing
+            let santiago = "America/Santiago";
+            assert_eq!(daily(santiago, "20240907T003000", DstPolicy::default()), ["09-07 00:30-04:00", "09-08 01:30-03:00", "09-09 00:30-03:00"]);
+
+            // One-off dates follow the same policy, and an EXDATE at the
+            // skipped local time removes the occurrence whatever the policy
+            let set = "DTSTART;TZID=America/New_York:20240301T023000\nRDATE;TZID=America/New_York:20240310T023000,20240320T023000";
+            assert_eq!(local_times(set, new_york, DstPolicy::default()), ["03-10 03:30-04:00", "03-20 02:30-04:00"]);
+            assert_eq!(local_times(set, new_york, skip), ["03-20 02:30-04:00"]);
+            let excluded = format!("{}\nEXDATE;TZID=America/New_York:20240310T023000", set);
+            assert_eq!(local_times(&excluded, new_york, shift_backward), ["03-20 02:30-04:00"]);
+        }
+
+        #[test]
+        fn test_occurrences_moved_by() {
+            let mut series = create_test_series();
+            series.timezone = "America/New_York".to_string();
+            series.rrule = "DTSTART;TZID=America/New_York:20240301T023000\nRRULE:FREQ=DAILY".to_string();
+            let manager = RecurrenceManager::new(series, create_test_task(), vec![]).unwrap();
+            let start = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+            let skip = DstPolicy { dst_gap: DstGapPolicy::Skip, ..Default::default() };
+
+            let moved = manager.occurrences_moved_by(skip, start, start + Duration::days(60)).unwrap();
+            assert_eq!(moved, vec![Utc.with_ymd_and_hms(2024, 3, 10, 7, 30, 0).unwrap()]);
+            assert!(manager.occurrences_moved_by(DstPolicy::default(), start, start + Duration::days(60)).unwrap().is_empty());
+        }
+
+        #[test]
+        fn test_recurrence_set_occurrences() {
+            let mut series = create_test_series();
+            series.dtstart = Utc.with_ymd_and_hms(2025, 7, 1, 9, 0, 0).unwrap();
+            series.rrule = "RRULE:FREQ=MONTHLY;BYMONTHDAY=1,15\nRRULE:FREQ=MONTHLY;BYDAY=-1FR\n\
+                            RDATE:20250704T090000Z\nEXRULE:FREQ=YEARLY;BYMONTH=8;BYMONTHDAY=15".to_string();
+            let manager = RecurrenceManager::new(series, create_test_task(), vec![]).unwrap();
+
+            let days: Vec<u32> = manager
+                .generate_occurrences_between(
+                    Utc.with_ymd_and_hms(2025, 7, 1, 0, 0, 0).unwrap(),
+                    Utc.with_ymd_and_hms(2025, 9, 1, 0, 0, 0).unwrap(),
+                )
+                .unwrap()
+                .iter()
+                .map(|o| o.occurrence_dt.day())
+                .collect();
+            // July 1, 4 (RDATE), 15, 25 (last Friday); August 1, 29; August 15 is excluded
+            assert_eq!(days, vec![1, 4, 15, 25, 1, 29]);
+            assert_eq!(
+                manager.next_occurrence_after(Utc.with_ymd_and_hms(2025, 8, 2, 0, 0, 0).unwrap()).unwrap(),
+                Some(Utc.with_ymd_and_hms(2025, 8, 29, 9, 0, 0).unwrap())
+            );
+            assert_eq!(manager.preview_occurrences(Utc.with_ymd_and_hms(2025, 8, 2, 0, 0, 0).unwrap(), 2).unwrap().len(), 2);
+        }
+
+        #[test]
+        fn test_upcoming_occurrences() {
+            let mut series = create_test_series();
+            series.dtstart = Utc.with_ymd_and_hms(2025, 7, 1, 9, 0, 0).unwrap();
+            let exception = |day, exception_type| SeriesException {
+                series_id: series.id,
+                occurrence_dt: Utc.with_ymd_and_hms(2025, 7, day, 9, 0, 0).unwrap(),
+                exception_type,
+                exception_task_id: None,
+                notes: None,
+                created_at: Utc::now(),
+            };
+            let exceptions = vec![
+                exception(2, ExceptionType::Skip),
+                exception(3, ExceptionType::Skip),
+                exception(4, ExceptionType::Skip),
+                exception(5, ExceptionType::Override),
+            ];
+            let manager = RecurrenceManager::new(series.clone(), create_test_task(), exceptions).unwrap();
+            let from = Utc.with_ymd_and_hms(2025, 7, 1, 10, 0, 0).unwrap();
+
+            // Skipped and hidden occurrences do not use up the count
+            assert!(manager.preview_occurrences(from, 3).unwrap().iter().all(|o| !o.is_visible()));
+            let days: Vec<u32> = manager
+                .upcoming_occurrences(from)
+                .filter(|o| o.is_visible())
+                .take(3)
+                .map(|o| o.occurrence_dt.day())
+                .collect();
+            assert_eq!(days, vec![6, 7, 8]);
+
+            // The search crosses month-long steps and stops after a year
+            series.rrule = "RRULE:FREQ=MONTHLY;BYMONTHDAY=1".to_string();
+            let manager = RecurrenceManager::new(series, create_test_task(), vec![]).unwrap();
+            let upcoming: Vec<_> = manager.upcoming_occurrences(from).collect();
+            assert_eq!(upcoming.len(), 12);
+            assert_eq!(upcoming[0].occurrence_dt, Utc.with_ymd_and_hms(2025, 8, 1, 9, 0, 0).unwrap());
+            assert_eq!(upcoming[11].occurrence_dt, Utc.with_ymd_and_hms(2026, 7, 1, 9, 0, 0).unwrap());
+        }
+
         #[test]
         fn test_normalize_rrule_with_timezone() {
             let dtstart = Utc::now();
@@ -772,6 +1610,48 @@ mod tests {
         }
     }
 
+    mod describe_rrule_tests {
+        use super::*;
+
+        #[test]
+        fn test_simple_frequencies() {
+            assert_eq!(describe_rrule("FREQ=DAILY"), "every day");
+            assert_eq!(describe_rrule("FREQ=DAILY;INTERVAL=3"), "every 3 days");
+            assert_eq!(describe_rrule("FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR"), "every weekday");
+            assert_eq!(describe_rrule("FREQ=SOMETIMES"), "FREQ=SOMETIMES");
+        }
+
+        #[test]
+        fn test_days_and_limits() {
+            assert_eq!(
+                describe_rrule("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE,FR;COUNT=10"),
+                "every 2 weeks on Monday, Wednesday and Friday, 10 times"
+            );
+            assert_eq!(
+                describe_rrule("DTSTART;TZID=UTC:20250101T090000\nRRULE:FREQ=MONTHLY;BYDAY=-1FR;UNTIL=20251231T235959Z"),
+                "every month on the last Friday, until 2025-12-31"
+            );
+            assert_eq!(
+                describe_rrule("FREQ=YEARLY;BYMONTH=3;BYMONTHDAY=1;BYHOUR=9;BYMINUTE=30"),
+                "every year in March on day 1 at 09:30"
+            );
+        }
+
+        #[test]
+        fn test_recurrence_sets() {
+            assert_eq!(
+                describe_rrule(
+                    "DTSTART;TZID=UTC:20250101T090000\nRRULE:FREQ=MONTHLY;BYMONTHDAY=1,15\nRRULE:FREQ=MONTHLY;BYDAY=-1FR\n\
+                     RDATE;TZID=UTC:20251224T090000,20251231T090000\nEXRULE:FREQ=YEARLY;BYMONTH=8\nEXDATE:20250301T090000Z"
+                ),
+                "every month on days 1 and 15 and every month on the last Friday, plus 2 more dates, \
+                 except every year in August and 1 date"
+            );
+            assert_eq!(describe_rrule("RDATE:20251224T090000Z"), "on 1 date");
+            assert_eq!(describe_rrule("RRULE:FREQ=DAILY\nRRULE:FREQ=SOMETIMES"), "RRULE:FREQ=DAILY\nRRULE:FREQ=SOMETIMES");
+        }
+    }
+
     mod materialization_manager_tests {
         use super::*;
         use crate::models::{Filter, DueDate};
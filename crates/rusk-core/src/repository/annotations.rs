@@ -0,0 +1,98 @@
+use crate::error::CoreError;
+use crate::models::Annotation;
+use crate::repository::SqliteRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Sqlite, Transaction};
+use uuid::Uuid;
+
+#[async_trait]
+impl super::AnnotationRepository for SqliteRepository {
+    async fn add_annotation(&self, task_id: Uuid, text: &str) -> Result<Annotation, CoreError> {
+        let text = super::annotation_text(text)?;
+        let mut tx = self.pool().begin().await?;
+
+        if Self::find_task_by_id_in_transaction(&mut tx, task_id).await?.is_none() {
+            return Err(CoreError::NotFound(task_id.to_string()));
+        }
+
+        let annotation = Annotation {
+            id: Uuid::now_v7(),
+            task_id,
+            text,
+            created_at: self.now(),
+        };
+        Self::insert_annotation_in_transaction(&mut tx, &annotation).await?;
+
+        tx.commit().await?;
+        Ok(annotation)
+    }
+
+    async fn find_annotations(&self, task_id: Uuid) -> Result<Vec<Annotation>, CoreError> {
+        let annotations = sqlx::query_as(
+            "SELECT id, task_id, text, created_at FROM annotations WHERE task_id = $1 ORDER BY created_at, id"
+        )
+        .bind(task_id)
+        .fetch_all(self.pool())
+        .await?;
+        Ok(annotations)
+    }
+}
+
+impl SqliteRepository {
+    pub(crate) async fn insert_annotation_in_transaction(tx: &mut Transaction<'_, Sqlite>, annotation: &Annotation) -> Result<(), CoreError> {
+        sqlx::query("INSERT INTO annotations (id, task_id, text, created_at) VALUES ($1, $2, $3, $4)")
+            .bind(annotation.id)
+            .bind(annotation.task_id)
+            .bind(&annotation.text)
+            .bind(annotation.created_at)
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+
+    /// Detach the notes of a series' instances due at or after `from` (all
+    /// instances when `None`) so they survive the instances being deleted
+    pub(crate) async fn detach_instance_annotations(
+        tx: &mut Transaction<'_, Sqlite>,
+        series_id: Uuid,
+        template_task_id: Uuid,
+        from: Option<DateTime<Utc>>,
+    ) -> Result<(), CoreError> {
+        sqlx::query(
+            r#"UPDATE annotations
+            SET series_id = $1,
+                occurrence_dt = (SELECT due_at FROM tasks WHERE tasks.id = annotations.task_id),
+                task_id = NULL
+            WHERE task_id IN (
+                SELECT id FROM tasks
+                WHERE series_id = $1 AND id != $2 AND due_at IS NOT NULL AND ($3 IS NULL OR due_at >= $3)
+            )"#,
+        )
+        .bind(series_id)
+        .bind(template_task_id)
+        .bind(from)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    /// Re-attach detached notes to the instance materialized for their occurrence
+    pub(crate) async fn reattach_annotations(
+        tx: &mut Transaction<'_, Sqlite>,
+        instance_id: Uuid,
+        series_id: Uuid,
+        occurrence_dt: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        sqlx::query(
+            r#"UPDATE annotations SET task_id = $1, series_id = NULL, occurrence_dt = NULL
+            WHERE task_id IS NULL AND series_id = $2 AND occurrence_dt = $3"#,
+        )
+        .bind(instance_id)
+        .bind(series_id)
+        .bind(occurrence_dt)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+}
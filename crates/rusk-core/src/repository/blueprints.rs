@@ -0,0 +1,182 @@
+use crate::error::CoreError;
+use crate::models::{Blueprint, BlueprintTask, NewBlueprint, Task};
+use crate::repository::{Repository, SqliteRepository};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
+use uuid::Uuid;
+
+#[async_trait]
+impl super::BlueprintRepository for SqliteRepository {
+    async fn save_blueprint(&self, data: NewBlueprint) -> Result<Blueprint, CoreError> {
+        let data = super::checked_blueprint(data)?;
+        let mut tx = self.pool().begin().await?;
+
+        if Self::find_blueprint_in(&mut tx, &data.name).await?.is_some() {
+            return Err(CoreError::InvalidInput(format!("Blueprint '{}' already exists", data.name)));
+        }
+
+        let blueprint = Blueprint {
+            id: Uuid::now_v7(),
+            name: data.name,
+            description: data.description,
+            created_at: self.now(),
+            tasks: data.tasks,
+        };
+        sqlx::query("INSERT INTO blueprints (id, name, description, created_at) VALUES ($1, $2, $3, $4)")
+            .bind(blueprint.id)
+            .bind(&blueprint.name)
+            .bind(&blueprint.description)
+            .bind(blueprint.created_at)
+            .execute(&mut *tx)
+            .await?;
+
+        for task in &blueprint.tasks {
+            sqlx::query(
+                r#"INSERT INTO blueprint_tasks (blueprint_id, position, parent_position, name, description, priority, due_offset_minutes, time_mode, estimate_minutes, rrule, timezone)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)"#,
+            )
+            .bind(blueprint.id)
+            .bind(task.position)
+            .bind(task.parent_position)
+            .bind(&task.name)
+            .bind(&task.description)
+            .bind(&task.priority)
+            .bind(task.due_offset_minutes)
+            .bind(task.time_mode)
+            .bind(task.estimate_minutes)
+            .bind(&task.rrule)
+            .bind(&task.timezone)
+            .execute(&mut *tx)
+            .await?;
+        }
+        for task in &blueprint.tasks {
+            for tag in &task.tags {
+                sqlx::query("INSERT INTO blueprint_task_tags (blueprint_id, position, tag_name) VALUES ($1, $2, $3)")
+                    .bind(blueprint.id)
+                    .bind(task.position)
+                    .bind(tag)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            for depends_on in &task.depends_on {
+                sqlx::query("INSERT INTO blueprint_task_dependencies (blueprint_id, position, depends_on_position) VALUES ($1, $2, $3)")
+                    .bind(blueprint.id)
+                    .bind(task.position)
+                    .bind(depends_on)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        tx.commit().await?;
+        Ok(blueprint)
+    }
+
+    async fn find_blueprint(&self, name: &str) -> Result<Option<Blueprint>, CoreError> {
+        let mut conn = self.pool().acquire().await?;
+        Self::find_blueprint_in(&mut conn, name).await
+    }
+
+    async fn find_blueprints(&self) -> Result<Vec<Blueprint>, CoreError> {
+        let mut conn = self.pool().acquire().await?;
+        let mut blueprints: Vec<Blueprint> = sqlx::query_as("SELECT * FROM blueprints ORDER BY name")
+            .fetch_all(&mut *conn)
+            .await?;
+        for blueprint in &mut blueprints {
+            blueprint.tasks = Self::find_blueprint_tasks(&mut conn, blueprint.id).await?;
+        }
+        Ok(blueprints)
+    }
+
+    async fn delete_blueprint(&self, name: &str) -> Result<(), CoreError> {
+        let mut tx = self.pool().begin().await?;
+        let blueprint = Self::find_blueprint_in(&mut tx, name)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Blueprint '{}' not found", name)))?;
+
+        for table in ["blueprint_task_dependencies", "blueprint_task_tags", "blueprint_tasks"] {
+            sqlx::query(&format!("DELETE FROM {} WHERE blueprint_id = $1", table))
+                .bind(blueprint.id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        sqlx::query("DELETE FROM blueprints WHERE id = $1")
+            .bind(blueprint.id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn apply_blueprint(&self, name: &str, anchor: DateTime<Utc>, project_name: Option<String>) -> Result<Vec<Task>, CoreError> {
+        let mut tx = self.pool().begin().await?;
+        let blueprint = Self::find_blueprint_in(&mut tx, name)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Blueprint '{}' not found", name)))?;
+
+        let mut created: Vec<Task> = Vec::with_capacity(blueprint.tasks.len());
+        for task in &blueprint.tasks {
+            let mut data = task.task_data(anchor);
+            data.project_name = project_name.clone();
+            data.parent_id = task.parent_position.map(|parent| created[parent as usize].id);
+            let data = data.anchored(self.local_timezone());
+            created.push(self.add_task_or_series_in_transaction(&mut tx, data).await?);
+        }
+        for task in &blueprint.tasks {
+            for &depends_on in &task.depends_on {
+                sqlx::query("INSERT INTO task_dependencies (task_id, depends_on_id) VALUES ($1, $2)")
+                    .bind(created[task.position as usize].id)
+                    .bind(created[depends_on as usize].id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        tx.commit().await?;
+        Ok(created)
+    }
+}
+
+impl SqliteRepository {
+    async fn find_blueprint_in(conn: &mut SqliteConnection, name: &str) -> Result<Option<Blueprint>, CoreError> {
+        let blueprint: Option<Blueprint> = sqlx::query_as("SELECT * FROM blueprints WHERE name = $1")
+            .bind(name)
+            .fetch_optional(&mut *conn)
+            .await?;
+        let Some(mut blueprint) = blueprint else {
+            return Ok(None);
+        };
+        blueprint.tasks = Self::find_blueprint_tasks(conn, blueprint.id).await?;
+        Ok(Some(blueprint))
+    }
+
+    /// Tasks of a blueprint by position, with their tags and dependencies
+    async fn find_blueprint_tasks(conn: &mut SqliteConnection, blueprint_id: Uuid) -> Result<Vec<BlueprintTask>, CoreError> {
+        let mut tasks: Vec<BlueprintTask> = sqlx::query_as("SELECT * FROM blueprint_tasks WHERE blueprint_id = $1 ORDER BY position")
+            .bind(blueprint_id)
+            .fetch_all(&mut *conn)
+            .await?;
+        let tags: Vec<(i64, String)> = sqlx::query_as(
+            "SELECT position, tag_name FROM blueprint_task_tags WHERE blueprint_id = $1 ORDER BY position, tag_name"
+        )
+        .bind(blueprint_id)
+        .fetch_all(&mut *conn)
+        .await?;
+        let dependencies: Vec<(i64, i64)> = sqlx::query_as(
+            "SELECT position, depends_on_position FROM blueprint_task_dependencies WHERE blueprint_id = $1 ORDER BY position, depends_on_position"
+        )
+        .bind(blueprint_id)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        for (position, tag) in tags {
+            tasks[position as usize].tags.push(tag);
+        }
+        for (position, depends_on) in dependencies {
+            tasks[position as usize].depends_on.push(depends_on);
+        }
+        Ok(tasks)
+    }
+}
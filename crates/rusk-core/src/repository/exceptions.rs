@@ -1,6 +1,7 @@
 use crate::error::CoreError;
+use crate::forecast;
 use crate::models::{
-    NewTaskData, Task, TaskSeries, SeriesException, NewSeriesException, ExceptionType,
+    NewTaskData, Project, Task, TaskPriority, TaskSeries, SeriesException, NewSeriesException, ExceptionType,
 };
 use crate::repository::SqliteRepository;
 use async_trait::async_trait;
@@ -59,7 +60,7 @@ impl super::ExceptionRepository for SqliteRepository {
             exception_type: exception.exception_type,
             exception_task_id: exception.exception_task_id,
             notes: exception.notes,
-            created_at: Utc::now(),
+            created_at: self.now(),
         };
 
         sqlx::query(
@@ -90,20 +91,16 @@ impl super::ExceptionRepository for SqliteRepository {
     }
 
     async fn remove_series_exception(&self, series_id: Uuid, occurrence_dt: DateTime<Utc>) -> Result<(), CoreError> {
-        let result = sqlx::query(
-            "DELETE FROM series_exceptions WHERE series_id = $1 AND occurrence_dt = $2"
-        )
-        .bind(series_id)
-        .bind(occurrence_dt)
-        .execute(self.pool())
-        .await?;
+        let mut tx = self.pool().begin().await?;
 
-        if result.rows_affected() == 0 {
+        let removed = Self::remove_series_exception_in_transaction(&mut tx, series_id, occurrence_dt, self.now()).await?;
+        if !removed {
             return Err(CoreError::NotFound(
                 format!("Exception not found for series {} at occurrence {}", series_id, occurrence_dt)
             ));
         }
 
+        tx.commit().await?;
         Ok(())
     }
 
@@ -115,7 +112,7 @@ impl super::ExceptionRepository for SqliteRepository {
             // Validate each exception
             Self::validate_exception_data(self, &exception).await?;
             
-            let created = Self::add_series_exception_in_transaction(&mut tx, exception).await?;
+            let created = Self::add_series_exception_in_transaction(&mut tx, exception, self.now()).await?;
             created_exceptions.push(created);
         }
 
@@ -128,15 +125,9 @@ impl super::ExceptionRepository for SqliteRepository {
         let mut total_removed = 0;
 
         for occurrence_dt in occurrence_dts {
-            let result = sqlx::query(
-                "DELETE FROM series_exceptions WHERE series_id = $1 AND occurrence_dt = $2"
-            )
-            .bind(series_id)
-            .bind(occurrence_dt)
-            .execute(&mut *tx)
-            .await?;
-
-            total_removed += result.rows_affected() as usize;
+            if Self::remove_series_exception_in_transaction(&mut tx, series_id, occurrence_dt, self.now()).await? {
+                total_removed += 1;
+            }
         }
 
         tx.commit().await?;
@@ -159,8 +150,26 @@ impl super::ExceptionRepository for SqliteRepository {
     async fn override_occurrence_with_task(&self, series_id: Uuid, occurrence_dt: DateTime<Utc>, override_task_data: NewTaskData) -> Result<Task, CoreError> {
         let mut tx = self.pool().begin().await?;
 
-        // Create the override task
-        let override_task = Self::add_task_in_transaction(&mut tx, override_task_data).await?;
+        let series: TaskSeries = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
+            .bind(series_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Series with id {} not found", series_id)))?;
+
+        // An already materialized instance becomes the override task, keeping its history
+        let instance: Option<Task> = sqlx::query_as(
+            "SELECT * FROM tasks WHERE series_id = $1 AND due_at = $2 AND id != $3"
+        )
+        .bind(series_id)
+        .bind(occurrence_dt)
+        .bind(series.template_task_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let override_task = match instance {
+            Some(instance) => Self::replace_task_fields_in_transaction(&mut tx, instance.id, override_task_data, self.now()).await?,
+            None => Self::add_task_in_transaction(&mut tx, override_task_data, self.now()).await?,
+        };
 
         // Create or update the exception
         let exception = NewSeriesException {
@@ -171,7 +180,7 @@ impl super::ExceptionRepository for SqliteRepository {
             notes: Some(format!("Override task created: {}", override_task.name)),
         };
 
-        Self::add_series_exception_in_transaction(&mut tx, exception).await?;
+        Self::add_series_exception_in_transaction(&mut tx, exception, self.now()).await?;
 
         tx.commit().await?;
         Ok(override_task)
@@ -209,10 +218,26 @@ impl super::ExceptionRepository for SqliteRepository {
             rrule: None,
             series_id: None, // This is a standalone moved task
             timezone: Some(timezone.to_string()),
+            estimate: template_task.estimate(),
+            scheduled_at: template_task.date_relative_to_due(template_task.scheduled_at, to_dt),
+            wait_until: template_task.date_relative_to_due(template_task.wait_until, to_dt),
+            start_at: template_task.date_relative_to_due(template_task.start_at, to_dt),
             ..Default::default()
         };
 
-        let moved_task = Self::add_task_in_transaction(&mut tx, moved_task_data).await?;
+        // An already materialized instance is moved itself, keeping its history
+        let instance: Option<Task> = sqlx::query_as(
+            "SELECT * FROM tasks WHERE series_id = $1 AND due_at = $2 AND id != $3"
+        )
+        .bind(series_id)
+        .bind(from_dt)
+        .bind(series.template_task_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let moved_task = match instance {
+            Some(instance) => Self::move_task_in_transaction(&mut tx, &instance, to_dt, self.now()).await?,
+            None => Self::add_task_in_transaction(&mut tx, moved_task_data, self.now()).await?,
+        };
 
         // Create move exception
         let exception = NewSeriesException {
@@ -223,7 +248,7 @@ impl super::ExceptionRepository for SqliteRepository {
             notes: Some(format!("Moved from {} to {} ({})", from_dt.format("%Y-%m-%d %H:%M"), to_dt.format("%Y-%m-%d %H:%M"), timezone)),
         };
 
-        Self::add_series_exception_in_transaction(&mut tx, exception).await?;
+        Self::add_series_exception_in_transaction(&mut tx, exception, self.now()).await?;
 
         tx.commit().await?;
         Ok(moved_task)
@@ -265,12 +290,143 @@ impl SqliteRepository {
         Ok(())
     }
 
+    /// Remove the exception at an occurrence within an existing transaction,
+    /// returning whether there was one
+    ///
+    /// The task of an override or move goes back to being the series instance
+    /// for the occurrence, with the template's fields but its own status,
+    /// notes and tracked time.
+    pub(crate) async fn remove_series_exception_in_transaction(
+        tx: &mut Transaction<'_, Sqlite>,
+        series_id: Uuid,
+        occurrence_dt: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<bool, CoreError> {
+        let exception: Option<SeriesException> = sqlx::query_as(
+            "SELECT * FROM series_exceptions WHERE series_id = $1 AND occurrence_dt = $2"
+        )
+        .bind(series_id)
+        .bind(occurrence_dt)
+        .fetch_optional(&mut **tx)
+        .await?;
+        let Some(exception) = exception else {
+            return Ok(false);
+        };
+
+        sqlx::query("DELETE FROM series_exceptions WHERE series_id = $1 AND occurrence_dt = $2")
+            .bind(series_id)
+            .bind(occurrence_dt)
+            .execute(&mut **tx)
+            .await?;
+
+        if let Some(task_id) = exception.exception_task_id {
+            let series: TaskSeries = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
+                .bind(series_id)
+                .fetch_one(&mut **tx)
+                .await?;
+            let template = Self::find_task_by_id_in_transaction(tx, series.template_task_id)
+                .await?
+                .ok_or_else(|| CoreError::NotFound(format!("Template task with id {} not found", series.template_task_id)))?;
+            Self::replace_task_fields_in_transaction(tx, task_id, template.instance_data(series_id, occurrence_dt), now).await?;
+        }
+
+        Ok(true)
+    }
+
+    /// Move a series instance to `to_dt` as a standalone task, keeping its
+    /// scheduling dates at the same distance from the due date
+    pub(crate) async fn move_task_in_transaction(
+        tx: &mut Transaction<'_, Sqlite>,
+        task: &Task,
+        to_dt: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<Task, CoreError> {
+        sqlx::query(
+            r#"UPDATE tasks
+            SET due_at = $1, scheduled_at = $2, wait_until = $3, start_at = $4, series_id = NULL, updated_at = $5
+            WHERE id = $6"#
+        )
+        .bind(to_dt)
+        .bind(task.date_relative_to_due(task.scheduled_at, to_dt))
+        .bind(task.date_relative_to_due(task.wait_until, to_dt))
+        .bind(task.date_relative_to_due(task.start_at, to_dt))
+        .bind(now)
+        .bind(task.id)
+        .execute(&mut **tx)
+        .await?;
+
+        Self::find_task_by_id_in_transaction(tx, task.id)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Task with id {} not found", task.id)))
+    }
+
+    /// Overwrite the fields and tags of an existing task with `data`
+    ///
+    /// Status, dependencies, notes and time entries are left alone.
+    pub(crate) async fn replace_task_fields_in_transaction(
+        tx: &mut Transaction<'_, Sqlite>,
+        task_id: Uuid,
+        data: NewTaskData,
+        now: DateTime<Utc>,
+    ) -> Result<Task, CoreError> {
+        let project_id = match (data.project_id, &data.project_name) {
+            (Some(project_id), _) => Some(project_id),
+            (None, Some(project_name)) => {
+                let project: Option<Project> = sqlx::query_as("SELECT * FROM projects WHERE name = $1")
+                    .bind(project_name)
+                    .fetch_optional(&mut **tx)
+                    .await?;
+                Some(project.map(|p| p.id).ok_or_else(|| CoreError::NotFound(project_name.clone()))?)
+            }
+            (None, None) => None,
+        };
+        let estimate_minutes = data.estimate.map(forecast::estimate_minutes).transpose()?;
+
+        sqlx::query(
+            r#"UPDATE tasks
+            SET name = $1, description = $2, priority = $3, due_at = $4, project_id = $5, parent_id = $6,
+                series_id = $7, estimate_minutes = $8, scheduled_at = $9, wait_until = $10, start_at = $11, updated_at = $12
+            WHERE id = $13"#
+        )
+        .bind(&data.name)
+        .bind(&data.description)
+        .bind(data.priority.unwrap_or(TaskPriority::None))
+        .bind(data.due_at)
+        .bind(project_id)
+        .bind(data.parent_id)
+        .bind(data.series_id)
+        .bind(estimate_minutes)
+        .bind(data.scheduled_at)
+        .bind(data.wait_until)
+        .bind(data.start_at)
+        .bind(now)
+        .bind(task_id)
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query("DELETE FROM task_tags WHERE task_id = $1")
+            .bind(task_id)
+            .execute(&mut **tx)
+            .await?;
+        for tag in &data.tags {
+            sqlx::query("INSERT OR IGNORE INTO task_tags (task_id, tag_name) VALUES ($1, $2)")
+                .bind(task_id)
+                .bind(tag)
+                .execute(&mut **tx)
+                .await?;
+        }
+
+        Self::find_task_by_id_in_transaction(tx, task_id)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Task with id {} not found", task_id)))
+    }
+
     /// Add a series exception within an existing transaction
     pub(crate) async fn add_series_exception_in_transaction(
         tx: &mut Transaction<'_, Sqlite>, 
-        exception: NewSeriesException
+        exception: NewSeriesException,
+        now: DateTime<Utc>,
     ) -> Result<SeriesException, CoreError> {
-        let now = Utc::now();
 
         let created_exception = SeriesException {
             series_id: exception.series_id,
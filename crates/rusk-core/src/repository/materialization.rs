@@ -1,9 +1,10 @@
 use crate::error::CoreError;
-use crate::models::{Task, TaskSeries, TaskStatus, SeriesException};
-use crate::recurrence::RecurrenceManager;
-use crate::repository::{SqliteRepository, SeriesRepository, MaterializationRepository};
+use crate::models::{DstPolicy, Task, TaskSeries, TaskStatus, SeriesException, TimeMode};
+use crate::recurrence::{same_wall_clock, RecurrenceManager};
+use crate::repository::{Repository, SqliteRepository, SeriesRepository, MaterializationRepository};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use sqlx::{Sqlite, Transaction};
 use std::collections::HashSet;
 use uuid::Uuid;
@@ -40,7 +41,7 @@ impl super::MaterializationRepository for SqliteRepository {
             "UPDATE task_series SET last_materialized_until = $1, updated_at = $2 WHERE id = $3"
         )
         .bind(boundary)
-        .bind(Utc::now())
+        .bind(self.now())
         .bind(series_id)
         .execute(self.pool())
         .await?;
@@ -57,6 +58,7 @@ impl SqliteRepository {
     /// Refresh materialization for a single series (public method)
     pub async fn refresh_single_series_materialization(&self, series_id: Uuid, window_start: DateTime<Utc>, window_end: DateTime<Utc>) -> Result<(), CoreError> {
         let mut tx = self.pool().begin().await?;
+        let now = self.now();
 
         // Get series and template task
         let series: TaskSeries = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
@@ -64,6 +66,7 @@ impl SqliteRepository {
             .fetch_optional(&mut *tx)
             .await?
             .ok_or_else(|| CoreError::NotFound(format!("Series with id {} not found", series_id)))?;
+        let series = Self::follow_local_timezone(&mut tx, series, self.local_timezone(), now).await?;
 
         let template_task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = $1")
             .bind(series.template_task_id)
@@ -103,48 +106,15 @@ impl SqliteRepository {
         // Create missing instances
         let mut created_count = 0;
         for occurrence in occurrences {
-            if !occurrence.is_visible() {
-                continue; // Skip hidden occurrences
+            if !occurrence.needs_instance() {
+                continue; // Skipped, or represented by an override or moved task
             }
 
             if existing_due_dates.contains(&occurrence.effective_dt) {
                 continue; // Already materialized
             }
 
-            // Create instance task
-            let instance_task = Task {
-                id: Uuid::now_v7(),
-                name: template_task.name.clone(),
-                description: template_task.description.clone(),
-                status: TaskStatus::Pending,
-                priority: template_task.priority.clone(),
-                due_at: Some(occurrence.effective_dt),
-                completed_at: None,
-                created_at: Utc::now(),
-                updated_at: Utc::now(),
-                project_id: template_task.project_id,
-                parent_id: template_task.parent_id,
-                series_id: Some(series_id),
-            };
-
-            sqlx::query(
-                r#"INSERT INTO tasks (id, name, description, status, priority, due_at, completed_at, created_at, updated_at, project_id, parent_id, series_id)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)"#
-            )
-            .bind(instance_task.id)
-            .bind(&instance_task.name)
-            .bind(&instance_task.description)
-            .bind(&instance_task.status)
-            .bind(&instance_task.priority)
-            .bind(instance_task.due_at)
-            .bind(instance_task.completed_at)
-            .bind(instance_task.created_at)
-            .bind(instance_task.updated_at)
-            .bind(instance_task.project_id)
-            .bind(instance_task.parent_id)
-            .bind(instance_task.series_id)
-            .execute(&mut *tx)
-            .await?;
+            Self::insert_instance_in_transaction(&mut tx, &template_task, series_id, occurrence.effective_dt, now).await?;
 
             created_count += 1;
 
@@ -160,7 +130,7 @@ impl SqliteRepository {
                 "UPDATE task_series SET last_materialized_until = $1, updated_at = $2 WHERE id = $3"
             )
             .bind(window_end)
-            .bind(Utc::now())
+            .bind(now)
             .bind(series_id)
             .execute(&mut *tx)
             .await?;
@@ -170,12 +140,139 @@ impl SqliteRepository {
         Ok(())
     }
 
+    /// Move the pending tasks and exceptions of `series` from the wall-clock
+    /// times they have in `from` to the same times in `to`
+    pub(crate) async fn shift_wall_clock_times<'a>(
+        tx: &mut Transaction<'a, Sqlite>,
+        series: &TaskSeries,
+        from: Tz,
+        to: Tz,
+        now: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        let shift = |dt: DateTime<Utc>| same_wall_clock(dt, from, to);
+
+        let pending: Vec<Task> = sqlx::query_as(
+            "SELECT * FROM tasks WHERE (series_id = $1 OR id = $2) AND status = 'pending'"
+        )
+        .bind(series.id)
+        .bind(series.template_task_id)
+        .fetch_all(&mut **tx)
+        .await?;
+        for task in pending {
+            sqlx::query(
+                "UPDATE tasks SET due_at = $1, scheduled_at = $2, wait_until = $3, start_at = $4, updated_at = $5 WHERE id = $6"
+            )
+            .bind(task.due_at.map(shift))
+            .bind(task.scheduled_at.map(shift))
+            .bind(task.wait_until.map(shift))
+            .bind(task.start_at.map(shift))
+            .bind(now)
+            .bind(task.id)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        let exceptions: Vec<SeriesException> = sqlx::query_as(
+            "SELECT * FROM series_exceptions WHERE series_id = $1"
+        )
+        .bind(series.id)
+        .fetch_all(&mut **tx)
+        .await?;
+        for exception in exceptions {
+            sqlx::query("UPDATE series_exceptions SET occurrence_dt = $1 WHERE series_id = $2 AND occurrence_dt = $3")
+                .bind(shift(exception.occurrence_dt))
+                .bind(series.id)
+                .bind(exception.occurrence_dt)
+                .execute(&mut **tx)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Move a floating series into `local_timezone`, keeping the wall-clock
+    /// times of its start, exceptions and pending tasks
+    ///
+    /// Returns the series as it is stored afterwards; other series come back
+    /// unchanged.
+    pub(crate) async fn follow_local_timezone<'a>(
+        tx: &mut Transaction<'a, Sqlite>,
+        series: TaskSeries,
+        local_timezone: Tz,
+        now: DateTime<Utc>,
+    ) -> Result<TaskSeries, CoreError> {
+        if series.time_mode != TimeMode::Floating || series.timezone == local_timezone.name() {
+            return Ok(series);
+        }
+        let from: Tz = series.timezone.parse()
+            .map_err(|_| CoreError::InvalidTimezone(series.timezone.clone()))?;
+        Self::shift_wall_clock_times(tx, &series, from, local_timezone, now).await?;
+
+        let series = TaskSeries {
+            dtstart: same_wall_clock(series.dtstart, from, local_timezone),
+            timezone: local_timezone.name().to_string(),
+            updated_at: now,
+            ..series
+        };
+        sqlx::query("UPDATE task_series SET dtstart = $1, timezone = $2, updated_at = $3 WHERE id = $4")
+            .bind(series.dtstart)
+            .bind(&series.timezone)
+            .bind(now)
+            .bind(series.id)
+            .execute(&mut **tx)
+            .await?;
+        Ok(series)
+    }
+
+    /// Delete the pending instances that `series` would place elsewhere
+    /// under `policy`, from `now` on, before its DST policy changes
+    ///
+    /// Materialization recreates them at their new times; overridden and
+    /// moved occurrences keep their own tasks.
+    pub(crate) async fn remove_instances_moved_by<'a>(
+        tx: &mut Transaction<'a, Sqlite>,
+        series: &TaskSeries,
+        policy: DstPolicy,
+        now: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        let instances: Vec<Task> = sqlx::query_as(
+            r#"SELECT * FROM tasks
+            WHERE series_id = $1 AND id != $2 AND status = 'pending' AND due_at >= $3
+            AND id NOT IN (SELECT exception_task_id FROM series_exceptions WHERE series_id = $1 AND exception_task_id IS NOT NULL)"#
+        )
+        .bind(series.id)
+        .bind(series.template_task_id)
+        .bind(now)
+        .fetch_all(&mut **tx)
+        .await?;
+        let Some(last_due) = instances.iter().filter_map(|t| t.due_at).max() else {
+            return Ok(());
+        };
+
+        let template_task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = $1")
+            .bind(series.template_task_id)
+            .fetch_one(&mut **tx)
+            .await?;
+        let moved: HashSet<DateTime<Utc>> = RecurrenceManager::new(series.clone(), template_task, Vec::new())?
+            .occurrences_moved_by(policy, now, last_due)?
+            .into_iter()
+            .collect();
+
+        for instance in instances.iter().filter(|t| t.due_at.is_some_and(|due| moved.contains(&due))) {
+            sqlx::query("DELETE FROM tasks WHERE id = $1")
+                .bind(instance.id)
+                .execute(&mut **tx)
+                .await?;
+        }
+        Ok(())
+    }
+
     /// Refresh materialization for a single series within an existing transaction
     pub(crate) async fn refresh_single_series_materialization_in_transaction<'a>(
         tx: &mut Transaction<'a, Sqlite>,
         series_id: Uuid,
         window_start: DateTime<Utc>,
         window_end: DateTime<Utc>,
+        now: DateTime<Utc>,
     ) -> Result<(), CoreError> {
         // Get series and template task
         let series: TaskSeries = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
@@ -222,48 +319,15 @@ impl SqliteRepository {
         // Create missing instances
         let mut created_count = 0;
         for occurrence in occurrences {
-            if !occurrence.is_visible() {
-                continue; // Skip hidden occurrences
+            if !occurrence.needs_instance() {
+                continue; // Skipped, or represented by an override or moved task
             }
 
             if existing_due_dates.contains(&occurrence.effective_dt) {
                 continue; // Already materialized
             }
 
-            // Create instance task
-            let instance_task = Task {
-                id: Uuid::now_v7(),
-                name: template_task.name.clone(),
-                description: template_task.description.clone(),
-                status: TaskStatus::Pending,
-                priority: template_task.priority.clone(),
-                due_at: Some(occurrence.effective_dt),
-                completed_at: None,
-                created_at: Utc::now(),
-                updated_at: Utc::now(),
-                project_id: template_task.project_id,
-                parent_id: template_task.parent_id,
-                series_id: Some(series_id),
-            };
-
-            sqlx::query(
-                r#"INSERT INTO tasks (id, name, description, status, priority, due_at, completed_at, created_at, updated_at, project_id, parent_id, series_id)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)"#
-            )
-            .bind(instance_task.id)
-            .bind(&instance_task.name)
-            .bind(&instance_task.description)
-            .bind(&instance_task.status)
-            .bind(&instance_task.priority)
-            .bind(instance_task.due_at)
-            .bind(instance_task.completed_at)
-            .bind(instance_task.created_at)
-            .bind(instance_task.updated_at)
-            .bind(instance_task.project_id)
-            .bind(instance_task.parent_id)
-            .bind(instance_task.series_id)
-            .execute(&mut **tx)
-            .await?;
+            Self::insert_instance_in_transaction(tx, &template_task, series_id, occurrence.effective_dt, now).await?;
 
             created_count += 1;
 
@@ -280,7 +344,7 @@ impl SqliteRepository {
                 "UPDATE task_series SET last_materialized_until = $1, updated_at = $2 WHERE id = $3"
             )
             .bind(window_end)
-            .bind(Utc::now())
+            .bind(now)
             .bind(series_id)
             .execute(&mut **tx)
             .await?;
@@ -289,6 +353,90 @@ impl SqliteRepository {
         Ok(())
     }
 
+    /// Create the instance of a series due at `due_at` from its template,
+    /// with copies of the template's subtasks, picking up any annotations
+    /// left on that occurrence
+    async fn insert_instance_in_transaction<'a>(
+        tx: &mut Transaction<'a, Sqlite>,
+        template_task: &Task,
+        series_id: Uuid,
+        due_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<Uuid, CoreError> {
+        let instance_id = Uuid::now_v7();
+        sqlx::query(
+            r#"INSERT INTO tasks (id, name, description, status, priority, due_at, completed_at, created_at, updated_at, project_id, parent_id, series_id, estimate_minutes, scheduled_at, wait_until, start_at, all_day)
+            VALUES ($1, $2, $3, $4, $5, $6, NULL, $7, $7, $8, $9, $10, $11, $12, $13, $14, $15)"#
+        )
+        .bind(instance_id)
+        .bind(&template_task.name)
+        .bind(&template_task.description)
+        .bind(TaskStatus::Pending)
+        .bind(&template_task.priority)
+        .bind(due_at)
+        .bind(now)
+        .bind(template_task.project_id)
+        .bind(template_task.parent_id)
+        .bind(series_id)
+        .bind(template_task.estimate_minutes)
+        .bind(template_task.date_relative_to_due(template_task.scheduled_at, due_at))
+        .bind(template_task.date_relative_to_due(template_task.wait_until, due_at))
+        .bind(template_task.date_relative_to_due(template_task.start_at, due_at))
+        .bind(template_task.all_day)
+        .execute(&mut **tx)
+        .await?;
+        Self::reattach_annotations(tx, instance_id, series_id, due_at).await?;
+        Self::copy_template_subtasks_in_transaction(tx, template_task, instance_id, due_at, now).await?;
+        Ok(instance_id)
+    }
+
+    /// Copy the subtasks under `template_task`, however deep, to the instance
+    /// `instance_id` due at `due_at`
+    ///
+    /// The copies start out pending and keep their dates at the same distance
+    /// from the due date as the originals have from the template's.
+    async fn copy_template_subtasks_in_transaction<'a>(
+        tx: &mut Transaction<'a, Sqlite>,
+        template_task: &Task,
+        instance_id: Uuid,
+        due_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        let mut frontier = vec![(template_task.id, instance_id)];
+        while let Some((source_id, copy_parent_id)) = frontier.pop() {
+            let subtasks: Vec<Task> = sqlx::query_as("SELECT * FROM tasks WHERE parent_id = $1 ORDER BY created_at")
+                .bind(source_id)
+                .fetch_all(&mut **tx)
+                .await?;
+
+            for subtask in subtasks {
+                let copy_id = Uuid::now_v7();
+                sqlx::query(
+                    r#"INSERT INTO tasks (id, name, description, status, priority, due_at, completed_at, created_at, updated_at, project_id, parent_id, series_id, estimate_minutes, scheduled_at, wait_until, start_at, all_day)
+                    VALUES ($1, $2, $3, $4, $5, $6, NULL, $7, $7, $8, $9, NULL, $10, $11, $12, $13, $14)"#
+                )
+                .bind(copy_id)
+                .bind(&subtask.name)
+                .bind(&subtask.description)
+                .bind(TaskStatus::Pending)
+                .bind(&subtask.priority)
+                .bind(template_task.date_relative_to_due(subtask.due_at, due_at))
+                .bind(now)
+                .bind(subtask.project_id)
+                .bind(copy_parent_id)
+                .bind(subtask.estimate_minutes)
+                .bind(template_task.date_relative_to_due(subtask.scheduled_at, due_at))
+                .bind(template_task.date_relative_to_due(subtask.wait_until, due_at))
+                .bind(template_task.date_relative_to_due(subtask.start_at, due_at))
+                .bind(subtask.all_day)
+                .execute(&mut **tx)
+                .await?;
+                frontier.push((subtask.id, copy_id));
+            }
+        }
+        Ok(())
+    }
+
     /// Ensures materialization for any query with intelligent window calculation
     pub(crate) async fn ensure_materialization_for_query(&self, query: &crate::query::Query) -> Result<(), CoreError> {
         use crate::repository::query_builder::SqlQueryBuilder;
@@ -301,7 +449,7 @@ impl SqliteRepository {
             self.materialization_manager().calculate_window_for_filters(&filters)
         } else {
             // Default window for all non-date-filtered queries to ensure recurring tasks are visible
-            let now = Utc::now();
+            let now = self.now();
             let grace_days = self.materialization_manager().config().materialization_grace_days as i64;
             let lookahead_days = self.materialization_manager().config().lookahead_days as i64;
             (now - chrono::Duration::days(grace_days), now + chrono::Duration::days(lookahead_days))
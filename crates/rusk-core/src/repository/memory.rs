@@ -0,0 +1,2705 @@
+//! In-memory implementation of the repository traits.
+//!
+//! [`InMemoryRepository`] keeps every table in plain collections behind a
+//! mutex and mirrors the behaviour of [`SqliteRepository`](super::SqliteRepository):
+//! query evaluation over [`Query`], cascading deletes, series materialization
+//! and reminders. It is meant for tests and for applications embedding
+//! `rusk-core` that do not want a database file.
+//!
+//! Every write works on a copy of the state that only replaces the original
+//! once the whole operation succeeded, which gives the same all-or-nothing
+//! behaviour as the SQLite transactions.
+
+use crate::clock::Clock;
+use crate::error::CoreError;
+use crate::forecast;
+use crate::models::{
+    BulkAction, CompletionPolicy, CompletionResult, SubtaskPolicy, DstPolicy, DueReminder, EditScope, ExceptionType, NewSeriesData, NewSeriesException,
+    NewTaskData, Project, SeriesException, SeriesRuleChange, SeriesStatistics, Task, TaskPriority, TaskRelations, TaskReminder,
+    TaskSeries, TaskStatus, UpdateSeriesData, UpdateTaskData, WorkflowStatus, WorkflowTransition,
+    TimeEntry, TimesheetEntry, Annotation, TimeMode, Blueprint, NewBlueprint,
+};
+use crate::query::{DueDate, Filter, Operator, Query, TagFilter, TextFilter};
+use crate::recurrence::{same_wall_clock, timezone_move, truncated_rrule, MaterializationManager, RecurrenceManager};
+use crate::repository::query_builder::{local_date, utc_start_of_day, SqlQueryBuilder};
+use crate::repository::TaskQueryResult;
+use crate::workflow::{self, MoveAction};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, SecondsFormat, SubsecRound, Utc};
+use chrono_tz::Tz;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Batch limit used when a series is materialized as part of a larger write,
+/// matching the fixed limit of the SQLite implementation
+const IN_TRANSACTION_MAX_BATCH_SIZE: usize = 100;
+
+/// Repository that keeps all data in memory
+pub struct InMemoryRepository {
+    state: Mutex<MemoryState>,
+    materialization_manager: MaterializationManager,
+    clock: Arc<dyn Clock>,
+    completion_policy: CompletionPolicy,
+    local_timezone: Tz,
+}
+
+impl InMemoryRepository {
+    /// Create an empty repository that shares the materialization manager's clock
+    pub fn new(materialization_manager: MaterializationManager) -> Self {
+        let clock = materialization_manager.clock().clone();
+        Self {
+            state: Mutex::new(MemoryState::new(clock.now())),
+            materialization_manager,
+            clock,
+            completion_policy: CompletionPolicy::default(),
+            local_timezone: Tz::UTC,
+        }
+    }
+
+    /// Create an empty repository driven by `clock`, which also replaces the
+    /// materialization manager's clock so both agree on the current time
+    pub fn with_clock(mut materialization_manager: MaterializationManager, clock: Arc<dyn Clock>) -> Self {
+        materialization_manager.set_clock(clock.clone());
+        Self {
+            state: Mutex::new(MemoryState::new(clock.now())),
+            materialization_manager,
+            clock,
+            completion_policy: CompletionPolicy::default(),
+            local_timezone: Tz::UTC,
+        }
+    }
+
+    /// Use `policy` whenever a task is completed
+    pub fn with_completion_policy(mut self, policy: CompletionPolicy) -> Self {
+        self.completion_policy = policy;
+        self
+    }
+
+    /// Read floating series and relative day filters such as `due:today` in
+    /// `timezone`, the timezone the user is in (UTC unless set)
+    pub fn with_local_timezone(mut self, timezone: Tz) -> Self {
+        self.local_timezone = timezone;
+        self
+    }
+
+    /// Get the clock used for timestamps and time-relative queries
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+
+    fn read<T>(&self, f: impl FnOnce(&MemoryState) -> T) -> T {
+        f(&self.state.lock().unwrap())
+    }
+
+    /// Run `f` against a copy of the state and keep the result only on success
+    fn transaction<T>(&self, f: impl FnOnce(&mut MemoryState) -> Result<T, CoreError>) -> Result<T, CoreError> {
+        let mut state = self.state.lock().unwrap();
+        let mut draft = state.clone();
+        let result = f(&mut draft)?;
+        *state = draft;
+        Ok(result)
+    }
+
+    fn max_batch_size(&self) -> usize {
+        self.materialization_manager.config().max_batch_size as usize
+    }
+
+    fn ensure_materialization_for_query(&self, query: &Query) -> Result<(), CoreError> {
+        let filters = SqlQueryBuilder::extract_filters_from_query(query);
+
+        let (window_start, window_end) = if filters.iter().any(|f| matches!(f, crate::models::Filter::DueDate(_))) {
+            self.materialization_manager.calculate_window_for_filters(&filters)
+        } else {
+            let now = self.now();
+            let config = self.materialization_manager.config();
+            (
+                now - Duration::days(config.materialization_grace_days as i64),
+                now + Duration::days(config.lookahead_days as i64),
+            )
+        };
+
+        self.refresh_active_series(window_start, window_end)
+    }
+
+    fn refresh_active_series(&self, window_start: DateTime<Utc>, window_end: DateTime<Utc>) -> Result<(), CoreError> {
+        let now = self.now();
+        let max_batch_size = self.max_batch_size();
+        let local_timezone = self.local_timezone;
+        self.transaction(|state| {
+            for series_id in state.active_series().iter().map(|s| s.id).collect::<Vec<_>>() {
+                state.follow_local_timezone(series_id, local_timezone, now)?;
+                state.refresh_series(series_id, window_start, window_end, now, max_batch_size)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Complete a task, recording `workflow_status` as its workflow status
+    fn complete_task_as(&self, id: Uuid, workflow_status: &str) -> Result<CompletionResult, CoreError> {
+        self.transaction(|state| self.complete_task_in_state(state, id, workflow_status))
+    }
+
+    /// Complete a task in `state`, if the workflow lets it move into `workflow_status`
+    fn complete_task_in_state(
+        &self,
+        state: &mut MemoryState,
+        id: Uuid,
+        workflow_status: &str,
+    ) -> Result<CompletionResult, CoreError> {
+        let now = self.now();
+        let policy = self.completion_policy;
+        let max_batch_size = self.max_batch_size();
+        let task = state.task(id).cloned().ok_or_else(|| CoreError::NotFound(id.to_string()))?;
+        workflow::check_status_change(&task, workflow_status, &state.transitions)?;
+
+        let blocking: Vec<&str> = state
+            .dependencies
+            .iter()
+            .filter(|(task_id, _)| *task_id == id)
+            .filter_map(|(_, depends_on_id)| state.task(*depends_on_id))
+            .filter(|t| t.status != TaskStatus::Completed)
+            .map(|t| t.name.as_str())
+            .collect();
+        if !blocking.is_empty() {
+            return Err(CoreError::TaskBlocked(blocking.join(", ")));
+        }
+        state.complete_subtasks(id, &[], policy.subtasks, now)?;
+
+        let completed_task = {
+            let task = state.task_mut(id).expect("task checked above");
+            task.status = TaskStatus::Completed;
+            task.workflow_status = workflow_status.to_string();
+            task.completed_at = Some(now);
+            task.updated_at = now;
+            task.clone()
+        };
+        if policy.complete_parent {
+            state.complete_finished_parents(&task, now);
+        }
+
+        let Some(series_id) = task.series_id else {
+            return Ok(CompletionResult::Single(completed_task));
+        };
+
+        let recurrence_manager = state.recurrence_manager(series_id)?;
+        let next_occurrence = recurrence_manager.next_occurrence_after(completed_task.due_at.unwrap_or(now))?;
+
+        let find_instance = |state: &MemoryState, due: DateTime<Utc>| {
+            state
+                .tasks
+                .iter()
+                .find(|t| t.series_id == Some(series_id) && t.due_at == Some(due))
+                .cloned()
+        };
+
+        let next = match next_occurrence {
+            Some(next_due) => {
+                let (window_start, window_end) = self.materialization_manager.calculate_window_for_filters(&[]);
+                if next_due >= window_start && next_due <= window_end {
+                    if find_instance(state, next_due).is_none() {
+                        state.refresh_series(
+                            series_id,
+                            next_due - Duration::minutes(1),
+                            next_due + Duration::minutes(1),
+                            now,
+                            max_batch_size,
+                        )?;
+                    }
+                    find_instance(state, next_due)
+                } else {
+                    None
+                }
+            }
+            None => None,
+        };
+
+        Ok(CompletionResult::SeriesInstance {
+            completed: completed_task,
+            next,
+            series_id,
+            next_occurrence,
+        })
+    }
+
+    /// Cancel a task, recording `workflow_status` as its workflow status
+    fn cancel_task_as(&self, id: Uuid, workflow_status: &str) -> Result<Task, CoreError> {
+        self.transaction(|state| self.cancel_task_in_state(state, id, workflow_status))
+    }
+
+    /// Cancel a task in `state`, if the workflow lets it move into `workflow_status`
+    fn cancel_task_in_state(&self, state: &mut MemoryState, id: Uuid, workflow_status: &str) -> Result<Task, CoreError> {
+        let now = self.now();
+        let transitions = state.transitions.clone();
+        let task = state.task_mut(id).ok_or_else(|| CoreError::NotFound(id.to_string()))?;
+        workflow::check_status_change(task, workflow_status, &transitions)?;
+        task.status = TaskStatus::Cancelled;
+        task.workflow_status = workflow_status.to_string();
+        task.updated_at = now;
+        Ok(task.clone())
+    }
+
+    /// Move a task in `state` into the workflow status `name`
+    fn move_task_in_state(&self, state: &mut MemoryState, id: Uuid, name: &str) -> Result<Task, CoreError> {
+        let task = state.task(id).cloned().ok_or_else(|| CoreError::NotFound(id.to_string()))?;
+        let target = state.workflow_status(name).cloned().ok_or_else(|| workflow::unknown_status(name))?;
+
+        match workflow::plan_move(&task, &target, &state.transitions)? {
+            MoveAction::Unchanged => Ok(task),
+            MoveAction::Complete => Ok(self.complete_task_in_state(state, id, &target.name)?.into_completed()),
+            MoveAction::Cancel => self.cancel_task_in_state(state, id, &target.name),
+            MoveAction::Relabel { reopen } => {
+                let task = state.task_mut(id).expect("task checked above");
+                task.status = target.category.clone();
+                task.workflow_status = target.name.clone();
+                if reopen {
+                    task.completed_at = None;
+                }
+                task.updated_at = self.now();
+                Ok(task.clone())
+            }
+        }
+    }
+}
+
+impl Default for InMemoryRepository {
+    fn default() -> Self {
+        Self::new(MaterializationManager::with_defaults())
+    }
+}
+
+/// All tables, in insertion order where SQLite would return rowid order
+#[derive(Debug, Clone, Default)]
+struct MemoryState {
+    projects: Vec<Project>,
+    tasks: Vec<Task>,
+    tags: HashMap<Uuid, BTreeSet<String>>,
+    /// `(task_id, depends_on_id)` pairs
+    dependencies: Vec<(Uuid, Uuid)>,
+    series: Vec<TaskSeries>,
+    exceptions: Vec<SeriesException>,
+    rule_changes: Vec<SeriesRuleChange>,
+    reminders: Vec<TaskReminder>,
+    /// `(task_id, due_at)` pairs for which a reminder went out
+    deliveries: HashSet<(Uuid, DateTime<Utc>)>,
+    workflow_statuses: Vec<WorkflowStatus>,
+    transitions: Vec<WorkflowTransition>,
+    time_entries: Vec<TimeEntry>,
+    annotations: Vec<Annotation>,
+    /// Notes of regenerated series instances, by `(series_id, occurrence_dt)`
+    detached_annotations: Vec<(Uuid, DateTime<Utc>, Annotation)>,
+    blueprints: Vec<Blueprint>,
+}
+
+fn constraint_violation(message: String) -> CoreError {
+    CoreError::InvalidInput(message)
+}
+
+fn series_not_found(id: Uuid) -> CoreError {
+    CoreError::NotFound(format!("Series with id {} not found", id))
+}
+
+impl MemoryState {
+    /// Empty state holding only the built-in workflow statuses
+    fn new(now: DateTime<Utc>) -> Self {
+        let workflow_statuses = [TaskStatus::Pending, TaskStatus::Completed, TaskStatus::Cancelled]
+            .into_iter()
+            .map(|category| WorkflowStatus {
+                name: category.as_str().to_string(),
+                category,
+                position: 0,
+                created_at: now,
+            })
+            .collect();
+        Self {
+            workflow_statuses,
+            ..Self::default()
+        }
+    }
+
+    fn workflow_status(&self, name: &str) -> Option<&WorkflowStatus> {
+        self.workflow_statuses.iter().find(|s| s.name == name)
+    }
+
+    fn task(&self, id: Uuid) -> Option<&Task> {
+        self.tasks.iter().find(|t| t.id == id)
+    }
+
+    fn task_mut(&mut self, id: Uuid) -> Option<&mut Task> {
+        self.tasks.iter_mut().find(|t| t.id == id)
+    }
+
+    fn require_task(&self, id: Uuid) -> Result<(), CoreError> {
+        self.task(id)
+            .map(|_| ())
+            .ok_or_else(|| CoreError::NotFound(id.to_string()))
+    }
+
+    /// Tasks anywhere below `id` in the hierarchy
+    fn subtree(&self, id: Uuid) -> Vec<&Task> {
+        let mut ids = HashSet::new();
+        let mut frontier = vec![id];
+        while let Some(parent) = frontier.pop() {
+            for task in &self.tasks {
+                if task.parent_id == Some(parent) && task.id != id && ids.insert(task.id) {
+                    frontier.push(task.id);
+                }
+            }
+        }
+        self.tasks.iter().filter(|t| ids.contains(&t.id)).collect()
+    }
+
+    /// Mark a task completed in the completed category
+    fn mark_completed(&mut self, id: Uuid, now: DateTime<Utc>) {
+        if let Some(task) = self.task_mut(id) {
+            task.status = TaskStatus::Completed;
+            task.workflow_status = TaskStatus::Completed.as_str().to_string();
+            task.completed_at = Some(now);
+            task.updated_at = now;
+        }
+    }
+
+    /// Apply the subtask policy before `id` is completed: refuse while
+    /// subtasks outside `batch` are pending, or complete them along with it
+    fn complete_subtasks(&mut self, id: Uuid, batch: &[Uuid], policy: SubtaskPolicy, now: DateTime<Utc>) -> Result<(), CoreError> {
+        if policy == SubtaskPolicy::Allow {
+            return Ok(());
+        }
+
+        let mut pending: Vec<&Task> = self
+            .subtree(id)
+            .into_iter()
+            .filter(|t| t.status == TaskStatus::Pending && !batch.contains(&t.id))
+            .collect();
+        if pending.is_empty() {
+            return Ok(());
+        }
+        pending.sort_by_key(|t| (t.created_at, t.id));
+
+        if policy == SubtaskPolicy::Block {
+            let names = pending.iter().map(|t| t.name.as_str()).collect::<Vec<_>>().join(", ");
+            return Err(CoreError::PendingSubtasks(names));
+        }
+        for subtask_id in pending.iter().map(|t| t.id).collect::<Vec<_>>() {
+            self.mark_completed(subtask_id, now);
+        }
+        Ok(())
+    }
+
+    /// Complete the ancestors of a just completed `task` that are left without
+    /// pending subtasks or dependencies
+    fn complete_finished_parents(&mut self, task: &Task, now: DateTime<Utc>) {
+        let mut visited = vec![task.id];
+        let mut parent_id = task.parent_id;
+        while let Some(id) = parent_id.filter(|id| !visited.contains(id)) {
+            visited.push(id);
+            let Some(parent) = self.task(id) else {
+                break;
+            };
+            let has_pending_subtasks = self
+                .tasks
+                .iter()
+                .any(|t| t.parent_id == Some(id) && t.status == TaskStatus::Pending);
+            let is_blocked = self
+                .dependencies
+                .iter()
+                .filter(|(task_id, _)| *task_id == id)
+                .filter_map(|(_, depends_on_id)| self.task(*depends_on_id))
+                .any(|t| t.status != TaskStatus::Completed);
+            if parent.status != TaskStatus::Pending || has_pending_subtasks || is_blocked {
+                break;
+            }
+
+            parent_id = parent.parent_id;
+            self.mark_completed(id, now);
+        }
+    }
+
+    fn project_by_name(&self, name: &str) -> Option<&Project> {
+        self.projects.iter().find(|p| p.name == name)
+    }
+
+    fn series(&self, id: Uuid) -> Option<&TaskSeries> {
+        self.series.iter().find(|s| s.id == id)
+    }
+
+    fn series_mut(&mut self, id: Uuid) -> Option<&mut TaskSeries> {
+        self.series.iter_mut().find(|s| s.id == id)
+    }
+
+    fn active_series(&self) -> Vec<TaskSeries> {
+        let mut active: Vec<TaskSeries> = self.series.iter().filter(|s| s.active).cloned().collect();
+        active.sort_by_key(|s| s.created_at);
+        active
+    }
+
+    fn series_exceptions(&self, series_id: Uuid) -> Vec<SeriesException> {
+        self.exceptions
+            .iter()
+            .filter(|e| e.series_id == series_id)
+            .cloned()
+            .collect()
+    }
+
+    fn is_template(&self, task_id: Uuid) -> bool {
+        self.series.iter().any(|s| s.template_task_id == task_id)
+    }
+
+    fn recurrence_manager(&self, series_id: Uuid) -> Result<RecurrenceManager, CoreError> {
+        let series = self.series(series_id).cloned().ok_or_else(|| series_not_found(series_id))?;
+        let template = self.task(series.template_task_id).cloned().ok_or_else(|| {
+            CoreError::NotFound(format!("Template task with id {} not found", series.template_task_id))
+        })?;
+        let exceptions = self.series_exceptions(series_id);
+        RecurrenceManager::new(series, template, exceptions)
+    }
+
+    /// Whether `end` can be reached from `start` by following dependencies
+    fn path_exists(&self, start: Uuid, end: Uuid) -> bool {
+        let mut stack = vec![start];
+        let mut visited = HashSet::new();
+        while let Some(current) = stack.pop() {
+            for &(task_id, depends_on_id) in &self.dependencies {
+                if task_id != current {
+                    continue;
+                }
+                if depends_on_id == end {
+                    return true;
+                }
+                if visited.insert(depends_on_id) {
+                    stack.push(depends_on_id);
+                }
+            }
+        }
+        false
+    }
+
+    fn resolve_project(&self, name: &str) -> Result<Uuid, CoreError> {
+        self.project_by_name(name)
+            .map(|p| p.id)
+            .ok_or_else(|| CoreError::NotFound(name.to_string()))
+    }
+
+    fn add_dependency(&mut self, task_id: Uuid, depends_on_id: Uuid) -> Result<(), CoreError> {
+        if task_id == depends_on_id {
+            return Err(CoreError::InvalidInput(
+                "A task cannot depend on itself.".to_string(),
+            ));
+        }
+        self.require_task(depends_on_id)?;
+
+        if self.path_exists(depends_on_id, task_id) {
+            let name_of = |id: Uuid| {
+                self.task(id)
+                    .map(|t| t.name.clone())
+                    .unwrap_or_else(|| id.to_string())
+            };
+            return Err(CoreError::CircularDependency(name_of(task_id), name_of(depends_on_id)));
+        }
+
+        self.dependencies.push((task_id, depends_on_id));
+        Ok(())
+    }
+
+    /// Add a task, creating its series and materializing it over `window`
+    /// when it has an RRULE; returns the template in that case
+    fn add_task_or_series(
+        &mut self,
+        data: NewTaskData,
+        now: DateTime<Utc>,
+        (window_start, window_end): (DateTime<Utc>, DateTime<Utc>),
+    ) -> Result<Task, CoreError> {
+        let Some(rrule) = data.rrule.clone() else {
+            return self.add_task(data, now);
+        };
+
+        let mut template_data = data.clone();
+        template_data.rrule = None;
+        template_data.series_id = None;
+        let template_task = self.add_task(template_data, now)?;
+
+        let series = self.create_series(
+            NewSeriesData {
+                template_task_id: template_task.id,
+                rrule,
+                dtstart: data.due_at.unwrap_or(now),
+                timezone: data.timezone.unwrap_or_else(|| "UTC".to_string()),
+                time_mode: data.time_mode,
+                dst_policy: data.dst_policy,
+            },
+            now,
+        )?;
+
+        self.refresh_series(series.id, window_start, window_end, now, IN_TRANSACTION_MAX_BATCH_SIZE)?;
+        Ok(template_task)
+    }
+
+    fn add_task(&mut self, mut data: NewTaskData, now: DateTime<Utc>) -> Result<Task, CoreError> {
+        if data.project_id.is_none() {
+            if let Some(project_name) = &data.project_name {
+                data.project_id = Some(self.resolve_project(project_name)?);
+            }
+        }
+        if let Some(project_id) = data.project_id {
+            if !self.projects.iter().any(|p| p.id == project_id) {
+                return Err(CoreError::NotFound(project_id.to_string()));
+            }
+        }
+        if let Some(parent_id) = data.parent_id {
+            self.require_task(parent_id)?;
+        }
+        if let Some(series_id) = data.series_id {
+            self.series(series_id).ok_or_else(|| series_not_found(series_id))?;
+        }
+
+        let estimate_minutes = data.estimate.map(forecast::estimate_minutes).transpose()?;
+
+        let task = Task {
+            id: Uuid::now_v7(),
+            name: data.name,
+            description: data.description,
+            status: TaskStatus::Pending,
+            priority: data.priority.unwrap_or(TaskPriority::None),
+            due_at: data.due_at,
+            completed_at: None,
+            created_at: now,
+            updated_at: now,
+            project_id: data.project_id,
+            parent_id: data.parent_id,
+            series_id: data.series_id,
+            workflow_status: TaskStatus::Pending.as_str().to_string(),
+            estimate_minutes,
+            scheduled_at: data.scheduled_at,
+            wait_until: data.wait_until,
+            start_at: data.start_at,
+            all_day: data.time_mode == TimeMode::AllDay,
+        };
+        self.tasks.push(task.clone());
+
+        if let Some(depends_on_id) = data.depends_on {
+            self.add_dependency(task.id, depends_on_id)?;
+        }
+
+        if let Some(remind_before) = data.remind_before {
+            self.set_reminder(task.id, remind_before, now)?;
+        }
+
+        self.tags.entry(task.id).or_default().extend(data.tags);
+
+        Ok(task)
+    }
+
+    /// Delete a task together with everything SQLite would cascade to
+    fn delete_task(&mut self, id: Uuid) -> bool {
+        if self.task(id).is_none() {
+            return false;
+        }
+
+        // Subtasks are deleted with their parent
+        let mut doomed = HashSet::from([id]);
+        let mut frontier = vec![id];
+        while let Some(parent) = frontier.pop() {
+            for task in &self.tasks {
+                if task.parent_id == Some(parent) && doomed.insert(task.id) {
+                    frontier.push(task.id);
+                }
+            }
+        }
+
+        // Series whose template is deleted go away; their instances are detached
+        let dropped_series: HashSet<Uuid> = self
+            .series
+            .iter()
+            .filter(|s| doomed.contains(&s.template_task_id))
+            .map(|s| s.id)
+            .collect();
+        self.series.retain(|s| !dropped_series.contains(&s.id));
+        for task in &mut self.tasks {
+            if task.series_id.is_some_and(|sid| dropped_series.contains(&sid)) {
+                task.series_id = None;
+            }
+        }
+
+        self.tasks.retain(|t| !doomed.contains(&t.id));
+        self.tags.retain(|task_id, _| !doomed.contains(task_id));
+        self.dependencies
+            .retain(|(task_id, depends_on_id)| !doomed.contains(task_id) && !doomed.contains(depends_on_id));
+        self.exceptions.retain(|e| {
+            !dropped_series.contains(&e.series_id)
+                && !e.exception_task_id.is_some_and(|tid| doomed.contains(&tid))
+        });
+        self.reminders.retain(|r| !doomed.contains(&r.task_id));
+        self.deliveries.retain(|(task_id, _)| !doomed.contains(task_id));
+        self.time_entries.retain(|e| !doomed.contains(&e.task_id));
+        self.annotations.retain(|a| !doomed.contains(&a.task_id));
+        self.detached_annotations.retain(|(series_id, _, _)| !dropped_series.contains(series_id));
+        true
+    }
+
+    fn delete_series_instances(&mut self, series_id: Uuid, keep: impl Fn(&Task) -> bool) {
+        let doomed: Vec<(Uuid, Option<DateTime<Utc>>)> = self
+            .tasks
+            .iter()
+            .filter(|t| t.series_id == Some(series_id) && !keep(t))
+            .map(|t| (t.id, t.due_at))
+            .collect();
+
+        // Notes stay with the occurrence until it is materialized again
+        for (id, due_at) in &doomed {
+            let Some(occurrence_dt) = *due_at else {
+                continue;
+            };
+            let (detached, kept) = std::mem::take(&mut self.annotations)
+                .into_iter()
+                .partition(|a| a.task_id == *id);
+            self.annotations = kept;
+            self.detached_annotations
+                .extend(detached.into_iter().map(|a: Annotation| (series_id, occurrence_dt, a)));
+        }
+
+        for (id, _) in doomed {
+            self.delete_task(id);
+        }
+    }
+
+    /// Delete a task, taking the series of a template with it
+    fn delete_task_or_series(&mut self, id: Uuid) -> bool {
+        match self.series.iter().find(|s| s.template_task_id == id).map(|s| s.id) {
+            Some(series_id) => {
+                self.delete_whole_series(series_id, true);
+                true
+            }
+            None => self.delete_task(id),
+        }
+    }
+
+    /// Delete a task and the occurrences of its series that `scope` covers,
+    /// see [`TaskRepository::delete_task_with_scope`](super::TaskRepository::delete_task_with_scope)
+    fn delete_in_scope(&mut self, id: Uuid, scope: EditScope, keep_history: bool, now: DateTime<Utc>) -> Result<(), CoreError> {
+        let task = self.task(id).cloned().ok_or_else(|| CoreError::NotFound(id.to_string()))?;
+        // Moved occurrences leave the series; their exception still knows it
+        // and the time they were scheduled for
+        let exception = self.exceptions.iter().find(|e| e.exception_task_id == Some(id)).cloned();
+        let series_id = task.series_id.or(exception.as_ref().map(|e| e.series_id));
+        let series = self
+            .series
+            .iter()
+            .find(|s| series_id == Some(s.id) || s.template_task_id == id)
+            .cloned();
+        let Some(series) = series else {
+            self.delete_task(id);
+            return Ok(());
+        };
+        if series.template_task_id == id {
+            self.delete_whole_series(series.id, keep_history);
+            return Ok(());
+        }
+        let occurrence_dt = exception.map(|e| e.occurrence_dt).or(task.due_at);
+
+        match (scope, occurrence_dt) {
+            (EditScope::ThisOccurrence, Some(occurrence_dt)) => {
+                self.delete_task(id);
+                self.exceptions.retain(|e| !(e.series_id == series.id && e.occurrence_dt == occurrence_dt));
+                let skip = NewSeriesException {
+                    series_id: series.id,
+                    occurrence_dt,
+                    exception_type: ExceptionType::Skip,
+                    exception_task_id: None,
+                    notes: None,
+                };
+                self.add_exception(skip, now)?;
+            }
+            (EditScope::ThisOccurrence, None) => {
+                self.delete_task(id);
+            }
+            (EditScope::ThisAndFuture, Some(cut)) if cut > series.dtstart => {
+                self.end_series_before(series.id, cut, now)?;
+            }
+            _ => self.delete_whole_series(series.id, keep_history),
+        }
+        Ok(())
+    }
+
+    /// Delete a series with its instances and exceptions, leaving its template
+    fn delete_series(&mut self, id: Uuid) {
+        self.exceptions.retain(|e| e.series_id != id);
+        self.rule_changes.retain(|c| c.series_id != id);
+        self.delete_series_instances(id, |_| false);
+        self.detached_annotations.retain(|(series_id, _, _)| *series_id != id);
+        self.series.retain(|s| s.id != id);
+    }
+
+    /// Delete a series with its template, instances and exceptions
+    ///
+    /// With `keep_history` the completed instances stay as regular tasks.
+    fn delete_whole_series(&mut self, id: Uuid, keep_history: bool) {
+        let Some(template_id) = self.series(id).map(|s| s.template_task_id) else {
+            return;
+        };
+        if keep_history {
+            for task in &mut self.tasks {
+                if task.series_id == Some(id) && task.status == TaskStatus::Completed {
+                    task.series_id = None;
+                }
+            }
+        }
+        // Moved occurrences have left the series but go with it all the same
+        let moved: Vec<Uuid> = self
+            .exceptions
+            .iter()
+            .filter(|e| e.series_id == id)
+            .filter_map(|e| e.exception_task_id)
+            .filter(|task_id| !(keep_history && self.task(*task_id).is_some_and(|t| t.status == TaskStatus::Completed)))
+            .collect();
+        for task_id in moved {
+            self.delete_task(task_id);
+        }
+        self.delete_series(id);
+        self.delete_task(template_id);
+    }
+
+    /// End a series before `cut`, deleting the occurrences from `cut` on
+    /// along with their exceptions and notes
+    fn end_series_before(&mut self, id: Uuid, cut: DateTime<Utc>, now: DateTime<Utc>) -> Result<(), CoreError> {
+        let series = self.series(id).cloned().ok_or_else(|| series_not_found(id))?;
+        let rrule = truncated_rrule(&series, cut)?;
+
+        // Moved occurrences go by the time they were scheduled for
+        let moved: HashSet<Uuid> = self
+            .exceptions
+            .iter()
+            .filter(|e| e.series_id == id && e.occurrence_dt >= cut)
+            .filter_map(|e| e.exception_task_id)
+            .collect();
+        self.exceptions.retain(|e| !(e.series_id == id && e.occurrence_dt >= cut));
+        self.detached_annotations
+            .retain(|(series_id, occurrence_dt, _)| !(*series_id == id && *occurrence_dt >= cut));
+        let doomed: Vec<Uuid> = self
+            .tasks
+            .iter()
+            .filter(|t| moved.contains(&t.id) || (t.series_id == Some(id) && t.due_at.is_some_and(|due| due >= cut)))
+            .map(|t| t.id)
+            .collect();
+        for task_id in doomed {
+            self.delete_task(task_id);
+        }
+
+        self.apply_series_update(id, UpdateSeriesData { rrule: Some(rrule), ..Default::default() }, now)?;
+        Ok(())
+    }
+
+    /// Instances whose series no longer exists, oldest first
+    fn orphaned_instances(&self) -> Vec<Task> {
+        let mut orphans: Vec<Task> = self
+            .tasks
+            .iter()
+            .filter(|t| t.series_id.is_some_and(|series_id| self.series(series_id).is_none()))
+            .cloned()
+            .collect();
+        orphans.sort_by_key(|t| (t.created_at, t.id));
+        orphans
+    }
+
+    fn set_reminder(&mut self, task_id: Uuid, remind_before: Duration, now: DateTime<Utc>) -> Result<TaskReminder, CoreError> {
+        if remind_before < Duration::zero() {
+            return Err(CoreError::InvalidInput(
+                "Reminder offset cannot be negative".to_string(),
+            ));
+        }
+
+        let offset_minutes = remind_before.num_minutes();
+        match self.reminders.iter_mut().find(|r| r.task_id == task_id) {
+            Some(existing) => {
+                existing.offset_minutes = offset_minutes;
+                Ok(existing.clone())
+            }
+            None => {
+                let reminder = TaskReminder {
+                    task_id,
+                    offset_minutes,
+                    created_at: now,
+                };
+                self.reminders.push(reminder.clone());
+                Ok(reminder)
+            }
+        }
+    }
+
+    fn update_task_fields(&mut self, id: Uuid, data: &UpdateTaskData, now: DateTime<Utc>) -> Result<(), CoreError> {
+        let project_id = match &data.project_name {
+            Some(Some(project_name)) => Some(Some(self.resolve_project(project_name)?)),
+            Some(None) => Some(None),
+            None => None,
+        };
+        if let Some(Some(parent_id)) = data.parent_id {
+            self.require_task(parent_id)?;
+        }
+        if let Some(Some(series_id)) = data.series_id {
+            self.series(series_id).ok_or_else(|| series_not_found(series_id))?;
+        }
+
+        if let Some(depends_on) = data.depends_on {
+            self.dependencies.retain(|(task_id, _)| *task_id != id);
+            if let Some(depends_on_id) = depends_on {
+                self.add_dependency(id, depends_on_id)?;
+            }
+        }
+
+        let estimate_minutes = match data.estimate {
+            Some(estimate) => Some(estimate.map(forecast::estimate_minutes).transpose()?),
+            None => None,
+        };
+
+        match data.remind_before {
+            Some(Some(remind_before)) => {
+                self.set_reminder(id, remind_before, now)?;
+            }
+            Some(None) => self.reminders.retain(|r| r.task_id != id),
+            None => {}
+        }
+
+        if let Some(tags_to_add) = &data.add_tags {
+            self.tags.entry(id).or_default().extend(tags_to_add.iter().cloned());
+        }
+        if let Some(tags_to_remove) = &data.remove_tags {
+            if let Some(tags) = self.tags.get_mut(&id) {
+                tags.retain(|t| !tags_to_remove.contains(t));
+            }
+        }
+
+        let Some(task) = self.task_mut(id) else {
+            return Ok(());
+        };
+        let mut updated = false;
+        if let Some(name) = &data.name {
+            task.name = name.clone();
+            updated = true;
+        }
+        if let Some(description) = &data.description {
+            task.description = description.clone();
+            updated = true;
+        }
+        if let Some(due_at) = data.due_at {
+            task.due_at = due_at;
+            updated = true;
+        }
+        if let Some(priority) = &data.priority {
+            task.priority = priority.clone();
+            updated = true;
+        }
+        if let Some(status) = &data.status {
+            task.status = status.clone();
+            task.workflow_status = status.as_str().to_string();
+            updated = true;
+        }
+        if let Some(parent_id) = data.parent_id {
+            task.parent_id = parent_id;
+            updated = true;
+        }
+        if let Some(series_id) = data.series_id {
+            task.series_id = series_id;
+            updated = true;
+        }
+        if let Some(estimate_minutes) = estimate_minutes {
+            task.estimate_minutes = estimate_minutes;
+            updated = true;
+        }
+        for (field, date) in [
+            (&mut task.scheduled_at, data.scheduled_at),
+            (&mut task.wait_until, data.wait_until),
+            (&mut task.start_at, data.start_at),
+        ] {
+            if let Some(date) = date {
+                *field = date;
+                updated = true;
+            }
+        }
+        if let Some(project_id) = project_id {
+            task.project_id = project_id;
+            updated = true;
+        }
+        if data.depends_on.is_some() {
+            updated = true;
+        }
+        if updated {
+            task.updated_at = now;
+        }
+        Ok(())
+    }
+
+    fn create_series(&mut self, data: NewSeriesData, now: DateTime<Utc>) -> Result<TaskSeries, CoreError> {
+        let normalized_rrule = RecurrenceManager::normalize_series_rrule(&data.rrule, data.dtstart, &data.timezone, data.time_mode)?;
+
+        let template_task = self
+            .task(data.template_task_id)
+            .ok_or_else(|| CoreError::NotFound(data.template_task_id.to_string()))?;
+
+        if template_task.series_id.is_some() {
+            return Err(CoreError::InvalidInput(
+                "Template task is already part of a series".to_string()
+            ));
+        }
+
+        if self.is_template(data.template_task_id) {
+            return Err(CoreError::InvalidInput(
+                "A series already exists for this template task".to_string()
+            ));
+        }
+
+        let series = TaskSeries {
+            id: Uuid::now_v7(),
+            template_task_id: data.template_task_id,
+            rrule: normalized_rrule,
+            dtstart: data.dtstart,
+            timezone: data.timezone,
+            time_mode: data.time_mode,
+            dst_policy: data.dst_policy,
+            active: true,
+            last_materialized_until: None,
+            created_at: now,
+            updated_at: now,
+        };
+        self.series.push(series.clone());
+        Ok(series)
+    }
+
+    /// Validated series update, as performed by `SeriesRepository::update_series`
+    fn update_series(&mut self, id: Uuid, mut data: UpdateSeriesData, now: DateTime<Utc>) -> Result<TaskSeries, CoreError> {
+        let current = self.series(id).cloned().ok_or_else(|| series_not_found(id))?;
+        // A new timezone alone keeps occurrences at their times of day
+        if let Some((from, to)) = timezone_move(&current, &mut data)? {
+            self.shift_wall_clock_times(&current, from, to, now);
+        }
+
+        if let Some(rrule) = &data.rrule {
+            let timezone = data.timezone.as_deref().unwrap_or(&current.timezone);
+            let dtstart = data.dtstart.unwrap_or(current.dtstart);
+            RecurrenceManager::validate_rrule_at(rrule, timezone, dtstart)?;
+        }
+        if let Some(timezone) = &data.timezone {
+            RecurrenceManager::validate_rrule(&current.rrule, timezone)?;
+        }
+        let dst_policy = data.dst_policy.filter(|policy| *policy != current.dst_policy);
+        if let Some(policy) = dst_policy {
+            self.remove_instances_moved_by(id, policy, now)?;
+        }
+
+        let series = self.series_mut(id).expect("series checked above");
+        let updated = data.rrule.is_some() || data.dtstart.is_some() || data.timezone.is_some() || data.active.is_some() || dst_policy.is_some();
+        if let Some(rrule) = data.rrule.clone() {
+            series.rrule = rrule;
+        }
+        if let Some(dtstart) = data.dtstart {
+            series.dtstart = dtstart;
+        }
+        if let Some(timezone) = data.timezone.clone() {
+            series.timezone = timezone;
+        }
+        if let Some(active) = data.active {
+            series.active = active;
+        }
+        if let Some(policy) = dst_policy {
+            series.dst_policy = policy;
+        }
+        if updated {
+            series.updated_at = now;
+            if data.rrule.is_some() || data.timezone.is_some() || dst_policy.is_some() {
+                series.last_materialized_until = None;
+            }
+        }
+        let series = series.clone();
+        self.rule_changes.extend(SeriesRuleChange::between(&current, &series, now));
+        Ok(series)
+    }
+
+    /// Delete the pending instances that the series would place elsewhere
+    /// under `policy`, from `now` on, before its DST policy changes
+    ///
+    /// Materialization recreates them at their new times; overridden and
+    /// moved occurrences keep their own tasks.
+    fn remove_instances_moved_by(&mut self, series_id: Uuid, policy: DstPolicy, now: DateTime<Utc>) -> Result<(), CoreError> {
+        let recurrence_manager = self.recurrence_manager(series_id)?;
+        let template_id = recurrence_manager.series().template_task_id;
+        let exception_tasks: HashSet<Uuid> = self
+            .series_exceptions(series_id)
+            .into_iter()
+            .filter_map(|e| e.exception_task_id)
+            .collect();
+        let is_instance = |t: &Task| {
+            t.series_id == Some(series_id)
+                && t.id != template_id
+                && t.status == TaskStatus::Pending
+                && !exception_tasks.contains(&t.id)
+                && t.due_at.is_some_and(|due| due >= now)
+        };
+        let Some(last_due) = self.tasks.iter().filter(|t| is_instance(t)).filter_map(|t| t.due_at).max() else {
+            return Ok(());
+        };
+
+        let moved: HashSet<DateTime<Utc>> = recurrence_manager
+            .occurrences_moved_by(policy, now, last_due)?
+            .into_iter()
+            .collect();
+        let doomed: Vec<Uuid> = self
+            .tasks
+            .iter()
+            .filter(|t| is_instance(t) && t.due_at.is_some_and(|due| moved.contains(&due)))
+            .map(|t| t.id)
+            .collect();
+        for id in doomed {
+            self.delete_task(id);
+        }
+        Ok(())
+    }
+
+    /// Unvalidated series update used by bulk operations
+    fn apply_series_update(&mut self, id: Uuid, mut data: UpdateSeriesData, now: DateTime<Utc>) -> Result<TaskSeries, CoreError> {
+        let current = self.series(id).cloned().ok_or_else(|| series_not_found(id))?;
+        // A new timezone alone keeps occurrences at their times of day
+        if let Some((from, to)) = timezone_move(&current, &mut data)? {
+            self.shift_wall_clock_times(&current, from, to, now);
+        }
+        let series = self.series_mut(id).expect("series checked above");
+        let reset_boundary = data.rrule.is_some() || data.timezone.is_some() || data.dtstart.is_some() || data.dst_policy.is_some();
+        let updated = reset_boundary || data.active.is_some();
+
+        if let Some(rrule) = data.rrule {
+            series.rrule = rrule;
+        }
+        if let Some(dtstart) = data.dtstart {
+            series.dtstart = dtstart;
+        }
+        if let Some(timezone) = data.timezone {
+            series.timezone = timezone;
+        }
+        if let Some(active) = data.active {
+            series.active = active;
+        }
+        if let Some(policy) = data.dst_policy {
+            series.dst_policy = policy;
+        }
+        if updated {
+            series.updated_at = now;
+        }
+        if reset_boundary {
+            series.last_materialized_until = None;
+        }
+        let series = series.clone();
+        self.rule_changes.extend(SeriesRuleChange::between(&current, &series, now));
+        Ok(series)
+    }
+
+    /// Re-attach detached notes to the instance materialized for their occurrence
+    fn reattach_annotations(&mut self, instance_id: Uuid, series_id: Uuid, occurrence_dt: DateTime<Utc>) {
+        let (reattached, detached): (Vec<_>, Vec<_>) = std::mem::take(&mut self.detached_annotations)
+            .into_iter()
+            .partition(|(sid, dt, _)| *sid == series_id && *dt == occurrence_dt);
+        self.detached_annotations = detached;
+        self.annotations.extend(reattached.into_iter().map(|(_, _, a)| Annotation { task_id: instance_id, ..a }));
+    }
+
+    /// Create the missing instances of a series inside a window
+    /// Move a floating series into `local_timezone`, keeping the wall-clock
+    /// times of its start, exceptions and pending tasks
+    fn follow_local_timezone(&mut self, series_id: Uuid, local_timezone: Tz, now: DateTime<Utc>) -> Result<(), CoreError> {
+        let series = self.series(series_id).cloned().ok_or_else(|| series_not_found(series_id))?;
+        if series.time_mode != TimeMode::Floating || series.timezone == local_timezone.name() {
+            return Ok(());
+        }
+        let from: Tz = series.timezone.parse()
+            .map_err(|_| CoreError::InvalidTimezone(series.timezone.clone()))?;
+        self.shift_wall_clock_times(&series, from, local_timezone, now);
+
+        let series = self.series_mut(series_id).ok_or_else(|| series_not_found(series_id))?;
+        series.dtstart = same_wall_clock(series.dtstart, from, local_timezone);
+        series.timezone = local_timezone.name().to_string();
+        series.updated_at = now;
+        Ok(())
+    }
+
+    /// Move the pending tasks and exceptions of `series` from the wall-clock
+    /// times they have in `from` to the same times in `to`
+    fn shift_wall_clock_times(&mut self, series: &TaskSeries, from: Tz, to: Tz, now: DateTime<Utc>) {
+        let shift = |dt: DateTime<Utc>| same_wall_clock(dt, from, to);
+
+        for task in self.tasks.iter_mut().filter(|t| {
+            (t.series_id == Some(series.id) || t.id == series.template_task_id) && t.status == TaskStatus::Pending
+        }) {
+            task.due_at = task.due_at.map(shift);
+            task.scheduled_at = task.scheduled_at.map(shift);
+            task.wait_until = task.wait_until.map(shift);
+            task.start_at = task.start_at.map(shift);
+            task.updated_at = now;
+        }
+        for exception in self.exceptions.iter_mut().filter(|e| e.series_id == series.id) {
+            exception.occurrence_dt = shift(exception.occurrence_dt);
+        }
+    }
+
+    fn refresh_series(
+        &mut self,
+        series_id: Uuid,
+        window_start: DateTime<Utc>,
+        window_end: DateTime<Utc>,
+        now: DateTime<Utc>,
+        max_batch_size: usize,
+    ) -> Result<(), CoreError> {
+        let recurrence_manager = self.recurrence_manager(series_id)?;
+        let template_task = recurrence_manager.template_task().clone();
+        let occurrences = recurrence_manager.generate_occurrences_between(window_start, window_end)?;
+
+        let existing_due_dates: HashSet<DateTime<Utc>> = self
+            .tasks
+            .iter()
+            .filter(|t| t.series_id == Some(series_id))
+            .filter_map(|t| t.due_at)
+            .filter(|due| *due >= window_start && *due <= window_end)
+            .collect();
+
+        let mut created_count = 0;
+        for occurrence in occurrences {
+            if !occurrence.needs_instance() || existing_due_dates.contains(&occurrence.effective_dt) {
+                continue;
+            }
+
+            let instance_id = Uuid::now_v7();
+            self.tasks.push(Task {
+                id: instance_id,
+                name: template_task.name.clone(),
+                description: template_task.description.clone(),
+                status: TaskStatus::Pending,
+                priority: template_task.priority.clone(),
+                due_at: Some(occurrence.effective_dt),
+                completed_at: None,
+                created_at: now,
+                updated_at: now,
+                project_id: template_task.project_id,
+                parent_id: template_task.parent_id,
+                series_id: Some(series_id),
+                workflow_status: TaskStatus::Pending.as_str().to_string(),
+                estimate_minutes: template_task.estimate_minutes,
+                scheduled_at: template_task.date_relative_to_due(template_task.scheduled_at, occurrence.effective_dt),
+                wait_until: template_task.date_relative_to_due(template_task.wait_until, occurrence.effective_dt),
+                start_at: template_task.date_relative_to_due(template_task.start_at, occurrence.effective_dt),
+                all_day: template_task.all_day,
+            });
+            self.reattach_annotations(instance_id, series_id, occurrence.effective_dt);
+            self.copy_template_subtasks(&template_task, instance_id, occurrence.effective_dt, now);
+
+            created_count += 1;
+            if created_count >= max_batch_size {
+                break;
+            }
+        }
+
+        if created_count > 0 {
+            let series = self.series_mut(series_id).expect("series checked above");
+            series.last_materialized_until = Some(window_end);
+            series.updated_at = now;
+        }
+        Ok(())
+    }
+
+    /// Copy the subtasks under `template_task`, however deep, to the instance
+    /// `instance_id` due at `due_at`, pending and with their dates at the
+    /// same distance from the due date
+    fn copy_template_subtasks(&mut self, template_task: &Task, instance_id: Uuid, due_at: DateTime<Utc>, now: DateTime<Utc>) {
+        let mut frontier = vec![(template_task.id, instance_id)];
+        while let Some((source_id, copy_parent_id)) = frontier.pop() {
+            let subtasks: Vec<Task> = self.tasks.iter().filter(|t| t.parent_id == Some(source_id)).cloned().collect();
+            for subtask in subtasks {
+                let copy_id = Uuid::now_v7();
+                self.tasks.push(Task {
+                    id: copy_id,
+                    status: TaskStatus::Pending,
+                    due_at: template_task.date_relative_to_due(subtask.due_at, due_at),
+                    completed_at: None,
+                    created_at: now,
+                    updated_at: now,
+                    parent_id: Some(copy_parent_id),
+                    series_id: None,
+                    workflow_status: TaskStatus::Pending.as_str().to_string(),
+                    scheduled_at: template_task.date_relative_to_due(subtask.scheduled_at, due_at),
+                    wait_until: template_task.date_relative_to_due(subtask.wait_until, due_at),
+                    start_at: template_task.date_relative_to_due(subtask.start_at, due_at),
+                    ..subtask.clone()
+                });
+                frontier.push((subtask.id, copy_id));
+            }
+        }
+    }
+
+    /// Move a series instance to `to_dt` as a standalone task, keeping its
+    /// scheduling dates at the same distance from the due date
+    fn move_task(&mut self, id: Uuid, to_dt: DateTime<Utc>, now: DateTime<Utc>) -> Result<Task, CoreError> {
+        let task = self
+            .task_mut(id)
+            .ok_or_else(|| CoreError::NotFound(format!("Task with id {} not found", id)))?;
+        task.scheduled_at = task.date_relative_to_due(task.scheduled_at, to_dt);
+        task.wait_until = task.date_relative_to_due(task.wait_until, to_dt);
+        task.start_at = task.date_relative_to_due(task.start_at, to_dt);
+        task.due_at = Some(to_dt);
+        task.series_id = None;
+        task.updated_at = now;
+        Ok(task.clone())
+    }
+
+    /// Overwrite the fields and tags of an existing task with `data`, leaving
+    /// its status, dependencies, notes and time entries alone
+    fn replace_task_fields(&mut self, id: Uuid, data: NewTaskData, now: DateTime<Utc>) -> Result<Task, CoreError> {
+        let project_id = match (data.project_id, &data.project_name) {
+            (None, Some(project_name)) => Some(self.resolve_project(project_name)?),
+            (project_id, _) => project_id,
+        };
+        let estimate_minutes = data.estimate.map(forecast::estimate_minutes).transpose()?;
+        self.tags.insert(id, data.tags.into_iter().collect());
+
+        let task = self
+            .task_mut(id)
+            .ok_or_else(|| CoreError::NotFound(format!("Task with id {} not found", id)))?;
+        task.name = data.name;
+        task.description = data.description;
+        task.priority = data.priority.unwrap_or(TaskPriority::None);
+        task.due_at = data.due_at;
+        task.project_id = project_id;
+        task.parent_id = data.parent_id;
+        task.series_id = data.series_id;
+        task.estimate_minutes = estimate_minutes;
+        task.scheduled_at = data.scheduled_at;
+        task.wait_until = data.wait_until;
+        task.start_at = data.start_at;
+        task.updated_at = now;
+        Ok(task.clone())
+    }
+
+    /// Remove the exception at an occurrence, returning whether there was one;
+    /// the task of an override or move goes back to being the series instance
+    fn remove_exception(&mut self, series_id: Uuid, occurrence_dt: DateTime<Utc>, now: DateTime<Utc>) -> Result<bool, CoreError> {
+        let Some(index) = self
+            .exceptions
+            .iter()
+            .position(|e| e.series_id == series_id && e.occurrence_dt == occurrence_dt)
+        else {
+            return Ok(false);
+        };
+        let exception = self.exceptions.remove(index);
+
+        if let Some(task_id) = exception.exception_task_id {
+            let series = self.series(series_id).cloned().ok_or_else(|| series_not_found(series_id))?;
+            let template = self.task(series.template_task_id).cloned().ok_or_else(|| {
+                CoreError::NotFound(format!("Template task with id {} not found", series.template_task_id))
+            })?;
+            self.replace_task_fields(task_id, template.instance_data(series_id, occurrence_dt), now)?;
+        }
+        Ok(true)
+    }
+
+    fn add_exception(&mut self, exception: NewSeriesException, now: DateTime<Utc>) -> Result<SeriesException, CoreError> {
+        if self.series(exception.series_id).is_none() {
+            return Err(series_not_found(exception.series_id));
+        }
+        if let Some(task_id) = exception.exception_task_id {
+            if self.task(task_id).is_none() {
+                return Err(CoreError::NotFound(format!("Exception task with id {} not found", task_id)));
+            }
+        }
+        if self
+            .exceptions
+            .iter()
+            .any(|e| e.series_id == exception.series_id && e.occurrence_dt == exception.occurrence_dt)
+        {
+            return Err(constraint_violation(format!(
+                "An exception already exists for series {} at {}",
+                exception.series_id, exception.occurrence_dt
+            )));
+        }
+
+        let created = SeriesException {
+            series_id: exception.series_id,
+            occurrence_dt: exception.occurrence_dt,
+            exception_type: exception.exception_type,
+            exception_task_id: exception.exception_task_id,
+            notes: exception.notes,
+            created_at: now,
+        };
+        self.exceptions.push(created.clone());
+        Ok(created)
+    }
+
+    fn query_tasks(&self, query: &Query, now: DateTime<Utc>, timezone: Tz) -> Vec<TaskQueryResult> {
+        // Walk the hierarchy the same way the recursive CTE does
+        let mut rows: Vec<(String, i32, &Task)> = Vec::new();
+        let mut frontier: Vec<(String, i32, &Task)> = self
+            .tasks
+            .iter()
+            .filter(|t| t.parent_id.is_none())
+            .map(|t| (timestamp_text(t.created_at), 0, t))
+            .collect();
+        while !frontier.is_empty() {
+            let mut next = Vec::new();
+            for (path, depth, task) in &frontier {
+                for child in self.tasks.iter().filter(|t| t.parent_id == Some(task.id)) {
+                    next.push((format!("{} -> {}", path, timestamp_text(child.created_at)), depth + 1, child));
+                }
+            }
+            rows.append(&mut frontier);
+            frontier = next;
+        }
+        rows.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let no_tags = BTreeSet::new();
+        let is_pending = |id: Uuid| self.task(id).is_some_and(|t| t.status == TaskStatus::Pending);
+        rows.into_iter()
+            .filter_map(|(_, depth, task)| {
+                let project_name = task
+                    .project_id
+                    .and_then(|pid| self.projects.iter().find(|p| p.id == pid))
+                    .map(|p| p.name.clone());
+                let tags = self.tags.get(&task.id).unwrap_or(&no_tags);
+                let notes: Vec<&str> = self
+                    .annotations
+                    .iter()
+                    .filter(|a| a.task_id == task.id)
+                    .map(|a| a.text.as_str())
+                    .collect();
+
+                if evaluate(query, task, project_name.as_deref(), tags, &notes, now, timezone) != Some(true) {
+                    return None;
+                }
+                let subtree = self.subtree(task.id);
+
+                Some(TaskQueryResult {
+                    id: task.id,
+                    name: task.name.clone(),
+                    description: task.description.clone(),
+                    status: task.status.clone(),
+                    priority: task.priority.clone(),
+                    due_at: task.due_at,
+                    completed_at: task.completed_at,
+                    created_at: task.created_at,
+                    updated_at: task.updated_at,
+                    project_id: task.project_id,
+                    parent_id: task.parent_id,
+                    series_id: task.series_id,
+                    workflow_status: task.workflow_status.clone(),
+                    estimate_minutes: task.estimate_minutes,
+                    scheduled_at: task.scheduled_at,
+                    wait_until: task.wait_until,
+                    start_at: task.start_at,
+                    all_day: task.all_day,
+                    project_name,
+                    tracked_seconds: self
+                        .time_entries
+                        .iter()
+                        .filter(|e| e.task_id == task.id)
+                        .filter_map(|e| e.duration_seconds)
+                        .sum(),
+                    is_blocked: self
+                        .dependencies
+                        .iter()
+                        .any(|&(task_id, depends_on_id)| task_id == task.id && is_pending(depends_on_id)),
+                    is_blocking: self
+                        .dependencies
+                        .iter()
+                        .any(|&(task_id, depends_on_id)| depends_on_id == task.id && is_pending(task_id)),
+                    subtask_count: subtree.iter().filter(|t| t.status != TaskStatus::Cancelled).count() as i64,
+                    completed_subtask_count: subtree.iter().filter(|t| t.status == TaskStatus::Completed).count() as i64,
+                    depth,
+                    tags: if tags.is_empty() {
+                        None
+                    } else {
+                        Some(tags.iter().cloned().collect::<Vec<_>>().join(","))
+                    },
+                })
+            })
+            .collect()
+    }
+}
+
+/// Text form of a timestamp as stored by the SQLite backend
+fn timestamp_text(dt: DateTime<Utc>) -> String {
+    dt.to_rfc3339_opts(SecondsFormat::AutoSi, false)
+}
+
+/// SQL `LIKE` with `%` and `_` wildcards, ASCII case-insensitive
+fn sql_like(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().map(|c| c.to_ascii_lowercase()).collect();
+    let text: Vec<char> = text.chars().map(|c| c.to_ascii_lowercase()).collect();
+
+    // matches[j]: pattern[..i] matches text[..j]
+    let mut matches = vec![false; text.len() + 1];
+    matches[0] = true;
+    for &p in &pattern {
+        let mut next = vec![false; text.len() + 1];
+        match p {
+            '%' => {
+                let mut seen = false;
+                for j in 0..=text.len() {
+                    seen |= matches[j];
+                    next[j] = seen;
+                }
+            }
+            _ => {
+                for j in 1..=text.len() {
+                    next[j] = matches[j - 1] && (p == '_' || p == text[j - 1]);
+                }
+            }
+        }
+        matches = next;
+    }
+    matches[text.len()]
+}
+
+/// Three-valued AND, with `None` standing in for SQL `NULL`
+fn and3(left: Option<bool>, right: Option<bool>) -> Option<bool> {
+    match (left, right) {
+        (Some(false), _) | (_, Some(false)) => Some(false),
+        (Some(true), Some(true)) => Some(true),
+        _ => None,
+    }
+}
+
+/// Three-valued OR, with `None` standing in for SQL `NULL`
+fn or3(left: Option<bool>, right: Option<bool>) -> Option<bool> {
+    match (left, right) {
+        (Some(true), _) | (_, Some(true)) => Some(true),
+        (Some(false), Some(false)) => Some(false),
+        _ => None,
+    }
+}
+
+/// Evaluate a query against one task with SQL `NULL` semantics, so that the
+/// result matches the `WHERE` clause built by [`SqlQueryBuilder`]
+fn evaluate(
+    query: &Query,
+    task: &Task,
+    project_name: Option<&str>,
+    tags: &BTreeSet<String>,
+    notes: &[&str],
+    now: DateTime<Utc>,
+    timezone: Tz,
+) -> Option<bool> {
+    match query {
+        Query::Filter(filter) => match filter {
+            Filter::Id(id) => Some(task.id == *id),
+            Filter::Project(name) => project_name.map(|p| p == name),
+            Filter::Status(status) => Some(task.status == *status),
+            Filter::WorkflowStatus(name) => Some(task.workflow_status == *name),
+            Filter::Priority(priority) => Some(task.priority == *priority),
+            Filter::Tags(tag_filter) => Some(evaluate_tags(tag_filter, tags)),
+            Filter::Name(text_filter) => Some(evaluate_text(text_filter, &task.name)),
+            Filter::Description(text_filter) => task
+                .description
+                .as_deref()
+                .map(|description| evaluate_text(text_filter, description)),
+            Filter::Note(TextFilter::NotContains(text)) => {
+                let contains = TextFilter::Contains(text.clone());
+                Some(!notes.iter().any(|note| evaluate_text(&contains, note)))
+            }
+            Filter::Note(text_filter) => Some(notes.iter().any(|note| evaluate_text(text_filter, note))),
+            Filter::Due(due_date) => evaluate_due(due_date, task, now, timezone),
+            Filter::Waiting => Some(task.is_waiting(now)),
+            Filter::Scheduled => Some(task.is_scheduled_later(now)),
+            Filter::All => Some(true),
+        },
+        Query::Not(inner) => evaluate(inner, task, project_name, tags, notes, now, timezone).map(|b| !b),
+        Query::Binary { op, left, right } => {
+            let left = evaluate(left, task, project_name, tags, notes, now, timezone);
+            let right = evaluate(right, task, project_name, tags, notes, now, timezone);
+            match op {
+                Operator::And => and3(left, right),
+                Operator::Or => or3(left, right),
+            }
+        }
+    }
+}
+
+fn evaluate_tags(tag_filter: &TagFilter, tags: &BTreeSet<String>) -> bool {
+    let matched = |wanted: &[String]| tags.iter().filter(|t| wanted.contains(t)).count();
+    match tag_filter {
+        TagFilter::Has(tag) => tags.contains(tag),
+        TagFilter::HasAll(wanted) => !wanted.is_empty() && matched(wanted) == wanted.len(),
+        TagFilter::HasAny(wanted) => matched(wanted) > 0,
+        TagFilter::Exact(wanted) => {
+            !wanted.is_empty() && matched(wanted) == wanted.len() && tags.iter().all(|t| wanted.contains(t))
+        }
+        TagFilter::NotHas(tag) => !tags.contains(tag),
+        TagFilter::NotHasAny(wanted) => matched(wanted) == 0,
+    }
+}
+
+fn evaluate_text(text_filter: &TextFilter, value: &str) -> bool {
+    match text_filter {
+        TextFilter::Contains(text) => sql_like(&format!("%{}%", text), value),
+        TextFilter::Equals(text) => value.eq_ignore_ascii_case(text),
+        TextFilter::StartsWith(text) => sql_like(&format!("{}%", text), value),
+        TextFilter::EndsWith(text) => sql_like(&format!("%{}", text), value),
+        TextFilter::NotContains(text) => !sql_like(&format!("%{}%", text), value),
+    }
+}
+
+fn evaluate_due(due_date: &DueDate, task: &Task, now: DateTime<Utc>, timezone: Tz) -> Option<bool> {
+    let today = local_date(now, timezone);
+    let same_day = |due: DateTime<Utc>, day: NaiveDate| {
+        if task.all_day {
+            due.date_naive() == day
+        } else {
+            local_date(due, timezone) == day
+        }
+    };
+    match due_date {
+        DueDate::On(date_time) => task.due_at.map(|due| same_day(due, local_date(*date_time, timezone))),
+        DueDate::Before(date_time) => task.due_at.map(|due| due < *date_time),
+        DueDate::After(date_time) => task.due_at.map(|due| due > *date_time),
+        DueDate::Today => task.due_at.map(|due| same_day(due, today)),
+        DueDate::Tomorrow => task.due_at.map(|due| same_day(due, today + Duration::days(1))),
+        DueDate::Yesterday => task.due_at.map(|due| same_day(due, today - Duration::days(1))),
+        DueDate::Overdue => and3(
+            task.due_at.map(|due| if task.all_day { due < utc_start_of_day(today) } else { due < now }),
+            Some(task.status == TaskStatus::Pending),
+        ),
+        DueDate::Within(duration) => task.due_at.map(|due| due >= now && due <= now + *duration),
+        DueDate::Ago(duration) => task.due_at.map(|due| due >= now - *duration && due <= now),
+    }
+}
+
+#[async_trait]
+impl super::TaskRepository for InMemoryRepository {
+    async fn add_task(&self, data: NewTaskData) -> Result<Task, CoreError> {
+        let now = self.now();
+        let data = data.anchored(self.local_timezone);
+        let window = self.materialization_manager.calculate_window_for_filters(&[]);
+        self.transaction(|state| state.add_task_or_series(data, now, window))
+    }
+
+    async fn find_task_by_id(&self, id: Uuid) -> Result<Option<Task>, CoreError> {
+        Ok(self.read(|state| state.task(id).cloned()))
+    }
+
+    async fn find_tasks_by_short_id_prefix(&self, short_id: &str) -> Result<Vec<Task>, CoreError> {
+        let prefix = short_id.replace('-', "").to_ascii_lowercase();
+        Ok(self.read(|state| {
+            state
+                .tasks
+                .iter()
+                .filter(|t| t.id.simple().to_string().starts_with(&prefix))
+                .cloned()
+                .collect()
+        }))
+    }
+
+    async fn find_tasks_with_details(&self, query: &Query) -> Result<Vec<TaskQueryResult>, CoreError> {
+        self.ensure_materialization_for_query(query)?;
+        let now = self.now();
+        Ok(self.read(|state| state.query_tasks(query, now, self.local_timezone)))
+    }
+
+    async fn delete_task(&self, id: Uuid) -> Result<(), CoreError> {
+        self.transaction(|state| {
+            if !state.delete_task_or_series(id) {
+                return Err(CoreError::NotFound(id.to_string()));
+            }
+            Ok(())
+        })
+    }
+
+    async fn delete_task_with_scope(&self, id: Uuid, scope: EditScope, keep_history: bool) -> Result<(), CoreError> {
+        let now = self.now();
+        self.transaction(|state| state.delete_in_scope(id, scope, keep_history, now))
+    }
+
+    async fn complete_task(&self, id: Uuid) -> Result<CompletionResult, CoreError> {
+        self.complete_task_as(id, TaskStatus::Completed.as_str())
+    }
+
+    async fn cancel_task(&self, id: Uuid) -> Result<Task, CoreError> {
+        self.cancel_task_as(id, TaskStatus::Cancelled.as_str())
+    }
+
+    async fn update_task(&self, id: Uuid, data: UpdateTaskData, scope: Option<EditScope>) -> Result<Task, CoreError> {
+        let now = self.now();
+        self.transaction(|state| {
+            let current_task = state.task(id).cloned().ok_or_else(|| CoreError::NotFound(id.to_string()))?;
+
+            match (current_task.series_id, scope.unwrap_or(EditScope::ThisOccurrence)) {
+                (None, _) => {
+                    if data.rrule.is_some() || data.timezone.is_some() {
+                        return Err(CoreError::InvalidInput(
+                            "Cannot add recurrence to existing task. Create a new recurring task instead".to_string()
+                        ));
+                    }
+                    state.update_task_fields(id, &data, now)?;
+                }
+                (Some(_), EditScope::ThisOccurrence) => {
+                    if data.rrule.is_some() || data.timezone.is_some() {
+                        return Err(CoreError::InvalidInput(
+                            "Cannot modify recurrence for single occurrence. Use EditScope::ThisAndFuture or EditScope::EntireSeries".to_string()
+                        ));
+                    }
+                    state.update_task_fields(id, &data, now)?;
+                }
+                (Some(series_id), scope) => {
+                    if data.rrule.is_some() || data.timezone.is_some() {
+                        let series_update = UpdateSeriesData {
+                            rrule: data.rrule.clone().flatten(),
+                            timezone: data.timezone.clone().flatten(),
+                            ..Default::default()
+                        };
+                        state.update_series(series_id, series_update, now)?;
+                    }
+
+                    let template_id = state
+                        .series(series_id)
+                        .map(|s| s.template_task_id)
+                        .ok_or_else(|| series_not_found(series_id))?;
+                    // Scheduling dates given for this occurrence keep their distance from due
+                    let mut template_update = match state.task(template_id).and_then(|t| t.due_at) {
+                        Some(due_at) => data.clone().dates_relative_to(&current_task, due_at),
+                        None => data.clone(),
+                    };
+                    template_update.rrule = None;
+                    template_update.timezone = None;
+                    state.update_task_fields(template_id, &template_update, now)?;
+
+                    if scope == EditScope::ThisAndFuture {
+                        if let Some(due_at) = current_task.due_at {
+                            state.delete_series_instances(series_id, |t| {
+                                t.id == template_id || t.due_at.is_none_or(|d| d < due_at)
+                            });
+                            state.series_mut(series_id).expect("series checked above").last_materialized_until =
+                                Some(due_at - Duration::days(1));
+                        }
+                    } else {
+                        state.delete_series_instances(series_id, |t| t.id == template_id);
+                        state.series_mut(series_id).expect("series checked above").last_materialized_until = None;
+                    }
+                }
+            }
+
+            // Series-wide edits replace the edited instance; report the template then
+            let template_id = current_task
+                .series_id
+                .and_then(|series_id| state.series(series_id))
+                .map(|series| series.template_task_id);
+            state
+                .task(id)
+                .or_else(|| template_id.and_then(|template_id| state.task(template_id)))
+                .cloned()
+                .ok_or(CoreError::Database(sqlx::Error::RowNotFound))
+        })
+    }
+
+    async fn bulk_update_tasks(&self, ids: &[Uuid], action: BulkAction) -> Result<Vec<Task>, CoreError> {
+        let action = super::checked_bulk_action(action)?;
+        let now = self.now();
+        let policy = self.completion_policy;
+        let max_batch_size = self.max_batch_size();
+        let (window_start, window_end) = self.materialization_manager.calculate_window_for_filters(&[]);
+        self.transaction(|state| {
+            let tasks = ids
+                .iter()
+                .map(|&id| state.task(id).cloned().ok_or_else(|| CoreError::NotFound(id.to_string())))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let mut completed_series: Vec<Uuid> = Vec::new();
+            for task in &tasks {
+                match &action {
+                    BulkAction::Complete => {
+                        workflow::check_status_change(task, TaskStatus::Completed.as_str(), &state.transitions)?;
+                        // Dependencies completed by the same batch do not block
+                        let blocking: Vec<&str> = state
+                            .dependencies
+                            .iter()
+                            .filter(|(task_id, depends_on_id)| *task_id == task.id && !ids.contains(depends_on_id))
+                            .filter_map(|(_, depends_on_id)| state.task(*depends_on_id))
+                            .filter(|t| t.status != TaskStatus::Completed)
+                            .map(|t| t.name.as_str())
+                            .collect();
+                        if !blocking.is_empty() {
+                            return Err(CoreError::TaskBlocked(blocking.join(", ")));
+                        }
+
+                        state.complete_subtasks(task.id, ids, policy.subtasks, now)?;
+                        state.mark_completed(task.id, now);
+                        if policy.complete_parent {
+                            state.complete_finished_parents(task, now);
+                        }
+
+                        if let Some(series_id) = task.series_id.filter(|id| !completed_series.contains(id)) {
+                            completed_series.push(series_id);
+                        }
+                    }
+                    BulkAction::Cancel => {
+                        workflow::check_status_change(task, TaskStatus::Cancelled.as_str(), &state.transitions)?;
+                        let cancelled = state.task_mut(task.id).expect("task checked above");
+                        cancelled.status = TaskStatus::Cancelled;
+                        cancelled.workflow_status = TaskStatus::Cancelled.as_str().to_string();
+                        cancelled.updated_at = now;
+                    }
+                    BulkAction::Delete => {
+                        // Subtasks of a task deleted earlier in the batch are already gone
+                        if state.task(task.id).is_some() {
+                            // Occurrences are skipped, as deleting a single one does
+                            state.delete_in_scope(task.id, EditScope::ThisOccurrence, true, now)?;
+                        }
+                    }
+                    BulkAction::Update(data) => {
+                        if data.rrule.is_some() || data.timezone.is_some() {
+                            let message = match task.series_id {
+                                None => "Cannot add recurrence to existing task. Create a new recurring task instead",
+                                Some(_) => "Cannot modify recurrence for single occurrence. Use EditScope::ThisAndFuture or EditScope::EntireSeries",
+                            };
+                            return Err(CoreError::InvalidInput(message.to_string()));
+                        }
+                        state.update_task_fields(task.id, data, now)?;
+                    }
+                    BulkAction::Move(status) => {
+                        self.move_task_in_state(state, task.id, status)?;
+                    }
+                    BulkAction::Annotate(text) => {
+                        state.annotations.push(Annotation { id: Uuid::now_v7(), task_id: task.id, text: text.clone(), created_at: now });
+                    }
+                    BulkAction::LogTime(duration) => {
+                        state.time_entries.push(TimeEntry {
+                            id: Uuid::now_v7(),
+                            task_id: task.id,
+                            started_at: now - *duration,
+                            ended_at: Some(now),
+                            duration_seconds: Some(duration.num_seconds()),
+                        });
+                    }
+                }
+            }
+
+            if let BulkAction::Delete = action {
+                return Ok(tasks);
+            }
+
+            // Completed occurrences make room for the next ones of active series
+            for series_id in completed_series {
+                if state.series(series_id).is_some_and(|s| s.active) {
+                    state.refresh_series(series_id, window_start, window_end, now, max_batch_size)?;
+                }
+            }
+
+            tasks
+                .iter()
+                .map(|task| state.task(task.id).cloned().ok_or_else(|| CoreError::NotFound(task.id.to_string())))
+                .collect()
+        })
+    }
+
+    async fn find_task_relations(&self, id: Uuid) -> Result<TaskRelations, CoreError> {
+        self.read(|state| {
+            let task = state.task(id).ok_or_else(|| CoreError::NotFound(id.to_string()))?;
+
+            let mut ancestors: Vec<Task> = Vec::new();
+            let mut parent_id = task.parent_id;
+            while let Some(pid) = parent_id {
+                if pid == id || ancestors.iter().any(|a| a.id == pid) {
+                    break;
+                }
+                let Some(parent) = state.task(pid) else {
+                    break;
+                };
+                parent_id = parent.parent_id;
+                ancestors.push(parent.clone());
+            }
+
+            let sorted = |tasks: Vec<&Task>| {
+                let mut tasks: Vec<Task> = tasks.into_iter().cloned().collect();
+                tasks.sort_by_key(|t| (t.created_at, t.id));
+                tasks
+            };
+            let subtasks = sorted(state.tasks.iter().filter(|t| t.parent_id == Some(id)).collect());
+            let depends_on = sorted(
+                state
+                    .dependencies
+                    .iter()
+                    .filter(|(task_id, _)| *task_id == id)
+                    .filter_map(|(_, depends_on_id)| state.task(*depends_on_id))
+                    .collect(),
+            );
+            let dependents = sorted(
+                state
+                    .dependencies
+                    .iter()
+                    .filter(|(_, depends_on_id)| *depends_on_id == id)
+                    .filter_map(|(task_id, _)| state.task(*task_id))
+                    .collect(),
+            );
+
+            Ok(TaskRelations { ancestors, subtasks, depends_on, dependents })
+        })
+    }
+}
+
+#[async_trait]
+impl super::ProjectRepository for InMemoryRepository {
+    async fn add_project(&self, name: String, description: Option<String>) -> Result<Project, CoreError> {
+        // SQLite's CURRENT_TIMESTAMP default has second precision
+        let created_at = self.now().trunc_subsecs(0);
+        self.transaction(|state| {
+            if state.project_by_name(&name).is_some() {
+                return Err(constraint_violation(format!("Project '{}' already exists", name)));
+            }
+            let project = Project {
+                id: Uuid::now_v7(),
+                name,
+                description,
+                created_at,
+            };
+            state.projects.push(project.clone());
+            Ok(project)
+        })
+    }
+
+    async fn find_project_by_id(&self, id: Uuid) -> Result<Option<Project>, CoreError> {
+        Ok(self.read(|state| state.projects.iter().find(|p| p.id == id).cloned()))
+    }
+
+    async fn find_project_by_name(&self, name: &str) -> Result<Option<Project>, CoreError> {
+        Ok(self.read(|state| state.project_by_name(name).cloned()))
+    }
+
+    async fn find_projects(&self) -> Result<Vec<Project>, CoreError> {
+        Ok(self.read(|state| state.projects.clone()))
+    }
+
+    async fn delete_project(&self, name: String) -> Result<(), CoreError> {
+        self.transaction(|state| {
+            let project_id = state
+                .project_by_name(&name)
+                .map(|p| p.id)
+                .ok_or_else(|| CoreError::NotFound("Project not found".to_string()))?;
+
+            let task_count = state.tasks.iter().filter(|t| t.project_id == Some(project_id)).count();
+            if task_count > 0 {
+                return Err(CoreError::InvalidInput(format!(
+                    "Cannot delete project '{}' because it has {} associated task(s). Delete or move the tasks first.",
+                    name, task_count
+                )));
+            }
+
+            state.projects.retain(|p| p.id != project_id);
+            Ok(())
+        })
+    }
+}
+
+#[async_trait]
+impl super::SeriesRepository for InMemoryRepository {
+    async fn create_series(&self, data: NewSeriesData) -> Result<TaskSeries, CoreError> {
+        let now = self.now();
+        self.transaction(|state| state.create_series(data, now))
+    }
+
+    async fn find_series_by_id(&self, id: Uuid) -> Result<Option<TaskSeries>, CoreError> {
+        Ok(self.read(|state| state.series(id).cloned()))
+    }
+
+    async fn find_series_by_template(&self, template_id: Uuid) -> Result<Option<TaskSeries>, CoreError> {
+        Ok(self.read(|state| state.series.iter().find(|s| s.template_task_id == template_id).cloned()))
+    }
+
+    async fn update_series(&self, id: Uuid, data: UpdateSeriesData) -> Result<TaskSeries, CoreError> {
+        let now = self.now();
+        self.transaction(|state| state.update_series(id, data, now))
+    }
+
+    async fn delete_series(&self, id: Uuid) -> Result<(), CoreError> {
+        self.transaction(|state| {
+            if state.series(id).is_none() {
+                return Err(series_not_found(id));
+            }
+            state.delete_series(id);
+            Ok(())
+        })
+    }
+
+    async fn find_active_series(&self) -> Result<Vec<TaskSeries>, CoreError> {
+        Ok(self.read(|state| state.active_series()))
+    }
+
+    async fn duplicate_series(&self, series_id: Uuid, new_name: String, new_timezone: Option<String>) -> Result<TaskSeries, CoreError> {
+        let now = self.now();
+        self.transaction(|state| {
+            let original_series = state.series(series_id).cloned().ok_or_else(|| series_not_found(series_id))?;
+            let original_template = state.task(original_series.template_task_id).cloned().ok_or_else(|| {
+                CoreError::NotFound(format!("Template task with id {} not found", original_series.template_task_id))
+            })?;
+
+            let new_template = state.add_task(
+                NewTaskData {
+                    name: new_name,
+                    description: original_template.description.clone(),
+                    due_at: original_template.due_at,
+                    priority: Some(original_template.priority.clone()),
+                    project_id: original_template.project_id,
+                    timezone: new_timezone.clone(),
+                    estimate: original_template.estimate(),
+                    scheduled_at: original_template.scheduled_at,
+                    wait_until: original_template.wait_until,
+                    start_at: original_template.start_at,
+                    ..Default::default()
+                },
+                now,
+            )?;
+
+            let new_series = state.create_series(
+                NewSeriesData {
+                    template_task_id: new_template.id,
+                    rrule: original_series.rrule.clone(),
+                    dtstart: original_series.dtstart,
+                    timezone: new_timezone.unwrap_or(original_series.timezone.clone()),
+                    time_mode: original_series.time_mode,
+                    dst_policy: original_series.dst_policy,
+                },
+                now,
+            )?;
+
+            if let Some(tags) = state.tags.get(&original_template.id).cloned() {
+                state.tags.insert(new_template.id, tags);
+            }
+
+            Ok(new_series)
+        })
+    }
+
+    async fn archive_completed_series(&self, series_id: Uuid) -> Result<(), CoreError> {
+        let now = self.now();
+        self.transaction(|state| {
+            let pending_count = state
+                .tasks
+                .iter()
+                .filter(|t| t.series_id == Some(series_id) && t.status == TaskStatus::Pending)
+                .count();
+            if pending_count > 0 {
+                return Err(CoreError::SeriesNotCompleted(format!(
+                    "Series has {} pending tasks that must be completed or cancelled before archiving",
+                    pending_count
+                )));
+            }
+
+            if let Some(series) = state.series_mut(series_id) {
+                series.active = false;
+                series.updated_at = now;
+            }
+            Ok(())
+        })
+    }
+
+    async fn bulk_update_series(&self, updates: Vec<(Uuid, UpdateSeriesData)>) -> Result<Vec<TaskSeries>, CoreError> {
+        let now = self.now();
+        self.transaction(|state| {
+            updates
+                .into_iter()
+                .map(|(series_id, data)| state.apply_series_update(series_id, data, now))
+                .collect()
+        })
+    }
+
+    async fn find_series_by_pattern(&self, pattern: &str) -> Result<Vec<TaskSeries>, CoreError> {
+        let like = format!("%{}%", pattern);
+        Ok(self.read(|state| {
+            state
+                .series
+                .iter()
+                .filter(|s| {
+                    state.task(s.template_task_id).is_some_and(|template| {
+                        sql_like(&like, &template.name) || sql_like(&like, &s.rrule)
+                    })
+                })
+                .cloned()
+                .collect()
+        }))
+    }
+
+    async fn get_series_statistics(&self, series_id: Uuid) -> Result<SeriesStatistics, CoreError> {
+        let now = self.now();
+        self.read(|state| {
+            let series = state.series(series_id).ok_or_else(|| series_not_found(series_id))?;
+
+            let instances: Vec<&Task> = state.tasks.iter().filter(|t| t.series_id == Some(series_id)).collect();
+            let count_status = |status: TaskStatus| instances.iter().filter(|t| t.status == status).count();
+            let total = instances.len();
+            let completed = count_status(TaskStatus::Completed);
+            let pending = count_status(TaskStatus::Pending);
+            let cancelled = count_status(TaskStatus::Cancelled);
+
+            let exceptions = state.series_exceptions(series_id);
+            let count_type = |kind: ExceptionType| exceptions.iter().filter(|e| e.exception_type == kind).count();
+
+            let due_dates = instances.iter().filter_map(|t| t.due_at);
+            let first_occurrence = due_dates.clone().min();
+            let last_occurrence = due_dates.max();
+
+            if state.task(series.template_task_id).is_none() {
+                return Err(CoreError::NotFound("Template task not found".to_string()));
+            }
+            let next_occurrence = if series.active {
+                state.recurrence_manager(series_id)?.next_occurrence_after(now)?
+            } else {
+                None
+            };
+
+            let completion_rate = if total > 0 {
+                completed as f64 / total as f64
+            } else {
+                1.0
+            };
+            let activity_factor = if series.active { 1.0 } else { 0.8 };
+            let consistency_factor = if (exceptions.len() as f64) / (total.max(1) as f64) < 0.2 { 1.0 } else { 0.9 };
+
+            // Average tracked time over completed occurrences that have time entries
+            let tracked: Vec<i64> = instances
+                .iter()
+                .filter(|t| t.status == TaskStatus::Completed)
+                .filter_map(|t| {
+                    let mut durations = state.time_entries.iter().filter(|e| e.task_id == t.id).filter_map(|e| e.duration_seconds).peekable();
+                    durations.peek().is_some().then(|| durations.sum())
+                })
+                .collect();
+            let average_completion_time_hours = (!tracked.is_empty())
+                .then(|| tracked.iter().sum::<i64>() as f64 / tracked.len() as f64 / 3600.0);
+
+            Ok(SeriesStatistics {
+                series_id,
+                total_occurrences_created: total as u32,
+                completed_occurrences: completed as u32,
+                pending_occurrences: pending as u32,
+                cancelled_occurrences: cancelled as u32,
+                total_exceptions: exceptions.len() as u32,
+                skip_exceptions: count_type(ExceptionType::Skip) as u32,
+                override_exceptions: count_type(ExceptionType::Override) as u32,
+                move_exceptions: count_type(ExceptionType::Move) as u32,
+                first_occurrence,
+                last_occurrence,
+                next_occurrence,
+                average_completion_time_hours,
+                series_health_score: completion_rate * activity_factor * consistency_factor,
+            })
+        })
+    }
+
+    async fn find_series_rule_changes(&self, series_id: Uuid) -> Result<Vec<SeriesRuleChange>, CoreError> {
+        Ok(self.read(|state| {
+            let mut changes: Vec<SeriesRuleChange> =
+                state.rule_changes.iter().filter(|c| c.series_id == series_id).cloned().collect();
+            changes.sort_by_key(|c| (c.changed_at, c.id));
+            changes
+        }))
+    }
+
+    async fn find_orphaned_instances(&self) -> Result<Vec<Task>, CoreError> {
+        Ok(self.read(|state| state.orphaned_instances()))
+    }
+
+    async fn detach_orphaned_instances(&self) -> Result<Vec<Task>, CoreError> {
+        let now = self.now();
+        self.transaction(|state| {
+            let mut detached = Vec::new();
+            for orphan in state.orphaned_instances() {
+                let task = state.task_mut(orphan.id).expect("orphan found above");
+                task.series_id = None;
+                task.updated_at = now;
+                detached.push(task.clone());
+            }
+            Ok(detached)
+        })
+    }
+}
+
+#[async_trait]
+impl super::MaterializationRepository for InMemoryRepository {
+    async fn refresh_series_materialization(&self, window_start: DateTime<Utc>, window_end: DateTime<Utc>) -> Result<(), CoreError> {
+        self.refresh_active_series(window_start, window_end)
+    }
+
+    async fn find_materialized_tasks_for_series(&self, series_id: Uuid, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<Task>, CoreError> {
+        Ok(self.read(|state| {
+            let mut tasks: Vec<Task> = state
+                .tasks
+                .iter()
+                .filter(|t| t.series_id == Some(series_id))
+                .filter(|t| t.due_at.is_some_and(|due| due >= start && due <= end))
+                .cloned()
+                .collect();
+            tasks.sort_by_key(|t| t.due_at);
+            tasks
+        }))
+    }
+
+    async fn update_series_materialization_boundary(&self, series_id: Uuid, boundary: DateTime<Utc>) -> Result<(), CoreError> {
+        let now = self.now();
+        self.transaction(|state| {
+            let series = state.series_mut(series_id).ok_or_else(|| series_not_found(series_id))?;
+            series.last_materialized_until = Some(boundary);
+            series.updated_at = now;
+            Ok(())
+        })
+    }
+}
+
+#[async_trait]
+impl super::ExceptionRepository for InMemoryRepository {
+    async fn add_series_exception(&self, exception: NewSeriesException) -> Result<SeriesException, CoreError> {
+        let now = self.now();
+        self.transaction(|state| {
+            if state.series(exception.series_id).is_none() {
+                return Err(series_not_found(exception.series_id));
+            }
+            match exception.exception_type {
+                ExceptionType::Skip if exception.exception_task_id.is_some() => {
+                    return Err(CoreError::InvalidInput(
+                        "Skip exceptions should not have an exception_task_id".to_string()
+                    ));
+                }
+                ExceptionType::Override | ExceptionType::Move if exception.exception_task_id.is_none() => {
+                    return Err(CoreError::InvalidInput(
+                        "Override and Move exceptions require an exception_task_id".to_string()
+                    ));
+                }
+                _ => {}
+            }
+            state.add_exception(exception, now)
+        })
+    }
+
+    async fn find_series_exceptions(&self, series_id: Uuid) -> Result<Vec<SeriesException>, CoreError> {
+        Ok(self.read(|state| {
+            let mut exceptions = state.series_exceptions(series_id);
+            exceptions.sort_by_key(|e| e.occurrence_dt);
+            exceptions
+        }))
+    }
+
+    async fn remove_series_exception(&self, series_id: Uuid, occurrence_dt: DateTime<Utc>) -> Result<(), CoreError> {
+        let now = self.now();
+        self.transaction(|state| {
+            if !state.remove_exception(series_id, occurrence_dt, now)? {
+                return Err(CoreError::NotFound(
+                    format!("Exception not found for series {} at occurrence {}", series_id, occurrence_dt)
+                ));
+            }
+            Ok(())
+        })
+    }
+
+    async fn add_bulk_series_exceptions(&self, exceptions: Vec<NewSeriesException>) -> Result<Vec<SeriesException>, CoreError> {
+        let now = self.now();
+        self.transaction(|state| {
+            let mut created_exceptions = Vec::new();
+            for exception in exceptions {
+                if state.series(exception.series_id).is_none() {
+                    return Err(series_not_found(exception.series_id));
+                }
+                match exception.exception_type {
+                    ExceptionType::Skip if exception.exception_task_id.is_some() => {
+                        return Err(CoreError::InvalidException(
+                            "Skip exceptions cannot have an exception_task_id".to_string()
+                        ));
+                    }
+                    ExceptionType::Override | ExceptionType::Move if exception.exception_task_id.is_none() => {
+                        return Err(CoreError::InvalidException(
+                            format!("{:?} exceptions must have an exception_task_id", exception.exception_type)
+                        ));
+                    }
+                    _ => {}
+                }
+                created_exceptions.push(state.add_exception(exception, now)?);
+            }
+            Ok(created_exceptions)
+        })
+    }
+
+    async fn remove_bulk_series_exceptions(&self, series_id: Uuid, occurrence_dts: Vec<DateTime<Utc>>) -> Result<usize, CoreError> {
+        let now = self.now();
+        self.transaction(|state| {
+            let mut total_removed = 0;
+            for occurrence_dt in occurrence_dts {
+                if state.remove_exception(series_id, occurrence_dt, now)? {
+                    total_removed += 1;
+                }
+            }
+            Ok(total_removed)
+        })
+    }
+
+    async fn validate_exception_conflicts(&self, series_id: Uuid, new_exception: &NewSeriesException) -> Result<Vec<SeriesException>, CoreError> {
+        Ok(self.read(|state| {
+            state
+                .exceptions
+                .iter()
+                .filter(|e| e.series_id == series_id && e.occurrence_dt == new_exception.occurrence_dt)
+                .cloned()
+                .collect()
+        }))
+    }
+
+    async fn override_occurrence_with_task(&self, series_id: Uuid, occurrence_dt: DateTime<Utc>, override_task_data: NewTaskData) -> Result<Task, CoreError> {
+        let now = self.now();
+        self.transaction(|state| {
+            let series = state.series(series_id).cloned().ok_or_else(|| series_not_found(series_id))?;
+            // An already materialized instance becomes the override task, keeping its history
+            let instance = state
+                .tasks
+                .iter()
+                .find(|t| t.series_id == Some(series_id) && t.due_at == Some(occurrence_dt) && t.id != series.template_task_id)
+                .map(|t| t.id);
+            let override_task = match instance {
+                Some(instance_id) => state.replace_task_fields(instance_id, override_task_data, now)?,
+                None => state.add_task(override_task_data, now)?,
+            };
+            state.add_exception(
+                NewSeriesException {
+                    series_id,
+                    occurrence_dt,
+                    exception_type: ExceptionType::Override,
+                    exception_task_id: Some(override_task.id),
+                    notes: Some(format!("Override task created: {}", override_task.name)),
+                },
+                now,
+            )?;
+            Ok(override_task)
+        })
+    }
+
+    async fn move_occurrence_with_validation(&self, series_id: Uuid, from_dt: DateTime<Utc>, to_dt: DateTime<Utc>, timezone: &str) -> Result<Task, CoreError> {
+        let now = self.now();
+        self.transaction(|state| {
+            let series = state.series(series_id).cloned().ok_or_else(|| series_not_found(series_id))?;
+            let template_task = state.task(series.template_task_id).cloned().ok_or_else(|| {
+                CoreError::NotFound(format!("Template task with id {} not found", series.template_task_id))
+            })?;
+
+            crate::timezone::validate_timezone(timezone)?;
+
+            // An already materialized instance is moved itself, keeping its history
+            let instance = state
+                .tasks
+                .iter()
+                .find(|t| t.series_id == Some(series_id) && t.due_at == Some(from_dt) && t.id != series.template_task_id)
+                .map(|t| t.id);
+            let moved_task = match instance {
+                Some(instance_id) => state.move_task(instance_id, to_dt, now)?,
+                None => state.add_task(
+                    NewTaskData {
+                        name: template_task.name.clone(),
+                        description: template_task.description.clone(),
+                        due_at: Some(to_dt),
+                        priority: Some(template_task.priority.clone()),
+                        project_id: template_task.project_id,
+                        parent_id: template_task.parent_id,
+                        timezone: Some(timezone.to_string()),
+                        estimate: template_task.estimate(),
+                        scheduled_at: template_task.date_relative_to_due(template_task.scheduled_at, to_dt),
+                        wait_until: template_task.date_relative_to_due(template_task.wait_until, to_dt),
+                        start_at: template_task.date_relative_to_due(template_task.start_at, to_dt),
+                        ..Default::default()
+                    },
+                    now,
+                )?,
+            };
+
+            state.add_exception(
+                NewSeriesException {
+                    series_id,
+                    occurrence_dt: from_dt,
+                    exception_type: ExceptionType::Move,
+                    exception_task_id: Some(moved_task.id),
+                    notes: Some(format!(
+                        "Moved from {} to {} ({})",
+                        from_dt.format("%Y-%m-%d %H:%M"),
+                        to_dt.format("%Y-%m-%d %H:%M"),
+                        timezone
+                    )),
+                },
+                now,
+            )?;
+
+            Ok(moved_task)
+        })
+    }
+}
+
+#[async_trait]
+impl super::ReminderRepository for InMemoryRepository {
+    async fn set_task_reminder(&self, task_id: Uuid, remind_before: Duration) -> Result<TaskReminder, CoreError> {
+        let now = self.now();
+        self.transaction(|state| {
+            state.require_task(task_id)?;
+            state.set_reminder(task_id, remind_before, now)
+        })
+    }
+
+    async fn clear_task_reminder(&self, task_id: Uuid) -> Result<(), CoreError> {
+        self.transaction(|state| {
+            state.reminders.retain(|r| r.task_id != task_id);
+            Ok(())
+        })
+    }
+
+    async fn find_task_reminder(&self, task_id: Uuid) -> Result<Option<TaskReminder>, CoreError> {
+        Ok(self.read(|state| state.reminders.iter().find(|r| r.task_id == task_id).cloned()))
+    }
+
+    async fn find_pending_reminders(&self, due_after: DateTime<Utc>) -> Result<Vec<DueReminder>, CoreError> {
+        Ok(self.read(|state| {
+            let offset_of = |task_id: Uuid| {
+                state
+                    .reminders
+                    .iter()
+                    .find(|r| r.task_id == task_id)
+                    .map(|r| r.offset_minutes)
+            };
+
+            let mut reminders: Vec<DueReminder> = state
+                .tasks
+                .iter()
+                .filter(|t| t.status == TaskStatus::Pending && !state.is_template(t.id))
+                .filter_map(|t| {
+                    let due_at = t.due_at.filter(|due| *due >= due_after)?;
+                    if state.deliveries.contains(&(t.id, due_at)) {
+                        return None;
+                    }
+                    // Instances fall back to their template's reminder
+                    let offset_minutes = offset_of(t.id).or_else(|| {
+                        t.series_id
+                            .and_then(|sid| state.series(sid))
+                            .and_then(|s| offset_of(s.template_task_id))
+                    })?;
+                    Some(DueReminder {
+                        task: t.clone(),
+                        offset_minutes,
+                    })
+                })
+                .collect();
+            reminders.sort_by_key(|r| r.task.due_at);
+            reminders
+        }))
+    }
+
+    async fn mark_reminder_delivered(&self, task_id: Uuid, due_at: DateTime<Utc>, _delivered_at: DateTime<Utc>) -> Result<(), CoreError> {
+        self.transaction(|state| {
+            state.require_task(task_id)?;
+            state.deliveries.insert((task_id, due_at));
+            Ok(())
+        })
+    }
+}
+
+#[async_trait]
+impl super::WorkflowRepository for InMemoryRepository {
+    async fn add_workflow_status(&self, name: &str, category: TaskStatus) -> Result<WorkflowStatus, CoreError> {
+        let name = workflow::normalize_status_name(name)?;
+        let now = self.now();
+        self.transaction(|state| {
+            if state.workflow_status(&name).is_some() {
+                return Err(workflow::duplicate_status(&name));
+            }
+
+            let position = state
+                .workflow_statuses
+                .iter()
+                .filter(|s| s.category == category)
+                .map(|s| s.position)
+                .max()
+                .unwrap_or(0)
+                + 1;
+            let status = WorkflowStatus {
+                name,
+                category,
+                position,
+                created_at: now,
+            };
+            state.workflow_statuses.push(status.clone());
+            Ok(status)
+        })
+    }
+
+    async fn find_workflow_status(&self, name: &str) -> Result<Option<WorkflowStatus>, CoreError> {
+        let name = name.trim().to_lowercase();
+        Ok(self.read(|state| state.workflow_status(&name).cloned()))
+    }
+
+    async fn find_workflow_statuses(&self) -> Result<Vec<WorkflowStatus>, CoreError> {
+        let mut statuses = self.read(|state| state.workflow_statuses.clone());
+        workflow::sort_statuses(&mut statuses);
+        Ok(statuses)
+    }
+
+    async fn delete_workflow_status(&self, name: &str) -> Result<(), CoreError> {
+        let name = workflow::normalize_status_name(name)?;
+        self.transaction(|state| {
+            let in_use = state.tasks.iter().filter(|t| t.workflow_status == name).count() as i64;
+            workflow::check_deletable(&name, in_use)?;
+
+            if state.workflow_status(&name).is_none() {
+                return Err(workflow::unknown_status(&name));
+            }
+            state.workflow_statuses.retain(|s| s.name != name);
+            state.transitions.retain(|t| t.from_status != name && t.to_status != name);
+            Ok(())
+        })
+    }
+
+    async fn add_workflow_transition(&self, from: &str, to: &str) -> Result<WorkflowTransition, CoreError> {
+        let transition = WorkflowTransition {
+            from_status: workflow::normalize_status_name(from)?,
+            to_status: workflow::normalize_status_name(to)?,
+        };
+        if transition.from_status == transition.to_status {
+            return Err(CoreError::InvalidInput("A status cannot transition to itself".to_string()));
+        }
+
+        self.transaction(|state| {
+            for name in [&transition.from_status, &transition.to_status] {
+                if state.workflow_status(name).is_none() {
+                    return Err(workflow::unknown_status(name));
+                }
+            }
+            if !state.transitions.contains(&transition) {
+                state.transitions.push(transition.clone());
+            }
+            Ok(transition)
+        })
+    }
+
+    async fn remove_workflow_transition(&self, from: &str, to: &str) -> Result<(), CoreError> {
+        let from = workflow::normalize_status_name(from)?;
+        let to = workflow::normalize_status_name(to)?;
+        self.transaction(|state| {
+            let before = state.transitions.len();
+            state.transitions.retain(|t| !(t.from_status == from && t.to_status == to));
+            if state.transitions.len() == before {
+                return Err(workflow::transition_not_found(&from, &to));
+            }
+            Ok(())
+        })
+    }
+
+    async fn find_workflow_transitions(&self) -> Result<Vec<WorkflowTransition>, CoreError> {
+        let mut transitions = self.read(|state| state.transitions.clone());
+        transitions.sort_by(|a, b| (&a.from_status, &a.to_status).cmp(&(&b.from_status, &b.to_status)));
+        Ok(transitions)
+    }
+
+    async fn move_task(&self, id: Uuid, status: &str) -> Result<Task, CoreError> {
+        let name = workflow::normalize_status_name(status)?;
+        self.transaction(|state| self.move_task_in_state(state, id, &name))
+    }
+}
+
+#[async_trait]
+impl super::TimeTrackingRepository for InMemoryRepository {
+    async fn start_timer(&self, task_id: Uuid) -> Result<TimeEntry, CoreError> {
+        let now = self.now();
+        self.transaction(|state| {
+            state.require_task(task_id)?;
+            if let Some(active) = state.time_entries.iter().find(|e| e.is_running()) {
+                let running = state.task(active.task_id).map_or_else(|| active.task_id.to_string(), |t| t.name.clone());
+                return Err(CoreError::TimerRunning(running));
+            }
+
+            let entry = TimeEntry {
+                id: Uuid::now_v7(),
+                task_id,
+                started_at: now,
+                ended_at: None,
+                duration_seconds: None,
+            };
+            state.time_entries.push(entry.clone());
+            Ok(entry)
+        })
+    }
+
+    async fn stop_timer(&self) -> Result<Option<TimeEntry>, CoreError> {
+        let now = self.now();
+        self.transaction(|state| {
+            let Some(entry) = state.time_entries.iter_mut().find(|e| e.is_running()) else {
+                return Ok(None);
+            };
+            entry.ended_at = Some(now);
+            entry.duration_seconds = Some((now - entry.started_at).num_seconds().max(0));
+            Ok(Some(entry.clone()))
+        })
+    }
+
+    async fn find_active_timer(&self) -> Result<Option<TimeEntry>, CoreError> {
+        Ok(self.read(|state| state.time_entries.iter().find(|e| e.is_running()).cloned()))
+    }
+
+    async fn log_time(&self, task_id: Uuid, duration: Duration) -> Result<TimeEntry, CoreError> {
+        if duration <= Duration::zero() {
+            return Err(CoreError::InvalidInput("Tracked time must be positive".to_string()));
+        }
+
+        let now = self.now();
+        self.transaction(|state| {
+            state.require_task(task_id)?;
+            let entry = TimeEntry {
+                id: Uuid::now_v7(),
+                task_id,
+                started_at: now - duration,
+                ended_at: Some(now),
+                duration_seconds: Some(duration.num_seconds()),
+            };
+            state.time_entries.push(entry.clone());
+            Ok(entry)
+        })
+    }
+
+    async fn find_time_entries(&self, task_id: Uuid) -> Result<Vec<TimeEntry>, CoreError> {
+        Ok(self.read(|state| {
+            let mut entries: Vec<TimeEntry> = state.time_entries.iter().filter(|e| e.task_id == task_id).cloned().collect();
+            entries.sort_by_key(|e| (e.started_at, e.id));
+            entries
+        }))
+    }
+
+    async fn find_timesheet_entries(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<TimesheetEntry>, CoreError> {
+        Ok(self.read(|state| {
+            let mut entries: Vec<(Uuid, TimesheetEntry)> = state
+                .time_entries
+                .iter()
+                .filter(|e| e.started_at >= start && e.started_at < end)
+                .filter_map(|e| {
+                    let task = state.task(e.task_id)?;
+                    let tags = state.tags.get(&task.id).filter(|tags| !tags.is_empty());
+                    Some((e.id, TimesheetEntry {
+                        task_id: task.id,
+                        task_name: task.name.clone(),
+                        project_name: task
+                            .project_id
+                            .and_then(|pid| state.projects.iter().find(|p| p.id == pid))
+                            .map(|p| p.name.clone()),
+                        tags: tags.map(|tags| tags.iter().cloned().collect::<Vec<_>>().join(",")),
+                        started_at: e.started_at,
+                        duration_seconds: e.duration_seconds?,
+                    }))
+                })
+                .collect();
+            entries.sort_by_key(|(id, e)| (e.started_at, *id));
+            entries.into_iter().map(|(_, e)| e).collect()
+        }))
+    }
+}
+
+#[async_trait]
+impl super::AnnotationRepository for InMemoryRepository {
+    async fn add_annotation(&self, task_id: Uuid, text: &str) -> Result<Annotation, CoreError> {
+        let text = super::annotation_text(text)?;
+        let now = self.now();
+        self.transaction(|state| {
+            if state.task(task_id).is_none() {
+                return Err(CoreError::NotFound(task_id.to_string()));
+            }
+            let annotation = Annotation {
+                id: Uuid::now_v7(),
+                task_id,
+                text,
+                created_at: now,
+            };
+            state.annotations.push(annotation.clone());
+            Ok(annotation)
+        })
+    }
+
+    async fn find_annotations(&self, task_id: Uuid) -> Result<Vec<Annotation>, CoreError> {
+        Ok(self.read(|state| {
+            let mut annotations: Vec<Annotation> =
+                state.annotations.iter().filter(|a| a.task_id == task_id).cloned().collect();
+            annotations.sort_by_key(|a| (a.created_at, a.id));
+            annotations
+        }))
+    }
+}
+
+#[async_trait]
+impl super::BlueprintRepository for InMemoryRepository {
+    async fn save_blueprint(&self, data: NewBlueprint) -> Result<Blueprint, CoreError> {
+        let data = super::checked_blueprint(data)?;
+        let now = self.now();
+        self.transaction(|state| {
+            if state.blueprints.iter().any(|b| b.name == data.name) {
+                return Err(CoreError::InvalidInput(format!("Blueprint '{}' already exists", data.name)));
+            }
+            let blueprint = Blueprint {
+                id: Uuid::now_v7(),
+                name: data.name,
+                description: data.description,
+                created_at: now,
+                tasks: data.tasks,
+            };
+            state.blueprints.push(blueprint.clone());
+            Ok(blueprint)
+        })
+    }
+
+    async fn find_blueprint(&self, name: &str) -> Result<Option<Blueprint>, CoreError> {
+        Ok(self.read(|state| state.blueprints.iter().find(|b| b.name == name).cloned()))
+    }
+
+    async fn find_blueprints(&self) -> Result<Vec<Blueprint>, CoreError> {
+        Ok(self.read(|state| {
+            let mut blueprints = state.blueprints.clone();
+            blueprints.sort_by(|a, b| a.name.cmp(&b.name));
+            blueprints
+        }))
+    }
+
+    async fn delete_blueprint(&self, name: &str) -> Result<(), CoreError> {
+        self.transaction(|state| {
+            let index = state
+                .blueprints
+                .iter()
+                .position(|b| b.name == name)
+                .ok_or_else(|| CoreError::NotFound(format!("Blueprint '{}' not found", name)))?;
+            state.blueprints.remove(index);
+            Ok(())
+        })
+    }
+
+    async fn apply_blueprint(&self, name: &str, anchor: DateTime<Utc>, project_name: Option<String>) -> Result<Vec<Task>, CoreError> {
+        let now = self.now();
+        let window = self.materialization_manager.calculate_window_for_filters(&[]);
+        self.transaction(|state| {
+            let blueprint = state
+                .blueprints
+                .iter()
+                .find(|b| b.name == name)
+                .cloned()
+                .ok_or_else(|| CoreError::NotFound(format!("Blueprint '{}' not found", name)))?;
+
+            let mut created: Vec<Task> = Vec::with_capacity(blueprint.tasks.len());
+            for task in &blueprint.tasks {
+                let mut data = task.task_data(anchor);
+                data.project_name = project_name.clone();
+                data.parent_id = task.parent_position.map(|parent| created[parent as usize].id);
+                let data = data.anchored(self.local_timezone);
+                created.push(state.add_task_or_series(data, now, window)?);
+            }
+            for task in &blueprint.tasks {
+                for &depends_on in &task.depends_on {
+                    state.add_dependency(created[task.position as usize].id, created[depends_on as usize].id)?;
+                }
+            }
+            Ok(created)
+        })
+    }
+}
+
+impl super::Repository for InMemoryRepository {
+    fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    fn local_timezone(&self) -> Tz {
+        self.local_timezone
+    }
+}
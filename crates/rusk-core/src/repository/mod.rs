@@ -1,15 +1,18 @@
-use crate::db::DbPool;
+use crate::clock::Clock;
 use crate::error::CoreError;
 use crate::models::{
-    CompletionResult, NewTaskData, Project, Task, TaskPriority, TaskStatus,
+    BulkAction, CompletionPolicy, CompletionResult, NewTaskData, Project, Task, TaskPriority, TaskStatus, TaskRelations,
     UpdateTaskData, TaskSeries, SeriesException, NewSeriesData, UpdateSeriesData, 
-    NewSeriesException, EditScope, SeriesStatistics,
+    NewSeriesException, EditScope, SeriesRuleChange, SeriesStatistics, TaskReminder, DueReminder,
+    WorkflowStatus, WorkflowTransition, TimeEntry, TimesheetEntry, Annotation, Blueprint, NewBlueprint,
 };
 use crate::query::Query;
 use crate::recurrence::MaterializationManager;
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
-use sqlx::FromRow;
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+use sqlx::{FromRow, SqlitePool};
+use std::sync::Arc;
 use uuid::Uuid;
 
 // Re-export domain modules
@@ -18,8 +21,18 @@ pub mod projects;
 pub mod series;
 pub mod materialization;
 pub mod exceptions;
+pub mod reminders;
+pub mod time_tracking;
+pub mod annotations;
+pub mod blueprints;
+pub mod workflow;
+pub mod memory;
+pub mod postgres;
 pub mod query_builder;
 
+pub use memory::InMemoryRepository;
+pub use postgres::PostgresRepository;
+
 // Traits are defined in this module and implemented in respective domain modules
 
 // Core types needed across domains
@@ -37,7 +50,25 @@ pub struct TaskQueryResult {
     pub project_id: Option<Uuid>,
     pub parent_id: Option<Uuid>,
     pub series_id: Option<Uuid>,
+    pub workflow_status: String,
+    /// Estimated effort in minutes
+    pub estimate_minutes: Option<i64>,
+    pub scheduled_at: Option<DateTime<Utc>>,
+    pub wait_until: Option<DateTime<Utc>>,
+    pub start_at: Option<DateTime<Utc>>,
+    /// Due on a date rather than at a time
+    pub all_day: bool,
     pub project_name: Option<String>,
+    /// Total seconds of finished time entries on this task
+    pub tracked_seconds: i64,
+    /// Whether the task depends on a pending task
+    pub is_blocked: bool,
+    /// Whether a pending task depends on this task
+    pub is_blocking: bool,
+    /// Subtasks anywhere below this task, not counting cancelled ones
+    pub subtask_count: i64,
+    /// Completed subtasks anywhere below this task
+    pub completed_subtask_count: i64,
     pub depth: i32,
     pub tags: Option<String>,
 }
@@ -49,10 +80,28 @@ pub trait TaskRepository {
     async fn find_task_by_id(&self, id: Uuid) -> Result<Option<Task>, CoreError>;
     async fn find_tasks_by_short_id_prefix(&self, short_id: &str) -> Result<Vec<Task>, CoreError>;
     async fn find_tasks_with_details(&self, query: &Query) -> Result<Vec<TaskQueryResult>, CoreError>;
+    /// Delete a task with its subtasks; deleting the template of a series
+    /// deletes the whole series like [`Self::delete_task_with_scope`] with
+    /// `keep_history` set
     async fn delete_task(&self, id: Uuid) -> Result<(), CoreError>;
+    /// Delete a series instance together with the occurrences `scope` covers
+    ///
+    /// `ThisOccurrence` deletes the instance and skips its occurrence,
+    /// `ThisAndFuture` deletes it and every later one and ends the series'
+    /// rule before it, and `EntireSeries` deletes the series with its template
+    /// and instances. With `keep_history` the completed instances of a deleted
+    /// series stay as regular tasks. A template always takes its whole series,
+    /// and tasks outside any series are simply deleted.
+    async fn delete_task_with_scope(&self, id: Uuid, scope: EditScope, keep_history: bool) -> Result<(), CoreError>;
     async fn complete_task(&self, id: Uuid) -> Result<CompletionResult, CoreError>;
     async fn cancel_task(&self, id: Uuid) -> Result<Task, CoreError>;
     async fn update_task(&self, id: Uuid, data: UpdateTaskData, scope: Option<EditScope>) -> Result<Task, CoreError>;
+    /// Apply one action to every task in a single transaction: either all tasks
+    /// change or none do. Returns the tasks after the change (as they were, for deletes).
+    /// Deleted occurrences are skipped in their series, as with `EditScope::ThisOccurrence`
+    async fn bulk_update_tasks(&self, ids: &[Uuid], action: BulkAction) -> Result<Vec<Task>, CoreError>;
+    /// Parent chain, subtasks and dependencies in both directions of a task
+    async fn find_task_relations(&self, id: Uuid) -> Result<TaskRelations, CoreError>;
 }
 
 /// Domain-specific trait for project operations
@@ -79,6 +128,15 @@ pub trait SeriesRepository {
     async fn bulk_update_series(&self, updates: Vec<(Uuid, UpdateSeriesData)>) -> Result<Vec<TaskSeries>, CoreError>;
     async fn find_series_by_pattern(&self, pattern: &str) -> Result<Vec<TaskSeries>, CoreError>;
     async fn get_series_statistics(&self, series_id: Uuid) -> Result<SeriesStatistics, CoreError>;
+    /// Edits of a series' RRULE, start or timezone, oldest first
+    async fn find_series_rule_changes(&self, series_id: Uuid) -> Result<Vec<SeriesRuleChange>, CoreError>;
+    /// Instances whose series no longer exists, oldest first
+    ///
+    /// These are left behind when rows are deleted without foreign keys
+    /// being enforced, e.g. from a database shell.
+    async fn find_orphaned_instances(&self) -> Result<Vec<Task>, CoreError>;
+    /// Turn orphaned instances into regular tasks, returning them
+    async fn detach_orphaned_instances(&self) -> Result<Vec<Task>, CoreError>;
 }
 
 /// Domain-specific trait for materialization operations
@@ -94,14 +152,98 @@ pub trait MaterializationRepository {
 pub trait ExceptionRepository {
     async fn add_series_exception(&self, exception: NewSeriesException) -> Result<SeriesException, CoreError>;
     async fn find_series_exceptions(&self, series_id: Uuid) -> Result<Vec<SeriesException>, CoreError>;
+    /// Remove the exception at an occurrence
+    ///
+    /// The task of an override or move is reverted to the series instance for
+    /// the occurrence: it takes the template's fields again but keeps its id,
+    /// status, notes and tracked time.
     async fn remove_series_exception(&self, series_id: Uuid, occurrence_dt: DateTime<Utc>) -> Result<(), CoreError>;
     async fn add_bulk_series_exceptions(&self, exceptions: Vec<NewSeriesException>) -> Result<Vec<SeriesException>, CoreError>;
     async fn remove_bulk_series_exceptions(&self, series_id: Uuid, occurrence_dts: Vec<DateTime<Utc>>) -> Result<usize, CoreError>;
     async fn validate_exception_conflicts(&self, series_id: Uuid, new_exception: &NewSeriesException) -> Result<Vec<SeriesException>, CoreError>;
+    /// Replace an occurrence with a task built from `override_task_data`
+    ///
+    /// An instance already materialized for the occurrence is rewritten in
+    /// place rather than duplicated, so its notes and tracked time stay.
     async fn override_occurrence_with_task(&self, series_id: Uuid, occurrence_dt: DateTime<Utc>, override_task_data: NewTaskData) -> Result<Task, CoreError>;
     async fn move_occurrence_with_validation(&self, series_id: Uuid, from_dt: DateTime<Utc>, to_dt: DateTime<Utc>, timezone: &str) -> Result<Task, CoreError>;
 }
 
+/// Domain-specific trait for reminder operations
+#[async_trait]
+pub trait ReminderRepository {
+    async fn set_task_reminder(&self, task_id: Uuid, remind_before: chrono::Duration) -> Result<TaskReminder, CoreError>;
+    async fn clear_task_reminder(&self, task_id: Uuid) -> Result<(), CoreError>;
+    async fn find_task_reminder(&self, task_id: Uuid) -> Result<Option<TaskReminder>, CoreError>;
+    /// Pending, undelivered reminders whose task is due at or after `due_after`
+    async fn find_pending_reminders(&self, due_after: DateTime<Utc>) -> Result<Vec<DueReminder>, CoreError>;
+    async fn mark_reminder_delivered(&self, task_id: Uuid, due_at: DateTime<Utc>, delivered_at: DateTime<Utc>) -> Result<(), CoreError>;
+}
+
+/// Domain-specific trait for custom workflow statuses
+#[async_trait]
+pub trait WorkflowRepository {
+    /// Define a new status in `category`, ordered after the category's existing statuses
+    async fn add_workflow_status(&self, name: &str, category: TaskStatus) -> Result<WorkflowStatus, CoreError>;
+    async fn find_workflow_status(&self, name: &str) -> Result<Option<WorkflowStatus>, CoreError>;
+    /// All statuses in display order (see [`crate::workflow::sort_statuses`])
+    async fn find_workflow_statuses(&self) -> Result<Vec<WorkflowStatus>, CoreError>;
+    /// Delete an unused custom status together with its transitions
+    async fn delete_workflow_status(&self, name: &str) -> Result<(), CoreError>;
+    async fn add_workflow_transition(&self, from: &str, to: &str) -> Result<WorkflowTransition, CoreError>;
+    async fn remove_workflow_transition(&self, from: &str, to: &str) -> Result<(), CoreError>;
+    async fn find_workflow_transitions(&self) -> Result<Vec<WorkflowTransition>, CoreError>;
+    /// Move a task into `status`, honouring the transition rules
+    ///
+    /// Entering a `Completed` status completes the task like
+    /// [`TaskRepository::complete_task`], entering a `Cancelled` status cancels
+    /// it and entering a `Pending` status reopens it.
+    async fn move_task(&self, id: Uuid, status: &str) -> Result<Task, CoreError>;
+}
+
+/// Domain-specific trait for time tracking
+#[async_trait]
+pub trait TimeTrackingRepository {
+    /// Start the timer on a task; fails with [`CoreError::TimerRunning`] while another timer runs
+    async fn start_timer(&self, task_id: Uuid) -> Result<TimeEntry, CoreError>;
+    /// Stop the running timer and return the finished entry, if a timer was running
+    async fn stop_timer(&self) -> Result<Option<TimeEntry>, CoreError>;
+    async fn find_active_timer(&self) -> Result<Option<TimeEntry>, CoreError>;
+    /// Record `duration` of work on a task, ending now
+    async fn log_time(&self, task_id: Uuid, duration: chrono::Duration) -> Result<TimeEntry, CoreError>;
+    /// All entries of a task, oldest first
+    async fn find_time_entries(&self, task_id: Uuid) -> Result<Vec<TimeEntry>, CoreError>;
+    /// Finished entries that started in `[start, end)`, oldest first
+    async fn find_timesheet_entries(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<TimesheetEntry>, CoreError>;
+}
+
+/// Domain-specific trait for task annotations
+#[async_trait]
+pub trait AnnotationRepository {
+    /// Add a note to a task, timestamped now
+    async fn add_annotation(&self, task_id: Uuid, text: &str) -> Result<Annotation, CoreError>;
+    /// Notes of a task, oldest first
+    async fn find_annotations(&self, task_id: Uuid) -> Result<Vec<Annotation>, CoreError>;
+}
+
+/// Domain-specific trait for task blueprints
+#[async_trait]
+pub trait BlueprintRepository {
+    /// Save a blueprint under a name no other blueprint has
+    async fn save_blueprint(&self, data: NewBlueprint) -> Result<Blueprint, CoreError>;
+    async fn find_blueprint(&self, name: &str) -> Result<Option<Blueprint>, CoreError>;
+    /// All blueprints by name
+    async fn find_blueprints(&self) -> Result<Vec<Blueprint>, CoreError>;
+    async fn delete_blueprint(&self, name: &str) -> Result<(), CoreError>;
+    /// Create the tasks of a blueprint, due relative to `anchor`, in a single
+    /// transaction
+    ///
+    /// Subtasks, tags, dependencies and the series of recurring tasks are
+    /// created along with the tasks, which all go into `project_name` when
+    /// given. Returns the created tasks by position, templates for recurring ones.
+    async fn apply_blueprint(&self, name: &str, anchor: DateTime<Utc>, project_name: Option<String>) -> Result<Vec<Task>, CoreError>;
+}
+
 /// Main repository trait that composes all domain traits
 #[async_trait]
 pub trait Repository: 
@@ -109,25 +251,142 @@ pub trait Repository:
     ProjectRepository + 
     SeriesRepository + 
     MaterializationRepository + 
-    ExceptionRepository 
+    ExceptionRepository +
+    ReminderRepository +
+    WorkflowRepository +
+    TimeTrackingRepository +
+    AnnotationRepository +
+    BlueprintRepository
 {
-    // This trait automatically composes all domain-specific repositories
+    // This trait composes all domain-specific repositories
     // Individual domain operations are defined in their respective traits
+
+    /// Clock the repository stamps and compares times with
+    fn clock(&self) -> &Arc<dyn Clock>;
+
+    /// The timezone the user is in, which floating series follow
+    fn local_timezone(&self) -> Tz;
+}
+
+/// Trimmed annotation text, rejecting notes with nothing in them
+pub(crate) fn annotation_text(text: &str) -> Result<String, CoreError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(CoreError::InvalidInput("Annotation text cannot be empty".to_string()));
+    }
+    Ok(text.to_string())
+}
+
+/// The action with its input checked as the single-task operations check it:
+/// a well-formed status name, note text with something in it, positive time
+pub(crate) fn checked_bulk_action(action: BulkAction) -> Result<BulkAction, CoreError> {
+    Ok(match action {
+        BulkAction::Move(status) => BulkAction::Move(crate::workflow::normalize_status_name(&status)?),
+        BulkAction::Annotate(text) => BulkAction::Annotate(annotation_text(&text)?),
+        BulkAction::LogTime(duration) if duration <= Duration::zero() => {
+            return Err(CoreError::InvalidInput("Tracked time must be positive".to_string()));
+        }
+        action => action,
+    })
+}
+
+/// A blueprint ready to be saved, with each task's tags and dependencies
+/// sorted and deduplicated
+///
+/// Fails unless the name is set and the tasks refer to each other
+/// consistently: positions in order, parents before their subtasks and no
+/// dependency cycles.
+pub(crate) fn checked_blueprint(mut data: NewBlueprint) -> Result<NewBlueprint, CoreError> {
+    for task in &mut data.tasks {
+        task.tags.sort();
+        task.tags.dedup();
+        task.depends_on.sort();
+        task.depends_on.dedup();
+    }
+    if data.name.trim().is_empty() {
+        return Err(CoreError::InvalidInput("Blueprint name cannot be empty".to_string()));
+    }
+    let count = data.tasks.len() as i64;
+    for (index, task) in data.tasks.iter().enumerate() {
+        let position = index as i64;
+        if task.position != position {
+            return Err(CoreError::InvalidInput(format!("Blueprint task '{}' is out of position", task.name)));
+        }
+        if task.name.trim().is_empty() {
+            return Err(CoreError::InvalidInput("Blueprint task name cannot be empty".to_string()));
+        }
+        if task.parent_position.is_some_and(|parent| parent < 0 || parent >= position) {
+            return Err(CoreError::InvalidInput(format!("Parent of blueprint task '{}' must come before it", task.name)));
+        }
+        if task.depends_on.iter().any(|&dep| dep < 0 || dep >= count || dep == position) {
+            return Err(CoreError::InvalidInput(format!("Blueprint task '{}' has an invalid dependency", task.name)));
+        }
+    }
+    // Depth-first search for a dependency path leading back to its start
+    for start in &data.tasks {
+        let mut stack = start.depends_on.clone();
+        let mut seen = std::collections::HashSet::new();
+        while let Some(position) = stack.pop() {
+            if position == start.position {
+                let other = &data.tasks[start.depends_on[0] as usize];
+                return Err(CoreError::CircularDependency(start.name.clone(), other.name.clone()));
+            }
+            if seen.insert(position) {
+                stack.extend(&data.tasks[position as usize].depends_on);
+            }
+        }
+    }
+    Ok(data)
 }
 
 /// SQLite implementation of the repository pattern
 pub struct SqliteRepository {
-    pool: DbPool,
+    pool: SqlitePool,
     materialization_manager: MaterializationManager,
+    clock: Arc<dyn Clock>,
+    completion_policy: CompletionPolicy,
+    local_timezone: Tz,
 }
 
 impl SqliteRepository {
-    pub fn new(pool: DbPool, materialization_manager: MaterializationManager) -> Self {
-        Self { pool, materialization_manager }
+    /// Create a repository that shares the materialization manager's clock
+    pub fn new(pool: SqlitePool, materialization_manager: MaterializationManager) -> Self {
+        let clock = materialization_manager.clock().clone();
+        Self { pool, materialization_manager, clock, completion_policy: CompletionPolicy::default(), local_timezone: Tz::UTC }
+    }
+
+    /// Create a repository driven by `clock`, which also replaces the
+    /// materialization manager's clock so both agree on the current time
+    pub fn with_clock(pool: SqlitePool, mut materialization_manager: MaterializationManager, clock: Arc<dyn Clock>) -> Self {
+        materialization_manager.set_clock(clock.clone());
+        Self { pool, materialization_manager, clock, completion_policy: CompletionPolicy::default(), local_timezone: Tz::UTC }
+    }
+
+    /// Use `policy` whenever a task is completed
+    pub fn with_completion_policy(mut self, policy: CompletionPolicy) -> Self {
+        self.completion_policy = policy;
+        self
+    }
+
+    /// Read floating series and relative day filters such as `due:today` in
+    /// `timezone`, the timezone the user is in (UTC unless set)
+    pub fn with_local_timezone(mut self, timezone: Tz) -> Self {
+        self.local_timezone = timezone;
+        self
+    }
+
+    /// Get the clock used for timestamps and time-relative queries
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    /// Current time according to the repository clock
+    pub(crate) fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
     }
     
     /// Get a reference to the database pool for internal use across modules
-    pub(crate) fn pool(&self) -> &DbPool {
+    pub(crate) fn pool(&self) -> &SqlitePool {
         &self.pool
     }
     
@@ -135,8 +394,25 @@ impl SqliteRepository {
     pub(crate) fn materialization_manager(&self) -> &MaterializationManager {
         &self.materialization_manager
     }
+
+    /// How completing a task affects its subtasks and parent
+    pub(crate) fn completion_policy(&self) -> CompletionPolicy {
+        self.completion_policy
+    }
+
+    /// Close the underlying pool, waiting for open connections to shut down
+    pub async fn close(&self) {
+        self.pool.close().await;
+    }
 }
 
-// The main Repository trait implementation will automatically be available
-// when all domain trait implementations are defined
-impl Repository for SqliteRepository {}
\ No newline at end of file
+// The main Repository trait composes the domain trait implementations above
+impl Repository for SqliteRepository {
+    fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    fn local_timezone(&self) -> Tz {
+        self.local_timezone
+    }
+}
\ No newline at end of file
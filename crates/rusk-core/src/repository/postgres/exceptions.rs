@@ -0,0 +1,400 @@
+use crate::error::CoreError;
+use crate::forecast;
+use crate::models::{
+    ExceptionType, NewSeriesException, NewTaskData, Project, SeriesException, Task, TaskPriority, TaskSeries,
+};
+use crate::repository::PostgresRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[async_trait]
+impl crate::repository::ExceptionRepository for PostgresRepository {
+    async fn add_series_exception(&self, exception: NewSeriesException) -> Result<SeriesException, CoreError> {
+        let mut tx = self.pool().begin().await?;
+
+        let series: Option<TaskSeries> = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
+            .bind(exception.series_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        if series.is_none() {
+            return Err(CoreError::NotFound(format!("Series with id {} not found", exception.series_id)));
+        }
+
+        match exception.exception_type {
+            ExceptionType::Skip => {
+                if exception.exception_task_id.is_some() {
+                    return Err(CoreError::InvalidInput(
+                        "Skip exceptions should not have an exception_task_id".to_string()
+                    ));
+                }
+            }
+            ExceptionType::Override | ExceptionType::Move => {
+                let Some(task_id) = exception.exception_task_id else {
+                    return Err(CoreError::InvalidInput(
+                        "Override and Move exceptions require an exception_task_id".to_string()
+                    ));
+                };
+
+                if Self::find_task_by_id_in_transaction(&mut tx, task_id).await?.is_none() {
+                    return Err(CoreError::NotFound(format!("Exception task with id {} not found", task_id)));
+                }
+            }
+        }
+
+        let created = Self::add_series_exception_in_transaction(&mut tx, exception, self.now()).await?;
+        tx.commit().await?;
+        Ok(created)
+    }
+
+    async fn find_series_exceptions(&self, series_id: Uuid) -> Result<Vec<SeriesException>, CoreError> {
+        let exceptions = sqlx::query_as(
+            "SELECT * FROM series_exceptions WHERE series_id = $1 ORDER BY occurrence_dt"
+        )
+        .bind(series_id)
+        .fetch_all(self.pool())
+        .await?;
+        Ok(exceptions)
+    }
+
+    async fn remove_series_exception(&self, series_id: Uuid, occurrence_dt: DateTime<Utc>) -> Result<(), CoreError> {
+        let mut tx = self.pool().begin().await?;
+
+        let removed = Self::remove_series_exception_in_transaction(&mut tx, series_id, occurrence_dt, self.now()).await?;
+        if !removed {
+            return Err(CoreError::NotFound(
+                format!("Exception not found for series {} at occurrence {}", series_id, occurrence_dt)
+            ));
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn add_bulk_series_exceptions(&self, exceptions: Vec<NewSeriesException>) -> Result<Vec<SeriesException>, CoreError> {
+        let mut tx = self.pool().begin().await?;
+        let mut created_exceptions = Vec::new();
+
+        for exception in exceptions {
+            Self::validate_exception_data(&mut tx, &exception).await?;
+
+            let created = Self::add_series_exception_in_transaction(&mut tx, exception, self.now()).await?;
+            created_exceptions.push(created);
+        }
+
+        tx.commit().await?;
+        Ok(created_exceptions)
+    }
+
+    async fn remove_bulk_series_exceptions(&self, series_id: Uuid, occurrence_dts: Vec<DateTime<Utc>>) -> Result<usize, CoreError> {
+        let mut tx = self.pool().begin().await?;
+        let mut total_removed = 0;
+
+        for occurrence_dt in occurrence_dts {
+            if Self::remove_series_exception_in_transaction(&mut tx, series_id, occurrence_dt, self.now()).await? {
+                total_removed += 1;
+            }
+        }
+
+        tx.commit().await?;
+        Ok(total_removed)
+    }
+
+    async fn validate_exception_conflicts(&self, series_id: Uuid, new_exception: &NewSeriesException) -> Result<Vec<SeriesException>, CoreError> {
+        let existing_exceptions = sqlx::query_as(
+            "SELECT * FROM series_exceptions WHERE series_id = $1 AND occurrence_dt = $2"
+        )
+        .bind(series_id)
+        .bind(new_exception.occurrence_dt)
+        .fetch_all(self.pool())
+        .await?;
+
+        Ok(existing_exceptions)
+    }
+
+    async fn override_occurrence_with_task(&self, series_id: Uuid, occurrence_dt: DateTime<Utc>, override_task_data: NewTaskData) -> Result<Task, CoreError> {
+        let mut tx = self.pool().begin().await?;
+
+        let series: TaskSeries = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
+            .bind(series_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Series with id {} not found", series_id)))?;
+
+        // An already materialized instance becomes the override task, keeping its history
+        let instance: Option<Task> = sqlx::query_as(
+            "SELECT * FROM tasks WHERE series_id = $1 AND due_at = $2 AND id != $3"
+        )
+        .bind(series_id)
+        .bind(occurrence_dt)
+        .bind(series.template_task_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let override_task = match instance {
+            Some(instance) => Self::replace_task_fields_in_transaction(&mut tx, instance.id, override_task_data, self.now()).await?,
+            None => Self::add_task_in_transaction(&mut tx, override_task_data, self.now()).await?,
+        };
+
+        let exception = NewSeriesException {
+            series_id,
+            occurrence_dt,
+            exception_type: ExceptionType::Override,
+            exception_task_id: Some(override_task.id),
+            notes: Some(format!("Override task created: {}", override_task.name)),
+        };
+        Self::add_series_exception_in_transaction(&mut tx, exception, self.now()).await?;
+
+        tx.commit().await?;
+        Ok(override_task)
+    }
+
+    async fn move_occurrence_with_validation(&self, series_id: Uuid, from_dt: DateTime<Utc>, to_dt: DateTime<Utc>, timezone: &str) -> Result<Task, CoreError> {
+        let mut tx = self.pool().begin().await?;
+
+        let series: TaskSeries = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
+            .bind(series_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Series with id {} not found", series_id)))?;
+
+        let template_task = Self::find_task_by_id_in_transaction(&mut tx, series.template_task_id)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Template task with id {} not found", series.template_task_id)))?;
+
+        crate::timezone::validate_timezone(timezone)?;
+
+        // The moved occurrence becomes a standalone task based on the template
+        let moved_task_data = NewTaskData {
+            name: template_task.name.clone(),
+            description: template_task.description.clone(),
+            due_at: Some(to_dt),
+            priority: Some(template_task.priority.clone()),
+            project_id: template_task.project_id,
+            parent_id: template_task.parent_id,
+            timezone: Some(timezone.to_string()),
+            estimate: template_task.estimate(),
+            scheduled_at: template_task.date_relative_to_due(template_task.scheduled_at, to_dt),
+            wait_until: template_task.date_relative_to_due(template_task.wait_until, to_dt),
+            start_at: template_task.date_relative_to_due(template_task.start_at, to_dt),
+            ..Default::default()
+        };
+        // An already materialized instance is moved itself, keeping its history
+        let instance: Option<Task> = sqlx::query_as(
+            "SELECT * FROM tasks WHERE series_id = $1 AND due_at = $2 AND id != $3"
+        )
+        .bind(series_id)
+        .bind(from_dt)
+        .bind(series.template_task_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let moved_task = match instance {
+            Some(instance) => Self::move_task_in_transaction(&mut tx, &instance, to_dt, self.now()).await?,
+            None => Self::add_task_in_transaction(&mut tx, moved_task_data, self.now()).await?,
+        };
+
+        let exception = NewSeriesException {
+            series_id,
+            occurrence_dt: from_dt,
+            exception_type: ExceptionType::Move,
+            exception_task_id: Some(moved_task.id),
+            notes: Some(format!("Moved from {} to {} ({})", from_dt.format("%Y-%m-%d %H:%M"), to_dt.format("%Y-%m-%d %H:%M"), timezone)),
+        };
+        Self::add_series_exception_in_transaction(&mut tx, exception, self.now()).await?;
+
+        tx.commit().await?;
+        Ok(moved_task)
+    }
+}
+
+impl PostgresRepository {
+    /// Validate exception data for consistency and business rules
+    async fn validate_exception_data(tx: &mut Transaction<'_, Postgres>, exception: &NewSeriesException) -> Result<(), CoreError> {
+        let series_exists = sqlx::query("SELECT 1 FROM task_series WHERE id = $1")
+            .bind(exception.series_id)
+            .fetch_optional(&mut **tx)
+            .await?
+            .is_some();
+
+        if !series_exists {
+            return Err(CoreError::NotFound(format!("Series with id {} not found", exception.series_id)));
+        }
+
+        match exception.exception_type {
+            ExceptionType::Skip => {
+                if exception.exception_task_id.is_some() {
+                    return Err(CoreError::InvalidException(
+                        "Skip exceptions cannot have an exception_task_id".to_string()
+                    ));
+                }
+            }
+            ExceptionType::Override | ExceptionType::Move => {
+                if exception.exception_task_id.is_none() {
+                    return Err(CoreError::InvalidException(
+                        format!("{:?} exceptions must have an exception_task_id", exception.exception_type)
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remove the exception at an occurrence within an existing transaction,
+    /// returning whether there was one
+    ///
+    /// The task of an override or move goes back to being the series instance
+    /// for the occurrence, with the template's fields but its own status,
+    /// notes and tracked time.
+    pub(crate) async fn remove_series_exception_in_transaction(
+        tx: &mut Transaction<'_, Postgres>,
+        series_id: Uuid,
+        occurrence_dt: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<bool, CoreError> {
+        let exception: Option<SeriesException> = sqlx::query_as(
+            "DELETE FROM series_exceptions WHERE series_id = $1 AND occurrence_dt = $2 RETURNING *"
+        )
+        .bind(series_id)
+        .bind(occurrence_dt)
+        .fetch_optional(&mut **tx)
+        .await?;
+        let Some(exception) = exception else {
+            return Ok(false);
+        };
+
+        if let Some(task_id) = exception.exception_task_id {
+            let series: TaskSeries = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
+                .bind(series_id)
+                .fetch_one(&mut **tx)
+                .await?;
+            let template = Self::find_task_by_id_in_transaction(tx, series.template_task_id)
+                .await?
+                .ok_or_else(|| CoreError::NotFound(format!("Template task with id {} not found", series.template_task_id)))?;
+            Self::replace_task_fields_in_transaction(tx, task_id, template.instance_data(series_id, occurrence_dt), now).await?;
+        }
+
+        Ok(true)
+    }
+
+    /// Move a series instance to `to_dt` as a standalone task, keeping its
+    /// scheduling dates at the same distance from the due date
+    pub(crate) async fn move_task_in_transaction(
+        tx: &mut Transaction<'_, Postgres>,
+        task: &Task,
+        to_dt: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<Task, CoreError> {
+        sqlx::query(
+            r#"UPDATE tasks
+            SET due_at = $1, scheduled_at = $2, wait_until = $3, start_at = $4, series_id = NULL, updated_at = $5
+            WHERE id = $6"#
+        )
+        .bind(to_dt)
+        .bind(task.date_relative_to_due(task.scheduled_at, to_dt))
+        .bind(task.date_relative_to_due(task.wait_until, to_dt))
+        .bind(task.date_relative_to_due(task.start_at, to_dt))
+        .bind(now)
+        .bind(task.id)
+        .execute(&mut **tx)
+        .await?;
+
+        Self::find_task_by_id_in_transaction(tx, task.id)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Task with id {} not found", task.id)))
+    }
+
+    /// Overwrite the fields and tags of an existing task with `data`
+    ///
+    /// Status, dependencies, notes and time entries are left alone.
+    pub(crate) async fn replace_task_fields_in_transaction(
+        tx: &mut Transaction<'_, Postgres>,
+        task_id: Uuid,
+        data: NewTaskData,
+        now: DateTime<Utc>,
+    ) -> Result<Task, CoreError> {
+        let project_id = match (data.project_id, &data.project_name) {
+            (Some(project_id), _) => Some(project_id),
+            (None, Some(project_name)) => {
+                let project: Option<Project> = sqlx::query_as("SELECT * FROM projects WHERE name = $1")
+                    .bind(project_name)
+                    .fetch_optional(&mut **tx)
+                    .await?;
+                Some(project.map(|p| p.id).ok_or_else(|| CoreError::NotFound(project_name.clone()))?)
+            }
+            (None, None) => None,
+        };
+        let estimate_minutes = data.estimate.map(forecast::estimate_minutes).transpose()?;
+
+        sqlx::query(
+            r#"UPDATE tasks
+            SET name = $1, description = $2, priority = $3, due_at = $4, project_id = $5, parent_id = $6,
+                series_id = $7, estimate_minutes = $8, scheduled_at = $9, wait_until = $10, start_at = $11, updated_at = $12
+            WHERE id = $13"#
+        )
+        .bind(&data.name)
+        .bind(&data.description)
+        .bind(data.priority.unwrap_or(TaskPriority::None))
+        .bind(data.due_at)
+        .bind(project_id)
+        .bind(data.parent_id)
+        .bind(data.series_id)
+        .bind(estimate_minutes)
+        .bind(data.scheduled_at)
+        .bind(data.wait_until)
+        .bind(data.start_at)
+        .bind(now)
+        .bind(task_id)
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query("DELETE FROM task_tags WHERE task_id = $1")
+            .bind(task_id)
+            .execute(&mut **tx)
+            .await?;
+        for tag in &data.tags {
+            sqlx::query("INSERT INTO task_tags (task_id, tag_name) VALUES ($1, $2) ON CONFLICT DO NOTHING")
+                .bind(task_id)
+                .bind(tag)
+                .execute(&mut **tx)
+                .await?;
+        }
+
+        Self::find_task_by_id_in_transaction(tx, task_id)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Task with id {} not found", task_id)))
+    }
+
+    /// Add a series exception within an existing transaction
+    pub(crate) async fn add_series_exception_in_transaction(
+        tx: &mut Transaction<'_, Postgres>,
+        exception: NewSeriesException,
+        now: DateTime<Utc>,
+    ) -> Result<SeriesException, CoreError> {
+        let created_exception = SeriesException {
+            series_id: exception.series_id,
+            occurrence_dt: exception.occurrence_dt,
+            exception_type: exception.exception_type,
+            exception_task_id: exception.exception_task_id,
+            notes: exception.notes,
+            created_at: now,
+        };
+
+        sqlx::query(
+            "INSERT INTO series_exceptions (series_id, occurrence_dt, exception_type, exception_task_id, notes, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6)"
+        )
+        .bind(created_exception.series_id)
+        .bind(created_exception.occurrence_dt)
+        .bind(&created_exception.exception_type)
+        .bind(created_exception.exception_task_id)
+        .bind(&created_exception.notes)
+        .bind(created_exception.created_at)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(created_exception)
+    }
+}
@@ -0,0 +1,381 @@
+use crate::error::CoreError;
+use crate::models::{DstPolicy, SeriesException, Task, TaskSeries, TaskStatus, TimeMode};
+use crate::query::Query;
+use crate::recurrence::{same_wall_clock, RecurrenceManager};
+use crate::repository::query_builder::SqlQueryBuilder;
+use crate::repository::{MaterializationRepository, PostgresRepository, Repository, SeriesRepository};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use sqlx::{Postgres, Transaction};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Upper bound on instances created per series when materializing inside a
+/// caller's transaction, matching the SQLite backend
+const IN_TRANSACTION_MAX_BATCH_SIZE: usize = 100;
+
+#[async_trait]
+impl MaterializationRepository for PostgresRepository {
+    async fn refresh_series_materialization(&self, window_start: DateTime<Utc>, window_end: DateTime<Utc>) -> Result<(), CoreError> {
+        let active_series = self.find_active_series().await?;
+
+        for series in active_series {
+            self.refresh_single_series_materialization(series.id, window_start, window_end).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn find_materialized_tasks_for_series(&self, series_id: Uuid, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<Task>, CoreError> {
+        let tasks = sqlx::query_as(
+            r#"SELECT * FROM tasks
+            WHERE series_id = $1
+            AND due_at BETWEEN $2 AND $3
+            ORDER BY due_at"#
+        )
+        .bind(series_id)
+        .bind(start)
+        .bind(end)
+        .fetch_all(self.pool())
+        .await?;
+        Ok(tasks)
+    }
+
+    async fn update_series_materialization_boundary(&self, series_id: Uuid, boundary: DateTime<Utc>) -> Result<(), CoreError> {
+        let result = sqlx::query(
+            "UPDATE task_series SET last_materialized_until = $1, updated_at = $2 WHERE id = $3"
+        )
+        .bind(boundary)
+        .bind(self.now())
+        .bind(series_id)
+        .execute(self.pool())
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(CoreError::NotFound(format!("Series with id {} not found", series_id)));
+        }
+
+        Ok(())
+    }
+}
+
+impl PostgresRepository {
+    /// Refresh materialization for a single series
+    pub async fn refresh_single_series_materialization(&self, series_id: Uuid, window_start: DateTime<Utc>, window_end: DateTime<Utc>) -> Result<(), CoreError> {
+        let mut tx = self.pool().begin().await?;
+        let max_batch_size = self.materialization_manager().config().max_batch_size as usize;
+        let series: TaskSeries = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
+            .bind(series_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Series with id {} not found", series_id)))?;
+        Self::follow_local_timezone(&mut tx, series, self.local_timezone(), self.now()).await?;
+        Self::materialize_series(&mut tx, series_id, window_start, window_end, self.now(), max_batch_size).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Delete the pending instances that `series` would place elsewhere
+    /// under `policy`, from `now` on, before its DST policy changes
+    ///
+    /// Materialization recreates them at their new times; overridden and
+    /// moved occurrences keep their own tasks.
+    pub(crate) async fn remove_instances_moved_by(
+        tx: &mut Transaction<'_, Postgres>,
+        series: &TaskSeries,
+        policy: DstPolicy,
+        now: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        let instances: Vec<Task> = sqlx::query_as(
+            r#"SELECT * FROM tasks
+            WHERE series_id = $1 AND id != $2 AND status = 'pending' AND due_at >= $3
+            AND id NOT IN (SELECT exception_task_id FROM series_exceptions WHERE series_id = $1 AND exception_task_id IS NOT NULL)"#
+        )
+        .bind(series.id)
+        .bind(series.template_task_id)
+        .bind(now)
+        .fetch_all(&mut **tx)
+        .await?;
+        let Some(last_due) = instances.iter().filter_map(|t| t.due_at).max() else {
+            return Ok(());
+        };
+
+        let template_task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = $1")
+            .bind(series.template_task_id)
+            .fetch_one(&mut **tx)
+            .await?;
+        let moved: HashSet<DateTime<Utc>> = RecurrenceManager::new(series.clone(), template_task, Vec::new())?
+            .occurrences_moved_by(policy, now, last_due)?
+            .into_iter()
+            .collect();
+
+        for instance in instances.iter().filter(|t| t.due_at.is_some_and(|due| moved.contains(&due))) {
+            sqlx::query("DELETE FROM tasks WHERE id = $1")
+                .bind(instance.id)
+                .execute(&mut **tx)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Move the pending tasks and exceptions of `series` from the wall-clock
+    /// times they have in `from` to the same times in `to`
+    pub(crate) async fn shift_wall_clock_times(
+        tx: &mut Transaction<'_, Postgres>,
+        series: &TaskSeries,
+        from: Tz,
+        to: Tz,
+        now: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        let shift = |dt: DateTime<Utc>| same_wall_clock(dt, from, to);
+
+        let pending: Vec<Task> = sqlx::query_as(
+            "SELECT * FROM tasks WHERE (series_id = $1 OR id = $2) AND status = 'pending'"
+        )
+        .bind(series.id)
+        .bind(series.template_task_id)
+        .fetch_all(&mut **tx)
+        .await?;
+        for task in pending {
+            sqlx::query(
+                "UPDATE tasks SET due_at = $1, scheduled_at = $2, wait_until = $3, start_at = $4, updated_at = $5 WHERE id = $6"
+            )
+            .bind(task.due_at.map(shift))
+            .bind(task.scheduled_at.map(shift))
+            .bind(task.wait_until.map(shift))
+            .bind(task.start_at.map(shift))
+            .bind(now)
+            .bind(task.id)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        let exceptions: Vec<SeriesException> = sqlx::query_as(
+            "SELECT * FROM series_exceptions WHERE series_id = $1"
+        )
+        .bind(series.id)
+        .fetch_all(&mut **tx)
+        .await?;
+        for exception in exceptions {
+            sqlx::query("UPDATE series_exceptions SET occurrence_dt = $1 WHERE series_id = $2 AND occurrence_dt = $3")
+                .bind(shift(exception.occurrence_dt))
+                .bind(series.id)
+                .bind(exception.occurrence_dt)
+                .execute(&mut **tx)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Move a floating series into `local_timezone`, keeping the wall-clock
+    /// times of its start, exceptions and pending tasks
+    async fn follow_local_timezone(
+        tx: &mut Transaction<'_, Postgres>,
+        series: TaskSeries,
+        local_timezone: Tz,
+        now: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        if series.time_mode != TimeMode::Floating || series.timezone == local_timezone.name() {
+            return Ok(());
+        }
+        let from: Tz = series.timezone.parse()
+            .map_err(|_| CoreError::InvalidTimezone(series.timezone.clone()))?;
+        Self::shift_wall_clock_times(tx, &series, from, local_timezone, now).await?;
+
+        sqlx::query("UPDATE task_series SET dtstart = $1, timezone = $2, updated_at = $3 WHERE id = $4")
+            .bind(same_wall_clock(series.dtstart, from, local_timezone))
+            .bind(local_timezone.name())
+            .bind(now)
+            .bind(series.id)
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+
+    /// Refresh materialization for a single series within an existing transaction
+    pub(crate) async fn refresh_single_series_materialization_in_transaction(
+        tx: &mut Transaction<'_, Postgres>,
+        series_id: Uuid,
+        window_start: DateTime<Utc>,
+        window_end: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        Self::materialize_series(tx, series_id, window_start, window_end, now, IN_TRANSACTION_MAX_BATCH_SIZE).await
+    }
+
+    /// Create the missing visible instances of a series inside the window
+    async fn materialize_series(
+        tx: &mut Transaction<'_, Postgres>,
+        series_id: Uuid,
+        window_start: DateTime<Utc>,
+        window_end: DateTime<Utc>,
+        now: DateTime<Utc>,
+        max_batch_size: usize,
+    ) -> Result<(), CoreError> {
+        let series: TaskSeries = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
+            .bind(series_id)
+            .fetch_optional(&mut **tx)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Series with id {} not found", series_id)))?;
+
+        let template_task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = $1")
+            .bind(series.template_task_id)
+            .fetch_optional(&mut **tx)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Template task with id {} not found", series.template_task_id)))?;
+
+        let exceptions: Vec<SeriesException> = sqlx::query_as(
+            "SELECT * FROM series_exceptions WHERE series_id = $1"
+        )
+        .bind(series_id)
+        .fetch_all(&mut **tx)
+        .await?;
+
+        let recurrence_manager = RecurrenceManager::new(series, template_task.clone(), exceptions)?;
+        let occurrences = recurrence_manager.generate_occurrences_between(window_start, window_end)?;
+
+        let existing_due_dates: HashSet<DateTime<Utc>> = sqlx::query_scalar(
+            r#"SELECT due_at FROM tasks
+            WHERE series_id = $1
+            AND due_at BETWEEN $2 AND $3"#
+        )
+        .bind(series_id)
+        .bind(window_start)
+        .bind(window_end)
+        .fetch_all(&mut **tx)
+        .await?
+        .into_iter()
+        .collect();
+
+        let mut created_count = 0;
+        for occurrence in occurrences {
+            if !occurrence.needs_instance() || existing_due_dates.contains(&occurrence.effective_dt) {
+                continue;
+            }
+
+            Self::insert_instance_in_transaction(tx, &template_task, series_id, occurrence.effective_dt, now).await?;
+
+            created_count += 1;
+            if created_count >= max_batch_size {
+                break;
+            }
+        }
+
+        if created_count > 0 {
+            sqlx::query(
+                "UPDATE task_series SET last_materialized_until = $1, updated_at = $2 WHERE id = $3"
+            )
+            .bind(window_end)
+            .bind(now)
+            .bind(series_id)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Create the instance of a series due at `due_at` from its template,
+    /// with copies of the template's subtasks, picking up any annotations
+    /// left on that occurrence
+    async fn insert_instance_in_transaction(
+        tx: &mut Transaction<'_, Postgres>,
+        template_task: &Task,
+        series_id: Uuid,
+        due_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<Uuid, CoreError> {
+        let instance_id = Uuid::now_v7();
+        sqlx::query(
+            r#"INSERT INTO tasks (id, name, description, status, priority, due_at, completed_at, created_at, updated_at, project_id, parent_id, series_id, estimate_minutes, scheduled_at, wait_until, start_at, all_day)
+            VALUES ($1, $2, $3, $4, $5, $6, NULL, $7, $7, $8, $9, $10, $11, $12, $13, $14, $15)"#
+        )
+        .bind(instance_id)
+        .bind(&template_task.name)
+        .bind(&template_task.description)
+        .bind(TaskStatus::Pending)
+        .bind(&template_task.priority)
+        .bind(due_at)
+        .bind(now)
+        .bind(template_task.project_id)
+        .bind(template_task.parent_id)
+        .bind(series_id)
+        .bind(template_task.estimate_minutes)
+        .bind(template_task.date_relative_to_due(template_task.scheduled_at, due_at))
+        .bind(template_task.date_relative_to_due(template_task.wait_until, due_at))
+        .bind(template_task.date_relative_to_due(template_task.start_at, due_at))
+        .bind(template_task.all_day)
+        .execute(&mut **tx)
+        .await?;
+        Self::reattach_annotations(tx, instance_id, series_id, due_at).await?;
+        Self::copy_template_subtasks_in_transaction(tx, template_task, instance_id, due_at, now).await?;
+        Ok(instance_id)
+    }
+
+    /// Copy the subtasks under `template_task`, however deep, to the instance
+    /// `instance_id` due at `due_at`
+    ///
+    /// The copies start out pending and keep their dates at the same distance
+    /// from the due date as the originals have from the template's.
+    async fn copy_template_subtasks_in_transaction(
+        tx: &mut Transaction<'_, Postgres>,
+        template_task: &Task,
+        instance_id: Uuid,
+        due_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        let mut frontier = vec![(template_task.id, instance_id)];
+        while let Some((source_id, copy_parent_id)) = frontier.pop() {
+            let subtasks: Vec<Task> = sqlx::query_as("SELECT * FROM tasks WHERE parent_id = $1 ORDER BY created_at")
+                .bind(source_id)
+                .fetch_all(&mut **tx)
+                .await?;
+
+            for subtask in subtasks {
+                let copy_id = Uuid::now_v7();
+                sqlx::query(
+                    r#"INSERT INTO tasks (id, name, description, status, priority, due_at, completed_at, created_at, updated_at, project_id, parent_id, series_id, estimate_minutes, scheduled_at, wait_until, start_at, all_day)
+                    VALUES ($1, $2, $3, $4, $5, $6, NULL, $7, $7, $8, $9, NULL, $10, $11, $12, $13, $14)"#
+                )
+                .bind(copy_id)
+                .bind(&subtask.name)
+                .bind(&subtask.description)
+                .bind(TaskStatus::Pending)
+                .bind(&subtask.priority)
+                .bind(template_task.date_relative_to_due(subtask.due_at, due_at))
+                .bind(now)
+                .bind(subtask.project_id)
+                .bind(copy_parent_id)
+                .bind(subtask.estimate_minutes)
+                .bind(template_task.date_relative_to_due(subtask.scheduled_at, due_at))
+                .bind(template_task.date_relative_to_due(subtask.wait_until, due_at))
+                .bind(template_task.date_relative_to_due(subtask.start_at, due_at))
+                .bind(subtask.all_day)
+                .execute(&mut **tx)
+                .await?;
+                frontier.push((subtask.id, copy_id));
+            }
+        }
+        Ok(())
+    }
+
+    /// Ensures materialization for any query with intelligent window calculation
+    pub(crate) async fn ensure_materialization_for_query(&self, query: &Query) -> Result<(), CoreError> {
+        let filters = SqlQueryBuilder::extract_filters_from_query(query);
+
+        let (window_start, window_end) = if filters.iter().any(|f| matches!(f, crate::models::Filter::DueDate(_))) {
+            self.materialization_manager().calculate_window_for_filters(&filters)
+        } else {
+            // Default window so recurring tasks show up in unfiltered listings
+            let now = self.now();
+            let config = self.materialization_manager().config();
+            (
+                now - chrono::Duration::days(config.materialization_grace_days as i64),
+                now + chrono::Duration::days(config.lookahead_days as i64),
+            )
+        };
+
+        self.refresh_series_materialization(window_start, window_end).await
+    }
+}
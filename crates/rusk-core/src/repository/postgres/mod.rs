@@ -0,0 +1,103 @@
+//! PostgreSQL implementation of the repository traits.
+//!
+//! [`PostgresRepository`] mirrors [`SqliteRepository`](super::SqliteRepository)
+//! domain by domain so a team can share one task database on a server. Ids
+//! are native `UUID` columns and timestamps `TIMESTAMPTZ`; the schema lives in
+//! `migrations/postgres` and is applied by [`crate::db::connect_postgres`].
+
+use crate::clock::Clock;
+use crate::models::CompletionPolicy;
+use crate::recurrence::MaterializationManager;
+use crate::repository::Repository;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+mod annotations;
+mod blueprints;
+mod exceptions;
+mod materialization;
+mod projects;
+mod reminders;
+mod series;
+mod tasks;
+mod time_tracking;
+mod workflow;
+
+/// PostgreSQL implementation of the repository pattern
+pub struct PostgresRepository {
+    pool: PgPool,
+    materialization_manager: MaterializationManager,
+    clock: Arc<dyn Clock>,
+    completion_policy: CompletionPolicy,
+    local_timezone: Tz,
+}
+
+impl PostgresRepository {
+    /// Create a repository that shares the materialization manager's clock
+    pub fn new(pool: PgPool, materialization_manager: MaterializationManager) -> Self {
+        let clock = materialization_manager.clock().clone();
+        Self { pool, materialization_manager, clock, completion_policy: CompletionPolicy::default(), local_timezone: Tz::UTC }
+    }
+
+    /// Create a repository driven by `clock`, which also replaces the
+    /// materialization manager's clock so both agree on the current time
+    pub fn with_clock(pool: PgPool, mut materialization_manager: MaterializationManager, clock: Arc<dyn Clock>) -> Self {
+        materialization_manager.set_clock(clock.clone());
+        Self { pool, materialization_manager, clock, completion_policy: CompletionPolicy::default(), local_timezone: Tz::UTC }
+    }
+
+    /// Use `policy` whenever a task is completed
+    pub fn with_completion_policy(mut self, policy: CompletionPolicy) -> Self {
+        self.completion_policy = policy;
+        self
+    }
+
+    /// Read floating series and relative day filters such as `due:today` in
+    /// `timezone`, the timezone the user is in (UTC unless set)
+    pub fn with_local_timezone(mut self, timezone: Tz) -> Self {
+        self.local_timezone = timezone;
+        self
+    }
+
+    /// Get the clock used for timestamps and time-relative queries
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    /// Current time according to the repository clock
+    pub(crate) fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+
+    /// Get a reference to the database pool for internal use across modules
+    pub(crate) fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
+    /// Get a reference to the materialization manager for internal use
+    pub(crate) fn materialization_manager(&self) -> &MaterializationManager {
+        &self.materialization_manager
+    }
+
+    /// How completing a task affects its subtasks and parent
+    pub(crate) fn completion_policy(&self) -> CompletionPolicy {
+        self.completion_policy
+    }
+
+    /// Close the underlying pool, waiting for open connections to shut down
+    pub async fn close(&self) {
+        self.pool.close().await;
+    }
+}
+
+impl Repository for PostgresRepository {
+    fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    fn local_timezone(&self) -> Tz {
+        self.local_timezone
+    }
+}
@@ -0,0 +1,645 @@
+use crate::error::CoreError;
+use crate::models::{
+    NewSeriesData, NewTaskData, SeriesException, SeriesRuleChange, SeriesStatistics, Task, TaskSeries, TaskStatus,
+    UpdateSeriesData,
+};
+use crate::recurrence::{timezone_move, truncated_rrule, RecurrenceManager};
+use crate::repository::PostgresRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, QueryBuilder, Transaction};
+use uuid::Uuid;
+
+#[async_trait]
+impl crate::repository::SeriesRepository for PostgresRepository {
+    async fn create_series(&self, data: NewSeriesData) -> Result<TaskSeries, CoreError> {
+        let mut tx = self.pool().begin().await?;
+        let series = Self::create_series_in_transaction(&mut tx, data, self.now()).await?;
+        tx.commit().await?;
+        Ok(series)
+    }
+
+    async fn find_series_by_id(&self, id: Uuid) -> Result<Option<TaskSeries>, CoreError> {
+        let series = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
+            .bind(id)
+            .fetch_optional(self.pool())
+            .await?;
+        Ok(series)
+    }
+
+    async fn find_series_by_template(&self, template_id: Uuid) -> Result<Option<TaskSeries>, CoreError> {
+        let series = sqlx::query_as("SELECT * FROM task_series WHERE template_task_id = $1")
+            .bind(template_id)
+            .fetch_optional(self.pool())
+            .await?;
+        Ok(series)
+    }
+
+    async fn update_series(&self, id: Uuid, mut data: UpdateSeriesData) -> Result<TaskSeries, CoreError> {
+        let mut tx = self.pool().begin().await?;
+
+        let current_series: TaskSeries = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Series with id {} not found", id)))?;
+
+        // A new timezone alone keeps occurrences at their times of day
+        if let Some((from, to)) = timezone_move(&current_series, &mut data)? {
+            Self::shift_wall_clock_times(&mut tx, &current_series, from, to, self.now()).await?;
+        }
+
+        if let Some(rrule) = &data.rrule {
+            let timezone = data.timezone.as_deref().unwrap_or(&current_series.timezone);
+            let dtstart = data.dtstart.unwrap_or(current_series.dtstart);
+            RecurrenceManager::validate_rrule_at(rrule, timezone, dtstart)?;
+        }
+
+        if let Some(timezone) = &data.timezone {
+            RecurrenceManager::validate_rrule(&current_series.rrule, timezone)?;
+        }
+
+        let mut qb: QueryBuilder<Postgres> = QueryBuilder::new("UPDATE task_series SET updated_at = ");
+        qb.push_bind(self.now());
+        let mut updated = false;
+
+        if let Some(rrule) = &data.rrule {
+            qb.push(", rrule = ");
+            qb.push_bind(rrule);
+            updated = true;
+        }
+
+        if let Some(dtstart) = data.dtstart {
+            qb.push(", dtstart = ");
+            qb.push_bind(dtstart);
+            updated = true;
+        }
+
+        if let Some(timezone) = &data.timezone {
+            qb.push(", timezone = ");
+            qb.push_bind(timezone);
+            updated = true;
+        }
+
+        if let Some(active) = data.active {
+            qb.push(", active = ");
+            qb.push_bind(active);
+            updated = true;
+        }
+
+        let dst_policy = data.dst_policy.filter(|policy| *policy != current_series.dst_policy);
+        if let Some(policy) = dst_policy {
+            Self::remove_instances_moved_by(&mut tx, &current_series, policy, self.now()).await?;
+
+            qb.push(", dst_gap = ");
+            qb.push_bind(policy.dst_gap);
+            qb.push(", dst_overlap = ");
+            qb.push_bind(policy.dst_overlap);
+            updated = true;
+        }
+
+        if updated {
+            // If RRULE, timezone or DST policy changed, reset materialization boundary
+            if data.rrule.is_some() || data.timezone.is_some() || dst_policy.is_some() {
+                qb.push(", last_materialized_until = NULL");
+            }
+            qb.push(" WHERE id = ");
+            qb.push_bind(id);
+            qb.build().execute(&mut *tx).await?;
+        }
+
+        let updated_series: TaskSeries = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+        Self::record_rule_change(&mut tx, &current_series, &updated_series, self.now()).await?;
+
+        tx.commit().await?;
+        Ok(updated_series)
+    }
+
+    async fn delete_series(&self, id: Uuid) -> Result<(), CoreError> {
+        let mut tx = self.pool().begin().await?;
+
+        // Exceptions and rule changes go with the series; instances have to be removed explicitly
+        // because their foreign key only nulls out series_id
+        sqlx::query("DELETE FROM tasks WHERE series_id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        let result = sqlx::query("DELETE FROM task_series WHERE id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(CoreError::NotFound(format!("Series with id {} not found", id)));
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn find_active_series(&self) -> Result<Vec<TaskSeries>, CoreError> {
+        let series = sqlx::query_as("SELECT * FROM task_series WHERE active = true ORDER BY created_at, id")
+            .fetch_all(self.pool())
+            .await?;
+        Ok(series)
+    }
+
+    async fn duplicate_series(&self, series_id: Uuid, new_name: String, new_timezone: Option<String>) -> Result<TaskSeries, CoreError> {
+        let mut tx = self.pool().begin().await?;
+
+        let original_series: TaskSeries = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
+            .bind(series_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Series with id {} not found", series_id)))?;
+
+        let original_template: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = $1")
+            .bind(original_series.template_task_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Template task with id {} not found", original_series.template_task_id)))?;
+
+        let new_template_data = NewTaskData {
+            name: new_name,
+            description: original_template.description.clone(),
+            due_at: original_template.due_at,
+            priority: Some(original_template.priority.clone()),
+            project_id: original_template.project_id,
+            timezone: new_timezone.clone(),
+            estimate: original_template.estimate(),
+            scheduled_at: original_template.scheduled_at,
+            wait_until: original_template.wait_until,
+            start_at: original_template.start_at,
+            ..Default::default()
+        };
+        let new_template = Self::add_task_in_transaction(&mut tx, new_template_data, self.now()).await?;
+
+        let new_series_data = NewSeriesData {
+            template_task_id: new_template.id,
+            rrule: original_series.rrule.clone(),
+            dtstart: original_series.dtstart,
+            timezone: new_timezone.unwrap_or(original_series.timezone.clone()),
+            time_mode: original_series.time_mode,
+            dst_policy: original_series.dst_policy,
+        };
+        let new_series = Self::create_series_in_transaction(&mut tx, new_series_data, self.now()).await?;
+
+        sqlx::query(
+            "INSERT INTO task_tags (task_id, tag_name) SELECT $1, tag_name FROM task_tags WHERE task_id = $2"
+        )
+        .bind(new_template.id)
+        .bind(original_template.id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(new_series)
+    }
+
+    async fn archive_completed_series(&self, series_id: Uuid) -> Result<(), CoreError> {
+        let mut tx = self.pool().begin().await?;
+
+        let pending_count: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM tasks WHERE series_id = $1 AND status = 'pending'"
+        )
+        .bind(series_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if pending_count.0 > 0 {
+            return Err(CoreError::SeriesNotCompleted(format!(
+                "Series has {} pending tasks that must be completed or cancelled before archiving",
+                pending_count.0
+            )));
+        }
+
+        sqlx::query("UPDATE task_series SET active = false, updated_at = $1 WHERE id = $2")
+            .bind(self.now())
+            .bind(series_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn bulk_update_series(&self, updates: Vec<(Uuid, UpdateSeriesData)>) -> Result<Vec<TaskSeries>, CoreError> {
+        let mut tx = self.pool().begin().await?;
+        let mut updated_series = Vec::new();
+
+        for (series_id, update_data) in updates {
+            let updated = Self::update_series_in_transaction(&mut tx, series_id, update_data, self.now()).await?;
+            updated_series.push(updated);
+        }
+
+        tx.commit().await?;
+        Ok(updated_series)
+    }
+
+    async fn find_series_by_pattern(&self, pattern: &str) -> Result<Vec<TaskSeries>, CoreError> {
+        // ILIKE keeps the case-insensitive matching of SQLite's LIKE
+        let series: Vec<TaskSeries> = sqlx::query_as(
+            "SELECT ts.* FROM task_series ts
+             JOIN tasks t ON ts.template_task_id = t.id
+             WHERE t.name ILIKE $1 OR ts.rrule ILIKE $1
+             ORDER BY ts.created_at, ts.id"
+        )
+        .bind(format!("%{}%", pattern))
+        .fetch_all(self.pool())
+        .await?;
+
+        Ok(series)
+    }
+
+    async fn get_series_statistics(&self, series_id: Uuid) -> Result<SeriesStatistics, CoreError> {
+        let series: TaskSeries = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
+            .bind(series_id)
+            .fetch_optional(self.pool())
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Series with id {} not found", series_id)))?;
+
+        let task_stats: (i64, i64, i64, i64) = sqlx::query_as(
+            "SELECT
+                COUNT(*) AS total,
+                COUNT(*) FILTER (WHERE status = 'completed') AS completed,
+                COUNT(*) FILTER (WHERE status = 'pending') AS pending,
+                COUNT(*) FILTER (WHERE status = 'cancelled') AS cancelled
+             FROM tasks
+             WHERE series_id = $1"
+        )
+        .bind(series_id)
+        .fetch_one(self.pool())
+        .await?;
+
+        let exception_stats: (i64, i64, i64, i64) = sqlx::query_as(
+            "SELECT
+                COUNT(*) AS total,
+                COUNT(*) FILTER (WHERE exception_type = 'skip') AS skip,
+                COUNT(*) FILTER (WHERE exception_type = 'override') AS override,
+                COUNT(*) FILTER (WHERE exception_type = 'move') AS move
+             FROM series_exceptions
+             WHERE series_id = $1"
+        )
+        .bind(series_id)
+        .fetch_one(self.pool())
+        .await?;
+
+        let time_stats: (Option<DateTime<Utc>>, Option<DateTime<Utc>>) = sqlx::query_as(
+            "SELECT MIN(due_at), MAX(due_at) FROM tasks WHERE series_id = $1 AND due_at IS NOT NULL"
+        )
+        .bind(series_id)
+        .fetch_one(self.pool())
+        .await?;
+
+        let template_task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = $1")
+            .bind(series.template_task_id)
+            .fetch_optional(self.pool())
+            .await?
+            .ok_or_else(|| CoreError::NotFound("Template task not found".to_string()))?;
+
+        let exceptions: Vec<SeriesException> = sqlx::query_as(
+            "SELECT * FROM series_exceptions WHERE series_id = $1"
+        )
+        .bind(series_id)
+        .fetch_all(self.pool())
+        .await?;
+
+        let next_occurrence = if series.active {
+            let recurrence_manager = RecurrenceManager::new(series.clone(), template_task, exceptions)?;
+            recurrence_manager.next_occurrence_after(self.now())?
+        } else {
+            None
+        };
+
+        // Average tracked time over completed occurrences that have time entries
+        let average_completion_time_hours: Option<f64> = sqlx::query_scalar(
+            r#"SELECT (AVG(total) / 3600.0)::DOUBLE PRECISION FROM (
+                SELECT SUM(te.duration_seconds) AS total
+                FROM tasks t
+                JOIN time_entries te ON te.task_id = t.id
+                WHERE t.series_id = $1 AND t.status = 'completed' AND te.duration_seconds IS NOT NULL
+                GROUP BY t.id
+            ) AS tracked"#,
+        )
+        .bind(series_id)
+        .fetch_one(self.pool())
+        .await?;
+
+        // Same health score as the SQLite backend
+        let completion_rate = if task_stats.0 > 0 {
+            task_stats.1 as f64 / task_stats.0 as f64
+        } else {
+            1.0
+        };
+        let activity_factor = if series.active { 1.0 } else { 0.8 };
+        let consistency_factor = if (exception_stats.0 as f64) / (task_stats.0.max(1) as f64) < 0.2 { 1.0 } else { 0.9 };
+        let health_score = completion_rate * activity_factor * consistency_factor;
+
+        Ok(SeriesStatistics {
+            series_id,
+            total_occurrences_created: task_stats.0 as u32,
+            completed_occurrences: task_stats.1 as u32,
+            pending_occurrences: task_stats.2 as u32,
+            cancelled_occurrences: task_stats.3 as u32,
+            total_exceptions: exception_stats.0 as u32,
+            skip_exceptions: exception_stats.1 as u32,
+            override_exceptions: exception_stats.2 as u32,
+            move_exceptions: exception_stats.3 as u32,
+            first_occurrence: time_stats.0,
+            last_occurrence: time_stats.1,
+            next_occurrence,
+            average_completion_time_hours,
+            series_health_score: health_score,
+        })
+    }
+
+    async fn find_series_rule_changes(&self, series_id: Uuid) -> Result<Vec<SeriesRuleChange>, CoreError> {
+        let changes = sqlx::query_as(
+            "SELECT * FROM series_rule_changes WHERE series_id = $1 ORDER BY changed_at, id"
+        )
+        .bind(series_id)
+        .fetch_all(self.pool())
+        .await?;
+        Ok(changes)
+    }
+
+    async fn find_orphaned_instances(&self) -> Result<Vec<Task>, CoreError> {
+        let tasks = sqlx::query_as(
+            r#"SELECT * FROM tasks
+            WHERE series_id IS NOT NULL AND series_id NOT IN (SELECT id FROM task_series)
+            ORDER BY created_at, id"#
+        )
+        .fetch_all(self.pool())
+        .await?;
+        Ok(tasks)
+    }
+
+    async fn detach_orphaned_instances(&self) -> Result<Vec<Task>, CoreError> {
+        let mut tasks: Vec<Task> = sqlx::query_as(
+            r#"UPDATE tasks SET series_id = NULL, updated_at = $1
+            WHERE series_id IS NOT NULL AND series_id NOT IN (SELECT id FROM task_series)
+            RETURNING *"#
+        )
+        .bind(self.now())
+        .fetch_all(self.pool())
+        .await?;
+        tasks.sort_by_key(|t| (t.created_at, t.id));
+        Ok(tasks)
+    }
+}
+
+impl PostgresRepository {
+    /// Create a series within an existing transaction
+    pub(crate) async fn create_series_in_transaction(
+        tx: &mut Transaction<'_, Postgres>,
+        data: NewSeriesData,
+        now: DateTime<Utc>,
+    ) -> Result<TaskSeries, CoreError> {
+        let normalized_rrule = RecurrenceManager::normalize_series_rrule(
+            &data.rrule,
+            data.dtstart,
+            &data.timezone,
+            data.time_mode
+        )?;
+
+        let template_task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = $1")
+            .bind(data.template_task_id)
+            .fetch_optional(&mut **tx)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(data.template_task_id.to_string()))?;
+
+        if template_task.series_id.is_some() {
+            return Err(CoreError::InvalidInput(
+                "Template task is already part of a series".to_string()
+            ));
+        }
+
+        let existing_series: Option<TaskSeries> = sqlx::query_as(
+            "SELECT * FROM task_series WHERE template_task_id = $1"
+        )
+        .bind(data.template_task_id)
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        if existing_series.is_some() {
+            return Err(CoreError::InvalidInput(
+                "A series already exists for this template task".to_string()
+            ));
+        }
+
+        let series = TaskSeries {
+            id: Uuid::now_v7(),
+            template_task_id: data.template_task_id,
+            rrule: normalized_rrule,
+            dtstart: data.dtstart,
+            timezone: data.timezone,
+            time_mode: data.time_mode,
+            dst_policy: data.dst_policy,
+            active: true,
+            last_materialized_until: None,
+            created_at: now,
+            updated_at: now,
+        };
+
+        sqlx::query(
+            r#"INSERT INTO task_series (id, template_task_id, rrule, dtstart, timezone, time_mode, dst_gap, dst_overlap, active, last_materialized_until, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)"#
+        )
+        .bind(series.id)
+        .bind(series.template_task_id)
+        .bind(&series.rrule)
+        .bind(series.dtstart)
+        .bind(&series.timezone)
+        .bind(series.time_mode)
+        .bind(series.dst_policy.dst_gap)
+        .bind(series.dst_policy.dst_overlap)
+        .bind(series.active)
+        .bind(series.last_materialized_until)
+        .bind(series.created_at)
+        .bind(series.updated_at)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(series)
+    }
+
+    /// Update a series within an existing transaction
+    pub(crate) async fn update_series_in_transaction(
+        tx: &mut Transaction<'_, Postgres>,
+        series_id: Uuid,
+        mut data: UpdateSeriesData,
+        now: DateTime<Utc>,
+    ) -> Result<TaskSeries, CoreError> {
+        let current_series: TaskSeries = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
+            .bind(series_id)
+            .fetch_optional(&mut **tx)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Series with id {} not found", series_id)))?;
+
+        // A new timezone alone keeps occurrences at their times of day
+        if let Some((from, to)) = timezone_move(&current_series, &mut data)? {
+            Self::shift_wall_clock_times(tx, &current_series, from, to, now).await?;
+        }
+
+        let mut qb: QueryBuilder<Postgres> = QueryBuilder::new("UPDATE task_series SET updated_at = ");
+        qb.push_bind(now);
+        let mut updated = false;
+
+        if let Some(rrule) = &data.rrule {
+            qb.push(", rrule = ");
+            qb.push_bind(rrule);
+            updated = true;
+        }
+
+        if let Some(dtstart) = data.dtstart {
+            qb.push(", dtstart = ");
+            qb.push_bind(dtstart);
+            updated = true;
+        }
+
+        if let Some(timezone) = &data.timezone {
+            qb.push(", timezone = ");
+            qb.push_bind(timezone);
+            updated = true;
+        }
+
+        if let Some(active) = data.active {
+            qb.push(", active = ");
+            qb.push_bind(active);
+            updated = true;
+        }
+
+        if let Some(policy) = data.dst_policy {
+            qb.push(", dst_gap = ");
+            qb.push_bind(policy.dst_gap);
+            qb.push(", dst_overlap = ");
+            qb.push_bind(policy.dst_overlap);
+            updated = true;
+        }
+
+        if updated {
+            // Reset materialization boundary if the schedule changed
+            if data.rrule.is_some() || data.timezone.is_some() || data.dtstart.is_some() || data.dst_policy.is_some() {
+                qb.push(", last_materialized_until = NULL");
+            }
+            qb.push(" WHERE id = ");
+            qb.push_bind(series_id);
+
+            let result = qb.build().execute(&mut **tx).await?;
+            if result.rows_affected() == 0 {
+                return Err(CoreError::NotFound(format!("Series with id {} not found", series_id)));
+            }
+        }
+
+        let updated_series: TaskSeries = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
+            .bind(series_id)
+            .fetch_one(&mut **tx)
+            .await?;
+        Self::record_rule_change(tx, &current_series, &updated_series, now).await?;
+
+        Ok(updated_series)
+    }
+
+    /// Record the edit of a series' schedule when `after` changed it
+    pub(crate) async fn record_rule_change(
+        tx: &mut Transaction<'_, Postgres>,
+        before: &TaskSeries,
+        after: &TaskSeries,
+        now: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        let Some(change) = SeriesRuleChange::between(before, after, now) else {
+            return Ok(());
+        };
+        sqlx::query(
+            r#"INSERT INTO series_rule_changes (id, series_id, changed_at, old_rrule, new_rrule, old_dtstart, new_dtstart, old_timezone, new_timezone)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"#
+        )
+        .bind(change.id)
+        .bind(change.series_id)
+        .bind(change.changed_at)
+        .bind(&change.old_rrule)
+        .bind(&change.new_rrule)
+        .bind(change.old_dtstart)
+        .bind(change.new_dtstart)
+        .bind(&change.old_timezone)
+        .bind(&change.new_timezone)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    /// Delete `series` with its template, instances and exceptions
+    ///
+    /// With `keep_history` the completed instances stay as regular tasks.
+    pub(crate) async fn delete_whole_series(
+        tx: &mut Transaction<'_, Postgres>,
+        series: &TaskSeries,
+        keep_history: bool,
+    ) -> Result<(), CoreError> {
+        if keep_history {
+            sqlx::query("UPDATE tasks SET series_id = NULL WHERE series_id = $1 AND status = $2")
+                .bind(series.id)
+                .bind(TaskStatus::Completed)
+                .execute(&mut **tx)
+                .await?;
+        }
+        // Moved occurrences have left the series but go with it all the same
+        sqlx::query(
+            r#"DELETE FROM tasks WHERE id IN (SELECT exception_task_id FROM series_exceptions WHERE series_id = $1)
+            AND NOT ($2 AND status = $3)"#
+        )
+        .bind(series.id)
+        .bind(keep_history)
+        .bind(TaskStatus::Completed)
+        .execute(&mut **tx)
+        .await?;
+
+        // Exceptions and rule changes go with the series; instances have to be removed explicitly
+        // because their foreign key only nulls out series_id
+        for query in ["DELETE FROM tasks WHERE series_id = $1", "DELETE FROM task_series WHERE id = $1"] {
+            sqlx::query(query).bind(series.id).execute(&mut **tx).await?;
+        }
+        sqlx::query("DELETE FROM tasks WHERE id = $1")
+            .bind(series.template_task_id)
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+
+    /// End `series` before `cut`, deleting the occurrences from `cut` on
+    /// along with their exceptions and notes
+    pub(crate) async fn end_series_before(
+        tx: &mut Transaction<'_, Postgres>,
+        series: &TaskSeries,
+        cut: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        let rrule = truncated_rrule(series, cut)?;
+
+        // Moved occurrences go by the time they were scheduled for
+        sqlx::query(
+            r#"DELETE FROM tasks WHERE id IN (
+                SELECT exception_task_id FROM series_exceptions WHERE series_id = $1 AND occurrence_dt >= $2
+            )"#
+        )
+        .bind(series.id)
+        .bind(cut)
+        .execute(&mut **tx)
+        .await?;
+        for query in [
+            "DELETE FROM series_exceptions WHERE series_id = $1 AND occurrence_dt >= $2",
+            "DELETE FROM annotations WHERE series_id = $1 AND occurrence_dt >= $2",
+            "DELETE FROM tasks WHERE series_id = $1 AND due_at >= $2",
+        ] {
+            sqlx::query(query).bind(series.id).bind(cut).execute(&mut **tx).await?;
+        }
+
+        let data = UpdateSeriesData { rrule: Some(rrule), ..Default::default() };
+        Self::update_series_in_transaction(tx, series.id, data, now).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,1194 @@
+use crate::error::CoreError;
+use crate::forecast;
+use crate::models::{
+    Annotation, BulkAction, CompletionResult, EditScope, ExceptionType, NewSeriesData, NewSeriesException, NewTaskData, Project,
+    SeriesException, SubtaskPolicy, Task,
+    TaskPriority, TaskRelations, TaskSeries, TaskStatus, TimeEntry, TimeMode, UpdateSeriesData, UpdateTaskData,
+};
+use crate::query::Query;
+use crate::recurrence::RecurrenceManager;
+use crate::repository::query_builder::SqlQueryBuilder;
+use crate::repository::{PostgresRepository, Repository, SeriesRepository, TaskQueryResult};
+use crate::workflow;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, QueryBuilder, Transaction};
+use uuid::Uuid;
+
+#[async_trait]
+impl crate::repository::TaskRepository for PostgresRepository {
+    async fn add_task(&self, data: NewTaskData) -> Result<Task, CoreError> {
+        let data = data.anchored(self.local_timezone());
+        let mut tx = self.pool().begin().await?;
+        let task = self.add_task_or_series_in_transaction(&mut tx, data).await?;
+        tx.commit().await?;
+        Ok(task)
+    }
+
+    async fn find_task_by_id(&self, id: Uuid) -> Result<Option<Task>, CoreError> {
+        let task = sqlx::query_as("SELECT * FROM tasks WHERE id = $1")
+            .bind(id)
+            .fetch_optional(self.pool())
+            .await?;
+        Ok(task)
+    }
+
+    async fn find_tasks_by_short_id_prefix(&self, short_id: &str) -> Result<Vec<Task>, CoreError> {
+        // Compare against the undashed text form so prefixes may span a dash
+        let mut pattern = String::with_capacity(short_id.len() + 1);
+        pattern.extend(short_id.chars().filter(|c| *c != '-').map(|c| c.to_ascii_lowercase()));
+        pattern.push('%');
+
+        let tasks: Vec<Task> = sqlx::query_as("SELECT * FROM tasks WHERE replace(id::text, '-', '') LIKE $1")
+            .bind(pattern)
+            .fetch_all(self.pool())
+            .await?;
+        Ok(tasks)
+    }
+
+    async fn find_tasks_with_details(&self, query: &Query) -> Result<Vec<TaskQueryResult>, CoreError> {
+        // ALWAYS ensure materialization before any query to prevent missing recurring tasks
+        self.ensure_materialization_for_query(query).await?;
+
+        // Paths are built from fixed-width UTC timestamps so that ordering by
+        // path lists every subtree right after its parent, oldest first
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(
+            r#"WITH RECURSIVE task_hierarchy (id, name, description, status, priority, due_at, completed_at, created_at, updated_at, project_id, parent_id, series_id, workflow_status, estimate_minutes, scheduled_at, wait_until, start_at, all_day, depth, path) AS (
+                SELECT
+                    t.id, t.name, t.description, t.status, t.priority, t.due_at, t.completed_at, t.created_at, t.updated_at, t.project_id, t.parent_id, t.series_id, t.workflow_status, t.estimate_minutes, t.scheduled_at, t.wait_until, t.start_at, t.all_day,
+                    0 AS depth,
+                    to_char(t.created_at AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS.US') AS path
+                FROM tasks t
+                WHERE t.parent_id IS NULL
+                UNION ALL
+                SELECT
+                    t.id, t.name, t.description, t.status, t.priority, t.due_at, t.completed_at, t.created_at, t.updated_at, t.project_id, t.parent_id, t.series_id, t.workflow_status, t.estimate_minutes, t.scheduled_at, t.wait_until, t.start_at, t.all_day,
+                    th.depth + 1,
+                    th.path || ' -> ' || to_char(t.created_at AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS.US')
+                FROM tasks t
+                JOIN task_hierarchy th ON t.parent_id = th.id
+            )
+            SELECT
+                th.id, th.name, th.description, th.status, th.priority, th.due_at, th.completed_at, th.created_at, th.updated_at, th.project_id, th.parent_id, th.series_id, th.workflow_status, th.estimate_minutes, th.scheduled_at, th.wait_until, th.start_at, th.all_day, th.depth, th.path,
+                p.name AS project_name,
+                string_agg(tt.tag_name, ',' ORDER BY tt.tag_name) AS tags,
+                (SELECT COALESCE(SUM(te.duration_seconds), 0)::BIGINT FROM time_entries te WHERE te.task_id = th.id) AS tracked_seconds,
+                EXISTS (SELECT 1 FROM task_dependencies d JOIN tasks dt ON dt.id = d.depends_on_id WHERE d.task_id = th.id AND dt.status = 'pending') AS is_blocked,
+                EXISTS (SELECT 1 FROM task_dependencies d JOIN tasks dt ON dt.id = d.task_id WHERE d.depends_on_id = th.id AND dt.status = 'pending') AS is_blocking,
+                (WITH RECURSIVE subtree (id, status) AS (
+                    SELECT c.id, c.status FROM tasks c WHERE c.parent_id = th.id
+                    UNION
+                    SELECT c.id, c.status FROM tasks c JOIN subtree s ON c.parent_id = s.id
+                ) SELECT COUNT(*) FROM subtree WHERE status != 'cancelled') AS subtask_count,
+                (WITH RECURSIVE subtree (id, status) AS (
+                    SELECT c.id, c.status FROM tasks c WHERE c.parent_id = th.id
+                    UNION
+                    SELECT c.id, c.status FROM tasks c JOIN subtree s ON c.parent_id = s.id
+                ) SELECT COUNT(*) FROM subtree WHERE status = 'completed') AS completed_subtask_count
+            FROM task_hierarchy th
+            LEFT JOIN projects p ON th.project_id = p.id
+            LEFT JOIN task_tags tt ON th.id = tt.task_id
+            "#,
+        );
+
+        query_builder.push(" WHERE ");
+        SqlQueryBuilder::build_sql_where_clause(query, self.now(), self.local_timezone(), &mut query_builder);
+
+        query_builder.push(" GROUP BY th.id, th.name, th.description, th.status, th.priority, th.due_at, th.completed_at, th.created_at, th.updated_at, th.project_id, th.parent_id, th.series_id, th.workflow_status, th.estimate_minutes, th.scheduled_at, th.wait_until, th.start_at, th.all_day, th.depth, th.path, p.name");
+        query_builder.push(" ORDER BY th.path");
+
+        let tasks = query_builder.build_query_as().fetch_all(self.pool()).await?;
+        Ok(tasks)
+    }
+
+    async fn delete_task(&self, id: Uuid) -> Result<(), CoreError> {
+        let mut tx = self.pool().begin().await?;
+        if !Self::delete_task_in_transaction(&mut tx, id).await? {
+            return Err(CoreError::NotFound(id.to_string()));
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn delete_task_with_scope(&self, id: Uuid, scope: EditScope, keep_history: bool) -> Result<(), CoreError> {
+        let mut tx = self.pool().begin().await?;
+        if let Err(e) = self.delete_in_scope(&mut tx, id, scope, keep_history).await {
+            tx.rollback().await?;
+            return Err(e);
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn complete_task(&self, id: Uuid) -> Result<CompletionResult, CoreError> {
+        self.complete_task_as(id, TaskStatus::Completed.as_str()).await
+    }
+
+    async fn cancel_task(&self, id: Uuid) -> Result<Task, CoreError> {
+        self.cancel_task_as(id, TaskStatus::Cancelled.as_str()).await
+    }
+
+    async fn update_task(&self, id: Uuid, data: UpdateTaskData, scope: Option<EditScope>) -> Result<Task, CoreError> {
+        let mut tx = self.pool().begin().await?;
+
+        let current_task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(id.to_string()))?;
+
+        // Dispatch based on series membership and edit scope
+        let result = match (current_task.series_id, scope.unwrap_or(EditScope::ThisOccurrence)) {
+            (None, _) => self.update_regular_task(&mut tx, id, &data).await,
+            (Some(_), EditScope::ThisOccurrence) => self.update_single_occurrence(&mut tx, id, &data).await,
+            (Some(_), EditScope::ThisAndFuture) => self.update_series_from_future(&mut tx, &current_task, &data).await,
+            (Some(_), EditScope::EntireSeries) => self.update_entire_series(&mut tx, &current_task, &data).await,
+        };
+
+        if let Err(e) = result {
+            tx.rollback().await?;
+            return Err(e);
+        }
+
+        // Series-wide edits replace the edited instance; report the template then
+        let updated_task: Option<Task> = sqlx::query_as("SELECT * FROM tasks WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        let updated_task = match updated_task {
+            Some(task) => task,
+            None => sqlx::query_as(
+                "SELECT t.* FROM tasks t JOIN task_series s ON s.template_task_id = t.id WHERE s.id = $1"
+            )
+            .bind(current_task.series_id)
+            .fetch_one(&mut *tx)
+            .await?,
+        };
+
+        tx.commit().await?;
+        Ok(updated_task)
+    }
+
+    async fn bulk_update_tasks(&self, ids: &[Uuid], action: BulkAction) -> Result<Vec<Task>, CoreError> {
+        let action = crate::repository::checked_bulk_action(action)?;
+        let mut tx = self.pool().begin().await?;
+
+        match self.bulk_update_in_transaction(&mut tx, ids, &action).await {
+            Ok(tasks) => {
+                tx.commit().await?;
+                Ok(tasks)
+            }
+            Err(e) => {
+                tx.rollback().await?;
+                Err(e)
+            }
+        }
+    }
+
+    async fn find_task_relations(&self, id: Uuid) -> Result<TaskRelations, CoreError> {
+        let task = self.find_task_by_id(id).await?.ok_or_else(|| CoreError::NotFound(id.to_string()))?;
+
+        let mut ancestors: Vec<Task> = Vec::new();
+        let mut parent_id = task.parent_id;
+        while let Some(pid) = parent_id {
+            if pid == id || ancestors.iter().any(|a| a.id == pid) {
+                break;
+            }
+            let Some(parent) = self.find_task_by_id(pid).await? else {
+                break;
+            };
+            parent_id = parent.parent_id;
+            ancestors.push(parent);
+        }
+
+        let subtasks = sqlx::query_as("SELECT * FROM tasks WHERE parent_id = $1 ORDER BY created_at, id")
+            .bind(id)
+            .fetch_all(self.pool())
+            .await?;
+        let depends_on = sqlx::query_as(
+            "SELECT t.* FROM tasks t JOIN task_dependencies d ON d.depends_on_id = t.id WHERE d.task_id = $1 ORDER BY t.created_at, t.id"
+        )
+        .bind(id)
+        .fetch_all(self.pool())
+        .await?;
+        let dependents = sqlx::query_as(
+            "SELECT t.* FROM tasks t JOIN task_dependencies d ON d.task_id = t.id WHERE d.depends_on_id = $1 ORDER BY t.created_at, t.id"
+        )
+        .bind(id)
+        .fetch_all(self.pool())
+        .await?;
+
+        Ok(TaskRelations { ancestors, subtasks, depends_on, dependents })
+    }
+}
+
+impl PostgresRepository {
+    /// Delete a task, taking the series of a template with it; `false` if
+    /// there was no such task
+    pub(crate) async fn delete_task_in_transaction(tx: &mut Transaction<'_, Postgres>, id: Uuid) -> Result<bool, CoreError> {
+        let series: Option<TaskSeries> = sqlx::query_as("SELECT * FROM task_series WHERE template_task_id = $1")
+            .bind(id)
+            .fetch_optional(&mut **tx)
+            .await?;
+        if let Some(series) = series {
+            Self::delete_whole_series(tx, &series, true).await?;
+            return Ok(true);
+        }
+
+        let result = sqlx::query("DELETE FROM tasks WHERE id = $1")
+            .bind(id)
+            .execute(&mut **tx)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Delete a task and the occurrences of its series that `scope` covers,
+    /// see [`TaskRepository::delete_task_with_scope`](crate::repository::TaskRepository::delete_task_with_scope)
+    async fn delete_in_scope(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        id: Uuid,
+        scope: EditScope,
+        keep_history: bool,
+    ) -> Result<(), CoreError> {
+        let task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&mut **tx)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(id.to_string()))?;
+        // Moved occurrences leave the series; their exception still knows it
+        // and the time they were scheduled for
+        let exception: Option<SeriesException> = sqlx::query_as("SELECT * FROM series_exceptions WHERE exception_task_id = $1")
+            .bind(id)
+            .fetch_optional(&mut **tx)
+            .await?;
+        let series: Option<TaskSeries> = sqlx::query_as("SELECT * FROM task_series WHERE id = $1 OR template_task_id = $2")
+            .bind(task.series_id.or(exception.as_ref().map(|e| e.series_id)))
+            .bind(id)
+            .fetch_optional(&mut **tx)
+            .await?;
+        let Some(series) = series else {
+            Self::delete_task_in_transaction(tx, id).await?;
+            return Ok(());
+        };
+        if series.template_task_id == id {
+            return Self::delete_whole_series(tx, &series, keep_history).await;
+        }
+        let occurrence_dt = exception.map(|e| e.occurrence_dt).or(task.due_at);
+
+        match (scope, occurrence_dt) {
+            (EditScope::ThisOccurrence, Some(occurrence_dt)) => {
+                Self::delete_task_in_transaction(tx, id).await?;
+                sqlx::query("DELETE FROM series_exceptions WHERE series_id = $1 AND occurrence_dt = $2")
+                    .bind(series.id)
+                    .bind(occurrence_dt)
+                    .execute(&mut **tx)
+                    .await?;
+                let skip = NewSeriesException {
+                    series_id: series.id,
+                    occurrence_dt,
+                    exception_type: ExceptionType::Skip,
+                    exception_task_id: None,
+                    notes: None,
+                };
+                Self::add_series_exception_in_transaction(tx, skip, self.now()).await?;
+                Ok(())
+            }
+            (EditScope::ThisOccurrence, None) => {
+                Self::delete_task_in_transaction(tx, id).await?;
+                Ok(())
+            }
+            (EditScope::ThisAndFuture, Some(cut)) if cut > series.dtstart => {
+                Self::end_series_before(tx, &series, cut, self.now()).await
+            }
+            _ => Self::delete_whole_series(tx, &series, keep_history).await,
+        }
+    }
+
+    /// Complete a task, recording `workflow_status` as its completed-category status
+    pub(crate) async fn complete_task_as(&self, id: Uuid, workflow_status: &str) -> Result<CompletionResult, CoreError> {
+        let mut tx = self.pool().begin().await?;
+        let result = self.complete_task_in_transaction(&mut tx, id, workflow_status).await?;
+        tx.commit().await?;
+        Ok(result)
+    }
+
+    /// Complete a task inside `tx`, if the workflow lets it move into `workflow_status`
+    pub(crate) async fn complete_task_in_transaction(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        id: Uuid,
+        workflow_status: &str,
+    ) -> Result<CompletionResult, CoreError> {
+        let task = Self::lock_task_in_transaction(tx, id)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(id.to_string()))?;
+        let transitions = Self::find_workflow_transitions_in_transaction(tx).await?;
+        workflow::check_status_change(&task, workflow_status, &transitions)?;
+
+        // Check for blocking dependencies
+        let dependencies: Vec<Task> = sqlx::query_as(
+            r#"SELECT t.* FROM tasks t
+            INNER JOIN task_dependencies td ON t.id = td.depends_on_id
+            WHERE td.task_id = $1 AND t.status != 'completed'"#,
+        )
+        .bind(id)
+        .fetch_all(&mut **tx)
+        .await?;
+
+        if !dependencies.is_empty() {
+            let dependency_names = dependencies
+                .iter()
+                .map(|t| t.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(CoreError::TaskBlocked(dependency_names));
+        }
+
+        self.complete_subtasks(tx, id, &[], self.now()).await?;
+
+        let completed_task: Task = sqlx::query_as(
+            r#"UPDATE tasks
+            SET status = $1, workflow_status = $2, completed_at = $3, updated_at = $3
+            WHERE id = $4
+            RETURNING *
+            "#,
+        )
+        .bind(TaskStatus::Completed)
+        .bind(workflow_status)
+        .bind(self.now())
+        .bind(id)
+        .fetch_one(&mut **tx)
+        .await?;
+        self.complete_finished_parents(tx, &task, self.now()).await?;
+
+        let Some(series_id) = task.series_id else {
+            return Ok(CompletionResult::Single(completed_task));
+        };
+
+        let series: TaskSeries = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
+            .bind(series_id)
+            .fetch_optional(&mut **tx)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Series with id {} not found", series_id)))?;
+
+        let template_task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = $1")
+            .bind(series.template_task_id)
+            .fetch_optional(&mut **tx)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Template task with id {} not found", series.template_task_id)))?;
+
+        let exceptions: Vec<SeriesException> = sqlx::query_as(
+            "SELECT * FROM series_exceptions WHERE series_id = $1"
+        )
+        .bind(series_id)
+        .fetch_all(&mut **tx)
+        .await?;
+
+        let recurrence_manager = RecurrenceManager::new(series, template_task, exceptions)?;
+        let next_occurrence = recurrence_manager.next_occurrence_after(completed_task.due_at.unwrap_or_else(|| self.now()))?;
+
+        // Materialize the next occurrence if it falls inside the lookahead window
+        let next_task = match next_occurrence {
+            Some(next_due) => {
+                let (window_start, window_end) = self.materialization_manager().calculate_window_for_filters(&[]);
+                if next_due >= window_start && next_due <= window_end {
+                    Self::refresh_single_series_materialization_in_transaction(
+                        tx,
+                        series_id,
+                        next_due - chrono::Duration::minutes(1),
+                        next_due + chrono::Duration::minutes(1),
+                        self.now(),
+                    ).await?;
+
+                    sqlx::query_as("SELECT * FROM tasks WHERE series_id = $1 AND due_at = $2")
+                        .bind(series_id)
+                        .bind(next_due)
+                        .fetch_optional(&mut **tx)
+                        .await?
+                } else {
+                    None
+                }
+            }
+            None => None,
+        };
+
+        Ok(CompletionResult::SeriesInstance {
+            completed: completed_task,
+            next: next_task,
+            series_id,
+            next_occurrence,
+        })
+    }
+
+    /// Apply a bulk action to every task inside `tx`, failing on the first task that cannot change
+    async fn bulk_update_in_transaction(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        ids: &[Uuid],
+        action: &BulkAction,
+    ) -> Result<Vec<Task>, CoreError> {
+        let now = self.now();
+        let mut tasks = Vec::with_capacity(ids.len());
+        for &id in ids {
+            let task = Self::find_task_by_id_in_transaction(tx, id)
+                .await?
+                .ok_or_else(|| CoreError::NotFound(id.to_string()))?;
+            tasks.push(task);
+        }
+        let transitions = Self::find_workflow_transitions_in_transaction(tx).await?;
+
+        let mut completed_series: Vec<Uuid> = Vec::new();
+        for task in &tasks {
+            match action {
+                BulkAction::Complete => {
+                    workflow::check_status_change(task, TaskStatus::Completed.as_str(), &transitions)?;
+                    let dependencies: Vec<Task> = sqlx::query_as(
+                        r#"SELECT t.* FROM tasks t
+                        INNER JOIN task_dependencies td ON t.id = td.depends_on_id
+                        WHERE td.task_id = $1 AND t.status != 'completed'"#,
+                    )
+                    .bind(task.id)
+                    .fetch_all(&mut **tx)
+                    .await?;
+
+                    // Dependencies completed by the same batch do not block
+                    let blocking: Vec<&str> = dependencies
+                        .iter()
+                        .filter(|t| !ids.contains(&t.id))
+                        .map(|t| t.name.as_str())
+                        .collect();
+                    if !blocking.is_empty() {
+                        return Err(CoreError::TaskBlocked(blocking.join(", ")));
+                    }
+
+                    self.complete_subtasks(tx, task.id, ids, now).await?;
+                    sqlx::query(
+                        "UPDATE tasks SET status = $1, workflow_status = $2, completed_at = $3, updated_at = $3 WHERE id = $4",
+                    )
+                    .bind(TaskStatus::Completed)
+                    .bind(TaskStatus::Completed.as_str())
+                    .bind(now)
+                    .bind(task.id)
+                    .execute(&mut **tx)
+                    .await?;
+                    self.complete_finished_parents(tx, task, now).await?;
+
+                    if let Some(series_id) = task.series_id.filter(|id| !completed_series.contains(id)) {
+                        completed_series.push(series_id);
+                    }
+                }
+                BulkAction::Cancel => {
+                    workflow::check_status_change(task, TaskStatus::Cancelled.as_str(), &transitions)?;
+                    sqlx::query("UPDATE tasks SET status = $1, workflow_status = $2, updated_at = $3 WHERE id = $4")
+                        .bind(TaskStatus::Cancelled)
+                        .bind(TaskStatus::Cancelled.as_str())
+                        .bind(now)
+                        .bind(task.id)
+                        .execute(&mut **tx)
+                        .await?;
+                }
+                BulkAction::Delete => {
+                    // Subtasks of a task deleted earlier in the batch are already gone
+                    if Self::find_task_by_id_in_transaction(tx, task.id).await?.is_some() {
+                        // Occurrences are skipped, as deleting a single one does
+                        self.delete_in_scope(tx, task.id, EditScope::ThisOccurrence, true).await?;
+                    }
+                }
+                BulkAction::Update(data) => match task.series_id {
+                    None => self.update_regular_task(tx, task.id, data).await?,
+                    Some(_) => self.update_single_occurrence(tx, task.id, data).await?,
+                },
+                BulkAction::Move(status) => {
+                    self.move_task_to_status_in_transaction(tx, task.id, status).await?;
+                }
+                BulkAction::Annotate(text) => {
+                    let annotation = Annotation { id: Uuid::now_v7(), task_id: task.id, text: text.clone(), created_at: now };
+                    Self::insert_annotation_in_transaction(tx, &annotation).await?;
+                }
+                BulkAction::LogTime(duration) => {
+                    let entry = TimeEntry {
+                        id: Uuid::now_v7(),
+                        task_id: task.id,
+                        started_at: now - *duration,
+                        ended_at: Some(now),
+                        duration_seconds: Some(duration.num_seconds()),
+                    };
+                    Self::insert_time_entry_in_transaction(tx, &entry).await?;
+                }
+            }
+        }
+
+        if let BulkAction::Delete = action {
+            return Ok(tasks);
+        }
+
+        // Completed occurrences make room for the next ones of active series
+        let (window_start, window_end) = self.materialization_manager().calculate_window_for_filters(&[]);
+        for series_id in completed_series {
+            let series: Option<TaskSeries> = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
+                .bind(series_id)
+                .fetch_optional(&mut **tx)
+                .await?;
+            if series.is_some_and(|s| s.active) {
+                Self::refresh_single_series_materialization_in_transaction(tx, series_id, window_start, window_end, now).await?;
+            }
+        }
+
+        let mut updated = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            let task = Self::find_task_by_id_in_transaction(tx, task.id)
+                .await?
+                .ok_or_else(|| CoreError::NotFound(task.id.to_string()))?;
+            updated.push(task);
+        }
+        Ok(updated)
+    }
+
+    /// Apply the subtask policy before `task_id` is completed: refuse while
+    /// subtasks outside `batch` are pending, or complete them along with it
+    async fn complete_subtasks(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        task_id: Uuid,
+        batch: &[Uuid],
+        now: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        let policy = self.completion_policy().subtasks;
+        if policy == SubtaskPolicy::Allow {
+            return Ok(());
+        }
+
+        let pending: Vec<Task> = sqlx::query_as(
+            r#"WITH RECURSIVE subtree (id) AS (
+                SELECT id FROM tasks WHERE parent_id = $1
+                UNION
+                SELECT t.id FROM tasks t JOIN subtree s ON t.parent_id = s.id
+            )
+            SELECT t.* FROM tasks t JOIN subtree s ON s.id = t.id
+            WHERE t.status = 'pending'
+            ORDER BY t.created_at, t.id"#,
+        )
+        .bind(task_id)
+        .fetch_all(&mut **tx)
+        .await?;
+        let pending: Vec<Task> = pending.into_iter().filter(|t| !batch.contains(&t.id)).collect();
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        if policy == SubtaskPolicy::Block {
+            let names = pending.iter().map(|t| t.name.as_str()).collect::<Vec<_>>().join(", ");
+            return Err(CoreError::PendingSubtasks(names));
+        }
+        for subtask in pending {
+            sqlx::query(
+                "UPDATE tasks SET status = $1, workflow_status = $2, completed_at = $3, updated_at = $3 WHERE id = $4",
+            )
+            .bind(TaskStatus::Completed)
+            .bind(TaskStatus::Completed.as_str())
+            .bind(now)
+            .bind(subtask.id)
+            .execute(&mut **tx)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Complete the ancestors of a just completed `task` that are left without
+    /// pending subtasks or dependencies, when the policy asks for it
+    async fn complete_finished_parents(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        task: &Task,
+        now: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        if !self.completion_policy().complete_parent {
+            return Ok(());
+        }
+
+        let mut visited = vec![task.id];
+        let mut parent_id = task.parent_id;
+        while let Some(id) = parent_id.filter(|id| !visited.contains(id)) {
+            visited.push(id);
+            let Some(parent) = Self::find_task_by_id_in_transaction(tx, id).await? else {
+                break;
+            };
+            let unfinished: i64 = sqlx::query_scalar(
+                r#"SELECT
+                    (SELECT COUNT(*) FROM tasks WHERE parent_id = $1 AND status = 'pending')
+                    + (SELECT COUNT(*) FROM task_dependencies td JOIN tasks t ON t.id = td.depends_on_id
+                       WHERE td.task_id = $1 AND t.status != 'completed')"#,
+            )
+            .bind(id)
+            .fetch_one(&mut **tx)
+            .await?;
+            if parent.status != TaskStatus::Pending || unfinished > 0 {
+                break;
+            }
+
+            sqlx::query(
+                "UPDATE tasks SET status = $1, workflow_status = $2, completed_at = $3, updated_at = $3 WHERE id = $4",
+            )
+            .bind(TaskStatus::Completed)
+            .bind(TaskStatus::Completed.as_str())
+            .bind(now)
+            .bind(id)
+            .execute(&mut **tx)
+            .await?;
+            parent_id = parent.parent_id;
+        }
+        Ok(())
+    }
+
+    /// Cancel a task, recording `workflow_status` as its cancelled-category status
+    pub(crate) async fn cancel_task_as(&self, id: Uuid, workflow_status: &str) -> Result<Task, CoreError> {
+        let mut tx = self.pool().begin().await?;
+        let task = Self::cancel_task_in_transaction(&mut tx, id, workflow_status, self.now()).await?;
+        tx.commit().await?;
+        Ok(task)
+    }
+
+    /// Cancel a task inside `tx`, if the workflow lets it move into `workflow_status`
+    pub(crate) async fn cancel_task_in_transaction(
+        tx: &mut Transaction<'_, Postgres>,
+        id: Uuid,
+        workflow_status: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Task, CoreError> {
+        let task = Self::lock_task_in_transaction(tx, id)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(id.to_string()))?;
+        let transitions = Self::find_workflow_transitions_in_transaction(tx).await?;
+        workflow::check_status_change(&task, workflow_status, &transitions)?;
+
+        let updated_task: Task = sqlx::query_as(
+            r#"UPDATE tasks
+            SET status = $1, workflow_status = $2, updated_at = $3
+            WHERE id = $4
+            RETURNING *
+            "#,
+        )
+        .bind(TaskStatus::Cancelled)
+        .bind(workflow_status)
+        .bind(now)
+        .bind(id)
+        .fetch_optional(&mut **tx)
+        .await?
+        .ok_or_else(|| CoreError::NotFound(id.to_string()))?;
+
+        Ok(updated_task)
+    }
+}
+
+impl PostgresRepository {
+    /// Updates a regular (non-series) task with validation
+    async fn update_regular_task(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        task_id: Uuid,
+        data: &UpdateTaskData,
+    ) -> Result<(), CoreError> {
+        if data.rrule.is_some() || data.timezone.is_some() {
+            return Err(CoreError::InvalidInput(
+                "Cannot add recurrence to existing task. Create a new recurring task instead".to_string()
+            ));
+        }
+
+        Self::update_task_fields(tx, task_id, data, self.now()).await
+    }
+
+    /// Updates a single task occurrence with validation
+    async fn update_single_occurrence(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        task_id: Uuid,
+        data: &UpdateTaskData,
+    ) -> Result<(), CoreError> {
+        if data.rrule.is_some() || data.timezone.is_some() {
+            return Err(CoreError::InvalidInput(
+                "Cannot modify recurrence for single occurrence. Use EditScope::ThisAndFuture or EditScope::EntireSeries".to_string()
+            ));
+        }
+
+        Self::update_task_fields(tx, task_id, data, self.now()).await
+    }
+
+    /// Updates series and re-materializes instances from a specific point forward
+    async fn update_series_from_future(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        current_task: &Task,
+        data: &UpdateTaskData,
+    ) -> Result<(), CoreError> {
+        let series_id = current_task.series_id.unwrap();
+
+        self.update_series_metadata(series_id, data).await?;
+        self.update_template_task(tx, current_task, data).await?;
+
+        if let Some(due_at) = current_task.due_at {
+            Self::clean_series_instances(tx, series_id, Some(due_at)).await?;
+            // Reset to one day before the boundary to trigger re-materialization
+            sqlx::query("UPDATE task_series SET last_materialized_until = $1 WHERE id = $2")
+                .bind(due_at - chrono::Duration::days(1))
+                .bind(series_id)
+                .execute(&mut **tx)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Updates entire series and re-materializes all instances
+    async fn update_entire_series(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        current_task: &Task,
+        data: &UpdateTaskData,
+    ) -> Result<(), CoreError> {
+        let series_id = current_task.series_id.unwrap();
+        self.update_series_metadata(series_id, data).await?;
+        self.update_template_task(tx, current_task, data).await?;
+
+        Self::clean_series_instances(tx, series_id, None).await?;
+        sqlx::query("UPDATE task_series SET last_materialized_until = NULL WHERE id = $1")
+            .bind(series_id)
+            .execute(&mut **tx)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Updates series recurrence metadata (rrule, timezone)
+    async fn update_series_metadata(&self, series_id: Uuid, data: &UpdateTaskData) -> Result<(), CoreError> {
+        if data.rrule.is_some() || data.timezone.is_some() {
+            let mut series_update = UpdateSeriesData::default();
+            if let Some(rrule) = &data.rrule {
+                series_update.rrule = rrule.clone();
+            }
+            if let Some(timezone) = &data.timezone {
+                series_update.timezone = timezone.clone();
+            }
+            self.update_series(series_id, series_update).await?;
+        }
+        Ok(())
+    }
+
+    /// Updates template task with non-recurrence fields
+    async fn update_template_task(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        current_task: &Task,
+        data: &UpdateTaskData,
+    ) -> Result<(), CoreError> {
+        let series: TaskSeries = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
+            .bind(current_task.series_id)
+            .fetch_one(&mut **tx)
+            .await?;
+
+        let template: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = $1")
+            .bind(series.template_task_id)
+            .fetch_one(&mut **tx)
+            .await?;
+
+        // Scheduling dates given for this occurrence keep their distance from due
+        let mut template_update = match template.due_at {
+            Some(due_at) => data.clone().dates_relative_to(current_task, due_at),
+            None => data.clone(),
+        };
+        template_update.rrule = None;
+        template_update.timezone = None;
+
+        Self::update_task_fields(tx, series.template_task_id, &template_update, self.now()).await
+    }
+
+    /// Deletes the instances of a series due at or after `from`, or all of
+    /// them when `from` is `None`. The template task is always kept.
+    async fn clean_series_instances(
+        tx: &mut Transaction<'_, Postgres>,
+        series_id: Uuid,
+        from: Option<DateTime<Utc>>,
+    ) -> Result<(), CoreError> {
+        let series: TaskSeries = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
+            .bind(series_id)
+            .fetch_one(&mut **tx)
+            .await?;
+
+        Self::detach_instance_annotations(tx, series_id, series.template_task_id, from).await?;
+
+        let mut qb: QueryBuilder<Postgres> = QueryBuilder::new("DELETE FROM tasks WHERE series_id = ");
+        qb.push_bind(series_id);
+        qb.push(" AND id != ");
+        qb.push_bind(series.template_task_id);
+        if let Some(from) = from {
+            qb.push(" AND due_at >= ");
+            qb.push_bind(from);
+        }
+        qb.build().execute(&mut **tx).await?;
+
+        Ok(())
+    }
+
+    /// Add a task within an existing transaction, creating and materializing
+    /// its series when it has an RRULE; returns the template in that case
+    pub(crate) async fn add_task_or_series_in_transaction(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        data: NewTaskData,
+    ) -> Result<Task, CoreError> {
+        let Some(rrule) = &data.rrule else {
+            return Self::add_task_in_transaction(tx, data, self.now()).await;
+        };
+
+        // Create the template task first, then the series that owns it
+        let mut template_data = data.clone();
+        template_data.rrule = None;
+        template_data.series_id = None;
+
+        let template_task = Self::add_task_in_transaction(tx, template_data, self.now()).await?;
+
+        let series_data = NewSeriesData {
+            template_task_id: template_task.id,
+            rrule: rrule.clone(),
+            dtstart: data.due_at.unwrap_or_else(|| self.now()),
+            timezone: data.timezone.unwrap_or_else(|| "UTC".to_string()),
+            time_mode: data.time_mode,
+            dst_policy: data.dst_policy,
+        };
+        let series = Self::create_series_in_transaction(tx, series_data, self.now()).await?;
+
+        // Trigger initial materialization for lookahead window
+        let (window_start, window_end) = self.materialization_manager().calculate_window_for_filters(&[]);
+        Self::refresh_single_series_materialization_in_transaction(tx, series.id, window_start, window_end, self.now()).await?;
+
+        Ok(template_task)
+    }
+
+    /// Add a task within an existing transaction
+    pub(crate) async fn add_task_in_transaction(
+        tx: &mut Transaction<'_, Postgres>,
+        mut data: NewTaskData,
+        now: DateTime<Utc>,
+    ) -> Result<Task, CoreError> {
+        if data.project_id.is_none() {
+            if let Some(project_name) = &data.project_name {
+                let project: Option<Project> =
+                    sqlx::query_as("SELECT * FROM projects WHERE name = $1")
+                        .bind(project_name)
+                        .fetch_optional(&mut **tx)
+                        .await?;
+                data.project_id = Some(
+                    project
+                        .map(|p| p.id)
+                        .ok_or_else(|| CoreError::NotFound(project_name.clone()))?,
+                );
+            }
+        }
+
+        let estimate_minutes = data.estimate.map(forecast::estimate_minutes).transpose()?;
+
+        let task = Task {
+            id: Uuid::now_v7(),
+            name: data.name,
+            description: data.description,
+            status: TaskStatus::Pending,
+            priority: data.priority.unwrap_or(TaskPriority::None),
+            due_at: data.due_at,
+            completed_at: None,
+            created_at: now,
+            updated_at: now,
+            project_id: data.project_id,
+            parent_id: data.parent_id,
+            series_id: data.series_id,
+            workflow_status: TaskStatus::Pending.as_str().to_string(),
+            estimate_minutes,
+            scheduled_at: data.scheduled_at,
+            wait_until: data.wait_until,
+            start_at: data.start_at,
+            all_day: data.time_mode == TimeMode::AllDay,
+        };
+
+        sqlx::query(
+            r#"INSERT INTO tasks (id, name, description, status, priority, due_at, created_at, updated_at, project_id, parent_id, series_id, estimate_minutes, scheduled_at, wait_until, start_at, all_day)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+            "#,
+        )
+        .bind(task.id)
+        .bind(&task.name)
+        .bind(&task.description)
+        .bind(&task.status)
+        .bind(&task.priority)
+        .bind(task.due_at)
+        .bind(task.created_at)
+        .bind(task.updated_at)
+        .bind(task.project_id)
+        .bind(task.parent_id)
+        .bind(task.series_id)
+        .bind(task.estimate_minutes)
+        .bind(task.scheduled_at)
+        .bind(task.wait_until)
+        .bind(task.start_at)
+        .bind(task.all_day)
+        .execute(&mut **tx)
+        .await?;
+
+        if let Some(depends_on_id) = data.depends_on {
+            Self::add_dependency(tx, task.id, &task.name, depends_on_id).await?;
+        }
+
+        if let Some(remind_before) = data.remind_before {
+            Self::set_task_reminder_in_transaction(tx, task.id, remind_before, now).await?;
+        }
+
+        if !data.tags.is_empty() {
+            let mut query_builder: QueryBuilder<Postgres> =
+                QueryBuilder::new("INSERT INTO task_tags (task_id, tag_name) ");
+            query_builder.push_values(data.tags.iter(), |mut b, tag| {
+                b.push_bind(task.id).push_bind(tag);
+            });
+            query_builder.push(" ON CONFLICT DO NOTHING");
+            query_builder.build().execute(&mut **tx).await?;
+        }
+
+        Ok(task)
+    }
+
+    /// Find a task by ID within an existing transaction
+    pub(crate) async fn find_task_by_id_in_transaction(
+        tx: &mut Transaction<'_, Postgres>,
+        id: Uuid,
+    ) -> Result<Option<Task>, CoreError> {
+        let task = sqlx::query_as("SELECT * FROM tasks WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&mut **tx)
+            .await?;
+        Ok(task)
+    }
+
+    /// Fetch a task and lock its row until `tx` ends, so concurrent status
+    /// changes check the workflow against the status they replace
+    pub(crate) async fn lock_task_in_transaction(
+        tx: &mut Transaction<'_, Postgres>,
+        id: Uuid,
+    ) -> Result<Option<Task>, CoreError> {
+        let task = sqlx::query_as("SELECT * FROM tasks WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut **tx)
+            .await?;
+        Ok(task)
+    }
+
+    /// Record that `task_id` depends on `depends_on_id`, rejecting cycles
+    async fn add_dependency(
+        tx: &mut Transaction<'_, Postgres>,
+        task_id: Uuid,
+        task_name: &str,
+        depends_on_id: Uuid,
+    ) -> Result<(), CoreError> {
+        if task_id == depends_on_id {
+            return Err(CoreError::InvalidInput(
+                "A task cannot depend on itself.".to_string(),
+            ));
+        }
+
+        if Self::path_exists(tx, depends_on_id, task_id).await? {
+            let depends_on_task_name = Self::find_task_by_id_in_transaction(tx, depends_on_id)
+                .await?
+                .map(|t| t.name)
+                .unwrap_or_else(|| depends_on_id.to_string());
+            return Err(CoreError::CircularDependency(
+                task_name.to_string(),
+                depends_on_task_name,
+            ));
+        }
+
+        sqlx::query("INSERT INTO task_dependencies (task_id, depends_on_id) VALUES ($1, $2)")
+            .bind(task_id)
+            .bind(depends_on_id)
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+
+    /// Update task fields within an existing transaction
+    pub(crate) async fn update_task_fields(
+        tx: &mut Transaction<'_, Postgres>,
+        id: Uuid,
+        data: &UpdateTaskData,
+        now: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        let mut qb: QueryBuilder<Postgres> = QueryBuilder::new("UPDATE tasks SET updated_at = ");
+        qb.push_bind(now);
+        let mut touched = false;
+
+        if let Some(name) = &data.name {
+            qb.push(", name = ");
+            qb.push_bind(name);
+            touched = true;
+        }
+
+        if let Some(description) = &data.description {
+            qb.push(", description = ");
+            qb.push_bind(description);
+            touched = true;
+        }
+
+        if let Some(due_at) = &data.due_at {
+            qb.push(", due_at = ");
+            qb.push_bind(due_at);
+            touched = true;
+        }
+
+        if let Some(priority) = &data.priority {
+            qb.push(", priority = ");
+            qb.push_bind(priority);
+            touched = true;
+        }
+
+        if let Some(status) = &data.status {
+            qb.push(", status = ");
+            qb.push_bind(status);
+            qb.push(", workflow_status = ");
+            qb.push_bind(status.as_str());
+            touched = true;
+        }
+
+        if let Some(parent_id) = &data.parent_id {
+            qb.push(", parent_id = ");
+            qb.push_bind(parent_id);
+            touched = true;
+        }
+
+        if let Some(series_id) = &data.series_id {
+            qb.push(", series_id = ");
+            qb.push_bind(series_id);
+            touched = true;
+        }
+
+        if let Some(estimate) = &data.estimate {
+            let estimate_minutes = estimate.map(forecast::estimate_minutes).transpose()?;
+            qb.push(", estimate_minutes = ");
+            qb.push_bind(estimate_minutes);
+            touched = true;
+        }
+
+        for (column, date) in [
+            ("scheduled_at", data.scheduled_at),
+            ("wait_until", data.wait_until),
+            ("start_at", data.start_at),
+        ] {
+            if let Some(date) = date {
+                qb.push(format!(", {} = ", column));
+                qb.push_bind(date);
+                touched = true;
+            }
+        }
+
+        if let Some(project_name_option) = &data.project_name {
+            let project_id = match project_name_option {
+                Some(project_name) => {
+                    let project: Option<Project> =
+                        sqlx::query_as("SELECT * FROM projects WHERE name = $1")
+                            .bind(project_name)
+                            .fetch_optional(&mut **tx)
+                            .await?;
+                    Some(
+                        project
+                            .map(|p| p.id)
+                            .ok_or_else(|| CoreError::NotFound(project_name.clone()))?,
+                    )
+                }
+                None => None,
+            };
+            qb.push(", project_id = ");
+            qb.push_bind(project_id);
+            touched = true;
+        }
+
+        if let Some(depends_on_option) = &data.depends_on {
+            sqlx::query("DELETE FROM task_dependencies WHERE task_id = $1")
+                .bind(id)
+                .execute(&mut **tx)
+                .await?;
+
+            if let Some(depends_on_id) = depends_on_option {
+                let task_name = Self::find_task_by_id_in_transaction(tx, id)
+                    .await?
+                    .map(|t| t.name)
+                    .unwrap_or_else(|| id.to_string());
+                Self::add_dependency(tx, id, &task_name, *depends_on_id).await?;
+            }
+            touched = true;
+        }
+
+        if let Some(remind_option) = &data.remind_before {
+            match remind_option {
+                Some(remind_before) => {
+                    Self::set_task_reminder_in_transaction(tx, id, *remind_before, now).await?;
+                }
+                None => {
+                    sqlx::query("DELETE FROM task_reminders WHERE task_id = $1")
+                        .bind(id)
+                        .execute(&mut **tx)
+                        .await?;
+                }
+            }
+        }
+
+        if let Some(tags_to_add) = &data.add_tags {
+            if !tags_to_add.is_empty() {
+                let mut query_builder: QueryBuilder<Postgres> =
+                    QueryBuilder::new("INSERT INTO task_tags (task_id, tag_name) ");
+                query_builder.push_values(tags_to_add.iter(), |mut b, tag| {
+                    b.push_bind(id).push_bind(tag);
+                });
+                query_builder.push(" ON CONFLICT DO NOTHING");
+                query_builder.build().execute(&mut **tx).await?;
+            }
+        }
+
+        if let Some(tags_to_remove) = &data.remove_tags {
+            if !tags_to_remove.is_empty() {
+                sqlx::query("DELETE FROM task_tags WHERE task_id = $1 AND tag_name = ANY($2)")
+                    .bind(id)
+                    .bind(tags_to_remove)
+                    .execute(&mut **tx)
+                    .await?;
+            }
+        }
+
+        if touched {
+            qb.push(" WHERE id = ");
+            qb.push_bind(id);
+            qb.build().execute(&mut **tx).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Check if a dependency path exists from start_node to end_node (for circular dependency detection)
+    pub(crate) async fn path_exists(
+        tx: &mut Transaction<'_, Postgres>,
+        start_node_id: Uuid,
+        end_node_id: Uuid,
+    ) -> Result<bool, CoreError> {
+        let path_found: Option<i32> = sqlx::query_scalar(
+            r#"
+            WITH RECURSIVE dependency_path (id) AS (
+                SELECT depends_on_id FROM task_dependencies WHERE task_id = $1
+                UNION
+                SELECT td.depends_on_id
+                FROM task_dependencies td
+                JOIN dependency_path dp ON td.task_id = dp.id
+            )
+            SELECT 1 FROM dependency_path WHERE id = $2 LIMIT 1
+            "#,
+        )
+        .bind(start_node_id)
+        .bind(end_node_id)
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        Ok(path_found.is_some())
+    }
+}
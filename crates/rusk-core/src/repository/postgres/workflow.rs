@@ -0,0 +1,203 @@
+use crate::error::CoreError;
+use crate::models::{Task, TaskStatus, WorkflowStatus, WorkflowTransition};
+use crate::repository::PostgresRepository;
+use crate::workflow::{self, MoveAction};
+use async_trait::async_trait;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[async_trait]
+impl crate::repository::WorkflowRepository for PostgresRepository {
+    async fn add_workflow_status(&self, name: &str, category: TaskStatus) -> Result<WorkflowStatus, CoreError> {
+        let name = workflow::normalize_status_name(name)?;
+        let mut tx = self.pool().begin().await?;
+
+        let existing: Option<WorkflowStatus> = sqlx::query_as("SELECT * FROM workflow_statuses WHERE name = $1")
+            .bind(&name)
+            .fetch_optional(&mut *tx)
+            .await?;
+        if existing.is_some() {
+            return Err(workflow::duplicate_status(&name));
+        }
+
+        let position: i64 = sqlx::query_scalar(
+            "SELECT COALESCE(MAX(position), 0) + 1 FROM workflow_statuses WHERE category = $1"
+        )
+        .bind(&category)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let status = WorkflowStatus {
+            name,
+            category,
+            position,
+            created_at: self.now(),
+        };
+
+        sqlx::query(
+            "INSERT INTO workflow_statuses (name, category, position, created_at) VALUES ($1, $2, $3, $4)"
+        )
+        .bind(&status.name)
+        .bind(&status.category)
+        .bind(status.position)
+        .bind(status.created_at)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(status)
+    }
+
+    async fn find_workflow_status(&self, name: &str) -> Result<Option<WorkflowStatus>, CoreError> {
+        let status = sqlx::query_as("SELECT * FROM workflow_statuses WHERE name = $1")
+            .bind(name.trim().to_lowercase())
+            .fetch_optional(self.pool())
+            .await?;
+        Ok(status)
+    }
+
+    async fn find_workflow_statuses(&self) -> Result<Vec<WorkflowStatus>, CoreError> {
+        let mut statuses: Vec<WorkflowStatus> = sqlx::query_as("SELECT * FROM workflow_statuses")
+            .fetch_all(self.pool())
+            .await?;
+        workflow::sort_statuses(&mut statuses);
+        Ok(statuses)
+    }
+
+    async fn delete_workflow_status(&self, name: &str) -> Result<(), CoreError> {
+        let name = workflow::normalize_status_name(name)?;
+        let mut tx = self.pool().begin().await?;
+
+        let in_use: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tasks WHERE workflow_status = $1")
+            .bind(&name)
+            .fetch_one(&mut *tx)
+            .await?;
+        workflow::check_deletable(&name, in_use)?;
+
+        // Transitions go with the status through ON DELETE CASCADE
+        let result = sqlx::query("DELETE FROM workflow_statuses WHERE name = $1")
+            .bind(&name)
+            .execute(&mut *tx)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(workflow::unknown_status(&name));
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn add_workflow_transition(&self, from: &str, to: &str) -> Result<WorkflowTransition, CoreError> {
+        let transition = WorkflowTransition {
+            from_status: workflow::normalize_status_name(from)?,
+            to_status: workflow::normalize_status_name(to)?,
+        };
+        if transition.from_status == transition.to_status {
+            return Err(CoreError::InvalidInput("A status cannot transition to itself".to_string()));
+        }
+
+        let mut tx = self.pool().begin().await?;
+        for name in [&transition.from_status, &transition.to_status] {
+            let exists: Option<WorkflowStatus> = sqlx::query_as("SELECT * FROM workflow_statuses WHERE name = $1")
+                .bind(name)
+                .fetch_optional(&mut *tx)
+                .await?;
+            if exists.is_none() {
+                return Err(workflow::unknown_status(name));
+            }
+        }
+
+        sqlx::query(
+            "INSERT INTO workflow_transitions (from_status, to_status) VALUES ($1, $2) ON CONFLICT DO NOTHING"
+        )
+            .bind(&transition.from_status)
+            .bind(&transition.to_status)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(transition)
+    }
+
+    async fn remove_workflow_transition(&self, from: &str, to: &str) -> Result<(), CoreError> {
+        let from = workflow::normalize_status_name(from)?;
+        let to = workflow::normalize_status_name(to)?;
+
+        let result = sqlx::query("DELETE FROM workflow_transitions WHERE from_status = $1 AND to_status = $2")
+            .bind(&from)
+            .bind(&to)
+            .execute(self.pool())
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(workflow::transition_not_found(&from, &to));
+        }
+        Ok(())
+    }
+
+    async fn find_workflow_transitions(&self) -> Result<Vec<WorkflowTransition>, CoreError> {
+        let transitions = sqlx::query_as("SELECT * FROM workflow_transitions ORDER BY from_status, to_status")
+            .fetch_all(self.pool())
+            .await?;
+        Ok(transitions)
+    }
+
+    async fn move_task(&self, id: Uuid, status: &str) -> Result<Task, CoreError> {
+        let name = workflow::normalize_status_name(status)?;
+        let mut tx = self.pool().begin().await?;
+        let moved = self.move_task_to_status_in_transaction(&mut tx, id, &name).await?;
+        tx.commit().await?;
+        Ok(moved)
+    }
+}
+
+impl PostgresRepository {
+    pub(crate) async fn find_workflow_transitions_in_transaction(
+        tx: &mut Transaction<'_, Postgres>,
+    ) -> Result<Vec<WorkflowTransition>, CoreError> {
+        let transitions = sqlx::query_as("SELECT * FROM workflow_transitions ORDER BY from_status, to_status")
+            .fetch_all(&mut **tx)
+            .await?;
+        Ok(transitions)
+    }
+
+    /// Move a task into the status `name`, holding the task's row lock from
+    /// the transition check until the move commits
+    pub(crate) async fn move_task_to_status_in_transaction(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        id: Uuid,
+        name: &str,
+    ) -> Result<Task, CoreError> {
+        let task = Self::lock_task_in_transaction(tx, id)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(id.to_string()))?;
+        let target: WorkflowStatus = sqlx::query_as("SELECT * FROM workflow_statuses WHERE name = $1")
+            .bind(name)
+            .fetch_optional(&mut **tx)
+            .await?
+            .ok_or_else(|| workflow::unknown_status(name))?;
+        let transitions = Self::find_workflow_transitions_in_transaction(tx).await?;
+
+        match workflow::plan_move(&task, &target, &transitions)? {
+            MoveAction::Unchanged => Ok(task),
+            MoveAction::Complete => Ok(self.complete_task_in_transaction(tx, id, &target.name).await?.into_completed()),
+            MoveAction::Cancel => Self::cancel_task_in_transaction(tx, id, &target.name, self.now()).await,
+            MoveAction::Relabel { reopen } => {
+                sqlx::query(
+                    "UPDATE tasks SET status = $1, workflow_status = $2, completed_at = $3, updated_at = $4 WHERE id = $5"
+                )
+                .bind(&target.category)
+                .bind(&target.name)
+                .bind(if reopen { None } else { task.completed_at })
+                .bind(self.now())
+                .bind(id)
+                .execute(&mut **tx)
+                .await?;
+
+                Self::find_task_by_id_in_transaction(tx, id)
+                    .await?
+                    .ok_or_else(|| CoreError::NotFound(id.to_string()))
+            }
+        }
+    }
+}
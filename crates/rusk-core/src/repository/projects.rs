@@ -12,6 +12,7 @@ impl super::ProjectRepository for SqliteRepository {
         description: Option<String>,
     ) -> Result<Project, CoreError> {
         let project_id = Uuid::now_v7();
+        // fetch_all drives the statement to completion so the insert is committed
         let project = sqlx::query_as(
             r#"INSERT INTO projects (id, name, description)
             VALUES ($1, $2, $3)
@@ -21,8 +22,10 @@ impl super::ProjectRepository for SqliteRepository {
         .bind(project_id)
         .bind(name)
         .bind(description)
-        .fetch_one(self.pool())
-        .await?;
+        .fetch_all(self.pool())
+        .await?
+        .pop()
+        .ok_or(sqlx::Error::RowNotFound)?;
 
         Ok(project)
     }
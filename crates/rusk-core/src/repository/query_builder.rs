@@ -1,19 +1,63 @@
-use crate::models;
+use crate::models::{self, TaskPriority, TaskStatus};
 use crate::query::{Filter, Operator, Query, TagFilter, TextFilter, DueDate};
-use chrono::Utc;
-use sqlx::{QueryBuilder, Sqlite};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use sqlx::{Database, Encode, QueryBuilder, Type};
+use uuid::Uuid;
+
+/// The calendar date `dt` falls on in `timezone`
+pub(crate) fn local_date(dt: DateTime<Utc>, timezone: Tz) -> NaiveDate {
+    dt.with_timezone(&timezone).date_naive()
+}
+
+/// When `day` starts in `timezone`
+pub(crate) fn start_of_day(day: NaiveDate, timezone: Tz) -> DateTime<Utc> {
+    let midnight = day.and_time(NaiveTime::MIN);
+    timezone
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map(|start| start.with_timezone(&Utc))
+        .unwrap_or_else(|| midnight.and_utc())
+}
+
+/// Midnight UTC of `day`, where all-day tasks due on `day` keep their due time
+pub(crate) fn utc_start_of_day(day: NaiveDate) -> DateTime<Utc> {
+    day.and_time(NaiveTime::MIN).and_utc()
+}
 
 /// Utility functions for building SQL queries from our AST
+///
+/// The generated SQL only uses constructs shared by SQLite and PostgreSQL, so
+/// the same builder serves both backends. Calendar-day filters are turned
+/// into half-open UTC ranges instead of relying on a dialect's `DATE()`.
 pub struct SqlQueryBuilder;
 
 impl SqlQueryBuilder {
     /// Build a SQL WHERE clause from a Query AST
-    pub fn build_sql_where_clause<'a>(
+    ///
+    /// Relative filters such as `due:today` or `due:overdue` are resolved
+    /// against `now` rather than the database's own clock, and calendar days
+    /// are those of `timezone`.
+    pub fn build_sql_where_clause<'a, DB>(
         query: &Query,
-        qb: &mut QueryBuilder<'a, Sqlite>,
-    ) {
+        now: DateTime<Utc>,
+        timezone: Tz,
+        qb: &mut QueryBuilder<'a, DB>,
+    ) where
+        DB: Database,
+        String: Encode<'a, DB> + Type<DB>,
+        i64: Encode<'a, DB> + Type<DB>,
+        DateTime<Utc>: Encode<'a, DB> + Type<DB>,
+        TaskStatus: Encode<'a, DB> + Type<DB>,
+        TaskPriority: Encode<'a, DB> + Type<DB>,
+        Uuid: Encode<'a, DB> + Type<DB>,
+    {
         match query {
             Query::Filter(filter) => match filter {
+                Filter::Id(id) => {
+                    qb.push("th.id = ");
+                    qb.push_bind(*id);
+                }
                 Filter::Project(name) => {
                     qb.push("p.name = ");
                     qb.push_bind(name.clone());
@@ -22,6 +66,10 @@ impl SqlQueryBuilder {
                     qb.push("th.status = ");
                     qb.push_bind(status.clone());
                 }
+                Filter::WorkflowStatus(name) => {
+                    qb.push("th.workflow_status = ");
+                    qb.push_bind(name.clone());
+                }
                 Filter::Priority(priority) => {
                     qb.push("th.priority = ");
                     qb.push_bind(priority.clone());
@@ -35,33 +83,60 @@ impl SqlQueryBuilder {
                 Filter::Description(text_filter) => {
                     Self::build_text_filter_clause(text_filter, "th.description", qb);
                 }
+                Filter::Note(TextFilter::NotContains(text)) => {
+                    qb.push("th.id NOT IN (SELECT a.task_id FROM annotations a WHERE a.task_id IS NOT NULL AND ");
+                    Self::build_text_filter_clause(&TextFilter::Contains(text.clone()), "a.text", qb);
+                    qb.push(")");
+                }
+                Filter::Note(text_filter) => {
+                    qb.push("th.id IN (SELECT a.task_id FROM annotations a WHERE a.task_id IS NOT NULL AND ");
+                    Self::build_text_filter_clause(text_filter, "a.text", qb);
+                    qb.push(")");
+                }
                 Filter::Due(due_date) => {
-                    Self::build_due_date_clause(due_date, qb);
+                    Self::build_due_date_clause(due_date, now, timezone, qb);
+                }
+                Filter::Waiting => {
+                    qb.push("(th.wait_until IS NOT NULL AND th.wait_until > ");
+                    qb.push_bind(now);
+                    qb.push(")");
+                }
+                Filter::Scheduled => {
+                    qb.push("(th.scheduled_at IS NOT NULL AND th.scheduled_at > ");
+                    qb.push_bind(now);
+                    qb.push(")");
+                }
+                Filter::All => {
+                    qb.push("1 = 1");
                 }
             },
             Query::Not(query) => {
                 qb.push("NOT (");
-                Self::build_sql_where_clause(query, qb);
+                Self::build_sql_where_clause(query, now, timezone, qb);
                 qb.push(")");
             }
             Query::Binary { op, left, right } => {
                 qb.push("(");
-                Self::build_sql_where_clause(left, qb);
+                Self::build_sql_where_clause(left, now, timezone, qb);
                 match op {
                     Operator::And => qb.push(") AND ("),
                     Operator::Or => qb.push(") OR ("),
                 };
-                Self::build_sql_where_clause(right, qb);
+                Self::build_sql_where_clause(right, now, timezone, qb);
                 qb.push(")");
             }
         }
     }
 
     /// Build SQL clause for tag filters
-    fn build_tag_filter_clause<'a>(
+    fn build_tag_filter_clause<'a, DB>(
         tag_filter: &TagFilter,
-        qb: &mut QueryBuilder<'a, Sqlite>,
-    ) {
+        qb: &mut QueryBuilder<'a, DB>,
+    ) where
+        DB: Database,
+        String: Encode<'a, DB> + Type<DB>,
+        i64: Encode<'a, DB> + Type<DB>,
+    {
         match tag_filter {
             TagFilter::Has(tag) => {
                 qb.push("th.id IN (SELECT task_id FROM task_tags WHERE tag_name = ");
@@ -129,11 +204,14 @@ impl SqlQueryBuilder {
     }
 
     /// Build SQL clause for text filters
-    fn build_text_filter_clause<'a>(
+    fn build_text_filter_clause<'a, DB>(
         text_filter: &TextFilter,
         column: &str,
-        qb: &mut QueryBuilder<'a, Sqlite>,
-    ) {
+        qb: &mut QueryBuilder<'a, DB>,
+    ) where
+        DB: Database,
+        String: Encode<'a, DB> + Type<DB>,
+    {
         match text_filter {
             TextFilter::Contains(text) => {
                 qb.push(&format!("LOWER({}) LIKE LOWER(", column));
@@ -182,15 +260,19 @@ impl SqlQueryBuilder {
     }
 
     /// Build SQL clause for due date filters
-    fn build_due_date_clause<'a>(
+    fn build_due_date_clause<'a, DB>(
         due_date: &DueDate,
-        qb: &mut QueryBuilder<'a, Sqlite>,
-    ) {
+        now: DateTime<Utc>,
+        timezone: Tz,
+        qb: &mut QueryBuilder<'a, DB>,
+    ) where
+        DB: Database,
+        DateTime<Utc>: Encode<'a, DB> + Type<DB>,
+    {
+        let today = local_date(now, timezone);
         match due_date {
             DueDate::On(date_time) => {
-                qb.push("DATE(th.due_at) = DATE(");
-                qb.push_bind(date_time.clone());
-                qb.push(")");
+                Self::push_same_day_clause(local_date(*date_time, timezone), timezone, qb);
             }
             DueDate::Before(date_time) => {
                 qb.push("th.due_at < ");
@@ -201,31 +283,56 @@ impl SqlQueryBuilder {
                 qb.push_bind(date_time.clone());
             }
             DueDate::Today => {
-                qb.push("DATE(th.due_at) = DATE('now')");
+                Self::push_same_day_clause(today, timezone, qb);
             }
             DueDate::Tomorrow => {
-                qb.push("DATE(th.due_at) = DATE('now', '+1 day')");
+                Self::push_same_day_clause(today + Duration::days(1), timezone, qb);
             }
             DueDate::Yesterday => {
-                qb.push("DATE(th.due_at) = DATE('now', '-1 day')");
+                Self::push_same_day_clause(today - Duration::days(1), timezone, qb);
             }
             DueDate::Overdue => {
-                qb.push("th.due_at < datetime('now') AND th.status = 'pending'");
+                // All-day tasks are only overdue once their date has passed
+                qb.push("((NOT th.all_day AND th.due_at < ");
+                qb.push_bind(now);
+                qb.push(") OR (th.all_day AND th.due_at < ");
+                qb.push_bind(utc_start_of_day(today));
+                qb.push(")) AND th.status = 'pending'");
             }
             DueDate::Within(duration) => {
-                let target_date = Utc::now() + *duration;
-                qb.push("th.due_at BETWEEN datetime('now') AND ");
-                qb.push_bind(target_date);
+                qb.push("th.due_at BETWEEN ");
+                qb.push_bind(now);
+                qb.push(" AND ");
+                qb.push_bind(now + *duration);
             }
             DueDate::Ago(duration) => {
-                let start_date = Utc::now() - *duration;
                 qb.push("th.due_at BETWEEN ");
-                qb.push_bind(start_date);
-                qb.push(" AND datetime('now')");
+                qb.push_bind(now - *duration);
+                qb.push(" AND ");
+                qb.push_bind(now);
             }
         }
     }
 
+    /// Match tasks due on the calendar day `day`: timed tasks during that day
+    /// in `timezone`, all-day tasks on that date
+    fn push_same_day_clause<'a, DB>(day: NaiveDate, timezone: Tz, qb: &mut QueryBuilder<'a, DB>)
+    where
+        DB: Database,
+        DateTime<Utc>: Encode<'a, DB> + Type<DB>,
+    {
+        let next_day = day + Duration::days(1);
+        qb.push("((NOT th.all_day AND th.due_at >= ");
+        qb.push_bind(start_of_day(day, timezone));
+        qb.push(" AND th.due_at < ");
+        qb.push_bind(start_of_day(next_day, timezone));
+        qb.push(") OR (th.all_day AND th.due_at >= ");
+        qb.push_bind(utc_start_of_day(day));
+        qb.push(" AND th.due_at < ");
+        qb.push_bind(utc_start_of_day(next_day));
+        qb.push("))");
+    }
+
     /// Extract filters from Query structure for materialization window calculation
     pub fn extract_filters_from_query(query: &Query) -> Vec<models::Filter> {
         let mut filters = Vec::new();
@@ -0,0 +1,109 @@
+use crate::error::CoreError;
+use crate::models::{DueReminder, TaskReminder};
+use crate::repository::SqliteRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::{Sqlite, Transaction};
+use uuid::Uuid;
+
+#[async_trait]
+impl super::ReminderRepository for SqliteRepository {
+    async fn set_task_reminder(&self, task_id: Uuid, remind_before: Duration) -> Result<TaskReminder, CoreError> {
+        let mut tx = self.pool().begin().await?;
+
+        if Self::find_task_by_id_in_transaction(&mut tx, task_id).await?.is_none() {
+            return Err(CoreError::NotFound(task_id.to_string()));
+        }
+
+        let reminder = Self::set_task_reminder_in_transaction(&mut tx, task_id, remind_before, self.now()).await?;
+        tx.commit().await?;
+        Ok(reminder)
+    }
+
+    async fn clear_task_reminder(&self, task_id: Uuid) -> Result<(), CoreError> {
+        sqlx::query("DELETE FROM task_reminders WHERE task_id = $1")
+            .bind(task_id)
+            .execute(self.pool())
+            .await?;
+        Ok(())
+    }
+
+    async fn find_task_reminder(&self, task_id: Uuid) -> Result<Option<TaskReminder>, CoreError> {
+        let reminder = sqlx::query_as("SELECT * FROM task_reminders WHERE task_id = $1")
+            .bind(task_id)
+            .fetch_optional(self.pool())
+            .await?;
+        Ok(reminder)
+    }
+
+    async fn find_pending_reminders(&self, due_after: DateTime<Utc>) -> Result<Vec<DueReminder>, CoreError> {
+        // Instances fall back to their template's reminder; templates themselves
+        // are never reminded about since they only describe the pattern.
+        let reminders = sqlx::query_as(
+            r#"SELECT t.*, COALESCE(r.offset_minutes, tr.offset_minutes) AS offset_minutes
+            FROM tasks t
+            LEFT JOIN task_reminders r ON r.task_id = t.id
+            LEFT JOIN task_series s ON s.id = t.series_id
+            LEFT JOIN task_reminders tr ON tr.task_id = s.template_task_id
+            LEFT JOIN reminder_deliveries d ON d.task_id = t.id AND d.due_at = t.due_at
+            WHERE t.status = 'pending'
+            AND t.due_at IS NOT NULL
+            AND t.due_at >= $1
+            AND COALESCE(r.offset_minutes, tr.offset_minutes) IS NOT NULL
+            AND d.task_id IS NULL
+            AND t.id NOT IN (SELECT template_task_id FROM task_series)
+            ORDER BY t.due_at"#,
+        )
+        .bind(due_after)
+        .fetch_all(self.pool())
+        .await?;
+        Ok(reminders)
+    }
+
+    async fn mark_reminder_delivered(&self, task_id: Uuid, due_at: DateTime<Utc>, delivered_at: DateTime<Utc>) -> Result<(), CoreError> {
+        sqlx::query(
+            r#"INSERT INTO reminder_deliveries (task_id, due_at, delivered_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (task_id, due_at) DO NOTHING"#,
+        )
+        .bind(task_id)
+        .bind(due_at)
+        .bind(delivered_at)
+        .execute(self.pool())
+        .await?;
+        Ok(())
+    }
+}
+
+impl SqliteRepository {
+    /// Create or replace a task's reminder offset within an existing transaction
+    pub(crate) async fn set_task_reminder_in_transaction<'a>(
+        tx: &mut Transaction<'a, Sqlite>,
+        task_id: Uuid,
+        remind_before: Duration,
+        now: DateTime<Utc>,
+    ) -> Result<TaskReminder, CoreError> {
+        if remind_before < Duration::zero() {
+            return Err(CoreError::InvalidInput(
+                "Reminder offset cannot be negative".to_string(),
+            ));
+        }
+
+        sqlx::query(
+            r#"INSERT INTO task_reminders (task_id, offset_minutes, created_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (task_id) DO UPDATE SET offset_minutes = excluded.offset_minutes"#,
+        )
+        .bind(task_id)
+        .bind(remind_before.num_minutes())
+        .bind(now)
+        .execute(&mut **tx)
+        .await?;
+
+        let reminder = sqlx::query_as("SELECT * FROM task_reminders WHERE task_id = $1")
+            .bind(task_id)
+            .fetch_one(&mut **tx)
+            .await?;
+        Ok(reminder)
+    }
+}
@@ -1,8 +1,8 @@
 use crate::error::CoreError;
 use crate::models::{
-    NewTaskData, Task, TaskSeries, NewSeriesData, UpdateSeriesData, SeriesException, SeriesStatistics,
+    NewTaskData, Task, TaskSeries, TaskStatus, NewSeriesData, UpdateSeriesData, SeriesException, SeriesRuleChange, SeriesStatistics,
 };
-use crate::recurrence::RecurrenceManager;
+use crate::recurrence::{timezone_move, truncated_rrule, RecurrenceManager};
 use crate::repository::SqliteRepository;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -13,12 +13,14 @@ use uuid::Uuid;
 impl super::SeriesRepository for SqliteRepository {
     async fn create_series(&self, data: NewSeriesData) -> Result<TaskSeries, CoreError> {
         let mut tx = self.pool().begin().await?;
+        let now = self.now();
 
         // Validate RRULE and normalize it
-        let normalized_rrule = RecurrenceManager::normalize_rrule(
+        let normalized_rrule = RecurrenceManager::normalize_series_rrule(
             &data.rrule, 
             data.dtstart, 
-            &data.timezone
+            &data.timezone, 
+            data.time_mode
         )?;
 
         // Ensure template task exists and is not already part of a series
@@ -55,21 +57,26 @@ impl super::SeriesRepository for SqliteRepository {
             rrule: normalized_rrule,
             dtstart: data.dtstart,
             timezone: data.timezone,
+            time_mode: data.time_mode,
+            dst_policy: data.dst_policy,
             active: true,
             last_materialized_until: None,
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
+            created_at: now,
+            updated_at: now,
         };
 
         sqlx::query(
-            r#"INSERT INTO task_series (id, template_task_id, rrule, dtstart, timezone, active, last_materialized_until, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"#
+            r#"INSERT INTO task_series (id, template_task_id, rrule, dtstart, timezone, time_mode, dst_gap, dst_overlap, active, last_materialized_until, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)"#
         )
         .bind(series.id)
         .bind(series.template_task_id)
         .bind(&series.rrule)
         .bind(series.dtstart)
         .bind(&series.timezone)
+        .bind(series.time_mode)
+        .bind(series.dst_policy.dst_gap)
+        .bind(series.dst_policy.dst_overlap)
         .bind(series.active)
         .bind(series.last_materialized_until)
         .bind(series.created_at)
@@ -97,7 +104,7 @@ impl super::SeriesRepository for SqliteRepository {
         Ok(series)
     }
 
-    async fn update_series(&self, id: Uuid, data: UpdateSeriesData) -> Result<TaskSeries, CoreError> {
+    async fn update_series(&self, id: Uuid, mut data: UpdateSeriesData) -> Result<TaskSeries, CoreError> {
         let mut tx = self.pool().begin().await?;
 
         // Check if series exists
@@ -107,13 +114,19 @@ impl super::SeriesRepository for SqliteRepository {
             .await?
             .ok_or_else(|| CoreError::NotFound(format!("Series with id {} not found", id)))?;
 
+        // A new timezone alone keeps occurrences at their times of day
+        if let Some((from, to)) = timezone_move(&current_series, &mut data)? {
+            Self::shift_wall_clock_times(&mut tx, &current_series, from, to, self.now()).await?;
+        }
+
         let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("UPDATE task_series SET ");
         let mut updated = false;
 
         if let Some(rrule) = &data.rrule {
             // Validate the new RRULE
             let timezone = data.timezone.as_deref().unwrap_or(&current_series.timezone);
-            RecurrenceManager::validate_rrule(rrule, timezone)?;
+            let dtstart = data.dtstart.unwrap_or(current_series.dtstart);
+            RecurrenceManager::validate_rrule_at(rrule, timezone, dtstart)?;
             
             qb.push("rrule = ");
             qb.push_bind(rrule);
@@ -150,16 +163,30 @@ impl super::SeriesRepository for SqliteRepository {
             updated = true;
         }
 
+        let dst_policy = data.dst_policy.filter(|policy| *policy != current_series.dst_policy);
+        if let Some(policy) = dst_policy {
+            Self::remove_instances_moved_by(&mut tx, &current_series, policy, self.now()).await?;
+
+            if updated {
+                qb.push(", ");
+            }
+            qb.push("dst_gap = ");
+            qb.push_bind(policy.dst_gap);
+            qb.push(", dst_overlap = ");
+            qb.push_bind(policy.dst_overlap);
+            updated = true;
+        }
+
         if updated {
             qb.push(", updated_at = ");
-            qb.push_bind(Utc::now());
+            qb.push_bind(self.now());
             qb.push(" WHERE id = ");
             qb.push_bind(id);
 
             qb.build().execute(&mut *tx).await?;
 
-            // If RRULE or timezone changed, reset materialization boundary
-            if data.rrule.is_some() || data.timezone.is_some() {
+            // If RRULE, timezone or DST policy changed, reset materialization boundary
+            if data.rrule.is_some() || data.timezone.is_some() || dst_policy.is_some() {
                 sqlx::query("UPDATE task_series SET last_materialized_until = NULL WHERE id = $1")
                     .bind(id)
                     .execute(&mut *tx)
@@ -171,6 +198,7 @@ impl super::SeriesRepository for SqliteRepository {
             .bind(id)
             .fetch_one(&mut *tx)
             .await?;
+        Self::record_rule_change(&mut tx, &current_series, &updated_series, self.now()).await?;
 
         tx.commit().await?;
         Ok(updated_series)
@@ -195,6 +223,11 @@ impl super::SeriesRepository for SqliteRepository {
             .execute(&mut *tx)
             .await?;
 
+        sqlx::query("DELETE FROM series_rule_changes WHERE series_id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
         // Delete all instance tasks that belong to this series
         sqlx::query("DELETE FROM tasks WHERE series_id = $1")
             .bind(id)
@@ -251,10 +284,14 @@ impl super::SeriesRepository for SqliteRepository {
             rrule: None,
             series_id: None,
             timezone: new_timezone.clone(),
+            estimate: original_template.estimate(),
+            scheduled_at: original_template.scheduled_at,
+            wait_until: original_template.wait_until,
+            start_at: original_template.start_at,
             ..Default::default()
         };
 
-        let new_template = Self::add_task_in_transaction(&mut tx, new_template_data).await?;
+        let new_template = Self::add_task_in_transaction(&mut tx, new_template_data, self.now()).await?;
 
         // Create new series
         let new_series_data = NewSeriesData {
@@ -262,9 +299,11 @@ impl super::SeriesRepository for SqliteRepository {
             rrule: original_series.rrule.clone(),
             dtstart: original_series.dtstart,
             timezone: new_timezone.unwrap_or(original_series.timezone.clone()),
+            time_mode: original_series.time_mode,
+            dst_policy: original_series.dst_policy,
         };
 
-        let new_series = Self::create_series_in_transaction(&mut tx, new_series_data).await?;
+        let new_series = Self::create_series_in_transaction(&mut tx, new_series_data, self.now()).await?;
 
         // Copy tags from original template if any
         let original_tags: Vec<(String,)> = sqlx::query_as("SELECT tag_name FROM task_tags WHERE task_id = $1")
@@ -304,7 +343,7 @@ impl super::SeriesRepository for SqliteRepository {
 
         // Set series to inactive
         sqlx::query("UPDATE task_series SET active = false, updated_at = $1 WHERE id = $2")
-            .bind(Utc::now())
+            .bind(self.now())
             .bind(series_id)
             .execute(&mut *tx)
             .await?;
@@ -318,7 +357,7 @@ impl super::SeriesRepository for SqliteRepository {
         let mut updated_series = Vec::new();
 
         for (series_id, update_data) in updates {
-            let updated = Self::update_series_in_transaction(&mut tx, series_id, update_data).await?;
+            let updated = Self::update_series_in_transaction(&mut tx, series_id, update_data, self.now()).await?;
             updated_series.push(updated);
         }
 
@@ -399,11 +438,25 @@ impl super::SeriesRepository for SqliteRepository {
 
         let next_occurrence = if series.active {
             let recurrence_manager = RecurrenceManager::new(series.clone(), template_task, exceptions)?;
-            recurrence_manager.next_occurrence_after(Utc::now())? 
+            recurrence_manager.next_occurrence_after(self.now())? 
         } else {
             None
         };
 
+        // Average tracked time over completed occurrences that have time entries
+        let average_completion_time_hours: Option<f64> = sqlx::query_scalar(
+            r#"SELECT AVG(total) / 3600.0 FROM (
+                SELECT SUM(te.duration_seconds) AS total
+                FROM tasks t
+                JOIN time_entries te ON te.task_id = t.id
+                WHERE t.series_id = $1 AND t.status = 'completed' AND te.duration_seconds IS NOT NULL
+                GROUP BY t.id
+            ) AS tracked"#
+        )
+        .bind(series_id)
+        .fetch_one(self.pool())
+        .await?;
+
         // Calculate completion rate for health score
         let completion_rate = if task_stats.0 > 0 {
             task_stats.1 as f64 / task_stats.0 as f64
@@ -429,10 +482,44 @@ impl super::SeriesRepository for SqliteRepository {
             first_occurrence: time_stats.0,
             last_occurrence: time_stats.1,
             next_occurrence,
-            average_completion_time_hours: None, // Could be calculated if needed
+            average_completion_time_hours,
             series_health_score: health_score,
         })
     }
+
+    async fn find_series_rule_changes(&self, series_id: Uuid) -> Result<Vec<SeriesRuleChange>, CoreError> {
+        let changes = sqlx::query_as(
+            "SELECT * FROM series_rule_changes WHERE series_id = $1 ORDER BY changed_at, id"
+        )
+        .bind(series_id)
+        .fetch_all(self.pool())
+        .await?;
+        Ok(changes)
+    }
+
+    async fn find_orphaned_instances(&self) -> Result<Vec<Task>, CoreError> {
+        let tasks = sqlx::query_as(
+            r#"SELECT * FROM tasks
+            WHERE series_id IS NOT NULL AND series_id NOT IN (SELECT id FROM task_series)
+            ORDER BY created_at, id"#
+        )
+        .fetch_all(self.pool())
+        .await?;
+        Ok(tasks)
+    }
+
+    async fn detach_orphaned_instances(&self) -> Result<Vec<Task>, CoreError> {
+        let mut tasks: Vec<Task> = sqlx::query_as(
+            r#"UPDATE tasks SET series_id = NULL, updated_at = $1
+            WHERE series_id IS NOT NULL AND series_id NOT IN (SELECT id FROM task_series)
+            RETURNING *"#
+        )
+        .bind(self.now())
+        .fetch_all(self.pool())
+        .await?;
+        tasks.sort_by_key(|t| (t.created_at, t.id));
+        Ok(tasks)
+    }
 }
 
 impl SqliteRepository {
@@ -440,12 +527,14 @@ impl SqliteRepository {
     pub(crate) async fn create_series_in_transaction<'a>(
         tx: &mut Transaction<'a, Sqlite>,
         data: NewSeriesData,
+        now: DateTime<Utc>,
     ) -> Result<TaskSeries, CoreError> {
         // Validate RRULE and normalize it
-        let normalized_rrule = RecurrenceManager::normalize_rrule(
+        let normalized_rrule = RecurrenceManager::normalize_series_rrule(
             &data.rrule, 
             data.dtstart, 
-            &data.timezone
+            &data.timezone, 
+            data.time_mode
         )?;
 
         // Ensure template task exists and is not already part of a series
@@ -482,21 +571,26 @@ impl SqliteRepository {
             rrule: normalized_rrule,
             dtstart: data.dtstart,
             timezone: data.timezone,
+            time_mode: data.time_mode,
+            dst_policy: data.dst_policy,
             active: true,
             last_materialized_until: None,
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
+            created_at: now,
+            updated_at: now,
         };
 
         sqlx::query(
-            r#"INSERT INTO task_series (id, template_task_id, rrule, dtstart, timezone, active, last_materialized_until, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"#
+            r#"INSERT INTO task_series (id, template_task_id, rrule, dtstart, timezone, time_mode, dst_gap, dst_overlap, active, last_materialized_until, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)"#
         )
         .bind(series.id)
         .bind(series.template_task_id)
         .bind(&series.rrule)
         .bind(series.dtstart)
         .bind(&series.timezone)
+        .bind(series.time_mode)
+        .bind(series.dst_policy.dst_gap)
+        .bind(series.dst_policy.dst_overlap)
         .bind(series.active)
         .bind(series.last_materialized_until)
         .bind(series.created_at)
@@ -511,8 +605,20 @@ impl SqliteRepository {
     pub(crate) async fn update_series_in_transaction(
         tx: &mut Transaction<'_, Sqlite>, 
         series_id: Uuid, 
-        data: UpdateSeriesData
+        mut data: UpdateSeriesData,
+        now: DateTime<Utc>,
     ) -> Result<TaskSeries, CoreError> {
+        let current_series: TaskSeries = sqlx::query_as("SELECT * FROM task_series WHERE id = ?")
+            .bind(series_id)
+            .fetch_optional(&mut **tx)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Series with id {} not found", series_id)))?;
+
+        // A new timezone alone keeps occurrences at their times of day
+        if let Some((from, to)) = timezone_move(&current_series, &mut data)? {
+            Self::shift_wall_clock_times(tx, &current_series, from, to, now).await?;
+        }
+
         let mut query_parts = Vec::new();
         let mut params: Vec<String> = Vec::new();
 
@@ -536,18 +642,21 @@ impl SqliteRepository {
             params.push(if *active { "1".to_string() } else { "0".to_string() });
         }
 
-        if query_parts.is_empty() {
-            // No updates, just return current series
-            let series: TaskSeries = sqlx::query_as("SELECT * FROM task_series WHERE id = ?")
+        if let Some(policy) = data.dst_policy {
+            sqlx::query("UPDATE task_series SET dst_gap = ?, dst_overlap = ? WHERE id = ?")
+                .bind(policy.dst_gap)
+                .bind(policy.dst_overlap)
                 .bind(series_id)
-                .fetch_optional(&mut **tx)
-                .await?
-                .ok_or_else(|| CoreError::NotFound(format!("Series with id {} not found", series_id)))?;
-            return Ok(series);
+                .execute(&mut **tx)
+                .await?;
+        }
+
+        if query_parts.is_empty() && data.dst_policy.is_none() {
+            // No updates, just return current series
+            return Ok(current_series);
         }
 
         query_parts.push("updated_at = ?");
-        let now = Utc::now();
         params.push(now.to_rfc3339());
 
         let query = format!(
@@ -567,8 +676,8 @@ impl SqliteRepository {
             return Err(CoreError::NotFound(format!("Series with id {} not found", series_id)));
         }
 
-        // Reset materialization boundary if RRULE or timezone changed
-        if data.rrule.is_some() || data.timezone.is_some() || data.dtstart.is_some() {
+        // Reset materialization boundary if the schedule changed
+        if data.rrule.is_some() || data.timezone.is_some() || data.dtstart.is_some() || data.dst_policy.is_some() {
             sqlx::query("UPDATE task_series SET last_materialized_until = NULL WHERE id = ?")
                 .bind(series_id)
                 .execute(&mut **tx)
@@ -580,7 +689,111 @@ impl SqliteRepository {
             .bind(series_id)
             .fetch_one(&mut **tx)
             .await?;
+        Self::record_rule_change(tx, &current_series, &updated_series, now).await?;
 
         Ok(updated_series)
     }
-}
\ No newline at end of file
+
+    /// Record the edit of a series' schedule when `after` changed it
+    pub(crate) async fn record_rule_change(
+        tx: &mut Transaction<'_, Sqlite>,
+        before: &TaskSeries,
+        after: &TaskSeries,
+        now: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        let Some(change) = SeriesRuleChange::between(before, after, now) else {
+            return Ok(());
+        };
+        sqlx::query(
+            r#"INSERT INTO series_rule_changes (id, series_id, changed_at, old_rrule, new_rrule, old_dtstart, new_dtstart, old_timezone, new_timezone)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"#
+        )
+        .bind(change.id)
+        .bind(change.series_id)
+        .bind(change.changed_at)
+        .bind(&change.old_rrule)
+        .bind(&change.new_rrule)
+        .bind(change.old_dtstart)
+        .bind(change.new_dtstart)
+        .bind(&change.old_timezone)
+        .bind(&change.new_timezone)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    /// Delete `series` with its template, instances and exceptions
+    ///
+    /// With `keep_history` the completed instances stay as regular tasks.
+    pub(crate) async fn delete_whole_series(
+        tx: &mut Transaction<'_, Sqlite>,
+        series: &TaskSeries,
+        keep_history: bool,
+    ) -> Result<(), CoreError> {
+        if keep_history {
+            sqlx::query("UPDATE tasks SET series_id = NULL WHERE series_id = $1 AND status = $2")
+                .bind(series.id)
+                .bind(TaskStatus::Completed)
+                .execute(&mut **tx)
+                .await?;
+        }
+        // Moved occurrences have left the series but go with it all the same
+        sqlx::query(
+            r#"DELETE FROM tasks WHERE id IN (SELECT exception_task_id FROM series_exceptions WHERE series_id = $1)
+            AND NOT ($2 AND status = $3)"#
+        )
+        .bind(series.id)
+        .bind(keep_history)
+        .bind(TaskStatus::Completed)
+        .execute(&mut **tx)
+        .await?;
+
+        // The task foreign key only nulls out series_id, so instances go first
+        for query in [
+            "DELETE FROM series_exceptions WHERE series_id = $1",
+            "DELETE FROM series_rule_changes WHERE series_id = $1",
+            "DELETE FROM tasks WHERE series_id = $1",
+            "DELETE FROM task_series WHERE id = $1",
+        ] {
+            sqlx::query(query).bind(series.id).execute(&mut **tx).await?;
+        }
+        sqlx::query("DELETE FROM tasks WHERE id = $1")
+            .bind(series.template_task_id)
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+
+    /// End `series` before `cut`, deleting the occurrences from `cut` on
+    /// along with their exceptions and notes
+    pub(crate) async fn end_series_before(
+        tx: &mut Transaction<'_, Sqlite>,
+        series: &TaskSeries,
+        cut: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        let rrule = truncated_rrule(series, cut)?;
+
+        // Moved occurrences go by the time they were scheduled for
+        sqlx::query(
+            r#"DELETE FROM tasks WHERE id IN (
+                SELECT exception_task_id FROM series_exceptions WHERE series_id = $1 AND occurrence_dt >= $2
+            )"#
+        )
+        .bind(series.id)
+        .bind(cut)
+        .execute(&mut **tx)
+        .await?;
+        for query in [
+            "DELETE FROM series_exceptions WHERE series_id = $1 AND occurrence_dt >= $2",
+            "DELETE FROM annotations WHERE series_id = $1 AND occurrence_dt >= $2",
+            "DELETE FROM tasks WHERE series_id = $1 AND due_at >= $2",
+        ] {
+            sqlx::query(query).bind(series.id).bind(cut).execute(&mut **tx).await?;
+        }
+
+        let data = UpdateSeriesData { rrule: Some(rrule), ..Default::default() };
+        Self::update_series_in_transaction(tx, series.id, data, now).await?;
+        Ok(())
+    }
+}
@@ -1,12 +1,14 @@
 use crate::error::CoreError;
+use crate::forecast;
 use crate::models::{
-    CompletionResult, NewTaskData, Project, Task, TaskPriority, TaskStatus,
-    UpdateTaskData, TaskSeries, SeriesException, NewSeriesData,
+    Annotation, BulkAction, CompletionResult, SubtaskPolicy, NewTaskData, Project, Task, TaskPriority, TaskStatus, TaskRelations,
+    UpdateTaskData, TaskSeries, SeriesException, NewSeriesData, TimeMode, NewSeriesException, ExceptionType, TimeEntry,
 };
 use crate::query::Query;
 use crate::recurrence::RecurrenceManager;
-use crate::repository::{TaskQueryResult, SqliteRepository, SeriesRepository};
+use crate::repository::{Repository, TaskQueryResult, SqliteRepository};
 use crate::repository::query_builder::SqlQueryBuilder;
+use crate::workflow;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use sqlx::{QueryBuilder, Sqlite, Transaction};
@@ -15,40 +17,11 @@ use uuid::Uuid;
 #[async_trait]
 impl super::TaskRepository for SqliteRepository {
     async fn add_task(&self, data: NewTaskData) -> Result<Task, CoreError> {
+        let data = data.anchored(self.local_timezone());
         let mut tx = self.pool().begin().await?;
-        
-        // Check if this is a recurring task
-        if let Some(rrule) = &data.rrule {
-            // Create template task first
-            let mut template_data = data.clone();
-            template_data.rrule = None; // Remove rrule for template task
-            template_data.series_id = None; // Template tasks don't have series_id
-            
-            let template_task = Self::add_task_in_transaction(&mut tx, template_data).await?;
-            
-            // Create the series
-            let _series_data = NewSeriesData {
-                template_task_id: template_task.id,
-                rrule: rrule.clone(),
-                dtstart: data.due_at.unwrap_or_else(|| Utc::now()),
-                timezone: data.timezone.unwrap_or_else(|| "UTC".to_string()),
-            };
-            
-            // Create series using the transaction-aware method
-            let series = Self::create_series_in_transaction(&mut tx, _series_data).await?;
-            
-            // Trigger initial materialization for lookahead window
-            let (window_start, window_end) = self.materialization_manager().calculate_window_for_filters(&[]);
-            Self::refresh_single_series_materialization_in_transaction(&mut tx, series.id, window_start, window_end).await?;
-            
-            tx.commit().await?;
-            Ok(template_task)
-        } else {
-            // Regular task
-            let task = Self::add_task_in_transaction(&mut tx, data).await?;
-            tx.commit().await?;
-            Ok(task)
-        }
+        let task = self.add_task_or_series_in_transaction(&mut tx, data).await?;
+        tx.commit().await?;
+        Ok(task)
     }
 
     async fn find_task_by_id(&self, id: Uuid) -> Result<Option<Task>, CoreError> {
@@ -60,12 +33,12 @@ impl super::TaskRepository for SqliteRepository {
     }
 
     async fn find_tasks_by_short_id_prefix(&self, short_id: &str) -> Result<Vec<Task>, CoreError> {
-        // Optimize: avoid format! allocation by using a more efficient pattern
+        // Ids are stored as blobs, so match against their hex form
         let mut pattern = String::with_capacity(short_id.len() + 1);
-        pattern.push_str(short_id);
+        pattern.extend(short_id.chars().filter(|c| *c != '-').map(|c| c.to_ascii_lowercase()));
         pattern.push('%');
         
-        let tasks: Vec<Task> = sqlx::query_as("SELECT * FROM tasks WHERE id LIKE ?")
+        let tasks: Vec<Task> = sqlx::query_as("SELECT * FROM tasks WHERE lower(hex(id)) LIKE ?")
             .bind(pattern)
             .fetch_all(self.pool())
             .await?;
@@ -77,25 +50,38 @@ impl super::TaskRepository for SqliteRepository {
         self.ensure_materialization_for_query(query).await?;
 
         let mut query_builder: QueryBuilder<sqlx::Sqlite> = QueryBuilder::new(
-            r#"WITH RECURSIVE task_hierarchy (id, name, description, status, priority, due_at, completed_at, created_at, updated_at, project_id, parent_id, series_id, depth, path) AS (
+            r#"WITH RECURSIVE task_hierarchy (id, name, description, status, priority, due_at, completed_at, created_at, updated_at, project_id, parent_id, series_id, workflow_status, estimate_minutes, scheduled_at, wait_until, start_at, all_day, depth, path) AS (
                 SELECT
-                    t.id, t.name, t.description, t.status, t.priority, t.due_at, t.completed_at, t.created_at, t.updated_at, t.project_id, t.parent_id, t.series_id,
+                    t.id, t.name, t.description, t.status, t.priority, t.due_at, t.completed_at, t.created_at, t.updated_at, t.project_id, t.parent_id, t.series_id, t.workflow_status, t.estimate_minutes, t.scheduled_at, t.wait_until, t.start_at, t.all_day,
                     0 as depth,
                     CAST(t.created_at AS TEXT) as path
                 FROM tasks t
                 WHERE t.parent_id IS NULL
                 UNION ALL
                 SELECT
-                    t.id, t.name, t.description, t.status, t.priority, t.due_at, t.completed_at, t.created_at, t.updated_at, t.project_id, t.parent_id, t.series_id,
+                    t.id, t.name, t.description, t.status, t.priority, t.due_at, t.completed_at, t.created_at, t.updated_at, t.project_id, t.parent_id, t.series_id, t.workflow_status, t.estimate_minutes, t.scheduled_at, t.wait_until, t.start_at, t.all_day,
                     th.depth + 1,
                     th.path || ' -> ' || CAST(t.created_at AS TEXT)
                 FROM tasks t
                 JOIN task_hierarchy th ON t.parent_id = th.id
             )
             SELECT
-                th.id, th.name, th.description, th.status, th.priority, th.due_at, th.completed_at, th.created_at, th.updated_at, th.project_id, th.parent_id, th.series_id, th.depth, th.path,
+                th.id, th.name, th.description, th.status, th.priority, th.due_at, th.completed_at, th.created_at, th.updated_at, th.project_id, th.parent_id, th.series_id, th.workflow_status, th.estimate_minutes, th.scheduled_at, th.wait_until, th.start_at, th.all_day, th.depth, th.path,
                 p.name as project_name,
-                GROUP_CONCAT(tt.tag_name) as tags
+                GROUP_CONCAT(tt.tag_name) as tags,
+                (SELECT COALESCE(SUM(te.duration_seconds), 0) FROM time_entries te WHERE te.task_id = th.id) AS tracked_seconds,
+                EXISTS (SELECT 1 FROM task_dependencies d JOIN tasks dt ON dt.id = d.depends_on_id WHERE d.task_id = th.id AND dt.status = 'pending') AS is_blocked,
+                EXISTS (SELECT 1 FROM task_dependencies d JOIN tasks dt ON dt.id = d.task_id WHERE d.depends_on_id = th.id AND dt.status = 'pending') AS is_blocking,
+                (WITH RECURSIVE subtree (id, status) AS (
+                    SELECT c.id, c.status FROM tasks c WHERE c.parent_id = th.id
+                    UNION
+                    SELECT c.id, c.status FROM tasks c JOIN subtree s ON c.parent_id = s.id
+                ) SELECT COUNT(*) FROM subtree WHERE status != 'cancelled') AS subtask_count,
+                (WITH RECURSIVE subtree (id, status) AS (
+                    SELECT c.id, c.status FROM tasks c WHERE c.parent_id = th.id
+                    UNION
+                    SELECT c.id, c.status FROM tasks c JOIN subtree s ON c.parent_id = s.id
+                ) SELECT COUNT(*) FROM subtree WHERE status = 'completed') AS completed_subtask_count
             FROM task_hierarchy th
             LEFT JOIN projects p ON th.project_id = p.id
             LEFT JOIN task_tags tt ON th.id = tt.task_id
@@ -103,9 +89,9 @@ impl super::TaskRepository for SqliteRepository {
         );
 
         query_builder.push(" WHERE ");
-        SqlQueryBuilder::build_sql_where_clause(query, &mut query_builder);
+        SqlQueryBuilder::build_sql_where_clause(query, self.now(), self.local_timezone(), &mut query_builder);
 
-        query_builder.push(" GROUP BY th.id, th.name, th.description, th.status, th.priority, th.due_at, th.completed_at, th.created_at, th.updated_at, th.project_id, th.parent_id, th.series_id, th.depth, th.path, p.name");
+        query_builder.push(" GROUP BY th.id, th.name, th.description, th.status, th.priority, th.due_at, th.completed_at, th.created_at, th.updated_at, th.project_id, th.parent_id, th.series_id, th.workflow_status, th.estimate_minutes, th.scheduled_at, th.wait_until, th.start_at, th.all_day, th.depth, th.path, p.name");
         query_builder.push(" ORDER BY th.path");
 
         let tasks = query_builder.build_query_as().fetch_all(self.pool()).await?;
@@ -113,26 +99,253 @@ impl super::TaskRepository for SqliteRepository {
     }
 
     async fn delete_task(&self, id: Uuid) -> Result<(), CoreError> {
+        let mut tx = self.pool().begin().await?;
+        if !Self::delete_task_in_transaction(&mut tx, id).await? {
+            return Err(CoreError::NotFound(id.to_string()));
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn delete_task_with_scope(&self, id: Uuid, scope: crate::models::EditScope, keep_history: bool) -> Result<(), CoreError> {
+        let mut tx = self.pool().begin().await?;
+        if let Err(e) = self.delete_in_scope(&mut tx, id, scope, keep_history).await {
+            tx.rollback().await?;
+            return Err(e);
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn complete_task(&self, id: Uuid) -> Result<CompletionResult, CoreError> {
+        self.complete_task_as(id, TaskStatus::Completed.as_str()).await
+    }
+
+    async fn cancel_task(&self, id: Uuid) -> Result<Task, CoreError> {
+        self.cancel_task_as(id, TaskStatus::Cancelled.as_str()).await
+    }
+
+    async fn update_task(&self, id: Uuid, data: UpdateTaskData, scope: Option<crate::models::EditScope>) -> Result<Task, CoreError> {
+        let mut tx = self.pool().begin().await?;
+
+        let current_task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(id.to_string()))?;
+
+        // Dispatch based on series membership and edit scope
+        let result = match (current_task.series_id, scope.unwrap_or(crate::models::EditScope::ThisOccurrence)) {
+            (None, _) => {
+                // Regular task - validate no recurrence changes
+                self.update_regular_task(&mut tx, id, &data).await
+            }
+            (Some(_), crate::models::EditScope::ThisOccurrence) => {
+                // Single occurrence edit
+                self.update_single_occurrence(&mut tx, id, &data).await
+            }
+            (Some(_series_id), crate::models::EditScope::ThisAndFuture) => {
+                // Update from this occurrence forward
+                self.update_series_from_future(&mut tx, &current_task, &data).await
+            }
+            (Some(_series_id), crate::models::EditScope::EntireSeries) => {
+                // Update entire series
+                self.update_entire_series(&mut tx, &current_task, &data).await
+            }
+        };
+
+        // Roll back explicitly: a dropped transaction is only rolled back once its
+        // connection is recycled, and until then it keeps the database write-locked
+        if let Err(e) = result {
+            tx.rollback().await?;
+            return Err(e);
+        }
+
+        // Series-wide edits replace the edited instance; report the template then
+        let updated_task: Option<Task> = sqlx::query_as("SELECT * FROM tasks WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        let updated_task = match updated_task {
+            Some(task) => task,
+            None => sqlx::query_as(
+                "SELECT t.* FROM tasks t JOIN task_series s ON s.template_task_id = t.id WHERE s.id = $1"
+            )
+            .bind(current_task.series_id)
+            .fetch_one(&mut *tx)
+            .await?,
+        };
+
+        tx.commit().await?;
+        Ok(updated_task)
+    }
+
+    async fn bulk_update_tasks(&self, ids: &[Uuid], action: BulkAction) -> Result<Vec<Task>, CoreError> {
+        let action = super::checked_bulk_action(action)?;
+        let mut tx = self.pool().begin().await?;
+
+        match self.bulk_update_in_transaction(&mut tx, ids, &action).await {
+            Ok(tasks) => {
+                tx.commit().await?;
+                Ok(tasks)
+            }
+            Err(e) => {
+                tx.rollback().await?;
+                Err(e)
+            }
+        }
+    }
+
+    async fn find_task_relations(&self, id: Uuid) -> Result<TaskRelations, CoreError> {
+        let task = self.find_task_by_id(id).await?.ok_or_else(|| CoreError::NotFound(id.to_string()))?;
+
+        let mut ancestors: Vec<Task> = Vec::new();
+        let mut parent_id = task.parent_id;
+        while let Some(pid) = parent_id {
+            if pid == id || ancestors.iter().any(|a| a.id == pid) {
+                break;
+            }
+            let Some(parent) = self.find_task_by_id(pid).await? else {
+                break;
+            };
+            parent_id = parent.parent_id;
+            ancestors.push(parent);
+        }
+
+        let subtasks = sqlx::query_as("SELECT * FROM tasks WHERE parent_id = $1 ORDER BY created_at, id")
+            .bind(id)
+            .fetch_all(self.pool())
+            .await?;
+        let depends_on = sqlx::query_as(
+            "SELECT t.* FROM tasks t JOIN task_dependencies d ON d.depends_on_id = t.id WHERE d.task_id = $1 ORDER BY t.created_at, t.id"
+        )
+        .bind(id)
+        .fetch_all(self.pool())
+        .await?;
+        let dependents = sqlx::query_as(
+            "SELECT t.* FROM tasks t JOIN task_dependencies d ON d.task_id = t.id WHERE d.depends_on_id = $1 ORDER BY t.created_at, t.id"
+        )
+        .bind(id)
+        .fetch_all(self.pool())
+        .await?;
+
+        Ok(TaskRelations { ancestors, subtasks, depends_on, dependents })
+    }
+}
+
+impl SqliteRepository {
+    /// Delete a task, taking the series of a template with it; `false` if
+    /// there was no such task
+    pub(crate) async fn delete_task_in_transaction(tx: &mut Transaction<'_, Sqlite>, id: Uuid) -> Result<bool, CoreError> {
+        let series: Option<TaskSeries> = sqlx::query_as("SELECT * FROM task_series WHERE template_task_id = $1")
+            .bind(id)
+            .fetch_optional(&mut **tx)
+            .await?;
+        if let Some(series) = series {
+            Self::delete_whole_series(tx, &series, true).await?;
+            return Ok(true);
+        }
+
         let result = sqlx::query("DELETE FROM tasks WHERE id = $1")
             .bind(id)
-            .execute(self.pool())
+            .execute(&mut **tx)
             .await?;
+        Ok(result.rows_affected() > 0)
+    }
 
-        if result.rows_affected() == 0 {
-            return Err(CoreError::NotFound(id.to_string()));
+    /// Delete a task and the occurrences of its series that `scope` covers,
+    /// see [`TaskRepository::delete_task_with_scope`](super::TaskRepository::delete_task_with_scope)
+    async fn delete_in_scope(
+        &self,
+        tx: &mut Transaction<'_, Sqlite>,
+        id: Uuid,
+        scope: crate::models::EditScope,
+        keep_history: bool,
+    ) -> Result<(), CoreError> {
+        let task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&mut **tx)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(id.to_string()))?;
+        // Moved occurrences leave the series; their exception still knows it
+        // and the time they were scheduled for
+        let exception: Option<SeriesException> = sqlx::query_as("SELECT * FROM series_exceptions WHERE exception_task_id = $1")
+            .bind(id)
+            .fetch_optional(&mut **tx)
+            .await?;
+        let series: Option<TaskSeries> = sqlx::query_as("SELECT * FROM task_series WHERE id = $1 OR template_task_id = $2")
+            .bind(task.series_id.or(exception.as_ref().map(|e| e.series_id)))
+            .bind(id)
+            .fetch_optional(&mut **tx)
+            .await?;
+        let Some(series) = series else {
+            Self::delete_task_in_transaction(tx, id).await?;
+            return Ok(());
+        };
+        if series.template_task_id == id {
+            return Self::delete_whole_series(tx, &series, keep_history).await;
+        }
+        let occurrence_dt = exception.map(|e| e.occurrence_dt).or(task.due_at);
+
+        match (scope, occurrence_dt) {
+            (crate::models::EditScope::ThisOccurrence, Some(occurrence_dt)) => {
+                Self::delete_task_in_transaction(tx, id).await?;
+                sqlx::query("DELETE FROM series_exceptions WHERE series_id = $1 AND occurrence_dt = $2")
+                    .bind(series.id)
+                    .bind(occurrence_dt)
+                    .execute(&mut **tx)
+                    .await?;
+                let skip = NewSeriesException {
+                    series_id: series.id,
+                    occurrence_dt,
+                    exception_type: ExceptionType::Skip,
+                    exception_task_id: None,
+                    notes: None,
+                };
+                Self::add_series_exception_in_transaction(tx, skip, self.now()).await?;
+                Ok(())
+            }
+            (crate::models::EditScope::ThisOccurrence, None) => {
+                Self::delete_task_in_transaction(tx, id).await?;
+                Ok(())
+            }
+            (crate::models::EditScope::ThisAndFuture, Some(cut)) if cut > series.dtstart => {
+                Self::end_series_before(tx, &series, cut, self.now()).await
+            }
+            _ => Self::delete_whole_series(tx, &series, keep_history).await,
         }
-        Ok(())
     }
 
-    async fn complete_task(&self, id: Uuid) -> Result<CompletionResult, CoreError> {
+    /// Complete a task, recording `workflow_status` as its completed-category status
+    pub(crate) async fn complete_task_as(&self, id: Uuid, workflow_status: &str) -> Result<CompletionResult, CoreError> {
         let mut tx = self.pool().begin().await?;
+        match self.complete_task_in_transaction(&mut tx, id, workflow_status).await {
+            Ok(result) => {
+                tx.commit().await?;
+                Ok(result)
+            }
+            Err(e) => {
+                tx.rollback().await?;
+                Err(e)
+            }
+        }
+    }
 
+    /// Complete a task inside `tx`, if the workflow lets it move into `workflow_status`
+    pub(crate) async fn complete_task_in_transaction<'a>(
+        &self,
+        tx: &mut Transaction<'a, Sqlite>,
+        id: Uuid,
+        workflow_status: &str,
+    ) -> Result<CompletionResult, CoreError> {
         // Get the task to check if it's part of a series
         let task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = $1")
             .bind(id)
-            .fetch_optional(&mut *tx)
+            .fetch_optional(&mut **tx)
             .await?
             .ok_or_else(|| CoreError::NotFound(id.to_string()))?;
+        let transitions = Self::find_workflow_transitions_in_transaction(tx).await?;
+        workflow::check_status_change(&task, workflow_status, &transitions)?;
 
         // Check for blocking dependencies
         let dependencies: Vec<Task> = sqlx::query_as(
@@ -141,7 +354,7 @@ impl super::TaskRepository for SqliteRepository {
             WHERE td.task_id = $1 AND t.status != 'completed'"#,
         )
         .bind(id)
-        .fetch_all(&mut *tx)
+        .fetch_all(&mut **tx)
         .await?;
 
         if !dependencies.is_empty() {
@@ -154,33 +367,37 @@ impl super::TaskRepository for SqliteRepository {
             return Err(CoreError::TaskBlocked(dependency_names));
         }
 
+        self.complete_subtasks(tx, id, &[], self.now()).await?;
+
         // Mark the current task as completed
         let completed_task: Task = sqlx::query_as(
             r#"UPDATE tasks
-            SET status = $1, completed_at = $2, updated_at = $2
-            WHERE id = $3
+            SET status = $1, workflow_status = $2, completed_at = $3, updated_at = $3
+            WHERE id = $4
             RETURNING *
             "#,
         )
         .bind(TaskStatus::Completed)
-        .bind(Utc::now())
+        .bind(workflow_status)
+        .bind(self.now())
         .bind(id)
-        .fetch_one(&mut *tx)
+        .fetch_one(&mut **tx)
         .await
         .map_err(|_| CoreError::NotFound(id.to_string()))?;
+        self.complete_finished_parents(tx, &task, self.now()).await?;
 
         // Handle series-aware completion
         if let Some(series_id) = task.series_id {
             // This is a series instance, handle next occurrence
             let series: TaskSeries = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
                 .bind(series_id)
-                .fetch_optional(&mut *tx)
+                .fetch_optional(&mut **tx)
                 .await?
                 .ok_or_else(|| CoreError::NotFound(format!("Series with id {} not found", series_id)))?;
 
             let template_task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = $1")
                 .bind(series.template_task_id)
-                .fetch_optional(&mut *tx)
+                .fetch_optional(&mut **tx)
                 .await?
                 .ok_or_else(|| CoreError::NotFound(format!("Template task with id {} not found", series.template_task_id)))?;
 
@@ -189,7 +406,7 @@ impl super::TaskRepository for SqliteRepository {
                 "SELECT * FROM series_exceptions WHERE series_id = $1"
             )
             .bind(series_id)
-            .fetch_all(&mut *tx)
+            .fetch_all(&mut **tx)
             .await?;
 
             // Create RecurrenceManager to calculate next occurrence
@@ -199,7 +416,7 @@ impl super::TaskRepository for SqliteRepository {
             let next_occurrence = if let Some(due_at) = completed_task.due_at {
                 recurrence_manager.next_occurrence_after(due_at)?
             } else {
-                recurrence_manager.next_occurrence_after(Utc::now())?
+                recurrence_manager.next_occurrence_after(self.now())?
             };
 
             // If there's a next occurrence, check if it needs to be materialized
@@ -215,15 +432,17 @@ impl super::TaskRepository for SqliteRepository {
                     )
                     .bind(series_id)
                     .bind(next_due)
-                    .fetch_optional(&mut *tx)
+                    .fetch_optional(&mut **tx)
                     .await?;
 
                     if existing_task.is_none() {
                         // Materialize the next occurrence
-                        self.refresh_single_series_materialization(
-                            series_id, 
-                            next_due - chrono::Duration::minutes(1), 
-                            next_due + chrono::Duration::minutes(1)
+                        Self::refresh_single_series_materialization_in_transaction(
+                            tx,
+                            series_id,
+                            next_due - chrono::Duration::minutes(1),
+                            next_due + chrono::Duration::minutes(1),
+                            self.now(),
                         ).await?;
 
                         // Fetch the newly created task
@@ -232,7 +451,7 @@ impl super::TaskRepository for SqliteRepository {
                         )
                         .bind(series_id)
                         .bind(next_due)
-                        .fetch_optional(&mut *tx)
+                        .fetch_optional(&mut **tx)
                         .await?
                     } else {
                         existing_task
@@ -244,8 +463,6 @@ impl super::TaskRepository for SqliteRepository {
                 None
             };
 
-            tx.commit().await?;
-            
             Ok(CompletionResult::SeriesInstance {
                 completed: completed_task,
                 next: next_task,
@@ -254,64 +471,274 @@ impl super::TaskRepository for SqliteRepository {
             })
         } else {
             // Regular task completion
-            tx.commit().await?;
             Ok(CompletionResult::Single(completed_task))
         }
     }
 
-    async fn cancel_task(&self, id: Uuid) -> Result<Task, CoreError> {
-        let updated_task: Task = sqlx::query_as(
-            r#"UPDATE tasks
-            SET status = $1, updated_at = $2
-            WHERE id = $3
-            RETURNING *
-            "#,
+    /// Apply a bulk action to every task inside `tx`, failing on the first task that cannot change
+    async fn bulk_update_in_transaction<'a>(
+        &self,
+        tx: &mut Transaction<'a, Sqlite>,
+        ids: &[Uuid],
+        action: &BulkAction,
+    ) -> Result<Vec<Task>, CoreError> {
+        let now = self.now();
+        let mut tasks = Vec::with_capacity(ids.len());
+        for &id in ids {
+            let task = Self::find_task_by_id_in_transaction(tx, id)
+                .await?
+                .ok_or_else(|| CoreError::NotFound(id.to_string()))?;
+            tasks.push(task);
+        }
+        let transitions = Self::find_workflow_transitions_in_transaction(tx).await?;
+
+        let mut completed_series: Vec<Uuid> = Vec::new();
+        for task in &tasks {
+            match action {
+                BulkAction::Complete => {
+                    workflow::check_status_change(task, TaskStatus::Completed.as_str(), &transitions)?;
+                    let dependencies: Vec<Task> = sqlx::query_as(
+                        r#"SELECT t.* FROM tasks t
+                        INNER JOIN task_dependencies td ON t.id = td.depends_on_id
+                        WHERE td.task_id = $1 AND t.status != 'completed'"#,
+                    )
+                    .bind(task.id)
+                    .fetch_all(&mut **tx)
+                    .await?;
+
+                    // Dependencies completed by the same batch do not block
+                    let blocking: Vec<&str> = dependencies
+                        .iter()
+                        .filter(|t| !ids.contains(&t.id))
+                        .map(|t| t.name.as_str())
+                        .collect();
+                    if !blocking.is_empty() {
+                        return Err(CoreError::TaskBlocked(blocking.join(", ")));
+                    }
+
+                    self.complete_subtasks(tx, task.id, ids, now).await?;
+                    sqlx::query(
+                        "UPDATE tasks SET status = $1, workflow_status = $2, completed_at = $3, updated_at = $3 WHERE id = $4",
+                    )
+                    .bind(TaskStatus::Completed)
+                    .bind(TaskStatus::Completed.as_str())
+                    .bind(now)
+                    .bind(task.id)
+                    .execute(&mut **tx)
+                    .await?;
+                    self.complete_finished_parents(tx, task, now).await?;
+
+                    if let Some(series_id) = task.series_id.filter(|id| !completed_series.contains(id)) {
+                        completed_series.push(series_id);
+                    }
+                }
+                BulkAction::Cancel => {
+                    workflow::check_status_change(task, TaskStatus::Cancelled.as_str(), &transitions)?;
+                    sqlx::query("UPDATE tasks SET status = $1, workflow_status = $2, updated_at = $3 WHERE id = $4")
+                        .bind(TaskStatus::Cancelled)
+                        .bind(TaskStatus::Cancelled.as_str())
+                        .bind(now)
+                        .bind(task.id)
+                        .execute(&mut **tx)
+                        .await?;
+                }
+                BulkAction::Delete => {
+                    // Subtasks of a task deleted earlier in the batch are already gone
+                    if Self::find_task_by_id_in_transaction(tx, task.id).await?.is_some() {
+                        // Occurrences are skipped, as deleting a single one does
+                        self.delete_in_scope(tx, task.id, crate::models::EditScope::ThisOccurrence, true).await?;
+                    }
+                }
+                BulkAction::Update(data) => match task.series_id {
+                    None => self.update_regular_task(tx, task.id, data).await?,
+                    Some(_) => self.update_single_occurrence(tx, task.id, data).await?,
+                },
+                BulkAction::Move(status) => {
+                    self.move_task_to_status_in_transaction(tx, task.id, status).await?;
+                }
+                BulkAction::Annotate(text) => {
+                    let annotation = Annotation { id: Uuid::now_v7(), task_id: task.id, text: text.clone(), created_at: now };
+                    Self::insert_annotation_in_transaction(tx, &annotation).await?;
+                }
+                BulkAction::LogTime(duration) => {
+                    let entry = TimeEntry {
+                        id: Uuid::now_v7(),
+                        task_id: task.id,
+                        started_at: now - *duration,
+                        ended_at: Some(now),
+                        duration_seconds: Some(duration.num_seconds()),
+                    };
+                    Self::insert_time_entry_in_transaction(tx, &entry).await?;
+                }
+            }
+        }
+
+        if let BulkAction::Delete = action {
+            return Ok(tasks);
+        }
+
+        // Completed occurrences make room for the next ones of active series
+        let (window_start, window_end) = self.materialization_manager().calculate_window_for_filters(&[]);
+        for series_id in completed_series {
+            let series: Option<TaskSeries> = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
+                .bind(series_id)
+                .fetch_optional(&mut **tx)
+                .await?;
+            if series.is_some_and(|s| s.active) {
+                Self::refresh_single_series_materialization_in_transaction(tx, series_id, window_start, window_end, now).await?;
+            }
+        }
+
+        let mut updated = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            let task = Self::find_task_by_id_in_transaction(tx, task.id)
+                .await?
+                .ok_or_else(|| CoreError::NotFound(task.id.to_string()))?;
+            updated.push(task);
+        }
+        Ok(updated)
+    }
+
+    /// Apply the subtask policy before `task_id` is completed: refuse while
+    /// subtasks outside `batch` are pending, or complete them along with it
+    async fn complete_subtasks<'a>(
+        &self,
+        tx: &mut Transaction<'a, Sqlite>,
+        task_id: Uuid,
+        batch: &[Uuid],
+        now: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        let policy = self.completion_policy().subtasks;
+        if policy == SubtaskPolicy::Allow {
+            return Ok(());
+        }
+
+        let pending: Vec<Task> = sqlx::query_as(
+            r#"WITH RECURSIVE subtree (id) AS (
+                SELECT id FROM tasks WHERE parent_id = $1
+                UNION
+                SELECT t.id FROM tasks t JOIN subtree s ON t.parent_id = s.id
+            )
+            SELECT t.* FROM tasks t JOIN subtree s ON s.id = t.id
+            WHERE t.status = 'pending'
+            ORDER BY t.created_at, t.id"#,
         )
-        .bind(TaskStatus::Cancelled)
-        .bind(Utc::now())
-        .bind(id)
-        .fetch_optional(self.pool())
-        .await?
-        .ok_or_else(|| CoreError::NotFound(id.to_string()))?;
+        .bind(task_id)
+        .fetch_all(&mut **tx)
+        .await?;
+        let pending: Vec<Task> = pending.into_iter().filter(|t| !batch.contains(&t.id)).collect();
+        if pending.is_empty() {
+            return Ok(());
+        }
 
-        Ok(updated_task)
+        if policy == SubtaskPolicy::Block {
+            let names = pending.iter().map(|t| t.name.as_str()).collect::<Vec<_>>().join(", ");
+            return Err(CoreError::PendingSubtasks(names));
+        }
+        for subtask in pending {
+            sqlx::query(
+                "UPDATE tasks SET status = $1, workflow_status = $2, completed_at = $3, updated_at = $3 WHERE id = $4",
+            )
+            .bind(TaskStatus::Completed)
+            .bind(TaskStatus::Completed.as_str())
+            .bind(now)
+            .bind(subtask.id)
+            .execute(&mut **tx)
+            .await?;
+        }
+        Ok(())
     }
 
-    async fn update_task(&self, id: Uuid, data: UpdateTaskData, scope: Option<crate::models::EditScope>) -> Result<Task, CoreError> {
-        let mut tx = self.pool().begin().await?;
+    /// Complete the ancestors of a just completed `task` that are left without
+    /// pending subtasks or dependencies, when the policy asks for it
+    async fn complete_finished_parents<'a>(
+        &self,
+        tx: &mut Transaction<'a, Sqlite>,
+        task: &Task,
+        now: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        if !self.completion_policy().complete_parent {
+            return Ok(());
+        }
 
-        let current_task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = $1")
+        let mut visited = vec![task.id];
+        let mut parent_id = task.parent_id;
+        while let Some(id) = parent_id.filter(|id| !visited.contains(id)) {
+            visited.push(id);
+            let Some(parent) = Self::find_task_by_id_in_transaction(tx, id).await? else {
+                break;
+            };
+            let unfinished: i64 = sqlx::query_scalar(
+                r#"SELECT
+                    (SELECT COUNT(*) FROM tasks WHERE parent_id = $1 AND status = 'pending')
+                    + (SELECT COUNT(*) FROM task_dependencies td JOIN tasks t ON t.id = td.depends_on_id
+                       WHERE td.task_id = $1 AND t.status != 'completed')"#,
+            )
             .bind(id)
-            .fetch_optional(&mut *tx)
-            .await?
-            .ok_or_else(|| CoreError::NotFound(id.to_string()))?;
-
-        // Dispatch based on series membership and edit scope
-        match (current_task.series_id, scope.unwrap_or(crate::models::EditScope::ThisOccurrence)) {
-            (None, _) => {
-                // Regular task - validate no recurrence changes
-                self.update_regular_task(&mut tx, id, &data).await?;
-            }
-            (Some(_), crate::models::EditScope::ThisOccurrence) => {
-                // Single occurrence edit
-                self.update_single_occurrence(&mut tx, id, &data).await?;
+            .fetch_one(&mut **tx)
+            .await?;
+            if parent.status != TaskStatus::Pending || unfinished > 0 {
+                break;
             }
-            (Some(_series_id), crate::models::EditScope::ThisAndFuture) => {
-                // Update from this occurrence forward
-                self.update_series_from_future(&mut tx, &current_task, &data).await?;
+
+            sqlx::query(
+                "UPDATE tasks SET status = $1, workflow_status = $2, completed_at = $3, updated_at = $3 WHERE id = $4",
+            )
+            .bind(TaskStatus::Completed)
+            .bind(TaskStatus::Completed.as_str())
+            .bind(now)
+            .bind(id)
+            .execute(&mut **tx)
+            .await?;
+            parent_id = parent.parent_id;
+        }
+        Ok(())
+    }
+
+    /// Cancel a task, recording `workflow_status` as its cancelled-category status
+    pub(crate) async fn cancel_task_as(&self, id: Uuid, workflow_status: &str) -> Result<Task, CoreError> {
+        let mut tx = self.pool().begin().await?;
+        match Self::cancel_task_in_transaction(&mut tx, id, workflow_status, self.now()).await {
+            Ok(task) => {
+                tx.commit().await?;
+                Ok(task)
             }
-            (Some(series_id), crate::models::EditScope::EntireSeries) => {
-                // Update entire series
-                self.update_entire_series(&mut tx, series_id, &data).await?;
+            Err(e) => {
+                tx.rollback().await?;
+                Err(e)
             }
         }
+    }
 
-        let updated_task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = $1")
-            .bind(id)
-            .fetch_one(&mut *tx)
-            .await?;
+    /// Cancel a task inside `tx`, if the workflow lets it move into `workflow_status`
+    pub(crate) async fn cancel_task_in_transaction<'a>(
+        tx: &mut Transaction<'a, Sqlite>,
+        id: Uuid,
+        workflow_status: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Task, CoreError> {
+        let task = Self::find_task_by_id_in_transaction(tx, id)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(id.to_string()))?;
+        let transitions = Self::find_workflow_transitions_in_transaction(tx).await?;
+        workflow::check_status_change(&task, workflow_status, &transitions)?;
+
+        let updated_task: Task = sqlx::query_as(
+            r#"UPDATE tasks
+            SET status = $1, workflow_status = $2, updated_at = $3
+            WHERE id = $4
+            RETURNING *
+            "#,
+        )
+        .bind(TaskStatus::Cancelled)
+        .bind(workflow_status)
+        .bind(now)
+        .bind(id)
+        .fetch_all(&mut **tx)
+        .await?
+        .pop()
+        .ok_or_else(|| CoreError::NotFound(id.to_string()))?;
 
-        tx.commit().await?;
         Ok(updated_task)
     }
 }
@@ -330,7 +757,7 @@ impl SqliteRepository {
             ));
         }
         
-        Self::update_task_fields(tx, task_id, data).await
+        Self::update_task_fields(tx, task_id, data, self.now()).await
     }
 
     /// Updates a single task occurrence with validation
@@ -346,7 +773,7 @@ impl SqliteRepository {
             ));
         }
         
-        Self::update_task_fields(tx, task_id, data).await
+        Self::update_task_fields(tx, task_id, data, self.now()).await
     }
 
     /// Updates series and re-materializes instances from a specific point forward
@@ -362,7 +789,7 @@ impl SqliteRepository {
         self.update_series_metadata(tx, series_id, data).await?;
         
         // Update template task
-        self.update_template_task(tx, series_id, data).await?;
+        self.update_template_task(tx, current_task, data).await?;
         
         // Clean future instances and reset materialization boundary
         if let Some(due_at) = current_task.due_at {
@@ -377,14 +804,15 @@ impl SqliteRepository {
     async fn update_entire_series<'a>(
         &self,
         tx: &mut Transaction<'a, Sqlite>,
-        series_id: Uuid,
+        current_task: &Task,
         data: &UpdateTaskData,
     ) -> Result<(), CoreError> {
+        let series_id = current_task.series_id.unwrap();
         // Update series metadata
         self.update_series_metadata(tx, series_id, data).await?;
         
         // Update template task
-        self.update_template_task(tx, series_id, data).await?;
+        self.update_template_task(tx, current_task, data).await?;
         
         // Clean all instances and reset materialization
         self.clean_all_instances(tx, series_id).await?;
@@ -394,9 +822,12 @@ impl SqliteRepository {
     }
 
     /// Updates series recurrence metadata (rrule, timezone)
+    ///
+    /// Runs inside the edit's transaction; a separate one would wait on the
+    /// write lock the edit already holds.
     async fn update_series_metadata<'a>(
         &self,
-        _tx: &mut Transaction<'a, Sqlite>,
+        tx: &mut Transaction<'a, Sqlite>,
         series_id: Uuid,
         data: &UpdateTaskData,
     ) -> Result<(), CoreError> {
@@ -408,7 +839,17 @@ impl SqliteRepository {
             if let Some(timezone) = &data.timezone {
                 series_update.timezone = timezone.clone();
             }
-            self.update_series(series_id, series_update).await?;
+
+            let current_series: crate::models::TaskSeries = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
+                .bind(series_id)
+                .fetch_one(&mut **tx)
+                .await?;
+            RecurrenceManager::validate_rrule_at(
+                series_update.rrule.as_deref().unwrap_or(&current_series.rrule),
+                series_update.timezone.as_deref().unwrap_or(&current_series.timezone),
+                current_series.dtstart,
+            )?;
+            Self::update_series_in_transaction(tx, series_id, series_update, self.now()).await?;
         }
         Ok(())
     }
@@ -417,19 +858,28 @@ impl SqliteRepository {
     async fn update_template_task<'a>(
         &self,
         tx: &mut Transaction<'a, Sqlite>,
-        series_id: Uuid,
+        current_task: &Task,
         data: &UpdateTaskData,
     ) -> Result<(), CoreError> {
         let series: crate::models::TaskSeries = sqlx::query_as("SELECT * FROM task_series WHERE id = $1")
-            .bind(series_id)
+            .bind(current_task.series_id)
+            .fetch_one(&mut **tx)
+            .await?;
+
+        let template: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = $1")
+            .bind(series.template_task_id)
             .fetch_one(&mut **tx)
             .await?;
 
-        let mut template_update = data.clone();
+        // Scheduling dates given for this occurrence keep their distance from due
+        let mut template_update = match template.due_at {
+            Some(due_at) => data.clone().dates_relative_to(current_task, due_at),
+            None => data.clone(),
+        };
         template_update.rrule = None;    // Don't update rrule on template
         template_update.timezone = None; // Don't update timezone on template
         
-        Self::update_task_fields(tx, series.template_task_id, &template_update).await
+        Self::update_task_fields(tx, series.template_task_id, &template_update, self.now()).await
     }
 
     /// Cleans future instances from a specific date forward
@@ -444,6 +894,7 @@ impl SqliteRepository {
             .fetch_one(&mut **tx)
             .await?;
 
+        Self::detach_instance_annotations(tx, series_id, series.template_task_id, Some(from_date)).await?;
         sqlx::query("DELETE FROM tasks WHERE series_id = $1 AND due_at >= $2 AND id != $3")
             .bind(series_id)
             .bind(from_date)
@@ -465,6 +916,7 @@ impl SqliteRepository {
             .fetch_one(&mut **tx)
             .await?;
 
+        Self::detach_instance_annotations(tx, series_id, series.template_task_id, None).await?;
         sqlx::query("DELETE FROM tasks WHERE series_id = $1 AND id != $2")
             .bind(series_id)
             .bind(series.template_task_id) // Don't delete template
@@ -504,10 +956,51 @@ impl SqliteRepository {
 }
 
 impl SqliteRepository {
+    /// Add a task within an existing transaction, creating and materializing
+    /// its series when it has an RRULE; returns the template in that case
+    pub(crate) async fn add_task_or_series_in_transaction<'a>(
+        &self,
+        tx: &mut Transaction<'a, Sqlite>,
+        data: NewTaskData,
+    ) -> Result<Task, CoreError> {
+        // Check if this is a recurring task
+        let Some(rrule) = &data.rrule else {
+            // Regular task
+            return Self::add_task_in_transaction(tx, data, self.now()).await;
+        };
+
+        // Create template task first
+        let mut template_data = data.clone();
+        template_data.rrule = None; // Remove rrule for template task
+        template_data.series_id = None; // Template tasks don't have series_id
+
+        let template_task = Self::add_task_in_transaction(tx, template_data, self.now()).await?;
+
+        // Create the series
+        let series_data = NewSeriesData {
+            template_task_id: template_task.id,
+            rrule: rrule.clone(),
+            dtstart: data.due_at.unwrap_or_else(|| self.now()),
+            timezone: data.timezone.unwrap_or_else(|| "UTC".to_string()),
+            time_mode: data.time_mode,
+            dst_policy: data.dst_policy,
+        };
+
+        // Create series using the transaction-aware method
+        let series = Self::create_series_in_transaction(tx, series_data, self.now()).await?;
+
+        // Trigger initial materialization for lookahead window
+        let (window_start, window_end) = self.materialization_manager().calculate_window_for_filters(&[]);
+        Self::refresh_single_series_materialization_in_transaction(tx, series.id, window_start, window_end, self.now()).await?;
+
+        Ok(template_task)
+    }
+
     /// Add a task within an existing transaction
     pub(crate) async fn add_task_in_transaction<'a>(
         tx: &mut Transaction<'a, Sqlite>,
         mut data: NewTaskData,
+        now: DateTime<Utc>,
     ) -> Result<Task, CoreError> {
         if data.project_id.is_none() {
             if let Some(project_name) = &data.project_name {
@@ -524,6 +1017,8 @@ impl SqliteRepository {
             }
         }
 
+        let estimate_minutes = data.estimate.map(forecast::estimate_minutes).transpose()?;
+
         let task = Task {
             id: Uuid::now_v7(),
             name: data.name,
@@ -532,16 +1027,22 @@ impl SqliteRepository {
             priority: data.priority.unwrap_or(TaskPriority::None),
             due_at: data.due_at,
             completed_at: None,
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
+            created_at: now,
+            updated_at: now,
             project_id: data.project_id,
             parent_id: data.parent_id,
             series_id: data.series_id,
+            workflow_status: TaskStatus::Pending.as_str().to_string(),
+            estimate_minutes,
+            scheduled_at: data.scheduled_at,
+            wait_until: data.wait_until,
+            start_at: data.start_at,
+            all_day: data.time_mode == TimeMode::AllDay,
         };
 
         sqlx::query(
-            r#"INSERT INTO tasks (id, name, description, status, priority, due_at, created_at, updated_at, project_id, parent_id, series_id)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            r#"INSERT INTO tasks (id, name, description, status, priority, due_at, created_at, updated_at, project_id, parent_id, series_id, estimate_minutes, scheduled_at, wait_until, start_at, all_day)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
             "#,
         )
         .bind(task.id)
@@ -555,6 +1056,11 @@ impl SqliteRepository {
         .bind(task.project_id)
         .bind(task.parent_id)
         .bind(task.series_id)
+        .bind(task.estimate_minutes)
+        .bind(task.scheduled_at)
+        .bind(task.wait_until)
+        .bind(task.start_at)
+        .bind(task.all_day)
         .execute(&mut **tx)
         .await?;
 
@@ -583,10 +1089,14 @@ impl SqliteRepository {
                 .await?;
         }
 
+        if let Some(remind_before) = data.remind_before {
+            Self::set_task_reminder_in_transaction(tx, task.id, remind_before, now).await?;
+        }
+
         let tags = data.tags;
         if !tags.is_empty() {
             let mut query_builder: QueryBuilder<sqlx::Sqlite> =
-                QueryBuilder::new("INSERT INTO task_tags (task_id, tag_name) ");
+                QueryBuilder::new("INSERT OR IGNORE INTO task_tags (task_id, tag_name) ");
             query_builder.push_values(tags.iter(), |mut b, tag| {
                 b.push_bind(task.id).push_bind(tag);
             });
@@ -613,9 +1123,11 @@ impl SqliteRepository {
         tx: &mut Transaction<'a, Sqlite>,
         id: Uuid,
         data: &UpdateTaskData,
+        now: DateTime<Utc>,
     ) -> Result<(), CoreError> {
         let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("UPDATE tasks SET ");
         let mut updated = false;
+        let mut dependencies_changed = false;
 
         if let Some(name) = &data.name {
             qb.push("name = ");
@@ -656,6 +1168,8 @@ impl SqliteRepository {
             }
             qb.push("status = ");
             qb.push_bind(status);
+            qb.push(", workflow_status = ");
+            qb.push_bind(status.as_str());
             updated = true;
         }
 
@@ -677,6 +1191,31 @@ impl SqliteRepository {
             updated = true;
         }
 
+        if let Some(estimate) = &data.estimate {
+            let estimate_minutes = estimate.map(forecast::estimate_minutes).transpose()?;
+            if updated {
+                qb.push(", ");
+            }
+            qb.push("estimate_minutes = ");
+            qb.push_bind(estimate_minutes);
+            updated = true;
+        }
+
+        for (column, date) in [
+            ("scheduled_at", data.scheduled_at),
+            ("wait_until", data.wait_until),
+            ("start_at", data.start_at),
+        ] {
+            if let Some(date) = date {
+                if updated {
+                    qb.push(", ");
+                }
+                qb.push(format!("{} = ", column));
+                qb.push_bind(date);
+                updated = true;
+            }
+        }
+
         if let Some(project_name_option) = &data.project_name {
             let project_id = match project_name_option {
                 Some(project_name) => {
@@ -738,7 +1277,21 @@ impl SqliteRepository {
                     .await?;
             }
             // If depends_on_option is None, the dependency is just cleared, which we already did.
-            updated = true;
+            dependencies_changed = true;
+        }
+
+        if let Some(remind_option) = &data.remind_before {
+            match remind_option {
+                Some(remind_before) => {
+                    Self::set_task_reminder_in_transaction(tx, id, *remind_before, now).await?;
+                }
+                None => {
+                    sqlx::query("DELETE FROM task_reminders WHERE task_id = $1")
+                        .bind(id)
+                        .execute(&mut **tx)
+                        .await?;
+                }
+            }
         }
 
         if let Some(tags_to_add) = &data.add_tags {
@@ -755,8 +1308,9 @@ impl SqliteRepository {
         if let Some(tags_to_remove) = &data.remove_tags {
             if !tags_to_remove.is_empty() {
                 let mut query_builder: QueryBuilder<sqlx::Sqlite> =
-                    QueryBuilder::new("DELETE FROM task_tags WHERE task_id = $1 AND tag_name IN (");
+                    QueryBuilder::new("DELETE FROM task_tags WHERE task_id = ");
                 query_builder.push_bind(id);
+                query_builder.push(" AND tag_name IN (");
                 let mut separated = query_builder.separated(", ");
                 for tag in tags_to_remove.iter() {
                     separated.push_bind(tag);
@@ -766,9 +1320,12 @@ impl SqliteRepository {
             }
         }
 
-        if updated {
-            qb.push(", updated_at = ");
-            qb.push_bind(Utc::now());
+        if updated || dependencies_changed {
+            if updated {
+                qb.push(", ");
+            }
+            qb.push("updated_at = ");
+            qb.push_bind(now);
             qb.push(" WHERE id = ");
             qb.push_bind(id);
             qb.build().execute(&mut **tx).await?;
@@ -0,0 +1,138 @@
+use crate::error::CoreError;
+use crate::models::{TimeEntry, TimesheetEntry};
+use crate::repository::SqliteRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::{Sqlite, Transaction};
+use uuid::Uuid;
+
+#[async_trait]
+impl super::TimeTrackingRepository for SqliteRepository {
+    async fn start_timer(&self, task_id: Uuid) -> Result<TimeEntry, CoreError> {
+        let mut tx = self.pool().begin().await?;
+
+        if Self::find_task_by_id_in_transaction(&mut tx, task_id).await?.is_none() {
+            return Err(CoreError::NotFound(task_id.to_string()));
+        }
+        if let Some(active) = Self::find_active_timer_in_transaction(&mut tx).await? {
+            let running = Self::find_task_by_id_in_transaction(&mut tx, active.task_id).await?;
+            return Err(CoreError::TimerRunning(running.map_or_else(|| active.task_id.to_string(), |t| t.name)));
+        }
+
+        let entry = TimeEntry {
+            id: Uuid::now_v7(),
+            task_id,
+            started_at: self.now(),
+            ended_at: None,
+            duration_seconds: None,
+        };
+        Self::insert_time_entry_in_transaction(&mut tx, &entry).await?;
+
+        tx.commit().await?;
+        Ok(entry)
+    }
+
+    async fn stop_timer(&self) -> Result<Option<TimeEntry>, CoreError> {
+        let mut tx = self.pool().begin().await?;
+
+        let Some(mut entry) = Self::find_active_timer_in_transaction(&mut tx).await? else {
+            return Ok(None);
+        };
+        let now = self.now();
+        entry.ended_at = Some(now);
+        entry.duration_seconds = Some((now - entry.started_at).num_seconds().max(0));
+
+        sqlx::query("UPDATE time_entries SET ended_at = $1, duration_seconds = $2 WHERE id = $3")
+            .bind(entry.ended_at)
+            .bind(entry.duration_seconds)
+            .bind(entry.id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(Some(entry))
+    }
+
+    async fn find_active_timer(&self) -> Result<Option<TimeEntry>, CoreError> {
+        let entry = sqlx::query_as("SELECT * FROM time_entries WHERE ended_at IS NULL")
+            .fetch_optional(self.pool())
+            .await?;
+        Ok(entry)
+    }
+
+    async fn log_time(&self, task_id: Uuid, duration: Duration) -> Result<TimeEntry, CoreError> {
+        if duration <= Duration::zero() {
+            return Err(CoreError::InvalidInput("Tracked time must be positive".to_string()));
+        }
+
+        let mut tx = self.pool().begin().await?;
+        if Self::find_task_by_id_in_transaction(&mut tx, task_id).await?.is_none() {
+            return Err(CoreError::NotFound(task_id.to_string()));
+        }
+
+        let now = self.now();
+        let entry = TimeEntry {
+            id: Uuid::now_v7(),
+            task_id,
+            started_at: now - duration,
+            ended_at: Some(now),
+            duration_seconds: Some(duration.num_seconds()),
+        };
+        Self::insert_time_entry_in_transaction(&mut tx, &entry).await?;
+
+        tx.commit().await?;
+        Ok(entry)
+    }
+
+    async fn find_time_entries(&self, task_id: Uuid) -> Result<Vec<TimeEntry>, CoreError> {
+        let entries = sqlx::query_as("SELECT * FROM time_entries WHERE task_id = $1 ORDER BY started_at, id")
+            .bind(task_id)
+            .fetch_all(self.pool())
+            .await?;
+        Ok(entries)
+    }
+
+    async fn find_timesheet_entries(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<TimesheetEntry>, CoreError> {
+        let entries = sqlx::query_as(
+            r#"SELECT te.task_id, t.name AS task_name, p.name AS project_name,
+                (SELECT GROUP_CONCAT(tt.tag_name) FROM task_tags tt WHERE tt.task_id = t.id) AS tags,
+                te.started_at, te.duration_seconds
+            FROM time_entries te
+            JOIN tasks t ON t.id = te.task_id
+            LEFT JOIN projects p ON p.id = t.project_id
+            WHERE te.ended_at IS NOT NULL
+            AND te.started_at >= $1
+            AND te.started_at < $2
+            ORDER BY te.started_at, te.id"#,
+        )
+        .bind(start)
+        .bind(end)
+        .fetch_all(self.pool())
+        .await?;
+        Ok(entries)
+    }
+}
+
+impl SqliteRepository {
+    async fn find_active_timer_in_transaction(tx: &mut Transaction<'_, Sqlite>) -> Result<Option<TimeEntry>, CoreError> {
+        let entry = sqlx::query_as("SELECT * FROM time_entries WHERE ended_at IS NULL")
+            .fetch_optional(&mut **tx)
+            .await?;
+        Ok(entry)
+    }
+
+    pub(crate) async fn insert_time_entry_in_transaction(tx: &mut Transaction<'_, Sqlite>, entry: &TimeEntry) -> Result<(), CoreError> {
+        sqlx::query(
+            r#"INSERT INTO time_entries (id, task_id, started_at, ended_at, duration_seconds)
+            VALUES ($1, $2, $3, $4, $5)"#,
+        )
+        .bind(entry.id)
+        .bind(entry.task_id)
+        .bind(entry.started_at)
+        .bind(entry.ended_at)
+        .bind(entry.duration_seconds)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,211 @@
+//! Habit statistics of a recurring series
+//!
+//! Built on the series timeline, these look at how regularly a series'
+//! occurrences get done on time: streaks of on-time completions, the on-time
+//! rate and lateness, completions per weekday, and rates over rolling windows
+//! compared with the window before.
+//!
+//! Each past occurrence counts as on time, late or missed. Skipped, cancelled
+//! and not yet due occurrences count as neither: they do not break a streak.
+
+use crate::error::CoreError;
+use crate::models::{ExceptionType, TaskSeries, TaskStatus};
+use crate::repository::Repository;
+use crate::series_history::{series_timeline, TimelineEntry, TimelineEvent};
+use chrono::{DateTime, Datelike, Duration, Utc, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+/// Lengths in days of the rolling windows in [`HabitStatistics::trends`]
+pub const TREND_WINDOW_DAYS: [i64; 3] = [7, 30, 90];
+
+/// How a past occurrence turned out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    OnTime,
+    Late,
+    Missed,
+}
+
+impl Outcome {
+    /// The outcome of a timeline entry, if it counts towards habit statistics
+    pub fn of(entry: &TimelineEntry) -> Option<Self> {
+        match &entry.event {
+            TimelineEvent::RuleChange(_) => return None,
+            TimelineEvent::Exception { exception, .. } if exception.exception_type == ExceptionType::Skip => return None,
+            _ => {}
+        }
+        if entry.missed {
+            return Some(Outcome::Missed);
+        }
+        let task = entry.task()?;
+        if task.status != TaskStatus::Completed {
+            return None;
+        }
+        match entry.lateness() {
+            Some(lateness) if lateness > Duration::zero() => Some(Outcome::Late),
+            _ => Some(Outcome::OnTime),
+        }
+    }
+}
+
+/// Occurrences due on one weekday, in the series' timezone
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeekdayCompletion {
+    pub weekday: Weekday,
+    /// Occurrences that were completed or missed
+    pub due: u32,
+    pub completed: u32,
+    pub on_time: u32,
+}
+
+impl WeekdayCompletion {
+    /// Share of the due occurrences that were completed
+    pub fn completion_rate(&self) -> Option<f64> {
+        rate(self.completed, self.due)
+    }
+}
+
+/// Occurrences due in the last `days` days
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrendWindow {
+    pub days: i64,
+    /// Occurrences that were completed or missed
+    pub due: u32,
+    pub completed: u32,
+    pub on_time: u32,
+    pub on_time_rate: Option<f64>,
+    /// On-time rate over the `days` days before the window
+    pub previous_on_time_rate: Option<f64>,
+}
+
+/// Habit statistics of a series, see the [module docs](self)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HabitStatistics {
+    /// On-time completions since the last late or missed occurrence
+    pub current_streak: u32,
+    pub longest_streak: u32,
+    /// Occurrences that were completed or missed
+    pub due: u32,
+    pub completed: u32,
+    pub on_time: u32,
+    pub on_time_rate: Option<f64>,
+    /// Mean time between due and completion, early completions counting as
+    /// negative
+    pub average_lateness_hours: Option<f64>,
+    /// Monday first
+    pub weekdays: Vec<WeekdayCompletion>,
+    pub trends: Vec<TrendWindow>,
+}
+
+fn rate(count: u32, total: u32) -> Option<f64> {
+    (total > 0).then(|| count as f64 / total as f64)
+}
+
+#[derive(Default)]
+struct Tally {
+    due: u32,
+    completed: u32,
+    on_time: u32,
+}
+
+impl Tally {
+    fn add(&mut self, outcome: Outcome) {
+        self.due += 1;
+        self.completed += u32::from(outcome != Outcome::Missed);
+        self.on_time += u32::from(outcome == Outcome::OnTime);
+    }
+
+    fn on_time_rate(&self) -> Option<f64> {
+        rate(self.on_time, self.due)
+    }
+}
+
+impl HabitStatistics {
+    /// Statistics over `entries`, a timeline as returned by
+    /// [`series_timeline`], as of `now`
+    pub fn new(entries: &[TimelineEntry], timezone: Tz, now: DateTime<Utc>) -> Self {
+        let mut outcomes: Vec<(DateTime<Utc>, Outcome)> =
+            entries.iter().filter_map(|entry| Outcome::of(entry).map(|outcome| (entry.at, outcome))).collect();
+        outcomes.sort_by_key(|(at, _)| *at);
+
+        let mut current_streak = 0;
+        let mut longest_streak = 0;
+        let mut total = Tally::default();
+        let mut weekdays: [Tally; 7] = Default::default();
+        for &(at, outcome) in &outcomes {
+            if outcome == Outcome::OnTime {
+                current_streak += 1;
+                longest_streak = longest_streak.max(current_streak);
+            } else {
+                current_streak = 0;
+            }
+            total.add(outcome);
+            weekdays[at.with_timezone(&timezone).weekday().num_days_from_monday() as usize].add(outcome);
+        }
+
+        let lateness: Vec<Duration> = entries.iter().filter_map(TimelineEntry::lateness).collect();
+        let average_lateness_hours = (!lateness.is_empty()).then(|| {
+            lateness.iter().map(|l| l.num_seconds()).sum::<i64>() as f64 / lateness.len() as f64 / 3600.0
+        });
+
+        let tally_between = |from: DateTime<Utc>, until: DateTime<Utc>| {
+            let mut tally = Tally::default();
+            for &(_, outcome) in outcomes.iter().filter(|(at, _)| *at > from && *at <= until) {
+                tally.add(outcome);
+            }
+            tally
+        };
+        let trends = TREND_WINDOW_DAYS
+            .iter()
+            .map(|&days| {
+                let window_start = now - Duration::days(days);
+                let window = tally_between(window_start, now);
+                let previous = tally_between(window_start - Duration::days(days), window_start);
+                TrendWindow {
+                    days,
+                    due: window.due,
+                    completed: window.completed,
+                    on_time: window.on_time,
+                    on_time_rate: window.on_time_rate(),
+                    previous_on_time_rate: previous.on_time_rate(),
+                }
+            })
+            .collect();
+
+        HabitStatistics {
+            current_streak,
+            longest_streak,
+            due: total.due,
+            completed: total.completed,
+            on_time: total.on_time,
+            on_time_rate: total.on_time_rate(),
+            average_lateness_hours,
+            weekdays: weekdays
+                .iter()
+                .enumerate()
+                .map(|(day, tally)| WeekdayCompletion {
+                    weekday: Weekday::try_from(day as u8).expect("seven weekdays"),
+                    due: tally.due,
+                    completed: tally.completed,
+                    on_time: tally.on_time,
+                })
+                .collect(),
+            trends,
+        }
+    }
+}
+
+/// Habit statistics of `series` as of `now`
+pub async fn series_habits<R: Repository + Sync + ?Sized>(
+    repo: &R,
+    series: &TaskSeries,
+    now: DateTime<Utc>,
+) -> Result<HabitStatistics, CoreError> {
+    let timezone: Tz = series
+        .timezone
+        .parse()
+        .map_err(|_| CoreError::InvalidTimezone(format!("Invalid timezone: {}", series.timezone)))?;
+    let timeline = series_timeline(repo, series, now).await?;
+    Ok(HabitStatistics::new(&timeline, timezone, now))
+}
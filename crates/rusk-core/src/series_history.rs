@@ -0,0 +1,214 @@
+//! Timeline of a recurring series
+//!
+//! The timeline merges what happened to a series into one chronological
+//! list: its materialized instances with their status and how late they were
+//! completed, its skipped, overridden and moved occurrences, and the edits of
+//! its rule. Past occurrences that were never completed are marked as missed,
+//! including those that never got an instance at all.
+
+use crate::error::CoreError;
+use crate::models::{ExceptionType, SeriesException, SeriesRuleChange, Task, TaskSeries, TaskStatus};
+use crate::recurrence::RecurrenceManager;
+use crate::repository::Repository;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashSet;
+
+/// How far ahead of now materialized instances are looked up
+const INSTANCE_LOOKAHEAD_DAYS: i64 = 3650;
+
+/// What a timeline entry records
+#[derive(Debug, Clone)]
+pub enum TimelineEvent {
+    /// A materialized instance of an occurrence
+    Instance(Task),
+    /// A skipped, overridden or moved occurrence; overrides and moves carry
+    /// their task while it exists
+    Exception {
+        exception: SeriesException,
+        task: Option<Task>,
+    },
+    /// A past occurrence of the current rule that has neither an instance nor
+    /// an exception, e.g. because its instance was deleted
+    Unmaterialized,
+    /// An edit of the series' RRULE, start or timezone
+    RuleChange(SeriesRuleChange),
+}
+
+/// One entry of a series timeline
+#[derive(Debug, Clone)]
+pub struct TimelineEntry {
+    /// The occurrence time, or when the rule was edited
+    pub at: DateTime<Utc>,
+    pub event: TimelineEvent,
+    /// A past occurrence that was never completed or cancelled
+    pub missed: bool,
+}
+
+impl TimelineEntry {
+    /// The task standing for the entry's occurrence, if it has one
+    pub fn task(&self) -> Option<&Task> {
+        match &self.event {
+            TimelineEvent::Instance(task) => Some(task),
+            TimelineEvent::Exception { task, .. } => task.as_ref(),
+            TimelineEvent::Unmaterialized | TimelineEvent::RuleChange(_) => None,
+        }
+    }
+
+    /// How long after its due time the entry's task was completed
+    pub fn lateness(&self) -> Option<Duration> {
+        self.task().and_then(completion_lateness)
+    }
+}
+
+/// How long after its due time a completed task was completed; negative when
+/// it was completed early
+///
+/// All-day tasks count as due at the end of their day.
+pub fn completion_lateness(task: &Task) -> Option<Duration> {
+    if task.status != TaskStatus::Completed {
+        return None;
+    }
+    Some(task.completed_at? - deadline(task)?)
+}
+
+fn deadline(task: &Task) -> Option<DateTime<Utc>> {
+    task.due_at.map(|due| if task.all_day { due + Duration::days(1) } else { due })
+}
+
+fn is_missed(task: &Task, now: DateTime<Utc>) -> bool {
+    task.status == TaskStatus::Pending && deadline(task).is_some_and(|deadline| deadline < now)
+}
+
+/// Totals over a timeline
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TimelineSummary {
+    pub completed: usize,
+    /// Completed after their due time
+    pub completed_late: usize,
+    pub cancelled: usize,
+    pub missed: usize,
+    pub skipped: usize,
+    pub rule_changes: usize,
+    /// Mean lateness of the completed occurrences, early ones counting as
+    /// negative
+    pub average_lateness: Option<Duration>,
+}
+
+impl TimelineSummary {
+    pub fn new(entries: &[TimelineEntry]) -> Self {
+        let mut summary = Self::default();
+        let mut total_lateness = Duration::zero();
+        for entry in entries {
+            summary.missed += usize::from(entry.missed);
+            match &entry.event {
+                TimelineEvent::RuleChange(_) => summary.rule_changes += 1,
+                TimelineEvent::Exception { exception, .. } if exception.exception_type == ExceptionType::Skip => {
+                    summary.skipped += 1
+                }
+                _ => {}
+            }
+            match entry.task().map(|task| &task.status) {
+                Some(TaskStatus::Completed) => summary.completed += 1,
+                Some(TaskStatus::Cancelled) => summary.cancelled += 1,
+                _ => {}
+            }
+            if let Some(lateness) = entry.lateness() {
+                total_lateness += lateness;
+                summary.completed_late += usize::from(lateness > Duration::zero());
+            }
+        }
+        let with_lateness = entries.iter().filter(|e| e.lateness().is_some()).count();
+        if with_lateness > 0 {
+            summary.average_lateness = Some(total_lateness / with_lateness as i32);
+        }
+        summary
+    }
+}
+
+/// The timeline of `series` as of `now`, oldest entry first
+///
+/// Instances are matched to occurrences by their due time, and overridden or
+/// moved occurrences through their exception's task. Occurrences without an
+/// instance are only looked for from the latest of the series' creation, its
+/// start and its last rule edit, and only while the series is active: earlier
+/// rules and paused stretches did not ask for them.
+pub async fn series_timeline<R: Repository + Sync + ?Sized>(
+    repo: &R,
+    series: &TaskSeries,
+    now: DateTime<Utc>,
+) -> Result<Vec<TimelineEntry>, CoreError> {
+    let template = repo
+        .find_task_by_id(series.template_task_id)
+        .await?
+        .ok_or_else(|| CoreError::NotFound(format!("Template task with id {} not found", series.template_task_id)))?;
+    let exceptions = repo.find_series_exceptions(series.id).await?;
+    let rule_changes = repo.find_series_rule_changes(series.id).await?;
+
+    let exception_tasks: HashSet<_> = exceptions.iter().filter_map(|e| e.exception_task_id).collect();
+    let first_instance = series.dtstart.min(series.created_at);
+    let instances: Vec<Task> = repo
+        .find_materialized_tasks_for_series(series.id, first_instance, now + Duration::days(INSTANCE_LOOKAHEAD_DAYS))
+        .await?
+        .into_iter()
+        .filter(|t| t.id != template.id && !exception_tasks.contains(&t.id))
+        .collect();
+
+    let mut entries = Vec::new();
+    let mut accounted: HashSet<DateTime<Utc>> = HashSet::new();
+    for task in instances {
+        let Some(due_at) = task.due_at else {
+            continue;
+        };
+        accounted.insert(due_at);
+        entries.push(TimelineEntry {
+            at: due_at,
+            missed: is_missed(&task, now),
+            event: TimelineEvent::Instance(task),
+        });
+    }
+
+    for exception in &exceptions {
+        accounted.insert(exception.occurrence_dt);
+        let task = match exception.exception_task_id {
+            Some(task_id) => repo.find_task_by_id(task_id).await?,
+            None => None,
+        };
+        entries.push(TimelineEntry {
+            at: exception.occurrence_dt,
+            missed: task.as_ref().is_some_and(|t| is_missed(t, now)),
+            event: TimelineEvent::Exception { exception: exception.clone(), task },
+        });
+    }
+
+    if series.active {
+        let mut from = series.dtstart.max(series.created_at);
+        if let Some(last_edit) = rule_changes.last() {
+            from = from.max(last_edit.changed_at);
+        }
+        let manager = RecurrenceManager::new(series.clone(), template, exceptions)?;
+        // A day at a time, since longer windows cap the occurrences generated
+        while from < now {
+            let until = (from + Duration::days(1)).min(now);
+            for occurrence in manager.generate_occurrences_between(from, until)? {
+                if occurrence.occurrence_dt >= until || occurrence.has_exception || accounted.contains(&occurrence.occurrence_dt) {
+                    continue;
+                }
+                accounted.insert(occurrence.occurrence_dt);
+                entries.push(TimelineEntry {
+                    at: occurrence.occurrence_dt,
+                    event: TimelineEvent::Unmaterialized,
+                    missed: true,
+                });
+            }
+            from = until;
+        }
+    }
+
+    entries.extend(rule_changes.into_iter().map(|change| TimelineEntry {
+        at: change.changed_at,
+        event: TimelineEvent::RuleChange(change),
+        missed: false,
+    }));
+    entries.sort_by_key(|e| e.at);
+    Ok(entries)
+}
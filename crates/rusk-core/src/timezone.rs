@@ -1,5 +1,6 @@
 use crate::error::CoreError;
-use chrono::{DateTime, Utc, Datelike, TimeZone, Offset};
+use crate::models::{DstGapPolicy, DstOverlapPolicy, DstPolicy};
+use chrono::{DateTime, Duration, LocalResult, NaiveDateTime, Utc, Datelike, TimeZone, Offset};
 use chrono_tz::Tz;
 use std::str::FromStr;
 
@@ -29,6 +30,9 @@ pub fn is_dst_active(timezone: &str, at_time: DateTime<Utc>) -> Result<bool, Cor
 }
 
 /// Handle DST transitions correctly for recurring events
+///
+/// `target_local_time` on the local date of `original_time`, resolved with
+/// the default [`DstPolicy`].
 pub fn handle_dst_transition(
     original_time: DateTime<Utc>,
     timezone: &str,
@@ -38,22 +42,37 @@ pub fn handle_dst_transition(
         .map_err(|_| CoreError::InvalidTimezone(format!("Invalid timezone: {}", timezone)))?;
     
     let original_local = original_time.with_timezone(&tz);
-    let target_date = original_local.date_naive();
+    let target_naive = original_local.date_naive().and_time(target_local_time);
     
-    // Try to create the target datetime in the local timezone
-    let target_naive = target_date.and_time(target_local_time);
-    
-    // Handle ambiguous times during DST transitions
-    match tz.from_local_datetime(&target_naive).earliest() {
-        Some(local_dt) => Ok(local_dt.with_timezone(&Utc)),
-        None => {
-            // Time doesn't exist (spring forward) - move to next valid time
-            let next_time = target_local_time.overflowing_add_signed(chrono::Duration::hours(1)).0;
-            let next_naive = target_date.and_time(next_time);
-            match tz.from_local_datetime(&next_naive).earliest() {
-                Some(local_dt) => Ok(local_dt.with_timezone(&Utc)),
-                None => Ok(original_time), // Fallback to original time
-            }
+    Ok(resolve_local_time(tz, target_naive, DstPolicy::default()).unwrap_or(original_time))
+}
+
+/// `local` read as a wall-clock time in `tz`
+///
+/// A time that does not exist because the clocks spring forward is moved by
+/// the length of the gap, or left out (`None`), as `policy.dst_gap` says; a
+/// time that exists twice because the clocks fall back takes the instant
+/// `policy.dst_overlap` picks.
+pub fn resolve_local_time(tz: Tz, local: NaiveDateTime, policy: DstPolicy) -> Option<DateTime<Utc>> {
+    match tz.from_local_datetime(&local) {
+        LocalResult::Single(dt) => Some(dt.with_timezone(&Utc)),
+        LocalResult::Ambiguous(earliest, latest) => {
+            let dt = match policy.dst_overlap {
+                DstOverlapPolicy::Earliest => earliest,
+                DstOverlapPolicy::Latest => latest,
+            };
+            Some(dt.with_timezone(&Utc))
+        }
+        LocalResult::None => {
+            // Reading the time with the offset from before the gap lands
+            // after it, and with the offset from after the gap before it
+            let offset_at = |utc: NaiveDateTime| tz.offset_from_utc_datetime(&utc).fix().local_minus_utc();
+            let offset = match policy.dst_gap {
+                DstGapPolicy::ShiftForward => offset_at(local - Duration::days(1)),
+                DstGapPolicy::ShiftBackward => offset_at(local + Duration::days(1)),
+                DstGapPolicy::Skip => return None,
+            };
+            Some((local - Duration::seconds(offset.into())).and_utc())
         }
     }
 }
@@ -0,0 +1,153 @@
+//! Urgency scoring for deciding what to work on next
+//!
+//! Urgency is a weighted sum of task properties. Each property contributes a
+//! factor between 0 and 1 (or a flag) multiplied by a configurable
+//! coefficient, so the score of a task reads as "how much each reason adds".
+
+use crate::models::{TaskPriority, TaskStatus};
+use crate::repository::TaskQueryResult;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// Weights of the urgency factors
+#[derive(Debug, Clone, PartialEq)]
+pub struct UrgencyCoefficients {
+    pub priority_high: f64,
+    pub priority_medium: f64,
+    pub priority_low: f64,
+    /// Weight of due date proximity, reaching its maximum a week past due
+    pub due: f64,
+    /// Bonus for tasks other pending tasks depend on
+    pub blocking: f64,
+    /// Penalty (usually negative) for tasks waiting on a pending dependency
+    pub blocked: f64,
+    /// Penalty for tasks that are waiting or scheduled for later
+    pub waiting: f64,
+    /// Weight of task age, reaching its maximum at `age_max_days`
+    pub age: f64,
+    pub age_max_days: f64,
+    /// Bonus for belonging to any project
+    pub has_project: f64,
+    /// Bonus for having tags, growing up to three tags
+    pub has_tags: f64,
+    /// Extra weight for specific projects, by name
+    pub projects: HashMap<String, f64>,
+    /// Extra weight for specific tags, by name
+    pub tags: HashMap<String, f64>,
+}
+
+impl Default for UrgencyCoefficients {
+    fn default() -> Self {
+        Self {
+            priority_high: 6.0,
+            priority_medium: 3.9,
+            priority_low: 1.8,
+            due: 12.0,
+            blocking: 8.0,
+            blocked: -5.0,
+            waiting: -3.0,
+            age: 2.0,
+            age_max_days: 365.0,
+            has_project: 1.0,
+            has_tags: 1.0,
+            projects: HashMap::new(),
+            tags: HashMap::new(),
+        }
+    }
+}
+
+/// Compute the urgency of a task at `now`
+///
+/// Only pending tasks are urgent; completed and cancelled tasks score 0.
+pub fn urgency(task: &TaskQueryResult, coefficients: &UrgencyCoefficients, now: DateTime<Utc>) -> f64 {
+    if task.status != TaskStatus::Pending {
+        return 0.0;
+    }
+
+    let mut score = match task.priority {
+        TaskPriority::High => coefficients.priority_high,
+        TaskPriority::Medium => coefficients.priority_medium,
+        TaskPriority::Low => coefficients.priority_low,
+        TaskPriority::None => 0.0,
+    };
+
+    if let Some(due_at) = task.due_at {
+        score += coefficients.due * due_factor(due_at, now);
+    }
+
+    if task.is_blocking {
+        score += coefficients.blocking;
+    }
+    if task.is_blocked {
+        score += coefficients.blocked;
+    }
+    let not_before = |date: Option<DateTime<Utc>>| date.is_some_and(|date| date > now);
+    if not_before(task.wait_until) || not_before(task.scheduled_at) {
+        score += coefficients.waiting;
+    }
+
+    if coefficients.age_max_days > 0.0 {
+        let age_days = (now - task.created_at).num_seconds().max(0) as f64 / 86_400.0;
+        score += coefficients.age * (age_days / coefficients.age_max_days).min(1.0);
+    }
+
+    if let Some(project) = &task.project_name {
+        score += coefficients.has_project + coefficients.projects.get(project).copied().unwrap_or(0.0);
+    }
+
+    let tags: Vec<&str> = task.tags.as_deref().map_or_else(Vec::new, |t| t.split(',').collect());
+    score += coefficients.has_tags * match tags.len() {
+        0 => 0.0,
+        1 => 0.8,
+        2 => 0.9,
+        _ => 1.0,
+    };
+    score += tags
+        .iter()
+        .filter_map(|tag| coefficients.tags.get(*tag))
+        .sum::<f64>();
+
+    score
+}
+
+/// Due date factor: 0.2 two weeks or more ahead, rising linearly to 1.0 a week overdue
+fn due_factor(due_at: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+    let days_overdue = (now - due_at).num_seconds() as f64 / 86_400.0;
+    if days_overdue >= 7.0 {
+        1.0
+    } else if days_overdue >= -14.0 {
+        (days_overdue + 14.0) * 0.8 / 21.0 + 0.2
+    } else {
+        0.2
+    }
+}
+
+/// Comparison used by `urgency:` filters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Equal,
+}
+
+/// A condition on the urgency score, such as `urgency:>5`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UrgencyCondition {
+    pub comparison: Comparison,
+    pub value: f64,
+}
+
+impl UrgencyCondition {
+    /// Whether `score` satisfies the condition
+    pub fn matches(&self, score: f64) -> bool {
+        match self.comparison {
+            Comparison::Greater => score > self.value,
+            Comparison::GreaterOrEqual => score >= self.value,
+            Comparison::Less => score < self.value,
+            Comparison::LessOrEqual => score <= self.value,
+            Comparison::Equal => (score - self.value).abs() < 0.05,
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Rules for custom workflow statuses shared by all repository backends
+
+use crate::error::CoreError;
+use crate::models::{Task, TaskStatus, WorkflowStatus, WorkflowTransition};
+use std::str::FromStr;
+
+/// Validate and normalize a workflow status name to its stored lowercase form
+pub fn normalize_status_name(name: &str) -> Result<String, CoreError> {
+    let normalized = name.trim().to_lowercase();
+    if normalized.is_empty() {
+        return Err(CoreError::InvalidInput("Status name cannot be empty".to_string()));
+    }
+    if !normalized.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(CoreError::InvalidInput(format!(
+            "Invalid status name '{}'. Use letters, digits, '-' and '_' only",
+            name
+        )));
+    }
+    Ok(normalized)
+}
+
+/// Whether `name` is one of the built-in statuses named after a base category
+pub fn is_builtin_status(name: &str) -> bool {
+    TaskStatus::from_str(name).is_ok()
+}
+
+/// Check that a task may move from status `from` to status `to`
+///
+/// Moves are unrestricted until at least one transition leaves `from`.
+pub fn check_transition(from: &str, to: &str, transitions: &[WorkflowTransition]) -> Result<(), CoreError> {
+    let allowed: Vec<&str> = transitions
+        .iter()
+        .filter(|t| t.from_status == from)
+        .map(|t| t.to_status.as_str())
+        .collect();
+
+    if allowed.is_empty() || allowed.contains(&to) {
+        return Ok(());
+    }
+
+    Err(CoreError::InvalidTransition(format!(
+        "'{}' -> '{}' (allowed: {})",
+        from,
+        to,
+        allowed.join(", ")
+    )))
+}
+
+/// Check that `task` may be completed or cancelled into `status`
+///
+/// `rusk do` and `rusk cancel` follow the same transitions as `rusk move`; a
+/// task already in `status` stays where it is.
+pub(crate) fn check_status_change(task: &Task, status: &str, transitions: &[WorkflowTransition]) -> Result<(), CoreError> {
+    if task.workflow_status == status {
+        return Ok(());
+    }
+    check_transition(&task.workflow_status, status, transitions)
+}
+
+/// What a backend has to do to move a task into a workflow status
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MoveAction {
+    /// The task is already in the target status
+    Unchanged,
+    /// Complete the task, advancing its series
+    Complete,
+    /// Cancel the task
+    Cancel,
+    /// Only rewrite the status columns; `reopen` also clears `completed_at`
+    Relabel { reopen: bool },
+}
+
+/// Validate a move of `task` into `target` and decide how to apply it
+pub(crate) fn plan_move(
+    task: &Task,
+    target: &WorkflowStatus,
+    transitions: &[WorkflowTransition],
+) -> Result<MoveAction, CoreError> {
+    if task.workflow_status == target.name {
+        return Ok(MoveAction::Unchanged);
+    }
+    check_transition(&task.workflow_status, &target.name, transitions)?;
+
+    Ok(match (&task.status, &target.category) {
+        (current, category) if current == category => MoveAction::Relabel { reopen: false },
+        (_, TaskStatus::Completed) => MoveAction::Complete,
+        (_, TaskStatus::Cancelled) => MoveAction::Cancel,
+        (_, TaskStatus::Pending) => MoveAction::Relabel { reopen: true },
+    })
+}
+
+pub(crate) fn unknown_status(name: &str) -> CoreError {
+    CoreError::InvalidInput(format!("Unknown status '{}'", name))
+}
+
+pub(crate) fn duplicate_status(name: &str) -> CoreError {
+    CoreError::InvalidInput(format!("Status '{}' already exists", name))
+}
+
+/// Reject deleting built-in statuses and statuses that tasks are still in
+pub(crate) fn check_deletable(name: &str, tasks_in_status: i64) -> Result<(), CoreError> {
+    if is_builtin_status(name) {
+        return Err(CoreError::InvalidInput(format!("Built-in status '{}' cannot be deleted", name)));
+    }
+    if tasks_in_status > 0 {
+        return Err(CoreError::InvalidInput(format!(
+            "{} task(s) are in status '{}'; move them to another status first",
+            tasks_in_status, name
+        )));
+    }
+    Ok(())
+}
+
+pub(crate) fn transition_not_found(from: &str, to: &str) -> CoreError {
+    CoreError::NotFound(format!("Transition '{}' -> '{}'", from, to))
+}
+
+/// Order statuses for display: pending, completed then cancelled categories,
+/// each by position and name
+pub fn sort_statuses(statuses: &mut [WorkflowStatus]) {
+    statuses.sort_by(|a, b| {
+        category_rank(&a.category)
+            .cmp(&category_rank(&b.category))
+            .then(a.position.cmp(&b.position))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+fn category_rank(category: &TaskStatus) -> u8 {
+    match category {
+        TaskStatus::Pending => 0,
+        TaskStatus::Completed => 1,
+        TaskStatus::Cancelled => 2,
+    }
+}
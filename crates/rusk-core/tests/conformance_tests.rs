@@ -0,0 +1,2217 @@
+//! Behaviour shared by every repository backend.
+//!
+//! Each check is written once against the `Repository` trait and then run
+//! against `SqliteRepository`, `InMemoryRepository` and, when
+//! `RUSK_TEST_POSTGRES_URL` points at a scratch database (for example
+//! `postgres://postgres@localhost/rusk_test`), `PostgresRepository`.
+
+use rusk_core::blueprints::{capture_blueprint, project_roots};
+use rusk_core::clock::{Clock, FakeClock};
+use rusk_core::db::{connect_postgres, connect_sqlite};
+use rusk_core::error::CoreError;
+use rusk_core::exception_patterns::{apply_exception_plan, plan_exceptions, OccurrencePattern, PatternAction};
+use rusk_core::forecast;
+use rusk_core::models::*;
+use rusk_core::query::{DueDate, Filter as QueryFilter, Operator, Query, TagFilter, TextFilter};
+use rusk_core::recurrence::{recurrence_lines, MaterializationManager, RecurrenceManager};
+use rusk_core::repository::{InMemoryRepository, PostgresRepository, Repository, SqliteRepository};
+use rusk_core::series_habits::series_habits;
+use rusk_core::series_history::{series_timeline, TimelineEvent, TimelineSummary};
+use rusk_core::urgency::{self, UrgencyCoefficients};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc, Weekday};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+/// Monday morning, so weekday-based rules have a predictable start
+fn start_time() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 5, 6, 9, 0, 0).unwrap()
+}
+
+async fn sqlite_backend(_test: &str) -> Option<(SqliteRepository, FakeClock, Option<TempDir>)> {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+    let db_path = temp_dir.path().join("conformance.db");
+    let pool = connect_sqlite(&db_path.to_string_lossy())
+        .await
+        .expect("Failed to establish test database connection");
+
+    let clock = FakeClock::new(start_time());
+    let repo = SqliteRepository::with_clock(pool, MaterializationManager::with_defaults(), Arc::new(clock.clone()));
+    Some((repo, clock, Some(temp_dir)))
+}
+
+async fn memory_backend(_test: &str) -> Option<(InMemoryRepository, FakeClock, Option<TempDir>)> {
+    let clock = FakeClock::new(start_time());
+    let repo = InMemoryRepository::with_clock(MaterializationManager::with_defaults(), Arc::new(clock.clone()));
+    Some((repo, clock, None))
+}
+
+/// Each test runs in its own schema, recreated on every run, so tests can
+/// share one database and a failed run can be inspected afterwards
+async fn postgres_backend(test: &str) -> Option<(PostgresRepository, FakeClock, Option<TempDir>)> {
+    let Ok(url) = std::env::var("RUSK_TEST_POSTGRES_URL") else {
+        eprintln!("RUSK_TEST_POSTGRES_URL is not set, skipping PostgreSQL conformance test");
+        return None;
+    };
+
+    let schema = format!("rusk_conformance_{}", test);
+    let admin = sqlx::PgPool::connect(&url).await.expect("Failed to connect to PostgreSQL");
+    sqlx::query(&format!("DROP SCHEMA IF EXISTS {} CASCADE", schema)).execute(&admin).await.unwrap();
+    sqlx::query(&format!("CREATE SCHEMA {}", schema)).execute(&admin).await.unwrap();
+    admin.close().await;
+
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let pool = connect_postgres(&format!("{}{}options[search_path]={}", url, separator, schema))
+        .await
+        .expect("Failed to establish PostgreSQL test connection");
+
+    let clock = FakeClock::new(start_time());
+    let repo = PostgresRepository::with_clock(pool, MaterializationManager::with_defaults(), Arc::new(clock.clone()));
+    Some((repo, clock, None))
+}
+
+fn ids<T>(items: &[T], id: impl Fn(&T) -> Uuid) -> HashSet<Uuid> {
+    items.iter().map(id).collect()
+}
+
+async fn query_ids<R: Repository + Sync>(repo: &R, query: Query) -> HashSet<Uuid> {
+    let results = repo.find_tasks_with_details(&query).await.unwrap();
+    ids(&results, |r| r.id)
+}
+
+async fn check_task_lifecycle<R: Repository + Sync>(repo: &R, clock: &FakeClock) {
+    let project = repo.add_project("Home".to_string(), None).await.unwrap();
+    let task = repo.add_task(NewTaskData {
+        name: "Fix sink".to_string(),
+        description: Some("Kitchen".to_string()),
+        priority: Some(TaskPriority::High),
+        project_name: Some("Home".to_string()),
+        tags: vec!["plumbing".to_string(), "urgent".to_string()],
+        due_at: Some(start_time() + Duration::days(1)),
+        ..Default::default()
+    }).await.unwrap();
+    assert_eq!(task.status, TaskStatus::Pending);
+    assert_eq!(task.project_id, Some(project.id));
+    assert_eq!(task.created_at, start_time());
+
+    let found = repo.find_task_by_id(task.id).await.unwrap().unwrap();
+    assert_eq!(found.name, "Fix sink");
+    assert!(repo.find_task_by_id(Uuid::now_v7()).await.unwrap().is_none());
+
+    let short_id = &task.id.to_string()[..8];
+    let matches = repo.find_tasks_by_short_id_prefix(short_id).await.unwrap();
+    assert_eq!(ids(&matches, |t| t.id), HashSet::from([task.id]));
+
+    clock.advance(Duration::hours(1));
+    let updated = repo.update_task(task.id, UpdateTaskData {
+        name: Some("Fix kitchen sink".to_string()),
+        description: Some(None),
+        project_name: Some(None),
+        add_tags: Some(vec!["weekend".to_string()]),
+        remove_tags: Some(vec!["urgent".to_string()]),
+        ..Default::default()
+    }, None).await.unwrap();
+    assert_eq!(updated.name, "Fix kitchen sink");
+    assert_eq!(updated.description, None);
+    assert_eq!(updated.project_id, None);
+    assert_eq!(updated.updated_at, start_time() + Duration::hours(1));
+
+    let details = repo.find_tasks_with_details(&Query::Filter(QueryFilter::Name(TextFilter::Equals("fix kitchen sink".to_string())))).await.unwrap();
+    assert_eq!(details.len(), 1);
+    assert_eq!(details[0].tags.as_deref(), Some("plumbing,weekend"));
+    assert_eq!(details[0].project_name, None);
+
+    let err = repo.update_task(task.id, UpdateTaskData {
+        rrule: Some(Some("FREQ=DAILY".to_string())),
+        ..Default::default()
+    }, None).await.unwrap_err();
+    assert!(matches!(err, CoreError::InvalidInput(_)));
+
+    let cancelled = repo.cancel_task(task.id).await.unwrap();
+    assert_eq!(cancelled.status, TaskStatus::Cancelled);
+
+    repo.delete_task(task.id).await.unwrap();
+    assert!(repo.find_task_by_id(task.id).await.unwrap().is_none());
+    assert!(matches!(repo.delete_task(task.id).await, Err(CoreError::NotFound(_))));
+    assert!(matches!(repo.cancel_task(task.id).await, Err(CoreError::NotFound(_))));
+    assert!(matches!(repo.complete_task(task.id).await, Err(CoreError::NotFound(_))));
+}
+
+async fn check_failed_writes_leave_no_trace<R: Repository + Sync>(repo: &R) {
+    let err = repo.add_task(NewTaskData {
+        name: "Orphan".to_string(),
+        project_name: Some("Missing".to_string()),
+        ..Default::default()
+    }).await.unwrap_err();
+    assert!(matches!(err, CoreError::NotFound(_)));
+
+    let err = repo.add_task(NewTaskData {
+        name: "Bad reminder".to_string(),
+        remind_before: Some(Duration::minutes(-5)),
+        ..Default::default()
+    }).await.unwrap_err();
+    assert!(matches!(err, CoreError::InvalidInput(_)));
+
+    let err = repo.add_task(NewTaskData {
+        name: "Bad rule".to_string(),
+        rrule: Some("FREQ=SOMETIMES".to_string()),
+        ..Default::default()
+    }).await.unwrap_err();
+    assert!(matches!(err, CoreError::InvalidRRule(_)));
+
+    let all = Query::Not(Box::new(Query::Filter(QueryFilter::Name(TextFilter::Equals(String::new())))));
+    assert!(query_ids(repo, all).await.is_empty());
+}
+
+async fn check_query_evaluation<R: Repository + Sync>(repo: &R, clock: &FakeClock) {
+    repo.add_project("Work".to_string(), None).await.unwrap();
+    let now = start_time();
+
+    let report = repo.add_task(NewTaskData {
+        name: "Quarterly report".to_string(),
+        description: Some("Numbers for Q2".to_string()),
+        priority: Some(TaskPriority::High),
+        project_name: Some("Work".to_string()),
+        tags: vec!["finance".to_string(), "q2".to_string()],
+        due_at: Some(now + Duration::hours(3)),
+        ..Default::default()
+    }).await.unwrap();
+    let groceries = repo.add_task(NewTaskData {
+        name: "Buy groceries".to_string(),
+        tags: vec!["errand".to_string()],
+        due_at: Some(now - Duration::days(1)),
+        ..Default::default()
+    }).await.unwrap();
+    let backlog = repo.add_task(NewTaskData {
+        name: "Read 100% of the backlog_list".to_string(),
+        priority: Some(TaskPriority::Low),
+        ..Default::default()
+    }).await.unwrap();
+    let dentist = repo.add_task(NewTaskData {
+        name: "Dentist".to_string(),
+        description: Some("Bring insurance card".to_string()),
+        tags: vec!["health".to_string()],
+        due_at: Some(now + Duration::days(1)),
+        ..Default::default()
+    }).await.unwrap();
+    repo.complete_task(groceries.id).await.unwrap();
+
+    let cases: Vec<(&str, Query, HashSet<Uuid>)> = vec![
+        ("status", Query::Filter(QueryFilter::Status(TaskStatus::Pending)), HashSet::from([report.id, backlog.id, dentist.id])),
+        ("priority", Query::Filter(QueryFilter::Priority(TaskPriority::Low)), HashSet::from([backlog.id])),
+        ("project", Query::Filter(QueryFilter::Project("Work".to_string())), HashSet::from([report.id])),
+        ("not project skips unassigned tasks", Query::Not(Box::new(Query::Filter(QueryFilter::Project("Work".to_string())))), HashSet::new()),
+        ("has tag", Query::Filter(QueryFilter::Tags(TagFilter::Has("q2".to_string()))), HashSet::from([report.id])),
+        ("has all tags", Query::Filter(QueryFilter::Tags(TagFilter::HasAll(vec!["finance".to_string(), "q2".to_string()]))), HashSet::from([report.id])),
+        ("has any tag", Query::Filter(QueryFilter::Tags(TagFilter::HasAny(vec!["errand".to_string(), "health".to_string()]))), HashSet::from([groceries.id, dentist.id])),
+        ("exact tags", Query::Filter(QueryFilter::Tags(TagFilter::Exact(vec!["finance".to_string()]))), HashSet::new()),
+        ("not has any", Query::Filter(QueryFilter::Tags(TagFilter::NotHasAny(vec!["finance".to_string(), "errand".to_string()]))), HashSet::from([backlog.id, dentist.id])),
+        ("name contains", Query::Filter(QueryFilter::Name(TextFilter::Contains("REPORT".to_string()))), HashSet::from([report.id])),
+        ("name starts with", Query::Filter(QueryFilter::Name(TextFilter::StartsWith("buy".to_string()))), HashSet::from([groceries.id])),
+        ("name ends with", Query::Filter(QueryFilter::Name(TextFilter::EndsWith("list".to_string()))), HashSet::from([backlog.id])),
+        ("like wildcards", Query::Filter(QueryFilter::Name(TextFilter::Contains("g_oceries".to_string()))), HashSet::from([groceries.id])),
+        ("description not contains skips missing descriptions", Query::Filter(QueryFilter::Description(TextFilter::NotContains("card".to_string()))), HashSet::from([report.id])),
+        ("due today", Query::Filter(QueryFilter::Due(DueDate::Today)), HashSet::from([report.id])),
+        ("due tomorrow", Query::Filter(QueryFilter::Due(DueDate::Tomorrow)), HashSet::from([dentist.id])),
+        ("due yesterday", Query::Filter(QueryFilter::Due(DueDate::Yesterday)), HashSet::from([groceries.id])),
+        ("overdue ignores completed", Query::Filter(QueryFilter::Due(DueDate::Overdue)), HashSet::new()),
+        ("due within", Query::Filter(QueryFilter::Due(DueDate::Within(Duration::hours(4)))), HashSet::from([report.id])),
+        ("due ago", Query::Filter(QueryFilter::Due(DueDate::Ago(Duration::days(2)))), HashSet::from([groceries.id])),
+        ("due before", Query::Filter(QueryFilter::Due(DueDate::Before(now))), HashSet::from([groceries.id])),
+        ("not overdue keeps undated pending tasks out", Query::Not(Box::new(Query::Filter(QueryFilter::Due(DueDate::Overdue)))), HashSet::from([report.id, groceries.id, dentist.id])),
+        (
+            "or",
+            Query::or(vec![
+                QueryFilter::Priority(TaskPriority::Low),
+                QueryFilter::Tags(TagFilter::Has("health".to_string())),
+            ]),
+            HashSet::from([backlog.id, dentist.id]),
+        ),
+        (
+            "and with null",
+            Query::and(vec![
+                QueryFilter::Status(TaskStatus::Pending),
+                QueryFilter::Description(TextFilter::Contains("q2".to_string())),
+            ]),
+            HashSet::from([report.id]),
+        ),
+    ];
+
+    for (name, query, expected) in cases {
+        assert_eq!(query_ids(repo, query).await, expected, "query case '{}'", name);
+    }
+
+    // Relative filters follow the clock
+    clock.advance(Duration::hours(4));
+    assert_eq!(query_ids(repo, Query::Filter(QueryFilter::Due(DueDate::Overdue))).await, HashSet::from([report.id]));
+}
+
+async fn check_hierarchy_and_dependencies<R: Repository + Sync>(repo: &R, clock: &FakeClock) {
+    let parent = repo.add_task(NewTaskData { name: "Move house".to_string(), ..Default::default() }).await.unwrap();
+    clock.advance(Duration::seconds(1));
+    let child = repo.add_task(NewTaskData {
+        name: "Pack boxes".to_string(),
+        parent_id: Some(parent.id),
+        ..Default::default()
+    }).await.unwrap();
+    clock.advance(Duration::seconds(1));
+    let grandchild = repo.add_task(NewTaskData {
+        name: "Buy tape".to_string(),
+        parent_id: Some(child.id),
+        ..Default::default()
+    }).await.unwrap();
+    clock.advance(Duration::seconds(1));
+    let sibling = repo.add_task(NewTaskData {
+        name: "Book van".to_string(),
+        depends_on: Some(grandchild.id),
+        ..Default::default()
+    }).await.unwrap();
+
+    let rows = repo.find_tasks_with_details(&Query::Filter(QueryFilter::Status(TaskStatus::Pending))).await.unwrap();
+    let order: Vec<(Uuid, i32)> = rows.iter().map(|r| (r.id, r.depth)).collect();
+    assert_eq!(order, vec![(parent.id, 0), (child.id, 1), (grandchild.id, 2), (sibling.id, 0)]);
+
+    assert!(matches!(repo.complete_task(sibling.id).await, Err(CoreError::TaskBlocked(names)) if names == "Buy tape"));
+
+    let err = repo.update_task(grandchild.id, UpdateTaskData {
+        depends_on: Some(Some(sibling.id)),
+        ..Default::default()
+    }, None).await.unwrap_err();
+    assert!(matches!(err, CoreError::CircularDependency(_, _)));
+
+    let err = repo.update_task(sibling.id, UpdateTaskData {
+        depends_on: Some(Some(sibling.id)),
+        ..Default::default()
+    }, None).await.unwrap_err();
+    assert!(matches!(err, CoreError::InvalidInput(_)));
+
+    repo.complete_task(grandchild.id).await.unwrap();
+    assert!(matches!(repo.complete_task(sibling.id).await.unwrap(), CompletionResult::Single(t) if t.completed_at == Some(clock.now())));
+
+    // Deleting a parent removes the whole subtree
+    repo.delete_task(parent.id).await.unwrap();
+    assert!(repo.find_task_by_id(child.id).await.unwrap().is_none());
+    assert!(repo.find_task_by_id(grandchild.id).await.unwrap().is_none());
+    assert!(repo.find_task_by_id(sibling.id).await.unwrap().is_some());
+}
+
+async fn check_projects<R: Repository + Sync>(repo: &R) {
+    let work = repo.add_project("Work".to_string(), Some("Day job".to_string())).await.unwrap();
+    repo.add_project("Garden".to_string(), None).await.unwrap();
+    assert!(repo.add_project("Work".to_string(), None).await.is_err());
+
+    assert_eq!(repo.find_project_by_id(work.id).await.unwrap().unwrap().name, "Work");
+    assert_eq!(repo.find_project_by_name("Garden").await.unwrap().unwrap().description, None);
+    assert!(repo.find_project_by_name("Nope").await.unwrap().is_none());
+    let names: Vec<String> = repo.find_projects().await.unwrap().into_iter().map(|p| p.name).collect();
+    assert_eq!(names, vec!["Work".to_string(), "Garden".to_string()]);
+
+    let task = repo.add_task(NewTaskData {
+        name: "Timesheet".to_string(),
+        project_id: Some(work.id),
+        ..Default::default()
+    }).await.unwrap();
+    assert!(matches!(repo.delete_project("Work".to_string()).await, Err(CoreError::InvalidInput(_))));
+    repo.delete_task(task.id).await.unwrap();
+    repo.delete_project("Work".to_string()).await.unwrap();
+    assert!(matches!(repo.delete_project("Work".to_string()).await, Err(CoreError::NotFound(_))));
+}
+
+async fn check_series_materialization<R: Repository + Sync>(repo: &R, clock: &FakeClock) {
+    let template = repo.add_task(NewTaskData {
+        name: "Stand-up".to_string(),
+        due_at: Some(start_time()),
+        rrule: Some("FREQ=DAILY;COUNT=10".to_string()),
+        timezone: Some("UTC".to_string()),
+        tags: vec!["team".to_string()],
+        ..Default::default()
+    }).await.unwrap();
+    assert_eq!(template.series_id, None);
+    let series = repo.find_series_by_template(template.id).await.unwrap().unwrap();
+    assert!(series.rrule.contains("DTSTART"));
+    assert_eq!(repo.find_series_by_id(series.id).await.unwrap().unwrap().template_task_id, template.id);
+
+    let window_end = start_time() + Duration::days(30);
+    let instances = repo.find_materialized_tasks_for_series(series.id, start_time(), window_end).await.unwrap();
+    let due: Vec<DateTime<Utc>> = instances.iter().filter_map(|t| t.due_at).collect();
+    assert_eq!(due, (0..10).map(|d| start_time() + Duration::days(d)).collect::<Vec<_>>());
+    assert!(instances.iter().all(|t| t.series_id == Some(series.id) && t.name == "Stand-up"));
+
+    // Refreshing again is idempotent
+    repo.refresh_series_materialization(start_time(), window_end).await.unwrap();
+    assert_eq!(repo.find_materialized_tasks_for_series(series.id, start_time(), window_end).await.unwrap().len(), 10);
+
+    match repo.complete_task(instances[0].id).await.unwrap() {
+        CompletionResult::SeriesInstance { completed, series_id, next_occurrence, .. } => {
+            assert_eq!(series_id, series.id);
+            assert_eq!(completed.completed_at, Some(clock.now()));
+            assert!(next_occurrence.is_some());
+        }
+        other => panic!("expected a series completion, got {:?}", other),
+    }
+
+    // Editing one occurrence leaves the template alone
+    let template_before = repo.find_task_by_id(template.id).await.unwrap().unwrap();
+    repo.update_task(instances[2].id, UpdateTaskData {
+        priority: Some(TaskPriority::High),
+        ..Default::default()
+    }, Some(EditScope::ThisOccurrence)).await.unwrap();
+    assert_eq!(repo.find_task_by_id(template.id).await.unwrap().unwrap().priority, template_before.priority);
+
+    let stats = repo.get_series_statistics(series.id).await.unwrap();
+    assert_eq!(stats.total_occurrences_created, 10);
+    assert_eq!(stats.completed_occurrences, 1);
+    assert_eq!(stats.pending_occurrences, 9);
+    assert_eq!(stats.first_occurrence, Some(start_time()));
+    assert_eq!(stats.last_occurrence, Some(start_time() + Duration::days(9)));
+
+    assert!(matches!(repo.archive_completed_series(series.id).await, Err(CoreError::SeriesNotCompleted(_))));
+
+    assert_eq!(repo.find_series_by_pattern("stand").await.unwrap().len(), 1);
+    assert_eq!(repo.find_series_by_pattern("COUNT=10").await.unwrap().len(), 1);
+    assert!(repo.find_series_by_pattern("weekly").await.unwrap().is_empty());
+
+    let copy = repo.duplicate_series(series.id, "Stand-up (team B)".to_string(), Some("Europe/Paris".to_string())).await.unwrap();
+    assert_eq!(copy.timezone, "Europe/Paris");
+    let copy_template = repo.find_task_by_id(copy.template_task_id).await.unwrap().unwrap();
+    assert_eq!(copy_template.name, "Stand-up (team B)");
+    let tagged = query_ids(repo, Query::Filter(QueryFilter::Tags(TagFilter::Has("team".to_string())))).await;
+    assert!(tagged.contains(&copy_template.id));
+
+    let paused = repo.update_series(series.id, UpdateSeriesData { active: Some(false), ..Default::default() }).await.unwrap();
+    assert!(!paused.active);
+    assert_eq!(paused.updated_at, clock.now());
+    let active = repo.find_active_series().await.unwrap();
+    assert_eq!(ids(&active, |s| s.id), HashSet::from([copy.id]));
+    assert!(matches!(
+        repo.update_series(series.id, UpdateSeriesData { rrule: Some("FREQ=NEVER".to_string()), ..Default::default() }).await,
+        Err(CoreError::InvalidRRule(_))
+    ));
+
+    let bulk = repo.bulk_update_series(vec![
+        (series.id, UpdateSeriesData { active: Some(true), ..Default::default() }),
+        (copy.id, UpdateSeriesData { timezone: Some("UTC".to_string()), ..Default::default() }),
+    ]).await.unwrap();
+    assert!(bulk[0].active);
+    assert_eq!(bulk[1].last_materialized_until, None);
+
+    let boundary = start_time() + Duration::days(5);
+    repo.update_series_materialization_boundary(series.id, boundary).await.unwrap();
+    assert_eq!(repo.find_series_by_id(series.id).await.unwrap().unwrap().last_materialized_until, Some(boundary));
+    assert!(matches!(repo.update_series_materialization_boundary(Uuid::now_v7(), boundary).await, Err(CoreError::NotFound(_))));
+
+    repo.delete_series(series.id).await.unwrap();
+    assert!(repo.find_series_by_id(series.id).await.unwrap().is_none());
+    assert!(repo.find_task_by_id(instances[3].id).await.unwrap().is_none());
+    assert!(repo.find_task_by_id(template.id).await.unwrap().is_some());
+    assert!(matches!(repo.delete_series(series.id).await, Err(CoreError::NotFound(_))));
+}
+
+async fn check_exceptions<R: Repository + Sync>(repo: &R) {
+    let template = repo.add_task(NewTaskData {
+        name: "Water plants".to_string(),
+        due_at: Some(start_time() + Duration::days(40)),
+        rrule: Some("FREQ=DAILY".to_string()),
+        timezone: Some("UTC".to_string()),
+        ..Default::default()
+    }).await.unwrap();
+    let series = repo.find_series_by_template(template.id).await.unwrap().unwrap();
+    let day = |n: i64| start_time() + Duration::days(40 + n);
+
+    repo.add_series_exception(NewSeriesException {
+        series_id: series.id,
+        occurrence_dt: day(2),
+        exception_type: ExceptionType::Skip,
+        exception_task_id: None,
+        notes: Some("Away".to_string()),
+    }).await.unwrap();
+    assert!(repo.add_series_exception(NewSeriesException {
+        series_id: series.id,
+        occurrence_dt: day(2),
+        exception_type: ExceptionType::Skip,
+        exception_task_id: None,
+        notes: None,
+    }).await.is_err());
+    assert!(matches!(
+        repo.add_series_exception(NewSeriesException {
+            series_id: series.id,
+            occurrence_dt: day(3),
+            exception_type: ExceptionType::Override,
+            exception_task_id: None,
+            notes: None,
+        }).await,
+        Err(CoreError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        repo.add_series_exception(NewSeriesException {
+            series_id: Uuid::now_v7(),
+            occurrence_dt: day(3),
+            exception_type: ExceptionType::Skip,
+            exception_task_id: None,
+            notes: None,
+        }).await,
+        Err(CoreError::NotFound(_))
+    ));
+
+    let moved = repo.move_occurrence_with_validation(series.id, day(4), day(4) + Duration::hours(3), "Europe/Berlin").await.unwrap();
+    assert_eq!(moved.due_at, Some(day(4) + Duration::hours(3)));
+    assert!(repo.move_occurrence_with_validation(series.id, day(5), day(6), "Mars/Olympus").await.is_err());
+    let replacement = repo.override_occurrence_with_task(series.id, day(1), NewTaskData {
+        name: "Water plants (big pot)".to_string(),
+        due_at: Some(day(1)),
+        ..Default::default()
+    }).await.unwrap();
+
+    repo.refresh_series_materialization(day(0), day(6)).await.unwrap();
+    let instances = repo.find_materialized_tasks_for_series(series.id, day(0), day(6)).await.unwrap();
+    let due: Vec<DateTime<Utc>> = instances.iter().filter_map(|t| t.due_at).collect();
+    assert!(!due.contains(&day(2)), "skipped occurrence must not be materialized");
+
+    let exceptions = repo.find_series_exceptions(series.id).await.unwrap();
+    let kinds: Vec<(DateTime<Utc>, ExceptionType)> = exceptions.iter().map(|e| (e.occurrence_dt, e.exception_type.clone())).collect();
+    assert_eq!(kinds, vec![
+        (day(1), ExceptionType::Override),
+        (day(2), ExceptionType::Skip),
+        (day(4), ExceptionType::Move),
+    ]);
+
+    let conflict = NewSeriesException {
+        series_id: series.id,
+        occurrence_dt: day(4),
+        exception_type: ExceptionType::Skip,
+        exception_task_id: None,
+        notes: None,
+    };
+    assert_eq!(repo.validate_exception_conflicts(series.id, &conflict).await.unwrap().len(), 1);
+
+    // Deleting the override task drops its exception
+    repo.delete_task(replacement.id).await.unwrap();
+    assert_eq!(repo.find_series_exceptions(series.id).await.unwrap().len(), 2);
+
+    repo.remove_series_exception(series.id, day(2)).await.unwrap();
+    assert!(matches!(repo.remove_series_exception(series.id, day(2)).await, Err(CoreError::NotFound(_))));
+
+    let bulk = repo.add_bulk_series_exceptions(vec![
+        NewSeriesException { series_id: series.id, occurrence_dt: day(7), exception_type: ExceptionType::Skip, exception_task_id: None, notes: None },
+        NewSeriesException { series_id: series.id, occurrence_dt: day(8), exception_type: ExceptionType::Skip, exception_task_id: None, notes: None },
+    ]).await.unwrap();
+    assert_eq!(bulk.len(), 2);
+    assert!(matches!(
+        repo.add_bulk_series_exceptions(vec![
+            NewSeriesException { series_id: series.id, occurrence_dt: day(9), exception_type: ExceptionType::Skip, exception_task_id: None, notes: None },
+            NewSeriesException { series_id: series.id, occurrence_dt: day(10), exception_type: ExceptionType::Move, exception_task_id: None, notes: None },
+        ]).await,
+        Err(CoreError::InvalidException(_))
+    ));
+    assert_eq!(repo.find_series_exceptions(series.id).await.unwrap().len(), 3, "failed bulk insert is rolled back");
+
+    let removed = repo.remove_bulk_series_exceptions(series.id, vec![day(7), day(8), day(9)]).await.unwrap();
+    assert_eq!(removed, 2);
+}
+
+async fn check_occurrence_overrides<R: Repository + Sync>(repo: &R) {
+    repo.add_project("Garden".to_string(), None).await.unwrap();
+    let template = repo.add_task(NewTaskData {
+        name: "Water plants".to_string(),
+        due_at: Some(start_time() + Duration::days(40)),
+        rrule: Some("FREQ=DAILY".to_string()),
+        timezone: Some("UTC".to_string()),
+        ..Default::default()
+    }).await.unwrap();
+    let series = repo.find_series_by_template(template.id).await.unwrap().unwrap();
+    let day = |n: i64| start_time() + Duration::days(40 + n);
+    let tags_of = |id: Uuid| async move {
+        let rows = repo.find_tasks_with_details(&Query::Filter(QueryFilter::Id(id))).await.unwrap();
+        rows.into_iter().next().and_then(|row| row.tags)
+    };
+
+    repo.refresh_series_materialization(day(0), day(6)).await.unwrap();
+    let instances = repo.find_materialized_tasks_for_series(series.id, day(0), day(6)).await.unwrap();
+    let instance = instances.iter().find(|t| t.due_at == Some(day(1))).unwrap().clone();
+    repo.add_annotation(instance.id, "Fern looked dry").await.unwrap();
+
+    // Overriding a materialized occurrence rewrites its instance
+    let overridden = repo.override_occurrence_with_task(series.id, day(1), NewTaskData {
+        name: "Water plants (big pot)".to_string(),
+        due_at: Some(day(1) + Duration::hours(2)),
+        priority: Some(TaskPriority::High),
+        project_name: Some("Garden".to_string()),
+        // Repeated tags are kept once
+        tags: vec!["outside".to_string(), "outside".to_string()],
+        ..Default::default()
+    }).await.unwrap();
+    assert_eq!(overridden.id, instance.id);
+    assert_eq!(overridden.name, "Water plants (big pot)");
+    assert_eq!(overridden.due_at, Some(day(1) + Duration::hours(2)));
+    assert_eq!(overridden.series_id, None);
+    assert!(overridden.project_id.is_some());
+    assert_eq!(tags_of(overridden.id).await.as_deref(), Some("outside"));
+
+    // Occurrences without an instance get a new task, and neither is materialized again
+    let fresh = repo.override_occurrence_with_task(series.id, day(10), NewTaskData {
+        name: "Water plants (holiday)".to_string(),
+        due_at: Some(day(10)),
+        tags: vec!["away".to_string(), "away".to_string()],
+        ..Default::default()
+    }).await.unwrap();
+    assert!(instances.iter().all(|t| t.id != fresh.id));
+    assert_eq!(tags_of(fresh.id).await.as_deref(), Some("away"));
+    let moved = repo.move_occurrence_with_validation(series.id, day(8), day(8) + Duration::hours(3), "UTC").await.unwrap();
+    repo.refresh_series_materialization(day(0), day(12)).await.unwrap();
+    let due: Vec<DateTime<Utc>> = repo
+        .find_materialized_tasks_for_series(series.id, day(0), day(12))
+        .await
+        .unwrap()
+        .iter()
+        .filter_map(|t| t.due_at)
+        .collect();
+    assert!(!due.contains(&day(1)) && !due.contains(&day(10)));
+    assert!(!due.contains(&day(8)), "moved occurrence must not be materialized at its old time");
+    assert!(repo
+        .override_occurrence_with_task(series.id, day(10), NewTaskData { name: "Again".to_string(), ..Default::default() })
+        .await
+        .is_err());
+    assert!(matches!(
+        repo.override_occurrence_with_task(Uuid::now_v7(), day(2), NewTaskData { name: "Lost".to_string(), ..Default::default() }).await,
+        Err(CoreError::NotFound(_))
+    ));
+
+    // Reverting puts the series defaults back on the same task
+    repo.complete_task(overridden.id).await.unwrap();
+    repo.remove_series_exception(series.id, day(1)).await.unwrap();
+    let reverted = repo.find_task_by_id(instance.id).await.unwrap().unwrap();
+    assert_eq!(reverted.name, "Water plants");
+    assert_eq!(reverted.priority, template.priority);
+    assert_eq!((reverted.due_at, reverted.series_id, reverted.project_id), (Some(day(1)), Some(series.id), None));
+    assert_eq!(reverted.status, TaskStatus::Completed);
+    assert_eq!(tags_of(reverted.id).await, None);
+    let notes: Vec<String> = repo.find_annotations(reverted.id).await.unwrap().into_iter().map(|a| a.text).collect();
+    assert_eq!(notes, vec!["Fern looked dry"]);
+
+    assert_eq!(repo.remove_bulk_series_exceptions(series.id, vec![day(8), day(9)]).await.unwrap(), 1);
+    let moved_back = repo.find_task_by_id(moved.id).await.unwrap().unwrap();
+    assert_eq!((moved_back.due_at, moved_back.series_id), (Some(day(8)), Some(series.id)));
+
+    repo.refresh_series_materialization(day(0), day(9)).await.unwrap();
+    let instances = repo.find_materialized_tasks_for_series(series.id, day(0), day(9)).await.unwrap();
+    for n in 0..=8 {
+        let count = instances.iter().filter(|t| t.due_at == Some(day(n))).count();
+        assert_eq!(count, 1, "one task per occurrence after reverting, day {}", n);
+    }
+    assert_eq!(repo.find_series_exceptions(series.id).await.unwrap().len(), 1);
+}
+
+async fn check_recurrence_sets<R: Repository + Sync>(repo: &R) {
+    let template = repo.add_task(NewTaskData {
+        name: "Pay bills".to_string(),
+        due_at: Some(start_time()),
+        rrule: Some(
+            "FREQ=MONTHLY;BYMONTHDAY=1,15\nRRULE:FREQ=MONTHLY;BYDAY=-1FR\nRDATE:20240520T090000Z\n\
+             EXRULE:FREQ=MONTHLY;BYMONTH=6;BYMONTHDAY=15".to_string(),
+        ),
+        timezone: Some("UTC".to_string()),
+        ..Default::default()
+    }).await.unwrap();
+    let series = repo.find_series_by_template(template.id).await.unwrap().unwrap();
+    assert_eq!(
+        series.rrule,
+        "DTSTART;TZID=UTC:20240506T090000\nRRULE:FREQ=MONTHLY;BYMONTHDAY=1,15\nRRULE:FREQ=MONTHLY;BYDAY=-1FR\n\
+         RDATE:20240520T090000Z\nEXRULE:FREQ=MONTHLY;BYMONTH=6;BYMONTHDAY=15"
+    );
+
+    let window_end = start_time() + Duration::days(60);
+    repo.refresh_series_materialization(start_time(), window_end).await.unwrap();
+    let mut due: Vec<(u32, u32)> = repo
+        .find_materialized_tasks_for_series(series.id, start_time(), window_end)
+        .await
+        .unwrap()
+        .iter()
+        .filter(|t| t.id != template.id)
+        .filter_map(|t| t.due_at)
+        .map(|d| (d.month(), d.day()))
+        .collect();
+    due.sort();
+    assert_eq!(due, vec![(5, 15), (5, 20), (5, 31), (6, 1), (6, 28), (7, 1)]);
+
+    // Dropping the RDATE line keeps the other rules
+    let updated = repo.update_series(series.id, UpdateSeriesData {
+        rrule: Some(series.rrule.replace("RDATE:20240520T090000Z\n", "")),
+        ..Default::default()
+    }).await.unwrap();
+    let manager = RecurrenceManager::new(updated, template.clone(), vec![]).unwrap();
+    let next = manager.next_occurrence_after(start_time() + Duration::days(10)).unwrap();
+    assert_eq!(next, Some(start_time() + Duration::days(25)));
+
+    // Editing this and future occurrences can change the rule lines too
+    let instance = repo
+        .find_materialized_tasks_for_series(series.id, start_time() + Duration::days(30), window_end)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|t| t.id != template.id)
+        .unwrap();
+    repo.update_task(instance.id, UpdateTaskData {
+        rrule: Some(Some("FREQ=MONTHLY;BYMONTHDAY=1\nRDATE:20240704T090000Z".to_string())),
+        ..Default::default()
+    }, Some(EditScope::ThisAndFuture)).await.unwrap();
+    let edited = repo.find_series_by_id(series.id).await.unwrap().unwrap();
+    assert_eq!(recurrence_lines(&edited.rrule), vec!["RRULE:FREQ=MONTHLY;BYMONTHDAY=1", "RDATE:20240704T090000Z"]);
+
+    // A set needs at least one RRULE or RDATE
+    let invalid = repo.update_series(series.id, UpdateSeriesData {
+        rrule: Some("EXRULE:FREQ=DAILY".to_string()),
+        ..Default::default()
+    }).await;
+    assert!(matches!(invalid, Err(CoreError::InvalidRRule(_))));
+}
+
+/// Runs with New York as the local timezone
+async fn check_time_modes<R: Repository + Sync>(repo: &R, clock: &FakeClock) {
+    let at = |day: u32, hour: u32| Utc.with_ymd_and_hms(2024, 5, day, hour, 0, 0).unwrap();
+
+    // All-day tasks are due at midnight UTC of their date, in a UTC series
+    let birthday = repo.add_task(NewTaskData {
+        name: "Birthday".to_string(),
+        due_at: Some(at(8, 15)),
+        rrule: Some("FREQ=DAILY;COUNT=3".to_string()),
+        timezone: Some("Europe/Berlin".to_string()),
+        time_mode: TimeMode::AllDay,
+        ..Default::default()
+    }).await.unwrap();
+    assert!(birthday.all_day);
+    assert_eq!(birthday.due_at, Some(at(8, 0)));
+    let series = repo.find_series_by_template(birthday.id).await.unwrap().unwrap();
+    assert_eq!((series.time_mode, series.timezone.as_str()), (TimeMode::AllDay, "UTC"));
+    assert_eq!(series.rrule, "DTSTART;VALUE=DATE:20240508\nRRULE:FREQ=DAILY;COUNT=3");
+    let instances: Vec<Task> = repo
+        .find_materialized_tasks_for_series(series.id, at(6, 0), at(20, 0))
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|t| t.id != birthday.id)
+        .collect();
+    assert!(instances.iter().all(|t| t.all_day));
+    let due: Vec<_> = instances.iter().filter_map(|t| t.due_at).collect();
+    assert_eq!(due, vec![at(8, 0), at(9, 0), at(10, 0)]);
+
+    // Late on May 8th in New York it is already May 9th in UTC: today's
+    // all-day task is the one for May 8th, and it is not overdue yet
+    clock.set(at(9, 2));
+    let call = repo.add_task(NewTaskData {
+        name: "Evening call".to_string(),
+        due_at: Some(at(9, 1)),
+        ..Default::default()
+    }).await.unwrap();
+    let today = query_ids(repo, Query::Filter(QueryFilter::Due(DueDate::Today))).await;
+    let may_8th = instances[0].id;
+    assert!(today.contains(&may_8th) && today.contains(&call.id));
+    assert!(!today.contains(&instances[1].id));
+    let overdue = query_ids(repo, Query::Filter(QueryFilter::Due(DueDate::Overdue))).await;
+    assert!(overdue.contains(&call.id) && !overdue.contains(&may_8th));
+    clock.set(at(9, 5));
+    let overdue = query_ids(repo, Query::Filter(QueryFilter::Due(DueDate::Overdue))).await;
+    assert!(overdue.contains(&may_8th) && !overdue.contains(&instances[1].id));
+
+    // A new floating series starts out in the local timezone, stored without one
+    let walk = repo.add_task(NewTaskData {
+        name: "Morning walk".to_string(),
+        due_at: Some(at(10, 12)),
+        rrule: Some("FREQ=DAILY".to_string()),
+        time_mode: TimeMode::Floating,
+        ..Default::default()
+    }).await.unwrap();
+    let series = repo.find_series_by_template(walk.id).await.unwrap().unwrap();
+    assert_eq!(series.timezone, "America/New_York");
+    assert_eq!(series.rrule, "DTSTART:20240510T080000\nRRULE:FREQ=DAILY");
+
+    // One made elsewhere follows the user to New York at the next refresh,
+    // keeping its 8am wall-clock time
+    let template = repo.add_task(NewTaskData {
+        name: "Medication".to_string(),
+        due_at: Some(at(10, 6)),
+        ..Default::default()
+    }).await.unwrap();
+    let series = repo.create_series(NewSeriesData {
+        template_task_id: template.id,
+        rrule: "FREQ=DAILY;COUNT=5".to_string(),
+        dtstart: at(10, 6),
+        timezone: "Europe/Berlin".to_string(),
+        time_mode: TimeMode::Floating,
+        dst_policy: DstPolicy::default(),
+    }).await.unwrap();
+    assert_eq!(series.rrule, "DTSTART:20240510T080000\nRRULE:FREQ=DAILY;COUNT=5");
+    repo.add_series_exception(NewSeriesException {
+        series_id: series.id,
+        occurrence_dt: at(12, 6),
+        exception_type: ExceptionType::Skip,
+        exception_task_id: None,
+        notes: None,
+    }).await.unwrap();
+
+    repo.find_tasks_with_details(&Query::Filter(QueryFilter::All)).await.unwrap();
+    let moved = repo.find_series_by_id(series.id).await.unwrap().unwrap();
+    assert_eq!((moved.timezone.as_str(), moved.dtstart), ("America/New_York", at(10, 12)));
+    assert_eq!(moved.rrule, series.rrule);
+    assert_eq!(repo.find_task_by_id(template.id).await.unwrap().unwrap().due_at, Some(at(10, 12)));
+    let exceptions = repo.find_series_exceptions(series.id).await.unwrap();
+    assert_eq!(exceptions.iter().map(|e| e.occurrence_dt).collect::<Vec<_>>(), vec![at(12, 12)]);
+    let due: Vec<_> = repo
+        .find_materialized_tasks_for_series(series.id, at(6, 0), at(20, 0))
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|t| t.id != template.id)
+        .filter_map(|t| t.due_at)
+        .collect();
+    assert_eq!(due, vec![at(10, 12), at(11, 12), at(13, 12), at(14, 12)]);
+}
+
+async fn check_series_timezone_moves<R: Repository + Sync>(repo: &R, clock: &FakeClock) {
+    let at = |day: u32, hour: u32| Utc.with_ymd_and_hms(2024, 5, day, hour, 0, 0).unwrap();
+    let pending_dues = |series_id: Uuid| async move {
+        repo.find_materialized_tasks_for_series(series_id, at(1, 0), at(31, 0))
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|t| t.status == TaskStatus::Pending)
+            .filter_map(|t| t.due_at)
+            .collect::<Vec<_>>()
+    };
+
+    // 9am in Berlin, 7am UTC
+    let standup = repo.add_task(NewTaskData {
+        name: "Standup".to_string(),
+        due_at: Some(at(7, 7)),
+        rrule: Some("FREQ=DAILY;COUNT=6".to_string()),
+        timezone: Some("Europe/Berlin".to_string()),
+        ..Default::default()
+    }).await.unwrap();
+    let series = repo.find_series_by_template(standup.id).await.unwrap().unwrap();
+    let first = repo.find_materialized_tasks_for_series(series.id, at(7, 0), at(7, 23)).await.unwrap();
+    let first = first.iter().find(|t| t.id != standup.id).unwrap();
+    clock.set(at(8, 0));
+    repo.complete_task(first.id).await.unwrap();
+    repo.add_series_exception(NewSeriesException {
+        series_id: series.id,
+        occurrence_dt: at(12, 7),
+        exception_type: ExceptionType::Skip,
+        exception_task_id: None,
+        notes: None,
+    }).await.unwrap();
+    let before = pending_dues(series.id).await;
+    assert_eq!(before[..4], [at(8, 7), at(9, 7), at(10, 7), at(11, 7)]);
+
+    // Moving to New York keeps 9am: 1pm UTC from then on
+    let moved = repo.bulk_update_series(vec![
+        (series.id, UpdateSeriesData { timezone: Some("America/New_York".to_string()), ..Default::default() }),
+    ]).await.unwrap();
+    assert_eq!((moved[0].timezone.as_str(), moved[0].dtstart), ("America/New_York", at(7, 13)));
+    assert_eq!(moved[0].rrule, "DTSTART;TZID=America/New_York:20240507T090000\nRRULE:FREQ=DAILY;COUNT=6");
+    let after = pending_dues(series.id).await;
+    assert_eq!(after, before.iter().map(|due| *due + Duration::hours(6)).collect::<Vec<_>>());
+    assert_eq!(repo.find_task_by_id(first.id).await.unwrap().unwrap().due_at, Some(at(7, 7)));
+    let exceptions = repo.find_series_exceptions(series.id).await.unwrap();
+    assert_eq!(exceptions.iter().map(|e| e.occurrence_dt).collect::<Vec<_>>(), vec![at(12, 13)]);
+
+    // Materialization finds the moved instances where the rule now puts them
+    repo.refresh_series_materialization(clock.now(), clock.now() + Duration::days(14)).await.unwrap();
+    assert_eq!(pending_dues(series.id).await, after);
+    let changes = repo.find_series_rule_changes(series.id).await.unwrap();
+    assert_eq!((changes[0].old_timezone.as_str(), changes[0].new_timezone.as_str()), ("Europe/Berlin", "America/New_York"));
+
+    // And back again, one series at a time
+    let moved = repo.update_series(series.id, UpdateSeriesData {
+        timezone: Some("Europe/Berlin".to_string()),
+        ..Default::default()
+    }).await.unwrap();
+    assert_eq!(moved.dtstart, at(7, 7));
+    assert_eq!(pending_dues(series.id).await, before);
+
+    // A timezone set together with a rule is taken as given
+    let moved = repo.update_series(series.id, UpdateSeriesData {
+        rrule: Some("FREQ=DAILY;COUNT=6".to_string()),
+        timezone: Some("UTC".to_string()),
+        ..Default::default()
+    }).await.unwrap();
+    assert_eq!((moved.timezone.as_str(), moved.dtstart), ("UTC", at(7, 7)));
+}
+
+async fn check_scoped_deletion<R: Repository + Sync>(repo: &R, clock: &FakeClock) {
+    let day = |n: i64| start_time() + Duration::days(n);
+    let add_series = |name: &str, rrule: &str| {
+        let data = NewTaskData {
+            name: name.to_string(),
+            due_at: Some(start_time()),
+            rrule: Some(rrule.to_string()),
+            timezone: Some("UTC".to_string()),
+            ..Default::default()
+        };
+        async move {
+            let template = repo.add_task(data).await.unwrap();
+            let series = repo.find_series_by_template(template.id).await.unwrap().unwrap();
+            let instances: Vec<Task> = repo
+                .find_materialized_tasks_for_series(series.id, start_time(), day(30))
+                .await
+                .unwrap()
+                .into_iter()
+                .filter(|t| t.id != template.id)
+                .collect();
+            (template, series, instances)
+        }
+    };
+    let pending_dues = |series: TaskSeries| async move {
+        repo.refresh_series_materialization(start_time(), day(30)).await.unwrap();
+        let mut dues: Vec<DateTime<Utc>> = repo
+            .find_materialized_tasks_for_series(series.id, start_time(), day(30))
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|t| t.id != series.template_task_id && t.status == TaskStatus::Pending)
+            .filter_map(|t| t.due_at)
+            .collect();
+        dues.sort();
+        dues
+    };
+
+    let (journal, series, instances) = add_series("Journal", "FREQ=DAILY;COUNT=10").await;
+    assert_eq!(instances.len(), 10);
+    clock.set(day(1));
+    repo.complete_task(instances[0].id).await.unwrap();
+
+    // One occurrence goes and stays gone
+    repo.delete_task_with_scope(instances[2].id, EditScope::ThisOccurrence, false).await.unwrap();
+    assert!(repo.find_task_by_id(instances[2].id).await.unwrap().is_none());
+    let moved = repo.move_occurrence_with_validation(series.id, day(4), day(4) + Duration::hours(3), "UTC").await.unwrap();
+    repo.delete_task_with_scope(moved.id, EditScope::ThisOccurrence, false).await.unwrap();
+    let exceptions = repo.find_series_exceptions(series.id).await.unwrap();
+    assert_eq!(
+        exceptions.iter().map(|e| (e.occurrence_dt, e.exception_type.clone())).collect::<Vec<_>>(),
+        vec![(day(2), ExceptionType::Skip), (day(4), ExceptionType::Skip)]
+    );
+    assert_eq!(pending_dues(series.clone()).await, vec![day(1), day(3), day(5), day(6), day(7), day(8), day(9)]);
+
+    // This and future ends the rule before the occurrence
+    repo.delete_task_with_scope(instances[6].id, EditScope::ThisAndFuture, false).await.unwrap();
+    let ended = repo.find_series_by_id(series.id).await.unwrap().unwrap();
+    assert_eq!(ended.rrule, "DTSTART;TZID=UTC:20240506T090000\nRRULE:FREQ=DAILY;UNTIL=20240512T085959Z");
+    assert_eq!(pending_dues(ended.clone()).await, vec![day(1), day(3), day(5)]);
+    let changes = repo.find_series_rule_changes(series.id).await.unwrap();
+    assert_eq!(changes.last().map(|c| c.new_rrule.as_str()), Some(ended.rrule.as_str()));
+
+    // The entire series can keep its completed history as regular tasks
+    repo.delete_task_with_scope(instances[1].id, EditScope::EntireSeries, true).await.unwrap();
+    assert!(repo.find_series_by_id(series.id).await.unwrap().is_none());
+    assert!(repo.find_task_by_id(journal.id).await.unwrap().is_none());
+    assert!(repo.find_task_by_id(instances[5].id).await.unwrap().is_none());
+    assert_eq!(repo.find_task_by_id(instances[0].id).await.unwrap().unwrap().series_id, None);
+
+    // Rules that end before the cut keep their end; later dates are dropped
+    let (_, bills, _) = add_series("Bills", "FREQ=DAILY;COUNT=2\nRRULE:FREQ=WEEKLY;BYDAY=FR\nRDATE:20240508T090000Z,20240530T090000Z").await;
+    assert_eq!(pending_dues(bills.clone()).await.len(), 8);
+    let friday = repo.find_materialized_tasks_for_series(bills.id, day(11), day(11)).await.unwrap();
+    repo.delete_task_with_scope(friday[0].id, EditScope::ThisAndFuture, false).await.unwrap();
+    let ended = repo.find_series_by_id(bills.id).await.unwrap().unwrap();
+    assert_eq!(
+        recurrence_lines(&ended.rrule),
+        vec!["RRULE:FREQ=DAILY;COUNT=2", "RRULE:FREQ=WEEKLY;BYDAY=FR;UNTIL=20240517T085959Z", "RDATE:20240508T090000Z"]
+    );
+    assert_eq!(pending_dues(ended).await, vec![day(0), day(1), day(2), day(4)]);
+
+    // From the first occurrence on is the whole series, here with its history
+    let (_, walks, instances) = add_series("Walk", "FREQ=DAILY;COUNT=5").await;
+    repo.complete_task(instances[0].id).await.unwrap();
+    repo.delete_task_with_scope(instances[0].id, EditScope::ThisAndFuture, false).await.unwrap();
+    assert!(repo.find_series_by_id(walks.id).await.unwrap().is_none());
+    assert!(repo.find_task_by_id(instances[0].id).await.unwrap().is_none());
+
+    // Deleting a template leaves no instances behind pointing at nothing
+    let (plants, watering, instances) = add_series("Water plants", "FREQ=DAILY;COUNT=5").await;
+    repo.complete_task(instances[0].id).await.unwrap();
+    repo.delete_task(plants.id).await.unwrap();
+    assert!(repo.find_series_by_id(watering.id).await.unwrap().is_none());
+    assert!(repo.find_task_by_id(instances[1].id).await.unwrap().is_none());
+    assert_eq!(repo.find_task_by_id(instances[0].id).await.unwrap().unwrap().series_id, None);
+    assert!(repo.find_orphaned_instances().await.unwrap().is_empty());
+    assert!(repo.detach_orphaned_instances().await.unwrap().is_empty());
+
+    // Outside a series the scope does not matter
+    let chore = repo.add_task(NewTaskData { name: "Chore".to_string(), ..Default::default() }).await.unwrap();
+    repo.delete_task_with_scope(chore.id, EditScope::EntireSeries, false).await.unwrap();
+    assert!(matches!(
+        repo.delete_task_with_scope(chore.id, EditScope::ThisOccurrence, false).await,
+        Err(CoreError::NotFound(_))
+    ));
+}
+
+async fn check_dst_policy<R: Repository + Sync>(repo: &R, clock: &FakeClock) {
+    let at = |month: u32, day: u32, hour: u32, minute: u32| Utc.with_ymd_and_hms(2024, month, day, hour, minute, 0).unwrap();
+    let instance_dues = |series: TaskSeries, from: DateTime<Utc>| async move {
+        let until = from + Duration::days(14);
+        repo.refresh_series_materialization(from, until).await.unwrap();
+        repo.find_materialized_tasks_for_series(series.id, from, until)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter_map(|t| t.due_at)
+            .collect::<Vec<_>>()
+    };
+    clock.set(at(3, 8, 0, 0));
+
+    // 2:30am does not exist in New York on March 10th; this series skips it
+    let backup = repo.add_task(NewTaskData {
+        name: "Night backup".to_string(),
+        due_at: Some(at(3, 9, 7, 30)),
+        rrule: Some("FREQ=DAILY;COUNT=4".to_string()),
+        timezone: Some("America/New_York".to_string()),
+        dst_policy: DstPolicy { dst_gap: DstGapPolicy::Skip, ..Default::default() },
+        ..Default::default()
+    }).await.unwrap();
+    let series = repo.find_series_by_template(backup.id).await.unwrap().unwrap();
+    assert_eq!(series.dst_policy.dst_gap, DstGapPolicy::Skip);
+    assert_eq!(instance_dues(series.clone(), at(3, 1, 0, 0)).await, vec![at(3, 9, 7, 30), at(3, 11, 6, 30), at(3, 12, 6, 30)]);
+    let first = repo.find_materialized_tasks_for_series(series.id, at(3, 9, 0, 0), at(3, 9, 12, 0)).await.unwrap();
+    repo.complete_task(first[0].id).await.unwrap();
+
+    // Changing the policy reschedules the pending instances it moves
+    let shift_backward = DstPolicy { dst_gap: DstGapPolicy::ShiftBackward, ..Default::default() };
+    let series = repo.update_series(series.id, UpdateSeriesData { dst_policy: Some(shift_backward), ..Default::default() }).await.unwrap();
+    assert_eq!(series.dst_policy, shift_backward);
+    assert_eq!(
+        instance_dues(series.clone(), at(3, 1, 0, 0)).await,
+        vec![at(3, 9, 7, 30), at(3, 10, 6, 30), at(3, 11, 6, 30), at(3, 12, 6, 30)]
+    );
+    let series = repo.update_series(series.id, UpdateSeriesData { dst_policy: Some(DstPolicy::default()), ..Default::default() }).await.unwrap();
+    assert_eq!(repo.find_series_by_id(series.id).await.unwrap().unwrap().dst_policy, DstPolicy::default());
+    assert_eq!(
+        instance_dues(series.clone(), at(3, 1, 0, 0)).await,
+        vec![at(3, 9, 7, 30), at(3, 10, 7, 30), at(3, 11, 6, 30), at(3, 12, 6, 30)]
+    );
+    let done = repo.find_task_by_id(first[0].id).await.unwrap().unwrap();
+    assert_eq!(done.status, TaskStatus::Completed);
+
+    // 1:30am happens twice on November 3rd; this series takes the second
+    let rounds = repo.add_task(NewTaskData {
+        name: "Night rounds".to_string(),
+        due_at: Some(at(11, 2, 5, 30)),
+        rrule: Some("FREQ=DAILY;COUNT=3".to_string()),
+        timezone: Some("America/New_York".to_string()),
+        dst_policy: DstPolicy { dst_overlap: DstOverlapPolicy::Latest, ..Default::default() },
+        ..Default::default()
+    }).await.unwrap();
+    let series = repo.find_series_by_template(rounds.id).await.unwrap().unwrap();
+    assert_eq!(instance_dues(series, at(11, 1, 0, 0)).await, vec![at(11, 2, 5, 30), at(11, 3, 6, 30), at(11, 4, 6, 30)]);
+}
+
+async fn check_exception_patterns<R: Repository + Sync>(repo: &R, clock: &FakeClock) {
+    let series_with_manager = |name: &str, rrule: &str, timezone: &str| {
+        let data = NewTaskData {
+            name: name.to_string(),
+            due_at: Some(start_time()),
+            rrule: Some(rrule.to_string()),
+            timezone: Some(timezone.to_string()),
+            ..Default::default()
+        };
+        async move {
+            let template = repo.add_task(data).await.unwrap();
+            let series = repo.find_series_by_template(template.id).await.unwrap().unwrap();
+            let exceptions = repo.find_series_exceptions(series.id).await.unwrap();
+            let manager = RecurrenceManager::new(series.clone(), template, exceptions).unwrap();
+            (series, manager)
+        }
+    };
+    let date = |y: i32, m: u32, d: u32| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+    // Skip every occurrence in June, leaving materialized May alone
+    let (daily, manager) = series_with_manager("Stretch", "FREQ=DAILY", "UTC").await;
+    repo.refresh_series_materialization(start_time(), start_time() + Duration::days(10)).await.unwrap();
+    let june = OccurrencePattern { months: vec![6], ..Default::default() };
+    let plan = plan_exceptions(&manager, &june, PatternAction::Skip, clock.now()).unwrap();
+    assert_eq!(plan.len(), 30);
+    assert!(plan.iter().all(|p| p.move_to.is_none()));
+    assert!(repo.find_series_exceptions(daily.id).await.unwrap().is_empty(), "planning is a dry run");
+    let result = apply_exception_plan(repo, &daily, &plan, clock.now()).await.unwrap();
+    assert_eq!((result.successful_operations, result.failed_operations), (30, 0));
+    assert_eq!(result.created_exceptions.len(), 30);
+
+    // Holidays: a materialized instance goes away, a finished one is reported
+    let instances = repo.find_materialized_tasks_for_series(daily.id, start_time(), start_time() + Duration::days(10)).await.unwrap();
+    let holiday = instances.iter().find(|t| t.due_at.unwrap().date_naive() == date(2024, 5, 9)).unwrap().clone();
+    let done = instances.iter().find(|t| t.due_at.unwrap().date_naive() == date(2024, 5, 10)).unwrap().clone();
+    repo.complete_task(done.id).await.unwrap();
+    let exceptions = repo.find_series_exceptions(daily.id).await.unwrap();
+    let manager = RecurrenceManager::new(daily.clone(), manager.template_task().clone(), exceptions).unwrap();
+    let holidays = OccurrencePattern {
+        dates: vec![date(2024, 5, 9), date(2024, 5, 10), date(2024, 6, 3)],
+        ..Default::default()
+    };
+    let plan = plan_exceptions(&manager, &holidays, PatternAction::Skip, clock.now()).unwrap();
+    assert_eq!(plan.len(), 2, "already skipped June 3rd is not planned again");
+    let result = apply_exception_plan(repo, &daily, &plan, clock.now()).await.unwrap();
+    assert_eq!((result.successful_operations, result.failed_operations), (1, 1));
+    assert!(result.errors[0].starts_with("2024-05-10"));
+    assert!(repo.find_task_by_id(holiday.id).await.unwrap().is_none());
+    assert_eq!(repo.find_series_exceptions(daily.id).await.unwrap().len(), 31);
+
+    // Move every Friday in Q4 to Thursday at the same local time
+    let (weekly, manager) = series_with_manager("Review", "FREQ=WEEKLY;BYDAY=FR", "Europe/Berlin").await;
+    let q4 = OccurrencePattern {
+        from: Some(Utc.with_ymd_and_hms(2024, 10, 1, 0, 0, 0).unwrap()),
+        until: Some(Utc.with_ymd_and_hms(2024, 12, 31, 23, 59, 59).unwrap()),
+        weekdays: vec![Weekday::Fri],
+        ..Default::default()
+    };
+    let berlin: chrono_tz::Tz = "Europe/Berlin".parse().unwrap();
+    let plan = plan_exceptions(&manager, &q4, PatternAction::MoveToWeekday(Weekday::Thu), clock.now()).unwrap();
+    assert_eq!(plan.len(), 13);
+    for planned in &plan {
+        let from = planned.occurrence.occurrence_dt.with_timezone(&berlin);
+        let to = planned.move_to.unwrap().with_timezone(&berlin);
+        assert_eq!((to.weekday(), to.time()), (Weekday::Thu, from.time()));
+        assert_eq!(from.date_naive() - to.date_naive(), Duration::days(1));
+    }
+    let result = apply_exception_plan(repo, &weekly, &plan, clock.now()).await.unwrap();
+    assert_eq!(result.successful_operations, 13);
+    assert!(result.created_exceptions.iter().all(|e| e.exception_type == ExceptionType::Move));
+
+    // Applying the same plan again fails per occurrence instead of as a whole
+    let result = apply_exception_plan(repo, &weekly, &plan, clock.now()).await.unwrap();
+    assert_eq!((result.successful_operations, result.failed_operations, result.errors.len()), (0, 13, 13));
+
+    // Skip every 2nd occurrence
+    let (biweekly, manager) = series_with_manager("Clean windows", "FREQ=WEEKLY;COUNT=6", "UTC").await;
+    let every_second = OccurrencePattern { every_nth: Some(2), ..Default::default() };
+    let plan = plan_exceptions(&manager, &every_second, PatternAction::Skip, clock.now()).unwrap();
+    let skipped: Vec<DateTime<Utc>> = plan.iter().map(|p| p.occurrence.occurrence_dt).collect();
+    assert_eq!(skipped, vec![start_time() + Duration::weeks(1), start_time() + Duration::weeks(3), start_time() + Duration::weeks(5)]);
+    apply_exception_plan(repo, &biweekly, &plan, clock.now()).await.unwrap();
+    assert_eq!(repo.find_series_exceptions(biweekly.id).await.unwrap().len(), 3);
+    assert!(plan_exceptions(&manager, &OccurrencePattern { every_nth: Some(0), ..Default::default() }, PatternAction::Skip, clock.now()).is_err());
+}
+
+async fn check_series_history<R: Repository + Sync>(repo: &R, clock: &FakeClock) {
+    let day = |n: i64| start_time() + Duration::days(n);
+    let template = repo.add_task(NewTaskData {
+        name: "Water plants".to_string(),
+        due_at: Some(start_time()),
+        rrule: Some("FREQ=DAILY".to_string()),
+        timezone: Some("UTC".to_string()),
+        ..Default::default()
+    }).await.unwrap();
+    let series = repo.find_series_by_template(template.id).await.unwrap().unwrap();
+    repo.refresh_series_materialization(start_time(), day(7)).await.unwrap();
+    let instances: HashMap<DateTime<Utc>, Task> = repo
+        .find_materialized_tasks_for_series(series.id, start_time(), day(7))
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|t| t.id != template.id)
+        .map(|t| (t.due_at.unwrap(), t))
+        .collect();
+
+    // Done an hour late, done an hour early, skipped, deleted, left pending
+    clock.set(day(0) + Duration::hours(1));
+    repo.complete_task(instances[&day(0)].id).await.unwrap();
+    clock.set(day(1) - Duration::hours(1));
+    repo.complete_task(instances[&day(1)].id).await.unwrap();
+    repo.add_series_exception(NewSeriesException {
+        series_id: series.id,
+        occurrence_dt: day(2),
+        exception_type: ExceptionType::Skip,
+        exception_task_id: None,
+        notes: Some("Away".to_string()),
+    }).await.unwrap();
+    repo.delete_task(instances[&day(2)].id).await.unwrap();
+    repo.delete_task(instances[&day(3)].id).await.unwrap();
+    repo.move_occurrence_with_validation(series.id, day(6), day(6) + Duration::hours(6), "UTC").await.unwrap();
+    clock.set(day(5) + Duration::hours(3));
+
+    let timeline = series_timeline(repo, &series, clock.now()).await.unwrap();
+    let past: Vec<_> = timeline.iter().filter(|e| e.at <= clock.now()).collect();
+    assert_eq!(past.iter().map(|e| e.at).collect::<Vec<_>>(), (0..6).map(day).collect::<Vec<_>>());
+    assert_eq!(past[0].lateness(), Some(Duration::hours(1)));
+    assert_eq!(past[1].lateness(), Some(Duration::hours(-1)));
+    assert!(matches!(&past[2].event, TimelineEvent::Exception { exception, task: None } if exception.notes.as_deref() == Some("Away")));
+    assert!(matches!(past[3].event, TimelineEvent::Unmaterialized));
+    assert!(matches!(past[4].event, TimelineEvent::Instance(_)));
+    assert_eq!(past.iter().map(|e| e.missed).collect::<Vec<_>>(), vec![false, false, false, true, true, true]);
+
+    let moved = timeline.iter().find(|e| e.at == day(6)).unwrap();
+    assert!(matches!(&moved.event, TimelineEvent::Exception { task: Some(task), .. } if task.due_at == Some(day(6) + Duration::hours(6))));
+    assert!(!moved.missed);
+
+    let summary = TimelineSummary::new(&timeline);
+    assert_eq!((summary.completed, summary.completed_late, summary.missed, summary.skipped), (2, 1, 3, 1));
+    assert_eq!(summary.average_lateness, Some(Duration::zero()));
+
+    // Rule edits join the timeline; only their schedule changes are recorded
+    repo.update_series(series.id, UpdateSeriesData { active: Some(false), ..Default::default() }).await.unwrap();
+    repo.update_series(series.id, UpdateSeriesData {
+        rrule: Some("FREQ=WEEKLY".to_string()),
+        timezone: Some("Europe/Berlin".to_string()),
+        active: Some(true),
+        ..Default::default()
+    }).await.unwrap();
+    let changes = repo.find_series_rule_changes(series.id).await.unwrap();
+    assert_eq!(changes.len(), 1);
+    assert_eq!((changes[0].old_timezone.as_str(), changes[0].new_timezone.as_str()), ("UTC", "Europe/Berlin"));
+    assert!(changes[0].old_rrule.ends_with("RRULE:FREQ=DAILY"));
+    assert_eq!(changes[0].new_rrule, "FREQ=WEEKLY");
+    assert_eq!(changes[0].changed_at, clock.now());
+
+    // Occurrences before the edit are no longer expected by the new rule
+    let series = repo.find_series_by_id(series.id).await.unwrap().unwrap();
+    let timeline = series_timeline(repo, &series, clock.now()).await.unwrap();
+    assert!(!timeline.iter().any(|e| matches!(e.event, TimelineEvent::Unmaterialized)));
+    assert!(timeline.iter().any(|e| e.at == clock.now() && matches!(e.event, TimelineEvent::RuleChange(_))));
+
+    repo.delete_series(series.id).await.unwrap();
+    assert!(repo.find_series_rule_changes(series.id).await.unwrap().is_empty());
+}
+
+async fn check_series_habits<R: Repository + Sync>(repo: &R, clock: &FakeClock) {
+    let day = |n: i64| start_time() + Duration::days(n);
+    let template = repo.add_task(NewTaskData {
+        name: "Stretch".to_string(),
+        due_at: Some(start_time()),
+        rrule: Some("FREQ=DAILY".to_string()),
+        timezone: Some("UTC".to_string()),
+        ..Default::default()
+    }).await.unwrap();
+    let series = repo.find_series_by_template(template.id).await.unwrap().unwrap();
+    repo.refresh_series_materialization(start_time(), day(8)).await.unwrap();
+    let instances: HashMap<DateTime<Utc>, Task> = repo
+        .find_materialized_tasks_for_series(series.id, start_time(), day(8))
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|t| t.id != template.id)
+        .map(|t| (t.due_at.unwrap(), t))
+        .collect();
+
+    // Three on time, one two hours late, one missed, one skipped, one on time
+    for n in [0, 1, 2, 6] {
+        clock.set(day(n) - Duration::hours(1));
+        repo.complete_task(instances[&day(n)].id).await.unwrap();
+    }
+    clock.set(day(3) + Duration::hours(2));
+    repo.complete_task(instances[&day(3)].id).await.unwrap();
+    repo.add_series_exception(NewSeriesException {
+        series_id: series.id,
+        occurrence_dt: day(5),
+        exception_type: ExceptionType::Skip,
+        exception_task_id: None,
+        notes: None,
+    }).await.unwrap();
+    repo.delete_task(instances[&day(5)].id).await.unwrap();
+    clock.set(day(7) - Duration::hours(1));
+
+    let habits = series_habits(repo, &series, clock.now()).await.unwrap();
+    assert_eq!((habits.current_streak, habits.longest_streak), (1, 3));
+    assert_eq!((habits.due, habits.completed, habits.on_time), (6, 5, 4));
+    assert_eq!(habits.on_time_rate, Some(4.0 / 6.0));
+    assert_eq!(habits.average_lateness_hours, Some(-0.4));
+
+    // 2024-05-06 is a Monday, so the missed occurrence fell on a Friday
+    assert_eq!(habits.weekdays.len(), 7);
+    let friday = &habits.weekdays[4];
+    assert_eq!((friday.weekday, friday.due, friday.completed), (Weekday::Fri, 1, 0));
+    let saturday = &habits.weekdays[5];
+    assert_eq!(saturday.due, 0);
+    assert_eq!(saturday.completion_rate(), None);
+
+    let week = &habits.trends[0];
+    assert_eq!((week.days, week.due, week.on_time), (7, 6, 4));
+    assert_eq!(week.previous_on_time_rate, None);
+}
+
+async fn check_blueprints<R: Repository + Sync>(repo: &R) {
+    let day = |n: i64| start_time() + Duration::days(n);
+    repo.add_project("Onboarding".to_string(), None).await.unwrap();
+    let root = repo.add_task(NewTaskData {
+        name: "Onboard".to_string(),
+        due_at: Some(day(0)),
+        project_name: Some("Onboarding".to_string()),
+        tags: vec!["hr".to_string()],
+        ..Default::default()
+    }).await.unwrap();
+    let laptop = repo.add_task(NewTaskData {
+        name: "Set up laptop".to_string(),
+        due_at: Some(day(1)),
+        parent_id: Some(root.id),
+        project_name: Some("Onboarding".to_string()),
+        tags: vec!["it".to_string(), "hardware".to_string()],
+        estimate: Some(Duration::minutes(90)),
+        ..Default::default()
+    }).await.unwrap();
+    repo.add_task(NewTaskData {
+        name: "Create accounts".to_string(),
+        due_at: Some(day(2)),
+        parent_id: Some(root.id),
+        depends_on: Some(laptop.id),
+        project_name: Some("Onboarding".to_string()),
+        ..Default::default()
+    }).await.unwrap();
+    repo.add_task(NewTaskData {
+        name: "Weekly 1:1".to_string(),
+        due_at: Some(day(3)),
+        rrule: Some("FREQ=WEEKLY;COUNT=4".to_string()),
+        timezone: Some("Europe/Berlin".to_string()),
+        project_name: Some("Onboarding".to_string()),
+        ..Default::default()
+    }).await.unwrap();
+
+    // The project's roots bring their subtasks; series instances stay out
+    let roots = project_roots(repo, "Onboarding").await.unwrap();
+    assert_eq!(roots.len(), 2);
+    let tasks = capture_blueprint(repo, &roots, None).await.unwrap();
+    let names: Vec<&str> = tasks.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["Onboard", "Set up laptop", "Create accounts", "Weekly 1:1"]);
+    assert_eq!(tasks.iter().map(|t| t.due_offset_minutes).collect::<Vec<_>>(), vec![Some(540), Some(1980), Some(3420), Some(4860)]);
+    assert_eq!(tasks.iter().map(|t| t.parent_position).collect::<Vec<_>>(), vec![None, Some(0), Some(0), None]);
+    assert_eq!(tasks[1].tags, vec!["hardware", "it"]);
+    assert_eq!(tasks[2].depends_on, vec![1]);
+    assert_eq!(tasks[3].rrule.as_deref(), Some("RRULE:FREQ=WEEKLY;COUNT=4"));
+    assert_eq!(tasks[3].timezone.as_deref(), Some("Europe/Berlin"));
+
+    let saved = repo.save_blueprint(NewBlueprint {
+        name: "onboarding".to_string(),
+        description: Some("First weeks".to_string()),
+        tasks,
+    }).await.unwrap();
+    assert_eq!(repo.find_blueprint("onboarding").await.unwrap().unwrap(), saved);
+    assert!(repo.save_blueprint(NewBlueprint { name: "onboarding".to_string(), ..Default::default() }).await.is_err());
+    assert_eq!(repo.find_blueprints().await.unwrap(), vec![saved.clone()]);
+
+    // Broken references are refused
+    let mut cyclic = saved.tasks.clone();
+    cyclic[1].depends_on = vec![2];
+    let result = repo.save_blueprint(NewBlueprint { name: "cyclic".to_string(), description: None, tasks: cyclic }).await;
+    assert!(matches!(result, Err(CoreError::CircularDependency(_, _))));
+    let mut orphan = saved.tasks.clone();
+    orphan[1].parent_position = Some(3);
+    let result = repo.save_blueprint(NewBlueprint { name: "orphan".to_string(), description: None, tasks: orphan }).await;
+    assert!(matches!(result, Err(CoreError::InvalidInput(_))));
+
+    // Nothing is created when any part fails
+    let anchor = day(14) - Duration::hours(9);
+    let all_tasks = || async { repo.find_tasks_with_details(&Query::Filter(QueryFilter::All)).await.unwrap().len() };
+    let before = all_tasks().await;
+    assert!(matches!(repo.apply_blueprint("onboarding", anchor, Some("Alice".to_string())).await, Err(CoreError::NotFound(_))));
+    assert!(matches!(repo.apply_blueprint("missing", anchor, None).await, Err(CoreError::NotFound(_))));
+    assert_eq!(all_tasks().await, before);
+
+    repo.add_project("Alice".to_string(), None).await.unwrap();
+    let created = repo.apply_blueprint("onboarding", anchor, Some("Alice".to_string())).await.unwrap();
+    assert_eq!(created.iter().map(|t| t.due_at).collect::<Vec<_>>(), (14..18).map(|n| Some(day(n))).collect::<Vec<_>>());
+    let alice = repo.find_project_by_name("Alice").await.unwrap().unwrap();
+    assert!(created.iter().all(|t| t.project_id == Some(alice.id)));
+    assert_eq!(created[1].parent_id, Some(created[0].id));
+    assert_eq!(created[1].estimate_minutes, Some(90));
+    let relations = repo.find_task_relations(created[2].id).await.unwrap();
+    assert_eq!(relations.depends_on.iter().map(|t| t.id).collect::<Vec<_>>(), vec![created[1].id]);
+    let row = repo.find_tasks_with_details(&Query::Filter(QueryFilter::Id(created[1].id))).await.unwrap().remove(0);
+    let mut tags: Vec<String> = row.tags.unwrap().split(',').map(String::from).collect();
+    tags.sort();
+    assert_eq!(tags, vec!["hardware", "it"]);
+    let series = repo.find_series_by_template(created[3].id).await.unwrap().unwrap();
+    assert_eq!((series.dtstart, series.timezone.as_str()), (day(17), "Europe/Berlin"));
+    assert!(!repo.find_materialized_tasks_for_series(series.id, day(17), day(30)).await.unwrap().is_empty());
+
+    repo.delete_blueprint("onboarding").await.unwrap();
+    assert!(repo.find_blueprint("onboarding").await.unwrap().is_none());
+    assert!(matches!(repo.delete_blueprint("onboarding").await, Err(CoreError::NotFound(_))));
+}
+
+async fn check_reminders<R: Repository + Sync>(repo: &R) {
+    let due = start_time() + Duration::hours(5);
+    let task = repo.add_task(NewTaskData {
+        name: "Call plumber".to_string(),
+        due_at: Some(due),
+        remind_before: Some(Duration::minutes(45)),
+        ..Default::default()
+    }).await.unwrap();
+    let template = repo.add_task(NewTaskData {
+        name: "Take vitamins".to_string(),
+        due_at: Some(start_time() + Duration::hours(1)),
+        rrule: Some("FREQ=DAILY;COUNT=2".to_string()),
+        timezone: Some("UTC".to_string()),
+        remind_before: Some(Duration::minutes(10)),
+        ..Default::default()
+    }).await.unwrap();
+
+    assert_eq!(repo.find_task_reminder(task.id).await.unwrap().unwrap().offset_minutes, 45);
+    let updated = repo.set_task_reminder(task.id, Duration::minutes(30)).await.unwrap();
+    assert_eq!(updated.offset_minutes, 30);
+    assert!(matches!(repo.set_task_reminder(Uuid::now_v7(), Duration::minutes(5)).await, Err(CoreError::NotFound(_))));
+
+    let pending = repo.find_pending_reminders(start_time()).await.unwrap();
+    let summary: Vec<(String, i64)> = pending.iter().map(|r| (r.task.name.clone(), r.offset_minutes)).collect();
+    assert_eq!(summary, vec![
+        ("Take vitamins".to_string(), 10),
+        ("Call plumber".to_string(), 30),
+        ("Take vitamins".to_string(), 10),
+    ]);
+    assert!(pending.iter().all(|r| r.task.id != template.id));
+
+    repo.mark_reminder_delivered(task.id, due, start_time()).await.unwrap();
+    repo.mark_reminder_delivered(task.id, due, start_time()).await.unwrap();
+    assert_eq!(repo.find_pending_reminders(start_time()).await.unwrap().len(), 2);
+
+    repo.clear_task_reminder(template.id).await.unwrap();
+    assert!(repo.find_pending_reminders(start_time()).await.unwrap().is_empty());
+    assert!(repo.find_task_reminder(template.id).await.unwrap().is_none());
+}
+
+async fn check_workflow_statuses<R: Repository + Sync>(repo: &R, clock: &FakeClock) {
+    let builtin: Vec<String> = repo.find_workflow_statuses().await.unwrap().into_iter().map(|s| s.name).collect();
+    assert_eq!(builtin, vec!["pending", "completed", "cancelled"]);
+
+    let review = repo.add_workflow_status("Review", TaskStatus::Pending).await.unwrap();
+    assert_eq!((review.name.as_str(), review.position), ("review", 1));
+    repo.add_workflow_status("in-progress", TaskStatus::Pending).await.unwrap();
+    repo.add_workflow_status("shipped", TaskStatus::Completed).await.unwrap();
+    assert!(matches!(repo.add_workflow_status("review", TaskStatus::Pending).await, Err(CoreError::InvalidInput(_))));
+    assert!(matches!(repo.add_workflow_status("two words", TaskStatus::Pending).await, Err(CoreError::InvalidInput(_))));
+    let names: Vec<String> = repo.find_workflow_statuses().await.unwrap().into_iter().map(|s| s.name).collect();
+    assert_eq!(names, vec!["pending", "review", "in-progress", "completed", "shipped", "cancelled"]);
+
+    let task = repo.add_task(NewTaskData { name: "Write docs".to_string(), ..Default::default() }).await.unwrap();
+    assert_eq!(task.workflow_status, "pending");
+
+    // Without transitions every move is allowed
+    clock.advance(Duration::minutes(1));
+    let moved = repo.move_task(task.id, "IN-PROGRESS").await.unwrap();
+    assert_eq!((moved.status.clone(), moved.workflow_status.as_str()), (TaskStatus::Pending, "in-progress"));
+    assert_eq!(moved.updated_at, clock.now());
+
+    repo.add_workflow_transition("in-progress", "review").await.unwrap();
+    repo.add_workflow_transition("in-progress", "review").await.unwrap();
+    repo.add_workflow_transition("review", "shipped").await.unwrap();
+    assert!(matches!(repo.add_workflow_transition("review", "nowhere").await, Err(CoreError::InvalidInput(_))));
+    assert!(matches!(repo.add_workflow_transition("review", "review").await, Err(CoreError::InvalidInput(_))));
+    assert_eq!(repo.find_workflow_transitions().await.unwrap().len(), 2);
+
+    assert!(matches!(repo.move_task(task.id, "shipped").await, Err(CoreError::InvalidTransition(_))));
+    assert!(matches!(repo.move_task(task.id, "nowhere").await, Err(CoreError::InvalidInput(_))));
+    assert!(matches!(repo.move_task(Uuid::now_v7(), "review").await, Err(CoreError::NotFound(_))));
+
+    let filtered = query_ids(repo, Query::Filter(QueryFilter::WorkflowStatus("in-progress".to_string()))).await;
+    assert_eq!(filtered, HashSet::from([task.id]));
+    let details = repo.find_tasks_with_details(&Query::Filter(QueryFilter::WorkflowStatus("in-progress".to_string()))).await.unwrap();
+    assert_eq!(details[0].workflow_status, "in-progress");
+
+    repo.move_task(task.id, "review").await.unwrap();
+
+    // Completing and cancelling follow the same transitions as moving
+    assert!(matches!(repo.complete_task(task.id).await, Err(CoreError::InvalidTransition(_))));
+    assert!(matches!(repo.cancel_task(task.id).await, Err(CoreError::InvalidTransition(_))));
+    assert!(matches!(repo.bulk_update_tasks(&[task.id], BulkAction::Complete).await, Err(CoreError::InvalidTransition(_))));
+    assert!(matches!(repo.bulk_update_tasks(&[task.id], BulkAction::Cancel).await, Err(CoreError::InvalidTransition(_))));
+    let unchanged = repo.find_task_by_id(task.id).await.unwrap().unwrap();
+    assert_eq!((unchanged.status, unchanged.workflow_status.as_str()), (TaskStatus::Pending, "review"));
+
+    let shipped = repo.move_task(task.id, "shipped").await.unwrap();
+    assert_eq!((shipped.status.clone(), shipped.workflow_status.as_str()), (TaskStatus::Completed, "shipped"));
+    assert_eq!(shipped.completed_at, Some(clock.now()));
+
+    // Reopening clears the completion time
+    let reopened = repo.move_task(task.id, "pending").await.unwrap();
+    assert_eq!((reopened.status, reopened.completed_at), (TaskStatus::Pending, None));
+
+    // Plain completion and edits keep the workflow status on the base category
+    let completed = repo.complete_task(task.id).await.unwrap().into_completed();
+    assert_eq!(completed.workflow_status, "completed");
+    let edited = repo.update_task(task.id, UpdateTaskData { status: Some(TaskStatus::Pending), ..Default::default() }, None).await.unwrap();
+    assert_eq!(edited.workflow_status, "pending");
+
+    repo.move_task(task.id, "review").await.unwrap();
+    assert!(matches!(repo.delete_workflow_status("review").await, Err(CoreError::InvalidInput(_))));
+    assert!(matches!(repo.delete_workflow_status("pending").await, Err(CoreError::InvalidInput(_))));
+    repo.move_task(task.id, "shipped").await.unwrap();
+    repo.delete_workflow_status("review").await.unwrap();
+    assert!(repo.find_workflow_status("review").await.unwrap().is_none());
+    assert!(repo.find_workflow_transitions().await.unwrap().is_empty());
+    assert!(matches!(repo.delete_workflow_status("review").await, Err(CoreError::InvalidInput(_))));
+    assert!(matches!(repo.remove_workflow_transition("review", "shipped").await, Err(CoreError::NotFound(_))));
+}
+
+async fn check_time_tracking<R: Repository + Sync>(repo: &R, clock: &FakeClock) {
+    repo.add_project("Work".to_string(), None).await.unwrap();
+    let report = repo.add_task(NewTaskData {
+        name: "Write report".to_string(),
+        project_name: Some("Work".to_string()),
+        tags: vec!["writing".to_string(), "q2".to_string()],
+        ..Default::default()
+    }).await.unwrap();
+    let review = repo.add_task(NewTaskData { name: "Review PR".to_string(), ..Default::default() }).await.unwrap();
+
+    let timer = repo.start_timer(report.id).await.unwrap();
+    assert!(timer.is_running());
+    assert_eq!(repo.find_active_timer().await.unwrap(), Some(timer.clone()));
+    match repo.start_timer(review.id).await {
+        Err(CoreError::TimerRunning(name)) => assert_eq!(name, "Write report"),
+        other => panic!("expected a running timer error, got {:?}", other),
+    }
+    assert!(matches!(repo.start_timer(Uuid::now_v7()).await, Err(CoreError::NotFound(_))));
+
+    clock.advance(Duration::minutes(30));
+    let stopped = repo.stop_timer().await.unwrap().unwrap();
+    assert_eq!((stopped.id, stopped.ended_at, stopped.duration_seconds), (timer.id, Some(clock.now()), Some(1800)));
+    assert!(repo.find_active_timer().await.unwrap().is_none());
+    assert!(repo.stop_timer().await.unwrap().is_none());
+
+    let logged = repo.log_time(review.id, Duration::minutes(90)).await.unwrap();
+    assert_eq!(logged.started_at, clock.now() - Duration::minutes(90));
+    assert_eq!(logged.duration(), Some(Duration::minutes(90)));
+    assert!(matches!(repo.log_time(review.id, Duration::zero()).await, Err(CoreError::InvalidInput(_))));
+
+    // Running timers do not count towards the total yet
+    repo.start_timer(report.id).await.unwrap();
+    let tracked: HashMap<Uuid, i64> = repo
+        .find_tasks_with_details(&Query::Filter(QueryFilter::Status(TaskStatus::Pending)))
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|r| (r.id, r.tracked_seconds))
+        .collect();
+    assert_eq!((tracked[&report.id], tracked[&review.id]), (1800, 5400));
+    assert_eq!(repo.find_time_entries(report.id).await.unwrap().len(), 2);
+    clock.advance(Duration::minutes(15));
+    repo.stop_timer().await.unwrap();
+
+    let sheet = repo.find_timesheet_entries(start_time() - Duration::hours(1), start_time() + Duration::days(1)).await.unwrap();
+    let summary: Vec<(&str, Option<&str>, i64)> = sheet
+        .iter()
+        .map(|e| (e.task_name.as_str(), e.project_name.as_deref(), e.duration_seconds))
+        .collect();
+    assert_eq!(summary, vec![
+        ("Review PR", None, 5400),
+        ("Write report", Some("Work"), 1800),
+        ("Write report", Some("Work"), 900),
+    ]);
+    assert!(sheet[0].tags.is_none());
+    let mut tags: Vec<&str> = sheet[1].tags.as_deref().unwrap().split(',').collect();
+    tags.sort();
+    assert_eq!(tags, vec!["q2", "writing"]);
+    assert_eq!(repo.find_timesheet_entries(start_time(), start_time() + Duration::days(1)).await.unwrap().len(), 2);
+    assert!(repo.find_timesheet_entries(start_time() + Duration::days(1), start_time() + Duration::days(2)).await.unwrap().is_empty());
+
+    // Tracked time on completed occurrences feeds the series average
+    let template = repo.add_task(NewTaskData {
+        name: "Weekly review".to_string(),
+        due_at: Some(start_time()),
+        rrule: Some("FREQ=DAILY;COUNT=3".to_string()),
+        timezone: Some("UTC".to_string()),
+        ..Default::default()
+    }).await.unwrap();
+    let series = repo.find_series_by_template(template.id).await.unwrap().unwrap();
+    assert_eq!(repo.get_series_statistics(series.id).await.unwrap().average_completion_time_hours, None);
+    let instances = repo.find_materialized_tasks_for_series(series.id, start_time(), start_time() + Duration::days(5)).await.unwrap();
+    for (instance, hours) in instances.iter().zip([1, 2, 5]) {
+        repo.log_time(instance.id, Duration::hours(hours)).await.unwrap();
+    }
+    repo.complete_task(instances[0].id).await.unwrap();
+    repo.complete_task(instances[1].id).await.unwrap();
+    assert_eq!(repo.get_series_statistics(series.id).await.unwrap().average_completion_time_hours, Some(1.5));
+
+    repo.delete_task(review.id).await.unwrap();
+    assert!(repo.find_time_entries(review.id).await.unwrap().is_empty());
+}
+
+async fn check_estimates_and_forecast<R: Repository + Sync>(repo: &R) {
+    let report = repo.add_task(NewTaskData {
+        name: "Write report".to_string(),
+        due_at: Some(start_time() + Duration::hours(25)),
+        estimate: Some(Duration::hours(3)),
+        ..Default::default()
+    }).await.unwrap();
+    assert_eq!(report.estimate(), Some(Duration::hours(3)));
+    for (name, due_at) in [("Someday", None), ("Last week", Some(start_time() - Duration::days(7)))] {
+        repo.add_task(NewTaskData { name: name.to_string(), due_at, estimate: Some(Duration::hours(1)), ..Default::default() }).await.unwrap();
+    }
+    repo.add_task(NewTaskData { name: "Unestimated".to_string(), due_at: Some(start_time()), ..Default::default() }).await.unwrap();
+    assert!(matches!(
+        repo.add_task(NewTaskData { name: "Tiny".to_string(), estimate: Some(Duration::seconds(30)), ..Default::default() }).await,
+        Err(CoreError::InvalidInput(_))
+    ));
+
+    let update = |estimate| UpdateTaskData { estimate: Some(estimate), ..Default::default() };
+    let report = repo.update_task(report.id, update(Some(Duration::hours(4))), None).await.unwrap();
+    assert_eq!(report.estimate_minutes, Some(240));
+
+    // Instances inherit the template's estimate
+    let template = repo.add_task(NewTaskData {
+        name: "Standup".to_string(),
+        due_at: Some(start_time()),
+        rrule: Some("FREQ=DAILY".to_string()),
+        timezone: Some("UTC".to_string()),
+        estimate: Some(Duration::minutes(30)),
+        ..Default::default()
+    }).await.unwrap();
+    let series = repo.find_series_by_template(template.id).await.unwrap().unwrap();
+    let instances = repo.find_materialized_tasks_for_series(series.id, start_time(), start_time() + Duration::days(3)).await.unwrap();
+    assert!(!instances.is_empty());
+    assert!(instances.iter().all(|t| t.estimate_minutes == Some(30)));
+
+    repo.add_series_exception(NewSeriesException {
+        series_id: series.id,
+        occurrence_dt: start_time() + Duration::days(40),
+        exception_type: ExceptionType::Skip,
+        exception_task_id: None,
+        notes: None,
+    }).await.unwrap();
+    repo.complete_task(instances[3].id).await.unwrap();
+
+    // Sixty days reach well past the materialization window
+    let window_start = start_time() - Duration::hours(1);
+    let items = forecast::collect_forecast_items(repo, window_start, window_start + Duration::days(60)).await.unwrap();
+    assert!(items.iter().all(|i| i.task_id != Some(template.id)));
+    let standups: Vec<&forecast::ForecastItem> = items.iter().filter(|i| i.series_id == Some(series.id)).collect();
+    let due_dates: HashSet<DateTime<Utc>> = standups.iter().map(|i| i.due_at).collect();
+    assert_eq!((standups.len(), due_dates.len()), (58, 58));
+    assert!(!due_dates.contains(&(start_time() + Duration::days(3))));
+    assert!(!due_dates.contains(&(start_time() + Duration::days(40))));
+    assert!(standups.iter().any(|i| i.task_id.is_some()) && standups.iter().any(|i| i.task_id.is_none()));
+    let others: Vec<(&str, i64)> = items
+        .iter()
+        .filter(|i| i.series_id.is_none())
+        .map(|i| (i.name.as_str(), i.estimate_minutes))
+        .collect();
+    assert_eq!(others, vec![("Write report", 240)]);
+
+    let days = forecast::group_by_day(items, &Utc, start_time().date_naive(), 3);
+    let planned: Vec<i64> = days.iter().map(|d| d.planned_minutes()).collect();
+    assert_eq!(planned, vec![30, 270, 30]);
+    assert!(days[1].is_overloaded(240) && !days[0].is_overloaded(240));
+
+    let report = repo.update_task(report.id, update(None), None).await.unwrap();
+    assert_eq!(report.estimate_minutes, None);
+}
+
+async fn check_blocking_and_urgency<R: Repository + Sync>(repo: &R, clock: &FakeClock) {
+    let design = repo.add_task(NewTaskData {
+        name: "Design".to_string(),
+        priority: Some(TaskPriority::High),
+        ..Default::default()
+    }).await.unwrap();
+    let build = repo.add_task(NewTaskData {
+        name: "Build".to_string(),
+        priority: Some(TaskPriority::High),
+        depends_on: Some(design.id),
+        ..Default::default()
+    }).await.unwrap();
+    let overdue = repo.add_task(NewTaskData {
+        name: "Pay invoice".to_string(),
+        due_at: Some(clock.now() - Duration::days(7)),
+        tags: vec!["money".to_string()],
+        ..Default::default()
+    }).await.unwrap();
+
+    let everything = Query::or(vec![QueryFilter::Status(TaskStatus::Pending), QueryFilter::Status(TaskStatus::Completed)]);
+    let find = |tasks: &[rusk_core::repository::TaskQueryResult], id: Uuid| {
+        tasks.iter().find(|t| t.id == id).cloned().unwrap()
+    };
+    let tasks = repo.find_tasks_with_details(&everything).await.unwrap();
+    let (design_row, build_row) = (find(&tasks, design.id), find(&tasks, build.id));
+    assert!(design_row.is_blocking && !design_row.is_blocked);
+    assert!(build_row.is_blocked && !build_row.is_blocking);
+
+    let coefficients = UrgencyCoefficients {
+        tags: HashMap::from([("money".to_string(), 2.0)]),
+        ..Default::default()
+    };
+    let score = |task: &rusk_core::repository::TaskQueryResult| urgency::urgency(task, &coefficients, clock.now());
+    assert!((score(&design_row) - 14.0).abs() < 1e-9);
+    assert!((score(&build_row) - 1.0).abs() < 1e-9);
+    assert!((score(&find(&tasks, overdue.id)) - 14.8).abs() < 1e-9);
+
+    // Completing the dependency unblocks its dependent
+    repo.complete_task(design.id).await.unwrap();
+    let tasks = repo.find_tasks_with_details(&everything).await.unwrap();
+    let (design_row, build_row) = (find(&tasks, design.id), find(&tasks, build.id));
+    assert!(!build_row.is_blocked);
+    assert_eq!(score(&design_row), 0.0);
+    assert!((score(&build_row) - 6.0).abs() < 1e-9);
+}
+
+async fn check_scheduling_dates<R: Repository + Sync>(repo: &R, clock: &FakeClock) {
+    let now = clock.now();
+    let waiting = repo.add_task(NewTaskData {
+        name: "Renew passport".to_string(),
+        wait_until: Some(now + Duration::days(3)),
+        ..Default::default()
+    }).await.unwrap();
+    let scheduled = repo.add_task(NewTaskData {
+        name: "Plan trip".to_string(),
+        due_at: Some(now + Duration::days(10)),
+        scheduled_at: Some(now + Duration::days(8)),
+        start_at: Some(now + Duration::days(9)),
+        ..Default::default()
+    }).await.unwrap();
+    assert_eq!(scheduled.scheduled_at, Some(now + Duration::days(8)));
+    assert_eq!(scheduled.start_at, Some(now + Duration::days(9)));
+    repo.add_task(NewTaskData {
+        name: "Call bank".to_string(),
+        wait_until: Some(now - Duration::days(1)),
+        ..Default::default()
+    }).await.unwrap();
+
+    let names = |query: Query| async move {
+        let mut names: Vec<String> = repo.find_tasks_with_details(&query).await.unwrap().into_iter().map(|t| t.name).collect();
+        names.sort();
+        names
+    };
+    assert_eq!(names(Query::Filter(QueryFilter::Waiting)).await, vec!["Renew passport"]);
+    assert_eq!(names(Query::Filter(QueryFilter::Scheduled)).await, vec!["Plan trip"]);
+    let actionable = Query::and(vec![QueryFilter::Status(TaskStatus::Pending)]);
+    let actionable = Query::Binary {
+        op: Operator::And,
+        left: Box::new(actionable),
+        right: Box::new(Query::Not(Box::new(Query::or(vec![QueryFilter::Waiting, QueryFilter::Scheduled])))),
+    };
+    assert_eq!(names(actionable).await, vec!["Call bank"]);
+
+    repo.complete_task(waiting.id).await.unwrap();
+    assert_eq!(names(Query::Filter(QueryFilter::All)).await, vec!["Call bank", "Plan trip", "Renew passport"]);
+
+    let updated = repo.update_task(scheduled.id, UpdateTaskData {
+        scheduled_at: Some(None),
+        wait_until: Some(Some(now + Duration::days(2))),
+        ..Default::default()
+    }, None).await.unwrap();
+    assert_eq!((updated.scheduled_at, updated.wait_until), (None, Some(now + Duration::days(2))));
+    assert_eq!(updated.start_at, scheduled.start_at);
+
+    // Instances keep the template's distance from due
+    let template = repo.add_task(NewTaskData {
+        name: "Pay rent".to_string(),
+        due_at: Some(start_time()),
+        rrule: Some("FREQ=WEEKLY;COUNT=4".to_string()),
+        timezone: Some("UTC".to_string()),
+        scheduled_at: Some(start_time() - Duration::days(2)),
+        ..Default::default()
+    }).await.unwrap();
+    let series = repo.find_series_by_template(template.id).await.unwrap().unwrap();
+    let window_end = start_time() + Duration::days(30);
+    let instances = repo.find_materialized_tasks_for_series(series.id, start_time(), window_end).await.unwrap();
+    assert_eq!(instances.len(), 4);
+    assert!(instances.iter().all(|t| t.scheduled_at == t.due_at.map(|d| d - Duration::days(2))));
+    assert!(instances.iter().all(|t| t.wait_until.is_none()));
+
+    let moved = repo.move_occurrence_with_validation(series.id, instances[1].due_at.unwrap(), instances[1].due_at.unwrap() + Duration::hours(5), "UTC").await.unwrap();
+    assert_eq!(moved.scheduled_at, moved.due_at.map(|d| d - Duration::days(2)));
+
+    // A series edit given for one occurrence is re-anchored on the template
+    let third = &instances[2];
+    repo.update_task(third.id, UpdateTaskData {
+        wait_until: Some(Some(third.due_at.unwrap() - Duration::days(1))),
+        ..Default::default()
+    }, Some(EditScope::EntireSeries)).await.unwrap();
+    let template = repo.find_task_by_id(template.id).await.unwrap().unwrap();
+    assert_eq!(template.wait_until, Some(start_time() - Duration::days(1)));
+    repo.refresh_series_materialization(start_time(), window_end).await.unwrap();
+    let instances = repo.find_materialized_tasks_for_series(series.id, start_time(), window_end).await.unwrap();
+    assert!(!instances.is_empty());
+    assert!(instances.iter().all(|t| t.wait_until == t.due_at.map(|d| d - Duration::days(1))));
+}
+
+async fn check_annotations<R: Repository + Sync>(repo: &R, clock: &FakeClock) {
+    let task = repo.add_task(NewTaskData { name: "Order parts".to_string(), ..Default::default() }).await.unwrap();
+    let other = repo.add_task(NewTaskData { name: "Sweep floor".to_string(), ..Default::default() }).await.unwrap();
+
+    let first = repo.add_annotation(task.id, "  Asked for a quote ").await.unwrap();
+    assert_eq!((first.task_id, first.text.as_str(), first.created_at), (task.id, "Asked for a quote", clock.now()));
+    clock.advance(Duration::hours(2));
+    repo.add_annotation(task.id, "Quote arrived, invoice pending").await.unwrap();
+    let notes: Vec<String> = repo.find_annotations(task.id).await.unwrap().into_iter().map(|a| a.text).collect();
+    assert_eq!(notes, vec!["Asked for a quote", "Quote arrived, invoice pending"]);
+    assert!(repo.find_annotations(other.id).await.unwrap().is_empty());
+
+    assert!(matches!(repo.add_annotation(task.id, "   ").await, Err(CoreError::InvalidInput(_))));
+    assert!(matches!(repo.add_annotation(Uuid::now_v7(), "Lost").await, Err(CoreError::NotFound(_))));
+
+    let names = |filter: TextFilter| async move {
+        let query = Query::Filter(QueryFilter::Note(filter));
+        let mut names: Vec<String> = repo.find_tasks_with_details(&query).await.unwrap().into_iter().map(|t| t.name).collect();
+        names.sort();
+        names
+    };
+    assert_eq!(names(TextFilter::Contains("INVOICE".to_string())).await, vec!["Order parts"]);
+    assert_eq!(names(TextFilter::StartsWith("asked".to_string())).await, vec!["Order parts"]);
+    assert_eq!(names(TextFilter::NotContains("invoice".to_string())).await, vec!["Sweep floor"]);
+
+    // Notes stay with their occurrence when a series edit regenerates the instances
+    let template = repo.add_task(NewTaskData {
+        name: "Water plants".to_string(),
+        due_at: Some(start_time()),
+        rrule: Some("FREQ=WEEKLY;COUNT=4".to_string()),
+        timezone: Some("UTC".to_string()),
+        ..Default::default()
+    }).await.unwrap();
+    let series = repo.find_series_by_template(template.id).await.unwrap().unwrap();
+    let window_end = start_time() + Duration::days(30);
+    let instances = repo.find_materialized_tasks_for_series(series.id, start_time(), window_end).await.unwrap();
+    let second = &instances[1];
+    repo.add_annotation(second.id, "Fern looked dry").await.unwrap();
+
+    repo.update_task(instances[0].id, UpdateTaskData {
+        name: Some("Water all plants".to_string()),
+        ..Default::default()
+    }, Some(EditScope::EntireSeries)).await.unwrap();
+    repo.refresh_series_materialization(start_time(), window_end).await.unwrap();
+
+    let instances = repo.find_materialized_tasks_for_series(series.id, start_time(), window_end).await.unwrap();
+    let regenerated = instances.iter().find(|t| t.due_at == second.due_at).unwrap();
+    assert_ne!(regenerated.id, second.id);
+    assert_eq!(regenerated.name, "Water all plants");
+    let notes: Vec<String> = repo.find_annotations(regenerated.id).await.unwrap().into_iter().map(|a| a.text).collect();
+    assert_eq!(notes, vec!["Fern looked dry"]);
+    for instance in instances.iter().filter(|t| t.id != regenerated.id) {
+        assert!(repo.find_annotations(instance.id).await.unwrap().is_empty());
+    }
+
+    repo.delete_task(task.id).await.unwrap();
+    assert!(repo.find_annotations(task.id).await.unwrap().is_empty());
+}
+
+async fn check_task_relations<R: Repository + Sync>(repo: &R, clock: &FakeClock) {
+    let add = |name: &str, parent_id: Option<Uuid>, depends_on: Option<Uuid>| {
+        let data = NewTaskData { name: name.to_string(), parent_id, depends_on, ..Default::default() };
+        async move { repo.add_task(data).await.unwrap() }
+    };
+    let project = add("Renovate", None, None).await;
+    clock.advance(Duration::minutes(1));
+    let kitchen = add("Kitchen", Some(project.id), None).await;
+    clock.advance(Duration::minutes(1));
+    let paint = add("Buy paint", Some(kitchen.id), None).await;
+    clock.advance(Duration::minutes(1));
+    let walls = add("Paint walls", Some(kitchen.id), Some(paint.id)).await;
+    clock.advance(Duration::minutes(1));
+    let trim = add("Paint trim", None, Some(paint.id)).await;
+
+    let ids = |tasks: &[Task]| tasks.iter().map(|t| t.id).collect::<Vec<_>>();
+    let relations = repo.find_task_relations(walls.id).await.unwrap();
+    assert_eq!(ids(&relations.ancestors), vec![kitchen.id, project.id]);
+    assert!(relations.subtasks.is_empty());
+    assert_eq!(ids(&relations.depends_on), vec![paint.id]);
+    assert!(relations.dependents.is_empty());
+
+    let relations = repo.find_task_relations(paint.id).await.unwrap();
+    assert_eq!(ids(&relations.dependents), vec![walls.id, trim.id]);
+    assert_eq!(ids(&repo.find_task_relations(kitchen.id).await.unwrap().subtasks), vec![paint.id, walls.id]);
+    assert!(matches!(repo.find_task_relations(Uuid::now_v7()).await, Err(CoreError::NotFound(_))));
+
+    let rows = repo.find_tasks_with_details(&Query::Filter(QueryFilter::Id(walls.id))).await.unwrap();
+    assert_eq!(rows.iter().map(|t| (t.id, t.depth, t.is_blocked)).collect::<Vec<_>>(), vec![(walls.id, 2, true)]);
+}
+
+async fn check_bulk_updates<R: Repository + Sync>(repo: &R, clock: &FakeClock) {
+    repo.add_project("Old".to_string(), None).await.unwrap();
+    let new_project = repo.add_project("New".to_string(), None).await.unwrap();
+    let add = |name: &str, parent_id: Option<Uuid>, depends_on: Option<Uuid>| {
+        let data = NewTaskData {
+            name: name.to_string(),
+            project_name: Some("Old".to_string()),
+            parent_id,
+            depends_on,
+            ..Default::default()
+        };
+        async move { repo.add_task(data).await.unwrap() }
+    };
+    let draft = add("Draft", None, None).await;
+    let review = add("Review", None, Some(draft.id)).await;
+    let archive = add("Archive", None, None).await;
+    let folder = add("Folder", None, None).await;
+    let file = add("File", Some(folder.id), None).await;
+    clock.advance(Duration::minutes(5));
+
+    let moved = UpdateTaskData { project_name: Some(Some("New".to_string())), ..Default::default() };
+    let updated = repo.bulk_update_tasks(&[draft.id, archive.id], BulkAction::Update(Box::new(moved))).await.unwrap();
+    assert!(updated.iter().all(|t| t.project_id == Some(new_project.id) && t.updated_at == clock.now()));
+
+    // One blocked task rolls back the whole batch
+    let result = repo.bulk_update_tasks(&[archive.id, review.id], BulkAction::Complete).await;
+    assert!(matches!(result, Err(CoreError::TaskBlocked(_))));
+    assert_eq!(repo.find_task_by_id(archive.id).await.unwrap().unwrap().status, TaskStatus::Pending);
+
+    // A dependency completed in the same batch no longer blocks
+    let completed = repo.bulk_update_tasks(&[review.id, draft.id], BulkAction::Complete).await.unwrap();
+    assert_eq!(completed.iter().map(|t| t.id).collect::<Vec<_>>(), vec![review.id, draft.id]);
+    assert!(completed.iter().all(|t| t.status == TaskStatus::Completed && t.completed_at == Some(clock.now())));
+
+    let result = repo.bulk_update_tasks(&[archive.id, Uuid::now_v7()], BulkAction::Cancel).await;
+    assert!(matches!(result, Err(CoreError::NotFound(_))));
+    assert_eq!(repo.find_task_by_id(archive.id).await.unwrap().unwrap().status, TaskStatus::Pending);
+    let cancelled = repo.bulk_update_tasks(&[archive.id], BulkAction::Cancel).await.unwrap();
+    assert_eq!(cancelled[0].workflow_status, "cancelled");
+
+    // Subtasks already deleted with their parent do not fail the batch
+    let deleted = repo.bulk_update_tasks(&[folder.id, file.id], BulkAction::Delete).await.unwrap();
+    assert_eq!(deleted.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(), vec!["Folder", "File"]);
+    assert!(repo.find_task_by_id(file.id).await.unwrap().is_none());
+
+    // Occurrences deleted in bulk are skipped rather than re-materialized
+    let day = |n: i64| start_time() + Duration::days(n);
+    let data = NewTaskData {
+        name: "Stretch".to_string(),
+        due_at: Some(start_time()),
+        rrule: Some("FREQ=DAILY;COUNT=5".to_string()),
+        timezone: Some("UTC".to_string()),
+        ..Default::default()
+    };
+    let template = repo.add_task(data).await.unwrap();
+    let series = repo.find_series_by_template(template.id).await.unwrap().unwrap();
+    let instances = repo.find_materialized_tasks_for_series(series.id, start_time(), day(10)).await.unwrap();
+    let second = instances.iter().find(|t| t.due_at == Some(day(1))).unwrap();
+    repo.bulk_update_tasks(&[second.id], BulkAction::Delete).await.unwrap();
+    repo.refresh_series_materialization(start_time(), day(10)).await.unwrap();
+    let remaining = repo.find_materialized_tasks_for_series(series.id, start_time(), day(10)).await.unwrap();
+    assert!(remaining.iter().all(|t| t.id == template.id || t.due_at != Some(day(1))));
+    let exceptions = repo.find_series_exceptions(series.id).await.unwrap();
+    assert!(exceptions.iter().any(|e| e.occurrence_dt == day(1) && e.exception_type == ExceptionType::Skip));
+
+    // Moves, notes and logged time apply to every task, and bad input to none
+    let errands = vec![add("Post office", None, None).await.id, add("Bank", None, None).await.id];
+    assert!(repo.bulk_update_tasks(&errands, BulkAction::Move("nowhere".to_string())).await.is_err());
+    let moved = repo.bulk_update_tasks(&errands, BulkAction::Move("Completed".to_string())).await.unwrap();
+    assert!(moved.iter().all(|t| t.status == TaskStatus::Completed && t.workflow_status == "completed"));
+
+    let result = repo.bulk_update_tasks(&errands, BulkAction::Annotate("  ".to_string())).await;
+    assert!(matches!(result, Err(CoreError::InvalidInput(_))));
+    repo.bulk_update_tasks(&errands, BulkAction::Annotate(" Closes at five ".to_string())).await.unwrap();
+    let result = repo.bulk_update_tasks(&errands, BulkAction::LogTime(Duration::zero())).await;
+    assert!(matches!(result, Err(CoreError::InvalidInput(_))));
+    repo.bulk_update_tasks(&errands, BulkAction::LogTime(Duration::minutes(15))).await.unwrap();
+    for id in errands {
+        let notes = repo.find_annotations(id).await.unwrap();
+        assert_eq!(notes.iter().map(|a| a.text.as_str()).collect::<Vec<_>>(), vec!["Closes at five"]);
+        let entries = repo.find_time_entries(id).await.unwrap();
+        assert_eq!(entries.iter().map(|e| e.duration_seconds).collect::<Vec<_>>(), vec![Some(900)]);
+    }
+}
+
+/// Parent with two subtasks, the second of which has a subtask of its own
+async fn add_subtask_tree<R: Repository + Sync>(repo: &R, clock: &FakeClock) -> (Task, Task, Task, Task) {
+    let add = |name: &str, parent_id: Option<Uuid>| {
+        let data = NewTaskData { name: name.to_string(), parent_id, ..Default::default() };
+        async move { repo.add_task(data).await.unwrap() }
+    };
+    let parent = add("Move house", None).await;
+    clock.advance(Duration::minutes(1));
+    let pack = add("Pack boxes", Some(parent.id)).await;
+    clock.advance(Duration::minutes(1));
+    let van = add("Book van", Some(parent.id)).await;
+    clock.advance(Duration::minutes(1));
+    let quote = add("Get quotes", Some(van.id)).await;
+    clock.advance(Duration::minutes(1));
+    (parent, pack, van, quote)
+}
+
+async fn status_of<R: Repository + Sync>(repo: &R, id: Uuid) -> TaskStatus {
+    repo.find_task_by_id(id).await.unwrap().unwrap().status
+}
+
+async fn check_subtask_blocking<R: Repository + Sync>(repo: &R, clock: &FakeClock) {
+    let (parent, pack, van, quote) = add_subtask_tree(repo, clock).await;
+
+    let result = repo.complete_task(parent.id).await;
+    assert!(matches!(result, Err(CoreError::PendingSubtasks(names)) if names == "Pack boxes, Book van, Get quotes"));
+    assert_eq!(status_of(repo, parent.id).await, TaskStatus::Pending);
+
+    // Cancelled subtasks no longer hold the parent back
+    repo.complete_task(quote.id).await.unwrap();
+    repo.complete_task(van.id).await.unwrap();
+    repo.cancel_task(pack.id).await.unwrap();
+    let rows = repo.find_tasks_with_details(&Query::Filter(QueryFilter::Id(parent.id))).await.unwrap();
+    assert_eq!((rows[0].subtask_count, rows[0].completed_subtask_count), (2, 2));
+    repo.complete_task(parent.id).await.unwrap();
+
+    // Subtasks completed in the same batch do not block
+    let (parent, pack, van, quote) = add_subtask_tree(repo, clock).await;
+    repo.bulk_update_tasks(&[parent.id, pack.id, van.id, quote.id], BulkAction::Complete).await.unwrap();
+    assert_eq!(status_of(repo, parent.id).await, TaskStatus::Completed);
+}
+
+async fn check_subtask_cascade<R: Repository + Sync>(repo: &R, clock: &FakeClock) {
+    let (parent, pack, van, quote) = add_subtask_tree(repo, clock).await;
+    repo.cancel_task(pack.id).await.unwrap();
+
+    let rows = repo.find_tasks_with_details(&Query::Filter(QueryFilter::Id(parent.id))).await.unwrap();
+    assert_eq!((rows[0].subtask_count, rows[0].completed_subtask_count), (2, 0));
+
+    repo.complete_task(parent.id).await.unwrap();
+    for id in [van.id, quote.id] {
+        let task = repo.find_task_by_id(id).await.unwrap().unwrap();
+        assert_eq!((task.status, task.completed_at), (TaskStatus::Completed, Some(clock.now())));
+    }
+    assert_eq!(status_of(repo, pack.id).await, TaskStatus::Cancelled);
+}
+
+async fn check_parent_completion<R: Repository + Sync>(repo: &R, clock: &FakeClock) {
+    let (parent, pack, van, quote) = add_subtask_tree(repo, clock).await;
+
+    repo.complete_task(quote.id).await.unwrap();
+    assert_eq!(status_of(repo, van.id).await, TaskStatus::Completed);
+    assert_eq!(status_of(repo, parent.id).await, TaskStatus::Pending);
+
+    let rows = repo.find_tasks_with_details(&Query::Filter(QueryFilter::Id(parent.id))).await.unwrap();
+    assert_eq!((rows[0].subtask_count, rows[0].completed_subtask_count), (3, 2));
+
+    repo.complete_task(pack.id).await.unwrap();
+    let parent = repo.find_task_by_id(parent.id).await.unwrap().unwrap();
+    assert_eq!((parent.status, parent.completed_at), (TaskStatus::Completed, Some(clock.now())));
+
+    // A parent waiting on a dependency stays pending
+    let blocker = repo.add_task(NewTaskData { name: "Sign lease".to_string(), ..Default::default() }).await.unwrap();
+    let (parent, pack, van, quote) = add_subtask_tree(repo, clock).await;
+    let depends_on = UpdateTaskData { depends_on: Some(Some(blocker.id)), ..Default::default() };
+    repo.update_task(parent.id, depends_on, None).await.unwrap();
+    repo.bulk_update_tasks(&[pack.id, quote.id, van.id], BulkAction::Complete).await.unwrap();
+    assert_eq!(status_of(repo, parent.id).await, TaskStatus::Pending);
+}
+
+async fn check_template_subtasks<R: Repository + Sync>(repo: &R, clock: &FakeClock) {
+    let template = repo.add_task(NewTaskData {
+        name: "Weekly review".to_string(),
+        due_at: Some(start_time()),
+        rrule: Some("FREQ=WEEKLY".to_string()),
+        timezone: Some("UTC".to_string()),
+        ..Default::default()
+    }).await.unwrap();
+    let series = repo.find_series_by_template(template.id).await.unwrap().unwrap();
+    clock.advance(Duration::minutes(1));
+    let inbox = repo.add_task(NewTaskData {
+        name: "Empty inbox".to_string(),
+        parent_id: Some(template.id),
+        scheduled_at: Some(start_time() - Duration::days(1)),
+        ..Default::default()
+    }).await.unwrap();
+    clock.advance(Duration::minutes(1));
+    repo.add_task(NewTaskData { name: "File notes".to_string(), parent_id: Some(inbox.id), ..Default::default() }).await.unwrap();
+
+    // Instances materialized from here on get their own copy of the subtree
+    let window_start = start_time() + Duration::days(60);
+    let window_end = window_start + Duration::days(13);
+    repo.refresh_series_materialization(window_start, window_end).await.unwrap();
+    let instances = repo.find_materialized_tasks_for_series(series.id, window_start, window_end).await.unwrap();
+    assert_eq!(instances.len(), 2);
+    let mut copies = Vec::new();
+    for instance in &instances {
+        let subtasks = repo.find_task_relations(instance.id).await.unwrap().subtasks;
+        assert_eq!(subtasks.len(), 1);
+        let copy = &subtasks[0];
+        assert_eq!((copy.name.as_str(), copy.status.clone(), copy.series_id), ("Empty inbox", TaskStatus::Pending, None));
+        assert_eq!(copy.scheduled_at, instance.due_at.map(|due| due - Duration::days(1)));
+        let nested = repo.find_task_relations(copy.id).await.unwrap().subtasks;
+        assert_eq!(nested.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(), vec!["File notes"]);
+        copies.push(copy.id);
+    }
+    assert_ne!(copies[0], copies[1]);
+    assert_eq!(repo.find_task_relations(template.id).await.unwrap().subtasks.len(), 1);
+
+    // Completion cascades through the copies only
+    repo.complete_task(instances[0].id).await.unwrap();
+    assert_eq!(status_of(repo, copies[0]).await, TaskStatus::Completed);
+    assert_eq!(status_of(repo, inbox.id).await, TaskStatus::Pending);
+
+    // Deleting an instance takes its copies with it
+    repo.delete_task_with_scope(instances[1].id, EditScope::ThisOccurrence, false).await.unwrap();
+    assert!(repo.find_task_by_id(copies[1]).await.unwrap().is_none());
+}
+
+macro_rules! conformance_suite {
+    ($backend:ident, $setup:ident) => {
+        mod $backend {
+            use super::*;
+
+            #[tokio::test]
+            async fn task_lifecycle() {
+                let Some((repo, clock, _guard)) = $setup(stringify!(task_lifecycle)).await else { return };
+                check_task_lifecycle(&repo, &clock).await;
+            }
+
+            #[tokio::test]
+            async fn failed_writes_leave_no_trace() {
+                let Some((repo, _clock, _guard)) = $setup(stringify!(failed_writes_leave_no_trace)).await else { return };
+                check_failed_writes_leave_no_trace(&repo).await;
+            }
+
+            #[tokio::test]
+            async fn query_evaluation() {
+                let Some((repo, clock, _guard)) = $setup(stringify!(query_evaluation)).await else { return };
+                check_query_evaluation(&repo, &clock).await;
+            }
+
+            #[tokio::test]
+            async fn hierarchy_and_dependencies() {
+                let Some((repo, clock, _guard)) = $setup(stringify!(hierarchy_and_dependencies)).await else { return };
+                check_hierarchy_and_dependencies(&repo, &clock).await;
+            }
+
+            #[tokio::test]
+            async fn projects() {
+                let Some((repo, _clock, _guard)) = $setup(stringify!(projects)).await else { return };
+                check_projects(&repo).await;
+            }
+
+            #[tokio::test]
+            async fn series_materialization() {
+                let Some((repo, clock, _guard)) = $setup(stringify!(series_materialization)).await else { return };
+                check_series_materialization(&repo, &clock).await;
+            }
+
+            #[tokio::test]
+            async fn exceptions() {
+                let Some((repo, _clock, _guard)) = $setup(stringify!(exceptions)).await else { return };
+                check_exceptions(&repo).await;
+            }
+
+            #[tokio::test]
+            async fn exception_patterns() {
+                let Some((repo, clock, _guard)) = $setup(stringify!(exception_patterns)).await else { return };
+                check_exception_patterns(&repo, &clock).await;
+            }
+
+            #[tokio::test]
+            async fn series_history() {
+                let Some((repo, clock, _guard)) = $setup(stringify!(series_history)).await else { return };
+                check_series_history(&repo, &clock).await;
+            }
+
+            #[tokio::test]
+            async fn series_habits() {
+                let Some((repo, clock, _guard)) = $setup(stringify!(series_habits)).await else { return };
+                check_series_habits(&repo, &clock).await;
+            }
+
+            #[tokio::test]
+            async fn blueprints() {
+                let Some((repo, _clock, _guard)) = $setup(stringify!(blueprints)).await else { return };
+                check_blueprints(&repo).await;
+            }
+
+            #[tokio::test]
+            async fn recurrence_sets() {
+                let Some((repo, _clock, _guard)) = $setup(stringify!(recurrence_sets)).await else { return };
+                check_recurrence_sets(&repo).await;
+            }
+
+            #[tokio::test]
+            async fn time_modes() {
+                let Some((repo, clock, _guard)) = $setup(stringify!(time_modes)).await else { return };
+                check_time_modes(&repo.with_local_timezone(chrono_tz::America::New_York), &clock).await;
+            }
+
+            #[tokio::test]
+            async fn series_timezone_moves() {
+                let Some((repo, clock, _guard)) = $setup(stringify!(series_timezone_moves)).await else { return };
+                check_series_timezone_moves(&repo, &clock).await;
+            }
+
+            #[tokio::test]
+            async fn scoped_deletion() {
+                let Some((repo, clock, _guard)) = $setup(stringify!(scoped_deletion)).await else { return };
+                check_scoped_deletion(&repo, &clock).await;
+            }
+
+            #[tokio::test]
+            async fn dst_policy() {
+                let Some((repo, clock, _guard)) = $setup(stringify!(dst_policy)).await else { return };
+                check_dst_policy(&repo, &clock).await;
+            }
+
+            #[tokio::test]
+            async fn occurrence_overrides() {
+                let Some((repo, _clock, _guard)) = $setup(stringify!(occurrence_overrides)).await else { return };
+                check_occurrence_overrides(&repo).await;
+            }
+
+            #[tokio::test]
+            async fn reminders() {
+                let Some((repo, _clock, _guard)) = $setup(stringify!(reminders)).await else { return };
+                check_reminders(&repo).await;
+            }
+
+            #[tokio::test]
+            async fn workflow_statuses() {
+                let Some((repo, clock, _guard)) = $setup(stringify!(workflow_statuses)).await else { return };
+                check_workflow_statuses(&repo, &clock).await;
+            }
+
+            #[tokio::test]
+            async fn time_tracking() {
+                let Some((repo, clock, _guard)) = $setup(stringify!(time_tracking)).await else { return };
+                check_time_tracking(&repo, &clock).await;
+            }
+
+            #[tokio::test]
+            async fn estimates_and_forecast() {
+                let Some((repo, _clock, _guard)) = $setup(stringify!(estimates_and_forecast)).await else { return };
+                check_estimates_and_forecast(&repo).await;
+            }
+
+            #[tokio::test]
+            async fn blocking_and_urgency() {
+                let Some((repo, clock, _guard)) = $setup(stringify!(blocking_and_urgency)).await else { return };
+                check_blocking_and_urgency(&repo, &clock).await;
+            }
+
+            #[tokio::test]
+            async fn scheduling_dates() {
+                let Some((repo, clock, _guard)) = $setup(stringify!(scheduling_dates)).await else { return };
+                check_scheduling_dates(&repo, &clock).await;
+            }
+
+            #[tokio::test]
+            async fn annotations() {
+                let Some((repo, clock, _guard)) = $setup(stringify!(annotations)).await else { return };
+                check_annotations(&repo, &clock).await;
+            }
+
+            #[tokio::test]
+            async fn task_relations() {
+                let Some((repo, clock, _guard)) = $setup(stringify!(task_relations)).await else { return };
+                check_task_relations(&repo, &clock).await;
+            }
+
+            #[tokio::test]
+            async fn bulk_updates() {
+                let Some((repo, clock, _guard)) = $setup(stringify!(bulk_updates)).await else { return };
+                check_bulk_updates(&repo, &clock).await;
+            }
+
+            #[tokio::test]
+            async fn subtask_blocking() {
+                let Some((repo, clock, _guard)) = $setup(stringify!(subtask_blocking)).await else { return };
+                let policy = CompletionPolicy { subtasks: SubtaskPolicy::Block, complete_parent: false };
+                check_subtask_blocking(&repo.with_completion_policy(policy), &clock).await;
+            }
+
+            #[tokio::test]
+            async fn subtask_cascade() {
+                let Some((repo, clock, _guard)) = $setup(stringify!(subtask_cascade)).await else { return };
+                let policy = CompletionPolicy { subtasks: SubtaskPolicy::Cascade, complete_parent: false };
+                check_subtask_cascade(&repo.with_completion_policy(policy), &clock).await;
+            }
+
+            #[tokio::test]
+            async fn template_subtasks() {
+                let Some((repo, clock, _guard)) = $setup(stringify!(template_subtasks)).await else { return };
+                let policy = CompletionPolicy { subtasks: SubtaskPolicy::Cascade, complete_parent: false };
+                check_template_subtasks(&repo.with_completion_policy(policy), &clock).await;
+            }
+
+            #[tokio::test]
+            async fn parent_completion() {
+                let Some((repo, clock, _guard)) = $setup(stringify!(parent_completion)).await else { return };
+                let policy = CompletionPolicy { complete_parent: true, ..Default::default() };
+                check_parent_completion(&repo.with_completion_policy(policy), &clock).await;
+            }
+        }
+    };
+}
+
+conformance_suite!(sqlite, sqlite_backend);
+conformance_suite!(memory, memory_backend);
+conformance_suite!(postgres, postgres_backend);
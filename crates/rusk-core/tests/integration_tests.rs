@@ -1,4 +1,4 @@
-use rusk_core::db::establish_connection;
+use rusk_core::db::connect_sqlite;
 use rusk_core::models::*;
 use rusk_core::recurrence::*;
 use rusk_core::error::CoreError;
@@ -6,9 +6,15 @@ use rusk_core::query::{Query, Filter as QueryFilter, DueDate, TagFilter};
 use rusk_core::models::{Filter as ModelsFilter, DueDate as ModelsDueDate};
 use rusk_core::repository::{
     SqliteRepository, TaskRepository, ProjectRepository, 
-    SeriesRepository, MaterializationRepository, ExceptionRepository
+    SeriesRepository, MaterializationRepository, ExceptionRepository, ReminderRepository
 };
-use chrono::{DateTime, Utc, Duration};
+use rusk_core::clock::{Clock, FakeClock};
+use rusk_core::daemon::{Daemon, DaemonConfig, ReminderSink};
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Utc, Duration, TimeZone};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteConnection};
+use sqlx::Connection;
 use tempfile::TempDir;
 use uuid::Uuid;
 
@@ -17,7 +23,7 @@ async fn setup_test_db() -> (SqliteRepository, TempDir) {
     let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
     let db_path = temp_dir.path().join("test.db");
     
-    let pool = establish_connection(&db_path.to_string_lossy())
+    let pool = connect_sqlite(&db_path.to_string_lossy())
         .await
         .expect("Failed to establish test database connection");
     
@@ -27,6 +33,25 @@ async fn setup_test_db() -> (SqliteRepository, TempDir) {
     (repository, temp_dir)
 }
 
+/// Helper function to create a test database whose repository runs on a fake clock
+async fn setup_test_db_with_clock(start: DateTime<Utc>) -> (SqliteRepository, FakeClock, TempDir) {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+    let db_path = temp_dir.path().join("test.db");
+
+    let pool = connect_sqlite(&db_path.to_string_lossy())
+        .await
+        .expect("Failed to establish test database connection");
+
+    let clock = FakeClock::new(start);
+    let repository = SqliteRepository::with_clock(
+        pool,
+        MaterializationManager::with_defaults(),
+        Arc::new(clock.clone()),
+    );
+
+    (repository, clock, temp_dir)
+}
+
 /// Helper function to create a test project
 async fn create_test_project(repo: &SqliteRepository, name: &str) -> Project {
     repo.add_project(
@@ -109,6 +134,23 @@ async fn test_basic_task_crud_workflow() {
     assert!(result.unwrap().is_none());
 }
 
+#[tokio::test]
+async fn test_short_id_prefix_lookup() {
+    let (repo, _temp_dir) = setup_test_db().await;
+    let task = create_test_task(&repo, "Prefixed Task", None).await;
+    create_test_task(&repo, "Other Task", None).await;
+
+    // Ids are stored as blobs; prefixes match their hex form, with or without dashes, in any case
+    let id = task.id.to_string();
+    for prefix in [id.clone(), id.replace('-', "").to_uppercase()] {
+        let found = repo.find_tasks_by_short_id_prefix(&prefix).await.unwrap();
+        assert_eq!(found.iter().map(|t| t.id).collect::<Vec<_>>(), vec![task.id], "prefix {}", prefix);
+    }
+    let found = repo.find_tasks_by_short_id_prefix(&id[..8]).await.unwrap();
+    assert!(found.iter().any(|t| t.id == task.id));
+    assert!(repo.find_tasks_by_short_id_prefix("zz").await.unwrap().is_empty());
+}
+
 #[tokio::test]
 async fn test_task_filtering_workflow() {
     let (repo, _temp_dir) = setup_test_db().await;
@@ -607,6 +649,30 @@ async fn test_edit_scope_this_occurrence() {
     }
 }
 
+#[tokio::test]
+async fn test_edit_scope_this_and_future_rrule_change() {
+    let (repo, _temp_dir) = setup_test_db().await;
+
+    let (template_task, series) = create_recurring_task(&repo, "FREQ=DAILY;COUNT=5").await;
+
+    let now = Utc::now();
+    let window_end = now + Duration::days(7);
+    repo.refresh_series_materialization(now, window_end).await.unwrap();
+
+    let tasks = repo.find_materialized_tasks_for_series(series.id, now, window_end).await.unwrap();
+    let instance_task = tasks.iter().find(|t| t.id != template_task.id).unwrap();
+
+    // The series is rewritten inside the edit's own transaction
+    let update_data = UpdateTaskData {
+        rrule: Some(Some("FREQ=WEEKLY;COUNT=3".to_string())),
+        ..Default::default()
+    };
+    repo.update_task(instance_task.id, update_data, Some(EditScope::ThisAndFuture)).await.unwrap();
+
+    let updated_series = repo.find_series_by_id(series.id).await.unwrap().unwrap();
+    assert!(updated_series.rrule.contains("FREQ=WEEKLY;COUNT=3"));
+}
+
 #[tokio::test]
 async fn test_edit_scope_this_occurrence_rrule_rejection() {
     let (repo, _temp_dir) = setup_test_db().await;
@@ -633,6 +699,38 @@ async fn test_edit_scope_this_occurrence_rrule_rejection() {
     assert!(matches!(result.unwrap_err(), CoreError::InvalidInput(_)));
 }
 
+#[tokio::test]
+async fn test_orphaned_instances_found_and_detached() {
+    let (repo, temp_dir) = setup_test_db().await;
+    let (template_task, series) = create_recurring_task(&repo, "FREQ=DAILY;COUNT=3").await;
+    let now = Utc::now();
+    let instances: Vec<Task> = repo
+        .find_materialized_tasks_for_series(series.id, now, now + Duration::days(7))
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|t| t.id != template_task.id)
+        .collect();
+    assert_eq!(instances.len(), 3);
+
+    // A database shell does not enforce foreign keys, so the instances keep their series id
+    let db_path = temp_dir.path().join("test.db");
+    let options = SqliteConnectOptions::new().filename(&db_path).foreign_keys(false);
+    let mut conn = SqliteConnection::connect_with(&options).await.unwrap();
+    sqlx::query("DELETE FROM task_series WHERE id = $1").bind(series.id).execute(&mut conn).await.unwrap();
+    conn.close().await.unwrap();
+
+    let orphans = repo.find_orphaned_instances().await.unwrap();
+    assert_eq!(orphans.iter().map(|t| t.id).collect::<Vec<_>>(), instances.iter().map(|t| t.id).collect::<Vec<_>>());
+    assert!(orphans.iter().all(|t| t.series_id == Some(series.id)));
+
+    let detached = repo.detach_orphaned_instances().await.unwrap();
+    assert_eq!(detached.len(), 3);
+    assert!(detached.iter().all(|t| t.series_id.is_none()));
+    assert!(repo.find_orphaned_instances().await.unwrap().is_empty());
+    assert_eq!(repo.find_task_by_id(instances[0].id).await.unwrap().unwrap().series_id, None);
+}
+
 #[tokio::test]
 async fn test_empty_query_regression() {
     let (repo, _temp_dir) = setup_test_db().await;
@@ -715,4 +813,232 @@ async fn test_query_parser_integration() {
             assert_eq!(tasks[0].id, task.id, "Wrong task returned for query: {:?}", query);
         }
     }
-}
\ No newline at end of file
+}
+
+/// Reminder sink that records delivered task ids, optionally failing every delivery
+#[derive(Clone, Default)]
+struct RecordingSink {
+    delivered: Arc<Mutex<Vec<Uuid>>>,
+    fail: bool,
+}
+
+#[async_trait]
+impl ReminderSink for RecordingSink {
+    fn name(&self) -> &str {
+        "recording"
+    }
+
+    async fn deliver(&self, reminder: &DueReminder, _now: DateTime<Utc>) -> Result<(), CoreError> {
+        if self.fail {
+            return Err(CoreError::ReminderDelivery("sink offline".to_string()));
+        }
+        self.delivered.lock().unwrap().push(reminder.task.id);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_daemon_reminder_fires_once_per_due_time() {
+    let (repo, _temp_dir) = setup_test_db().await;
+
+    let due = Utc::now() + Duration::days(2);
+    let task = repo.add_task(NewTaskData {
+        name: "Submit report".to_string(),
+        due_at: Some(due),
+        remind_before: Some(Duration::minutes(30)),
+        ..Default::default()
+    }).await.unwrap();
+    assert_eq!(repo.find_task_reminder(task.id).await.unwrap().unwrap().offset_minutes, 30);
+
+    let clock = FakeClock::new(due - Duration::hours(1));
+    let sink = RecordingSink::default();
+    let daemon = Daemon::new(&repo, Arc::new(clock.clone()), DaemonConfig::default())
+        .with_sink(Box::new(sink.clone()));
+
+    // Too early
+    assert!(daemon.tick().await.unwrap().delivered.is_empty());
+
+    // Inside the reminder window: delivered exactly once
+    clock.set(due - Duration::minutes(29));
+    assert_eq!(daemon.tick().await.unwrap().delivered.len(), 1);
+    assert!(daemon.tick().await.unwrap().delivered.is_empty());
+    assert_eq!(*sink.delivered.lock().unwrap(), vec![task.id]);
+
+    // Rescheduling re-arms the reminder for the new due time
+    let new_due = due + Duration::days(1);
+    repo.update_task(task.id, UpdateTaskData {
+        due_at: Some(Some(new_due)),
+        ..Default::default()
+    }, None).await.unwrap();
+    clock.set(new_due - Duration::minutes(10));
+    assert_eq!(daemon.tick().await.unwrap().delivered.len(), 1);
+
+    // Clearing the reminder stops further deliveries
+    repo.update_task(task.id, UpdateTaskData {
+        due_at: Some(Some(new_due + Duration::days(1))),
+        remind_before: Some(None),
+        ..Default::default()
+    }, None).await.unwrap();
+    clock.set(new_due + Duration::days(1));
+    assert!(daemon.tick().await.unwrap().delivered.is_empty());
+}
+
+#[tokio::test]
+async fn test_daemon_series_instances_inherit_template_reminder() {
+    let (repo, _temp_dir) = setup_test_db().await;
+
+    let template = repo.add_task(NewTaskData {
+        name: "Water plants".to_string(),
+        due_at: Some(Utc::now() + Duration::hours(1)),
+        rrule: Some("FREQ=DAILY".to_string()),
+        timezone: Some("UTC".to_string()),
+        remind_before: Some(Duration::hours(2)),
+        ..Default::default()
+    }).await.unwrap();
+    let series = repo.find_series_by_template(template.id).await.unwrap().unwrap();
+
+    let clock = FakeClock::new(Utc::now());
+    let sink = RecordingSink::default();
+    let daemon = Daemon::new(&repo, Arc::new(clock.clone()), DaemonConfig::default())
+        .with_sink(Box::new(sink.clone()));
+
+    let report = daemon.tick().await.unwrap();
+    assert_eq!(report.delivered.len(), 1, "only the first occurrence is within two hours");
+    assert_eq!(report.delivered[0].task.series_id, Some(series.id));
+    assert_ne!(report.delivered[0].task.id, template.id);
+
+    // A day later the next instance comes due
+    clock.advance(Duration::days(1));
+    let report = daemon.tick().await.unwrap();
+    assert_eq!(report.delivered.len(), 1);
+    assert_eq!(sink.delivered.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_daemon_retries_when_all_sinks_fail() {
+    let (repo, _temp_dir) = setup_test_db().await;
+
+    let due = Utc::now() + Duration::hours(3);
+    repo.add_task(NewTaskData {
+        name: "Call dentist".to_string(),
+        due_at: Some(due),
+        remind_before: Some(Duration::hours(1)),
+        ..Default::default()
+    }).await.unwrap();
+
+    let clock = Arc::new(FakeClock::new(due - Duration::minutes(30)));
+    let without_sinks = Daemon::new(&repo, clock.clone(), DaemonConfig::default());
+    assert!(without_sinks.tick().await.unwrap().delivered.is_empty());
+
+    let failing = Daemon::new(&repo, clock.clone(), DaemonConfig::default())
+        .with_sink(Box::new(RecordingSink { fail: true, ..Default::default() }));
+    let report = failing.tick().await.unwrap();
+    assert!(report.delivered.is_empty());
+    assert_eq!(report.failures.len(), 1);
+
+    let sink = RecordingSink::default();
+    let working = Daemon::new(&repo, clock, DaemonConfig::default())
+        .with_sink(Box::new(sink.clone()));
+    assert_eq!(working.tick().await.unwrap().delivered.len(), 1);
+}
+
+#[tokio::test]
+async fn test_time_travel_overdue_and_relative_due_filters() {
+    let start = Utc.with_ymd_and_hms(2024, 3, 10, 9, 0, 0).unwrap();
+    let (repo, clock, _temp_dir) = setup_test_db_with_clock(start).await;
+
+    let task = repo.add_task(NewTaskData {
+        name: "Pay rent".to_string(),
+        due_at: Some(start + Duration::hours(3)),
+        ..Default::default()
+    }).await.unwrap();
+    assert_eq!(task.created_at, start, "timestamps come from the repository clock");
+
+    let overdue = Query::Filter(QueryFilter::Due(DueDate::Overdue));
+    let today = Query::Filter(QueryFilter::Due(DueDate::Today));
+    let tomorrow = Query::Filter(QueryFilter::Due(DueDate::Tomorrow));
+    let yesterday = Query::Filter(QueryFilter::Due(DueDate::Yesterday));
+
+    assert!(repo.find_tasks_with_details(&overdue).await.unwrap().is_empty());
+    assert_eq!(repo.find_tasks_with_details(&today).await.unwrap().len(), 1);
+
+    // Later the same day the task slips past its due time
+    clock.advance(Duration::hours(4));
+    assert_eq!(repo.find_tasks_with_details(&overdue).await.unwrap().len(), 1);
+    assert_eq!(repo.find_tasks_with_details(&today).await.unwrap().len(), 1);
+
+    // The day before, it is due tomorrow; the day after, it was due yesterday
+    clock.set(start - Duration::days(1));
+    assert_eq!(repo.find_tasks_with_details(&tomorrow).await.unwrap().len(), 1);
+    assert!(repo.find_tasks_with_details(&overdue).await.unwrap().is_empty());
+    clock.set(start + Duration::days(1));
+    assert_eq!(repo.find_tasks_with_details(&yesterday).await.unwrap().len(), 1);
+    assert!(repo.find_tasks_with_details(&today).await.unwrap().is_empty());
+
+    let updated = repo.update_task(task.id, UpdateTaskData {
+        name: Some("Pay rent (late)".to_string()),
+        ..Default::default()
+    }, None).await.unwrap();
+    assert_eq!(updated.updated_at, start + Duration::days(1));
+}
+
+#[tokio::test]
+async fn test_time_travel_materialization_follows_clock() {
+    let start = Utc.with_ymd_and_hms(2021, 6, 1, 8, 0, 0).unwrap();
+    let (repo, clock, _temp_dir) = setup_test_db_with_clock(start).await;
+
+    // UNTIL lies in the real past but after the fake "now", so the rule is valid
+    let template = repo.add_task(NewTaskData {
+        name: "Morning run".to_string(),
+        due_at: Some(start),
+        rrule: Some("FREQ=DAILY;UNTIL=20210630T080000Z".to_string()),
+        timezone: Some("UTC".to_string()),
+        ..Default::default()
+    }).await.unwrap();
+    let series = repo.find_series_by_template(template.id).await.unwrap().unwrap();
+
+    let today = Query::Filter(QueryFilter::Due(DueDate::Today));
+    let instances_due_today = || async {
+        repo.find_tasks_with_details(&today).await.unwrap()
+            .into_iter()
+            .filter(|t| t.id != template.id)
+            .collect::<Vec<_>>()
+    };
+    let found = instances_due_today().await;
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].due_at, Some(start));
+
+    // Jump ahead ten days: the query materializes the new day on demand
+    clock.advance(Duration::days(10));
+    let found = instances_due_today().await;
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].due_at, Some(start + Duration::days(10)));
+    assert_eq!(found[0].series_id, Some(series.id));
+
+    // Completion is stamped with the fake time, and the next occurrence is
+    // still found even though UNTIL is long gone on the real clock
+    clock.advance(Duration::hours(1));
+    match repo.complete_task(found[0].id).await.unwrap() {
+        CompletionResult::SeriesInstance { completed, next_occurrence, .. } => {
+            assert_eq!(completed.completed_at, Some(clock.now()));
+            assert!(next_occurrence.is_some());
+        }
+        other => panic!("expected a series completion, got {:?}", other),
+    }
+
+    // Past UNTIL nothing is left to materialize
+    clock.set(Utc.with_ymd_and_hms(2021, 7, 15, 8, 0, 0).unwrap());
+    assert!(instances_due_today().await.is_empty());
+}
+
+#[test]
+fn test_validate_rrule_at_uses_reference_time() {
+    let tz = "UTC";
+    let rule = "FREQ=DAILY;UNTIL=20210630T080000Z";
+
+    let before_until = Utc.with_ymd_and_hms(2021, 6, 1, 8, 0, 0).unwrap();
+    assert!(RecurrenceManager::validate_rrule_at(rule, tz, before_until).is_ok());
+
+    let after_until = Utc.with_ymd_and_hms(2021, 7, 1, 8, 0, 0).unwrap();
+    assert!(RecurrenceManager::validate_rrule_at(rule, tz, after_until).is_err());
+}